@@ -0,0 +1,56 @@
+//! Copies a folder of many small files with one worker and with one worker per CPU, the way
+//! the copy and move operations do.
+//!
+//! Run with `cargo bench --bench recursive_copy`.
+
+use commander::operation::{recursive::Context, Controller, OperationOptions};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::{fs, path::Path};
+
+const FILES: usize = 2000;
+const FILE_LEN: usize = 4096;
+
+fn create_files(dir: &Path) {
+    let data = vec![0x55; FILE_LEN];
+    for i in 0..FILES {
+        // Some nesting, like a source tree
+        let subdir = dir.join(format!("dir{}", i / 100));
+        fs::create_dir_all(&subdir).unwrap();
+        fs::write(subdir.join(format!("file{}", i)), &data).unwrap();
+    }
+}
+
+fn copy_small_files(c: &mut Criterion) {
+    let source = tempfile::tempdir().unwrap();
+    let from = source.path().join("from");
+    create_files(&from);
+
+    let mut group = c.benchmark_group("copy_small_files");
+    group.sample_size(10);
+    for workers in [1, num_cpus::get()] {
+        group.bench_function(format!("{} workers", workers), |b| {
+            b.iter_batched(
+                || tempfile::tempdir().unwrap(),
+                |destination| {
+                    let options = OperationOptions {
+                        workers,
+                        ..OperationOptions::default()
+                    };
+                    let mut context = Context::new(Controller::default()).options(options);
+                    context
+                        .recursive_copy_or_move(
+                            vec![(from.clone(), destination.path().join("from"))],
+                            false,
+                        )
+                        .unwrap();
+                    destination
+                },
+                BatchSize::PerIteration,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, copy_small_files);
+criterion_main!(benches);
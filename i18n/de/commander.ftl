@@ -2,14 +2,46 @@ commander = Commmander
 empty-folder = Leerer Ordner
 empty-folder-hidden = Leerer Ordner (hat versteckte Elemente)
 no-results = Keine Ergebnisse gefunden
+filter-all = Alle Elemente
+filter-images = Bilder
+filter-video = Videos
+filter-audio = Audio
+filter-documents = Dokumente
+filter-archives = Archive
+filter-executables = Ausführbare Dateien
+filter-today = Heute geändert
+filter-this-week = Diese Woche geändert
 filesystem = Dateisystem
 home = Benutzerordner
 networks = Netzwerke
+devices = Geräte
+mount = Einhängen
+unmount = Aushängen
+eject = Auswerfen
+free-space = {$free} von {$total} frei
 notification-in-progress = Dateivorgänge sind im Gange.
+notification-complete = Dateivorgang abgeschlossen
+notification-failed = Dateivorgang fehlgeschlagen
+read-only-pane-error = {$dir} wird in einem schreibgeschützten Bereich angezeigt und kann nicht geändert werden
+notification-duration = Dauer: {$duration}
+duration-minutes = {$minutes} min {$seconds} s
 trash = Papierkorb
 recents = Zuletzt benutzt
 undo = Rückgängig
 today = Heute
+just-now = Gerade eben
+minutes-ago = vor {$count} {$count ->
+        [one] Minute
+        *[other] Minuten
+    }
+hours-ago = vor {$count} {$count ->
+        [one] Stunde
+        *[other] Stunden
+    }
+days-ago = vor {$count} {$count ->
+        [one] Tag
+        *[other] Tagen
+    }
 
 # Optionen für die Desktop-Ansicht
 desktop-view-options = Optionen für die Desktop-Ansicht...
@@ -17,8 +49,13 @@ show-on-desktop = Auf Desktop anzeigen
 desktop-folder-content = Inhalt des Desktop-Ordners
 mounted-drives = Eingehängte Laufwerke
 trash-folder-icon = Ordnersymbol des Papierkorbs
+home-folder-icon = Ordnersymbol des persönlichen Ordners
 icon-size-and-spacing = Symbolgröße und -abstand
 icon-size = Symbolgröße
+icon-placement = Symbolanordnung
+auto-arrange-icons = Symbole automatisch anordnen
+align-to-grid = Am Raster ausrichten
+arrange-icons = Symbole anordnen
 
 # Listenansicht
 name = Name
@@ -31,6 +68,12 @@ details = Details
 dismiss = Meldung verwerfen
 operations-running = {$running} laufende Vorgänge ({$percent} %)...
 operations-running-finished = {$running} laufende Vorgänge ({$percent} %), {$finished} abgeschlossen...
+progress-bytes = {$done} von {$total}
+progress-files-remaining = {$files} {$files ->
+        [one] Datei
+        *[other] Dateien
+    } übrig
+progress-speed = {$speed}/s, durchschnittlich {$average}/s
 pause = Pause
 resume = Fortsetzen
 
@@ -43,19 +86,100 @@ create-archive = Archiv erstellen
 empty-trash = Papierkorb leeren?
 empty-trash-warning = Bist du sicher, dass du alle Elemente im Papierkorb endgültig löschen möchtest?
 
+## Bestätigungsdialoge
+confirm-trash-warning = Bist du sicher, dass du {$items} {$items ->
+        [one] Element
+        *[other] Elemente
+    } in den Papierkorb verschieben möchtest?
+confirm-move = Auf ein anderes Gerät verschieben
+confirm-move-warning = {$items ->
+        [one] Das Element wird
+        *[other] Die {$items} Elemente werden
+    } nach „{$to}“ kopiert und danach hier gelöscht.
+confirm-execute = Ausführen
+confirm-execute-title = „{$name}“ ausführen oder öffnen?
+confirm-execute-warning = Die Datei kann als Programm ausgeführt werden. Führe nur Dateien aus, denen du vertraust.
+open-in-editor = Im Editor öffnen
+remember-for-type = Für diesen Dateityp merken
+
+## Starter erstellen
+create-launcher = Starter erstellen
+create-launcher-body = Der Starter startet „{$program}“ aus den Menüs oder vom Schreibtisch.
+launcher-icon = Symbol
+launcher-icon-placeholder = Symbolname oder Pfad eines Bildes
+launcher-categories = Kategorien
+launcher-terminal = In einem Terminal ausführen
+launcher-applications = Anwendungen
+launcher-created = Der Starter „{$path}“ wurde erstellt
+launcher-no-folder = Der Ordner für den Starter wurde nicht gefunden
+launcher-name-missing = Der Starter braucht einen Namen
+launcher-program-missing = „{$program}“ ist keine Datei
+launcher-icon-invalid = Symbole sind Namen des Symbolthemas ohne Endung oder Pfade von Bildern
+launcher-category-invalid = Unbekannte Kategorie
+
+## Dialog bei zu wenig Speicherplatz
+insufficient-space-copy = Nicht genug Speicherplatz zum Kopieren
+insufficient-space-move = Nicht genug Speicherplatz zum Verschieben
+insufficient-space-body = Die Elemente benötigen {$required}, in „{$destination}“ sind aber nur {$available} frei. Es fehlen {$missing}.
+continue-anyway = Trotzdem fortfahren
+
+## Dialog bei fehlgeschlagenen Elementen
+item-failed-title = „{$name}“ konnte nicht verarbeitet werden
+retry = Wiederholen
+abort = Abbrechen
+skipped-items = {$count} {$count ->
+        [one] Element wurde
+        *[other] Elemente wurden
+    } übersprungen
+copy-report = Bericht kopieren
+
+## Dialog zur Vorschau der Änderungen
+preview-changes-title = Dateien ersetzen
+preview-changes-trash = {$count} {$count ->
+        [one] Datei
+        *[other] Dateien
+    } ({$size}) werden in den Papierkorb verschoben.
+preview-changes-overwrite = {$count} {$count ->
+        [one] Datei
+        *[other] Dateien
+    } ({$size}) werden überschrieben.
+preview-changes-more = und {$count} weitere
+
 ## Einhängefehler-Dialog
 mount-error = Zugriff auf Laufwerk nicht möglich
 
 # Neue(r) Datei/Ordner-Dialog
 create-new-file = Neue Datei erstellen
+paste-as-new-file = Als neue Datei einfügen
+pasted-image = Eingefügtes Bild {$date}
+pasted-text = Eingefügter Text {$date}
 create-new-folder = Neuen Ordner erstellen
 file-name = Dateiname
 folder-name = Ordnername
 file-already-exists = Eine Datei mit diesem Namen existiert bereits.
+export = Exportieren
+export-listing = Auflistung exportieren
+export-listing-body = Schreibe die Elemente von {$folder} in eine Datei.
+listing-path = Pfad der Auflistung
+listing-recursive = Unterordner einbeziehen
+listing-checksum = SHA-256-Prüfsumme
+print = Drucken
+print-listing = Auflistung drucken
+print-listing-body = Drucke die Elemente von {$folder}.
+print-files-body = Drucke {$items} {$items ->
+        [one] Datei
+        *[other] Dateien
+    }.
+print-header = Kopfzeile mit Name und Datum
+print-footer = Fußzeile mit Seitenzahlen
+print-page = Seite {$page} von {$pages}
+print-sent = An den Drucker gesendet
 folder-already-exists = Ein Ordner mit diesem Namen existiert bereits.
 name-hidden = Mit „.“ beginnende Namen werden ausgeblendet.
 name-invalid = Name darf nicht „{$filename}“ sein.
 name-no-slashes = Namen dürfen keine Schrägstriche enthalten.
+name-reserved-character = Namen auf {$filesystem}-Dateisystemen dürfen kein „{$character}“ enthalten.
+name-reserved-ending = Namen auf {$filesystem}-Dateisystemen dürfen nicht mit einem Punkt oder Leerzeichen enden.
 
 # Öffnen/Speichern-Dialog
 cancel = Abbrechen
@@ -65,15 +189,23 @@ open-file = Datei öffnen
 open-folder = Ordner öffnen
 open-in-new-tab = In neuem Tab öffnen
 open-in-new-window = In neuem Fenster öffnen
+open-as-administrator = Als Administrator öffnen
+retry-as-administrator = Als Administrator wiederholen
 open-item-location = Speicherort des Elements öffnen
+show-all-in-folder = Alle im Ordner anzeigen
 open-multiple-files = Mehrere Dateien öffnen
 open-multiple-folders = Mehrere Ordner öffnen
 save = Speichern
+add = Hinzufügen
 save-file = Datei speichern
 
 ## Öffnen-mit-Dialog
 open-with-title = Wie möchtest du „{$name}“ öffnen?
 browse-store = {$store} durchsuchen
+other-applications = Andere Anwendungen
+search-applications = Anwendungen suchen
+custom-command = Benutzerdefinierter Befehl
+always-use-for-type = Immer für {$mime} verwenden
 
 # Umbenennen-Dialog
 rename-file = Datei umbenennen
@@ -90,6 +222,16 @@ apply-to-all = Auf alle anwenden
 keep-both = Beide behalten
 skip = Überspringen
 
+## Editor
+editor-find = Suchen
+editor-find-next = Weitersuchen
+editor-replace-all = Alle ersetzen
+editor-not-found = Nicht gefunden
+editor-replaced = {$count} ersetzt
+editor-saved = Gespeichert
+editor-cannot-encode = Der Text enthält Zeichen, die nicht als {$encoding} gespeichert werden können
+editor-cannot-decode = Die Datei ist kein gültiges {$encoding}
+
 ## Dialog zum Festlegen als ausführbar und starten
 set-executable-and-launch = Als ausführbar festlegen und starten
 set-executable-and-launch-description = Möchtest du „{$name}“ als ausführbar festlegen und starten?
@@ -145,6 +287,13 @@ password = Passwort
 remember-password = Passwort merken
 try-again = Erneut versuchen
 username = Benutzername
+unlock = Entsperren
+unlock-container-title = {$name} entsperren
+
+## Cloud-Speicher
+cloud-keyring-label = Commander rclone-Konfiguration
+cloud-password-title = Cloud-Speicher entsperren
+cloud-password-body = Die rclone-Konfiguration ist verschlüsselt. Gib ihr Passwort ein, um {$remote} zu verbinden.
 
 ## Vorgänge
 cancelled = Abgebrochen
@@ -157,6 +306,14 @@ progress-cancelled = {$percent} %, abgeschlossen
 progress-paused = {$percent} %, pausiert
 failed = Fehlgeschlagen
 complete = Abgeschlossen
+adding-to-archive = {$items} {$items ->
+        [one] Element wird
+        *[other] Elemente werden
+    } von „{$from}“ zu „{$to}“ hinzugefügt ({$progress})...
+added-to-archive = {$items} {$items ->
+        [one] Element wurde
+        *[other] Elemente wurden
+    } von „{$from}“ zu „{$to}“ hinzugefügt
 compressing = {$items} {$items ->
         [one] Element wird
         *[other] Elemente werden
@@ -178,6 +335,8 @@ copied = {$items} {$items ->
     } „{$from}“ nach „{$to}“ kopiert
 emptying-trash = {trash} wird geleert ({$progress})...
 emptied-trash = {trash} geleert
+exporting-listing = Auflistung von „{$from}“ wird nach „{$to}“ exportiert ({$progress})...
+exported-listing = Auflistung von „{$from}“ nach „{$to}“ exportiert
 extracting = {$items} {$items ->
         [one] Element wird
         *[other] Elemente werden
@@ -196,6 +355,14 @@ moved = {$items} {$items ->
         [one] Element wurde
         *[other] Elemente wurden
     } von „{$from}“ nach „{$to}“ verschoben
+linking = {$items} {$items ->
+        [one] Element wird
+        *[other] Elemente werden
+    } von „{$from}“ nach „{$to}“ verknüpft ({$progress})...
+linked = {$items} {$items ->
+        [one] Element wurde
+        *[other] Elemente wurden
+    } von „{$from}“ nach „{$to}“ verknüpft
 renaming = „{$from}“ wird in „{$to}“ umbenannt
 renamed = „{$from}“ wurde in „{$to}“ umbenannt
 restoring = {$items} {$items ->
@@ -206,6 +373,8 @@ restored = {$items} {$items ->
         [one] Element wurde
         *[other] Elemente wurden
     } aus dem {trash} wiederhergestellt
+syncing = „{$name}“ wird synchronisiert ({$progress})...
+synced = „{$name}“ wurde synchronisiert
 unknown-folder = unbekannter Ordner
 
 ## Öffnen mit
@@ -219,11 +388,110 @@ items = Elemente: {$items}
 item-size = Größe: {$size}
 item-created = Erstellt: {$created}
 item-modified = Geändert: {$modified}
+item-permissions = Berechtigungen: {$permissions}
+item-target = Ziel: {$target}
 item-accessed = Zugegriffen: {$accessed}
 calculating = Wird berechnet...
+selected-items = {$items} Objekte ausgewählt
+selected-files-folders = {$files} Dateien, {$folders} Ordner
+a11y-selected = Ausgewählt, {$selected} von {$total} Elementen ausgewählt
+type-count = {$mime}: {$items}
+permissions = Berechtigungen
+permission-read = Lesen
+permission-write = Schreiben
+permission-execute = Ausführen
+language = Sprache: {$name}
+open-full-viewer = Im Betrachter öffnen
+pdf-page = Seite {$page} von {$pages}
+open-hex-viewer = Als Hex anzeigen
+hex-mode-on = Hex
+hex-mode-off = Normale Ansicht
+hex-offset = Gehe zu Offset
+hex-search = Bytes oder Text suchen
+hex-searching = Suche...
+hex-not-found = Keine weiteren Treffer
+hex-invalid-offset = Ungültiger Offset
+
+## Media
+media-title = Titel
+media-artist = Künstler
+media-album = Album
+media-video-codec = Video-Codec
+media-audio-codec = Audio-Codec
+
+## Metadata
+metadata-camera = Kamera
+metadata-lens = Objektiv
+metadata-exposure = Belichtung
+metadata-aperture = Blende
+metadata-iso = ISO
+metadata-focal-length = Brennweite
+metadata-taken = Aufgenommen
+metadata-location = Ort
+open-in-maps = In Karte öffnen
+metadata-track = Titelnummer
+metadata-year = Jahr
+metadata-genre = Genre
+metadata-duration = Dauer
+metadata-bitrate = Bitrate
+metadata-sample-rate = Abtastrate
+metadata-channels = Kanäle
+metadata-resolution = Auflösung
+preview-skipped = Vorschau übersprungen: {$reason}
+preview-too-large = Dateigröße {$size} ist größer als {$max_size}
+preview-timed-out = dauerte länger als {$seconds} Sekunden
+
+## Checksums
+checksums = Prüfsummen
+calculate-checksums = Prüfsummen berechnen
+verify-checksums = Prüfsummen überprüfen
+checksum-pass = OK
+checksum-fail = Abweichung
+checksums-verified = {$passed} von {$total} Dateien bestanden
+close = Schließen
+
+## Vergleichen
+compare-files = Dateien vergleichen
+compare-changes = {$changes} geänderte Zeilen
+compare-too-large = {$name} ist zu groß zum Vergleichen
+compare-binary = {$name} ist keine Textdatei
+
+## Terminal
+terminal = Terminal
+new-terminal = Neues Terminal
+rename-terminal = Terminal umbenennen
+close-terminal = Terminal schließen
+terminal-name = Name des Terminals
+new-terminal-profile = Neues Terminal: {$profile}
+terminal-profiles = Terminalprofile
+add-profile = Profil hinzufügen
+profile-name = Name
+default-profile = Standard
+profile-command = Shell und Argumente, die Login-Shell wenn leer
+profile-working-directory = Arbeitsverzeichnis
+profile-tab-title = Tab-Titel
+profile-env = Umgebungsvariablen, wie NAME=Wert
+profile-startup-command = Startbefehl
+profile-scrollback = Zeilen im Verlauf
+cursor-block = Block
+cursor-underline = Unterstrich
+cursor-beam = Balken
+cursor-blinking = Blinkender Cursor
+profile-hold = Nach dem Beenden offen halten
+import-color-scheme = Farbschema importieren
+color-scheme-imported = Farbschema {$name} importiert
+insert-name-terminal = Dateiname ins Terminal einfügen
+insert-paths-terminal = Vollständige Pfade der Auswahl einfügen
 
 ## Einstellungen
 settings = Einstellungen
+search-settings = Einstellungen durchsuchen
+no-settings-found = Keine passenden Einstellungen
+settings-behavior = Verhalten
+settings-panes = Bereiche
+settings-operations = Vorgänge
+settings-keyboard = Tastatur
+settings-advanced = Erweitert
 
 ### Aussehen
 appearance = Aussehen
@@ -231,14 +499,59 @@ theme = Thema
 match-desktop = An Desktop anpassen
 dark = Dunkel
 light = Hell
+follow-system = Dem System folgen
+density = Dichte
+density-comfortable = Komfortabel
+density-compact = Kompakt
+density-dense = Dicht
+size-units = Größeneinheiten
+size-units-si = Dezimal (KB, MB)
+size-units-iec = Binär (KiB, MiB)
+size-units-bytes = Bytes
+date-format = Datumsformat
+date-format-locale = Standard der Sprache
+date-format-relative = Relativ (vor 2 Stunden)
+date-format-iso = ISO 8601 (2024-05-31 14:05)
+first-day-of-week = Erster Tag der Woche
+monday = Montag
+saturday = Samstag
+sunday = Sonntag
+accent-color = Akzentfarbe
+theme-default = Wie im Thema
+pane-tint-left = Tönung des linken Bereichs
+pane-tint-right = Tönung des rechten Bereichs
+color-blue = Blau
+color-teal = Blaugrün
+color-green = Grün
+color-yellow = Gelb
+color-orange = Orange
+color-red = Rot
+color-pink = Rosa
+color-purple = Lila
+color-gray = Grau
 
 # Kontextmenü
 add-to-sidebar = Zur Seitenleiste hinzufügen
 compress = Komprimieren
+pack-to-other-pane = In anderen Bereich packen
+add-to-archive = Zum Archiv hinzufügen
+add-to-archive-body = {$items} {$items ->
+        [one] Element
+        *[other] Elemente
+    } zu einem Zip- oder Tar-Archiv hinzufügen, ohne es neu zu packen
+archive-path = Pfad des Archivs
 extract-here = Entpacken
+unlock-container = Container entsperren
 new-file = Neue Datei
+empty-file = Leere Datei
+template-text = Textdatei
+template-markdown = Markdown-Dokument
+template-script = Shell-Skript
+template-document-name = Neues Dokument
+template-script-name = Neues Skript
 new-folder = Neuer Ordner
 open-in-terminal = Im Terminal öffnen
+run-in-terminal = Im Terminal ausführen
 move-to-trash = In den Papierkorb verschieben
 restore-from-trash = Aus dem Papierkorb wiederherstellen
 remove-from-sidebar = Von der Seitenleiste entfernen
@@ -266,8 +579,26 @@ quit = Beenden
 edit = Bearbeiten
 cut = Ausschneiden
 copy = Kopieren
+duplicate = Duplizieren
+copy-paths = Vollständige Pfade kopieren
+copy-names = Namen kopieren
+copy-directory-path = Ordnerpfad kopieren
 paste = Einfügen
+drop-copy = Hierher kopieren
+drop-move = Hierher verschieben
+drop-link = Hier verknüpfen
 select-all = Alles auswählen
+open-context-menu = Kontextmenü öffnen
+save-selection = Auswahl speichern
+restore-selection = Auswahl wiederherstellen
+load-selection = Auswahl aus Datei laden
+load-selection-body = Wähle die in einer Textdatei aufgelisteten Elemente aus, ein Pfad pro Zeile. Relative Pfade liegen im Ordner des Bereichs.
+selection-list-path = Pfad der Liste
+selection-saved = Auswahl von {$items} {$items ->
+        [one] Element
+        *[other] Elementen
+    } gespeichert
+load = Laden
 
 ## Ansicht
 zoom-in = Vergrößern
@@ -278,7 +609,71 @@ features = Funktionen
 show-button-row = Zeige Knopfreihe
 show-embedded-terminal = Zeige eingebautes Terminal
 show-second-panel = Zeige zweiten Dateimanager
+show-toolbar = Werkzeugleiste anzeigen
+toolbar-buttons = Knöpfe der Werkzeugleiste
+edit-toolbar = Werkzeugleiste bearbeiten...
+add-toolbar-button = Knopf hinzufügen
+reset-toolbar = Zurücksetzen
+toolbar-back = Zurück
+toolbar-forward = Vorwärts
+toolbar-up = Übergeordneter Ordner
+toolbar-reload = Neu laden
+toolbar-search = Suchen
+toolbar-view = Zwischen Raster- und Listenansicht wechseln
+toolbar-swap-panels = Bereiche tauschen
 queue-file-operations = Führe Dateioperationen nacheinander aus
+size-one-file-system = Beim Berechnen von Ordnergrößen an Dateisystemgrenzen anhalten
+vi-keys = Navigation mit Vi-Tasten
+vi-keys-description = Mit h, j, k und l bewegen, mit gg und G springen, mit v auswählen, mit yy, dd und p kopieren, ausschneiden und einfügen und mit / filtern
+quote-copied-paths = Kopierte Pfade und Namen für die Shell maskieren
+refuse-insufficient-space = Kopieren oder Verschieben ablehnen, wenn die Elemente nicht auf das Ziel passen
+preview-changes = Vor dem Start die Dateien auflisten, die gelöscht oder überschrieben werden
+confirm-trash = Vor dem Verschieben in den Papierkorb fragen
+confirm-empty-trash = Vor dem Leeren des Papierkorbs fragen
+confirm-overwrite = Vor dem Ersetzen vorhandener Elemente fragen
+confirm-move-across-devices = Vor dem Verschieben abgelegter Elemente auf ein anderes Gerät fragen
+confirm-run-executables = Fragen, ob ausführbare Dateien ausgeführt oder geöffnet werden
+remembered-run-choices = Gemerkte Auswahl für ausführbare Dateien
+forget = Vergessen
+copy-workers = Parallele Kopien
+copy-workers-description = Kleine Dateien werden von so vielen Threads gleichzeitig kopiert
+direct-io = Große Dateien am Cache vorbei kopieren
+direct-io-description = Große Dateien werden direkt von der Festplatte gelesen, damit sie keine anderen Dateien aus dem Speicher-Cache verdrängen
+remote-trash = Papierkorb auf entfernten Datenträgern
+remote-trash-description = Gelöschte Elemente auf Netzwerkfreigaben und anderen entfernten Datenträgern werden in einen Papierkorb-Ordner auf dem Server verschoben, statt fehlzuschlagen
+sounds = Klänge
+sounds-description = Klänge des Klangthemas der Arbeitsumgebung abspielen, wenn Elemente in den Papierkorb verschoben werden und wenn Vorgänge abgeschlossen werden oder fehlschlagen
+sound-volume = Lautstärke der Klänge
+share-click-settings = Dieselben Klick-Einstellungen für beide Bereiche verwenden
+left-pane = Linker Bereich
+right-pane = Rechter Bereich
+single-click-open = Elemente mit einem Klick öffnen
+double-click-open = Elemente mit einem Doppelklick öffnen
+click-empty-clears-selection = Ein Klick auf eine freie Fläche hebt die Auswahl auf
+open-together = Ausgewählte Dateien gemeinsam öffnen
+open-together-description = Dateien mit derselben Standardanwendung werden mit einem Start der Anwendung geöffnet, wenn sie mehrere Dateien unterstützt
+mouse-gestures = Mausgesten
+mouse-gestures-description = Ziehen mit gedrückter rechter Taste: nach oben öffnet den übergeordneten Ordner, nach links geht zurück, nach rechts vorwärts, nach unten lädt neu
+rocker-gestures = Wippgesten
+rocker-gestures-description = Halte die linke Taste und klicke die rechte, um zurückzugehen, halte die rechte Taste und klicke die linke, um vorwärtszugehen
+preview-max-size = Größte Datei für Vorschauen
+preview-timeout = Zeitlimit für Vorschauen
+duration-seconds = {$seconds} Sekunden
+slideshow-interval = Intervall der Diashow
+diff-tool = Vergleichsprogramm
+built-in-diff = Eingebaut
+hide-patterns = Muster für versteckte Dateien
+hide-patterns-description = Namen, die auf diese Glob-Muster passen, werden wie Namen mit „.“ am Anfang ausgeblendet.
+add-hide-pattern = Muster hinzufügen
+invalid-pattern = Ungültiges Muster
+color-rules = Namensfarben
+color-rules-description = Namen werden nach der ersten passenden Regel eingefärbt: ein Glob-Muster wie *.rs, ein MIME-Typ wie image/* oder folder, executable, symlink oder hidden.
+add-color-rule = Regel hinzufügen
+color-scheme-dark = Dunkles Farbschema
+color-scheme-light = Helles Farbschema
+import-color-scheme-hint = Farbschemata von alacritty, iTerm2 oder Xresources werden über das Kontextmenü der Datei importiert
+default-applications = Standardanwendungen
+no-default-applications = Öffne Ordner, um Standardanwendungen für ihre Dateitypen auszuwählen
 f2-rename = F2 Umbenennen
 f3-view = F3 Ansicht
 f4-edit = F4 Bearbeiten
@@ -290,11 +685,25 @@ f9-Term = F9 Terminal
 f10-quit = F10 Beenden
 copy-tab = Kopiere aktuelles Tab zur anderen Seite
 move-tab = Verschiebe aktuelles Tab zur anderen Seite
+duplicate-tab = Tab duplizieren
+reopen-closed-tab = Geschlossenes Tab wieder öffnen
+pin-tab = Tab anheften
+unpin-tab = Tab lösen
+lock-tab = Tab auf Ordner festlegen
+unlock-tab = Tab entsperren
+quick-jump = Zu Ordner springen
+quick-jump-hint = Ordnername
+quick-view = Schnellansicht
+pane-history = Zuletzt besuchte Ordner des Bereichs
 grid-view = Rasteransicht
 list-view = Listenansicht
 show-hidden-files = Versteckte Dateien anzeigen
+dim-hidden-files = Versteckte Dateien abblenden
 list-directories-first = Verzeichnisse zuerst auflisten
+read-only-pane = Schreibgeschützter Bereich
 gallery-preview = Galerie-Vorschau
+image-transform-animated = {$name} hat mehr als ein Bild oder eine Seite, die beim Drehen oder Spiegeln verloren gingen
+image-transform-lossy = {$name} kann nicht ohne Verlust von Details gedreht oder gespiegelt werden
 menu-settings = Einstellungen...
 menu-about = Über  Commander...
 
@@ -306,3 +715,65 @@ sort-newest-first = Neueste zuerst
 sort-oldest-first = Älteste zuerst
 sort-smallest-to-largest = Kleinste bis größte
 sort-largest-to-smallest = Größte bis kleinste
+
+## Werkzeuge
+tools = Werkzeuge
+edit-tools = Werkzeuge bearbeiten...
+add-tool = Werkzeug hinzufügen
+tool-name = Name
+tool-command = Befehl
+tool-terminal = Terminal
+tools-placeholders = %f wird durch die ausgewählten Dateien ersetzt, %d durch den aktuellen Ordner und %D durch den Ordner des anderen Bereichs
+
+## Synchronisationsprofile
+sync-profiles = Synchronisationsprofile
+edit-sync-profiles = Synchronisationsprofile bearbeiten...
+add-sync-profile = Synchronisationsprofil hinzufügen
+sync-name = Name
+sync-source = Quellordner
+sync-destination = Zielordner
+sync-two-way = In beide Richtungen
+sync-checksum = Prüfsummen vergleichen
+sync-delete-orphans = Dateien löschen, die in der Quelle fehlen
+sync-excludes = Ausgeschlossene Namen, wie *.tmp .git
+sync-mirror = Änderungen spiegeln
+
+## Senden an
+send-to = Senden an...
+send-to-email = E-Mail
+send-to-bluetooth = Bluetooth-Geräte
+send-to-folders = Ordner
+send-to-commands = Befehle
+sent-to = An {$name} gesendet
+send-to-failed = Senden an {$name} fehlgeschlagen
+
+## Schnellaktionen
+set-as-wallpaper = Als Hintergrundbild festlegen
+set-as-wallpaper-failed = Hintergrundbild konnte nicht festgelegt werden
+install-font = Schriftart installieren
+fonts-installed = {$count} {$count ->
+        [one] Schriftart
+        *[other] Schriftarten
+    } installiert
+install-font-failed = Schriftarten konnten nicht installiert werden: {$error}
+
+## Gehe zu
+go = Gehe zu
+go-back = Zurück
+go-forward = Vorwärts
+go-up = Übergeordneter Ordner
+desktop = Schreibtisch
+documents = Dokumente
+downloads = Downloads
+music = Musik
+pictures = Bilder
+videos = Videos
+computer = Computer
+enter-location = Ort eingeben...
+add-bookmark = Lesezeichen für diesen Ordner
+edit-bookmarks = Lesezeichen bearbeiten...
+bookmark-name = Name
+bookmark-folder = Ordner
+bookmark-icon = Symbolname
+no-bookmarks = Keine Lesezeichen, mit „Lesezeichen für diesen Ordner“ wird der aktuelle Ordner hinzugefügt
+no-shortcut = Kein Tastenkürzel
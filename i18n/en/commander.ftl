@@ -2,14 +2,46 @@ commander = Commmander
 empty-folder = Empty folder
 empty-folder-hidden = Empty folder (has hidden items)
 no-results = No results found
+filter-all = All items
+filter-images = Images
+filter-video = Videos
+filter-audio = Audio
+filter-documents = Documents
+filter-archives = Archives
+filter-executables = Executables
+filter-today = Modified today
+filter-this-week = Modified this week
 filesystem = Filesystem
 home = Home
 networks = Networks
+devices = Devices
+mount = Mount
+unmount = Unmount
+eject = Eject
+free-space = {$free} free of {$total}
 notification-in-progress = File operations are in progress.
+notification-complete = File operation complete
+notification-failed = File operation failed
+read-only-pane-error = {$dir} is shown in a read-only pane and cannot be changed
+notification-duration = Took {$duration}
+duration-minutes = {$minutes} min {$seconds} s
 trash = Trash
 recents = Recents
 undo = Undo
 today = Today
+just-now = Just now
+minutes-ago = {$count} {$count ->
+        [one] minute
+        *[other] minutes
+    } ago
+hours-ago = {$count} {$count ->
+        [one] hour
+        *[other] hours
+    } ago
+days-ago = {$count} {$count ->
+        [one] day
+        *[other] days
+    } ago
 
 # Desktop view options
 desktop-view-options = Desktop view options...
@@ -17,9 +49,14 @@ show-on-desktop = Show on Desktop
 desktop-folder-content = Desktop folder content
 mounted-drives = Mounted drives
 trash-folder-icon = Trash folder icon
+home-folder-icon = Home folder icon
 icon-size-and-spacing = Icon size and spacing
 icon-size = Icon size
 grid-spacing = Grid spacing
+icon-placement = Icon placement
+auto-arrange-icons = Arrange icons automatically
+align-to-grid = Align to grid
+arrange-icons = Arrange icons
 
 # List view
 name = Name
@@ -32,6 +69,12 @@ details = Details
 dismiss = Dismiss message
 operations-running = {$running} operations running ({$percent}%)...
 operations-running-finished = {$running} operations running ({$percent}%), {$finished} finished...
+progress-bytes = {$done} of {$total}
+progress-files-remaining = {$files} {$files ->
+        [one] file
+        *[other] files
+    } left
+progress-speed = {$speed}/s, average {$average}/s
 pause = Pause
 resume = Resume
 
@@ -47,19 +90,100 @@ extract-password-required = Password required
 empty-trash = Empty trash
 empty-trash-warning = Are you sure you want to permanently delete all the items in Trash?
 
+## Confirmation Dialogs
+confirm-trash-warning = Are you sure you want to move {$items} {$items ->
+        [one] item
+        *[other] items
+    } to the trash?
+confirm-move = Move to another device
+confirm-move-warning = The {$items ->
+        [one] item is
+        *[other] {$items} items are
+    } copied to "{$to}" and deleted here afterwards.
+confirm-execute = Run
+confirm-execute-title = Run or open "{$name}"?
+confirm-execute-warning = The file can be run as a program. Only run files that you trust.
+open-in-editor = Open in editor
+remember-for-type = Remember for this type of file
+
+## Create Launcher Dialog
+create-launcher = Create launcher
+create-launcher-body = The launcher starts "{$program}" from the menus or the desktop.
+launcher-icon = Icon
+launcher-icon-placeholder = Icon name or path of an image
+launcher-categories = Categories
+launcher-terminal = Run in a terminal
+launcher-applications = Applications
+launcher-created = Created the launcher "{$path}"
+launcher-no-folder = The folder for the launcher was not found
+launcher-name-missing = The launcher needs a name
+launcher-program-missing = "{$program}" is not a file
+launcher-icon-invalid = Icons are names of the icon theme without an extension or paths of images
+launcher-category-invalid = Unknown category
+
+## Insufficient Space Dialog
+insufficient-space-copy = Not enough space to copy
+insufficient-space-move = Not enough space to move
+insufficient-space-body = The items need {$required}, but only {$available} are free in "{$destination}". {$missing} are missing.
+continue-anyway = Continue anyway
+
+## Failed Item Dialog
+item-failed-title = "{$name}" could not be processed
+retry = Retry
+abort = Abort
+skipped-items = {$count} {$count ->
+        [one] item was
+        *[other] items were
+    } skipped
+copy-report = Copy report
+
+## Preview Changes Dialog
+preview-changes-title = Replace files
+preview-changes-trash = {$count} {$count ->
+        [one] file
+        *[other] files
+    } ({$size}) will be moved to the trash.
+preview-changes-overwrite = {$count} {$count ->
+        [one] file
+        *[other] files
+    } ({$size}) will be overwritten.
+preview-changes-more = and {$count} more
+
 ## Mount Error Dialog
 mount-error = Unable to access drive
 
 ## New File/Folder Dialog
 create-new-file = Create new file
+paste-as-new-file = Paste as new file
+pasted-image = Pasted image {$date}
+pasted-text = Pasted text {$date}
 create-new-folder = Create new folder
 file-name = File name
 folder-name = Folder name
 file-already-exists = A file with that name already exists.
+export = Export
+export-listing = Export listing
+export-listing-body = Write the items of {$folder} to a file.
+listing-path = Path of the listing
+listing-recursive = Include subfolders
+listing-checksum = SHA-256 checksum
+print = Print
+print-listing = Print listing
+print-listing-body = Print the items of {$folder}.
+print-files-body = Print {$items} {$items ->
+        [one] file
+        *[other] files
+    }.
+print-header = Header with the name and date
+print-footer = Footer with page numbers
+print-page = Page {$page} of {$pages}
+print-sent = Sent to the printer
 folder-already-exists = A folder with that name already exists.
 name-hidden = Names starting with "." will be hidden.
 name-invalid = Name cannot be "{$filename}".
 name-no-slashes = Name cannot contain slashes.
+name-reserved-character = Names on {$filesystem} file systems cannot contain "{$character}".
+name-reserved-ending = Names on {$filesystem} file systems cannot end with a dot or space.
 
 ## Open/Save Dialog
 cancel = Cancel
@@ -69,15 +193,23 @@ open-file = Open file
 open-folder = Open folder
 open-in-new-tab = Open in new tab
 open-in-new-window = Open in new window
+open-as-administrator = Open as administrator
+retry-as-administrator = Retry as administrator
 open-item-location = Open item location
+show-all-in-folder = Show all in folder
 open-multiple-files = Open multiple files
 open-multiple-folders = Open multiple folders
 save = Save
+add = Add
 save-file = Save file
 
 ## Open With Dialog
 open-with-title = How do you want to open "{$name}"?
 browse-store = Browse {$store}
+other-applications = Other applications
+search-applications = Search applications
+custom-command = Custom command
+always-use-for-type = Always use for {$mime}
 
 ## Rename Dialog
 rename-file = Rename file
@@ -94,6 +226,16 @@ apply-to-all = Apply to all
 keep-both = Keep both
 skip = Skip
 
+## Editor
+editor-find = Find
+editor-find-next = Find next
+editor-replace-all = Replace all
+editor-not-found = Not found
+editor-replaced = {$count} replaced
+editor-saved = Saved
+editor-cannot-encode = The text contains characters that cannot be saved as {$encoding}
+editor-cannot-decode = The file is not valid {$encoding}
+
 ## Set as Executable and Launch Dialog
 set-executable-and-launch = Set as executable and launch
 set-executable-and-launch-description = Do you want to set "{$name}" as executable and launch it?
@@ -150,6 +292,13 @@ password = Password
 remember-password = Remember password
 try-again = Try again
 username = Username
+unlock = Unlock
+unlock-container-title = Unlock {$name}
+
+## Cloud Storage
+cloud-keyring-label = Commander rclone configuration
+cloud-password-title = Unlock cloud storage
+cloud-password-body = The rclone configuration is encrypted. Enter its password to connect to {$remote}.
 
 ## Operations
 cancelled = Cancelled
@@ -162,6 +311,14 @@ progress-cancelled = {$percent}%, cancelled
 progress-paused = {$percent}%, paused
 failed = Failed
 complete = Complete
+adding-to-archive = Adding {$items} {$items ->
+        [one] item
+        *[other] items
+    } from "{$from}" to "{$to}" ({$progress})...
+added-to-archive = Added {$items} {$items ->
+        [one] item
+        *[other] items
+    } from "{$from}" to "{$to}"
 compressing = Compressing {$items} {$items ->
         [one] item
         *[other] items
@@ -183,6 +340,8 @@ copied = Copied {$items} {$items ->
     } from "{$from}" to "{$to}"
 emptying-trash = Emptying {trash} ({$progress})...
 emptied-trash = Emptied {trash}
+exporting-listing = Exporting the listing of "{$from}" to "{$to}" ({$progress})...
+exported-listing = Exported the listing of "{$from}" to "{$to}"
 extracting = Extracting {$items} {$items ->
         [one] item
         *[other] items
@@ -201,6 +360,14 @@ moved = Moved {$items} {$items ->
         [one] item
         *[other] items
     } from "{$from}" to "{$to}"
+linking = Linking {$items} {$items ->
+        [one] item
+        *[other] items
+    } from "{$from}" to "{$to}" ({$progress})...
+linked = Linked {$items} {$items ->
+        [one] item
+        *[other] items
+    } from "{$from}" to "{$to}"
 renaming = Renaming "{$from}" to "{$to}"
 renamed = Renamed "{$from}" to "{$to}"
 restoring = Restoring {$items} {$items ->
@@ -211,6 +378,8 @@ restored = Restored {$items} {$items ->
         [one] item
         *[other] items
     } from {trash}
+syncing = Synchronizing "{$name}" ({$progress})...
+synced = Synchronized "{$name}"
 unknown-folder = unknown folder
 
 ## Open with
@@ -224,11 +393,110 @@ items = Items: {$items}
 item-size = Size: {$size}
 item-created = Created: {$created}
 item-modified = Modified: {$modified}
+item-permissions = Permissions: {$permissions}
+item-target = Target: {$target}
 item-accessed = Accessed: {$accessed}
 calculating = Calculating...
+selected-items = {$items} items selected
+selected-files-folders = {$files} files, {$folders} folders
+a11y-selected = Selected, {$selected} of {$total} items selected
+type-count = {$mime}: {$items}
+permissions = Permissions
+permission-read = Read
+permission-write = Write
+permission-execute = Execute
+language = Language: {$name}
+open-full-viewer = Open full viewer
+pdf-page = Page {$page} of {$pages}
+open-hex-viewer = Show as hex
+hex-mode-on = Hex
+hex-mode-off = Normal view
+hex-offset = Go to offset
+hex-search = Search bytes or text
+hex-searching = Searching...
+hex-not-found = No more matches
+hex-invalid-offset = Invalid offset
+
+## Media
+media-title = Title
+media-artist = Artist
+media-album = Album
+media-video-codec = Video codec
+media-audio-codec = Audio codec
+
+## Metadata
+metadata-camera = Camera
+metadata-lens = Lens
+metadata-exposure = Exposure
+metadata-aperture = Aperture
+metadata-iso = ISO
+metadata-focal-length = Focal length
+metadata-taken = Taken
+metadata-location = Location
+open-in-maps = Open in maps
+metadata-track = Track
+metadata-year = Year
+metadata-genre = Genre
+metadata-duration = Duration
+metadata-bitrate = Bitrate
+metadata-sample-rate = Sample rate
+metadata-channels = Channels
+metadata-resolution = Resolution
+preview-skipped = Preview skipped: {$reason}
+preview-too-large = file size {$size} is larger than {$max_size}
+preview-timed-out = took longer than {$seconds} seconds
+
+## Checksums
+checksums = Checksums
+calculate-checksums = Calculate checksums
+verify-checksums = Verify checksums
+checksum-pass = OK
+checksum-fail = Mismatch
+checksums-verified = {$passed} of {$total} files passed
+close = Close
+
+## Compare
+compare-files = Compare files
+compare-changes = {$changes} changed lines
+compare-too-large = {$name} is too large to compare
+compare-binary = {$name} is not a text file
+
+## Terminal
+terminal = Terminal
+new-terminal = New terminal
+rename-terminal = Rename terminal
+close-terminal = Close terminal
+terminal-name = Terminal name
+new-terminal-profile = New terminal: {$profile}
+terminal-profiles = Terminal profiles
+add-profile = Add profile
+profile-name = Name
+default-profile = Default
+profile-command = Shell and arguments, the login shell if empty
+profile-working-directory = Working directory
+profile-tab-title = Tab title
+profile-env = Environment variables, like NAME=value
+profile-startup-command = Startup command
+profile-scrollback = Scrollback lines
+cursor-block = Block
+cursor-underline = Underline
+cursor-beam = Beam
+cursor-blinking = Blinking cursor
+profile-hold = Keep open after exit
+import-color-scheme = Import color scheme
+color-scheme-imported = Imported color scheme {$name}
+insert-name-terminal = Insert filename into terminal
+insert-paths-terminal = Insert full paths of selection
 
 ## Settings
 settings = Settings
+search-settings = Search settings
+no-settings-found = No matching settings
+settings-behavior = Behavior
+settings-panes = Panes
+settings-operations = Operations
+settings-keyboard = Keyboard
+settings-advanced = Advanced
 
 ### Appearance
 appearance = Appearance
@@ -236,14 +504,59 @@ theme = Theme
 match-desktop = Match desktop
 dark = Dark
 light = Light
+follow-system = Follow system
+density = Density
+density-comfortable = Comfortable
+density-compact = Compact
+density-dense = Dense
+size-units = Size units
+size-units-si = Decimal (KB, MB)
+size-units-iec = Binary (KiB, MiB)
+size-units-bytes = Bytes
+date-format = Date format
+date-format-locale = Language default
+date-format-relative = Relative (2 hours ago)
+date-format-iso = ISO 8601 (2024-05-31 14:05)
+first-day-of-week = First day of the week
+monday = Monday
+saturday = Saturday
+sunday = Sunday
+accent-color = Accent color
+theme-default = Theme default
+pane-tint-left = Left pane tint
+pane-tint-right = Right pane tint
+color-blue = Blue
+color-teal = Teal
+color-green = Green
+color-yellow = Yellow
+color-orange = Orange
+color-red = Red
+color-pink = Pink
+color-purple = Purple
+color-gray = Gray
 
 # Context menu
 add-to-sidebar = Add to sidebar
 compress = Compress
+pack-to-other-pane = Pack to other pane
+add-to-archive = Add to archive
+add-to-archive-body = Add {$items} {$items ->
+        [one] item
+        *[other] items
+    } to a zip or tar archive without repacking it
+archive-path = Path of the archive
 extract-here = Extract
+unlock-container = Unlock container
 new-file = New file...
+empty-file = Empty file
+template-text = Text file
+template-markdown = Markdown document
+template-script = Shell script
+template-document-name = New document
+template-script-name = New script
 new-folder = New folder...
 open-in-terminal = Open in terminal
+run-in-terminal = Run in terminal
 move-to-trash = Move to trash
 restore-from-trash = Restore from trash
 remove-from-sidebar = Remove from sidebar
@@ -271,8 +584,26 @@ quit = Quit
 edit = Edit
 cut = Cut
 copy = Copy
+duplicate = Duplicate
+copy-paths = Copy full paths
+copy-names = Copy names
+copy-directory-path = Copy folder path
 paste = Paste
+drop-copy = Copy here
+drop-move = Move here
+drop-link = Link here
 select-all = Select all
+open-context-menu = Open context menu
+save-selection = Save selection
+restore-selection = Restore selection
+load-selection = Load selection from file
+load-selection-body = Select the items listed in a text file, one path per line. Relative paths are in the folder of the pane.
+selection-list-path = Path of the list
+selection-saved = Saved the selection of {$items} {$items ->
+        [one] item
+        *[other] items
+    }
+load = Load
 
 ## View
 zoom-in = Zoom in
@@ -283,7 +614,71 @@ features = Features
 show-button-row = Show Button Row
 show-embedded-terminal = Show embedded Terminal
 show-second-panel = Show second Filemanager panel
+show-toolbar = Show toolbar
+toolbar-buttons = Toolbar buttons
+edit-toolbar = Edit toolbar...
+add-toolbar-button = Add button
+reset-toolbar = Reset
+toolbar-back = Back
+toolbar-forward = Forward
+toolbar-up = Parent folder
+toolbar-reload = Reload
+toolbar-search = Search
+toolbar-view = Switch between grid and list view
+toolbar-swap-panels = Swap panels
 queue-file-operations = Execute File Operations one after the other
+size-one-file-system = Stop at file system boundaries when calculating folder sizes
+vi-keys = Vi key navigation
+vi-keys-description = Move with h, j, k and l, jump with gg and G, select with v, copy, cut and paste with yy, dd and p, and filter with /
+quote-copied-paths = Quote copied paths and names for the shell
+refuse-insufficient-space = Refuse to copy or move items that do not fit on the destination
+preview-changes = List the files that are deleted or overwritten before starting
+confirm-trash = Ask before moving items to the trash
+confirm-empty-trash = Ask before emptying the trash
+confirm-overwrite = Ask before replacing existing items
+confirm-move-across-devices = Ask before moving dropped items to another device
+confirm-run-executables = Ask whether to run or open executable files
+remembered-run-choices = Remembered choices for executable files
+forget = Forget
+copy-workers = Parallel copies
+copy-workers-description = Small files are copied by this many threads at the same time
+direct-io = Copy large files past the cache
+direct-io-description = Large files are read directly from the disk, so they do not push other files out of the memory cache
+remote-trash = Trash on remote volumes
+remote-trash-description = Deleted items on network shares and other remote volumes are moved to a trash folder on the server instead of failing
+sounds = Sounds
+sounds-description = Play sounds of the desktop sound theme when items are moved to the trash and when operations finish or fail
+sound-volume = Sound volume
+share-click-settings = Use the same click settings for both panes
+left-pane = Left pane
+right-pane = Right pane
+single-click-open = Open items with a single click
+double-click-open = Open items with a double click
+click-empty-clears-selection = Clicking empty space clears the selection
+open-together = Open selected files together
+open-together-description = Files with the same default application are opened with one launch of it, if it supports several files
+mouse-gestures = Mouse gestures
+mouse-gestures-description = Drag with the right button held: up opens the parent folder, left goes back, right goes forward, down reloads
+rocker-gestures = Rocker gestures
+rocker-gestures-description = Hold the left button and click the right one to go back, hold the right button and click the left one to go forward
+preview-max-size = Largest file to preview
+preview-timeout = Preview time limit
+duration-seconds = {$seconds} seconds
+slideshow-interval = Slideshow interval
+diff-tool = Diff tool
+built-in-diff = Built-in
+hide-patterns = Hidden file patterns
+hide-patterns-description = Names matching these glob patterns are hidden like names starting with ".".
+add-hide-pattern = Add pattern
+invalid-pattern = Invalid pattern
+color-rules = Name colors
+color-rules-description = Names are colored by the first rule they match: a glob pattern like *.rs, a MIME type like image/*, or folder, executable, symlink or hidden.
+add-color-rule = Add rule
+color-scheme-dark = Dark color scheme
+color-scheme-light = Light color scheme
+import-color-scheme-hint = Import alacritty, iTerm2 or Xresources color schemes from the context menu of the file
+default-applications = Default applications
+no-default-applications = Open folders to choose default applications for their file types
 f2-rename = F2 Rename
 f3-view = F3 View
 f4-edit = F4 Edit
@@ -295,11 +690,25 @@ f9-Term = F9 Terminal
 f10-quit = F10 Quit
 copy-tab = Copy current tab to other side
 move-tab = Move current tab to other side
+duplicate-tab = Duplicate tab
+reopen-closed-tab = Reopen closed tab
+pin-tab = Pin tab
+unpin-tab = Unpin tab
+lock-tab = Lock tab to folder
+unlock-tab = Unlock tab
+quick-jump = Jump to folder
+quick-jump-hint = Folder name
+quick-view = Quick view
+pane-history = Recent folders of the pane
 grid-view = Grid view
 list-view = List view
 show-hidden-files = Show hidden files
+dim-hidden-files = Dim hidden files
 list-directories-first = List directories first
+read-only-pane = Read-only pane
 gallery-preview = Gallery preview
+image-transform-animated = {$name} has more than one frame or page, which rotating or flipping would drop
+image-transform-lossy = {$name} cannot be rotated or flipped without losing details
 menu-settings = Settings...
 menu-about = About  Commander...
 
@@ -311,3 +720,65 @@ sort-newest-first = Newest first
 sort-oldest-first = Oldest first
 sort-smallest-to-largest = Smallest to largest
 sort-largest-to-smallest = Largest to smallest
+
+## Tools
+tools = Tools
+edit-tools = Edit tools...
+add-tool = Add tool
+tool-name = Name
+tool-command = Command
+tool-terminal = Terminal
+tools-placeholders = %f is replaced by the selected files, %d by the current folder and %D by the folder of the other panel
+
+## Sync profiles
+sync-profiles = Sync profiles
+edit-sync-profiles = Edit sync profiles...
+add-sync-profile = Add sync profile
+sync-name = Name
+sync-source = Source folder
+sync-destination = Destination folder
+sync-two-way = Both ways
+sync-checksum = Compare checksums
+sync-delete-orphans = Delete files missing in the source
+sync-excludes = Excluded names, like *.tmp .git
+sync-mirror = Mirror changes
+
+## Send to
+send-to = Send to...
+send-to-email = Email
+send-to-bluetooth = Bluetooth devices
+send-to-folders = Folders
+send-to-commands = Commands
+sent-to = Sent to {$name}
+send-to-failed = Failed to send to {$name}
+
+## Quick actions
+set-as-wallpaper = Set as wallpaper
+set-as-wallpaper-failed = Failed to set the wallpaper
+install-font = Install font
+fonts-installed = Installed {$count} {$count ->
+        [one] font
+        *[other] fonts
+    }
+install-font-failed = Failed to install fonts: {$error}
+
+## Go
+go = Go
+go-back = Back
+go-forward = Forward
+go-up = Parent folder
+desktop = Desktop
+documents = Documents
+downloads = Downloads
+music = Music
+pictures = Pictures
+videos = Videos
+computer = Computer
+enter-location = Enter location...
+add-bookmark = Bookmark this folder
+edit-bookmarks = Edit bookmarks...
+bookmark-name = Name
+bookmark-folder = Folder
+bookmark-icon = Icon name
+no-bookmarks = No bookmarks, use "Bookmark this folder" to add the current folder
+no-shortcut = No shortcut
@@ -2,14 +2,46 @@ cosmos-commander = Commmander
 empty-folder = Tom katalog
 empty-folder-hidden = Tom katalog (har dolda objekt)
 no-results = Inga resultat hittades
+filter-all = Alla objekt
+filter-images = Bilder
+filter-video = Videor
+filter-audio = Ljud
+filter-documents = Dokument
+filter-archives = Arkiv
+filter-executables = Körbara filer
+filter-today = Ändrade idag
+filter-this-week = Ändrade denna vecka
 filesystem = Filsystem
 home = Hem
 networks = Nätverk
+devices = Enheter
+mount = Montera
+unmount = Avmontera
+eject = Mata ut
+free-space = {$free} ledigt av {$total}
 notification-in-progress = Filoperationer pågår.
+notification-complete = Filoperationen är klar
+notification-failed = Filoperationen misslyckades
+read-only-pane-error = {$dir} visas i en skrivskyddad panel och kan inte ändras
+notification-duration = Tog {$duration}
+duration-minutes = {$minutes} min {$seconds} s
 trash = Papperskorg
 recents = Senaste
 undo = Ångra
 today = Idag
+just-now = Just nu
+minutes-ago = för {$count} {$count ->
+        [one] minut
+        *[other] minuter
+    } sedan
+hours-ago = för {$count} {$count ->
+        [one] timme
+        *[other] timmar
+    } sedan
+days-ago = för {$count} {$count ->
+        [one] dag
+        *[other] dagar
+    } sedan
 
 # Skrivbordsvyalternativ
 desktop-view-options = Skrivbordsvyalternativ...
@@ -17,8 +49,13 @@ show-on-desktop = Visa på skrivbord
 desktop-folder-content = Skrivbordsmappinnehåll
 mounted-drives = Monterade enheter
 trash-folder-icon = Ikon för papperskorgen
+home-folder-icon = Ikon för hemmappen
 icon-size-and-spacing = Ikonstorlek och mellanrum
 icon-size = Ikonstorlek
+icon-placement = Ikonplacering
+auto-arrange-icons = Ordna ikoner automatiskt
+align-to-grid = Justera mot rutnät
+arrange-icons = Ordna ikoner
 
 # Listvy
 name = Namn
@@ -31,6 +68,12 @@ details = Detaljer
 dismiss = Stäng meddelande
 operations-running = {$running} operationer körs ({$percent}%)...
 operations-running-finished = {$running} operationer körs ({$percent}%), {$finished} färdig...
+progress-bytes = {$done} av {$total}
+progress-files-remaining = {$files} {$files ->
+        [one] fil
+        *[other] filer
+    } kvar
+progress-speed = {$speed}/s, i genomsnitt {$average}/s
 pause = Paus
 resume = Återuppta
 
@@ -43,19 +86,97 @@ create-archive = Skapa arkiv
 empty-trash = Töm papperskorgen
 empty-trash-warning = Är du säker på att du vill ta bort alla objekt i papperskorgen permanent?
 
+## Bekräftelsedialoger
+confirm-trash-warning = Är du säker på att du vill flytta {$items} {$items ->
+        [one] objekt
+        *[other] objekt
+    } till papperskorgen?
+confirm-move = Flytta till en annan enhet
+confirm-move-warning = {$items ->
+        [one] Objektet kopieras
+        *[other] De {$items} objekten kopieras
+    } till "{$to}" och tas sedan bort här.
+confirm-execute = Kör
+confirm-execute-title = Kör eller öppna "{$name}"?
+confirm-execute-warning = Filen kan köras som ett program. Kör bara filer som du litar på.
+open-in-editor = Öppna i redigeraren
+remember-for-type = Kom ihåg för den här filtypen
+
+## Skapa startare
+create-launcher = Skapa startare
+create-launcher-body = Startaren startar "{$program}" från menyerna eller skrivbordet.
+launcher-icon = Ikon
+launcher-icon-placeholder = Ikonnamn eller sökväg till en bild
+launcher-categories = Kategorier
+launcher-terminal = Kör i en terminal
+launcher-applications = Program
+launcher-created = Startaren "{$path}" skapades
+launcher-no-folder = Mappen för startaren hittades inte
+launcher-name-missing = Startaren behöver ett namn
+launcher-program-missing = "{$program}" är ingen fil
+launcher-icon-invalid = Ikoner är namn i ikontemat utan filändelse eller sökvägar till bilder
+launcher-category-invalid = Okänd kategori
+
+## Dialogruta för otillräckligt utrymme
+insufficient-space-copy = Inte tillräckligt med utrymme för att kopiera
+insufficient-space-move = Inte tillräckligt med utrymme för att flytta
+insufficient-space-body = Objekten behöver {$required}, men bara {$available} är ledigt i "{$destination}". {$missing} saknas.
+continue-anyway = Fortsätt ändå
+
+## Dialogruta för misslyckade objekt
+item-failed-title = "{$name}" kunde inte behandlas
+retry = Försök igen
+abort = Avbryt
+skipped-items = {$count} objekt hoppades över
+copy-report = Kopiera rapport
+
+## Dialogruta för förhandsvisning av ändringar
+preview-changes-title = Ersätt filer
+preview-changes-trash = {$count} {$count ->
+        [one] fil
+        *[other] filer
+    } ({$size}) flyttas till papperskorgen.
+preview-changes-overwrite = {$count} {$count ->
+        [one] fil
+        *[other] filer
+    } ({$size}) skrivs över.
+preview-changes-more = och {$count} till
+
 ## Monteringsfel dialogruta
 mount-error = Kan inte komma åt enheten
 
 ## Ny Fil/katalog dialogruta
 create-new-file = Skapa ny fil
+paste-as-new-file = Klistra in som ny fil
+pasted-image = Inklistrad bild {$date}
+pasted-text = Inklistrad text {$date}
 create-new-folder = Skapa ny katalog
 file-name = Filnamn
 folder-name = Katalognamn
 file-already-exists = En fil med det namnet finns redan.
+export = Exportera
+export-listing = Exportera listning
+export-listing-body = Skriv objekten i {$folder} till en fil.
+listing-path = Sökväg till listningen
+listing-recursive = Ta med undermappar
+listing-checksum = SHA-256-kontrollsumma
+print = Skriv ut
+print-listing = Skriv ut listning
+print-listing-body = Skriv ut objekten i {$folder}.
+print-files-body = Skriv ut {$items} {$items ->
+        [one] fil
+        *[other] filer
+    }.
+print-header = Sidhuvud med namn och datum
+print-footer = Sidfot med sidnummer
+print-page = Sida {$page} av {$pages}
+print-sent = Skickat till skrivaren
 folder-already-exists = En katalog med det namnet finns redan.
 name-hidden = Namn som börjar med "." kommer att vara dolda.
 name-invalid = Namnet kan inte vara "{$filename}".
 name-no-slashes = Namnet får inte innehålla snedstreck.
+name-reserved-character = Namn på {$filesystem}-filsystem får inte innehålla "{$character}".
+name-reserved-ending = Namn på {$filesystem}-filsystem får inte sluta med en punkt eller ett mellanslag.
 
 ## Öppna/Spara dialogruta
 cancel = Avbryt
@@ -65,15 +186,23 @@ open-file = Öppna fil
 open-folder = Öppna katalog
 open-in-new-tab = Öppna i en ny flik
 open-in-new-window = Öppna i nytt fönster
+open-as-administrator = Öppna som administratör
+retry-as-administrator = Försök igen som administratör
 open-item-location = Öppna objektets plats
+show-all-in-folder = Visa alla i mapp
 open-multiple-files = Öppna flera filer
 open-multiple-folders = Öppna flera kataloger
 save = Spara
+add = Lägg till
 save-file = Spara fil
 
 ## Öppna med dialogruta
 open-with-title = Hur vill du öppna "{$name}"?
 browse-store = Bläddra i {$store}
+other-applications = Andra program
+search-applications = Sök program
+custom-command = Anpassat kommando
+always-use-for-type = Använd alltid för {$mime}
 
 ## Byt namn dialogruta
 rename-file = Byt namn på fil
@@ -90,6 +219,16 @@ apply-to-all = Verkställ för alla
 keep-both = Behåll båda
 skip = Hoppa över
 
+## Editor
+editor-find = Sök
+editor-find-next = Sök nästa
+editor-replace-all = Ersätt alla
+editor-not-found = Hittades inte
+editor-replaced = {$count} ersatta
+editor-saved = Sparad
+editor-cannot-encode = Texten innehåller tecken som inte kan sparas som {$encoding}
+editor-cannot-decode = Filen är inte giltig {$encoding}
+
 ## Ställ in som körbar och starta dialogruta
 set-executable-and-launch = Ställ in som körbar och starta
 set-executable-and-launch-description = Vill du ställa in "{$name}" som körbar och starta den?
@@ -133,6 +272,13 @@ password = Lösenord
 remember-password = Kom ihåg lösenord
 try-again = Försök igen
 username = Användarnamn
+unlock = Lås upp
+unlock-container-title = Lås upp {$name}
+
+## Molnlagring
+cloud-keyring-label = Commander rclone-konfiguration
+cloud-password-title = Lås upp molnlagring
+cloud-password-body = rclone-konfigurationen är krypterad. Ange dess lösenord för att ansluta till {$remote}.
 
 ## Operationer
 cancelled = Avbruten
@@ -145,6 +291,14 @@ progress-cancelled = {$percent}%, avbruten
 progress-paused = {$percent}%, pausad
 failed = Misslyckades
 complete = Färdig
+adding-to-archive = Lägger till {$items} {$items ->
+        [one] objekt
+        *[other] objekt
+    } från "{$from}" i "{$to}" ({$progress})...
+added-to-archive = Lade till {$items} {$items ->
+        [one] objekt
+        *[other] objekt
+    } från "{$from}" i "{$to}"
 compressing = Komprimerar {$items} {$items ->
         [one] item
         *[other] items
@@ -166,6 +320,8 @@ copied = Kopierade {$items} {$items ->
     } från "{$from}" till "{$to}"
 emptying-trash = Tömmer {trash} ({$progress})...
 emptied-trash = Tömde {trash}
+exporting-listing = Exporterar listningen av "{$from}" till "{$to}" ({$progress})...
+exported-listing = Exporterade listningen av "{$from}" till "{$to}"
 extracting = Packar upp {$items} {$items ->
         [one] objekt
         *[other] flera objekt
@@ -184,6 +340,14 @@ moved = Flyttade {$items} {$items ->
         [one] objekt
         *[other] flera objekt
     } från "{$from}" till "{$to}"
+linking = Länkar {$items} {$items ->
+        [one] objekt
+        *[other] flera objekt
+    } från "{$from}" till "{$to}" ({$progress})...
+linked = Länkade {$items} {$items ->
+        [one] objekt
+        *[other] flera objekt
+    } från "{$from}" till "{$to}"
 renaming = Byter namn "{$from}" till "{$to}"
 renamed = Bytt namn "{$from}" till "{$to}"
 restoring = Återställer {$items} {$items ->
@@ -194,6 +358,8 @@ restored = Återställt {$items} {$items ->
         [one] objekt
         *[other] flera objekt
     } från {trash}
+syncing = Synkroniserar "{$name}" ({$progress})...
+synced = Synkroniserade "{$name}"
 unknown-folder = okänd katalog
 
 ## Öppna med
@@ -207,11 +373,150 @@ items = Objekt: {$items}
 item-size = Storlek: {$size}
 item-created = Skapad: {$created}
 item-modified = Modifierad: {$modified}
+item-permissions = Behörigheter: {$permissions}
+item-target = Mål: {$target}
 item-accessed = Åtkomst: {$accessed}
 calculating = Beräknar...
+selected-items = {$items} objekt markerade
+selected-files-folders = {$files} filer, {$folders} mappar
+a11y-selected = Markerad, {$selected} av {$total} objekt markerade
+type-count = {$mime}: {$items}
+permissions = Behörigheter
+permission-read = Läsa
+permission-write = Skriva
+permission-execute = Köra
+language = Språk: {$name}
+open-full-viewer = Öppna i visaren
+pdf-page = Sida {$page} av {$pages}
+open-hex-viewer = Visa som hex
+hex-mode-on = Hex
+hex-mode-off = Normal vy
+hex-offset = Gå till position
+hex-search = Sök byte eller text
+hex-searching = Söker...
+hex-not-found = Inga fler träffar
+hex-invalid-offset = Ogiltig position
+
+## Media
+media-title = Titel
+media-artist = Artist
+media-album = Album
+media-video-codec = Videokodek
+media-audio-codec = Ljudkodek
+
+## Metadata
+metadata-camera = Kamera
+metadata-lens = Objektiv
+metadata-exposure = Exponering
+metadata-aperture = Bländare
+metadata-iso = ISO
+metadata-focal-length = Brännvidd
+metadata-taken = Tagen
+metadata-location = Plats
+open-in-maps = Öppna i karta
+metadata-track = Spår
+metadata-year = År
+metadata-genre = Genre
+metadata-duration = Längd
+metadata-bitrate = Bithastighet
+metadata-sample-rate = Samplingsfrekvens
+metadata-channels = Kanaler
+metadata-resolution = Upplösning
+preview-skipped = Förhandsgranskning hoppades över: {$reason}
+preview-too-large = filstorleken {$size} är större än {$max_size}
+preview-timed-out = tog längre tid än {$seconds} sekunder
+
+## Checksums
+checksums = Kontrollsummor
+calculate-checksums = Beräkna kontrollsummor
+verify-checksums = Verifiera kontrollsummor
+checksum-pass = OK
+checksum-fail = Stämmer inte
+checksums-verified = {$passed} av {$total} filer godkändes
+close = Stäng
+
+## Jämför
+compare-files = Jämför filer
+compare-changes = {$changes} ändrade rader
+compare-too-large = {$name} är för stor för att jämföras
+compare-binary = {$name} är inte en textfil
+
+## Terminal
+terminal = Terminal
+new-terminal = Ny terminal
+rename-terminal = Byt namn på terminal
+close-terminal = Stäng terminal
+terminal-name = Terminalens namn
+new-terminal-profile = Ny terminal: {$profile}
+terminal-profiles = Terminalprofiler
+add-profile = Lägg till profil
+profile-name = Namn
+default-profile = Standard
+profile-command = Skal och argument, inloggningsskalet om tomt
+profile-working-directory = Arbetskatalog
+profile-tab-title = Fliktitel
+profile-env = Miljövariabler, som NAMN=värde
+profile-startup-command = Startkommando
+profile-scrollback = Rader i historiken
+cursor-block = Block
+cursor-underline = Understreck
+cursor-beam = Stapel
+cursor-blinking = Blinkande markör
+profile-hold = Håll öppen efter avslut
+import-color-scheme = Importera färgschema
+color-scheme-imported = Importerade färgschema {$name}
+insert-name-terminal = Infoga filnamn i terminalen
+insert-paths-terminal = Infoga fullständiga sökvägar för markeringen
 
 ## Inställningar
 settings = Inställningar
+search-settings = Sök inställningar
+no-settings-found = Inga matchande inställningar
+settings-behavior = Beteende
+settings-panes = Paneler
+settings-operations = Åtgärder
+refuse-insufficient-space = Vägra kopiera eller flytta objekt som inte får plats på målet
+preview-changes = Lista filerna som tas bort eller skrivs över innan start
+confirm-trash = Fråga innan objekt flyttas till papperskorgen
+confirm-empty-trash = Fråga innan papperskorgen töms
+confirm-overwrite = Fråga innan befintliga objekt ersätts
+confirm-move-across-devices = Fråga innan släppta objekt flyttas till en annan enhet
+confirm-run-executables = Fråga om körbara filer ska köras eller öppnas
+remembered-run-choices = Ihågkomna val för körbara filer
+forget = Glöm
+copy-workers = Parallella kopior
+copy-workers-description = Små filer kopieras av så här många trådar samtidigt
+direct-io = Kopiera stora filer förbi cachen
+direct-io-description = Stora filer läses direkt från disken, så att de inte tränger undan andra filer från minnescachen
+remote-trash = Papperskorg på fjärrvolymer
+remote-trash-description = Borttagna objekt på nätverksresurser och andra fjärrvolymer flyttas till en papperskorgsmapp på servern i stället för att misslyckas
+sounds = Ljud
+sounds-description = Spela upp ljud från skrivbordets ljudtema när objekt flyttas till papperskorgen och när åtgärder slutförs eller misslyckas
+sound-volume = Ljudvolym
+share-click-settings = Använd samma klickinställningar för båda panelerna
+left-pane = Vänster panel
+right-pane = Höger panel
+single-click-open = Öppna objekt med ett enkelklick
+double-click-open = Öppna objekt med ett dubbelklick
+click-empty-clears-selection = Klick på tom yta avmarkerar allt
+open-together = Öppna markerade filer tillsammans
+open-together-description = Filer med samma standardprogram öppnas med en start av det, om det hanterar flera filer
+mouse-gestures = Musgester
+mouse-gestures-description = Dra med höger knapp nedtryckt: uppåt öppnar den överordnade mappen, vänster går bakåt, höger går framåt, nedåt läser in igen
+rocker-gestures = Vippgester
+rocker-gestures-description = Håll vänster knapp och klicka med den högra för att gå bakåt, håll höger knapp och klicka med den vänstra för att gå framåt
+preview-max-size = Största fil att förhandsvisa
+preview-timeout = Tidsgräns för förhandsvisning
+duration-seconds = {$seconds} sekunder
+slideshow-interval = Intervall för bildspel
+diff-tool = Jämförelseverktyg
+built-in-diff = Inbyggt
+hide-patterns = Mönster för dolda filer
+hide-patterns-description = Namn som matchar dessa glob-mönster döljs som namn som börjar med ".".
+add-hide-pattern = Lägg till mönster
+invalid-pattern = Ogiltigt mönster
+settings-keyboard = Tangentbord
+settings-advanced = Avancerat
 
 ### Utseende
 appearance = Utseende
@@ -219,14 +524,67 @@ theme = Tema
 match-desktop = Matcha skrivbordet
 dark = Mörkt
 light = Ljust
+follow-system = Följ systemet
+density = Täthet
+density-comfortable = Bekväm
+density-compact = Kompakt
+density-dense = Tät
+size-units = Storleksenheter
+size-units-si = Decimal (KB, MB)
+size-units-iec = Binär (KiB, MiB)
+size-units-bytes = Byte
+date-format = Datumformat
+date-format-locale = Språkets standard
+date-format-relative = Relativ (för 2 timmar sedan)
+date-format-iso = ISO 8601 (2024-05-31 14:05)
+first-day-of-week = Veckans första dag
+monday = Måndag
+saturday = Lördag
+sunday = Söndag
+accent-color = Accentfärg
+theme-default = Temats standard
+pane-tint-left = Ton för vänster panel
+pane-tint-right = Ton för höger panel
+color-rules = Namnfärger
+color-rules-description = Namn färgas efter den första regel de matchar: ett glob-mönster som *.rs, en MIME-typ som image/* eller folder, executable, symlink eller hidden.
+add-color-rule = Lägg till regel
+color-scheme-dark = Mörkt färgschema
+color-scheme-light = Ljust färgschema
+import-color-scheme-hint = Importera färgscheman för alacritty, iTerm2 eller Xresources från filens snabbmeny
+default-applications = Standardprogram
+no-default-applications = Öppna mappar för att välja standardprogram för deras filtyper
+color-blue = Blå
+color-teal = Blågrön
+color-green = Grön
+color-yellow = Gul
+color-orange = Orange
+color-red = Röd
+color-pink = Rosa
+color-purple = Lila
+color-gray = Grå
 
 # Kontext meny
 add-to-sidebar = Lägg till i sidofält
 compress = Komprimera
+pack-to-other-pane = Packa till andra panelen
+add-to-archive = Lägg till i arkiv
+add-to-archive-body = Lägg till {$items} {$items ->
+        [one] objekt
+        *[other] objekt
+    } i ett zip- eller tar-arkiv utan att packa om det
+archive-path = Sökväg till arkivet
 extract-here = Packa upp
+unlock-container = Lås upp behållare
 new-file = Ny fil
+empty-file = Tom fil
+template-text = Textfil
+template-markdown = Markdown-dokument
+template-script = Skalskript
+template-document-name = Nytt dokument
+template-script-name = Nytt skript
 new-folder = Ny katalog
 open-in-terminal = Öppna i terminal
+run-in-terminal = Kör i terminalen
 move-to-trash = Flytta till papperskorg
 restore-from-trash = Återställ från papperskorgen
 remove-from-sidebar = Ta bort från sidofält
@@ -254,8 +612,26 @@ quit = Avsluta
 edit = Redigera
 cut = Klipp ut
 copy = Kopiera
+duplicate = Duplicera
+copy-paths = Kopiera fullständiga sökvägar
+copy-names = Kopiera namn
+copy-directory-path = Kopiera mappsökväg
 paste = Klistra in
+drop-copy = Kopiera hit
+drop-move = Flytta hit
+drop-link = Länka hit
 select-all = Välj alla
+open-context-menu = Öppna snabbmenyn
+save-selection = Spara markering
+restore-selection = Återställ markering
+load-selection = Läs in markering från fil
+load-selection-body = Markera objekten som listas i en textfil, en sökväg per rad. Relativa sökvägar ligger i panelens mapp.
+selection-list-path = Sökväg till listan
+selection-saved = Sparade markeringen av {$items} {$items ->
+        [one] objekt
+        *[other] objekt
+    }
+load = Läs in
 
 ## View
 zoom-in = Zooma in
@@ -265,6 +641,22 @@ view = Visa
 show-button-row = Visa knapprad
 show-embedded-terminal = Visa inbäddad terminal
 show-second-panel = Visa den andra filhanterarens panel
+show-toolbar = Visa verktygsfält
+toolbar-buttons = Knappar i verktygsfältet
+edit-toolbar = Redigera verktygsfält...
+add-toolbar-button = Lägg till knapp
+reset-toolbar = Återställ
+toolbar-back = Bakåt
+toolbar-forward = Framåt
+toolbar-up = Överordnad mapp
+toolbar-reload = Läs om
+toolbar-search = Sök
+toolbar-view = Växla mellan rutnäts- och listvy
+toolbar-swap-panels = Byt paneler
+size-one-file-system = Stanna vid filsystemsgränser när mappstorlekar beräknas
+vi-keys = Vi-tangentnavigering
+vi-keys-description = Flytta med h, j, k och l, hoppa med gg och G, markera med v, kopiera, klipp ut och klistra in med yy, dd och p, och filtrera med /
+quote-copied-paths = Citera kopierade sökvägar och namn för skalet
 f2-rename = F2 Byt namn
 f3-view = F3 Visa
 f4-edit = F4 Redigera
@@ -276,11 +668,25 @@ f9-Term = F9 Terminal
 f10-quit = F10 Avsluta
 copy-tab = Kopiera nuvarande flik till andra sidan
 move-tab = Flytta nuvarande flik till andra sidan
+duplicate-tab = Duplicera flik
+reopen-closed-tab = Återöppna stängd flik
+pin-tab = Fäst flik
+unpin-tab = Lossa flik
+lock-tab = Lås flik till mappen
+unlock-tab = Lås upp flik
+quick-jump = Hoppa till mapp
+quick-jump-hint = Mappnamn
+quick-view = Snabbvisning
+pane-history = Senaste mappar i panelen
 grid-view = Rutnätsvy
 list-view = Listvy
 show-hidden-files = Visa dolda filer
+dim-hidden-files = Tona ned dolda filer
 list-directories-first = Lista kataloger först
+read-only-pane = Skrivskyddad panel
 gallery-preview = Galleri förhandsvisning
+image-transform-animated = {$name} har mer än en bildruta eller sida, som skulle försvinna vid rotering eller spegling
+image-transform-lossy = {$name} kan inte roteras eller speglas utan att detaljer går förlorade
 menu-settings = Inställningar...
 menu-about = Om Cosmic Commander...
 
@@ -292,3 +698,65 @@ sort-newest-first = Nyaste först
 sort-oldest-first = Äldst först
 sort-smallest-to-largest = Minsta till största
 sort-largest-to-smallest = Största till minsta
+
+## Verktyg
+tools = Verktyg
+edit-tools = Redigera verktyg...
+add-tool = Lägg till verktyg
+tool-name = Namn
+tool-command = Kommando
+tool-terminal = Terminal
+tools-placeholders = %f ersätts med de markerade filerna, %d med den aktuella mappen och %D med mappen i den andra panelen
+
+## Synkroniseringsprofiler
+sync-profiles = Synkroniseringsprofiler
+edit-sync-profiles = Redigera synkroniseringsprofiler...
+add-sync-profile = Lägg till synkroniseringsprofil
+sync-name = Namn
+sync-source = Källmapp
+sync-destination = Målmapp
+sync-two-way = Åt båda hållen
+sync-checksum = Jämför kontrollsummor
+sync-delete-orphans = Ta bort filer som saknas i källan
+sync-excludes = Undantagna namn, som *.tmp .git
+sync-mirror = Spegla ändringar
+
+## Skicka till
+send-to = Skicka till...
+send-to-email = E-post
+send-to-bluetooth = Bluetooth-enheter
+send-to-folders = Mappar
+send-to-commands = Kommandon
+sent-to = Skickat till {$name}
+send-to-failed = Det gick inte att skicka till {$name}
+
+## Snabbåtgärder
+set-as-wallpaper = Ange som skrivbordsbakgrund
+set-as-wallpaper-failed = Det gick inte att ange skrivbordsbakgrunden
+install-font = Installera typsnitt
+fonts-installed = Installerade {$count} {$count ->
+        [one] typsnitt
+        *[other] typsnitt
+    }
+install-font-failed = Det gick inte att installera typsnitt: {$error}
+
+## Gå
+go = Gå
+go-back = Tillbaka
+go-forward = Framåt
+go-up = Överordnad mapp
+desktop = Skrivbord
+documents = Dokument
+downloads = Hämtningar
+music = Musik
+pictures = Bilder
+videos = Videor
+computer = Dator
+enter-location = Ange plats...
+add-bookmark = Bokmärk den här mappen
+edit-bookmarks = Redigera bokmärken...
+bookmark-name = Namn
+bookmark-folder = Mapp
+bookmark-icon = Ikonnamn
+no-bookmarks = Inga bokmärken, använd "Bokmärk den här mappen" för att lägga till den aktuella mappen
+no-shortcut = Inget kortkommando
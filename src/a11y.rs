@@ -0,0 +1,112 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Names and descriptions of widgets for screen readers. They are only exposed when built with
+//! the `a11y` feature, without it these helpers return the widgets unchanged.
+//!
+//! Screen reader support is not usable yet: the `a11y` feature crashes, so the widget tree is
+//! not exposed in normal builds. Until it is, changes of the selection and the progress of
+//! operations are spoken through speech-dispatcher while a screen reader is running.
+
+#[cfg(feature = "dbus")]
+use cosmic::iced::{
+    futures::{channel::mpsc, SinkExt, StreamExt},
+    stream, Subscription,
+};
+use cosmic::widget::button::Button;
+#[cfg(feature = "dbus")]
+use std::any::TypeId;
+use std::process;
+
+use crate::spawn_detached::spawn_detached;
+
+/// Name of a button without text, like the icon buttons
+#[cfg(feature = "a11y")]
+pub fn name<Message>(button: Button<'_, Message>, name: String) -> Button<'_, Message> {
+    button.name(name)
+}
+
+#[cfg(not(feature = "a11y"))]
+pub fn name<Message>(button: Button<'_, Message>, _name: String) -> Button<'_, Message> {
+    button
+}
+
+/// Description of the state of a button, read after its name
+#[cfg(feature = "a11y")]
+pub fn describe<Message>(button: Button<'_, Message>, description: String) -> Button<'_, Message> {
+    button.description(description)
+}
+
+#[cfg(not(feature = "a11y"))]
+pub fn describe<Message>(button: Button<'_, Message>, _description: String) -> Button<'_, Message> {
+    button
+}
+
+/// How speech-dispatcher queues an announcement
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Priority {
+    /// Dropped when newer text is announced, like the selection while moving through items
+    Text,
+    /// Thinned out when it comes too fast, the first and last announcements are always spoken
+    Progress,
+}
+
+impl Priority {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Progress => "progress",
+        }
+    }
+}
+
+/// Speak `text` with speech-dispatcher, which screen readers like Orca also speak through
+pub fn announce(text: &str, priority: Priority) {
+    let mut command = process::Command::new("spd-say");
+    command
+        .arg("--priority")
+        .arg(priority.as_str())
+        .arg("--")
+        .arg(text);
+    if let Err(err) = spawn_detached(&mut command) {
+        log::warn!("failed to announce {:?}: {}", text, err);
+    }
+}
+
+#[cfg(feature = "dbus")]
+#[zbus::proxy(
+    interface = "org.a11y.Status",
+    default_service = "org.a11y.Bus",
+    default_path = "/org/a11y/bus"
+)]
+trait Status {
+    #[zbus(property)]
+    fn screen_reader_enabled(&self) -> zbus::Result<bool>;
+}
+
+#[cfg(feature = "dbus")]
+async fn watch(output: &mut mpsc::Sender<bool>) -> zbus::Result<()> {
+    let connection = zbus::Connection::session().await?;
+    let status = StatusProxy::new(&connection).await?;
+    // Listen before reading so that no change is missed
+    let mut changes = status.receive_screen_reader_enabled_changed().await;
+    let _ = output.send(status.screen_reader_enabled().await?).await;
+    while let Some(change) = changes.next().await {
+        let _ = output.send(change.get().await?).await;
+    }
+    Ok(())
+}
+
+/// Whether a screen reader is running, sent at the start and whenever it is turned on or off
+#[cfg(feature = "dbus")]
+pub fn subscription() -> Subscription<bool> {
+    struct ScreenReaderSubscription;
+    Subscription::run_with_id(
+        TypeId::of::<ScreenReaderSubscription>(),
+        stream::channel(1, |mut output| async move {
+            if let Err(err) = watch(&mut output).await {
+                log::warn!("failed to watch screen reader status: {}", err);
+            }
+            std::future::pending().await
+        }),
+    )
+}
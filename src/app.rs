@@ -21,7 +21,7 @@ use cosmic::{
         clipboard::dnd::DndAction,
         event,
         futures::{self, SinkExt},
-        keyboard::{Event as KeyEvent, Key, Modifiers},
+        keyboard::{key::Named, Event as KeyEvent, Key, Modifiers},
         stream,
         window::{self, Event as WindowEvent, Id as WindowId},
         Alignment, Event, Length, Point, Rectangle, Size, Subscription,
@@ -39,11 +39,13 @@ use cosmic::{
     },
     Application, ApplicationExt, Element,
 };
+use hex_color::HexColor;
 use notify_debouncer_full::{
     new_debouncer,
     notify::{self, RecommendedWatcher, Watcher},
     DebouncedEvent, Debouncer, FileIdMap,
 };
+use serde::{Deserialize, Serialize};
 use slotmap::Key as SlotMapKey;
 use std::{
     any::TypeId,
@@ -60,30 +62,52 @@ use trash::TrashItem;
 #[cfg(feature = "wayland")]
 use wayland_client::{protocol::wl_output::WlOutput, Proxy};
 
-use alacritty_terminal::{event::Event as TermEvent, term, term::color::Colors as TermColors};
+#[cfg(feature = "dbus")]
+use crate::appearance;
+#[cfg(feature = "dbus")]
+use crate::file_manager1;
+#[cfg(feature = "dbus")]
+use crate::launcher_entry;
+use alacritty_terminal::{
+    event::Event as TermEvent, term, term::color::Colors as TermColors, vte::ansi::NamedColor,
+};
 
 use crate::{
-    clipboard::{ClipboardCopy, ClipboardKind, ClipboardPaste},
+    a11y,
+    checksum::{self, VerifyResult},
+    clipboard::{
+        path_from_text, ClipboardCopy, ClipboardImage, ClipboardKind, ClipboardPaste, CopyText,
+        KdeCutSelection,
+    },
     config::{
-        self, AppTheme, ColorSchemeKind, Config, DesktopConfig, Favorite, IconSizes, TabConfig1,
-        TabConfig2,
+        self, AppTheme, Bookmark, ColorSchemeId, ColorSchemeKind, Config, ConfirmConfig,
+        CursorStyle, DateFormat, Density, DesktopConfig, DesktopLayout, Favorite, FirstDayOfWeek,
+        FormatConfig, IconSizes, Profile, ProfileId, RunChoice, SizeUnits, TabConfig, TabState,
     },
-    fl, home_dir,
-    key_bind::{key_binds, key_binds_terminal},
+    desktop_entry::{self, Launcher, LauncherDir},
+    diff,
+    editor::{self, Editor},
+    fl, frecency, home_dir,
+    key_bind::{key_binds, key_binds_terminal, key_binds_vi, ViKey},
+    listing::{ListingColumns, ListingFormat},
     localize::LANGUAGE_SORTER,
     menu, mime_app, mime_icon,
-    mounter::{MounterAuth, MounterItem, MounterItems, MounterKey, MounterMessage, MOUNTERS},
-    operation::{Controller, Operation, OperationSelection, ReplaceResult},
+    mounter::{self, MounterAuth, MounterItem, MounterItems, MounterKey, MounterMessage, MOUNTERS},
+    operation::{
+        self, ChangePreview, Controller, ErrorResult, Operation, OperationError,
+        OperationSelection, ReplaceResult, SpaceCheck,
+    },
     pane_grid::{self, PaneGrid},
+    plugin,
+    print::{self, PrintJob, PrintOptions},
+    quick_action::{self, QuickAction},
+    recents,
+    send_to::{self, SendTo},
+    sound::{self, Sound},
     spawn_detached::spawn_detached,
-    tab1::{
-        self, HeadingOptions as HeadingOptions1, ItemMetadata as ItemMetadata1,
-        Location as Location1, Tab as Tab1, HOVER_DURATION as HOVER_DURATION1,
-    },
-    tab2::{
-        self, HeadingOptions as HeadingOptions2, ItemMetadata as ItemMetadata2,
-        Location as Location2, Tab as Tab2, HOVER_DURATION as HOVER_DURATION2,
-    },
+    sync,
+    tab::{self, HeadingOptions, ItemMetadata, Location, Tab, HOVER_DURATION},
+    template, terminal_theme, tool, volume_trash,
 };
 
 type TabModel = segmented_button::Model<segmented_button::SingleSelect>;
@@ -99,17 +123,31 @@ pub struct Flags {
     pub config_handler: Option<cosmic_config::Config>,
     pub config: Config,
     pub mode: Mode,
-    pub locations1: Vec<Location1>,
-    pub locations2: Vec<Location1>,
+    pub locations1: Vec<Location>,
+    pub locations2: Vec<Location>,
+    /// Items selected in a tab of their folder
+    pub selected: Vec<PathBuf>,
+    /// View of the opened tabs, overriding the config
+    pub view: Option<tab::View>,
+    pub show_settings: bool,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Action {
     About,
+    AddBookmark,
+    AddToArchive,
     AddToSidebar,
+    ArrangeDesktop,
+    BookmarkShortcut(u8),
     ClearScrollback,
+    Compare,
     Compress,
+    ContextMenu,
     Copy,
+    CopyDirectoryPath,
+    CopyNames,
+    CopyPaths,
     CopyTerminal,
     CopyOrSigint,
     CopyPrimary,
@@ -118,12 +156,20 @@ pub enum Action {
     CosmicSettingsAppearance,
     CosmicSettingsDisplays,
     CosmicSettingsWallpaper,
+    CreateLauncher,
     DesktopViewOptions,
+    Duplicate,
     EditHistory,
     EditLocation,
+    EditBookmarks,
+    EditSyncProfiles,
+    EditTerminalProfiles,
+    EditToolbar,
+    EditTools,
     EmptyTrash,
     #[cfg(feature = "desktop")]
     ExecEntryAction(usize),
+    ExportListing,
     ExtractHere,
     F2Rename,
     F3View,
@@ -134,49 +180,104 @@ pub enum Action {
     F8Delete,
     F9Terminal,
     F10Quit,
+    FocusNextPane,
     Gallery,
+    GoComputer,
+    GoDesktop,
+    GoDocuments,
+    GoDownloads,
+    GoHome,
+    GoMusic,
+    GoNetwork,
+    GoPictures,
+    GoRecents,
+    GoTrash,
+    GoVideos,
     HistoryNext,
     HistoryPrevious,
+    ImportColorScheme,
+    InsertNameTerminal,
+    InsertPathsTerminal,
     ItemDown,
     ItemLeft,
     ItemRight,
     ItemUp,
+    LoadSelection,
     LocationUp,
     MoveTab,
     MoveToTrash,
     NewFile,
     NewFolder,
+    NewFromTemplate(usize),
     Open,
+    OpenAsAdministrator,
+    OpenBookmark(usize),
     OpenInNewTab,
     OpenInNewWindow,
     OpenItemLocation,
     OpenTerminal,
     OpenWith,
+    PackToOtherPane,
+    PaneHistory,
     Paste,
     PastePrimary,
     PasteTerminal,
     PastePrimaryTerminal,
     Preview,
+    Print,
+    PrintListing,
+    QuickAction(QuickAction),
+    QuickJump,
+    QuickView,
     Rename,
     RestoreFromTrash,
+    RestoreSelection,
+    RunInTerminal,
+    RunPlugin(usize),
+    RunSync(usize),
+    RunTool(usize),
+    SaveSelection,
     SearchActivate,
     SelectFirst,
     SelectLast,
     SelectAll,
-    SetSort(HeadingOptions1, bool),
+    SelectInvert,
+    SelectToggle,
+    SelectToggleDown,
+    SendTo,
+    SetSort(HeadingOptions, bool),
     Settings,
+    ShowAllInFolder,
     SwapPanels,
+    TabActivateLeft,
+    TabActivateRight,
     TabClose,
+    TabDuplicate,
+    TabLock,
+    TabMoveBackward,
+    TabMoveForward,
     TabNew,
     TabNext,
+    TabPin,
     TabPrev,
+    TabReopen,
     TabRescan,
     TabViewGrid,
     TabViewList,
+    TabViewToggle,
+    TermClose,
+    TermNew,
+    TermNewProfile(ProfileId),
+    TermRename,
+    ToggleDimHidden,
     ToggleFoldersFirst,
+    ToggleReadOnly,
     ToggleShowHidden,
-    ToggleSortLeft(HeadingOptions1),
-    ToggleSortRight(HeadingOptions2),
+    ToggleSortLeft(HeadingOptions),
+    ToggleSortRight(HeadingOptions),
+    ToggleTerminal,
+    UnlockContainer,
+    VerifyChecksums,
     WindowClose,
     WindowNew,
     ZoomDefault,
@@ -189,22 +290,39 @@ impl Action {
     fn message(&self, entity_opt: Option<Entity>) -> Message {
         match self {
             Action::About => Message::ToggleContextPage(ContextPage::About),
+            Action::AddBookmark => Message::AddBookmark(entity_opt),
+            Action::AddToArchive => Message::AddToArchive(entity_opt),
             Action::AddToSidebar => Message::AddToSidebar(entity_opt),
+            Action::ArrangeDesktop => Message::ArrangeDesktop(entity_opt),
+            Action::BookmarkShortcut(digit) => Message::BookmarkShortcut(*digit),
             Action::ClearScrollback => Message::ClearScrollback(entity_opt),
+            Action::Compare => Message::Compare(entity_opt),
             Action::Compress => Message::Compress(entity_opt),
+            Action::ContextMenu => Message::ContextMenu(entity_opt),
             Action::Copy => Message::Copy(entity_opt),
             Action::CopyTerminal => Message::CopyTerminal(entity_opt),
+            Action::CreateLauncher => Message::CreateLauncher(entity_opt),
             Action::CopyOrSigint => Message::CopyOrSigint(entity_opt),
             Action::CopyPrimary => Message::CopyPrimary(entity_opt),
+            Action::CopyDirectoryPath => Message::CopyText(entity_opt, CopyText::Directory),
+            Action::CopyNames => Message::CopyText(entity_opt, CopyText::Names),
+            Action::CopyPaths => Message::CopyText(entity_opt, CopyText::Paths),
             Action::CopyTab => Message::CopyTab(entity_opt),
             Action::Cut => Message::Cut(entity_opt),
+            Action::Duplicate => Message::Duplicate(entity_opt),
             Action::CosmicSettingsAppearance => Message::CosmicSettings("appearance"),
             Action::CosmicSettingsDisplays => Message::CosmicSettings("displays"),
             Action::CosmicSettingsWallpaper => Message::CosmicSettings("wallpaper"),
             Action::DesktopViewOptions => Message::DesktopViewOptions,
             Action::EditHistory => Message::ToggleContextPage(ContextPage::EditHistory),
             Action::EditLocation => Message::EditLocation(entity_opt),
+            Action::EditBookmarks => Message::EditBookmarks,
+            Action::EditSyncProfiles => Message::EditSyncProfiles,
+            Action::EditTerminalProfiles => Message::EditTerminalProfiles,
+            Action::EditToolbar => Message::EditToolbar,
+            Action::EditTools => Message::EditTools,
             Action::EmptyTrash => Message::EmptyTrash(entity_opt),
+            Action::ExportListing => Message::ExportListing,
             Action::ExtractHere => Message::ExtractHere(entity_opt),
             #[cfg(feature = "desktop")]
             Action::ExecEntryAction(action) => Message::ExecEntryAction(entity_opt, *action),
@@ -217,49 +335,107 @@ impl Action {
             Action::F8Delete => Message::F8Delete,
             Action::F9Terminal => Message::F9Terminal,
             Action::F10Quit => Message::F10Quit,
+            Action::FocusNextPane => Message::FocusNextPane,
             Action::Gallery => Message::GalleryToggle(entity_opt),
+            Action::GoComputer => Message::GoLocation(Location::Path(PathBuf::from("/"))),
+            Action::GoDesktop => Message::GoFavorite(Favorite::Desktop),
+            Action::GoDocuments => Message::GoFavorite(Favorite::Documents),
+            Action::GoDownloads => Message::GoFavorite(Favorite::Downloads),
+            Action::GoHome => Message::GoFavorite(Favorite::Home),
+            Action::GoMusic => Message::GoFavorite(Favorite::Music),
+            Action::GoNetwork => Message::GoLocation(Location::Network(
+                "network:///".to_string(),
+                fl!("networks"),
+            )),
+            Action::GoPictures => Message::GoFavorite(Favorite::Pictures),
+            Action::GoRecents => Message::GoLocation(Location::Recents),
+            Action::GoTrash => Message::GoLocation(Location::Trash),
+            Action::GoVideos => Message::GoFavorite(Favorite::Videos),
             Action::HistoryNext => Message::HistoryNext(entity_opt),
             Action::HistoryPrevious => Message::HistoryPrevious(entity_opt),
+            Action::ImportColorScheme => Message::ImportColorScheme(entity_opt),
+            Action::InsertNameTerminal => Message::InsertTerminal(entity_opt, false),
+            Action::InsertPathsTerminal => Message::InsertTerminal(entity_opt, true),
             Action::ItemDown => Message::ItemDown(entity_opt),
             Action::ItemLeft => Message::ItemLeft(entity_opt),
             Action::ItemRight => Message::ItemRight(entity_opt),
             Action::ItemUp => Message::ItemUp(entity_opt),
+            Action::LoadSelection => Message::LoadSelection(entity_opt),
             Action::LocationUp => Message::LocationUp(entity_opt),
             Action::MoveTab => Message::MoveTab(entity_opt),
             Action::MoveToTrash => Message::MoveToTrash(entity_opt),
             Action::NewFile => Message::NewItem(entity_opt, false),
             Action::NewFolder => Message::NewItem(entity_opt, true),
+            Action::NewFromTemplate(index) => Message::NewFromTemplate(entity_opt, *index),
             Action::Open => Message::Open(entity_opt),
+            Action::OpenBookmark(index) => Message::OpenBookmark(*index),
             Action::OpenInNewTab => Message::OpenInNewTab(entity_opt),
             Action::OpenInNewWindow => Message::OpenInNewWindow(entity_opt),
+            Action::OpenAsAdministrator => Message::OpenAsAdministrator(entity_opt),
             Action::OpenItemLocation => Message::OpenItemLocation(entity_opt),
             Action::OpenTerminal => Message::OpenTerminal(entity_opt),
             Action::OpenWith => Message::OpenWithDialog(entity_opt),
+            Action::PackToOtherPane => Message::PackToOtherPane(entity_opt),
+            Action::PaneHistory => Message::PaneHistory,
             Action::Paste => Message::Paste(entity_opt),
             Action::PastePrimary => Message::PastePrimary(entity_opt),
             Action::PasteTerminal => Message::PasteTerminal(entity_opt),
             Action::PastePrimaryTerminal => Message::PastePrimaryTerminal(entity_opt),
             Action::Preview => Message::Preview(entity_opt),
+            Action::Print => Message::Print(entity_opt),
+            Action::PrintListing => Message::PrintListing,
+            Action::QuickAction(action) => Message::QuickAction(entity_opt, *action),
+            Action::QuickJump => Message::QuickJump,
+            Action::QuickView => Message::QuickView,
             Action::Rename => Message::Rename(entity_opt),
             Action::RestoreFromTrash => Message::RestoreFromTrash(entity_opt),
+            Action::RestoreSelection => Message::RestoreSelection(entity_opt),
+            Action::RunInTerminal => Message::RunInTerminal(entity_opt),
+            Action::RunPlugin(index) => Message::RunPlugin(*index),
+            Action::RunSync(index) => Message::RunSync(*index),
+            Action::RunTool(index) => Message::RunTool(*index),
             Action::SearchActivate => Message::SearchActivate,
+            Action::SaveSelection => Message::SaveSelection(entity_opt),
             Action::SelectAll => Message::SelectAll(entity_opt),
             Action::SelectFirst => Message::SelectFirst(entity_opt),
+            Action::SelectInvert => Message::SelectInvert(entity_opt),
             Action::SelectLast => Message::SelectLast(entity_opt),
+            Action::SelectToggle => Message::SelectToggle(entity_opt, false),
+            Action::SelectToggleDown => Message::SelectToggle(entity_opt, true),
+            Action::SendTo => Message::SendToDialog(entity_opt),
             Action::SetSort(sort, dir) => Message::SetSort(entity_opt, *sort, *dir),
             Action::Settings => Message::ToggleContextPage(ContextPage::Settings),
+            Action::ShowAllInFolder => Message::ShowAllInFolder(entity_opt),
             Action::SwapPanels => Message::SwapPanels,
+            Action::TabActivateLeft => Message::TabActivateLeft,
+            Action::TabActivateRight => Message::TabActivateRight,
             Action::TabClose => Message::TabClose(entity_opt),
+            Action::TabDuplicate => Message::TabDuplicate,
+            Action::TabLock => Message::TabLock,
+            Action::TabMoveBackward => Message::TabMoveBackward,
+            Action::TabMoveForward => Message::TabMoveForward,
             Action::TabNew => Message::TabNew,
             Action::TabNext => Message::TabNext,
+            Action::TabPin => Message::TabPin,
             Action::TabPrev => Message::TabPrev,
+            Action::TabReopen => Message::TabReopen,
             Action::TabRescan => Message::TabRescan,
-            Action::TabViewGrid => Message::TabView(entity_opt, tab1::View::Grid),
-            Action::TabViewList => Message::TabView(entity_opt, tab1::View::List),
+            Action::TabViewGrid => Message::TabView(entity_opt, tab::View::Grid),
+            Action::TabViewList => Message::TabView(entity_opt, tab::View::List),
+            Action::TabViewToggle => Message::TabViewToggle(entity_opt),
+            Action::TermClose => Message::TermClose(entity_opt),
+            Action::TermNew => Message::TermNew,
+            Action::TermNewProfile(profile_id) => Message::TermNewProfile(*profile_id),
+            Action::TermRename => Message::TermRename(entity_opt),
             Action::ToggleFoldersFirst => Message::ToggleFoldersFirst,
+            Action::ToggleDimHidden => Message::ToggleDimHidden(entity_opt),
+            Action::ToggleReadOnly => Message::ToggleReadOnly,
             Action::ToggleShowHidden => Message::ToggleShowHidden(entity_opt),
             Action::ToggleSortLeft(sort) => Message::ToggleSortLeft(entity_opt, *sort),
             Action::ToggleSortRight(sort) => Message::ToggleSortRight(entity_opt, *sort),
+            Action::ToggleTerminal => Message::ToggleTerminal,
+            Action::UnlockContainer => Message::UnlockContainer(entity_opt),
+            Action::VerifyChecksums => Message::VerifyChecksums(entity_opt),
             Action::WindowClose => Message::WindowClose,
             Action::WindowNew => Message::WindowNew,
             Action::ZoomDefault => Message::ZoomDefault(entity_opt),
@@ -270,6 +446,32 @@ impl Action {
     }
 }
 
+impl Action {
+    /// Whether the action changes the items of a pane, which read-only panes do not allow
+    pub fn changes_files(&self) -> bool {
+        matches!(
+            self,
+            Action::AddToArchive
+                | Action::Compress
+                | Action::Cut
+                | Action::Duplicate
+                | Action::ExtractHere
+                | Action::F2Rename
+                | Action::F6Move
+                | Action::F7Mkdir
+                | Action::F8Delete
+                | Action::MoveToTrash
+                | Action::NewFile
+                | Action::NewFolder
+                | Action::NewFromTemplate(_)
+                | Action::Paste
+                | Action::PastePrimary
+                | Action::QuickAction(_)
+                | Action::Rename
+        )
+    }
+}
+
 impl MenuAction for Action {
     type Message = Message;
 
@@ -279,33 +481,20 @@ impl MenuAction for Action {
 }
 
 #[derive(Clone, Debug)]
-pub struct PreviewItem1(pub tab1::Item);
-
-impl PartialEq for PreviewItem1 {
-    fn eq(&self, other: &Self) -> bool {
-        self.0.location_opt == other.0.location_opt
-    }
-}
-
-impl Eq for PreviewItem1 {}
-
-#[derive(Clone, Debug)]
-pub struct PreviewItem2(pub tab2::Item);
+pub struct PreviewItem(pub tab::Item);
 
-impl PartialEq for PreviewItem2 {
+impl PartialEq for PreviewItem {
     fn eq(&self, other: &Self) -> bool {
         self.0.location_opt == other.0.location_opt
     }
 }
 
-impl Eq for PreviewItem2 {}
+impl Eq for PreviewItem {}
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum PreviewKind {
-    Custom1(PreviewItem1),
-    Location1(Location1),
-    Custom2(PreviewItem2),
-    Location2(Location2),
+    Custom(PreviewItem),
+    Location(Location),
     Selected,
 }
 
@@ -332,39 +521,10 @@ impl Pane {
     }
 }
 
-fn convert_location1_to_location2(location: &Location1) -> Location2 {
-    let loc;
-    match location {
-        Location1::Path(path) => loc = Location2::Path(path.to_owned()),
-        Location1::Trash => loc = Location2::Trash,
-        Location1::Network(s1, s2) => loc = Location2::Network(s1.clone(), s2.clone()),
-        Location1::Recents => loc = Location2::Recents,
-        Location1::Search(path, s, b, i) => {
-            loc = Location2::Search(path.to_owned(), s.clone(), b.to_owned(), i.to_owned())
-        }
-        Location1::Desktop(p, s, d) => {
-            loc = Location2::Desktop(p.to_owned(), s.to_owned(), d.to_owned())
-        }
-    }
-    loc
-}
-
-fn convert_location2_to_location1(location: &Location2) -> Location1 {
-    let loc;
-    match location {
-        Location2::Path(path) => loc = Location1::Path(path.to_owned()),
-        Location2::Trash => loc = Location1::Trash,
-        Location2::Network(s1, s2) => loc = Location1::Network(s1.clone(), s2.clone()),
-        Location2::Recents => loc = Location1::Recents,
-        Location2::Search(path, s, b, i) => {
-            loc = Location1::Search(path.to_owned(), s.clone(), b.to_owned(), i.to_owned())
-        }
-        Location2::Desktop(p, s, d) => {
-            loc = Location1::Desktop(p.to_owned(), s.to_owned(), d.to_owned())
-        }
-    }
-    loc
-}
+/// Number of closed tabs that can be reopened
+const MAX_CLOSED_TABS: usize = 10;
+/// Files listed by the dialog that previews the changes of an operation
+const PREVIEW_CHANGES_MAX: usize = 500;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum NavMenuAction {
@@ -388,31 +548,69 @@ impl MenuAction for NavMenuAction {
 /// Messages that are used specifically by our [`App`].
 #[derive(Clone, Debug)]
 pub enum Message {
+    AccentColor(Option<HexColor>),
+    AddBookmark(Option<Entity>),
+    AddToArchive(Option<Entity>),
     AddToSidebar(Option<Entity>),
     AppTheme(AppTheme),
+    ArrangeDesktop(Option<Entity>),
+    BookmarkShortcut(u8),
+    ClearRunChoices,
     ClearScrollback(Option<segmented_button::Entity>),
+    ChangesPreviewed(Operation, ChangePreview),
     CloseToast(widget::ToastId),
     CloseToastLeft(widget::ToastId),
     CloseToastRight(widget::ToastId),
+    ColorRules(Vec<config::ColorRule>),
+    ColorSchemeImported(Result<config::ColorScheme, String>),
+    Compare(Option<Entity>),
+    CompareClose,
+    CompareResult(Result<Arc<diff::TextDiff>, String>),
     Compress(Option<Entity>),
     Config(Config),
+    ConfirmConfig(ConfirmConfig),
+    ContextMenu(Option<Entity>),
+    ClickEmptyClearsSelection(Option<PaneType>, bool),
     Copy(Option<Entity>),
     CopyTerminal(Option<Entity>),
     CopyOrSigint(Option<segmented_button::Entity>),
     CopyPrimary(Option<segmented_button::Entity>),
     CopyTab(Option<segmented_button::Entity>),
+    CopyText(Option<Entity>, CopyText),
+    CopyWorkers(usize),
     CosmicSettings(&'static str),
+    CreateLauncher(Option<Entity>),
     Cut(Option<Entity>),
+    DateFormat(DateFormat),
+    DefaultApp(mime_guess::Mime, usize),
+    Density(Density),
     DesktopConfig(DesktopConfig),
+    DesktopLayout(String, DesktopLayout),
     DesktopViewOptions,
+    DeviceEject(MounterKey, MounterItem),
+    DirectIo(bool),
+    Duplicate(Option<Entity>),
+    DeviceMount(MounterKey, MounterItem),
+    DeviceOpen(MounterKey, MounterItem),
+    DeviceUnmount(MounterKey, MounterItem),
+    DiffTool(String),
     DialogCancel,
     DialogComplete,
     DialogPush(DialogPage),
     DialogUpdate(DialogPage),
     DialogUpdateComplete(DialogPage),
+    DoubleClickOpen(Option<PaneType>, bool),
+    EditBookmarks,
     EditLocation(Option<Entity>),
+    EditSyncProfiles,
+    EditTerminalProfiles,
+    Editor(window::Id, editor::Message),
+    EditToolbar,
+    EditTools,
     EmptyTrash(Option<Entity>),
+    ErrorResult(ErrorResult),
     ExecEntryAction(Option<Entity>, usize),
+    ExportListing,
     ExtractHere(Option<Entity>),
     F2Rename,
     F3View,
@@ -423,13 +621,23 @@ pub enum Message {
     F8Delete,
     F9Terminal,
     F10Quit,
+    #[cfg(feature = "dbus")]
+    FileManager1(file_manager1::Request),
+    FirstDayOfWeek(FirstDayOfWeek),
+    FocusNextPane,
     GalleryToggle(Option<Entity>),
+    GoFavorite(Favorite),
+    GoLocation(Location),
+    HidePatterns(Vec<String>),
     HistoryNext(Option<Entity>),
     HistoryPrevious(Option<Entity>),
+    ImportColorScheme(Option<Entity>),
+    InsertTerminal(Option<Entity>, bool),
     ItemDown(Option<Entity>),
     ItemLeft(Option<Entity>),
     ItemRight(Option<Entity>),
     ItemUp(Option<Entity>),
+    LoadSelection(Option<Entity>),
     LocationUp(Option<Entity>),
     Key(Modifiers, Key),
     LaunchUrl(String),
@@ -440,13 +648,14 @@ pub enum Message {
     MoveToTrash(Option<Entity>),
     MounterItems(MounterKey, MounterItems),
     MountResult(MounterKey, MounterItem, Result<bool, String>),
-    NavBarClose(Entity),
+    MouseGestures(bool),
     NavBarContext(Entity),
     NavMenuAction(NavMenuAction),
     NetworkAuth(MounterKey, String, MounterAuth, mpsc::Sender<MounterAuth>),
     NetworkDriveInput(String),
     NetworkDriveSubmit,
     NetworkResult(MounterKey, String, Result<bool, String>),
+    NewFromTemplate(Option<Entity>, usize),
     NewItem(Option<Entity>, bool),
     #[cfg(feature = "notify")]
     Notification(Arc<Mutex<notify_rust::NotificationHandle>>),
@@ -455,13 +664,20 @@ pub enum Message {
     NotifyWatcherLeft(WatcherWrapper),
     NotifyWatcherRight(WatcherWrapper),
     Open(Option<Entity>),
+    OpenAsAdministrator(Option<Entity>),
+    OpenBookmark(usize),
     OpenTerminal(Option<Entity>),
+    OpenTogether(bool),
     OpenInNewTab(Option<Entity>),
     OpenInNewWindow(Option<Entity>),
     OpenItemLocation(Option<Entity>),
     OpenWithBrowse,
+    OpenWithCustomCommand(String),
     OpenWithDialog(Option<Entity>),
+    OpenWithSearch(String),
     OpenWithSelection(usize),
+    OpenWithSetDefault(bool),
+    PackToOtherPane(Option<Entity>),
     #[cfg(all(feature = "desktop", feature = "wayland"))]
     Overlap(OverlapNotifyEvent, window::Id),
     PaneUpdate,
@@ -470,10 +686,15 @@ pub enum Message {
     PaneFocusAdjacent(pane_grid::Direction),
     PaneClicked(pane_grid::Pane),
     PaneDragged(pane_grid::DragEvent),
+    PaneHistory,
+    PaneHistoryMenu(Option<PaneType>),
+    PaneHistoryOpen(PaneType, usize),
     PaneResized(pane_grid::ResizeEvent),
     //PaneTogglePin(pane_grid::Pane),
     PaneMaximize(pane_grid::Pane),
     PaneRestore,
+    PaneTintLeft(Option<HexColor>),
+    PaneTintRight(Option<HexColor>),
     //PaneClose(pane_grid::Pane),
     //PaneCloseFocused,
     Paste(Option<Entity>),
@@ -482,35 +703,84 @@ pub enum Message {
     PastePrimaryTerminal(Option<segmented_button::Entity>),
     PasteValueTerminal(String),
     PasteContents(PathBuf, ClipboardPaste),
+    PasteKdeCut(PathBuf, ClipboardPaste),
+    PasteImage(PathBuf, Option<ClipboardImage>),
+    PastePath(PathBuf, Option<String>),
     PendingCancel(u64),
     PendingCancelAll,
     PendingComplete(u64, OperationSelection),
     PendingDismiss,
-    PendingError(u64, String),
+    PendingError(u64, OperationError),
     PendingPause(u64, bool),
     PendingPauseAll(bool),
     Preview(Option<Entity>),
+    Print(Option<Entity>),
+    PrintListing,
+    Printed(Result<(), String>),
+    PreviewChanges(bool),
+    PreviewMaxSize(usize),
+    PreviewTimeout(usize),
     QueueFileOperations(bool),
+    QuickAction(Option<Entity>, QuickAction),
+    QuickActionResult(String),
+    QuickJump,
+    QuickJumpOpen(PathBuf),
+    QuickView,
+    QuoteCopiedPaths(bool),
+    RefuseInsufficientSpace(bool),
+    RemoteTrash(bool),
     RescanTrash,
     Rename(Option<Entity>),
     ReplaceResult(ReplaceResult),
     RestoreFromTrash(Option<Entity>),
+    RestoreSelection(Option<Entity>),
+    RockerGestures(bool),
+    RunChoice(RunChoice),
+    RunInTerminal(Option<Entity>),
+    RunPlugin(usize),
+    RunSync(usize),
+    RunTool(usize),
+    SaveSelection(Option<Entity>),
+    #[cfg(feature = "dbus")]
+    ScreenReader(bool),
     SearchActivate,
     SearchClear,
     SearchInput(String),
     SelectAll(Option<Entity>),
     SelectFirst(Option<Entity>),
+    SelectInvert(Option<Entity>),
     SelectLast(Option<Entity>),
-    SetSort(Option<Entity>, HeadingOptions1, bool),
-    SetSortRight(Option<Entity>, HeadingOptions2, bool),
+    SelectionLoaded(Result<Vec<PathBuf>, String>),
+    SelectToggle(Option<Entity>, bool),
+    SendTo(SendTo),
+    SendToDevices(Vec<send_to::BluetoothDevice>),
+    SendToDialog(Option<Entity>),
+    SentTo(String),
+    SetSort(Option<Entity>, HeadingOptions, bool),
+    SetSortRight(Option<Entity>, HeadingOptions, bool),
     SetShowDetails(bool),
+    SettingsPage(SettingsPage),
+    SettingsSearch(String),
+    ShowAllInFolder(Option<Entity>),
+    ShareClickSettings(bool),
     ShowButtonRow(bool),
+    ShowToolbar(bool),
     ShowEmbeddedTerminal(bool),
     ShowSecondPanel(bool),
     SystemThemeModeChange(cosmic_theme::ThemeMode),
+    #[cfg(feature = "dbus")]
+    SystemColorScheme(Option<bool>),
     Size(Size),
+    SingleClickOpen(Option<PaneType>, bool),
+    SizeOneFileSystem(bool),
+    SizeUnits(SizeUnits),
+    SlideshowInterval(usize),
+    SoundVolume(u8),
+    Sounds(bool),
+    SpaceChecked(Operation, Option<SpaceCheck>),
     StoreOpenPaths,
     SwapPanels,
+    SyntaxTheme(ColorSchemeKind, usize),
     TabActivate(Entity),
     TabActivateLeft,
     TabActivateRight,
@@ -522,28 +792,37 @@ pub enum Message {
     TabClose(Option<Entity>),
     TabCloseLeft(Option<Entity>),
     TabCloseRight(Option<Entity>),
-    TabConfigLeft(TabConfig1),
-    TabCreateLeft(Option<Location1>),
-    TabConfigRight(TabConfig2),
-    TabCreateRight(Option<Location2>),
-    TabMessage(Option<Entity>, tab1::Message),
-    TabMessageRight(Option<Entity>, tab2::Message),
+    TabConfigLeft(TabConfig),
+    TabCreateLeft(Option<Location>),
+    TabConfigRight(TabConfig),
+    TabCreateRight(Option<Location>),
+    TabDuplicate,
+    TabLock,
+    TabMessage(Option<Entity>, tab::Message),
+    TabMessageRight(Option<Entity>, tab::Message),
+    TabMoveBackward,
+    TabMoveForward,
     TabNew,
+    TabPin,
+    TabReopen,
     TabRescanLeft(
         Entity,
-        Location1,
-        Option<tab1::Item>,
-        Vec<tab1::Item>,
+        Location,
+        Option<tab::Item>,
+        Vec<tab::Item>,
         Option<Vec<PathBuf>>,
     ),
     TabRescanRight(
         Entity,
-        Location2,
-        Option<tab2::Item>,
-        Vec<tab2::Item>,
+        Location,
+        Option<tab::Item>,
+        Vec<tab::Item>,
         Option<Vec<PathBuf>>,
     ),
-    TabView(Option<Entity>, tab1::View),
+    TabView(Option<Entity>, tab::View),
+    TabViewToggle(Option<Entity>),
+    TermActivate(Entity),
+    TermClose(Option<Entity>),
     TermContextAction(Action),
     TermContextMenu(pane_grid::Pane, Option<Point>),
     TermEvent(pane_grid::Pane, Entity, alacritty_terminal::event::Event),
@@ -551,23 +830,33 @@ pub enum Message {
     TermMiddleClick(pane_grid::Pane, Option<segmented_button::Entity>),
     TermMouseEnter(pane_grid::Pane),
     TermNew,
+    TermNewProfile(ProfileId),
+    TermRename(Option<Entity>),
     ToggleContextPage(ContextPage),
+    ToggleDimHidden(Option<Entity>),
     ToggleFoldersFirst,
+    ToggleReadOnly,
     ToggleShowHidden(Option<Entity>),
-    ToggleSortLeft(Option<Entity>, HeadingOptions1),
-    ToggleSortRight(Option<Entity>, HeadingOptions2),
+    ToggleSortLeft(Option<Entity>, HeadingOptions),
+    ToggleSortRight(Option<Entity>, HeadingOptions),
+    ToggleTerminal,
     Undo(usize),
     UndoTrash(widget::ToastId, Arc<[PathBuf]>),
     UndoTrashStart(Vec<TrashItem>),
+    UnlockContainer(Option<Entity>),
+    VerifyChecksums(Option<Entity>),
+    VerifyChecksumsResult(PathBuf, Result<Vec<(PathBuf, VerifyResult)>, String>),
+    ViKeys(bool),
     WindowClose,
     WindowCloseRequested(window::Id),
+    WindowFocus,
     WindowNew,
     WindowUnfocus,
     ZoomDefault(Option<Entity>),
     ZoomIn(Option<Entity>),
     ZoomOut(Option<Entity>),
-    DndHoverLocTimeoutLeft(Location1),
-    DndHoverLocTimeoutRight(Location2),
+    DndHoverLocTimeoutLeft(Location),
+    DndHoverLocTimeoutRight(Location),
     DndHoverTabTimeout(Entity),
     DndEnterNav(Entity),
     DndExitNav,
@@ -608,6 +897,45 @@ pub enum ContextPage {
     Settings,
 }
 
+/// Page of the settings context drawer
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SettingsPage {
+    #[default]
+    Appearance,
+    Behavior,
+    Panes,
+    Terminal,
+    Operations,
+    Keyboard,
+    Advanced,
+}
+
+impl SettingsPage {
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::Appearance,
+            Self::Behavior,
+            Self::Panes,
+            Self::Terminal,
+            Self::Operations,
+            Self::Keyboard,
+            Self::Advanced,
+        ]
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::Appearance => fl!("appearance"),
+            Self::Behavior => fl!("settings-behavior"),
+            Self::Panes => fl!("settings-panes"),
+            Self::Terminal => fl!("terminal"),
+            Self::Operations => fl!("settings-operations"),
+            Self::Keyboard => fl!("settings-keyboard"),
+            Self::Advanced => fl!("settings-advanced"),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub enum ArchiveType {
     Tgz,
@@ -636,6 +964,13 @@ impl AsRef<str> for ArchiveType {
 
 #[derive(Clone, Debug)]
 pub enum DialogPage {
+    AddToArchive {
+        paths: Vec<PathBuf>,
+        archive: String,
+    },
+    Bookmarks {
+        bookmarks: Vec<Bookmark>,
+    },
     Compress {
         paths: Vec<PathBuf>,
         to: PathBuf,
@@ -643,12 +978,53 @@ pub enum DialogPage {
         archive_type: ArchiveType,
         password: Option<String>,
     },
+    /// Create a desktop entry for a program
+    CreateLauncher {
+        launcher: Launcher,
+        dir: LauncherDir,
+    },
+    /// Run an executable file or open it
+    ConfirmExecute {
+        path: PathBuf,
+        /// Binaries are not opened in the editor
+        binary: bool,
+        /// Remember the choice for the type of the file
+        remember: bool,
+    },
+    /// Move dropped items to another device
+    ConfirmMove {
+        to: PathBuf,
+        contents: ClipboardPaste,
+    },
+    ConfirmTrash {
+        paths: Vec<PathBuf>,
+    },
     EmptyTrash,
+    ExportListing {
+        dir: PathBuf,
+        to: String,
+        recursive: bool,
+        format: ListingFormat,
+        columns: ListingColumns,
+    },
     FailedOperation(u64),
     ExtractPassword {
         id: u64,
         password: String,
     },
+    InsufficientSpace {
+        operation: Operation,
+        check: SpaceCheck,
+    },
+    ItemFailed {
+        path: PathBuf,
+        error: String,
+        apply_to_all: bool,
+        tx: mpsc::Sender<ErrorResult>,
+    },
+    LoadSelection {
+        path: String,
+    },
     MountError {
         mounter_key: MounterKey,
         item: MounterItem,
@@ -669,12 +1045,26 @@ pub enum DialogPage {
         parent: PathBuf,
         name: String,
         dir: bool,
+        /// Pasted text or image data the new file is created with
+        contents: Option<Vec<u8>>,
     },
     OpenWith {
         path: PathBuf,
         mime: mime_guess::Mime,
         selected: usize,
         store_opt: Option<mime_app::MimeApp>,
+        search: String,
+        custom_command: String,
+        set_default: bool,
+    },
+    /// Files that the operation deletes or overwrites, shown before it starts
+    Print {
+        job: PrintJob,
+        options: PrintOptions,
+    },
+    PreviewChanges {
+        operation: Operation,
+        preview: ChangePreview,
     },
     RenameItem {
         from: PathBuf,
@@ -683,32 +1073,64 @@ pub enum DialogPage {
         dir: bool,
     },
     Replace1 {
-        from: tab1::Item,
-        to: tab1::Item,
+        from: tab::Item,
+        to: tab::Item,
         multiple: bool,
         apply_to_all: bool,
         tx: mpsc::Sender<ReplaceResult>,
     },
     Replace2 {
-        from: tab2::Item,
-        to: tab2::Item,
+        from: tab::Item,
+        to: tab::Item,
         multiple: bool,
         apply_to_all: bool,
         tx: mpsc::Sender<ReplaceResult>,
     },
+    QuickJump {
+        search: String,
+    },
+    SendTo {
+        paths: Vec<PathBuf>,
+        /// Bluetooth devices, listed after the dialog is opened
+        devices: Vec<send_to::BluetoothDevice>,
+    },
     SetExecutableAndLaunch {
         path: PathBuf,
     },
+    /// Items that failed and were skipped by an operation, with their errors
+    SkippedItems {
+        items: Vec<(PathBuf, String)>,
+    },
+    SyncProfiles {
+        profiles: Vec<config::SyncProfile>,
+    },
+    TermRename {
+        entity: Entity,
+        name: String,
+    },
+    TerminalProfiles {
+        profiles: BTreeMap<ProfileId, Profile>,
+        default_profile: Option<ProfileId>,
+    },
+    Toolbar {
+        actions: Vec<Action>,
+    },
+    Tools {
+        tools: Vec<config::UserTool>,
+    },
+    VerifyChecksums {
+        path: PathBuf,
+        result: Result<Vec<(PathBuf, VerifyResult)>, String>,
+    },
 }
 
 pub struct FavoriteIndex(usize);
 
-pub struct MounterData(MounterKey, MounterItem);
-
 #[derive(Clone, Debug)]
 pub enum WindowKind {
     Desktop(Entity),
     DesktopViewOptions,
+    Editor,
     Preview1(Option<Entity>, PreviewKind),
     Preview2(Option<Entity>, PreviewKind),
 }
@@ -735,6 +1157,102 @@ impl PartialEq for WatcherWrapper {
     }
 }
 
+/// Find the closest folder containing all of `paths`.
+fn common_ancestor(paths: &[PathBuf]) -> Option<PathBuf> {
+    let mut ancestor = paths.first()?.parent()?.to_path_buf();
+    for path in paths.iter().skip(1) {
+        while !path.starts_with(&ancestor) || path == &ancestor {
+            if !ancestor.pop() {
+                return None;
+            }
+        }
+    }
+    // Relative paths without a shared folder end up with an empty path
+    (!ancestor.as_os_str().is_empty()).then_some(ancestor)
+}
+
+/// Timestamp for the names of pasted files, without colons that are awkward in file names
+fn paste_date() -> String {
+    chrono::Local::now().format("%Y-%m-%d %H-%M-%S").to_string()
+}
+
+/// Duration of an operation in minutes and seconds, for the notification when it is finished
+#[cfg(feature = "notify")]
+fn format_duration(duration: time::Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds < 60 {
+        fl!("duration-seconds", seconds = seconds)
+    } else {
+        fl!(
+            "duration-minutes",
+            minutes = seconds / 60,
+            seconds = seconds % 60
+        )
+    }
+}
+
+/// Pane content on a faint background of the tint color
+fn tinted(element: Element<Message>, tint_opt: Option<HexColor>) -> Element<Message> {
+    match tint_opt {
+        Some(tint) => widget::container(element)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(move |_theme| widget::container::Style {
+                background: Some(
+                    cosmic::iced::Color::from_rgba8(tint.r, tint.g, tint.b, 0.08).into(),
+                ),
+                ..Default::default()
+            })
+            .into(),
+        None => element,
+    }
+}
+
+/// Line under the tab bar of a pane, in the accent color if the pane gets the global actions
+fn pane_indicator<'a>(active: bool) -> Element<'a, Message> {
+    widget::container(widget::Space::with_height(Length::Fixed(2.0)))
+        .width(Length::Fill)
+        .style(move |theme| widget::container::Style {
+            background: active
+                .then(|| cosmic::iced::Color::from(theme.cosmic().accent_color()).into()),
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Tab messages of clicks and menus, which make the pane of the tab the active pane
+fn focuses_pane(message: &tab::Message) -> bool {
+    matches!(
+        message,
+        tab::Message::Click(_)
+            | tab::Message::DoubleClick(_)
+            | tab::Message::ContextMenu(Some(_))
+            | tab::Message::EditLocation(Some(_))
+    )
+}
+
+/// What is announced about the selection of a tab, the name of a single item or the count
+fn selection_announcement(tab: &Tab) -> Option<String> {
+    let mut selected = tab.items_opt()?.iter().filter(|item| item.selected);
+    let first = selected.next()?;
+    let count = 1 + selected.count();
+    Some(if count == 1 {
+        first.display_name.clone()
+    } else {
+        fl!("selected-items", items = count)
+    })
+}
+
+/// Color scheme of the desktop portal, only watched when the theme follows it
+#[cfg(feature = "dbus")]
+fn appearance_subscription(app_theme: AppTheme) -> Subscription<Message> {
+    if app_theme == AppTheme::FollowSystem {
+        appearance::subscription().map(Message::SystemColorScheme)
+    } else {
+        Subscription::none()
+    }
+}
+
 fn osstr_to_string(osstr: std::ffi::OsString) -> String {
     match osstr.to_str() {
         Some(str) => return str.to_string(),
@@ -750,31 +1268,69 @@ pub struct App {
     nav_model: segmented_button::SingleSelectModel,
     tab_model1: segmented_button::Model<segmented_button::SingleSelect>,
     tab_model2: segmented_button::Model<segmented_button::SingleSelect>,
+    closed_tabs: VecDeque<(PaneType, Location)>,
     pane_model: crate::commanderpanegrid::CommanderPaneGrid,
     term_event_tx_opt:
         Option<mpsc::UnboundedSender<(pane_grid::Pane, Entity, alacritty_terminal::event::Event)>>,
-    terminal: Option<Mutex<crate::terminal::Terminal>>,
+    terminal_model: segmented_button::SingleSelectModel,
+    terminal_id: widget::Id,
     active_panel: PaneType,
     //terminal: Terminal,
     show_button_row: bool,
     show_embedded_terminal: bool,
     show_second_panel: bool,
+    /// The inactive pane previews the selected item of the active pane
+    quick_view: bool,
+    /// Pane with an open location history dropdown, and the highlighted directory
+    pane_history_menu: Option<(PaneType, usize)>,
     config_handler: Option<cosmic_config::Config>,
     config: Config,
     mode: Mode,
     app_themes: Vec<String>,
+    accent_colors: Vec<String>,
+    pane_tints: Vec<String>,
+    densities: Vec<String>,
+    size_units: Vec<String>,
+    date_formats: Vec<String>,
+    first_days_of_week: Vec<String>,
+    launcher_dirs: Vec<String>,
+    toolbar_actions: Vec<String>,
+    settings_page: SettingsPage,
+    settings_pages: Vec<String>,
+    settings_search: String,
+    /// Color scheme of the desktop portal, dark if true
+    system_prefer_dark: Option<bool>,
+    /// Selection changes and operation progress are announced while a screen reader runs
+    screen_reader: bool,
+    bookmark_shortcuts: Vec<String>,
+    preview_max_sizes: Vec<String>,
+    preview_timeouts: Vec<String>,
+    slideshow_intervals: Vec<String>,
+    copy_workers: Vec<String>,
+    cursor_styles: Vec<String>,
     themes: HashMap<(String, ColorSchemeKind), TermColors>,
     theme_names_dark: Vec<String>,
     theme_names_light: Vec<String>,
     context_page: ContextPage,
+    compare_opt: Option<Arc<diff::TextDiff>>,
     dialog_pages: VecDeque<DialogPage>,
     dialog_text_input: widget::Id,
     key_binds: HashMap<KeyBind, Action>,
     key_binds_terminal: HashMap<KeyBind, Action>,
     margin: HashMap<window::Id, (f32, f32, f32, f32)>,
     mime_app_cache: mime_app::MimeAppCache,
+    /// MIME types of all items that were shown, for the default application settings
+    seen_mimes: Vec<mime_guess::Mime>,
     modifiers: Modifiers,
+    /// Characters typed in the vi navigation mode that start a longer sequence
+    vi_pending: String,
+    /// Items are added to the selection while moving in the vi navigation mode
+    vi_visual: bool,
+    /// Selections saved by pane, for restoring them after navigating
+    saved_selections: HashMap<PaneType, Vec<PathBuf>>,
     mounter_items: HashMap<MounterKey, MounterItems>,
+    /// Total and free bytes by mount point of the devices
+    device_usage: HashMap<PathBuf, (u64, u64)>,
     network_drive_connecting: Option<(MounterKey, String)>,
     network_drive_input: String,
     #[cfg(feature = "notify")]
@@ -782,10 +1338,12 @@ pub struct App {
     overlap: HashMap<String, (window::Id, Rectangle)>,
     pending_operation_id: u64,
     pending_operations: BTreeMap<u64, (Operation, Controller)>,
+    /// Start of the pending operations, for the notification when they are finished
+    operation_started: HashMap<u64, Instant>,
     _fileops: BTreeMap<u64, (Operation, Controller)>,
     progress_operations: BTreeSet<u64>,
     complete_operations: BTreeMap<u64, Operation>,
-    failed_operations: BTreeMap<u64, (Operation, Controller, String)>,
+    failed_operations: BTreeMap<u64, (Operation, Controller, OperationError)>,
     search_id: widget::Id,
     size: Option<Size>,
     #[cfg(feature = "wayland")]
@@ -798,9 +1356,11 @@ pub struct App {
     watcher_opt_left: Option<(Debouncer<RecommendedWatcher, FileIdMap>, HashSet<PathBuf>)>,
     watcher_opt_right: Option<(Debouncer<RecommendedWatcher, FileIdMap>, HashSet<PathBuf>)>,
     window_id_opt: Option<window::Id>,
+    window_focused: bool,
     windows: HashMap<window::Id, WindowKind>,
-    nav_dnd_hover: Option<(Location1, Instant)>,
-    nav_dnd_hover_right: Option<(Location2, Instant)>,
+    editors: HashMap<window::Id, Editor>,
+    nav_dnd_hover: Option<(Location, Instant)>,
+    nav_dnd_hover_right: Option<(Location, Instant)>,
     tab_dnd_hover: Option<(Entity, Instant)>,
     tab_dnd_hover_left: Option<(Entity, Instant)>,
     tab_dnd_hover_right: Option<(Entity, Instant)>,
@@ -816,7 +1376,41 @@ pub struct App {
 }
 
 impl App {
-    fn open_file(&mut self, path: &PathBuf) {
+    fn add_seen_mimes<'a>(&mut self, mimes: impl Iterator<Item = &'a mime_guess::Mime>) {
+        let mut changed = false;
+        for mime in mimes {
+            if !self.seen_mimes.contains(mime) {
+                self.seen_mimes.push(mime.clone());
+                changed = true;
+            }
+        }
+        if changed {
+            self.seen_mimes
+                .sort_by(|a, b| a.essence_str().cmp(b.essence_str()));
+        }
+    }
+
+    fn launch_executable(&mut self, path: &Path) {
+        // Try opening executable
+        let mut command = std::process::Command::new(path);
+        match spawn_detached(&mut command) {
+            Ok(()) => {}
+            Err(err) => match err.kind() {
+                io::ErrorKind::PermissionDenied => {
+                    // If permission is denied, try marking as executable, then running
+                    self.dialog_pages
+                        .push_back(DialogPage::SetExecutableAndLaunch {
+                            path: path.to_path_buf(),
+                        });
+                }
+                _ => {
+                    log::warn!("failed to execute {:?}: {}", path, err);
+                }
+            },
+        }
+    }
+
+    fn open_file(&mut self, path: &Path) -> Task<Message> {
         let mime = mime_icon::mime_for_path(path);
         if mime == "application/x-desktop" {
             // Try opening desktop application
@@ -825,7 +1419,7 @@ impl App {
                     Some(exec) => match mime_app::exec_to_command(exec, None) {
                         Some(mut command) => match spawn_detached(&mut command) {
                             Ok(()) => {
-                                return;
+                                return Task::none();
                             }
                             Err(err) => {
                                 log::warn!("failed to execute {:?}: {}", path, err);
@@ -843,40 +1437,42 @@ impl App {
                     log::warn!("failed to parse {:?}: {}", path, err);
                 }
             }
-        } else if mime == "application/x-executable" || mime == "application/vnd.appimage" {
-            // Try opening executable
-            let mut command = std::process::Command::new(path);
-            match spawn_detached(&mut command) {
-                Ok(()) => {}
-                Err(err) => match err.kind() {
-                    io::ErrorKind::PermissionDenied => {
-                        // If permission is denied, try marking as executable, then running
-                        self.dialog_pages
-                            .push_back(DialogPage::SetExecutableAndLaunch {
-                                path: path.to_path_buf(),
-                            });
-                    }
-                    _ => {
-                        log::warn!("failed to execute {:?}: {}", path, err);
-                    }
-                },
+        } else if tool::is_runnable(path, &mime) {
+            let binary = tool::is_binary(&mime);
+            match self.config.run_choices.get(mime.essence_str()) {
+                Some(choice) => {
+                    return self.run_or_open(path, *choice);
+                }
+                None if self.config.confirm.execute => {
+                    self.dialog_pages.push_back(DialogPage::ConfirmExecute {
+                        path: path.to_path_buf(),
+                        binary,
+                        remember: false,
+                    });
+                    return Task::none();
+                }
+                // Without asking, binaries are run and scripts are opened
+                None if binary => {
+                    return self.run_or_open(path, RunChoice::Run);
+                }
+                None => {}
             }
-            return;
         }
 
+        self.open_with_default_app(path);
+        Task::none()
+    }
+
+    fn open_with_default_app(&mut self, path: &Path) {
+        let mime = mime_icon::mime_for_path(path);
         // Try mime apps, which should be faster than xdg-open
         for app in self.mime_app_cache.get(&mime) {
-            let Some(mut command) = app.command(Some(path.clone().into())) else {
+            let Some(mut command) = app.command(Some(path.as_os_str().to_owned())) else {
                 continue;
             };
             match spawn_detached(&mut command) {
                 Ok(()) => {
-                    let _ = recently_used_xbel::update_recently_used(
-                        path,
-                        App::APP_ID.to_string(),
-                        "commander".to_string(),
-                        None,
-                    );
+                    recents::add(path, &app.name, app.exec.as_deref().unwrap_or_default());
                     return;
                 }
                 Err(err) => {
@@ -888,12 +1484,7 @@ impl App {
         // Fall back to using open crate
         match open::that_detached(path) {
             Ok(()) => {
-                let _ = recently_used_xbel::update_recently_used(
-                    path,
-                    App::APP_ID.to_string(),
-                    "commander".to_string(),
-                    None,
-                );
+                recents::add(path, App::APP_ID, "commander");
             }
             Err(err) => {
                 log::warn!("failed to open {:?}: {}", path, err);
@@ -901,6 +1492,134 @@ impl App {
         }
     }
 
+    /// Open a text file in a window of the built-in editor
+    fn open_editor(&mut self, path: &Path) -> Result<Task<Message>, String> {
+        let editor = Editor::new(path)?;
+        let settings = window::Settings {
+            decorations: true,
+            min_size: Some(Size::new(480.0, 320.0)),
+            resizable: true,
+            size: Size::new(900.0, 700.0),
+            ..Default::default()
+        };
+        let (id, command) = window::open(settings);
+        let title = editor.title();
+        self.windows.insert(id, WindowKind::Editor);
+        self.editors.insert(id, editor);
+        Ok(Task::batch([
+            command.map(|_id| message::none()),
+            self.set_window_title(title, id),
+        ]))
+    }
+
+    /// Remembered run choices with `choice` for the type of `path`
+    fn run_choices_with(&self, path: &Path, choice: RunChoice) -> BTreeMap<String, RunChoice> {
+        let mut run_choices = self.config.run_choices.clone();
+        run_choices.insert(
+            mime_icon::mime_for_path(path).essence_str().to_string(),
+            choice,
+        );
+        run_choices
+    }
+
+    fn run_or_open(&mut self, path: &Path, choice: RunChoice) -> Task<Message> {
+        match choice {
+            RunChoice::Run => {
+                self.launch_executable(path);
+                Task::none()
+            }
+            RunChoice::RunInTerminal => self.run_in_terminal(path),
+            RunChoice::Open => match self.open_editor(path) {
+                Ok(task) => task,
+                Err(err) => {
+                    log::info!("not using the editor: {}", err);
+                    self.open_with_default_app(path);
+                    Task::none()
+                }
+            },
+        }
+    }
+
+    /// Run a program or script in the embedded terminal, from its own directory
+    fn run_in_terminal(&mut self, path: &Path) -> Task<Message> {
+        let mime = mime_icon::mime_for_path(path);
+        let Some(command) = tool::run_command(path, &mime) else {
+            log::warn!("failed to run {:?}: not executable", path);
+            return Task::none();
+        };
+        let (Some(dir), Some(mut args)) = (path.parent(), shlex::split(&command)) else {
+            return Task::none();
+        };
+        // Relative programs are ambiguous for a process started in another folder
+        if let Some(file) = args.last_mut() {
+            *file = path.to_string_lossy().to_string();
+        }
+        // The command gets its own terminal tab, so nothing is typed into a running program
+        let pane = self.pane_model.pane_by_type[&PaneType::TerminalPane];
+        let task = self.create_and_focus_new_terminal(
+            pane,
+            self.config.default_profile,
+            Some((dir.to_path_buf(), args)),
+        );
+        // Show the terminal so that the output is visible
+        if !self.config.show_embedded_terminal {
+            return Task::batch([task, self.update(Message::ShowEmbeddedTerminal(true))]);
+        }
+        task
+    }
+
+    /// Open several files, grouped by their default app so that each app is launched once
+    fn open_files(&mut self, paths: Vec<PathBuf>) -> Task<Message> {
+        let mut tasks = Vec::new();
+        let mut groups: Vec<(mime_app::MimeApp, Vec<PathBuf>)> = Vec::new();
+        for path in paths {
+            let mime = mime_icon::mime_for_path(&path);
+            // Desktop entries and runnable files are handled one by one, like a single file
+            let app_opt = if !self.config.open_together
+                || mime == "application/x-desktop"
+                || tool::is_runnable(&path, &mime)
+            {
+                None
+            } else {
+                self.mime_app_cache
+                    .get(&mime)
+                    .iter()
+                    .find(|app| app.exec.is_some())
+                    .cloned()
+            };
+            match app_opt {
+                Some(app) => match groups.iter_mut().find(|(group, _)| group.id == app.id) {
+                    Some((_, group_paths)) => group_paths.push(path),
+                    None => groups.push((app, vec![path])),
+                },
+                None => tasks.push(self.open_file(&path)),
+            }
+        }
+
+        for (app, paths) in groups {
+            let args: Vec<_> = paths
+                .iter()
+                .map(|path| path.clone().into_os_string())
+                .collect();
+            let Some(commands) = app.commands(&args) else {
+                // Fall back to the other apps and the open crate
+                for path in paths.iter() {
+                    self.open_with_default_app(path);
+                }
+                continue;
+            };
+            for mut command in commands {
+                if let Err(err) = spawn_detached(&mut command) {
+                    log::warn!("failed to open {:?} with {:?}: {}", paths, app.id, err);
+                }
+            }
+            for path in paths.iter() {
+                recents::add(path, &app.name, app.exec.as_deref().unwrap_or_default());
+            }
+        }
+        Task::batch(tasks)
+    }
+
     #[cfg(feature = "desktop")]
     fn exec_entry_action(entry: cosmic::desktop::DesktopEntryData, action: usize) {
         if let Some(action) = entry.desktop_actions.get(action) {
@@ -1019,17 +1738,18 @@ impl App {
 
     fn open_tab_entity_left(
         &mut self,
-        location: Location1,
+        location: Location,
         activate: bool,
         selection_paths: Option<Vec<PathBuf>>,
     ) -> (Entity, Task<Message>) {
         let tabconfig = self.config.tab_left;
-        let mut tab = Tab1::new(location.clone(), tabconfig);
+        let mut tab = Tab::new(location.clone(), tabconfig);
         tab.mode = match self.mode {
-            Mode::App => tab1::Mode::App,
+            Mode::App => tab::Mode::App,
             Mode::Desktop => {
-                tab.config.view = tab1::View::Grid;
-                tab1::Mode::Desktop
+                tab.config.view = tab::View::Grid;
+                tab.desktop_layouts = self.config.desktop_layouts.clone();
+                tab::Mode::Desktop
             }
         };
         let entity;
@@ -1057,19 +1777,21 @@ impl App {
 
     fn open_tab_entity_right(
         &mut self,
-        location: Location2,
+        location: Location,
         activate: bool,
         selection_paths: Option<Vec<PathBuf>>,
     ) -> (Entity, Task<Message>) {
         let mut tab;
         let tabconfig = self.config.tab_right;
-        tab = Tab2::new(location.clone(), tabconfig);
+        tab = Tab::new(location.clone(), tabconfig);
+        tab.pane = PaneType::RightPane;
 
         tab.mode = match self.mode {
-            Mode::App => tab2::Mode::App,
+            Mode::App => tab::Mode::App,
             Mode::Desktop => {
-                tab.config.view = tab2::View::Grid;
-                tab2::Mode::Desktop
+                tab.config.view = tab::View::Grid;
+                tab.desktop_layouts = self.config.desktop_layouts.clone();
+                tab::Mode::Desktop
             }
         };
         let entity;
@@ -1097,7 +1819,7 @@ impl App {
 
     fn open_tab(
         &mut self,
-        location: Location1,
+        location: Location,
         activate: bool,
         selection_paths: Option<Vec<PathBuf>>,
     ) -> Task<Message> {
@@ -1108,7 +1830,7 @@ impl App {
 
     fn open_tab_right(
         &mut self,
-        location: Location2,
+        location: Location,
         activate: bool,
         selection_paths: Option<Vec<PathBuf>>,
     ) -> Task<Message> {
@@ -1125,73 +1847,459 @@ impl App {
         self.active_panel = PaneType::RightPane;
     }
 
-    fn operation(&mut self, operation: Operation) {
-        let id = self.pending_operation_id;
-        self.pending_operation_id += 1;
-        if operation.show_progress_notification() {
-            self.progress_operations.insert(id);
-        }
-        /*        if self.config.queue_file_operations {
-            match operation {
-                Operation::Copy { to, paths } => {
-                    self.fileops.insert(id, (Operation::Copy { to, paths }, Controller::default()));
-                }
-                Operation::Move { to, paths } => {
-                    self.fileops.insert(id, (Operation::Move { to, paths }, Controller::default()));
+    fn tab_state(&self, pane_type: PaneType, entity: Entity) -> TabState {
+        let state_opt = match pane_type {
+            PaneType::LeftPane => self.tab_model1.data::<Tab>(entity).map(|tab| TabState {
+                pinned: tab.pinned,
+                locked: tab.locked,
+            }),
+            PaneType::RightPane => self.tab_model2.data::<Tab>(entity).map(|tab| TabState {
+                pinned: tab.pinned,
+                locked: tab.locked,
+            }),
+            _ => None,
+        };
+        state_opt.unwrap_or_default()
+    }
+
+    /// Pin or lock a tab, pinned tabs have no close button and an icon instead
+    fn set_tab_state(&mut self, pane_type: PaneType, entity: Entity, state: TabState) {
+        let tab_model = match pane_type {
+            PaneType::LeftPane => {
+                if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
+                    tab.pinned = state.pinned;
+                    tab.locked = state.locked;
                 }
-                _ => {
-                    self.pending_operations
-                    .insert(id, (operation, Controller::default()));
+                &mut self.tab_model1
+            }
+            PaneType::RightPane => {
+                if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
+                    tab.pinned = state.pinned;
+                    tab.locked = state.locked;
                 }
+                &mut self.tab_model2
             }
-        } else {*/
-        self.pending_operations
-            .insert(id, (operation, Controller::default()));
-        //}
-    }
-
-    fn remove_window(&mut self, id: &window::Id) {
-        if let Some(WindowKind::Desktop(entity)) = self.windows.remove(id) {
-            // Remove the tab from the tab model
-            if self.active_panel == PaneType::LeftPane {
-                self.tab_model1.remove(entity);
-            } else {
-                self.tab_model2.remove(entity);
+            _ => return,
+        };
+        tab_model.closable_set(entity, !state.pinned);
+        let icon_name_opt = if state.pinned {
+            Some("view-pin-symbolic")
+        } else if state.locked {
+            Some("changes-prevent-symbolic")
+        } else {
+            None
+        };
+        match icon_name_opt {
+            Some(icon_name) => {
+                tab_model.icon_set(entity, widget::icon::from_name(icon_name).size(16).icon());
+            }
+            None => {
+                tab_model.icon_remove(entity);
             }
         }
     }
 
-    fn rescan_operation_selection(&mut self, op_sel: OperationSelection) -> Task<Message> {
-        log::info!("rescan_operation_selection {:?}", op_sel);
-        if self.active_panel == PaneType::LeftPane {
-            let entity = self.tab_model1.active();
-            if let Some(tab) = self.tab_model1.data::<Tab1>(entity) {
-                let Some(items) = tab.items_opt() else {
-                    return Task::none();
+    /// Count a visit of a directory for the quick jump and the location history of the pane
+    fn visit_dir(&mut self, path: &Path, pane: PaneType) {
+        let mut recent_dirs = self.config.recent_dirs.clone();
+        frecency::visit(&mut recent_dirs, path, frecency::now());
+        let mut pane_history = self.pane_history(pane).to_vec();
+        frecency::visit_pane_history(&mut pane_history, path);
+        match &self.config_handler {
+            Some(config_handler) => {
+                if let Err(err) = self.config.set_recent_dirs(config_handler, recent_dirs) {
+                    log::warn!("failed to save config \"recent_dirs\": {}", err);
+                }
+                let result = if pane == PaneType::RightPane {
+                    self.config
+                        .set_pane_history_right(config_handler, pane_history)
+                } else {
+                    self.config
+                        .set_pane_history_left(config_handler, pane_history)
                 };
-                for item in items.iter() {
-                    if item.selected {
-                        if let Some(path) = item.path_opt() {
-                            if op_sel.selected.contains(path) || op_sel.ignored.contains(path) {
-                                // Ignore if path in selected or ignored paths
-                                continue;
-                            }
-                        }
-
-                        // Return if there is a previous selection not matching
-                        return Task::none();
-                    }
+                if let Err(err) = result {
+                    log::warn!("failed to save config \"pane_history\": {}", err);
+                }
+            }
+            None => {
+                self.config.recent_dirs = recent_dirs;
+                if pane == PaneType::RightPane {
+                    self.config.pane_history_right = pane_history;
+                } else {
+                    self.config.pane_history_left = pane_history;
                 }
-                return self.update_tab_left(entity, tab.location.clone(), Some(op_sel.selected));
-            } else {
-                return Task::none();
             }
+        }
+    }
+
+    /// Directories last visited in the pane, the latest first
+    fn pane_history(&self, pane: PaneType) -> &[PathBuf] {
+        if pane == PaneType::RightPane {
+            &self.config.pane_history_right
         } else {
-            let entity = self.tab_model2.active();
-            if let Some(tab) = self.tab_model2.data::<Tab2>(entity) {
-                let Some(items) = tab.items_opt() else {
-                    return Task::none();
-                };
+            &self.config.pane_history_left
+        }
+    }
+
+    /// Button at the end of the tab bar with a dropdown of the directories last visited in
+    /// the pane
+    fn pane_history_button(&self, pane: PaneType) -> Element<Message> {
+        let space_xxs = theme::active().cosmic().spacing.space_xxs;
+        let history = self.pane_history(pane);
+        let selected_opt = self
+            .pane_history_menu
+            .filter(|(menu_pane, _)| *menu_pane == pane)
+            .map(|(_, index)| index);
+        let mut button = widget::button::custom(
+            widget::icon::from_name("document-open-recent-symbolic").size(16),
+        )
+        .padding(space_xxs)
+        .class(theme::Button::Icon);
+        if !history.is_empty() {
+            button = button.on_press(Message::PaneHistoryMenu(
+                selected_opt.is_none().then_some(pane),
+            ));
+        }
+        let mut popover = widget::popover(button).position(widget::popover::Position::Bottom);
+        if let Some(selected) = selected_opt {
+            popover = popover.popup(menu::pane_history_menu(pane, history, selected));
+        }
+        popover.into()
+    }
+
+    /// Directories of the history, the sidebar and the open tabs matching `search`
+    fn quick_jump_results(&self, search: &str) -> Vec<PathBuf> {
+        let now = frecency::now();
+        let mut candidates = BTreeMap::new();
+        for dir in self.config.recent_dirs.iter() {
+            candidates.insert(dir.path.clone(), dir.score(now));
+        }
+        for favorite in self.config.favorites.iter() {
+            if let Some(path) = favorite.path_opt() {
+                candidates.entry(path).or_insert(0.0);
+            }
+        }
+        for entity in self.tab_model1.iter() {
+            if let Some(path) = self
+                .tab_model1
+                .data::<Tab>(entity)
+                .and_then(|tab| tab.location.path_opt())
+            {
+                candidates.entry(path.clone()).or_insert(0.0);
+            }
+        }
+        for entity in self.tab_model2.iter() {
+            if let Some(path) = self
+                .tab_model2
+                .data::<Tab>(entity)
+                .and_then(|tab| tab.location.path_opt())
+            {
+                candidates.entry(path.clone()).or_insert(0.0);
+            }
+        }
+        frecency::search(candidates.into_iter().collect(), search)
+    }
+
+    /// Remember the location of a tab that is closed, so it can be reopened
+    fn remember_closed_tab(&mut self, pane_type: PaneType, entity: Entity) {
+        let location_opt = match pane_type {
+            PaneType::LeftPane => self
+                .tab_model1
+                .data::<Tab>(entity)
+                .map(|tab| tab.location.clone()),
+            PaneType::RightPane => self
+                .tab_model2
+                .data::<Tab>(entity)
+                .map(|tab| tab.location.clone()),
+            _ => None,
+        };
+        if let Some(location) = location_opt {
+            self.closed_tabs.push_back((pane_type, location));
+            while self.closed_tabs.len() > MAX_CLOSED_TABS {
+                self.closed_tabs.pop_front();
+            }
+        }
+    }
+
+    /// Move the active tab of the active pane one position back or forward
+    fn move_tab_position(&mut self, forward: bool) {
+        let tab_model = if self.active_panel == PaneType::LeftPane {
+            &mut self.tab_model1
+        } else {
+            &mut self.tab_model2
+        };
+        let entity = tab_model.active();
+        let Some(position) = tab_model.position(entity) else {
+            return;
+        };
+        let new_position = if forward {
+            position + 1
+        } else if position > 0 {
+            position - 1
+        } else {
+            return;
+        };
+        if tab_model.entity_at(new_position).is_some() {
+            tab_model.position_set(entity, new_position);
+        }
+    }
+
+    /// Open the active tab in the other pane, and remove it from the active pane if it is moved
+    fn copy_tab_to_other_pane(&mut self, remove: bool) -> Task<Message> {
+        if self.active_panel == PaneType::LeftPane {
+            let entity = self.tab_model1.active();
+            let Some(location) = self
+                .tab_model1
+                .data::<Tab>(entity)
+                .map(|tab| tab.location.clone())
+            else {
+                return Task::none();
+            };
+            // The last tab stays, as closing it closes the window
+            if remove && self.tab_model1.iter().nth(1).is_some() {
+                if let Some(position) = self.tab_model1.position(entity) {
+                    let new_position = if position > 0 {
+                        position - 1
+                    } else {
+                        position + 1
+                    };
+                    self.tab_model1.activate_position(new_position);
+                }
+                self.tab_model1.remove(entity);
+            }
+            let task = self.open_tab_right(location.clone(), true, None);
+            let _ = self.update(Message::StoreOpenPaths);
+            Task::batch([task, self.update_watcher_left()])
+        } else {
+            let entity = self.tab_model2.active();
+            let Some(location) = self
+                .tab_model2
+                .data::<Tab>(entity)
+                .map(|tab| tab.location.clone())
+            else {
+                return Task::none();
+            };
+            // The last tab stays, as closing it closes the window
+            if remove && self.tab_model2.iter().nth(1).is_some() {
+                if let Some(position) = self.tab_model2.position(entity) {
+                    let new_position = if position > 0 {
+                        position - 1
+                    } else {
+                        position + 1
+                    };
+                    self.tab_model2.activate_position(new_position);
+                }
+                self.tab_model2.remove(entity);
+            }
+            let task = self.open_tab(location.clone(), true, None);
+            let _ = self.update(Message::StoreOpenPaths);
+            Task::batch([task, self.update_watcher_right()])
+        }
+    }
+
+    /// Folders of all tabs of read-only panes, not only of the active ones
+    fn read_only_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        for pane_type in [PaneType::LeftPane, PaneType::RightPane] {
+            let tab_model = self.tab_model(pane_type);
+            for entity in tab_model.iter() {
+                if let Some(tab) = tab_model.data::<Tab>(entity) {
+                    if let Some(path) = tab.location.path_opt().filter(|_| tab.config.read_only) {
+                        dirs.push(path.clone());
+                    }
+                }
+            }
+        }
+        dirs
+    }
+
+    /// Folder of a read-only pane that the operation would change
+    fn read_only_dir(&self, operation: &Operation) -> Option<PathBuf> {
+        self.read_only_dirs()
+            .into_iter()
+            .find(|dir| operation.changes_dir(dir))
+    }
+
+    /// Folder of a read-only pane that contains the path
+    fn read_only_path(&self, path: &Path) -> Option<PathBuf> {
+        self.read_only_dirs()
+            .into_iter()
+            .find(|dir| path.starts_with(dir))
+    }
+
+    /// Actions that change files are refused in read-only panes, the same as their menu items
+    fn action_refused(&self, entity: Entity, action: &Action) -> bool {
+        let refused = action.changes_files()
+            && self
+                .tab_model(self.active_panel)
+                .data::<Tab>(entity)
+                .is_some_and(|tab| tab.config.read_only);
+        if refused {
+            log::info!("Cannot {:?} in a read-only pane.", action);
+        }
+        refused
+    }
+
+    fn operation(&mut self, operation: Operation) {
+        let id = self.pending_operation_id;
+        self.pending_operation_id += 1;
+        // Refused like a failed operation, so that it is explained in the same dialog
+        if let Some(dir) = self.read_only_dir(&operation) {
+            let err = OperationError::from_str(fl!(
+                "read-only-pane-error",
+                dir = dir.display().to_string()
+            ));
+            self.failed_operations
+                .insert(id, (operation, Controller::default(), err));
+            self.dialog_pages.push_back(DialogPage::FailedOperation(id));
+            return;
+        }
+        if operation.show_progress_notification() {
+            self.progress_operations.insert(id);
+        }
+        /*        if self.config.queue_file_operations {
+            match operation {
+                Operation::Copy { to, paths } => {
+                    self.fileops.insert(id, (Operation::Copy { to, paths }, Controller::default()));
+                }
+                Operation::Move { to, paths } => {
+                    self.fileops.insert(id, (Operation::Move { to, paths }, Controller::default()));
+                }
+                _ => {
+                    self.pending_operations
+                    .insert(id, (operation, Controller::default()));
+                }
+            }
+        } else {*/
+        self.pending_operations
+            .insert(id, (operation, Controller::default()));
+        self.operation_started.insert(id, Instant::now());
+        //}
+    }
+
+    /// Hide the progress in docks after the last operation, and show a notification with
+    /// the summary and body when the window is not focused
+    fn operation_finished(
+        &mut self,
+        id: u64,
+        summary_opt: Option<(String, String)>,
+    ) -> Task<Message> {
+        let mut commands = Vec::with_capacity(2);
+        let started_opt = self.operation_started.remove(&id);
+
+        #[cfg(feature = "dbus")]
+        if self.pending_operations.is_empty() {
+            commands.push(Task::perform(
+                async move {
+                    if let Err(err) = launcher_entry::update(None, 0).await {
+                        log::warn!("failed to update launcher entry: {}", err);
+                    }
+                    message::none()
+                },
+                |x| x,
+            ));
+        }
+
+        #[cfg(feature = "notify")]
+        if let Some((summary, mut body)) = summary_opt {
+            if !self.window_focused || self.window_id_opt.is_none() {
+                if let Some(started) = started_opt {
+                    body.push('\n');
+                    body.push_str(&fl!(
+                        "notification-duration",
+                        duration = format_duration(started.elapsed())
+                    ));
+                }
+                commands.push(Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            if let Err(err) = notify_rust::Notification::new()
+                                .summary(&summary)
+                                .body(&body)
+                                .icon(App::APP_ID)
+                                .show()
+                            {
+                                log::warn!("failed to create notification: {}", err);
+                            }
+                        })
+                        .await
+                        .unwrap();
+                        message::none()
+                    },
+                    |x| x,
+                ));
+            }
+        }
+        #[cfg(not(feature = "notify"))]
+        let _ = (summary_opt, started_opt);
+
+        Task::batch(commands)
+    }
+
+    /// Play the sound in the background if sounds are enabled
+    fn play_sound(&self, sound: Sound) -> Task<Message> {
+        if !self.config.sounds {
+            return Task::none();
+        }
+        let volume = self.config.sound_volume;
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    if let Err(err) = sound::play(sound, volume) {
+                        log::warn!("failed to play sound: {}", err);
+                    }
+                })
+                .await
+                .unwrap();
+                message::none()
+            },
+            |x| x,
+        )
+    }
+
+    fn remove_window(&mut self, id: &window::Id) {
+        self.editors.remove(id);
+        if let Some(WindowKind::Desktop(entity)) = self.windows.remove(id) {
+            // Remove the tab from the tab model
+            if self.active_panel == PaneType::LeftPane {
+                self.tab_model1.remove(entity);
+            } else {
+                self.tab_model2.remove(entity);
+            }
+        }
+    }
+
+    fn rescan_operation_selection(&mut self, op_sel: OperationSelection) -> Task<Message> {
+        log::info!("rescan_operation_selection {:?}", op_sel);
+        if self.active_panel == PaneType::LeftPane {
+            let entity = self.tab_model1.active();
+            if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
+                let Some(items) = tab.items_opt() else {
+                    return Task::none();
+                };
+                for item in items.iter() {
+                    if item.selected {
+                        if let Some(path) = item.path_opt() {
+                            if op_sel.selected.contains(path) || op_sel.ignored.contains(path) {
+                                // Ignore if path in selected or ignored paths
+                                continue;
+                            }
+                        }
+
+                        // Return if there is a previous selection not matching
+                        return Task::none();
+                    }
+                }
+                return self.update_tab_left(entity, tab.location.clone(), Some(op_sel.selected));
+            } else {
+                return Task::none();
+            }
+        } else {
+            let entity = self.tab_model2.active();
+            if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
+                let Some(items) = tab.items_opt() else {
+                    return Task::none();
+                };
                 for item in items.iter() {
                     if item.selected {
                         if let Some(path) = item.path_opt() {
@@ -1212,13 +2320,31 @@ impl App {
         }
     }
 
+    /// Rescan the active tab of the inactive pane and select the paths there
+    fn select_in_other_pane(&mut self, paths: Vec<PathBuf>) -> Task<Message> {
+        let pane = self.other_pane();
+        let entity = self.tab_model(pane).active();
+        let Some(location) = self
+            .tab_model(pane)
+            .data::<Tab>(entity)
+            .map(|tab| tab.location.clone())
+        else {
+            return Task::none();
+        };
+        if pane == PaneType::RightPane {
+            self.update_tab_right(entity, location, Some(paths))
+        } else {
+            self.update_tab_left(entity, location, Some(paths))
+        }
+    }
+
     fn update_tab_left(
         &mut self,
         entity: Entity,
-        location: Location1,
+        location: Location,
         selection_paths: Option<Vec<PathBuf>>,
     ) -> Task<Message> {
-        if let Location1::Search(_, term, ..) = location {
+        if let Location::Search(_, term, ..) = location {
             self.search_set(entity, Some(term), selection_paths)
         } else {
             self.rescan_tab_left(entity, location, selection_paths)
@@ -1228,10 +2354,10 @@ impl App {
     fn update_tab_right(
         &mut self,
         entity: Entity,
-        location: Location2,
+        location: Location,
         selection_paths: Option<Vec<PathBuf>>,
     ) -> Task<Message> {
-        if let Location2::Search(_, term, ..) = location {
+        if let Location::Search(_, term, ..) = location {
             self.search_set(entity, Some(term), selection_paths)
         } else {
             self.rescan_tab_right(entity, location, selection_paths)
@@ -1241,7 +2367,7 @@ impl App {
     fn rescan_tab_left(
         &mut self,
         entity: Entity,
-        location: Location1,
+        location: Location,
         selection_paths: Option<Vec<PathBuf>>,
     ) -> Task<Message> {
         log::info!("rescan_tab {entity:?} {location:?} {selection_paths:?}");
@@ -1271,7 +2397,7 @@ impl App {
     fn rescan_tab_right(
         &mut self,
         entity: Entity,
-        location: Location2,
+        location: Location,
         selection_paths: Option<Vec<PathBuf>>,
     ) -> Task<Message> {
         log::info!("rescan_tab {entity:?} {location:?} {selection_paths:?}");
@@ -1298,15 +2424,53 @@ impl App {
         )
     }
 
+    /// Scan the locations of all tabs in both panes again
+    fn rescan_tabs(&mut self) -> Task<Message> {
+        let tabs_left: Vec<_> = self
+            .tab_model1
+            .iter()
+            .filter_map(|entity| {
+                let tab = self.tab_model1.data::<Tab>(entity)?;
+                Some((entity, tab.location.clone()))
+            })
+            .collect();
+        let tabs_right: Vec<_> = self
+            .tab_model2
+            .iter()
+            .filter_map(|entity| {
+                let tab = self.tab_model2.data::<Tab>(entity)?;
+                Some((entity, tab.location.clone()))
+            })
+            .collect();
+        let mut commands = Vec::with_capacity(tabs_left.len() + tabs_right.len());
+        for (entity, location) in tabs_left {
+            commands.push(self.update_tab_left(entity, location, None));
+        }
+        for (entity, location) in tabs_right {
+            commands.push(self.update_tab_right(entity, location, None));
+        }
+        Task::batch(commands)
+    }
+
+    /// Valid glob patterns from the config for names that are hidden
+    fn hide_patterns(&self) -> Vec<glob::Pattern> {
+        self.config
+            .hide_patterns
+            .iter()
+            .filter(|pattern| !pattern.is_empty())
+            .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+            .collect()
+    }
+
     fn rescan_trash(&mut self) -> Task<Message> {
         if self.active_panel == PaneType::LeftPane {
             let mut needs_reload = Vec::new();
             let entities: Vec<_> = self.tab_model1.iter().collect();
             for entity in entities {
-                if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                     {
-                        if let Location1::Trash = &tab.location {
-                            needs_reload.push((entity, Location1::Trash));
+                        if let Location::Trash = &tab.location {
+                            needs_reload.push((entity, Location::Trash));
                         }
                     }
                 }
@@ -1320,10 +2484,10 @@ impl App {
             let mut needs_reload = Vec::new();
             let entities: Vec<_> = self.tab_model2.iter().collect();
             for entity in entities {
-                if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                     {
-                        if let Location2::Trash = &tab.location {
-                            needs_reload.push((entity, Location2::Trash));
+                        if let Location::Trash = &tab.location {
+                            needs_reload.push((entity, Location::Trash));
                         }
                     }
                 }
@@ -1339,9 +2503,9 @@ impl App {
     fn search_get(&self) -> Option<&str> {
         if self.active_panel == PaneType::LeftPane {
             let entity = self.tab_model1.active();
-            if let Some(tab) = self.tab_model1.data::<Tab1>(entity) {
+            if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
                 match &tab.location {
-                    Location1::Search(_, term, ..) => Some(term),
+                    Location::Search(_, term, ..) => Some(term),
                     _ => None,
                 }
             } else {
@@ -1349,9 +2513,9 @@ impl App {
             }
         } else {
             let entity = self.tab_model2.active();
-            if let Some(tab) = self.tab_model2.data::<Tab2>(entity) {
+            if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
                 match &tab.location {
-                    Location2::Search(_, term, ..) => Some(term),
+                    Location::Search(_, term, ..) => Some(term),
                     _ => None,
                 }
             } else {
@@ -1378,23 +2542,17 @@ impl App {
     ) -> Task<Message> {
         if self.active_panel == PaneType::LeftPane {
             let mut title_location_opt = None;
-            if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+            if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                 let location_opt = match term_opt {
-                    Some(term) => match &tab.location {
-                        Location1::Path(path) | Location1::Search(path, ..) => Some((
-                            Location1::Search(
-                                path.to_path_buf(),
-                                term,
-                                tab.config.show_hidden,
-                                Instant::now(),
-                            ),
+                    Some(term) => tab.location.search_path().map(|path| {
+                        (
+                            Location::Search(path, term, tab.config.show_hidden, Instant::now()),
                             true,
-                        )),
-                        _ => None,
-                    },
+                        )
+                    }),
                     None => match &tab.location {
-                        Location1::Search(path, ..) => {
-                            Some((Location1::Path(path.to_path_buf()), false))
+                        Location::Search(path, ..) => {
+                            Some((Location::Path(path.to_path_buf()), false))
                         }
                         _ => None,
                     },
@@ -1419,23 +2577,17 @@ impl App {
             }
         } else {
             let mut title_location_opt = None;
-            if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+            if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                 let location_opt = match term_opt {
-                    Some(term) => match &tab.location {
-                        Location2::Path(path) | Location2::Search(path, ..) => Some((
-                            Location2::Search(
-                                path.to_path_buf(),
-                                term,
-                                tab.config.show_hidden,
-                                Instant::now(),
-                            ),
+                    Some(term) => tab.location.search_path().map(|path| {
+                        (
+                            Location::Search(path, term, tab.config.show_hidden, Instant::now()),
                             true,
-                        )),
-                        _ => None,
-                    },
+                        )
+                    }),
                     None => match &tab.location {
-                        Location2::Search(path, ..) => {
-                            Some((Location2::Path(path.to_path_buf()), false))
+                        Location::Search(path, ..) => {
+                            Some((Location::Path(path.to_path_buf()), false))
                         }
                         _ => None,
                     },
@@ -1463,45 +2615,240 @@ impl App {
         Task::none()
     }
 
-    fn selected_paths(&self, entity_opt: Option<Entity>) -> Vec<PathBuf> {
-        let mut paths = Vec::new();
-        let entity = match entity_opt {
-            Some(entity) => entity,
-            None => {
-                if self.active_panel == PaneType::LeftPane {
-                    self.tab_model1.active()
-                } else {
-                    self.tab_model2.active()
-                }
+    /// Move the keyboard focus to the left pane, the right pane or the terminal
+    fn focus_pane(&mut self, pane_type: PaneType) -> Task<Message> {
+        let Some(pane) = self.pane_model.pane_by_type.get(&pane_type).copied() else {
+            return Task::none();
+        };
+        self.pane_model.focus = pane;
+        match pane_type {
+            PaneType::TerminalPane => widget::text_input::focus(self.terminal_id.clone()),
+            _ => {
+                self.active_panel = pane_type;
+                // Focusing an id without a widget takes the keyboard focus from the terminal
+                widget::text_input::focus(widget::Id::unique())
             }
+        }
+    }
+
+    /// Terminal of the active tab of the terminal pane
+    fn terminal(&self) -> Option<&Mutex<crate::terminal::Terminal>> {
+        self.terminal_model
+            .active_data::<Mutex<crate::terminal::Terminal>>()
+    }
+
+    /// Handle a key of the vi navigation mode, returns None if the regular key bindings apply
+    fn vi_key(&mut self, entity: Entity, key: &Key) -> Option<Task<Message>> {
+        let c = match key {
+            Key::Character(c) => c.as_str(),
+            Key::Named(Named::Escape) if self.vi_visual || !self.vi_pending.is_empty() => {
+                self.vi_pending.clear();
+                self.vi_visual = false;
+                return Some(Task::none());
+            }
+            _ => return None,
         };
-        if self.active_panel == PaneType::LeftPane {
-            if let Some(tab) = self.tab_model1.data::<Tab1>(entity) {
-                for location in tab.selected_locations() {
-                    if let Some(path) = location.path_opt() {
-                        paths.push(path.to_path_buf());
-                    }
+        self.vi_pending.push_str(c);
+        let mut vi_key_opt = key_binds_vi(&self.vi_pending, self.vi_visual);
+        if vi_key_opt.is_none() && self.vi_pending.len() > c.len() {
+            // Start over with the last key if the sequence is unknown
+            self.vi_pending = c.to_string();
+            vi_key_opt = key_binds_vi(&self.vi_pending, self.vi_visual);
+        }
+        if vi_key_opt != Some(ViKey::Pending) {
+            self.vi_pending.clear();
+        }
+        match vi_key_opt? {
+            ViKey::Action(action) => {
+                if self.action_refused(entity, &action) {
+                    return Some(Task::none());
+                }
+                let visual = self.vi_visual;
+                if matches!(action, Action::Copy | Action::Cut) {
+                    self.vi_visual = false;
+                }
+                if visual {
+                    // Moving in the visual mode works like moving with Shift held
+                    let modifiers = self.modifiers;
+                    self.modifiers = Modifiers::SHIFT;
+                    let task = self.update(action.message(Some(entity)));
+                    self.modifiers = modifiers;
+                    Some(task)
+                } else {
+                    Some(self.update(action.message(Some(entity))))
                 }
             }
-        } else {
-            if let Some(tab) = self.tab_model2.data::<Tab2>(entity) {
-                for location in tab.selected_locations() {
-                    if let Some(path) = location.path_opt() {
-                        paths.push(path.to_path_buf());
-                    }
-                }
+            ViKey::Pending => Some(Task::none()),
+            ViKey::ToggleVisual => {
+                self.vi_visual = !self.vi_visual;
+                Some(Task::none())
             }
         }
-        paths
     }
 
-    fn pane_setup(
-        &mut self,
-        show_button_row: bool,
+    /// Run a tools menu or plugin command line on the selected items
+    fn run_command_line(&self, name: &str, command_line: &str, terminal: bool) {
+        let (dir_opt, other_dir_opt) = self.pane_dirs();
+        let Some(dir) = dir_opt else {
+            log::warn!("failed to run {:?}: no current directory", name);
+            return;
+        };
+        let files = self.selected_paths(None);
+        let Some(args) = tool::expand(command_line, &files, &dir, other_dir_opt.as_deref()) else {
+            log::warn!(
+                "failed to run {:?}: invalid command {:?}",
+                name,
+                command_line
+            );
+            return;
+        };
+        match self.terminal() {
+            Some(terminal_mutex) if terminal => {
+                let dir = dir.to_string_lossy();
+                match shlex::try_join(args.iter().map(String::as_str))
+                    .and_then(|command| Ok((shlex::try_quote(&dir)?, command)))
+                {
+                    Ok((dir, command)) => {
+                        let terminal = terminal_mutex.lock().unwrap();
+                        terminal.input_scroll(format!("cd {} && {}\r", dir, command).into_bytes());
+                    }
+                    Err(err) => {
+                        log::warn!("failed to run {:?}: {}", name, err);
+                    }
+                }
+            }
+            _ => {
+                let mut command = process::Command::new(&args[0]);
+                command.args(&args[1..]).current_dir(&dir);
+                if let Err(err) = spawn_detached(&mut command) {
+                    log::warn!("failed to run {:?}: {}", name, err);
+                }
+            }
+        }
+    }
+
+    /// Files to compare, either two selected in the active pane or one selected in each pane.
+    /// The file of the left pane comes first.
+    fn compare_paths(&self, entity_opt: Option<Entity>) -> Option<(PathBuf, PathBuf)> {
+        let paths = self.selected_paths(entity_opt);
+        match paths.as_slice() {
+            [left, right] => Some((left.clone(), right.clone())),
+            [path] => {
+                let mut other_paths = Vec::new();
+                if self.active_panel == PaneType::LeftPane {
+                    if let Some(tab) = self.tab_model2.data::<Tab>(self.tab_model2.active()) {
+                        for location in tab.selected_locations() {
+                            other_paths.extend(location.path_opt().cloned());
+                        }
+                    }
+                } else {
+                    if let Some(tab) = self.tab_model1.data::<Tab>(self.tab_model1.active()) {
+                        for location in tab.selected_locations() {
+                            other_paths.extend(location.path_opt().cloned());
+                        }
+                    }
+                }
+                match other_paths.as_slice() {
+                    [other_path] if self.active_panel == PaneType::LeftPane => {
+                        Some((path.clone(), other_path.clone()))
+                    }
+                    [other_path] => Some((other_path.clone(), path.clone())),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Directories of the active and the other pane
+    fn pane_dirs(&self) -> (Option<PathBuf>, Option<PathBuf>) {
+        let left_opt = self
+            .tab_model1
+            .data::<Tab>(self.tab_model1.active())
+            .and_then(|tab| tab.location.path_opt())
+            .map(|path| path.to_path_buf());
+        let right_opt = self
+            .tab_model2
+            .data::<Tab>(self.tab_model2.active())
+            .and_then(|tab| tab.location.path_opt())
+            .map(|path| path.to_path_buf());
+        if self.active_panel == PaneType::LeftPane {
+            (left_opt, right_opt)
+        } else {
+            (right_opt, left_opt)
+        }
+    }
+
+    fn paste_as_new_file(
+        &mut self,
+        parent: PathBuf,
+        name: String,
+        contents: Vec<u8>,
+    ) -> Task<Message> {
+        self.dialog_pages.push_back(DialogPage::NewItem {
+            parent,
+            name,
+            dir: false,
+            contents: Some(contents),
+        });
+        widget::text_input::focus(self.dialog_text_input.clone())
+    }
+
+    /// On the desktop only the content of the desktop folder is moved to the trash,
+    /// never the computer, home or drive icons shown next to it
+    fn trashable_paths(&self, mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+        if matches!(self.mode, Mode::Desktop) {
+            let desktop_dir = crate::desktop_dir();
+            paths.retain(|path| path.parent() == Some(desktop_dir.as_path()));
+        }
+        paths
+    }
+
+    fn selected_paths(&self, entity_opt: Option<Entity>) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        let entity = match entity_opt {
+            Some(entity) => entity,
+            None => {
+                if self.active_panel == PaneType::LeftPane {
+                    self.tab_model1.active()
+                } else {
+                    self.tab_model2.active()
+                }
+            }
+        };
+        if self.active_panel == PaneType::LeftPane {
+            if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
+                for location in tab.selected_locations() {
+                    if let Some(path) = location.path_opt() {
+                        paths.push(path.to_path_buf());
+                    }
+                }
+            }
+        } else {
+            if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
+                for location in tab.selected_locations() {
+                    if let Some(path) = location.path_opt() {
+                        paths.push(path.to_path_buf());
+                    }
+                }
+            }
+        }
+        paths
+    }
+
+    fn pane_setup(
+        &mut self,
+        show_button_row: bool,
         show_embedded_terminal: bool,
         show_second_panel: bool,
     ) {
         let pane = self.pane_model.first_pane;
+        // The ratio of the terminal split is the height of the panes above the terminal
+        let terminal_height = self.config.terminal_height;
+        let terminal_ratio = |default_height: u16| {
+            let height = terminal_height.unwrap_or(default_height);
+            1.0 - f32::from(height.clamp(5, 95)) / 100.0
+        };
         if show_button_row && show_embedded_terminal && show_second_panel {
             // full window
             if let Some((t, st)) = self.pane_model.panestates.split(
@@ -1510,7 +2857,7 @@ impl App {
                 segmented_button::ModelBuilder::default().build(),
             ) {
                 self.pane_model.panes_created += 1;
-                self.pane_model.panestates.resize(st, 0.75);
+                self.pane_model.panestates.resize(st, terminal_ratio(25));
                 if let Some((b, sb)) = self.pane_model.panestates.split(
                     pane_grid::Axis::Horizontal,
                     t,
@@ -1539,7 +2886,7 @@ impl App {
                 segmented_button::ModelBuilder::default().build(),
             ) {
                 self.pane_model.panes_created += 1;
-                self.pane_model.panestates.resize(st, 0.75);
+                self.pane_model.panestates.resize(st, terminal_ratio(25));
                 if let Some((b, sb)) = self.pane_model.panestates.split(
                     pane_grid::Axis::Horizontal,
                     t,
@@ -1559,7 +2906,7 @@ impl App {
                 segmented_button::ModelBuilder::default().build(),
             ) {
                 self.pane_model.panes_created += 1;
-                self.pane_model.panestates.resize(st, 0.75);
+                self.pane_model.panestates.resize(st, terminal_ratio(25));
                 self.pane_model
                     .insert(PaneType::TerminalPane, t, st, self.term_drag_id);
                 if let Some((r, sr)) = self.pane_model.panestates.split(
@@ -1597,7 +2944,7 @@ impl App {
                 segmented_button::ModelBuilder::default().build(),
             ) {
                 self.pane_model.panes_created += 1;
-                self.pane_model.panestates.resize(st, 0.85);
+                self.pane_model.panestates.resize(st, terminal_ratio(15));
                 self.pane_model
                     .insert(PaneType::TerminalPane, t, st, self.tab_drag_id_right);
             }
@@ -1628,12 +2975,18 @@ impl App {
     }
 
     fn update_config(&mut self) -> Task<Message> {
+        tab::set_format_config(self.config.formats);
         self.update_color_schemes();
-        let commands: Vec<_>;
+        self.update_terminal_colors();
         if self.show_button_row != self.config.show_button_row
             || self.show_embedded_terminal != self.config.show_embedded_terminal
             || self.show_second_panel != self.config.show_second_panel
         {
+            // Panes are split again from a single pane
+            self.pane_model = crate::commanderpanegrid::CommanderPaneGrid::new(
+                segmented_button::ModelBuilder::default().build(),
+                self.tab_drag_id_left,
+            );
             self.pane_setup(
                 self.config.show_button_row,
                 self.config.show_embedded_terminal,
@@ -1648,34 +3001,29 @@ impl App {
         }
         if self.active_panel == PaneType::LeftPane {
             self.update_nav_model_left();
-            // Tabs are collected first to placate the borrowck
-            let tabs: Vec<_> = self.tab_model1.iter().collect();
-            // Update main conf and each tab with the new config
-            commands = std::iter::once(cosmic::app::command::set_theme(
-                self.config.app_theme.theme(),
-            ))
-            .chain(tabs.into_iter().map(|entity| {
-                self.update(Message::TabMessage(
-                    Some(entity),
-                    tab1::Message::Config(self.config.tab_left),
-                ))
-            }))
-            .collect();
         } else {
             self.update_nav_model_right();
-            // Tabs are collected first to placate the borrowck
-            let tabs: Vec<_> = self.tab_model2.iter().collect();
-            // Update main conf and each tab with the new config
-            commands = std::iter::once(cosmic::app::command::set_theme(
-                self.config.app_theme.theme(),
-            ))
-            .chain(tabs.into_iter().map(|entity| {
-                self.update(Message::TabMessageRight(
-                    Some(entity),
-                    tab2::Message::Config(self.config.tab_right),
-                ))
-            }))
-            .collect();
+        }
+        // Tabs are collected first to placate the borrowck
+        let tabs_left: Vec<_> = self.tab_model1.iter().collect();
+        let tabs_right: Vec<_> = self.tab_model2.iter().collect();
+        // Update main conf and each tab of both panes with the new config, the panes
+        // can have different settings
+        let mut commands = Vec::with_capacity(1 + tabs_left.len() + tabs_right.len());
+        commands.push(cosmic::app::command::set_theme(
+            self.config.theme(self.system_prefer_dark),
+        ));
+        for entity in tabs_left {
+            commands.push(self.update(Message::TabMessage(
+                Some(entity),
+                tab::Message::Config(self.config.tab_left),
+            )));
+        }
+        for entity in tabs_right {
+            commands.push(self.update(Message::TabMessageRight(
+                Some(entity),
+                tab::Message::Config(self.config.tab_right),
+            )));
         }
         Task::batch(commands)
     }
@@ -1692,17 +3040,17 @@ impl App {
         for entity in entities {
             let mut needs_reload = Vec::new();
             if self.active_panel == PaneType::LeftPane {
-                if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
-                    if let Location1::Desktop(path, output, _) = &tab.location {
+                if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
+                    if let Location::Desktop(path, output, _) = &tab.location {
                         needs_reload.push((
                             entity,
-                            Location1::Desktop(path.clone(), output.clone(), self.config.desktop),
+                            Location::Desktop(path.clone(), output.clone(), self.config.desktop),
                         ));
                     };
                 }
                 let mut commands = Vec::with_capacity(needs_reload.len());
                 for (entity, location) in needs_reload {
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                    if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                         tab.location = location.clone();
                     }
                     commands.push(self.update_tab_left(entity, location, None));
@@ -1710,17 +3058,17 @@ impl App {
                 return Task::batch(commands);
             } else {
                 let mut needs_reload = Vec::new();
-                if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
-                    if let Location2::Desktop(path, output, _) = &tab.location {
+                if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
+                    if let Location::Desktop(path, output, _) = &tab.location {
                         needs_reload.push((
                             entity,
-                            Location2::Desktop(path.clone(), output.clone(), self.config.desktop),
+                            Location::Desktop(path.clone(), output.clone(), self.config.desktop),
                         ));
                     };
                 }
                 let mut commands = Vec::with_capacity(needs_reload.len());
                 for (entity, location) in needs_reload {
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                    if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                         tab.location = location.clone();
                     }
                     commands.push(self.update_tab_right(entity, location, None));
@@ -1731,10 +3079,10 @@ impl App {
         Task::none()
     }
 
-    fn activate_nav_model_location_left(&mut self, location: &Location1) {
+    fn activate_nav_model_location(&mut self, location: &Location) {
         let nav_bar_id = self.nav_model.iter().find(|&id| {
             self.nav_model
-                .data::<Location1>(id)
+                .data::<Location>(id)
                 .map(|l| l == location)
                 .unwrap_or_default()
         });
@@ -1747,30 +3095,13 @@ impl App {
         }
     }
 
-    fn activate_nav_model_location_right(&mut self, location: &Location2) {
-        let loc = convert_location2_to_location1(location);
-        let nav_bar_id = self.nav_model.iter().find(|&id| {
-            self.nav_model
-                .data::<Location1>(id)
-                .map(|l| *l == loc)
-                .unwrap_or_default()
-        });
-
-        if let Some(id) = nav_bar_id {
-            self.nav_model.activate(id);
-        } else {
-            let active = self.nav_model.active();
-            segmented_button::Selectable::deactivate(&mut self.nav_model, active);
-        }
-    }
-
     fn update_nav_model(&mut self) {
         let mut nav_model = segmented_button::ModelBuilder::default();
 
         nav_model = nav_model.insert(|b| {
             b.text(fl!("recents"))
                 .icon(widget::icon::from_name("document-open-recent-symbolic"))
-                .data(Location1::Recents)
+                .data(Location::Recents)
         });
 
         for (favorite_i, favorite) in self.config.favorites.iter().enumerate() {
@@ -1786,7 +3117,7 @@ impl App {
                     b.text(name.clone())
                         .icon(
                             widget::icon::icon(if path.is_dir() {
-                                tab1::folder_icon_symbolic(&path, 16)
+                                tab::folder_icon_symbolic(&path, 16)
                             } else {
                                 widget::icon::from_name("text-x-generic-symbolic")
                                     .size(16)
@@ -1794,72 +3125,83 @@ impl App {
                             })
                             .size(16),
                         )
-                        .data(Location1::Path(path.clone()))
+                        .data(Location::Path(path.clone()))
                         .data(FavoriteIndex(favorite_i))
                 });
             }
         }
 
+        // Bookmarks are shown after the favorites, separated by their folder
+        for (_folder, bookmarks) in config::bookmark_groups(&self.config.bookmarks) {
+            for (i, (_bookmark_i, bookmark)) in bookmarks.into_iter().enumerate() {
+                let name = bookmark.display_name();
+                let path = bookmark.path.clone();
+                let icon_name = bookmark.icon.clone();
+                nav_model = nav_model.insert(move |mut b| {
+                    b = b
+                        .text(name.clone())
+                        .icon(if icon_name.is_empty() {
+                            widget::icon::icon(tab::folder_icon_symbolic(&path, 16)).size(16)
+                        } else {
+                            widget::icon::from_name(icon_name.clone()).size(16).icon()
+                        })
+                        .data(Location::Path(path.clone()));
+                    if i == 0 {
+                        b = b.divider_above();
+                    }
+                    b
+                });
+            }
+        }
+
         nav_model = nav_model.insert(|b| {
             b.text(fl!("trash"))
-                .icon(widget::icon::icon(tab1::trash_icon_symbolic(16)))
-                .data(Location1::Trash)
+                .icon(widget::icon::icon(tab::trash_icon_symbolic(16)))
+                .data(Location::Trash)
                 .divider_above()
         });
 
-        if !MOUNTERS.is_empty() {
-            nav_model = nav_model.insert(|b| {
-                b.text(fl!("networks"))
-                    .icon(widget::icon::icon(
-                        widget::icon::from_name("network-workgroup-symbolic")
-                            .size(16)
-                            .handle(),
-                    ))
-                    .data(Location1::Network(
-                        "network:///".to_string(),
-                        fl!("networks"),
-                    ))
-                    .divider_above()
-            });
-        }
+        // Devices and networks are shown below the nav bar, see devices_view
+        self.nav_model = nav_model.build();
+    }
 
-        // Collect all mounter items
-        let mut nav_items = Vec::new();
-        for (key, items) in self.mounter_items.iter() {
-            for item in items.iter() {
-                nav_items.push((*key, item));
+    /// Free space of the mounted devices and of the file systems shown in the tabs,
+    /// network shares are skipped as they may block
+    fn update_device_usage(&mut self) {
+        let entities: Vec<_> = self.tab_model1.iter().collect();
+        for entity in entities {
+            if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
+                tab.update_filesystem();
             }
         }
-        // Sort by name lexically
-        nav_items.sort_by(|a, b| LANGUAGE_SORTER.compare(&a.1.name(), &b.1.name()));
-        // Add items to nav model
-        for (i, (key, item)) in nav_items.into_iter().enumerate() {
-            nav_model = nav_model.insert(|mut b| {
-                b = b.text(item.name()).data(MounterData(key, item.clone()));
-                if let Some(path) = item.path() {
-                    b = b.data(Location1::Path(path.clone()));
-                }
-                if let Some(icon) = item.icon(true) {
-                    b = b.icon(widget::icon::icon(icon).size(16));
-                }
-                if item.is_mounted() {
-                    b = b.closable();
+        let entities: Vec<_> = self.tab_model2.iter().collect();
+        for entity in entities {
+            if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
+                tab.update_filesystem();
+            }
+        }
+
+        self.device_usage.clear();
+        for items in self.mounter_items.values() {
+            for item in items.iter() {
+                if item.is_remote() {
+                    continue;
                 }
-                if i == 0 {
-                    b = b.divider_above();
+                if let Some(path) = item.path() {
+                    if let Some(usage) = mounter::disk_usage(&path) {
+                        self.device_usage.insert(path, usage);
+                    }
                 }
-                b
-            });
+            }
         }
-        self.nav_model = nav_model.build();
     }
 
     fn update_nav_model_left(&mut self) {
         self.update_nav_model();
 
         let tab_entity = self.tab_model1.active();
-        if let Some(tab) = self.tab_model1.data::<Tab1>(tab_entity) {
-            self.activate_nav_model_location_left(&tab.location.clone());
+        if let Some(tab) = self.tab_model1.data::<Tab>(tab_entity) {
+            self.activate_nav_model_location(&tab.location.clone());
         }
     }
 
@@ -1867,8 +3209,20 @@ impl App {
         self.update_nav_model();
 
         let tab_entity = self.tab_model2.active();
-        if let Some(tab) = self.tab_model2.data::<Tab2>(tab_entity) {
-            self.activate_nav_model_location_right(&tab.location.clone());
+        if let Some(tab) = self.tab_model2.data::<Tab>(tab_entity) {
+            self.activate_nav_model_location(&tab.location.clone());
+        }
+    }
+
+    /// Speak the selection of `tab_opt` if it is not the same as `before_opt`
+    fn announce_selection(&self, tab_opt: Option<&Tab>, before_opt: Option<String>) {
+        if !self.screen_reader {
+            return;
+        }
+        if let Some(selection) = tab_opt.and_then(selection_announcement) {
+            if Some(&selection) != before_opt.as_ref() {
+                a11y::announce(&selection, a11y::Priority::Text);
+            }
         }
     }
 
@@ -1921,9 +3275,21 @@ impl App {
         if let Some((mut watcher, old_paths)) = self.watcher_opt_left.take() {
             let mut new_paths = HashSet::new();
             for entity in self.tab_model1.iter() {
-                if let Some(tab) = self.tab_model1.data::<Tab1>(entity) {
-                    if let Location1::Path(path) = &tab.location {
-                        new_paths.insert(path.clone());
+                if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
+                    match &tab.location {
+                        Location::Path(path) => {
+                            new_paths.insert(path.clone());
+                        }
+                        // The file is replaced when it is written, so its directory is watched
+                        Location::Recents => {
+                            if let Some(dir) = recents::xbel_path()
+                                .as_deref()
+                                .and_then(|xbel_path| xbel_path.parent())
+                            {
+                                new_paths.insert(dir.to_path_buf());
+                            }
+                        }
+                        _ => {}
                     }
                 }
             }
@@ -1971,9 +3337,21 @@ impl App {
         if let Some((mut watcher, old_paths)) = self.watcher_opt_right.take() {
             let mut new_paths = HashSet::new();
             for entity in self.tab_model2.iter() {
-                if let Some(tab) = self.tab_model2.data::<Tab2>(entity) {
-                    if let Location2::Path(path) = &tab.location {
-                        new_paths.insert(path.clone());
+                if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
+                    match &tab.location {
+                        Location::Path(path) => {
+                            new_paths.insert(path.clone());
+                        }
+                        // The file is replaced when it is written, so its directory is watched
+                        Location::Recents => {
+                            if let Some(dir) = recents::xbel_path()
+                                .as_deref()
+                                .and_then(|xbel_path| xbel_path.parent())
+                            {
+                                new_paths.insert(dir.to_path_buf());
+                            }
+                        }
+                        _ => {}
                     }
                 }
             }
@@ -2091,6 +3469,26 @@ impl App {
         let mut children = Vec::new();
 
         let mut section = widget::settings::section().title(fl!("show-on-desktop"));
+        section = section.add(widget::settings::item::builder(fl!("computer")).toggler(
+            config.show_computer,
+            move |show_computer| {
+                Message::DesktopConfig(DesktopConfig {
+                    show_computer,
+                    ..config
+                })
+            },
+        ));
+        section = section.add(
+            widget::settings::item::builder(fl!("home-folder-icon")).toggler(
+                config.show_home,
+                move |show_home| {
+                    Message::DesktopConfig(DesktopConfig {
+                        show_home,
+                        ..config
+                    })
+                },
+            ),
+        );
         section = section.add(
             widget::settings::item::builder(fl!("desktop-folder-content")).toggler(
                 config.show_content,
@@ -2158,12 +3556,156 @@ impl App {
         );
         children.push(section.into());
 
+        let mut section = widget::settings::section().title(fl!("icon-placement"));
+        section = section.add(
+            widget::settings::item::builder(fl!("auto-arrange-icons")).toggler(
+                config.auto_arrange,
+                move |auto_arrange| {
+                    Message::DesktopConfig(DesktopConfig {
+                        auto_arrange,
+                        ..config
+                    })
+                },
+            ),
+        );
+        if !config.auto_arrange {
+            section = section.add(
+                widget::settings::item::builder(fl!("align-to-grid")).toggler(
+                    config.align_to_grid,
+                    move |align_to_grid| {
+                        Message::DesktopConfig(DesktopConfig {
+                            align_to_grid,
+                            ..config
+                        })
+                    },
+                ),
+            );
+        }
+        children.push(section.into());
+
         widget::column::with_children(children)
             .padding([0, space_l, space_l, space_l])
             .spacing(space_m)
             .into()
     }
 
+    /// Devices with their free space and buttons to mount, unmount or eject them,
+    /// followed by the networks and the mounted network shares
+    fn devices_view(&self) -> Element<Message> {
+        let cosmic_theme::Spacing {
+            space_xxxs,
+            space_xxs,
+            space_s,
+            ..
+        } = theme::active().cosmic().spacing;
+
+        // Collect all mounter items
+        let mut mounter_items = Vec::new();
+        for (key, items) in self.mounter_items.iter() {
+            for item in items.iter() {
+                mounter_items.push((*key, item));
+            }
+        }
+        // Sort by name lexically
+        mounter_items.sort_by(|a, b| LANGUAGE_SORTER.compare(&a.1.name(), &b.1.name()));
+
+        let device_row = |key: MounterKey, item: &MounterItem| -> Element<Message> {
+            let mut column = widget::column::with_capacity(3).spacing(space_xxxs);
+            column = column.push(widget::text::body(item.name()));
+            if let Some((total, free)) = item.path().and_then(|path| self.device_usage.get(&path)) {
+                if *total > 0 {
+                    column = column.push(
+                        widget::progress_bar(0.0..=1.0, (total - free) as f32 / *total as f32)
+                            .height(Length::Fixed(4.0)),
+                    );
+                    column = column.push(widget::text::caption(fl!(
+                        "free-space",
+                        free = tab::format_size(*free),
+                        total = tab::format_size(*total)
+                    )));
+                }
+            }
+
+            let mut row = widget::row::with_capacity(3)
+                .align_y(Alignment::Center)
+                .spacing(space_xxs);
+            if let Some(icon) = item.icon(true) {
+                row = row.push(widget::icon::icon(icon).size(16));
+            }
+            row = row.push(
+                widget::button::custom(column)
+                    .class(theme::Button::Text)
+                    .padding(space_xxxs)
+                    .width(Length::Fill)
+                    .on_press(Message::DeviceOpen(key, item.clone())),
+            );
+            let (icon_name, label, message) = if !item.is_mounted() {
+                (
+                    "folder-open-symbolic",
+                    fl!("mount"),
+                    Message::DeviceMount(key, item.clone()),
+                )
+            } else if item.can_eject() {
+                (
+                    "media-eject-symbolic",
+                    fl!("eject"),
+                    Message::DeviceEject(key, item.clone()),
+                )
+            } else {
+                (
+                    "media-eject-symbolic",
+                    fl!("unmount"),
+                    Message::DeviceUnmount(key, item.clone()),
+                )
+            };
+            row = row.push(widget::tooltip(
+                widget::button::icon(widget::icon::from_name(icon_name)).on_press(message),
+                widget::text::body(label),
+                widget::tooltip::Position::Bottom,
+            ));
+            row.into()
+        };
+
+        let mut column = widget::column::with_capacity(mounter_items.len() + 3)
+            .padding([space_xxs, space_s])
+            .spacing(space_xxxs);
+        let mut devices = mounter_items
+            .iter()
+            .filter(|(_, item)| !item.is_remote())
+            .peekable();
+        if devices.peek().is_some() {
+            column = column.push(widget::text::heading(fl!("devices")));
+            for (key, item) in devices {
+                column = column.push(device_row(*key, item));
+            }
+        }
+        if mounter::network_supported() {
+            column = column.push(
+                widget::button::custom(
+                    widget::row::with_children(vec![
+                        widget::icon::from_name("network-workgroup-symbolic")
+                            .size(16)
+                            .icon()
+                            .into(),
+                        widget::text::heading(fl!("networks")).into(),
+                    ])
+                    .align_y(Alignment::Center)
+                    .spacing(space_xxs),
+                )
+                .class(theme::Button::Text)
+                .padding([space_xxxs, 0])
+                .on_press(Message::GoLocation(Location::Network(
+                    "network:///".to_string(),
+                    fl!("networks"),
+                ))),
+            );
+            for (key, item) in mounter_items.iter().filter(|(_, item)| item.is_remote()) {
+                column = column.push(device_row(*key, item));
+            }
+        }
+        column.into()
+    }
+
     fn edit_history(&self) -> Element<Message> {
         let cosmic_theme::Spacing { space_m, .. } = theme::active().cosmic().spacing;
 
@@ -2176,7 +3718,7 @@ impl App {
             let mut section = widget::settings::section().title(fl!("pending"));
             for (id, (op, controller)) in self.pending_operations.iter().rev() {
                 let progress = controller.progress();
-                section = section.add(widget::column::with_children(vec![
+                let mut column = widget::column::with_children(vec![
                     widget::row::with_children(vec![
                         widget::progress_bar(0.0..=1.0, progress)
                             .height(progress_bar_height)
@@ -2216,7 +3758,11 @@ impl App {
                     .align_y(Alignment::Center)
                     .into(),
                     widget::text::body(op.pending_text(progress, controller.state())).into(),
-                ]));
+                ]);
+                if let Some(details) = controller.details().text() {
+                    column = column.push(widget::text::caption(details));
+                }
+                section = section.add(column);
             }
             children.push(section.into());
         }
@@ -2227,7 +3773,7 @@ impl App {
                 let progress = controller.progress();
                 section = section.add(widget::column::with_children(vec![
                     widget::text::body(op.pending_text(progress, controller.state())).into(),
-                    widget::text::body(error).into(),
+                    widget::text::body(error.to_string()).into(),
                 ]));
             }
             children.push(section.into());
@@ -2255,7 +3801,7 @@ impl App {
         entity_opt: &Option<Entity>,
         kind: &'a PreviewKind,
         context_drawer: bool,
-    ) -> Element<'a, tab1::Message> {
+    ) -> Element<'a, tab::Message> {
         let cosmic_theme::Spacing { space_l, .. } = theme::active().cosmic().spacing;
 
         let mut children = Vec::with_capacity(1);
@@ -2270,11 +3816,11 @@ impl App {
             }
         };
         match kind {
-            PreviewKind::Custom1(PreviewItem1(item)) => {
+            PreviewKind::Custom(PreviewItem(item)) => {
                 children.push(item.preview_view(Some(&self.mime_app_cache), IconSizes::default()));
             }
-            PreviewKind::Location1(location) => {
-                if let Some(tab) = self.tab_model1.data::<Tab1>(entity) {
+            PreviewKind::Location(location) => {
+                if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
                     if let Some(items) = tab.items_opt() {
                         for item in items.iter() {
                             if item.location_opt.as_ref() == Some(location) {
@@ -2291,8 +3837,10 @@ impl App {
                 }
             }
             PreviewKind::Selected => {
-                if let Some(tab) = self.tab_model1.data::<Tab1>(entity) {
-                    if let Some(items) = tab.items_opt() {
+                if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
+                    if let Some(view) = tab.selection_preview_view() {
+                        children.push(view);
+                    } else if let Some(items) = tab.items_opt() {
                         for item in items.iter() {
                             if item.selected {
                                 children.push(item.preview_view(
@@ -2331,7 +3879,7 @@ impl App {
         entity_opt: &Option<Entity>,
         kind: &'a PreviewKind,
         context_drawer: bool,
-    ) -> Element<'a, tab2::Message> {
+    ) -> Element<'a, tab::Message> {
         let cosmic_theme::Spacing { space_l, .. } = theme::active().cosmic().spacing;
 
         let mut children = Vec::with_capacity(1);
@@ -2346,11 +3894,11 @@ impl App {
             }
         };
         match kind {
-            PreviewKind::Custom2(PreviewItem2(item)) => {
+            PreviewKind::Custom(PreviewItem(item)) => {
                 children.push(item.preview_view(Some(&self.mime_app_cache), IconSizes::default()));
             }
-            PreviewKind::Location2(location) => {
-                if let Some(tab) = self.tab_model2.data::<Tab2>(entity) {
+            PreviewKind::Location(location) => {
+                if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
                     if let Some(items) = tab.items_opt() {
                         for item in items.iter() {
                             if item.location_opt.as_ref() == Some(location) {
@@ -2367,8 +3915,10 @@ impl App {
                 }
             }
             PreviewKind::Selected => {
-                if let Some(tab) = self.tab_model2.data::<Tab2>(entity) {
-                    if let Some(items) = tab.items_opt() {
+                if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
+                    if let Some(view) = tab.selection_preview_view() {
+                        children.push(view);
+                    } else if let Some(items) = tab.items_opt() {
                         for item in items.iter() {
                             if item.selected {
                                 children.push(item.preview_view(
@@ -2403,60 +3953,760 @@ impl App {
     }
 
     fn settings(&self) -> Element<Message> {
-        // TODO: Should dialog be updated here too?
-        widget::column::with_children(vec![
-            widget::settings::section()
-                .title(fl!("appearance"))
-                .add({
-                    let app_theme_selected = match self.config.app_theme {
-                        AppTheme::Dark => 1,
-                        AppTheme::Light => 2,
-                        AppTheme::System => 0,
-                    };
-                    widget::settings::item::builder(fl!("theme")).control(widget::dropdown(
-                        &self.app_themes,
-                        Some(app_theme_selected),
-                        move |index| {
-                            Message::AppTheme(match index {
-                                1 => AppTheme::Dark,
-                                2 => AppTheme::Light,
-                                _ => AppTheme::System,
-                            })
-                        },
-                    ))
-                })
-                .into(),
-            widget::settings::section()
-                .title(fl!("view"))
-                .add(
-                    widget::settings::item::builder(fl!("show-button-row"))
-                        .toggler(self.config.show_button_row, Message::ShowButtonRow),
-                )
-                .add(
-                    widget::settings::item::builder(fl!("show-embedded-terminal")).toggler(
-                        self.config.show_embedded_terminal,
-                        Message::ShowEmbeddedTerminal,
-                    ),
-                )
-                .add(
-                    widget::settings::item::builder(fl!("show-second-panel"))
-                        .toggler(self.config.show_second_panel, Message::ShowSecondPanel),
-                )
-                .into(),
-            widget::settings::section()
-                .title(fl!("features"))
-                .add(
-                    widget::settings::item::builder(fl!("queue-file-operations")).toggler(
-                        self.config.queue_file_operations,
-                        Message::QueueFileOperations,
-                    ),
-                )
-                .into(),
-        ])
-        .into()
+        let cosmic_theme::Spacing { space_s, .. } = theme::active().cosmic().spacing;
+        let search = self.settings_search.trim().to_lowercase();
+        let mut column = widget::column::with_capacity(3).spacing(space_s).push(
+            widget::text_input::search_input(fl!("search-settings"), self.settings_search.as_str())
+                .on_input(Message::SettingsSearch)
+                .on_clear(Message::SettingsSearch(String::new())),
+        );
+        if search.is_empty() {
+            let page = self.settings_page;
+            column = column.push(widget::dropdown(
+                &self.settings_pages,
+                SettingsPage::all().iter().position(|x| *x == page),
+                |index| Message::SettingsPage(SettingsPage::all()[index]),
+            ));
+            let mut section = widget::settings::section();
+            for (_label, item) in self.settings_items(page) {
+                section = section.add(item);
+            }
+            column = column.push(section);
+        } else {
+            // Searching goes through the options of all pages
+            let mut found = false;
+            for page in SettingsPage::all() {
+                let mut section = widget::settings::section().title(page.name());
+                let mut empty = true;
+                for (label, item) in self.settings_items(*page) {
+                    if label.to_lowercase().contains(&search) {
+                        section = section.add(item);
+                        empty = false;
+                    }
+                }
+                if !empty {
+                    column = column.push(section);
+                    found = true;
+                }
+            }
+            if !found {
+                column = column.push(widget::text::body(fl!("no-settings-found")));
+            }
+        }
+        column.into()
     }
 
-    fn view_pane_content(
+    /// Options of a settings page, with the text that the settings search matches
+    fn settings_items(&self, page: SettingsPage) -> Vec<(String, Element<Message>)> {
+        // TODO: Should dialog be updated here too?
+        let mut items: Vec<(String, Element<Message>)> = Vec::new();
+        match page {
+            SettingsPage::Appearance => {
+                let app_theme_selected = match self.config.app_theme {
+                    AppTheme::Dark => 1,
+                    AppTheme::Light => 2,
+                    AppTheme::System => 0,
+                    AppTheme::FollowSystem => 3,
+                };
+                items.push((
+                    fl!("theme"),
+                    widget::settings::item::builder(fl!("theme"))
+                        .control(widget::dropdown(
+                            &self.app_themes,
+                            Some(app_theme_selected),
+                            move |index| {
+                                Message::AppTheme(match index {
+                                    1 => AppTheme::Dark,
+                                    2 => AppTheme::Light,
+                                    3 => AppTheme::FollowSystem,
+                                    _ => AppTheme::System,
+                                })
+                            },
+                        ))
+                        .into(),
+                ));
+                let density_selected = Density::all()
+                    .iter()
+                    .position(|density| *density == self.config.density);
+                items.push((
+                    fl!("density"),
+                    widget::settings::item::builder(fl!("density"))
+                        .control(widget::dropdown(
+                            &self.densities,
+                            density_selected,
+                            |index| Message::Density(Density::all()[index]),
+                        ))
+                        .into(),
+                ));
+                let FormatConfig {
+                    size_units,
+                    date_format,
+                    first_day_of_week,
+                } = self.config.formats;
+                items.push((
+                    fl!("size-units"),
+                    widget::settings::item::builder(fl!("size-units"))
+                        .control(widget::dropdown(
+                            &self.size_units,
+                            SizeUnits::all().iter().position(|x| *x == size_units),
+                            |index| Message::SizeUnits(SizeUnits::all()[index]),
+                        ))
+                        .into(),
+                ));
+                items.push((
+                    fl!("date-format"),
+                    widget::settings::item::builder(fl!("date-format"))
+                        .control(widget::dropdown(
+                            &self.date_formats,
+                            DateFormat::all().iter().position(|x| *x == date_format),
+                            |index| Message::DateFormat(DateFormat::all()[index]),
+                        ))
+                        .into(),
+                ));
+                items.push((
+                    fl!("first-day-of-week"),
+                    widget::settings::item::builder(fl!("first-day-of-week"))
+                        .control(widget::dropdown(
+                            &self.first_days_of_week,
+                            FirstDayOfWeek::all()
+                                .iter()
+                                .position(|x| *x == first_day_of_week),
+                            |index| Message::FirstDayOfWeek(FirstDayOfWeek::all()[index]),
+                        ))
+                        .into(),
+                ));
+                items.push((
+                    fl!("accent-color"),
+                    widget::settings::item::builder(fl!("accent-color"))
+                        .control(widget::dropdown(
+                            &self.accent_colors,
+                            config::preset_color_index(self.config.accent_color),
+                            |index| Message::AccentColor(config::preset_color(index)),
+                        ))
+                        .into(),
+                ));
+                items.push((
+                    fl!("pane-tint-left"),
+                    widget::settings::item::builder(fl!("pane-tint-left"))
+                        .control(widget::dropdown(
+                            &self.pane_tints,
+                            config::preset_color_index(self.config.pane_tint_left),
+                            |index| Message::PaneTintLeft(config::preset_color(index)),
+                        ))
+                        .into(),
+                ));
+                items.push((
+                    fl!("pane-tint-right"),
+                    widget::settings::item::builder(fl!("pane-tint-right"))
+                        .control(widget::dropdown(
+                            &self.pane_tints,
+                            config::preset_color_index(self.config.pane_tint_right),
+                            |index| Message::PaneTintRight(config::preset_color(index)),
+                        ))
+                        .into(),
+                ));
+                items.push((
+                    fl!("show-toolbar"),
+                    widget::settings::item::builder(fl!("show-toolbar"))
+                        .toggler(self.config.show_toolbar, Message::ShowToolbar)
+                        .into(),
+                ));
+                items.push((
+                    fl!("toolbar-buttons"),
+                    widget::settings::item::builder(fl!("toolbar-buttons"))
+                        .control(
+                            widget::button::standard(fl!("edit-toolbar"))
+                                .on_press(Message::EditToolbar),
+                        )
+                        .into(),
+                ));
+            }
+            SettingsPage::Behavior => {
+                // Without shared settings, each pane has its own click settings
+                let panes = if self.config.share_click_settings {
+                    vec![(
+                        None,
+                        None,
+                        self.config.tab_left.single_click_open,
+                        self.config.tab_left.double_click_open,
+                        self.config.tab_left.click_empty_clears_selection,
+                    )]
+                } else {
+                    vec![
+                        (
+                            Some(PaneType::LeftPane),
+                            Some(fl!("left-pane")),
+                            self.config.tab_left.single_click_open,
+                            self.config.tab_left.double_click_open,
+                            self.config.tab_left.click_empty_clears_selection,
+                        ),
+                        (
+                            Some(PaneType::RightPane),
+                            Some(fl!("right-pane")),
+                            self.config.tab_right.single_click_open,
+                            self.config.tab_right.double_click_open,
+                            self.config.tab_right.click_empty_clears_selection,
+                        ),
+                    ]
+                };
+                items.push((
+                    fl!("share-click-settings"),
+                    widget::settings::item::builder(fl!("share-click-settings"))
+                        .toggler(
+                            self.config.share_click_settings,
+                            Message::ShareClickSettings,
+                        )
+                        .into(),
+                ));
+                for (pane_opt, heading_opt, single_click_open, double_click_open, clears) in panes {
+                    if let Some(heading) = heading_opt {
+                        // The heading is found with any of the options below it
+                        let label = format!(
+                            "{} {} {} {}",
+                            heading,
+                            fl!("single-click-open"),
+                            fl!("double-click-open"),
+                            fl!("click-empty-clears-selection")
+                        );
+                        items.push((label, widget::text::heading(heading).into()));
+                    }
+                    items.push((
+                        fl!("single-click-open"),
+                        widget::settings::item::builder(fl!("single-click-open"))
+                            .toggler(single_click_open, move |x| {
+                                Message::SingleClickOpen(pane_opt, x)
+                            })
+                            .into(),
+                    ));
+                    items.push((
+                        fl!("double-click-open"),
+                        widget::settings::item::builder(fl!("double-click-open"))
+                            .toggler(double_click_open, move |x| {
+                                Message::DoubleClickOpen(pane_opt, x)
+                            })
+                            .into(),
+                    ));
+                    items.push((
+                        fl!("click-empty-clears-selection"),
+                        widget::settings::item::builder(fl!("click-empty-clears-selection"))
+                            .toggler(clears, move |x| {
+                                Message::ClickEmptyClearsSelection(pane_opt, x)
+                            })
+                            .into(),
+                    ));
+                }
+                items.push((
+                    format!(
+                        "{} {}",
+                        fl!("open-together"),
+                        fl!("open-together-description")
+                    ),
+                    widget::settings::item::builder(fl!("open-together"))
+                        .description(fl!("open-together-description"))
+                        .toggler(self.config.open_together, Message::OpenTogether)
+                        .into(),
+                ));
+                items.push((
+                    fl!("mouse-gestures"),
+                    widget::settings::item::builder(fl!("mouse-gestures"))
+                        .description(fl!("mouse-gestures-description"))
+                        .toggler(self.config.tab_left.mouse_gestures, Message::MouseGestures)
+                        .into(),
+                ));
+                items.push((
+                    fl!("rocker-gestures"),
+                    widget::settings::item::builder(fl!("rocker-gestures"))
+                        .description(fl!("rocker-gestures-description"))
+                        .toggler(
+                            self.config.tab_left.rocker_gestures,
+                            Message::RockerGestures,
+                        )
+                        .into(),
+                ));
+            }
+            SettingsPage::Panes => {
+                items.push((
+                    fl!("show-second-panel"),
+                    widget::settings::item::builder(fl!("show-second-panel"))
+                        .toggler(self.config.show_second_panel, Message::ShowSecondPanel)
+                        .into(),
+                ));
+                items.push((
+                    fl!("show-button-row"),
+                    widget::settings::item::builder(fl!("show-button-row"))
+                        .toggler(self.config.show_button_row, Message::ShowButtonRow)
+                        .into(),
+                ));
+                items.push((
+                    fl!("size-one-file-system"),
+                    widget::settings::item::builder(fl!("size-one-file-system"))
+                        .toggler(
+                            self.config.tab_left.size_one_file_system,
+                            Message::SizeOneFileSystem,
+                        )
+                        .into(),
+                ));
+                items.push((
+                    fl!("preview-max-size"),
+                    widget::settings::item::builder(fl!("preview-max-size"))
+                        .control(widget::dropdown(
+                            &self.preview_max_sizes,
+                            config::PREVIEW_MAX_SIZES_MB.iter().position(|x| {
+                                *x == self.config.tab_left.preview_limits.max_size_mb
+                            }),
+                            Message::PreviewMaxSize,
+                        ))
+                        .into(),
+                ));
+                items.push((
+                    fl!("preview-timeout"),
+                    widget::settings::item::builder(fl!("preview-timeout"))
+                        .control(widget::dropdown(
+                            &self.preview_timeouts,
+                            config::PREVIEW_TIMEOUTS_SECS.iter().position(|x| {
+                                *x == self.config.tab_left.preview_limits.timeout_secs
+                            }),
+                            Message::PreviewTimeout,
+                        ))
+                        .into(),
+                ));
+                items.push((
+                    fl!("slideshow-interval"),
+                    widget::settings::item::builder(fl!("slideshow-interval"))
+                        .control(widget::dropdown(
+                            &self.slideshow_intervals,
+                            config::SLIDESHOW_INTERVALS_SECS
+                                .iter()
+                                .position(|x| *x == self.config.tab_left.slideshow_interval_secs),
+                            Message::SlideshowInterval,
+                        ))
+                        .into(),
+                ));
+                items.extend(self.hide_patterns_items());
+                items.extend(self.color_rules_items());
+            }
+            SettingsPage::Terminal => {
+                items.push((
+                    fl!("show-embedded-terminal"),
+                    widget::settings::item::builder(fl!("show-embedded-terminal"))
+                        .toggler(
+                            self.config.show_embedded_terminal,
+                            Message::ShowEmbeddedTerminal,
+                        )
+                        .into(),
+                ));
+                items.push((
+                    fl!("color-scheme-dark"),
+                    widget::settings::item::builder(fl!("color-scheme-dark"))
+                        .control(widget::dropdown(
+                            &self.theme_names_dark,
+                            self.theme_names_dark
+                                .iter()
+                                .position(|name| *name == self.config.syntax_theme_dark),
+                            |index| Message::SyntaxTheme(ColorSchemeKind::Dark, index),
+                        ))
+                        .into(),
+                ));
+                items.push((
+                    fl!("color-scheme-light"),
+                    widget::settings::item::builder(fl!("color-scheme-light"))
+                        .control(widget::dropdown(
+                            &self.theme_names_light,
+                            self.theme_names_light
+                                .iter()
+                                .position(|name| *name == self.config.syntax_theme_light),
+                            |index| Message::SyntaxTheme(ColorSchemeKind::Light, index),
+                        ))
+                        .into(),
+                ));
+                items.push((
+                    format!("{} {}", fl!("color-scheme-dark"), fl!("color-scheme-light")),
+                    widget::settings::item_row(vec![self.color_scheme_preview()]).into(),
+                ));
+                items.push((
+                    fl!("import-color-scheme-hint"),
+                    widget::settings::item_row(vec![widget::text::caption(fl!(
+                        "import-color-scheme-hint"
+                    ))
+                    .into()])
+                    .into(),
+                ));
+            }
+            SettingsPage::Operations => {
+                items.push((
+                    fl!("queue-file-operations"),
+                    widget::settings::item::builder(fl!("queue-file-operations"))
+                        .toggler(
+                            self.config.queue_file_operations,
+                            Message::QueueFileOperations,
+                        )
+                        .into(),
+                ));
+                items.push((
+                    fl!("quote-copied-paths"),
+                    widget::settings::item::builder(fl!("quote-copied-paths"))
+                        .toggler(self.config.quote_copied_paths, Message::QuoteCopiedPaths)
+                        .into(),
+                ));
+                items.push((
+                    fl!("refuse-insufficient-space"),
+                    widget::settings::item::builder(fl!("refuse-insufficient-space"))
+                        .toggler(
+                            self.config.refuse_insufficient_space,
+                            Message::RefuseInsufficientSpace,
+                        )
+                        .into(),
+                ));
+                items.push((
+                    fl!("preview-changes"),
+                    widget::settings::item::builder(fl!("preview-changes"))
+                        .toggler(self.config.preview_changes, Message::PreviewChanges)
+                        .into(),
+                ));
+                let confirm = self.config.confirm;
+                items.push((
+                    fl!("confirm-trash"),
+                    widget::settings::item::builder(fl!("confirm-trash"))
+                        .toggler(confirm.trash, move |trash| {
+                            Message::ConfirmConfig(ConfirmConfig { trash, ..confirm })
+                        })
+                        .into(),
+                ));
+                items.push((
+                    fl!("confirm-empty-trash"),
+                    widget::settings::item::builder(fl!("confirm-empty-trash"))
+                        .toggler(confirm.empty_trash, move |empty_trash| {
+                            Message::ConfirmConfig(ConfirmConfig {
+                                empty_trash,
+                                ..confirm
+                            })
+                        })
+                        .into(),
+                ));
+                items.push((
+                    fl!("confirm-overwrite"),
+                    widget::settings::item::builder(fl!("confirm-overwrite"))
+                        .toggler(confirm.overwrite, move |overwrite| {
+                            Message::ConfirmConfig(ConfirmConfig {
+                                overwrite,
+                                ..confirm
+                            })
+                        })
+                        .into(),
+                ));
+                items.push((
+                    fl!("confirm-move-across-devices"),
+                    widget::settings::item::builder(fl!("confirm-move-across-devices"))
+                        .toggler(confirm.move_across_devices, move |move_across_devices| {
+                            Message::ConfirmConfig(ConfirmConfig {
+                                move_across_devices,
+                                ..confirm
+                            })
+                        })
+                        .into(),
+                ));
+                items.push((
+                    fl!("confirm-run-executables"),
+                    widget::settings::item::builder(fl!("confirm-run-executables"))
+                        .toggler(confirm.execute, move |execute| {
+                            Message::ConfirmConfig(ConfirmConfig { execute, ..confirm })
+                        })
+                        .into(),
+                ));
+                if !self.config.run_choices.is_empty() {
+                    items.push((
+                        fl!("remembered-run-choices"),
+                        widget::settings::item::builder(fl!("remembered-run-choices"))
+                            .description(
+                                self.config
+                                    .run_choices
+                                    .keys()
+                                    .map(String::as_str)
+                                    .collect::<Vec<_>>()
+                                    .join(", "),
+                            )
+                            .control(
+                                widget::button::standard(fl!("forget"))
+                                    .on_press(Message::ClearRunChoices),
+                            )
+                            .into(),
+                    ));
+                }
+                items.push((
+                    format!(
+                        "{} {}",
+                        fl!("copy-workers"),
+                        fl!("copy-workers-description")
+                    ),
+                    widget::settings::item::builder(fl!("copy-workers"))
+                        .description(fl!("copy-workers-description"))
+                        .control(widget::dropdown(
+                            &self.copy_workers,
+                            config::COPY_WORKERS
+                                .iter()
+                                .position(|x| *x == self.config.copy_workers),
+                            Message::CopyWorkers,
+                        ))
+                        .into(),
+                ));
+                items.push((
+                    format!("{} {}", fl!("direct-io"), fl!("direct-io-description")),
+                    widget::settings::item::builder(fl!("direct-io"))
+                        .description(fl!("direct-io-description"))
+                        .toggler(self.config.direct_io, Message::DirectIo)
+                        .into(),
+                ));
+                items.push((
+                    format!(
+                        "{} {}",
+                        fl!("remote-trash"),
+                        fl!("remote-trash-description")
+                    ),
+                    widget::settings::item::builder(fl!("remote-trash"))
+                        .description(fl!("remote-trash-description"))
+                        .toggler(self.config.remote_trash, Message::RemoteTrash)
+                        .into(),
+                ));
+                items.push((
+                    format!("{} {}", fl!("sounds"), fl!("sounds-description")),
+                    widget::settings::item::builder(fl!("sounds"))
+                        .description(fl!("sounds-description"))
+                        .toggler(self.config.sounds, Message::Sounds)
+                        .into(),
+                ));
+                if self.config.sounds {
+                    items.push((
+                        fl!("sound-volume"),
+                        widget::settings::item::builder(fl!("sound-volume"))
+                            .description(format!("{}%", self.config.sound_volume))
+                            .control(
+                                widget::slider(
+                                    0..=100,
+                                    self.config.sound_volume,
+                                    Message::SoundVolume,
+                                )
+                                .step(10u8),
+                            )
+                            .into(),
+                    ));
+                }
+            }
+            SettingsPage::Keyboard => {
+                items.push((
+                    format!("{} {}", fl!("vi-keys"), fl!("vi-keys-description")),
+                    widget::settings::item::builder(fl!("vi-keys"))
+                        .description(fl!("vi-keys-description"))
+                        .toggler(self.config.vi_keys, Message::ViKeys)
+                        .into(),
+                ));
+            }
+            SettingsPage::Advanced => {
+                items.push((
+                    fl!("diff-tool"),
+                    widget::settings::item::builder(fl!("diff-tool"))
+                        .control(
+                            widget::text_input(
+                                fl!("built-in-diff"),
+                                self.config.diff_tool.as_str(),
+                            )
+                            .on_input(Message::DiffTool),
+                        )
+                        .into(),
+                ));
+                items.extend(self.default_apps_items());
+            }
+        }
+        items
+    }
+
+    fn hide_patterns_items(&self) -> Vec<(String, Element<Message>)> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+        let label = format!(
+            "{} {}",
+            fl!("hide-patterns"),
+            fl!("hide-patterns-description")
+        );
+        let patterns = &self.config.hide_patterns;
+        let mut items: Vec<(String, Element<Message>)> = vec![
+            (
+                label.clone(),
+                widget::text::heading(fl!("hide-patterns")).into(),
+            ),
+            (
+                label.clone(),
+                widget::settings::item_row(vec![widget::text::caption(fl!(
+                    "hide-patterns-description"
+                ))
+                .into()])
+                .into(),
+            ),
+        ];
+        for (i, pattern) in patterns.iter().enumerate() {
+            let mut removed = patterns.clone();
+            removed.remove(i);
+            let mut row = widget::row::with_capacity(3)
+                .align_y(Alignment::Center)
+                .spacing(space_xxs)
+                .push(
+                    widget::text_input("*.bak", pattern.as_str()).on_input(move |pattern| {
+                        let mut patterns = patterns.clone();
+                        patterns[i] = pattern;
+                        Message::HidePatterns(patterns)
+                    }),
+                );
+            if glob::Pattern::new(pattern).is_err() {
+                row = row.push(widget::text::caption(fl!("invalid-pattern")));
+            }
+            row = row.push(
+                widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                    .on_press(Message::HidePatterns(removed)),
+            );
+            items.push((
+                label.clone(),
+                widget::settings::item_row(vec![row.into()]).into(),
+            ));
+        }
+        let mut added = patterns.clone();
+        added.push(String::new());
+        items.push((
+            label,
+            widget::settings::item_row(vec![widget::button::text(fl!("add-hide-pattern"))
+                .on_press(Message::HidePatterns(added))
+                .into()])
+            .into(),
+        ));
+        items
+    }
+
+    fn color_rules_items(&self) -> Vec<(String, Element<Message>)> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+        let label = format!("{} {}", fl!("color-rules"), fl!("color-rules-description"));
+        let rules = &self.config.color_rules;
+        let mut items: Vec<(String, Element<Message>)> = vec![
+            (
+                label.clone(),
+                widget::text::heading(fl!("color-rules")).into(),
+            ),
+            (
+                label.clone(),
+                widget::settings::item_row(vec![widget::text::caption(fl!(
+                    "color-rules-description"
+                ))
+                .into()])
+                .into(),
+            ),
+        ];
+        for (i, rule) in rules.iter().enumerate() {
+            let mut removed = rules.clone();
+            removed.remove(i);
+            let mut row = widget::row::with_capacity(4)
+                .align_y(Alignment::Center)
+                .spacing(space_xxs)
+                .push(
+                    widget::text_input("*.rs", rule.pattern.as_str()).on_input(move |pattern| {
+                        let mut rules = rules.clone();
+                        rules[i].pattern = pattern;
+                        Message::ColorRules(rules)
+                    }),
+                )
+                .push(widget::dropdown(
+                    &self.pane_tints,
+                    config::preset_color_index(rule.color),
+                    move |index| {
+                        let mut rules = rules.clone();
+                        rules[i].color = config::preset_color(index);
+                        Message::ColorRules(rules)
+                    },
+                ));
+            let keyword = matches!(
+                rule.pattern.trim(),
+                "folder" | "executable" | "symlink" | "hidden"
+            );
+            if !keyword && glob::Pattern::new(rule.pattern.trim()).is_err() {
+                row = row.push(widget::text::caption(fl!("invalid-pattern")));
+            }
+            row = row.push(
+                widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                    .on_press(Message::ColorRules(removed)),
+            );
+            items.push((
+                label.clone(),
+                widget::settings::item_row(vec![row.into()]).into(),
+            ));
+        }
+        let mut added = rules.clone();
+        added.push(config::ColorRule::default());
+        items.push((
+            label,
+            widget::settings::item_row(vec![widget::button::text(fl!("add-color-rule"))
+                .on_press(Message::ColorRules(added))
+                .into()])
+            .into(),
+        ));
+        items
+    }
+
+    fn default_apps_items(&self) -> Vec<(String, Element<Message>)> {
+        // The heading is found with the MIME types too
+        let mut label = fl!("default-applications");
+        let mut items: Vec<(String, Element<Message>)> = Vec::new();
+        for mime in self.seen_mimes.iter() {
+            let apps = self.mime_app_cache.get(mime);
+            if apps.is_empty() {
+                continue;
+            }
+            label.push(' ');
+            label.push_str(mime.essence_str());
+            let mime_closure = mime.clone();
+            items.push((
+                format!("{} {}", fl!("default-applications"), mime.essence_str()),
+                widget::settings::item::builder(mime.essence_str().to_string())
+                    .control(
+                        widget::dropdown(
+                            apps,
+                            apps.iter().position(|x| x.is_default),
+                            move |index| Message::DefaultApp(mime_closure.clone(), index),
+                        )
+                        .icons(self.mime_app_cache.icons(mime)),
+                    )
+                    .into(),
+            ));
+        }
+        if items.is_empty() {
+            items.push((
+                label.clone(),
+                widget::settings::item_row(vec![widget::text::body(fl!(
+                    "no-default-applications"
+                ))
+                .into()])
+                .into(),
+            ));
+        }
+        items.insert(
+            0,
+            (
+                label,
+                widget::text::heading(fl!("default-applications")).into(),
+            ),
+        );
+        items
+    }
+
+    /// Preview of the selected item of the active pane, shown in place of the other pane
+    fn quick_view_pane(&self) -> Element<Message> {
+        let preview = if self.active_panel == PaneType::LeftPane {
+            let entity = self.tab_model1.active();
+            self.preview_left(&Some(entity), &PreviewKind::Selected, false)
+                .map(move |message| Message::TabMessage(Some(entity), message))
+        } else {
+            let entity = self.tab_model2.active();
+            self.preview_right(&Some(entity), &PreviewKind::Selected, false)
+                .map(move |message| Message::TabMessageRight(Some(entity), message))
+        };
+        widget::scrollable(preview).height(Length::Fill).into()
+    }
+
+    fn view_pane_content(
         &self,
         pane: pane_grid::Pane,
         _tab_model: &TabModel,
@@ -2467,7 +4717,10 @@ impl App {
         } = theme::active().cosmic().spacing;
         let pane_type = self.pane_model.type_by_pane[&pane];
         if pane_type == PaneType::LeftPane || pane_type == PaneType::RightPane {
-            let mut tab_column = widget::column::with_capacity(4);
+            if self.quick_view && self.show_second_panel && pane_type != self.active_panel {
+                return self.quick_view_pane();
+            }
+            let mut tab_column = widget::column::with_capacity(5);
             if self.core.is_condensed() {
                 if let Some(term) = self.search_get() {
                     tab_column = tab_column.push(
@@ -2486,27 +4739,38 @@ impl App {
             if pane_type == PaneType::LeftPane {
                 tab_column = tab_column.push(
                     widget::container(
-                        widget::tab_bar::horizontal(&self.tab_model1)
-                            .button_height(32)
-                            .button_spacing(space_xxs)
-                            .on_activate(|entity| Message::TabActivateLeftEntity(entity))
-                            .on_close(|entity| Message::TabCloseLeft(Some(entity)))
-                            .drag_id(self.tab_drag_id_left)
-                            .on_dnd_enter(|entity, _| Message::DndEnterTabLeft(entity))
-                            .on_dnd_leave(|_| Message::DndExitTabLeft)
-                            .on_dnd_drop(|entity, data, action| {
-                                Message::DndDropTabLeft(entity, data, action)
-                            }),
+                        widget::row::with_capacity(2)
+                            .push(
+                                widget::tab_bar::horizontal(&self.tab_model1)
+                                    .button_height(32)
+                                    .button_spacing(space_xxs)
+                                    .on_activate(|entity| Message::TabActivateLeftEntity(entity))
+                                    .on_close(|entity| Message::TabCloseLeft(Some(entity)))
+                                    .on_middle_press(|entity| Message::TabCloseLeft(Some(entity)))
+                                    .drag_id(self.tab_drag_id_left)
+                                    .on_dnd_enter(|entity, _| Message::DndEnterTabLeft(entity))
+                                    .on_dnd_leave(|_| Message::DndExitTabLeft)
+                                    .on_dnd_drop(|entity, data, action| {
+                                        Message::DndDropTabLeft(entity, data, action)
+                                    }),
+                            )
+                            .push(self.pane_history_button(PaneType::LeftPane))
+                            .align_y(Alignment::Center),
                     )
                     .class(style::Container::Background)
                     .width(Length::Fill)
                     .padding([0, space_s]),
                 );
+                tab_column = tab_column.push(pane_indicator(
+                    self.show_second_panel && self.active_panel == PaneType::LeftPane,
+                ));
                 let entity_left = self.tab_model1.active();
-                if let Some(tab) = self.tab_model1.data::<Tab1>(entity_left) {
-                    let tab_view_left = tab
-                        .view(&self.key_binds)
-                        .map(move |message| Message::TabMessage(Some(entity_left), message));
+                if let Some(tab) = self.tab_model1.data::<Tab>(entity_left) {
+                    let tab_view_left = tinted(
+                        tab.view(&self.key_binds)
+                            .map(move |message| Message::TabMessage(Some(entity_left), message)),
+                        self.config.pane_tint_left,
+                    );
                     tab_column = tab_column.push(tab_view_left)
                 }
                 // The toaster is added on top of an empty element to ensure that it does not override context menus
@@ -2517,26 +4781,38 @@ impl App {
             } else if pane_type == PaneType::RightPane {
                 tab_column = tab_column.push(
                     widget::container(
-                        widget::tab_bar::horizontal(&self.tab_model2)
-                            .button_height(32)
-                            .button_spacing(space_xxs)
-                            .on_activate(|entity| Message::TabActivateRightEntity(entity))
-                            .on_close(|entity| Message::TabCloseRight(Some(entity)))
-                            .drag_id(self.tab_drag_id_right)
-                            .on_dnd_enter(|entity, _| Message::DndEnterTabRight(entity))
-                            .on_dnd_leave(|_| Message::DndExitTabRight)
-                            .on_dnd_drop(|entity, data, action| {
-                                Message::DndDropTabRight(entity, data, action)
-                            }),
+                        widget::row::with_capacity(2)
+                            .push(
+                                widget::tab_bar::horizontal(&self.tab_model2)
+                                    .button_height(32)
+                                    .button_spacing(space_xxs)
+                                    .on_activate(|entity| Message::TabActivateRightEntity(entity))
+                                    .on_close(|entity| Message::TabCloseRight(Some(entity)))
+                                    .on_middle_press(|entity| Message::TabCloseRight(Some(entity)))
+                                    .drag_id(self.tab_drag_id_right)
+                                    .on_dnd_enter(|entity, _| Message::DndEnterTabRight(entity))
+                                    .on_dnd_leave(|_| Message::DndExitTabRight)
+                                    .on_dnd_drop(|entity, data, action| {
+                                        Message::DndDropTabRight(entity, data, action)
+                                    }),
+                            )
+                            .push(self.pane_history_button(PaneType::RightPane))
+                            .align_y(Alignment::Center),
                     )
                     .class(style::Container::Background)
                     .padding([0, space_s]),
                 );
+                tab_column = tab_column.push(pane_indicator(
+                    self.show_second_panel && self.active_panel == PaneType::RightPane,
+                ));
                 let entity_right = self.tab_model2.active();
-                if let Some(tab) = self.tab_model2.data::<Tab2>(entity_right) {
-                    let tab_view_right = tab
-                        .view(&self.key_binds)
-                        .map(move |message| Message::TabMessageRight(Some(entity_right), message));
+                if let Some(tab) = self.tab_model2.data::<Tab>(entity_right) {
+                    let tab_view_right = tinted(
+                        tab.view(&self.key_binds).map(move |message| {
+                            Message::TabMessageRight(Some(entity_right), message)
+                        }),
+                        self.config.pane_tint_right,
+                    );
                     tab_column = tab_column.push(tab_view_right)
                 }
                 // The toaster is added on top of an empty element to ensure that it does not override context menus
@@ -2551,7 +4827,8 @@ impl App {
             };
             DndDestination::for_data::<crate::dnd::DndDrop>(tab_column, move |data, action| {
                 if let Some(data) = data {
-                    if action == DndAction::Move {
+                    // Other applications usually offer copy, the effect is chosen by the modifiers
+                    if action == DndAction::Copy || action == DndAction::Move {
                         Message::DndPaneDrop(Some((p, data)))
                     } else {
                         log::warn!("unsuppported action: {:?}", action);
@@ -2614,9 +4891,28 @@ impl App {
         } else {
             // Terminal
             let mut tab_column = widget::column::with_capacity(1);
-            let terminal_id = widget::Id::unique();
+            let terminal_id = self.terminal_id.clone();
             let terminal_pane = self.pane_by_type(PaneType::TerminalPane);
-            if let Some(terminal) = &self.terminal {
+            tab_column = tab_column.push(
+                widget::container(
+                    widget::row::with_children(vec![
+                        widget::tab_bar::horizontal(&self.terminal_model)
+                            .button_height(32)
+                            .button_spacing(space_xxs)
+                            .on_activate(Message::TermActivate)
+                            .on_close(|entity| Message::TermClose(Some(entity)))
+                            .into(),
+                        widget::button::icon(widget::icon::from_name("list-add-symbolic"))
+                            .on_press(Message::TermNew)
+                            .into(),
+                    ])
+                    .align_y(Alignment::Center),
+                )
+                .class(style::Container::Background)
+                .width(Length::Fill)
+                .padding([0, space_s]),
+            );
+            if let Some(terminal) = self.terminal() {
                 let terminal_box = crate::terminal_box::terminal_box(&terminal)
                     .id(terminal_id)
                     .on_context_menu(move |position_opt| {
@@ -2656,7 +4952,8 @@ impl App {
             };
             DndDestination::for_data::<crate::dnd::DndDrop>(tab_column, move |data, action| {
                 if let Some(data) = data {
-                    if action == DndAction::Move {
+                    // Other applications usually offer copy, the effect is chosen by the modifiers
+                    if action == DndAction::Copy || action == DndAction::Move {
                         Message::DndPaneDrop(Some((p, data)))
                     } else {
                         log::warn!("unsuppported action: {:?}", action);
@@ -2670,10 +4967,131 @@ impl App {
         }
     }
 
-    fn pane_by_type(&self, panetype: PaneType) -> pane_grid::Pane {
-        if self.config.show_button_row
-            && self.config.show_embedded_terminal
-            && self.config.show_second_panel
+    /// Tabs of a file pane
+    fn tab_model(&self, pane_type: PaneType) -> &TabModel {
+        match pane_type {
+            PaneType::RightPane => &self.tab_model2,
+            _ => &self.tab_model1,
+        }
+    }
+
+    /// File pane that is not active, which Tab focuses and F5 and F6 copy and move to
+    fn other_pane(&self) -> PaneType {
+        match self.active_panel {
+            PaneType::RightPane => PaneType::LeftPane,
+            _ => PaneType::RightPane,
+        }
+    }
+
+    /// Start a copy or move after checking that the destination has enough free space,
+    /// other operations start right away
+    fn operation_space_checked(&mut self, operation: Operation) -> Task<Message> {
+        let (paths, to, moving) = match &operation {
+            Operation::Copy { paths, to } => (paths.clone(), to.clone(), false),
+            Operation::Move { paths, to } => (paths.clone(), to.clone(), true),
+            _ => {
+                self.operation(operation);
+                return Task::none();
+            }
+        };
+        Task::perform(
+            async move {
+                let check_opt = tokio::task::spawn_blocking(move || {
+                    operation::space_check(&paths, &to, moving)
+                })
+                .await
+                .unwrap_or(None);
+                message::app(Message::SpaceChecked(operation, check_opt))
+            },
+            |x| x,
+        )
+    }
+
+    /// List the files that the operation deletes or overwrites if enabled, and start it when
+    /// there are none or after they were confirmed
+    fn operation_previewed(&mut self, operation: Operation) -> Task<Message> {
+        // The preview of the changes asks by itself
+        if let Operation::Delete { paths } = &operation {
+            if self.config.confirm.trash && !self.config.preview_changes {
+                self.dialog_pages.push_back(DialogPage::ConfirmTrash {
+                    paths: paths.clone(),
+                });
+                return Task::none();
+            }
+        }
+        self.operation_previewed_confirmed(operation)
+    }
+
+    fn operation_previewed_confirmed(&mut self, operation: Operation) -> Task<Message> {
+        if !self.config.preview_changes
+            || !matches!(
+                operation,
+                Operation::Copy { .. }
+                    | Operation::Delete { .. }
+                    | Operation::Move { .. }
+                    | Operation::Sync { .. }
+            )
+        {
+            self.operation(operation);
+            return Task::none();
+        }
+        Task::perform(
+            async move {
+                let preview_operation = operation.clone();
+                let preview = tokio::task::spawn_blocking(move || {
+                    operation::preview_changes(&preview_operation)
+                })
+                .await
+                .unwrap_or_default();
+                message::app(Message::ChangesPreviewed(operation, preview))
+            },
+            |x| x,
+        )
+    }
+
+    /// Paste dropped items, moves to other devices are confirmed first if enabled
+    fn drop_contents(&mut self, to: PathBuf, contents: ClipboardPaste) -> Task<Message> {
+        if self.config.confirm.move_across_devices
+            && matches!(contents.kind, ClipboardKind::Cut)
+            && operation::crosses_devices(&contents.paths, &to)
+        {
+            self.dialog_pages
+                .push_back(DialogPage::ConfirmMove { to, contents });
+            return Task::none();
+        }
+        self.update(Message::PasteContents(to, contents))
+    }
+
+    fn empty_trash(&mut self) {
+        if self.config.confirm.empty_trash {
+            self.dialog_pages.push_back(DialogPage::EmptyTrash);
+        } else {
+            self.operation(Operation::EmptyTrash);
+        }
+    }
+
+    /// Copy or move the selected items of the active pane to the location of the other pane
+    fn operation_to_other_pane(&mut self, moving: bool) -> Task<Message> {
+        let paths = self.selected_paths(None);
+        let tab_model = self.tab_model(self.other_pane());
+        let Some(to) = tab_model
+            .data::<Tab>(tab_model.active())
+            .and_then(|tab| tab.location.path_opt())
+            .map(|path| path.to_path_buf())
+        else {
+            return Task::none();
+        };
+        if moving {
+            self.operation_space_checked(Operation::Move { paths, to })
+        } else {
+            self.operation_space_checked(Operation::Copy { paths, to })
+        }
+    }
+
+    fn pane_by_type(&self, panetype: PaneType) -> pane_grid::Pane {
+        if self.config.show_button_row
+            && self.config.show_embedded_terminal
+            && self.config.show_second_panel
         {
             // full window
             match panetype {
@@ -2753,50 +5171,117 @@ impl App {
         }
     }
 
+    /// Open a terminal tab with the shell of the profile, or with the program and arguments of
+    /// `command_opt` run in its folder, which keeps the tab open after the program exits
     fn create_and_focus_new_terminal(
         &mut self,
         pane: pane_grid::Pane,
-        //profile_id_opt: Option<ProfileId>,
+        profile_id_opt: Option<ProfileId>,
+        command_opt: Option<(PathBuf, Vec<String>)>,
     ) -> Task<Message> {
         self.pane_model.focus = pane;
+        let profile_opt =
+            profile_id_opt.and_then(|profile_id| self.config.profiles.get(&profile_id));
         match &self.term_event_tx_opt {
             Some(term_event_tx) => {
-                let colors = match self.config.color_scheme_kind() {
-                    ColorSchemeKind::Dark => self
-                        .themes
-                        .get(&(config::COSMIC_THEME_DARK.to_string(), ColorSchemeKind::Dark)),
-                    ColorSchemeKind::Light => self.themes.get(&(
-                        config::COSMIC_THEME_LIGHT.to_string(),
-                        ColorSchemeKind::Light,
-                    )),
-                };
-                match colors {
+                let colors_opt = self.terminal_colors(profile_opt).copied();
+                match colors_opt {
                     Some(colors) => {
                         let current_pane = pane;
-                        // Use the startup options, profile options, or defaults
-                        let (options, tab_title_override) =
-                            (alacritty_terminal::tty::Options::default(), None);
+                        // Use the profile options, or defaults
+                        let (mut options, term_config, mut tab_title_override) = match profile_opt {
+                            Some(profile) => {
+                                let mut shell = None;
+                                if let Some(mut args) = shlex::split(&profile.command) {
+                                    if !args.is_empty() {
+                                        let command = args.remove(0);
+                                        shell = Some(alacritty_terminal::tty::Shell::new(
+                                            command, args,
+                                        ));
+                                    }
+                                }
+                                let working_directory = (!profile.working_directory.is_empty())
+                                    .then(|| profile.working_directory.clone().into());
+                                let options = alacritty_terminal::tty::Options {
+                                    shell,
+                                    working_directory,
+                                    hold: profile.hold,
+                                    env: profile.env_vars(),
+                                };
+                                let term_config = term::Config {
+                                    scrolling_history: profile.scrollback,
+                                    default_cursor_style:
+                                        alacritty_terminal::vte::ansi::CursorStyle {
+                                            shape: profile.cursor_style.into(),
+                                            blinking: profile.cursor_blinking,
+                                        },
+                                    ..Default::default()
+                                };
+                                let tab_title_override = (!profile.tab_title.is_empty())
+                                    .then(|| profile.tab_title.clone());
+                                (options, term_config, tab_title_override)
+                            }
+                            None => (
+                                alacritty_terminal::tty::Options::default(),
+                                term::Config::default(),
+                                None,
+                            ),
+                        };
+                        let mut startup_command = profile_opt
+                            .map(|profile| profile.startup_command.clone())
+                            .unwrap_or_default();
+                        if let Some((dir, mut args)) =
+                            command_opt.filter(|(_, args)| !args.is_empty())
+                        {
+                            // The tab is named after the run file, not its interpreter
+                            tab_title_override = args
+                                .last()
+                                .and_then(|arg| Path::new(arg).file_name())
+                                .map(|name| name.to_string_lossy().to_string());
+                            let program = args.remove(0);
+                            options.shell =
+                                Some(alacritty_terminal::tty::Shell::new(program, args));
+                            options.working_directory = Some(dir);
+                            options.hold = true;
+                            startup_command.clear();
+                        }
+                        let text = match (&tab_title_override, profile_opt) {
+                            (Some(tab_title), _) => tab_title.clone(),
+                            (None, Some(profile)) => profile.name.clone(),
+                            (None, None) => fl!("terminal"),
+                        };
+                        let entity = self
+                            .terminal_model
+                            .insert()
+                            .text(text)
+                            .closable()
+                            .activate()
+                            .id();
                         match crate::terminal::Terminal::new(
                             current_pane,
-                            Entity::default(),
+                            entity,
                             term_event_tx.clone(),
-                            term::Config {
-                                ..Default::default()
-                            },
+                            term_config,
                             options,
                             //&self.config,
-                            *colors,
-                            //profile_id_opt,
+                            colors,
+                            profile_id_opt,
                             tab_title_override,
                         ) {
                             Ok(terminal) => {
                                 //terminal.set_config(&self.config, &self.themes);
-                                self.terminal = Some(Mutex::new(terminal));
+                                if !startup_command.is_empty() {
+                                    terminal.input_scroll(
+                                        format!("{}\r", startup_command).into_bytes(),
+                                    );
+                                }
+                                self.terminal_model.data_set(entity, Mutex::new(terminal));
                                 return Task::none();
                             }
                             Err(err) => {
                                 log::error!("failed to open terminal: {}", err);
                                 // Clean up partially created tab
+                                self.terminal_model.remove(entity);
                                 return Task::none();
                             }
                         }
@@ -2860,6 +5345,100 @@ impl App {
         self.theme_names_light
             .sort_by(|a, b| LANGUAGE_SORTER.compare(a, b));
     }
+
+    /// Colors of the color scheme for the current theme, falling back to the builtin one
+    fn terminal_colors(&self, profile_opt: Option<&Profile>) -> Option<&TermColors> {
+        let (name, color_scheme_kind) = self.config.syntax_theme(profile_opt);
+        self.themes.get(&(name, color_scheme_kind)).or_else(|| {
+            let name = match color_scheme_kind {
+                ColorSchemeKind::Dark => config::COSMIC_THEME_DARK,
+                ColorSchemeKind::Light => config::COSMIC_THEME_LIGHT,
+            };
+            self.themes.get(&(name.to_string(), color_scheme_kind))
+        })
+    }
+
+    /// Apply changed color schemes to all open terminals
+    fn update_terminal_colors(&self) {
+        for entity in self.terminal_model.iter() {
+            if let Some(terminal) = self
+                .terminal_model
+                .data::<Mutex<crate::terminal::Terminal>>(entity)
+            {
+                let mut terminal = terminal.lock().unwrap();
+                let profile_opt = terminal
+                    .profile_id_opt
+                    .and_then(|profile_id| self.config.profiles.get(&profile_id));
+                if let Some(colors) = self.terminal_colors(profile_opt) {
+                    terminal.set_colors(*colors);
+                }
+            }
+        }
+    }
+
+    /// Color swatches and sample output in the color scheme for the current theme
+    fn color_scheme_preview(&self) -> Element<Message> {
+        let cosmic_theme::Spacing {
+            space_xxxs,
+            space_xxs,
+            space_xs,
+            ..
+        } = theme::active().cosmic().spacing;
+        let Some(colors) = self.terminal_colors(None) else {
+            return horizontal_space().into();
+        };
+        let color_opt = |index: usize| {
+            colors[index].map(|rgb| cosmic::iced::Color::from_rgb8(rgb.r, rgb.g, rgb.b))
+        };
+
+        let mut swatches = widget::row::with_capacity(16).spacing(space_xxxs);
+        for index in 0..16 {
+            let background_opt = color_opt(index);
+            swatches = swatches.push(
+                widget::container(widget::Space::new(Length::Fixed(16.0), Length::Fixed(16.0)))
+                    .style(move |_theme| widget::container::Style {
+                        background: background_opt.map(Into::into),
+                        ..Default::default()
+                    }),
+            );
+        }
+
+        // Sample output of a shell, with the index of the ANSI color of each part
+        let foreground_opt = color_opt(NamedColor::Foreground as usize);
+        let background_opt = color_opt(NamedColor::Background as usize);
+        let spans: Vec<_> = [
+            ("user@host", Some(2)),
+            (":", None),
+            ("~", Some(4)),
+            ("$ ls\n", None),
+            ("notes.txt  ", None),
+            ("projects  ", Some(12)),
+            ("run.sh", Some(10)),
+        ]
+        .into_iter()
+        .map(|(text, index_opt)| {
+            let span = cosmic::iced::widget::span(text).font(cosmic::font::mono());
+            match index_opt.map_or(foreground_opt, color_opt) {
+                Some(color) => span.color(color),
+                None => span,
+            }
+        })
+        .collect();
+
+        widget::column::with_children(vec![
+            swatches.into(),
+            widget::container(cosmic::iced::widget::rich_text(spans).size(12))
+                .padding(space_xs)
+                .width(Length::Fill)
+                .style(move |_theme| widget::container::Style {
+                    background: background_opt.map(Into::into),
+                    ..Default::default()
+                })
+                .into(),
+        ])
+        .spacing(space_xxs)
+        .into()
+    }
 }
 
 /// Implement [`Application`] to integrate with COSMIC.
@@ -2902,9 +5481,104 @@ impl Application for App {
             }
         }
 
-        let app_themes = vec![fl!("match-desktop"), fl!("dark"), fl!("light")];
+        let app_themes = vec![
+            fl!("match-desktop"),
+            fl!("dark"),
+            fl!("light"),
+            fl!("follow-system"),
+        ];
+        let color_names = [
+            fl!("color-blue"),
+            fl!("color-teal"),
+            fl!("color-green"),
+            fl!("color-yellow"),
+            fl!("color-orange"),
+            fl!("color-red"),
+            fl!("color-pink"),
+            fl!("color-purple"),
+            fl!("color-gray"),
+        ];
+        let accent_colors = std::iter::once(fl!("theme-default"))
+            .chain(color_names.iter().cloned())
+            .collect();
+        let pane_tints = std::iter::once(fl!("none"))
+            .chain(color_names.iter().cloned())
+            .collect();
+        let bookmark_shortcuts = std::iter::once(fl!("no-shortcut"))
+            .chain((1..=9).map(|digit| format!("Ctrl+{}", digit)))
+            .collect();
+        let preview_max_sizes = config::PREVIEW_MAX_SIZES_MB
+            .iter()
+            .map(|size_mb| format!("{} MB", size_mb))
+            .collect();
+        let preview_timeouts = config::PREVIEW_TIMEOUTS_SECS
+            .iter()
+            .map(|secs| fl!("duration-seconds", seconds = secs))
+            .collect();
+        let slideshow_intervals = config::SLIDESHOW_INTERVALS_SECS
+            .iter()
+            .map(|secs| fl!("duration-seconds", seconds = secs))
+            .collect();
+        let copy_workers = config::COPY_WORKERS
+            .iter()
+            .map(|workers| workers.to_string())
+            .collect();
+        let cursor_styles = CursorStyle::all()
+            .iter()
+            .map(|cursor_style| match cursor_style {
+                CursorStyle::Block => fl!("cursor-block"),
+                CursorStyle::Underline => fl!("cursor-underline"),
+                CursorStyle::Beam => fl!("cursor-beam"),
+            })
+            .collect();
+        let densities = Density::all()
+            .iter()
+            .map(|density| match density {
+                Density::Comfortable => fl!("density-comfortable"),
+                Density::Compact => fl!("density-compact"),
+                Density::Dense => fl!("density-dense"),
+            })
+            .collect();
+        let size_units = SizeUnits::all()
+            .iter()
+            .map(|size_units| match size_units {
+                SizeUnits::Si => fl!("size-units-si"),
+                SizeUnits::Iec => fl!("size-units-iec"),
+                SizeUnits::Bytes => fl!("size-units-bytes"),
+            })
+            .collect();
+        let date_formats = DateFormat::all()
+            .iter()
+            .map(|date_format| match date_format {
+                DateFormat::Locale => fl!("date-format-locale"),
+                DateFormat::Relative => fl!("date-format-relative"),
+                DateFormat::Iso => fl!("date-format-iso"),
+            })
+            .collect();
+        let first_days_of_week = FirstDayOfWeek::all()
+            .iter()
+            .map(|first_day| match first_day {
+                FirstDayOfWeek::Monday => fl!("monday"),
+                FirstDayOfWeek::Saturday => fl!("saturday"),
+                FirstDayOfWeek::Sunday => fl!("sunday"),
+            })
+            .collect();
+        let launcher_dirs = LauncherDir::all()
+            .iter()
+            .map(|dir| match dir {
+                LauncherDir::Applications => fl!("launcher-applications"),
+                LauncherDir::Desktop => fl!("desktop"),
+            })
+            .collect();
+
+        let settings_pages = SettingsPage::all().iter().map(|page| page.name()).collect();
+
+        let toolbar_actions = menu::TOOLBAR_ACTIONS
+            .iter()
+            .map(|action| menu::toolbar_button(action).0)
+            .collect();
 
-        let key_binds = key_binds(&tab1::Mode::App);
+        let key_binds = key_binds(&tab::Mode::App);
         let key_binds_terminal = key_binds_terminal();
 
         let window_id_opt = core.main_window_id();
@@ -2916,7 +5590,6 @@ impl Application for App {
         //let initial_pane_id= 0;
         //let config = alacritty_terminal::term::Config {..Default::default()};
         let term_event_tx_opt = None;
-        let terminal = None;
 
         let mut app = App {
             core,
@@ -2924,29 +5597,58 @@ impl Application for App {
             nav_model: segmented_button::ModelBuilder::default().build(),
             tab_model1: segmented_button::ModelBuilder::default().build(),
             tab_model2: segmented_button::ModelBuilder::default().build(),
+            closed_tabs: VecDeque::new(),
             pane_model,
             term_event_tx_opt,
-            terminal,
+            terminal_model: segmented_button::ModelBuilder::default().build(),
+            terminal_id: widget::Id::unique(),
             active_panel: PaneType::LeftPane,
             show_button_row: flags.config.show_button_row,
             show_embedded_terminal: flags.config.show_embedded_terminal,
             show_second_panel: flags.config.show_second_panel,
+            quick_view: false,
+            pane_history_menu: None,
             config_handler: flags.config_handler,
             config: flags.config.clone(),
             mode: flags.mode,
             app_themes,
+            accent_colors,
+            pane_tints,
+            densities,
+            size_units,
+            date_formats,
+            first_days_of_week,
+            launcher_dirs,
+            toolbar_actions,
+            settings_page: SettingsPage::default(),
+            settings_pages,
+            settings_search: String::new(),
+            system_prefer_dark: None,
+            screen_reader: false,
+            bookmark_shortcuts,
+            preview_max_sizes,
+            preview_timeouts,
+            slideshow_intervals,
+            copy_workers,
+            cursor_styles,
             themes: HashMap::new(),
             theme_names_dark: Vec::new(),
             theme_names_light: Vec::new(),
             context_page: ContextPage::Preview(None, PreviewKind::Selected),
+            compare_opt: None,
             dialog_pages: VecDeque::new(),
             dialog_text_input: widget::Id::unique(),
             key_binds,
             key_binds_terminal,
             margin: HashMap::new(),
             mime_app_cache: mime_app::MimeAppCache::new(),
+            seen_mimes: Vec::new(),
             modifiers: Modifiers::empty(),
+            vi_pending: String::new(),
+            vi_visual: false,
+            saved_selections: HashMap::new(),
             mounter_items: HashMap::new(),
+            device_usage: HashMap::new(),
             network_drive_connecting: None,
             network_drive_input: String::new(),
             #[cfg(feature = "notify")]
@@ -2954,6 +5656,7 @@ impl Application for App {
             overlap: HashMap::new(),
             pending_operation_id: 0,
             pending_operations: BTreeMap::new(),
+            operation_started: HashMap::new(),
             _fileops: BTreeMap::new(),
             progress_operations: BTreeSet::new(),
             complete_operations: BTreeMap::new(),
@@ -2970,7 +5673,9 @@ impl Application for App {
             watcher_opt_left: None,
             watcher_opt_right: None,
             window_id_opt,
+            window_focused: true,
             windows: HashMap::new(),
+            editors: HashMap::new(),
             nav_dnd_hover: None,
             nav_dnd_hover_right: None,
             tab_dnd_hover: None,
@@ -3000,7 +5705,7 @@ impl Application for App {
                 if path.is_file() {
                     if let Some(parent) = path.parent() {
                         commands.push(app.open_tab(
-                            Location1::Path(parent.to_path_buf()),
+                            Location::Path(parent.to_path_buf()),
                             true,
                             Some(vec![path.to_path_buf()]),
                         ));
@@ -3015,7 +5720,7 @@ impl Application for App {
                 if path.is_file() {
                     if let Some(parent) = path.parent() {
                         commands.push(app.open_tab_right(
-                            Location2::Path(parent.to_path_buf()),
+                            Location::Path(parent.to_path_buf()),
                             true,
                             Some(vec![path.to_path_buf()]),
                         ));
@@ -3023,37 +5728,79 @@ impl Application for App {
                     }
                 }
             }
-            commands.push(app.open_tab(location, true, None));
+            commands.push(app.open_tab_right(location.clone(), true, None));
+        }
+        for path in flags.selected.iter() {
+            if let Some(parent) = path.parent() {
+                commands.push(app.open_tab(
+                    Location::Path(parent.to_path_buf()),
+                    true,
+                    Some(vec![path.clone()]),
+                ));
+            }
         }
         // restore previously opened tabs
         for i in 0..app.config.paths_left.len() {
             commands.push(app.open_tab(
-                Location1::Path(PathBuf::from(&app.config.paths_left[i])),
+                Location::Path(PathBuf::from(&app.config.paths_left[i])),
                 true,
                 None,
             ));
+            if let Some(state) = app.config.tab_states_left.get(i).copied() {
+                let entity = app.tab_model1.active();
+                app.set_tab_state(PaneType::LeftPane, entity, state);
+            }
         }
         for i in 0..app.config.paths_right.len() {
             commands.push(app.open_tab_right(
-                Location2::Path(PathBuf::from(&app.config.paths_right[i])),
+                Location::Path(PathBuf::from(&app.config.paths_right[i])),
                 true,
                 None,
             ));
+            if let Some(state) = app.config.tab_states_right.get(i).copied() {
+                let entity = app.tab_model2.active();
+                app.set_tab_state(PaneType::RightPane, entity, state);
+            }
         }
-        if app.config.paths_left.len() == 0 && flags.locations1.len() == 0 {
+        if app.config.paths_left.len() == 0
+            && flags.locations1.len() == 0
+            && flags.selected.is_empty()
+        {
             if let Ok(current_dir) = env::current_dir() {
-                commands.push(app.open_tab(Location1::Path(current_dir), true, None));
+                commands.push(app.open_tab(Location::Path(current_dir), true, None));
             } else {
-                commands.push(app.open_tab(Location1::Path(home_dir()), true, None));
+                commands.push(app.open_tab(Location::Path(home_dir()), true, None));
             }
         }
         if app.config.paths_right.len() == 0 && flags.locations2.len() == 0 {
             if let Ok(current_dir) = env::current_dir() {
-                commands.push(app.open_tab_right(Location2::Path(current_dir), true, None));
+                commands.push(app.open_tab_right(Location::Path(current_dir), true, None));
             } else {
-                commands.push(app.open_tab_right(Location2::Path(home_dir()), true, None));
+                commands.push(app.open_tab_right(Location::Path(home_dir()), true, None));
+            }
+        }
+        // Startup options only apply to this window and are not saved
+        if let Some(view) = flags.view {
+            let entities: Vec<_> = app.tab_model1.iter().collect();
+            for entity in entities {
+                if let Some(tab) = app.tab_model1.data_mut::<Tab>(entity) {
+                    tab.config.view = view;
+                }
+            }
+            let entities: Vec<_> = app.tab_model2.iter().collect();
+            for entity in entities {
+                if let Some(tab) = app.tab_model2.data_mut::<Tab>(entity) {
+                    tab.config.view = match view {
+                        tab::View::Grid => tab::View::Grid,
+                        tab::View::List => tab::View::List,
+                    };
+                }
             }
         }
+        if flags.show_settings {
+            app.context_page = ContextPage::Settings;
+            app.core.window.show_context = true;
+        }
         app.core.nav_bar_set_toggled(false);
         (app, Task::batch(commands))
     }
@@ -3075,25 +5822,26 @@ impl Application for App {
             cosmic::app::Message::App(Message::DndDropNav(entity, data, action))
         })
         .on_context(|entity| cosmic::app::Message::App(Message::NavBarContext(entity)))
-        .on_close(|entity| cosmic::app::Message::App(Message::NavBarClose(entity)))
         .on_middle_press(|entity| {
             cosmic::app::Message::App(Message::NavMenuAction(NavMenuAction::OpenInNewTab(entity)))
         })
         .context_menu(self.nav_context_menu(self.nav_bar_context_id))
-        .close_icon(
-            widget::icon::from_name("media-eject-symbolic")
-                .size(16)
-                .icon(),
-        )
         .into_container();
 
+        let mut devices = widget::container(self.devices_view().map(cosmic::app::Message::App));
         if !self.core().is_condensed() {
             nav = nav.max_width(280);
+            devices = devices.width(Length::Fixed(280.0));
         }
 
         Some(Element::from(
-            // XXX both must be shrink to avoid flex layout from ignoring it
-            nav.width(Length::Shrink).height(Length::Shrink),
+            widget::column::with_children(vec![
+                // XXX both must be shrink to avoid flex layout from ignoring it
+                nav.width(Length::Shrink).height(Length::Shrink).into(),
+                devices.into(),
+            ])
+            .width(Length::Shrink)
+            .height(Length::Shrink),
         ))
     }
 
@@ -3102,11 +5850,11 @@ impl Application for App {
         entity: widget::nav_bar::Id,
     ) -> Option<Vec<widget::menu::Tree<cosmic::app::Message<Self::Message>>>> {
         let favorite_index_opt = self.nav_model.data::<FavoriteIndex>(entity);
-        let location_opt = self.nav_model.data::<Location1>(entity);
+        let location_opt = self.nav_model.data::<Location>(entity);
         if self.active_panel == PaneType::RightPane && location_opt.is_some() {
             let location_opt2;
             if let Some(path) = location_opt.unwrap().path_opt() {
-                location_opt2 = Some(Location2::Path(path.to_owned()));
+                location_opt2 = Some(Location::Path(path.to_owned()));
             } else {
                 location_opt2 = None;
             }
@@ -3153,7 +5901,7 @@ impl Application for App {
                     NavMenuAction::RemoveFromSidebar(entity),
                 ));
             }
-            if matches!(location_opt, Some(Location1::Trash)) {
+            if matches!(location_opt, Some(Location::Trash)) {
                 items.push(cosmic::widget::menu::Item::Button(
                     fl!("empty-trash"),
                     None,
@@ -3205,7 +5953,7 @@ impl Application for App {
                     NavMenuAction::RemoveFromSidebar(entity),
                 ));
             }
-            if matches!(location_opt, Some(Location1::Trash)) {
+            if matches!(location_opt, Some(Location::Trash)) {
                 items.push(cosmic::widget::menu::Item::Button(
                     fl!("empty-trash"),
                     None,
@@ -3226,26 +5974,20 @@ impl Application for App {
 
     fn on_nav_select(&mut self, entity: Entity) -> Task<Self::Message> {
         self.nav_model.activate(entity);
-        if let Some(location) = self.nav_model.data::<Location1>(entity) {
+        if let Some(location) = self.nav_model.data::<Location>(entity) {
             if self.active_panel == PaneType::LeftPane {
-                let message = Message::TabMessage(None, tab1::Message::Location(location.clone()));
+                let message = Message::TabMessage(None, tab::Message::Location(location.clone()));
                 return self.update(message);
             } else {
                 let location2;
                 if let Some(path) = location.path_opt() {
-                    location2 = Location2::Path(path.to_owned());
+                    location2 = Location::Path(path.to_owned());
                     let message =
-                        Message::TabMessageRight(None, tab2::Message::Location(location2.clone()));
+                        Message::TabMessageRight(None, tab::Message::Location(location2.clone()));
                     return self.update(message);
                 }
             }
         }
-
-        if let Some(data) = self.nav_model.data::<MounterData>(entity) {
-            if let Some(mounter) = MOUNTERS.get(&data.0) {
-                return mounter.mount(data.1.clone()).map(|_| message::none());
-            }
-        }
         Task::none()
     }
 
@@ -3291,7 +6033,7 @@ impl Application for App {
 
         if self.active_panel == PaneType::LeftPane {
             let entity = self.tab_model1.active();
-            if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+            if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                 if tab.gallery {
                     tab.gallery = false;
                     return Task::none();
@@ -3306,6 +6048,11 @@ impl Application for App {
                     return Task::none();
                 }
 
+                if tab.rename.is_some() {
+                    tab.rename = None;
+                    return Task::none();
+                }
+
                 let had_focused_button = tab.select_focus_id().is_some();
                 if tab.select_none() {
                     if had_focused_button {
@@ -3317,7 +6064,7 @@ impl Application for App {
             }
         } else {
             let entity = self.tab_model2.active();
-            if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+            if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                 if tab.gallery {
                     tab.gallery = false;
                     return Task::none();
@@ -3332,6 +6079,11 @@ impl Application for App {
                     return Task::none();
                 }
 
+                if tab.rename.is_some() {
+                    tab.rename = None;
+                    return Task::none();
+                }
+
                 let had_focused_button = tab.select_focus_id().is_some();
                 if tab.select_none() {
                     if had_focused_button {
@@ -3376,6 +6128,61 @@ impl Application for App {
         }
 
         match message {
+            Message::AddBookmark(entity_opt) => {
+                let path_opt = if self.active_panel == PaneType::LeftPane {
+                    let entity = entity_opt.unwrap_or_else(|| self.tab_model1.active());
+                    self.tab_model1
+                        .data::<Tab>(entity)
+                        .and_then(|tab| tab.location.path_opt().cloned())
+                } else {
+                    let entity = entity_opt.unwrap_or_else(|| self.tab_model2.active());
+                    self.tab_model2
+                        .data::<Tab>(entity)
+                        .and_then(|tab| tab.location.path_opt().cloned())
+                };
+                if let Some(path) = path_opt {
+                    let mut bookmarks = self.config.bookmarks.clone();
+                    if !bookmarks.iter().any(|bookmark| bookmark.path == path) {
+                        bookmarks.push(Bookmark {
+                            path,
+                            ..Default::default()
+                        });
+                    }
+                    config_set!(bookmarks, bookmarks);
+                    return self.update_config();
+                }
+            }
+            Message::AddToArchive(entity_opt) => {
+                let paths = self.selected_paths(entity_opt);
+                if paths.is_empty() {
+                    return Task::none();
+                }
+                // An archive selected in the other pane is suggested, or its folder
+                let pane = self.other_pane();
+                let tab_model = self.tab_model(pane);
+                let other_selected: Vec<PathBuf> = tab_model
+                    .data::<Tab>(tab_model.active())
+                    .map(|tab| {
+                        tab.selected_locations()
+                            .into_iter()
+                            .filter_map(|location| location.path_opt().cloned())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let archive = match other_selected.as_slice() {
+                    [archive] if operation::archive_appendable(archive) => {
+                        archive.to_string_lossy().to_string()
+                    }
+                    _ => self
+                        .pane_dirs()
+                        .1
+                        .map(|dir| format!("{}/", dir.display()))
+                        .unwrap_or_default(),
+                };
+                self.dialog_pages
+                    .push_back(DialogPage::AddToArchive { paths, archive });
+                return widget::text_input::focus(self.dialog_text_input.clone());
+            }
             Message::AddToSidebar(entity_opt) => {
                 let mut favorites = self.config.favorites.clone();
                 for path in self.selected_paths(entity_opt) {
@@ -3387,18 +6194,155 @@ impl Application for App {
                 config_set!(favorites, favorites);
                 return self.update_config();
             }
+            Message::AccentColor(accent_color) => {
+                config_set!(accent_color, accent_color);
+                return self.update_config();
+            }
             Message::AppTheme(app_theme) => {
                 config_set!(app_theme, app_theme);
                 return self.update_config();
             }
+            Message::ArrangeDesktop(entity_opt) => {
+                if self.active_panel == PaneType::LeftPane {
+                    return self.update(Message::TabMessage(
+                        entity_opt,
+                        tab::Message::ArrangeDesktop,
+                    ));
+                } else {
+                    return self.update(Message::TabMessageRight(
+                        entity_opt,
+                        tab::Message::ArrangeDesktop,
+                    ));
+                }
+            }
+            Message::BookmarkShortcut(digit) => {
+                if let Some(index) = self
+                    .config
+                    .bookmarks
+                    .iter()
+                    .position(|bookmark| bookmark.shortcut == Some(digit))
+                {
+                    return self.update(Message::OpenBookmark(index));
+                }
+            }
+            Message::ClearRunChoices => {
+                config_set!(run_choices, BTreeMap::new());
+                return self.update_config();
+            }
             Message::ClearScrollback(_entity_opt) => {
-                if let Some(terminalmutex) = &self.terminal.as_mut() {
+                if let Some(terminalmutex) = self.terminal() {
                     if let Ok(terminal) = terminalmutex.lock() {
                         let mut term = terminal.term.lock();
                         term.grid_mut().clear_history();
                     }
                 }
             }
+            Message::ColorRules(color_rules) => {
+                config_set!(color_rules, color_rules);
+                return self.rescan_tabs();
+            }
+            Message::ColorSchemeImported(result) => match result {
+                Ok(color_scheme) => {
+                    let color_scheme_kind = terminal_theme::color_scheme_kind(&color_scheme);
+                    let name = color_scheme.name.clone();
+                    let mut color_schemes = self.config.color_schemes(color_scheme_kind).clone();
+                    let color_scheme_id = color_schemes
+                        .keys()
+                        .next_back()
+                        .map_or(ColorSchemeId(0), |id| ColorSchemeId(id.0 + 1));
+                    color_schemes.insert(color_scheme_id, color_scheme);
+                    match color_scheme_kind {
+                        ColorSchemeKind::Dark => {
+                            config_set!(color_schemes_dark, color_schemes);
+                        }
+                        ColorSchemeKind::Light => {
+                            config_set!(color_schemes_light, color_schemes);
+                        }
+                    }
+                    return Task::batch([
+                        self.update_config(),
+                        self.toasts
+                            .push(widget::toaster::Toast::new(fl!(
+                                "color-scheme-imported",
+                                name = name
+                            )))
+                            .map(cosmic::app::Message::App),
+                    ]);
+                }
+                Err(err) => {
+                    return self
+                        .toasts
+                        .push(widget::toaster::Toast::new(err))
+                        .map(cosmic::app::Message::App);
+                }
+            },
+            Message::Compare(entity_opt) => {
+                let Some((left, right)) = self.compare_paths(entity_opt) else {
+                    return Task::none();
+                };
+                let diff_tool = self.config.diff_tool.trim();
+                if diff_tool.is_empty() {
+                    return Task::perform(
+                        async move {
+                            let result = tokio::task::spawn_blocking(move || {
+                                diff::TextDiff::new(&left, &right)
+                            })
+                            .await
+                            .unwrap();
+                            message::app(Message::CompareResult(result.map(Arc::new)))
+                        },
+                        |x| x,
+                    );
+                }
+                match shlex::split(diff_tool).filter(|args| !args.is_empty()) {
+                    Some(args) => {
+                        let mut command = process::Command::new(&args[0]);
+                        command.args(&args[1..]).arg(&left).arg(&right);
+                        if let Err(err) = spawn_detached(&mut command) {
+                            log::warn!("failed to run diff tool {:?}: {}", diff_tool, err);
+                        }
+                    }
+                    None => {
+                        log::warn!("invalid diff tool {:?}", diff_tool);
+                    }
+                }
+            }
+            Message::CompareClose => {
+                self.compare_opt = None;
+            }
+            Message::CompareResult(result) => match result {
+                Ok(text_diff) => {
+                    self.compare_opt = Some(text_diff);
+                }
+                Err(err) => {
+                    return self
+                        .toasts
+                        .push(widget::toaster::Toast::new(err))
+                        .map(cosmic::app::Message::App);
+                }
+            },
+            Message::ContextMenu(entity_opt) => {
+                if self.active_panel == PaneType::LeftPane {
+                    return self.update(Message::TabMessage(
+                        entity_opt,
+                        tab::Message::ContextMenuFocus,
+                    ));
+                } else {
+                    return self.update(Message::TabMessageRight(
+                        entity_opt,
+                        tab::Message::ContextMenuFocus,
+                    ));
+                }
+            }
+            Message::CreateLauncher(entity_opt) => {
+                if let Some(path) = self.selected_paths(entity_opt).into_iter().next() {
+                    self.dialog_pages.push_back(DialogPage::CreateLauncher {
+                        launcher: Launcher::new(&path),
+                        dir: LauncherDir::default(),
+                    });
+                    return widget::text_input::focus(self.dialog_text_input.clone());
+                }
+            }
             Message::Compress(entity_opt) => {
                 let paths = self.selected_paths(entity_opt);
                 if let Some(current_path) = paths.first() {
@@ -3417,6 +6361,26 @@ impl Application for App {
                     }
                 }
             }
+            Message::PackToOtherPane(entity_opt) => {
+                // Archives are created in the folder of the other pane by default
+                let paths = self.selected_paths(entity_opt);
+                let (_, other_dir_opt) = self.pane_dirs();
+                let name_opt = paths
+                    .first()
+                    .and_then(|path| path.file_stem())
+                    .and_then(|name| name.to_str());
+                if let Some((to, name)) = other_dir_opt.zip(name_opt) {
+                    let name = name.to_string();
+                    self.dialog_pages.push_back(DialogPage::Compress {
+                        paths,
+                        to,
+                        name,
+                        archive_type: ArchiveType::default(),
+                        password: None,
+                    });
+                    return widget::text_input::focus(self.dialog_text_input.clone());
+                }
+            }
             Message::Config(config) => {
                 if config != self.config {
                     log::info!("update config");
@@ -3427,13 +6391,26 @@ impl Application for App {
                     return self.update_config();
                 }
             }
+            Message::ClickEmptyClearsSelection(pane_opt, clears) => {
+                if pane_opt != Some(PaneType::RightPane) {
+                    let mut config_left = self.config.tab_left;
+                    config_left.click_empty_clears_selection = clears;
+                    config_set!(tab_left, config_left);
+                }
+                if pane_opt != Some(PaneType::LeftPane) {
+                    let mut config_right = self.config.tab_right;
+                    config_right.click_empty_clears_selection = clears;
+                    config_set!(tab_right, config_right);
+                }
+                return self.update_config();
+            }
             Message::Copy(entity_opt) => {
                 let paths = self.selected_paths(entity_opt);
                 let contents = ClipboardCopy::new(ClipboardKind::Copy, &paths);
                 return clipboard::write_data(contents);
             }
             Message::CopyTerminal(_entity_opt) => {
-                if let Some(terminal) = self.terminal.as_mut() {
+                if let Some(terminal) = self.terminal() {
                     let terminal = terminal.lock().unwrap();
                     let term = terminal.term.lock();
                     if let Some(text) = term.selection_to_string() {
@@ -3444,7 +6421,7 @@ impl Application for App {
                 }
             }
             Message::CopyOrSigint(_entity_opt) => {
-                if let Some(terminalmutex) = self.terminal.as_mut() {
+                if let Some(terminalmutex) = self.terminal() {
                     if let Ok(terminal) = terminalmutex.lock() {
                         let term = terminal.term.lock();
                         if let Some(text) = term.selection_to_string() {
@@ -3459,7 +6436,7 @@ impl Application for App {
                 }
             }
             Message::CopyPrimary(_entity_opt) => {
-                if let Some(terminalmutex) = self.terminal.as_mut() {
+                if let Some(terminalmutex) = self.terminal() {
                     if let Ok(terminal) = terminalmutex.lock() {
                         let term = terminal.term.lock();
                         if let Some(text) = term.selection_to_string() {
@@ -3471,47 +6448,31 @@ impl Application for App {
                 }
             }
             Message::CopyTab(_entity_opt) => {
-                let entity;
-                // get the selected paths of the active panel
-                let tempactive;
-                let saveactive;
-                if self.active_panel == PaneType::LeftPane {
-                    entity = self.tab_model1.active();
-                    tempactive = PaneType::RightPane;
-                    saveactive = PaneType::LeftPane;
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
-                        let location = tab.location.clone();
-                        let newlocation = convert_location1_to_location2(&location);
-                        // create a new tab in the other panel
-                        self.active_panel = tempactive;
-                        let _ = self.update(Message::TabCreateRight(Some(newlocation.clone())));
-                        let _ = self.update_title();
-                        let _ = self.update_watcher_right();
-                        let _ = self.update_tab_right(entity, newlocation, None);
-                        self.active_panel = saveactive;
-                    }
+                return self.copy_tab_to_other_pane(false);
+            }
+            Message::CopyText(entity_opt, copy_text) => {
+                let paths = if copy_text == CopyText::Directory {
+                    self.pane_dirs().0.into_iter().collect()
                 } else {
-                    entity = self.tab_model2.active();
-                    tempactive = PaneType::LeftPane;
-                    saveactive = PaneType::RightPane;
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
-                        let location = tab.location.clone();
-                        // create a new tab in the other panel
-                        self.active_panel = tempactive;
-                        let newlocation = convert_location2_to_location1(&location);
-                        let _ = self.update(Message::TabCreateLeft(Some(newlocation.clone())));
-                        let _ = self.update_title();
-                        let _ = self.update_watcher_left();
-                        let _ = self.update_tab_left(entity, newlocation, None);
-                        self.active_panel = saveactive;
-                    }
+                    self.selected_paths(entity_opt)
+                };
+                if paths.is_empty() {
+                    return Task::none();
                 }
+                let text = copy_text.text(&paths, self.config.quote_copied_paths);
+                return clipboard::write(text);
             }
             Message::Cut(entity_opt) => {
                 let paths = self.selected_paths(entity_opt);
                 let contents = ClipboardCopy::new(ClipboardKind::Cut, &paths);
                 return clipboard::write_data(contents);
             }
+            Message::DefaultApp(mime, index) => {
+                if let Some(app) = self.mime_app_cache.get(&mime).get(index) {
+                    let id = app.id.clone();
+                    self.mime_app_cache.set_default(mime, id);
+                }
+            }
             Message::CloseToast(id) => {
                 self.toasts.remove(id);
             }
@@ -3532,12 +6493,77 @@ impl Application for App {
                     }
                 }
             }
+            Message::DateFormat(date_format) => {
+                config_set!(
+                    formats,
+                    FormatConfig {
+                        date_format,
+                        ..self.config.formats
+                    }
+                );
+                return self.update_config();
+            }
+            Message::Density(density) => {
+                config_set!(density, density);
+                // Icons are resized with the spacing, they can be zoomed again afterwards
+                let mut config_left = self.config.tab_left;
+                config_left.icon_sizes = density.icon_sizes();
+                config_set!(tab_left, config_left);
+                let mut config_right = self.config.tab_right;
+                config_right.icon_sizes = density.icon_sizes();
+                config_set!(tab_right, config_right);
+                return self.update_config();
+            }
             Message::DesktopConfig(config) => {
                 if config != self.config.desktop {
                     config_set!(desktop, config);
                     return self.update_desktop();
                 }
             }
+            Message::DesktopLayout(key, layout) => {
+                let mut desktop_layouts = self.config.desktop_layouts.clone();
+                if layout.is_empty() {
+                    desktop_layouts.remove(&key);
+                } else {
+                    desktop_layouts.insert(key, layout);
+                }
+                config_set!(desktop_layouts, desktop_layouts);
+            }
+            Message::DeviceEject(mounter_key, item) => {
+                if let Some(mounter) = MOUNTERS.get(&mounter_key) {
+                    return mounter.eject(item).map(|_| message::none());
+                }
+            }
+            Message::DeviceMount(mounter_key, item) => {
+                if let Some(mounter) = MOUNTERS.get(&mounter_key) {
+                    return mounter.mount(item).map(|_| message::none());
+                }
+            }
+            Message::DeviceOpen(mounter_key, item) => match item.path() {
+                Some(path) => return self.update(Message::GoLocation(Location::Path(path))),
+                None => return self.update(Message::DeviceMount(mounter_key, item)),
+            },
+            Message::DeviceUnmount(mounter_key, item) => {
+                if let Some(mounter) = MOUNTERS.get(&mounter_key) {
+                    return mounter.unmount(item).map(|_| message::none());
+                }
+            }
+            Message::DoubleClickOpen(pane_opt, double_click_open) => {
+                if pane_opt != Some(PaneType::RightPane) {
+                    let mut config_left = self.config.tab_left;
+                    config_left.double_click_open = double_click_open;
+                    config_set!(tab_left, config_left);
+                }
+                if pane_opt != Some(PaneType::LeftPane) {
+                    let mut config_right = self.config.tab_right;
+                    config_right.double_click_open = double_click_open;
+                    config_set!(tab_right, config_right);
+                }
+                return self.update_config();
+            }
+            Message::DiffTool(diff_tool) => {
+                config_set!(diff_tool, diff_tool);
+            }
             Message::DesktopViewOptions => {
                 let mut settings = window::Settings {
                     decorations: true,
@@ -3565,6 +6591,16 @@ impl Application for App {
             Message::DialogComplete => {
                 if let Some(dialog_page) = self.dialog_pages.pop_front() {
                     match dialog_page {
+                        DialogPage::AddToArchive { paths, archive } => {
+                            let archive = PathBuf::from(archive);
+                            if operation::archive_appendable(&archive) {
+                                self.operation(Operation::AddToArchive { paths, archive });
+                            }
+                        }
+                        DialogPage::Bookmarks { bookmarks } => {
+                            config_set!(bookmarks, bookmarks);
+                            return self.update_config();
+                        }
                         DialogPage::Compress {
                             paths,
                             to,
@@ -3582,12 +6618,70 @@ impl Application for App {
                                 password,
                             })
                         }
+                        DialogPage::CreateLauncher { launcher, dir } => {
+                            let result = match dir.path() {
+                                Some(dir) => launcher.create(&dir),
+                                None => Err(fl!("launcher-no-folder")),
+                            };
+                            let text = match result {
+                                Ok(path) => {
+                                    // The new launcher is offered in Open With
+                                    self.mime_app_cache.reload();
+                                    fl!("launcher-created", path = path.display().to_string())
+                                }
+                                Err(err) => err,
+                            };
+                            return self
+                                .toasts
+                                .push(widget::toaster::Toast::new(text))
+                                .map(cosmic::app::Message::App);
+                        }
+                        DialogPage::ConfirmExecute { path, remember, .. } => {
+                            if remember {
+                                let run_choices = self.run_choices_with(&path, RunChoice::Run);
+                                config_set!(run_choices, run_choices);
+                            }
+                            return self.run_or_open(&path, RunChoice::Run);
+                        }
+                        DialogPage::ConfirmMove { to, contents } => {
+                            return self.update(Message::PasteContents(to, contents));
+                        }
+                        DialogPage::ConfirmTrash { paths } => {
+                            return self.operation_previewed_confirmed(Operation::Delete { paths });
+                        }
                         DialogPage::EmptyTrash => {
                             self.operation(Operation::EmptyTrash);
                         }
-                        DialogPage::FailedOperation(id) => {
-                            log::warn!("TODO: retry operation {}", id);
+                        DialogPage::ExportListing {
+                            dir,
+                            to,
+                            recursive,
+                            format,
+                            columns,
+                        } => {
+                            self.operation(Operation::ExportListing {
+                                dir,
+                                to: PathBuf::from(to),
+                                recursive,
+                                format,
+                                columns,
+                            });
+                        }
+                        DialogPage::InsufficientSpace { operation, .. } => {
+                            if !self.config.refuse_insufficient_space {
+                                return self.operation_previewed(operation);
+                            }
                         }
+                        DialogPage::FailedOperation(id) => match self.failed_operations.get(&id) {
+                            Some((operation, _, err))
+                                if err.is_permission_denied()
+                                    && operation.elevated_command().is_some() =>
+                            {
+                                let operation = Box::new(operation.clone());
+                                self.operation(Operation::Elevated { operation });
+                            }
+                            _ => log::warn!("TODO: retry operation {}", id),
+                        },
                         DialogPage::ExtractPassword { id, password } => {
                             let (operation, _, _err) = self.failed_operations.get(&id).unwrap();
                             let new_op = match &operation {
@@ -3600,6 +6694,33 @@ impl Application for App {
                             };
                             self.operation(new_op);
                         }
+                        DialogPage::LoadSelection { path } => {
+                            let path = PathBuf::from(path);
+                            // Relative paths in the list are in the folder of the pane
+                            let dir_opt = self.pane_dirs().0;
+                            return Task::perform(
+                                async move {
+                                    let result = tokio::task::spawn_blocking(move || {
+                                        let text = fs::read_to_string(&path).map_err(|err| {
+                                            format!("{}: {}", path.display(), err)
+                                        })?;
+                                        let dir = dir_opt
+                                            .or_else(|| path.parent().map(Path::to_path_buf))
+                                            .unwrap_or_default();
+                                        Ok(text
+                                            .lines()
+                                            .map(str::trim)
+                                            .filter(|line| !line.is_empty())
+                                            .map(|line| dir.join(line))
+                                            .collect())
+                                    })
+                                    .await
+                                    .unwrap();
+                                    message::app(Message::SelectionLoaded(result))
+                                },
+                                |x| x,
+                            );
+                        }
                         DialogPage::MountError {
                             mounter_key,
                             item,
@@ -3634,64 +6755,177 @@ impl Application for App {
                                 self.update(Message::NetworkDriveSubmit),
                             ]);
                         }
-                        DialogPage::NewItem { parent, name, dir } => {
+                        DialogPage::NewItem {
+                            parent,
+                            name,
+                            dir,
+                            contents,
+                        } => {
                             let path = parent.join(name);
                             self.operation(if dir {
                                 Operation::NewFolder { path }
                             } else {
-                                Operation::NewFile { path }
+                                Operation::NewFile {
+                                    path,
+                                    contents: contents.unwrap_or_default(),
+                                }
                             });
                         }
                         DialogPage::OpenWith {
                             path,
                             mime,
                             selected,
+                            custom_command,
+                            set_default,
                             ..
                         } => {
-                            if let Some(app) = self.mime_app_cache.get(&mime).get(selected) {
-                                if let Some(mut command) = app.command(Some(path.clone().into())) {
-                                    match spawn_detached(&mut command) {
-                                        Ok(()) => {
-                                            let _ = recently_used_xbel::update_recently_used(
-                                                &path,
-                                                App::APP_ID.to_string(),
-                                                "commander".to_string(),
-                                                None,
-                                            );
-                                        }
-                                        Err(err) => {
-                                            log::warn!(
-                                                "failed to open {:?} with {:?}: {}",
-                                                path,
-                                                app.id,
-                                                err
-                                            )
-                                        }
+                            let custom_command = custom_command.trim();
+                            let (command_opt, id, exec) = if !custom_command.is_empty() {
+                                (
+                                    mime_app::custom_command(custom_command, path.clone().into()),
+                                    custom_command.to_string(),
+                                    custom_command.to_string(),
+                                )
+                            } else if let Some(app) =
+                                self.mime_app_cache.open_with(&mime).nth(selected)
+                            {
+                                (
+                                    app.command(Some(path.clone().into())),
+                                    app.id.clone(),
+                                    app.exec.clone().unwrap_or_default(),
+                                )
+                            } else {
+                                return Task::none();
+                            };
+                            if let Some(mut command) = command_opt {
+                                match spawn_detached(&mut command) {
+                                    Ok(()) => {
+                                        recents::add(&path, &id, &exec);
+                                    }
+                                    Err(err) => {
+                                        log::warn!(
+                                            "failed to open {:?} with {:?}: {}",
+                                            path,
+                                            id,
+                                            err
+                                        )
                                     }
+                                }
+                            } else {
+                                log::warn!(
+                                    "failed to open {:?} with {:?}: failed to get command",
+                                    path,
+                                    id
+                                );
+                            }
+                            if set_default {
+                                if custom_command.is_empty() {
+                                    self.mime_app_cache.set_default(mime, id);
                                 } else {
-                                    log::warn!(
-                                        "failed to open {:?} with {:?}: failed to get command",
-                                        path,
-                                        app.id
-                                    );
+                                    match mime_app::create_custom_app(custom_command, &mime) {
+                                        Ok(id) => self.mime_app_cache.set_default(mime, id),
+                                        Err(err) => log::warn!(
+                                            "failed to create application for {:?}: {}",
+                                            custom_command,
+                                            err
+                                        ),
+                                    }
                                 }
                             }
                         }
+                        DialogPage::PreviewChanges { operation, .. } => {
+                            self.operation(operation);
+                        }
+                        DialogPage::Print { job, options } => {
+                            return Task::perform(
+                                async move {
+                                    let result = tokio::task::spawn_blocking(move || {
+                                        print::print(&job, options)
+                                    })
+                                    .await
+                                    .unwrap();
+                                    message::app(Message::Printed(result))
+                                },
+                                |x| x,
+                            );
+                        }
                         DialogPage::RenameItem {
                             from, parent, name, ..
                         } => {
                             let to = parent.join(name);
                             self.operation(Operation::Rename { from, to });
                         }
+                        DialogPage::ItemFailed { .. } => {
+                            log::warn!("failed item dialog should be completed with error result");
+                        }
                         DialogPage::Replace1 { .. } => {
                             log::warn!("replace dialog should be completed with replace result");
                         }
                         DialogPage::Replace2 { .. } => {
                             log::warn!("replace dialog should be completed with replace result");
                         }
+                        DialogPage::QuickJump { search } => {
+                            if let Some(path) = self.quick_jump_results(&search).into_iter().next()
+                            {
+                                return self.update(Message::QuickJumpOpen(path));
+                            }
+                        }
+                        DialogPage::SendTo { .. } => {}
                         DialogPage::SetExecutableAndLaunch { path } => {
                             self.operation(Operation::SetExecutableAndLaunch { path });
                         }
+                        DialogPage::SkippedItems { items } => {
+                            let report: Vec<&str> =
+                                items.iter().map(|(_path, error)| error.as_str()).collect();
+                            return clipboard::write(report.join("\n"));
+                        }
+                        DialogPage::SyncProfiles { profiles } => {
+                            let profiles = profiles
+                                .into_iter()
+                                .filter(|profile| {
+                                    !profile.name.trim().is_empty()
+                                        && !profile.source.as_os_str().is_empty()
+                                        && !profile.destination.as_os_str().is_empty()
+                                })
+                                .collect();
+                            config_set!(sync_profiles, profiles);
+                        }
+                        DialogPage::TermRename { entity, name } => {
+                            let name = name.trim();
+                            if let Some(terminal) =
+                                self.terminal_model
+                                    .data::<Mutex<crate::terminal::Terminal>>(entity)
+                            {
+                                // An empty name uses the title set by the shell again
+                                terminal.lock().unwrap().tab_title_override =
+                                    (!name.is_empty()).then(|| name.to_string());
+                            }
+                            if !name.is_empty() {
+                                self.terminal_model.text_set(entity, name.to_string());
+                            }
+                        }
+                        DialogPage::TerminalProfiles {
+                            profiles,
+                            default_profile,
+                        } => {
+                            let default_profile =
+                                default_profile.filter(|id| profiles.contains_key(id));
+                            config_set!(profiles, profiles);
+                            config_set!(default_profile, default_profile);
+                        }
+                        DialogPage::Toolbar { actions } => {
+                            config_set!(toolbar, actions);
+                        }
+                        DialogPage::Tools { tools } => {
+                            let tools = tools
+                                .into_iter()
+                                .filter(|tool| {
+                                    !tool.name.trim().is_empty() && !tool.command.trim().is_empty()
+                                })
+                                .collect();
+                            config_set!(tools, tools);
+                        }
+                        DialogPage::VerifyChecksums { .. } => {}
                     }
                 }
             }
@@ -3713,22 +6947,68 @@ impl Application for App {
                 if self.active_panel == PaneType::LeftPane {
                     return self.update(Message::TabMessage(
                         entity_opt,
-                        tab1::Message::EditLocationEnable,
+                        tab::Message::EditLocationEnable,
                     ));
                 } else {
                     return self.update(Message::TabMessageRight(
                         entity_opt,
-                        tab2::Message::EditLocationEnable,
+                        tab::Message::EditLocationEnable,
                     ));
                 }
             }
+            Message::Editor(id, editor_message) => {
+                // Files in read-only panes are not saved
+                let read_only_opt = match editor_message {
+                    editor::Message::Save => self
+                        .editors
+                        .get(&id)
+                        .and_then(|editor| self.read_only_path(editor.path())),
+                    _ => None,
+                };
+                if let Some(editor) = self.editors.get_mut(&id) {
+                    match read_only_opt {
+                        Some(dir) => editor.set_status(fl!(
+                            "read-only-pane-error",
+                            dir = dir.display().to_string()
+                        )),
+                        None => editor.update(editor_message),
+                    }
+                    return self.set_window_title(editor.title(), id);
+                }
+            }
+            Message::EditBookmarks => {
+                return self.update(Message::DialogPush(DialogPage::Bookmarks {
+                    bookmarks: self.config.bookmarks.clone(),
+                }));
+            }
+            Message::EditSyncProfiles => {
+                return self.update(Message::DialogPush(DialogPage::SyncProfiles {
+                    profiles: self.config.sync_profiles.clone(),
+                }));
+            }
+            Message::EditTerminalProfiles => {
+                return self.update(Message::DialogPush(DialogPage::TerminalProfiles {
+                    profiles: self.config.profiles.clone(),
+                    default_profile: self.config.default_profile,
+                }));
+            }
+            Message::EditToolbar => {
+                return self.update(Message::DialogPush(DialogPage::Toolbar {
+                    actions: self.config.toolbar.clone(),
+                }));
+            }
+            Message::EditTools => {
+                return self.update(Message::DialogPush(DialogPage::Tools {
+                    tools: self.config.tools.clone(),
+                }));
+            }
             Message::EmptyTrash(entity_opt) => {
                 if self.active_panel == PaneType::LeftPane {
-                    return self.update(Message::TabMessage(entity_opt, tab1::Message::EmptyTrash));
+                    return self.update(Message::TabMessage(entity_opt, tab::Message::EmptyTrash));
                 } else {
                     return self.update(Message::TabMessageRight(
                         entity_opt,
-                        tab2::Message::EmptyTrash,
+                        tab::Message::EmptyTrash,
                     ));
                 }
             }
@@ -3736,15 +7016,55 @@ impl Application for App {
                 if self.active_panel == PaneType::LeftPane {
                     return self.update(Message::TabMessage(
                         entity_opt,
-                        tab1::Message::ExecEntryAction(None, action),
+                        tab::Message::ExecEntryAction(None, action),
                     ));
                 } else {
                     return self.update(Message::TabMessageRight(
                         entity_opt,
-                        tab2::Message::ExecEntryAction(None, action),
+                        tab::Message::ExecEntryAction(None, action),
                     ));
                 }
             }
+            Message::Duplicate(entity_opt) => {
+                // Copies into the folder of the original get a unique name from the operation
+                let mut groups: Vec<(PathBuf, Vec<PathBuf>)> = Vec::new();
+                for path in self.selected_paths(entity_opt) {
+                    let Some(parent) = path.parent() else {
+                        continue;
+                    };
+                    match groups.iter_mut().find(|(to, _)| to == parent) {
+                        Some((_, paths)) => paths.push(path),
+                        None => groups.push((parent.to_path_buf(), vec![path])),
+                    }
+                }
+                for (to, paths) in groups {
+                    self.operation(Operation::Copy { paths, to });
+                }
+            }
+            Message::ExportListing => {
+                let (dir_opt, other_dir_opt) = self.pane_dirs();
+                let Some(dir) = dir_opt else {
+                    return Task::none();
+                };
+                // The listing is written to the folder of the other pane
+                let format = ListingFormat::default();
+                let name = dir
+                    .file_name()
+                    .map_or_else(|| "listing".into(), |name| name.to_string_lossy());
+                let to = other_dir_opt.unwrap_or_else(|| dir.clone()).join(format!(
+                    "{}.{}",
+                    name,
+                    format.extension()
+                ));
+                self.dialog_pages.push_back(DialogPage::ExportListing {
+                    to: to.to_string_lossy().to_string(),
+                    dir,
+                    recursive: false,
+                    format,
+                    columns: ListingColumns::default(),
+                });
+                return widget::text_input::focus(self.dialog_text_input.clone());
+            }
             Message::ExtractHere(entity_opt) => {
                 let paths = self.selected_paths(entity_opt);
                 if let Some(destination) = paths
@@ -3784,71 +7104,23 @@ impl Application for App {
                 } else {
                     entity = self.tab_model2.active();
                 }
-                return self.update(Message::OpenWithDialog(Some(entity)));
-            }
-            Message::F5Copy => {
-                let to;
-                if self.active_panel == PaneType::LeftPane {
-                    let entity = self.tab_model1.active();
-                    // get the selected paths of the active panel
-                    let paths = self.selected_paths(Some(entity));
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(self.tab_model2.active()) {
-                        if let Some(path) = tab.location.path_opt() {
-                            to = path.to_owned();
-                        } else {
-                            return Task::none();
+                // Text files are edited in a window of the built-in editor, everything else
+                // is opened with an application
+                if let [path] = self.selected_paths(Some(entity)).as_slice() {
+                    if path.is_file() {
+                        match self.open_editor(path) {
+                            Ok(task) => return task,
+                            Err(err) => log::info!("not using the editor: {}", err),
                         }
-                    } else {
-                        return Task::none();
-                    }
-                    self.operation(Operation::Copy { paths, to });
-                } else {
-                    let entity = self.tab_model2.active();
-                    // get the selected paths of the active panel
-                    let paths = self.selected_paths(Some(entity));
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(self.tab_model1.active()) {
-                        if let Some(path) = tab.location.path_opt() {
-                            to = path.to_owned();
-                        } else {
-                            return Task::none();
-                        }
-                    } else {
-                        return Task::none();
                     }
-                    self.operation(Operation::Copy { paths, to });
                 }
+                return self.update(Message::OpenWithDialog(Some(entity)));
+            }
+            Message::F5Copy => {
+                return self.operation_to_other_pane(false);
             }
             Message::F6Move => {
-                let to;
-                if self.active_panel == PaneType::LeftPane {
-                    let entity = self.tab_model1.active();
-                    // get the selected paths of the active panel
-                    let paths = self.selected_paths(Some(entity));
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(self.tab_model2.active()) {
-                        if let Some(path) = tab.location.path_opt() {
-                            to = path.to_owned();
-                        } else {
-                            return Task::none();
-                        }
-                    } else {
-                        return Task::none();
-                    }
-                    self.operation(Operation::Move { paths, to });
-                } else {
-                    let entity = self.tab_model2.active();
-                    // get the selected paths of the active panel
-                    let paths = self.selected_paths(Some(entity));
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(self.tab_model1.active()) {
-                        if let Some(path) = tab.location.path_opt() {
-                            to = path.to_owned();
-                        } else {
-                            return Task::none();
-                        }
-                    } else {
-                        return Task::none();
-                    }
-                    self.operation(Operation::Move { paths, to });
-                }
+                return self.operation_to_other_pane(true);
             }
             Message::F7Mkdir => {
                 let entity;
@@ -3867,7 +7139,7 @@ impl Application for App {
                     if paths.len() == 0 {
                         return Task::none();
                     }
-                    self.operation(Operation::Delete { paths });
+                    return self.operation_previewed(Operation::Delete { paths });
                 } else {
                     let entity = self.tab_model2.active();
                     // get the selected paths of the active panel
@@ -3875,7 +7147,7 @@ impl Application for App {
                     if paths.len() == 0 {
                         return Task::none();
                     }
-                    self.operation(Operation::Delete { paths });
+                    return self.operation_previewed(Operation::Delete { paths });
                 }
             }
             Message::F9Terminal => {
@@ -3890,73 +7162,200 @@ impl Application for App {
             Message::F10Quit => {
                 return self.update(Message::WindowClose);
             }
-            Message::GalleryToggle(entity_opt) => {
+            #[cfg(feature = "dbus")]
+            Message::FileManager1(request) => {
+                let mut commands = Vec::new();
+                let show_properties =
+                    matches!(request, file_manager1::Request::ShowItemProperties(_));
+                match request {
+                    file_manager1::Request::ShowFolders(paths) => {
+                        for path in paths {
+                            commands.push(self.open_tab(Location::Path(path), true, None));
+                        }
+                    }
+                    file_manager1::Request::ShowItems(paths)
+                    | file_manager1::Request::ShowItemProperties(paths) => {
+                        // Items in the same folder are selected in one tab
+                        let mut by_parent: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+                        for path in paths {
+                            if let Some(parent) = path.parent() {
+                                by_parent
+                                    .entry(parent.to_path_buf())
+                                    .or_default()
+                                    .push(path);
+                            }
+                        }
+                        for (parent, paths) in by_parent {
+                            commands.push(self.open_tab(Location::Path(parent), true, Some(paths)));
+                        }
+                        if show_properties {
+                            self.context_page = ContextPage::Preview(None, PreviewKind::Selected);
+                            self.core.window.show_context = true;
+                        }
+                    }
+                }
+                if let Some(window_id) = self.window_id_opt {
+                    commands.push(window::gain_focus(window_id));
+                }
+                return Task::batch(commands);
+            }
+            Message::FirstDayOfWeek(first_day_of_week) => {
+                config_set!(
+                    formats,
+                    FormatConfig {
+                        first_day_of_week,
+                        ..self.config.formats
+                    }
+                );
+                return self.update_config();
+            }
+            Message::FocusNextPane => {
+                // Cycle through the left pane, the right pane and the terminal
+                let mut pane_types = vec![PaneType::LeftPane];
+                if self.show_second_panel {
+                    pane_types.push(PaneType::RightPane);
+                }
+                if self.show_embedded_terminal {
+                    pane_types.push(PaneType::TerminalPane);
+                }
+                let focused = match self.pane_model.type_by_pane.get(&self.pane_model.focus) {
+                    Some(PaneType::TerminalPane) => PaneType::TerminalPane,
+                    _ => self.active_panel,
+                };
+                let index = pane_types
+                    .iter()
+                    .position(|pane_type| *pane_type == focused)
+                    .map_or(0, |index| (index + 1) % pane_types.len());
+                return self.focus_pane(pane_types[index]);
+            }
+            Message::GoFavorite(favorite) => match favorite.path_opt() {
+                Some(path) => return self.update(Message::GoLocation(Location::Path(path))),
+                None => log::warn!("failed to find the folder of {:?}", favorite),
+            },
+            Message::GoLocation(location) => {
                 if self.active_panel == PaneType::LeftPane {
-                    return self.update(Message::TabMessage(
-                        entity_opt,
-                        tab1::Message::GalleryToggle,
+                    return self
+                        .update(Message::TabMessage(None, tab::Message::Location(location)));
+                } else {
+                    return self.update(Message::TabMessageRight(
+                        None,
+                        tab::Message::Location(location.clone()),
                     ));
+                }
+            }
+            Message::GalleryToggle(entity_opt) => {
+                if self.active_panel == PaneType::LeftPane {
+                    return self
+                        .update(Message::TabMessage(entity_opt, tab::Message::GalleryToggle));
                 } else {
                     return self.update(Message::TabMessageRight(
                         entity_opt,
-                        tab2::Message::GalleryToggle,
+                        tab::Message::GalleryToggle,
                     ));
                 }
             }
+            Message::HidePatterns(hide_patterns) => {
+                config_set!(hide_patterns, hide_patterns);
+                return self.rescan_tabs();
+            }
             Message::HistoryNext(entity_opt) => {
                 if self.active_panel == PaneType::LeftPane {
-                    return self.update(Message::TabMessage(entity_opt, tab1::Message::GoNext));
+                    return self.update(Message::TabMessage(entity_opt, tab::Message::GoNext));
                 } else {
-                    return self
-                        .update(Message::TabMessageRight(entity_opt, tab2::Message::GoNext));
+                    return self.update(Message::TabMessageRight(entity_opt, tab::Message::GoNext));
                 }
             }
             Message::HistoryPrevious(entity_opt) => {
                 if self.active_panel == PaneType::LeftPane {
-                    return self.update(Message::TabMessage(entity_opt, tab1::Message::GoPrevious));
+                    return self.update(Message::TabMessage(entity_opt, tab::Message::GoPrevious));
                 } else {
                     return self.update(Message::TabMessageRight(
                         entity_opt,
-                        tab2::Message::GoPrevious,
+                        tab::Message::GoPrevious,
                     ));
                 }
             }
+            Message::ImportColorScheme(entity_opt) => {
+                if let Some(path) = self
+                    .selected_paths(entity_opt)
+                    .into_iter()
+                    .find(|path| terminal_theme::is_color_scheme_file(path))
+                {
+                    return Task::perform(
+                        async move {
+                            let result =
+                                tokio::task::spawn_blocking(move || terminal_theme::import(&path))
+                                    .await
+                                    .unwrap();
+                            message::app(Message::ColorSchemeImported(result))
+                        },
+                        |x| x,
+                    );
+                }
+            }
+            Message::InsertTerminal(entity_opt, full_paths) => {
+                let mut words = Vec::new();
+                for path in self.selected_paths(entity_opt) {
+                    let word = if full_paths {
+                        path.to_string_lossy().into_owned()
+                    } else {
+                        match path.file_name() {
+                            Some(name) => name.to_string_lossy().into_owned(),
+                            None => continue,
+                        }
+                    };
+                    match shlex::try_quote(&word) {
+                        Ok(word) => words.push(word.into_owned()),
+                        Err(err) => {
+                            log::warn!("failed to quote {:?}: {}", word, err);
+                        }
+                    }
+                }
+                if words.is_empty() {
+                    return Task::none();
+                }
+                match self.terminal() {
+                    Some(terminal) => {
+                        // The trailing space separates the paths from what is typed next
+                        let terminal = terminal.lock().unwrap();
+                        terminal.input_scroll(format!("{} ", words.join(" ")).into_bytes());
+                    }
+                    None => {
+                        log::warn!("failed to insert paths: no terminal");
+                    }
+                }
+            }
             Message::ItemDown(entity_opt) => {
                 if self.active_panel == PaneType::LeftPane {
-                    return self.update(Message::TabMessage(entity_opt, tab1::Message::ItemDown));
+                    return self.update(Message::TabMessage(entity_opt, tab::Message::ItemDown));
                 } else {
-                    return self.update(Message::TabMessageRight(
-                        entity_opt,
-                        tab2::Message::ItemDown,
-                    ));
+                    return self
+                        .update(Message::TabMessageRight(entity_opt, tab::Message::ItemDown));
                 }
             }
             Message::ItemLeft(entity_opt) => {
                 if self.active_panel == PaneType::LeftPane {
-                    return self.update(Message::TabMessage(entity_opt, tab1::Message::ItemLeft));
+                    return self.update(Message::TabMessage(entity_opt, tab::Message::ItemLeft));
                 } else {
-                    return self.update(Message::TabMessageRight(
-                        entity_opt,
-                        tab2::Message::ItemLeft,
-                    ));
+                    return self
+                        .update(Message::TabMessageRight(entity_opt, tab::Message::ItemLeft));
                 }
             }
             Message::ItemRight(entity_opt) => {
                 if self.active_panel == PaneType::LeftPane {
-                    return self.update(Message::TabMessage(entity_opt, tab1::Message::ItemRight));
+                    return self.update(Message::TabMessage(entity_opt, tab::Message::ItemRight));
                 } else {
                     return self.update(Message::TabMessageRight(
                         entity_opt,
-                        tab2::Message::ItemRight,
+                        tab::Message::ItemRight,
                     ));
                 }
             }
             Message::ItemUp(entity_opt) => {
                 if self.active_panel == PaneType::LeftPane {
-                    return self.update(Message::TabMessage(entity_opt, tab1::Message::ItemUp));
+                    return self.update(Message::TabMessage(entity_opt, tab::Message::ItemUp));
                 } else {
-                    return self
-                        .update(Message::TabMessageRight(entity_opt, tab2::Message::ItemUp));
+                    return self.update(Message::TabMessageRight(entity_opt, tab::Message::ItemUp));
                 }
             }
             Message::Key(modifiers, key) => {
@@ -3976,24 +7375,94 @@ impl Application for App {
                     } else {
                         entity = self.tab_model2.active();
                     }
-                    for (key_bind, action) in self.key_binds.iter() {
-                        if key_bind.matches(modifiers, &key) {
-                            return self.update(action.message(Some(entity)));
+                    // The arrow keys choose a directory of the open location history dropdown
+                    if let Some((pane, index)) = self.pane_history_menu {
+                        let last = self.pane_history(pane).len().saturating_sub(1);
+                        match key {
+                            Key::Named(Named::ArrowDown) => {
+                                self.pane_history_menu = Some((pane, (index + 1).min(last)));
+                                return Task::none();
+                            }
+                            Key::Named(Named::ArrowUp) => {
+                                self.pane_history_menu = Some((pane, index.saturating_sub(1)));
+                                return Task::none();
+                            }
+                            Key::Named(Named::Enter) => {
+                                return self.update(Message::PaneHistoryOpen(pane, index));
+                            }
+                            Key::Named(Named::Escape) => {
+                                self.pane_history_menu = None;
+                                return Task::none();
+                            }
+                            _ => {}
                         }
                     }
-                }
-            }
-            Message::LocationUp(entity_opt) => {
-                if self.active_panel == PaneType::LeftPane {
-                    return self.update(Message::TabMessage(entity_opt, tab1::Message::LocationUp));
-                } else {
-                    return self.update(Message::TabMessageRight(
-                        entity_opt,
-                        tab2::Message::LocationUp,
-                    ));
-                }
-            }
-            Message::MaybeExit => {
+                    // Tab completes the path in the location bar while it is edited
+                    if key == Key::Named(Named::Tab) && modifiers.is_empty() {
+                        if self.active_panel == PaneType::LeftPane {
+                            if self
+                                .tab_model1
+                                .data::<Tab>(entity)
+                                .map_or(false, |tab| tab.edit_location.is_some())
+                            {
+                                return self.update(Message::TabMessage(
+                                    Some(entity),
+                                    tab::Message::EditLocationCompleteInline,
+                                ));
+                            }
+                        } else if self
+                            .tab_model2
+                            .data::<Tab>(entity)
+                            .map_or(false, |tab| tab.edit_location.is_some())
+                        {
+                            return self.update(Message::TabMessageRight(
+                                Some(entity),
+                                tab::Message::EditLocationCompleteInline,
+                            ));
+                        }
+                    }
+                    if self.config.vi_keys
+                        && !(modifiers.control() || modifiers.alt() || modifiers.logo())
+                    {
+                        if let Some(task) = self.vi_key(entity, &key) {
+                            return task;
+                        }
+                    }
+                    for (key_bind, action) in self.key_binds.iter() {
+                        if key_bind.matches(modifiers, &key) {
+                            if self.action_refused(entity, action) {
+                                return Task::none();
+                            }
+                            return self.update(action.message(Some(entity)));
+                        }
+                    }
+                }
+            }
+            Message::LoadSelection(entity_opt) => {
+                // A selected list is suggested, or the folder of the pane
+                let path = match self.selected_paths(entity_opt).as_slice() {
+                    [path] if path.is_file() => path.to_string_lossy().to_string(),
+                    _ => self
+                        .pane_dirs()
+                        .0
+                        .map(|dir| format!("{}/", dir.display()))
+                        .unwrap_or_default(),
+                };
+                self.dialog_pages
+                    .push_back(DialogPage::LoadSelection { path });
+                return widget::text_input::focus(self.dialog_text_input.clone());
+            }
+            Message::LocationUp(entity_opt) => {
+                if self.active_panel == PaneType::LeftPane {
+                    return self.update(Message::TabMessage(entity_opt, tab::Message::LocationUp));
+                } else {
+                    return self.update(Message::TabMessageRight(
+                        entity_opt,
+                        tab::Message::LocationUp,
+                    ));
+                }
+            }
+            Message::MaybeExit => {
                 if self.window_id_opt.is_none() && self.pending_operations.is_empty() {
                     // Exit if window is closed and there are no pending operations
                     process::exit(0);
@@ -4007,50 +7476,23 @@ impl Application for App {
             },
             Message::Modifiers(modifiers) => {
                 self.modifiers = modifiers;
-            }
-            Message::MoveTab(entity_opt) => {
-                let entity;
-                // get the selected paths of the active panel
-                let tempactive;
-                let saveactive;
-                if self.active_panel == PaneType::LeftPane {
-                    entity = self.tab_model1.active();
-                    tempactive = PaneType::LeftPane;
-                    saveactive = PaneType::RightPane;
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
-                        let location = tab.location.clone();
-                        let newlocation = convert_location1_to_location2(&location);
-                        // create a new tab in the other panel
-                        self.active_panel = tempactive;
-                        let _ = self.update(Message::TabCreateRight(Some(newlocation.clone())));
-                        let _ = self.update_title();
-                        let _ = self.update_watcher_right();
-                        let _ = self.update_tab_right(entity, newlocation, None);
-                        self.active_panel = saveactive;
-                        let _ = self.update(Message::TabClose(entity_opt));
-                    }
-                } else {
-                    entity = self.tab_model2.active();
-                    tempactive = PaneType::LeftPane;
-                    saveactive = PaneType::RightPane;
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
-                        let location = tab.location.clone();
-                        // create a new tab in the other panel
-                        self.active_panel = tempactive;
-                        let newlocation = convert_location2_to_location1(&location);
-                        let _ = self.update(Message::TabCreateLeft(Some(newlocation.clone())));
-                        let _ = self.update_title();
-                        let _ = self.update_watcher_left();
-                        let _ = self.update_tab_left(entity, newlocation, None);
-                        self.active_panel = saveactive;
-                        let _ = self.update(Message::TabClose(entity_opt));
-                    }
+                // The drop effect shown while dragging depends on the modifiers
+                let entity = self.tab_model1.active();
+                if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
+                    tab.dnd_modifiers = modifiers;
+                }
+                let entity = self.tab_model2.active();
+                if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
+                    tab.dnd_modifiers = modifiers;
                 }
             }
+            Message::MoveTab(_entity_opt) => {
+                return self.copy_tab_to_other_pane(true);
+            }
             Message::MoveToTrash(entity_opt) => {
-                let paths = self.selected_paths(entity_opt);
+                let paths = self.trashable_paths(self.selected_paths(entity_opt));
                 if !paths.is_empty() {
-                    self.operation(Operation::Delete { paths });
+                    return self.operation_previewed(Operation::Delete { paths });
                 }
             }
             Message::MounterItems(mounter_key, mounter_items) => {
@@ -4074,16 +7516,16 @@ impl Application for App {
                                             }
                                         }
                                         if !still_mounted {
-                                            unmounted.push(Location1::Path(old_path));
+                                            unmounted.push(Location::Path(old_path));
                                         }
                                     }
                                 }
                             }
                         }
-                        let home_location = Location1::Path(home_dir());
+                        let home_location = Location::Path(home_dir());
                         let entities: Vec<_> = self.tab_model1.iter().collect();
                         for entity in entities {
-                            let title_opt = match self.tab_model1.data_mut::<Tab1>(entity) {
+                            let title_opt = match self.tab_model1.data_mut::<Tab>(entity) {
                                 Some(tab) => {
                                     if unmounted.contains(&tab.location) {
                                         tab.change_location(&home_location, None);
@@ -4109,6 +7551,7 @@ impl Application for App {
                         }
                         // Insert new items
                         self.mounter_items.insert(mounter_key, mounter_items);
+                        self.update_device_usage();
 
                         // Update nav bar
                         //TODO: this could change favorites IDs while they are in use
@@ -4133,16 +7576,16 @@ impl Application for App {
                                             }
                                         }
                                         if !still_mounted {
-                                            unmounted.push(Location2::Path(old_path));
+                                            unmounted.push(Location::Path(old_path));
                                         }
                                     }
                                 }
                             }
                         }
-                        let home_location = Location2::Path(home_dir());
+                        let home_location = Location::Path(home_dir());
                         let entities: Vec<_> = self.tab_model2.iter().collect();
                         for entity in entities {
-                            let title_opt = match self.tab_model2.data_mut::<Tab2>(entity) {
+                            let title_opt = match self.tab_model2.data_mut::<Tab>(entity) {
                                 Some(tab) => {
                                     if unmounted.contains(&tab.location) {
                                         tab.change_location(&home_location, None);
@@ -4168,6 +7611,7 @@ impl Application for App {
                         }
                         // Insert new items
                         self.mounter_items.insert(mounter_key, mounter_items);
+                        self.update_device_usage();
 
                         // Update nav bar
                         //TODO: this could change favorites IDs while they are in use
@@ -4211,6 +7655,9 @@ impl Application for App {
             Message::NetworkDriveSubmit => {
                 //TODO: know which mounter to use for network drives
                 for (mounter_key, mounter) in MOUNTERS.iter() {
+                    if !mounter.network_supported() {
+                        continue;
+                    }
                     self.network_drive_connecting =
                         Some((*mounter_key, self.network_drive_input.clone()));
                     return mounter
@@ -4246,6 +7693,51 @@ impl Application for App {
                     }
                 }
             }
+            Message::NewFromTemplate(entity_opt, index) => {
+                let Some(template) = template::templates().into_iter().nth(index) else {
+                    return Task::none();
+                };
+                let dir_opt = if self.active_panel == PaneType::LeftPane {
+                    let entity = entity_opt.unwrap_or_else(|| self.tab_model1.active());
+                    self.tab_model1
+                        .data::<Tab>(entity)
+                        .and_then(|tab| tab.location.path_opt())
+                        .map(|path| path.to_path_buf())
+                } else {
+                    let entity = entity_opt.unwrap_or_else(|| self.tab_model2.active());
+                    self.tab_model2
+                        .data::<Tab>(entity)
+                        .and_then(|tab| tab.location.path_opt())
+                        .map(|path| path.to_path_buf())
+                };
+                let Some(dir) = dir_opt else {
+                    return Task::none();
+                };
+                match template.create(&dir) {
+                    Ok(path) => {
+                        // Ask for a name right away, the file was created with a default one
+                        let name = path
+                            .file_name()
+                            .map(|name| name.to_string_lossy().into_owned())
+                            .unwrap_or_default();
+                        self.dialog_pages.push_back(DialogPage::RenameItem {
+                            from: path,
+                            parent: dir,
+                            name,
+                            dir: false,
+                        });
+                        return widget::text_input::focus(self.dialog_text_input.clone());
+                    }
+                    Err(err) => {
+                        log::warn!(
+                            "failed to create {:?} in {:?}: {}",
+                            template.file_name,
+                            dir,
+                            err
+                        );
+                    }
+                }
+            }
             Message::NewItem(entity_opt, dir) => {
                 let entity = match entity_opt {
                     Some(entity) => entity,
@@ -4258,23 +7750,25 @@ impl Application for App {
                     }
                 };
                 if self.active_panel == PaneType::LeftPane {
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                    if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                         if let Some(path) = &tab.location.path_opt() {
                             self.dialog_pages.push_back(DialogPage::NewItem {
                                 parent: path.to_path_buf(),
                                 name: String::new(),
                                 dir,
+                                contents: None,
                             });
                             return widget::text_input::focus(self.dialog_text_input.clone());
                         }
                     }
                 } else {
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                    if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                         if let Some(path) = &tab.location.path_opt() {
                             self.dialog_pages.push_back(DialogPage::NewItem {
                                 parent: path.to_path_buf(),
                                 name: String::new(),
                                 dir,
+                                contents: None,
                             });
                             return widget::text_input::focus(self.dialog_text_input.clone());
                         }
@@ -4288,11 +7782,20 @@ impl Application for App {
             Message::NotifyEvents(events) => {
                 log::debug!("{:?}", events);
 
+                // Recent files are changed by other applications too
+                let recents_changed = recents::xbel_path().map_or(false, |xbel_path| {
+                    events.iter().any(|event| event.paths.contains(&xbel_path))
+                });
+
                 if self.active_panel == PaneType::LeftPane {
                     let mut needs_reload = Vec::new();
                     let entities: Vec<_> = self.tab_model1.iter().collect();
                     for entity in entities {
-                        if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                        if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
+                            if recents_changed && matches!(tab.location, Location::Recents) {
+                                needs_reload.push((entity, tab.location.clone()));
+                                continue;
+                            }
                             if let Some(path) = &tab.location.path_opt() {
                                 let mut contains_change = false;
                                 for event in events.iter() {
@@ -4313,7 +7816,7 @@ impl Application for App {
                                                                 //TODO: reload more, like mime types?
                                                                 match fs::metadata(event_path) {
                                                                     Ok(new_metadata) => {
-                                                                        if let ItemMetadata1::Path {
+                                                                        if let ItemMetadata::Path {
                                                                             metadata,
                                                                             ..
                                                                         } = &mut item.metadata
@@ -4354,7 +7857,11 @@ impl Application for App {
                     let mut needs_reload = Vec::new();
                     let entities: Vec<_> = self.tab_model2.iter().collect();
                     for entity in entities {
-                        if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                        if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
+                            if recents_changed && matches!(tab.location, Location::Recents) {
+                                needs_reload.push((entity, tab.location.clone()));
+                                continue;
+                            }
                             if let Some(path) = &tab.location.path_opt() {
                                 let mut contains_change = false;
                                 for event in events.iter() {
@@ -4375,7 +7882,7 @@ impl Application for App {
                                                                 //TODO: reload more, like mime types?
                                                                 match fs::metadata(event_path) {
                                                                     Ok(new_metadata) => {
-                                                                        if let ItemMetadata2::Path {
+                                                                        if let ItemMetadata::Path {
                                                                             metadata,
                                                                             ..
                                                                         } = &mut item.metadata
@@ -4458,7 +7965,7 @@ impl Application for App {
                     && self.pane_model.focus
                         == self.pane_model.pane_by_type[&PaneType::TerminalPane]
                 {
-                    if let Some(terminal) = self.terminal.as_mut() {
+                    if let Some(terminal) = self.terminal() {
                         if let Ok(mut terminal_ok) = terminal.lock() {
                             //if terminal_ok.needs_update {
                             terminal_ok.update();
@@ -4468,15 +7975,19 @@ impl Application for App {
                 } else {
                     if self.active_panel == PaneType::LeftPane {
                         return self
-                            .update(Message::TabMessage(entity_opt, tab1::Message::Open(None)));
+                            .update(Message::TabMessage(entity_opt, tab::Message::Open(None)));
                     } else {
                         return self.update(Message::TabMessageRight(
                             entity_opt,
-                            tab2::Message::Open(None),
+                            tab::Message::Open(None),
                         ));
                     }
                 }
             }
+            Message::OpenTogether(open_together) => {
+                config_set!(open_together, open_together);
+                return self.update_config();
+            }
             Message::OpenTerminal(entity_opt) => {
                 if let Some(terminal) = self.mime_app_cache.terminal() {
                     let mut paths = Vec::new();
@@ -4491,7 +8002,7 @@ impl Application for App {
                         }
                     };
                     if self.active_panel == PaneType::LeftPane {
-                        if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                        if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                             if let Some(path) = &tab.location.path_opt() {
                                 if let Some(items) = tab.items_opt() {
                                     for item in items.iter() {
@@ -4526,7 +8037,7 @@ impl Application for App {
                             }
                         }
                     } else {
-                        if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                        if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                             if let Some(path) = &tab.location.path_opt() {
                                 if let Some(items) = tab.items_opt() {
                                     for item in items.iter() {
@@ -4568,7 +8079,7 @@ impl Application for App {
                     && self.pane_model.focus
                         == self.pane_model.pane_by_type[&PaneType::TerminalPane]
                 {
-                    if let Some(terminal) = self.terminal.as_mut() {
+                    if let Some(terminal) = self.terminal() {
                         if let Ok(mut terminal_ok) = terminal.lock() {
                             if terminal_ok.needs_update {
                                 terminal_ok.update();
@@ -4581,13 +8092,9 @@ impl Application for App {
                             |path| {
                                 if path.is_dir() {
                                     if self.active_panel == PaneType::LeftPane {
-                                        Some(self.open_tab(Location1::Path(path), false, None))
+                                        Some(self.open_tab(Location::Path(path), false, None))
                                     } else {
-                                        Some(self.open_tab_right(
-                                            Location2::Path(path),
-                                            false,
-                                            None,
-                                        ))
+                                        Some(self.open_tab_right(Location::Path(path), false, None))
                                     }
                                 } else {
                                     None
@@ -4598,15 +8105,60 @@ impl Application for App {
                     return commands;
                 }
             }
+            Message::OpenAsAdministrator(entity_opt) => {
+                let exe = match env::current_exe() {
+                    Ok(exe) => exe,
+                    Err(err) => {
+                        log::error!("failed to get current executable path: {}", err);
+                        return Task::none();
+                    }
+                };
+                let mut paths: Vec<PathBuf> = self
+                    .selected_paths(entity_opt)
+                    .into_iter()
+                    .filter(|p| p.is_dir())
+                    .collect();
+                if paths.is_empty() {
+                    paths.extend(self.pane_dirs().0);
+                }
+                for path in paths {
+                    // pkexec clears the environment, the display is needed to show the window
+                    let mut command = process::Command::new("pkexec");
+                    command.arg("env");
+                    for var in [
+                        "DISPLAY",
+                        "WAYLAND_DISPLAY",
+                        "XDG_RUNTIME_DIR",
+                        "XAUTHORITY",
+                    ] {
+                        if let Some(value) = env::var_os(var) {
+                            let mut arg = std::ffi::OsString::from(var);
+                            arg.push("=");
+                            arg.push(value);
+                            command.arg(arg);
+                        }
+                    }
+                    command.arg(&exe).arg("--new-window").arg(path);
+                    if let Err(err) = spawn_detached(&mut command) {
+                        log::error!("failed to execute pkexec: {}", err);
+                    }
+                }
+            }
             Message::OpenInNewWindow(entity_opt) => match env::current_exe() {
                 Ok(exe) => self
                     .selected_paths(entity_opt)
                     .into_iter()
                     .filter(|p| p.is_dir())
-                    .for_each(|path| match process::Command::new(&exe).arg(path).spawn() {
-                        Ok(_child) => {}
-                        Err(err) => {
-                            log::error!("failed to execute {:?}: {}", exe, err);
+                    .for_each(|path| {
+                        match process::Command::new(&exe)
+                            .arg("--new-window")
+                            .arg(path)
+                            .spawn()
+                        {
+                            Ok(_child) => {}
+                            Err(err) => {
+                                log::error!("failed to execute {:?}: {}", exe, err);
+                            }
                         }
                     }),
                 Err(err) => {
@@ -4618,9 +8170,9 @@ impl Application for App {
                     |path| {
                         path.parent().map(Path::to_path_buf).map(|parent| {
                             if self.active_panel == PaneType::LeftPane {
-                                self.open_tab(Location1::Path(parent), true, Some(vec![path]))
+                                self.open_tab(Location::Path(parent), true, Some(vec![path]))
                             } else {
-                                self.open_tab_right(Location2::Path(parent), true, Some(vec![path]))
+                                self.open_tab_right(Location::Path(parent), true, Some(vec![path]))
                             }
                         })
                     },
@@ -4665,7 +8217,7 @@ impl Application for App {
                     }
                 };
                 if self.active_panel == PaneType::LeftPane {
-                    if let Some(tab) = self.tab_model1.data::<Tab1>(entity) {
+                    if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
                         if let Some(items) = tab.items_opt() {
                             for item in items {
                                 if !item.selected {
@@ -4684,12 +8236,15 @@ impl Application for App {
                                         .and_then(|mime| {
                                             self.mime_app_cache.get(&mime).first().cloned()
                                         }),
+                                    search: String::new(),
+                                    custom_command: String::new(),
+                                    set_default: false,
                                 }));
                             }
                         }
                     }
                 } else {
-                    if let Some(tab) = self.tab_model2.data::<Tab2>(entity) {
+                    if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
                         if let Some(items) = tab.items_opt() {
                             for item in items {
                                 if !item.selected {
@@ -4708,15 +8263,43 @@ impl Application for App {
                                         .and_then(|mime| {
                                             self.mime_app_cache.get(&mime).first().cloned()
                                         }),
+                                    search: String::new(),
+                                    custom_command: String::new(),
+                                    set_default: false,
                                 }));
                             }
                         }
                     }
                 }
             }
+            Message::OpenWithCustomCommand(value) => {
+                if let Some(DialogPage::OpenWith { custom_command, .. }) =
+                    self.dialog_pages.front_mut()
+                {
+                    *custom_command = value;
+                }
+            }
+            Message::OpenWithSearch(value) => {
+                if let Some(DialogPage::OpenWith { search, .. }) = self.dialog_pages.front_mut() {
+                    *search = value;
+                }
+            }
             Message::OpenWithSelection(index) => {
-                if let Some(DialogPage::OpenWith { selected, .. }) = self.dialog_pages.front_mut() {
+                if let Some(DialogPage::OpenWith {
+                    selected,
+                    custom_command,
+                    ..
+                }) = self.dialog_pages.front_mut()
+                {
                     *selected = index;
+                    custom_command.clear();
+                }
+            }
+            Message::OpenWithSetDefault(value) => {
+                if let Some(DialogPage::OpenWith { set_default, .. }) =
+                    self.dialog_pages.front_mut()
+                {
+                    *set_default = value;
                 }
             }
             Message::PaneUpdate => {
@@ -4745,7 +8328,16 @@ impl Application for App {
             */
             Message::PaneFocusAdjacent(_direction) => {}
             Message::PaneClicked(pane) => {
-                match self.pane_model.type_by_pane[&pane] {
+                let pane_type = self.pane_model.type_by_pane[&pane];
+                // Clicking the quick view keeps the pane of the previewed item active
+                if self.quick_view
+                    && self.show_second_panel
+                    && matches!(pane_type, PaneType::LeftPane | PaneType::RightPane)
+                    && pane_type != self.active_panel
+                {
+                    return Task::none();
+                }
+                match pane_type {
                     PaneType::LeftPane => self.active_panel = PaneType::LeftPane,
                     PaneType::RightPane => self.active_panel = PaneType::RightPane,
                     _ => {}
@@ -4754,15 +8346,50 @@ impl Application for App {
             }
             Message::PaneResized(pane_grid::ResizeEvent { split, ratio }) => {
                 self.pane_model.panestates.resize(split, ratio);
+                if self.pane_model.split_by_type.get(&PaneType::TerminalPane) == Some(&split) {
+                    let terminal_height = ((1.0 - ratio) * 100.0).round() as u16;
+                    if self.config.terminal_height != Some(terminal_height) {
+                        config_set!(terminal_height, Some(terminal_height));
+                    }
+                }
             }
             Message::PaneDragged(pane_grid::DragEvent::Dropped { pane, target }) => {
                 self.pane_model.panestates.drop(pane, target);
             }
             Message::PaneDragged(_) => {}
+            Message::PaneHistory => {
+                let pane = self.active_panel;
+                let open =
+                    matches!(self.pane_history_menu, Some((menu_pane, _)) if menu_pane == pane);
+                return self.update(Message::PaneHistoryMenu((!open).then_some(pane)));
+            }
+            Message::PaneHistoryMenu(pane_opt) => {
+                // The first directory is the current one, so the one before it is highlighted
+                self.pane_history_menu = pane_opt
+                    .filter(|pane| !self.pane_history(*pane).is_empty())
+                    .map(|pane| (pane, usize::from(self.pane_history(pane).len() > 1)));
+            }
+            Message::PaneHistoryOpen(pane, index) => {
+                self.pane_history_menu = None;
+                if let Some(path) = self.pane_history(pane).get(index).cloned() {
+                    let message = tab::Message::Location(Location::Path(path));
+                    if pane == PaneType::RightPane {
+                        return self.update(Message::TabMessageRight(None, message));
+                    } else {
+                        return self.update(Message::TabMessage(None, message));
+                    }
+                }
+            }
             Message::PaneMaximize(pane) => self.pane_model.panestates.maximize(pane),
             Message::PaneRestore => {
                 self.pane_model.panestates.restore();
             }
+            Message::PaneTintLeft(pane_tint_left) => {
+                config_set!(pane_tint_left, pane_tint_left);
+            }
+            Message::PaneTintRight(pane_tint_right) => {
+                config_set!(pane_tint_right, pane_tint_right);
+            }
             /*
             Message::PaneClose(pane) => {
                 if let Some((_, sibling)) = self.panestates.close(pane) {
@@ -4793,35 +8420,113 @@ impl Application for App {
                     }
                 };
                 if self.active_panel == PaneType::LeftPane {
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                    if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                         if let Some(path) = tab.location.path_opt() {
                             let to = path.clone();
                             return clipboard::read_data::<ClipboardPaste>().map(
                                 move |contents_opt| match contents_opt {
                                     Some(contents) => {
-                                        message::app(Message::PasteContents(to.clone(), contents))
+                                        message::app(Message::PasteKdeCut(to.clone(), contents))
                                     }
-                                    None => message::none(),
+                                    None => message::app(Message::PasteImage(to.clone(), None)),
                                 },
                             );
                         }
                     }
                 } else {
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                    if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                         if let Some(path) = tab.location.path_opt() {
                             let to = path.clone();
                             return clipboard::read_data::<ClipboardPaste>().map(
                                 move |contents_opt| match contents_opt {
                                     Some(contents) => {
-                                        message::app(Message::PasteContents(to.clone(), contents))
+                                        message::app(Message::PasteKdeCut(to.clone(), contents))
                                     }
-                                    None => message::none(),
+                                    None => message::app(Message::PasteImage(to.clone(), None)),
                                 },
                             );
                         }
                     }
                 }
             }
+            Message::PasteKdeCut(to, contents) => {
+                // Dolphin marks cut files with a separate mime type next to text/uri-list
+                return clipboard::read_data::<KdeCutSelection>().map(move |cut_opt| {
+                    let mut contents = contents.clone();
+                    if cut_opt.is_some_and(|cut| cut.0) {
+                        contents.kind = ClipboardKind::Cut;
+                    }
+                    message::app(Message::PasteContents(to.clone(), contents))
+                });
+            }
+            Message::PasteImage(to, None) => {
+                // No files in the clipboard, image data is pasted as a new file
+                return clipboard::read_data::<ClipboardImage>().map(move |image_opt| {
+                    message::app(match image_opt {
+                        Some(image) => Message::PasteImage(to.clone(), Some(image)),
+                        None => Message::PastePath(to.clone(), None),
+                    })
+                });
+            }
+            Message::PasteImage(to, Some(image)) => {
+                let name = format!(
+                    "{}.{}",
+                    fl!("pasted-image", date = paste_date()),
+                    image.extension
+                );
+                return self.paste_as_new_file(to, name, image.data);
+            }
+            Message::PastePath(to, None) => {
+                // A path pasted as text is opened, other text is pasted as a new file
+                return clipboard::read().map(move |text_opt| match text_opt {
+                    Some(text) => message::app(Message::PastePath(to.clone(), Some(text))),
+                    None => message::none(),
+                });
+            }
+            Message::PastePath(to, Some(text)) => {
+                if text.trim().is_empty() {
+                    return Task::none();
+                }
+                let Some(path) = path_from_text(&text) else {
+                    let name = format!("{}.txt", fl!("pasted-text", date = paste_date()));
+                    return self.paste_as_new_file(to, name, text.into_bytes());
+                };
+                let (dir, select) = if path.is_dir() {
+                    (path, None)
+                } else {
+                    match path.parent() {
+                        Some(parent) => (parent.to_path_buf(), Some(vec![path.clone()])),
+                        None => return Task::none(),
+                    }
+                };
+                if self.active_panel == PaneType::LeftPane {
+                    let entity = self.tab_model1.active();
+                    let location = Location::Path(dir);
+                    if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
+                        tab.change_location(&location, None);
+                        let title = tab.title();
+                        self.tab_model1.text_set(entity, title);
+                        return Task::batch([
+                            self.update_title(),
+                            self.update_watcher_left(),
+                            self.update_tab_left(entity, location, select),
+                        ]);
+                    }
+                } else {
+                    let entity = self.tab_model2.active();
+                    let location = Location::Path(dir);
+                    if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
+                        tab.change_location(&location, None);
+                        let title = tab.title();
+                        self.tab_model2.text_set(entity, title);
+                        return Task::batch([
+                            self.update_title(),
+                            self.update_watcher_right(),
+                            self.update_tab_right(entity, location, select),
+                        ]);
+                    }
+                }
+            }
             Message::PastePrimary(_entity_opt) => {
                 return clipboard::read_primary().map(move |value_opt| match value_opt {
                     Some(value) => message::app(Message::PasteValueTerminal(value)),
@@ -4841,7 +8546,7 @@ impl Application for App {
                 });
             }
             Message::PasteValueTerminal(value) => {
-                if let Some(terminalmutex) = &self.terminal.as_mut() {
+                if let Some(terminalmutex) = self.terminal() {
                     if let Ok(terminal) = terminalmutex.lock() {
                         terminal.paste(value);
                     }
@@ -4852,13 +8557,19 @@ impl Application for App {
                 if !contents.paths.is_empty() {
                     match contents.kind {
                         ClipboardKind::Copy => {
-                            self.operation(Operation::Copy {
+                            return self.operation_space_checked(Operation::Copy {
                                 paths: contents.paths,
                                 to,
                             });
                         }
                         ClipboardKind::Cut => {
-                            self.operation(Operation::Move {
+                            return self.operation_space_checked(Operation::Move {
+                                paths: contents.paths,
+                                to,
+                            });
+                        }
+                        ClipboardKind::Link => {
+                            self.operation(Operation::Symlink {
                                 paths: contents.paths,
                                 to,
                             });
@@ -4880,8 +8591,17 @@ impl Application for App {
             }
             Message::PendingComplete(id, op_sel) => {
                 let mut commands = Vec::with_capacity(4);
+                // Operations change the free space of the devices
+                self.update_device_usage();
                 // Show toast for some operations
                 if let Some((op, _)) = self.pending_operations.remove(&id) {
+                    // Archives packed to the other pane are selected there
+                    if let Operation::Compress { ref to, .. } = op {
+                        let (_, other_dir_opt) = self.pane_dirs();
+                        if other_dir_opt.is_some() && to.parent() == other_dir_opt.as_deref() {
+                            commands.push(self.select_in_other_pane(vec![to.clone()]));
+                        }
+                    }
                     if let Some(description) = op.toast() {
                         if let Operation::Delete { ref paths } = op {
                             let paths: Arc<[PathBuf]> = Arc::from(paths.as_slice());
@@ -4897,6 +8617,18 @@ impl Application for App {
                             );
                         }
                     }
+                    if matches!(op, Operation::Delete { .. } | Operation::EmptyTrash) {
+                        commands.push(self.play_sound(Sound::Trash));
+                    } else if op.show_progress_notification() {
+                        commands.push(self.play_sound(Sound::Complete));
+                    }
+                    let summary_opt = op
+                        .show_progress_notification()
+                        .then(|| (fl!("notification-complete"), op.completed_text()));
+                    if let Some((_, text)) = summary_opt.as_ref().filter(|_| self.screen_reader) {
+                        a11y::announce(text, a11y::Priority::Progress);
+                    }
+                    commands.push(self.operation_finished(id, summary_opt));
                     self.complete_operations.insert(id, op);
                 }
                 // Close progress notification if all relavent operations are finished
@@ -4909,6 +8641,11 @@ impl Application for App {
                 }
                 // Potentially show a notification
                 commands.push(self.update_notification());
+                if !op_sel.skipped.is_empty() {
+                    self.dialog_pages.push_back(DialogPage::SkippedItems {
+                        items: op_sel.skipped.clone(),
+                    });
+                }
                 // Rescan and select based on operation
                 commands.push(self.rescan_operation_selection(op_sel));
                 // Manually rescan any trash tabs after any operation is completed
@@ -4919,11 +8656,26 @@ impl Application for App {
                 self.progress_operations.clear();
             }
             Message::PendingError(id, err) => {
+                let mut commands = Vec::with_capacity(2);
                 if let Some((op, controller)) = self.pending_operations.remove(&id) {
-                    // Only show dialog if not cancelled
+                    // Only show dialog and notification if not cancelled
+                    let mut summary_opt = None;
                     if !controller.is_cancelled() {
                         self.dialog_pages.push_back(DialogPage::FailedOperation(id));
+                        commands.push(self.play_sound(Sound::Error));
+                        if op.show_progress_notification() {
+                            let text = op.pending_text(controller.progress(), controller.state());
+                            if self.screen_reader {
+                                a11y::announce(
+                                    &format!("{} {}", fl!("notification-failed"), text),
+                                    a11y::Priority::Progress,
+                                );
+                            }
+                            summary_opt =
+                                Some((fl!("notification-failed"), format!("{}\n{}", text, err)));
+                        }
                     }
+                    commands.push(self.operation_finished(id, summary_opt));
                     // Remove from progress
                     self.progress_operations.remove(&id);
                     self.failed_operations.insert(id, (op, controller, err));
@@ -4937,7 +8689,8 @@ impl Application for App {
                     self.progress_operations.clear();
                 }
                 // Manually rescan any trash tabs after any operation is completed
-                return self.rescan_trash();
+                commands.push(self.rescan_trash());
+                return Task::batch(commands);
             }
             Message::PendingPause(id, pause) => {
                 if let Some((_, controller)) = self.pending_operations.get(&id) {
@@ -4957,6 +8710,37 @@ impl Application for App {
                     }
                 }
             }
+            Message::Print(entity_opt) => {
+                let paths: Vec<PathBuf> = self
+                    .selected_paths(entity_opt)
+                    .into_iter()
+                    .filter(|path| print::can_print(path))
+                    .collect();
+                if !paths.is_empty() {
+                    self.dialog_pages.push_back(DialogPage::Print {
+                        job: PrintJob::Files(paths),
+                        options: PrintOptions::default(),
+                    });
+                }
+            }
+            Message::PrintListing => {
+                if let Some(dir) = self.pane_dirs().0 {
+                    self.dialog_pages.push_back(DialogPage::Print {
+                        job: PrintJob::Listing(dir),
+                        options: PrintOptions::default(),
+                    });
+                }
+            }
+            Message::Printed(result) => {
+                let text = match result {
+                    Ok(()) => fl!("print-sent"),
+                    Err(err) => err,
+                };
+                return self
+                    .toasts
+                    .push(widget::toaster::Toast::new(text))
+                    .map(cosmic::app::Message::App);
+            }
             Message::Preview(entity_opt) => {
                 match self.mode {
                     Mode::App => {
@@ -4991,7 +8775,7 @@ impl Application for App {
                                     id,
                                     WindowKind::Preview1(
                                         entity_opt,
-                                        PreviewKind::Location1(Location1::Path(path)),
+                                        PreviewKind::Location(Location::Path(path)),
                                     ),
                                 );
                             } else {
@@ -4999,7 +8783,7 @@ impl Application for App {
                                     id,
                                     WindowKind::Preview2(
                                         entity_opt,
-                                        PreviewKind::Location2(Location2::Path(path)),
+                                        PreviewKind::Location(Location::Path(path)),
                                     ),
                                 );
                             }
@@ -5014,17 +8798,170 @@ impl Application for App {
                 config_set!(queue_file_operations, self.config.queue_file_operations);
                 return self.update_config();
             }
+            Message::QuoteCopiedPaths(quote_copied_paths) => {
+                self.config.quote_copied_paths = quote_copied_paths;
+                config_set!(quote_copied_paths, self.config.quote_copied_paths);
+                return self.update_config();
+            }
+            Message::ConfirmConfig(confirm) => {
+                config_set!(confirm, confirm);
+                return self.update_config();
+            }
+            Message::PreviewChanges(preview_changes) => {
+                config_set!(preview_changes, preview_changes);
+                return self.update_config();
+            }
+            Message::RefuseInsufficientSpace(refuse_insufficient_space) => {
+                config_set!(refuse_insufficient_space, refuse_insufficient_space);
+                return self.update_config();
+            }
+            Message::CopyWorkers(index) => {
+                if let Some(workers) = config::COPY_WORKERS.get(index) {
+                    config_set!(copy_workers, *workers);
+                    return self.update_config();
+                }
+            }
+            Message::DirectIo(direct_io) => {
+                config_set!(direct_io, direct_io);
+                return self.update_config();
+            }
+            Message::RemoteTrash(remote_trash) => {
+                config_set!(remote_trash, remote_trash);
+                return self.update_config();
+            }
+            Message::Sounds(sounds) => {
+                config_set!(sounds, sounds);
+                return self.update_config();
+            }
+            Message::SoundVolume(sound_volume) => {
+                config_set!(sound_volume, sound_volume);
+                return self.update_config();
+            }
+            Message::QuickAction(entity_opt, action) => {
+                let paths = self.selected_paths(entity_opt);
+                if paths.is_empty() {
+                    return Task::none();
+                }
+                match action {
+                    #[cfg(feature = "dbus")]
+                    QuickAction::SetWallpaper => {
+                        let path = paths[0].clone();
+                        return Task::perform(
+                            async move {
+                                match quick_action::set_wallpaper(&path).await {
+                                    Ok(()) => message::none(),
+                                    Err(err) => {
+                                        log::warn!(
+                                            "failed to set {:?} as wallpaper: {}",
+                                            path,
+                                            err
+                                        );
+                                        message::app(Message::QuickActionResult(fl!(
+                                            "set-as-wallpaper-failed"
+                                        )))
+                                    }
+                                }
+                            },
+                            |x| x,
+                        );
+                    }
+                    QuickAction::InstallFont => {
+                        return Task::perform(
+                            async move {
+                                let count = paths.len();
+                                let result = tokio::task::spawn_blocking(move || {
+                                    quick_action::install_fonts(&paths)
+                                })
+                                .await
+                                .unwrap();
+                                let message = match result {
+                                    Ok(_font_dir) => fl!("fonts-installed", count = count),
+                                    Err(err) => {
+                                        log::warn!("failed to install fonts: {}", err);
+                                        fl!("install-font-failed", error = err.to_string())
+                                    }
+                                };
+                                message::app(Message::QuickActionResult(message))
+                            },
+                            |x| x,
+                        );
+                    }
+                }
+            }
+            Message::QuickActionResult(message) => {
+                return self
+                    .toasts
+                    .push(widget::toaster::Toast::new(message))
+                    .map(cosmic::app::Message::App);
+            }
+            Message::QuickJump => {
+                self.dialog_pages.push_back(DialogPage::QuickJump {
+                    search: String::new(),
+                });
+                return widget::text_input::focus(self.dialog_text_input.clone());
+            }
+            Message::OpenBookmark(index) => {
+                if let Some(bookmark) = self.config.bookmarks.get(index) {
+                    let path = bookmark.path.clone();
+                    if self.active_panel == PaneType::LeftPane {
+                        return self.update(Message::TabMessage(
+                            None,
+                            tab::Message::Location(Location::Path(path)),
+                        ));
+                    } else {
+                        return self.update(Message::TabMessageRight(
+                            None,
+                            tab::Message::Location(Location::Path(path)),
+                        ));
+                    }
+                }
+            }
+            Message::QuickJumpOpen(path) => {
+                if let Some(DialogPage::QuickJump { .. }) = self.dialog_pages.front() {
+                    self.dialog_pages.pop_front();
+                }
+                if self.active_panel == PaneType::LeftPane {
+                    return self.update(Message::TabMessage(
+                        None,
+                        tab::Message::Location(Location::Path(path)),
+                    ));
+                } else {
+                    return self.update(Message::TabMessageRight(
+                        None,
+                        tab::Message::Location(Location::Path(path)),
+                    ));
+                }
+            }
+            Message::QuickView => {
+                self.quick_view = !self.quick_view;
+            }
+            Message::SpaceChecked(operation, check_opt) => match check_opt {
+                Some(check) if !check.sufficient() => {
+                    self.dialog_pages
+                        .push_back(DialogPage::InsufficientSpace { operation, check });
+                }
+                // The operation fails by itself if the free space is unknown and not enough
+                _ => return self.operation_previewed(operation),
+            },
+            Message::ChangesPreviewed(operation, preview) => {
+                if preview.is_empty() {
+                    self.operation(operation);
+                } else {
+                    self.dialog_pages
+                        .push_back(DialogPage::PreviewChanges { operation, preview });
+                }
+            }
             Message::RescanTrash => {
                 // Update trash icon if empty/full
                 let maybe_entity = self.nav_model.iter().find(|&entity| {
                     self.nav_model
-                        .data::<Location1>(entity)
-                        .map(|loc| matches!(loc, Location1::Trash))
+                        .data::<Location>(entity)
+                        .map(|loc| matches!(loc, Location::Trash))
                         .unwrap_or_default()
                 });
                 if let Some(entity) = maybe_entity {
                     self.nav_model
-                        .icon_set(entity, widget::icon::icon(tab1::trash_icon_symbolic(16)));
+                        .icon_set(entity, widget::icon::icon(tab::trash_icon_symbolic(16)));
                 }
 
                 return Task::batch([self.rescan_trash(), self.update_desktop()]);
@@ -5042,7 +8979,7 @@ impl Application for App {
                     }
                 };
                 if self.active_panel == PaneType::LeftPane {
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                    if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                         if let Some(items) = tab.items_opt() {
                             let mut selected = Vec::new();
                             for item in items.iter() {
@@ -5052,6 +8989,13 @@ impl Application for App {
                                     }
                                 }
                             }
+                            // A single item is renamed in place
+                            if selected.len() == 1 {
+                                return self.update(Message::TabMessage(
+                                    Some(entity),
+                                    tab::Message::Rename(selected.pop()),
+                                ));
+                            }
                             if !selected.is_empty() {
                                 //TODO: batch rename
                                 for path in selected {
@@ -5076,7 +9020,7 @@ impl Application for App {
                         }
                     }
                 } else {
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                    if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                         if let Some(items) = tab.items_opt() {
                             let mut selected = Vec::new();
                             for item in items.iter() {
@@ -5086,6 +9030,13 @@ impl Application for App {
                                     }
                                 }
                             }
+                            // A single item is renamed in place
+                            if selected.len() == 1 {
+                                return self.update(Message::TabMessageRight(
+                                    Some(entity),
+                                    tab::Message::Rename(selected.pop()),
+                                ));
+                            }
                             if !selected.is_empty() {
                                 //TODO: batch rename
                                 for path in selected {
@@ -5111,23 +9062,42 @@ impl Application for App {
                     }
                 }
             }
-            Message::ReplaceResult(replace_result) => {
+            Message::ErrorResult(error_result) => {
                 if let Some(dialog_page) = self.dialog_pages.pop_front() {
                     match dialog_page {
-                        DialogPage::Replace1 { tx, .. } => {
+                        DialogPage::ItemFailed { tx, .. } => {
                             return Task::perform(
                                 async move {
-                                    let _ = tx.send(replace_result).await;
+                                    let _ = tx.send(error_result).await;
                                     message::none()
                                 },
                                 |x| x,
                             );
                         }
-                        DialogPage::Replace2 { tx, .. } => {
-                            return Task::perform(
-                                async move {
-                                    let _ = tx.send(replace_result).await;
-                                    message::none()
+                        other => {
+                            log::warn!("tried to send error result to the wrong dialog");
+                            self.dialog_pages.push_front(other);
+                        }
+                    }
+                }
+            }
+            Message::ReplaceResult(replace_result) => {
+                if let Some(dialog_page) = self.dialog_pages.pop_front() {
+                    match dialog_page {
+                        DialogPage::Replace1 { tx, .. } => {
+                            return Task::perform(
+                                async move {
+                                    let _ = tx.send(replace_result).await;
+                                    message::none()
+                                },
+                                |x| x,
+                            );
+                        }
+                        DialogPage::Replace2 { tx, .. } => {
+                            return Task::perform(
+                                async move {
+                                    let _ = tx.send(replace_result).await;
+                                    message::none()
                                 },
                                 |x| x,
                             );
@@ -5139,6 +9109,21 @@ impl Application for App {
                     }
                 }
             }
+            Message::RestoreSelection(entity_opt) => {
+                if let Some(paths) = self.saved_selections.get(&self.active_panel).cloned() {
+                    if self.active_panel == PaneType::LeftPane {
+                        return self.update(Message::TabMessage(
+                            entity_opt,
+                            tab::Message::SelectPaths(paths),
+                        ));
+                    } else {
+                        return self.update(Message::TabMessageRight(
+                            entity_opt,
+                            tab::Message::SelectPaths(paths),
+                        ));
+                    }
+                }
+            }
             Message::RestoreFromTrash(entity_opt) => {
                 let mut trash_items = Vec::new();
                 let entity = match entity_opt {
@@ -5152,12 +9137,12 @@ impl Application for App {
                     }
                 };
                 if self.active_panel == PaneType::LeftPane {
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                    if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                         if let Some(items) = tab.items_opt() {
                             for item in items.iter() {
                                 if item.selected {
                                     match &item.metadata {
-                                        ItemMetadata1::Trash { entry, .. } => {
+                                        ItemMetadata::Trash { entry, .. } => {
                                             trash_items.push(entry.clone());
                                         }
                                         _ => {
@@ -5172,12 +9157,12 @@ impl Application for App {
                         self.operation(Operation::Restore { items: trash_items });
                     }
                 } else {
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                    if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                         if let Some(items) = tab.items_opt() {
                             for item in items.iter() {
                                 if item.selected {
                                     match &item.metadata {
-                                        ItemMetadata2::Trash { entry, .. } => {
+                                        ItemMetadata::Trash { entry, .. } => {
                                             trash_items.push(entry.clone());
                                         }
                                         _ => {
@@ -5193,6 +9178,57 @@ impl Application for App {
                     }
                 }
             }
+            Message::RunChoice(choice) => match self.dialog_pages.pop_front() {
+                Some(DialogPage::ConfirmExecute { path, remember, .. }) => {
+                    if remember {
+                        let run_choices = self.run_choices_with(&path, choice);
+                        config_set!(run_choices, run_choices);
+                    }
+                    return self.run_or_open(&path, choice);
+                }
+                Some(other) => {
+                    log::warn!("tried to send run choice to the wrong dialog");
+                    self.dialog_pages.push_front(other);
+                }
+                None => {}
+            },
+            Message::RunInTerminal(entity_opt) => {
+                if let Some(path) = self.selected_paths(entity_opt).into_iter().next() {
+                    return self.run_in_terminal(&path);
+                }
+            }
+            Message::RunPlugin(index) => {
+                if let Some(plugin) = plugin::plugins().get(index) {
+                    self.run_command_line(&plugin.name, &plugin.exec, plugin.terminal);
+                }
+            }
+            Message::RunSync(index) => {
+                if let Some(profile) = self.config.sync_profiles.get(index) {
+                    return self.operation_previewed(Operation::Sync {
+                        profile: profile.clone(),
+                    });
+                }
+            }
+            Message::RunTool(index) => {
+                if let Some(tool) = self.config.tools.get(index) {
+                    self.run_command_line(&tool.name, &tool.command, tool.terminal);
+                }
+            }
+            Message::SaveSelection(entity_opt) => {
+                let paths = self.selected_paths(entity_opt);
+                if paths.is_empty() {
+                    return Task::none();
+                }
+                let items = paths.len();
+                self.saved_selections.insert(self.active_panel, paths);
+                return self
+                    .toasts
+                    .push(widget::toaster::Toast::new(fl!(
+                        "selection-saved",
+                        items = items
+                    )))
+                    .map(cosmic::app::Message::App);
+            }
             Message::SearchActivate => {
                 return if self.search_get().is_none() {
                     self.search_set_active(Some(String::new()))
@@ -5208,71 +9244,270 @@ impl Application for App {
             }
             Message::SelectAll(entity_opt) => {
                 if self.active_panel == PaneType::LeftPane {
-                    return self.update(Message::TabMessage(entity_opt, tab1::Message::SelectAll));
+                    return self.update(Message::TabMessage(entity_opt, tab::Message::SelectAll));
                 } else {
                     return self.update(Message::TabMessageRight(
                         entity_opt,
-                        tab2::Message::SelectAll,
+                        tab::Message::SelectAll,
                     ));
                 }
             }
             Message::SelectFirst(entity_opt) => {
+                if self.active_panel == PaneType::LeftPane {
+                    return self.update(Message::TabMessage(entity_opt, tab::Message::SelectFirst));
+                } else {
+                    return self.update(Message::TabMessageRight(
+                        entity_opt,
+                        tab::Message::SelectFirst,
+                    ));
+                }
+            }
+            Message::SelectInvert(entity_opt) => {
                 if self.active_panel == PaneType::LeftPane {
                     return self
-                        .update(Message::TabMessage(entity_opt, tab1::Message::SelectFirst));
+                        .update(Message::TabMessage(entity_opt, tab::Message::SelectInvert));
                 } else {
                     return self.update(Message::TabMessageRight(
                         entity_opt,
-                        tab2::Message::SelectFirst,
+                        tab::Message::SelectInvert,
                     ));
                 }
             }
             Message::SelectLast(entity_opt) => {
                 if self.active_panel == PaneType::LeftPane {
-                    return self.update(Message::TabMessage(entity_opt, tab1::Message::SelectLast));
+                    return self.update(Message::TabMessage(entity_opt, tab::Message::SelectLast));
+                } else {
+                    return self.update(Message::TabMessageRight(
+                        entity_opt,
+                        tab::Message::SelectLast,
+                    ));
+                }
+            }
+            Message::SelectionLoaded(result) => match result {
+                Ok(paths) => {
+                    if self.active_panel == PaneType::LeftPane {
+                        return self
+                            .update(Message::TabMessage(None, tab::Message::SelectPaths(paths)));
+                    } else {
+                        return self.update(Message::TabMessageRight(
+                            None,
+                            tab::Message::SelectPaths(paths),
+                        ));
+                    }
+                }
+                Err(err) => {
+                    return self
+                        .toasts
+                        .push(widget::toaster::Toast::new(err))
+                        .map(cosmic::app::Message::App);
+                }
+            },
+            Message::SelectToggle(entity_opt, down) => {
+                if self.active_panel == PaneType::LeftPane {
+                    return self.update(Message::TabMessage(
+                        entity_opt,
+                        tab::Message::SelectToggle(down),
+                    ));
                 } else {
                     return self.update(Message::TabMessageRight(
                         entity_opt,
-                        tab2::Message::SelectLast,
+                        tab::Message::SelectToggle(down),
                     ));
                 }
             }
+            Message::SendTo(target) => {
+                let paths = match self.dialog_pages.pop_front() {
+                    Some(DialogPage::SendTo { paths, .. }) => paths,
+                    Some(dialog_page) => {
+                        self.dialog_pages.push_front(dialog_page);
+                        return Task::none();
+                    }
+                    None => return Task::none(),
+                };
+                match target {
+                    SendTo::Email => {
+                        if let Err(err) = spawn_detached(&mut send_to::email_command(&paths)) {
+                            log::warn!("failed to compose email with {:?}: {}", paths, err);
+                        }
+                    }
+                    #[cfg(feature = "dbus")]
+                    SendTo::Bluetooth(device) => {
+                        return Task::perform(
+                            async move {
+                                let name = device.name.clone();
+                                let message = match send_to::bluetooth_send(&device, &paths).await {
+                                    Ok(()) => fl!("sent-to", name = name),
+                                    Err(err) => {
+                                        log::warn!(
+                                            "failed to send {:?} to {:?}: {}",
+                                            paths,
+                                            device,
+                                            err
+                                        );
+                                        fl!("send-to-failed", name = name)
+                                    }
+                                };
+                                message::app(Message::SentTo(message))
+                            },
+                            |x| x,
+                        );
+                    }
+                    SendTo::Folder(to) => {
+                        return self.operation_space_checked(Operation::Copy { paths, to });
+                    }
+                    SendTo::Command(index) => {
+                        if let Some(tool) = self.config.send_to.get(index) {
+                            self.run_command_line(&tool.name, &tool.command, tool.terminal);
+                        }
+                    }
+                }
+            }
+            Message::SendToDevices(value) => {
+                if let Some(DialogPage::SendTo { devices, .. }) = self.dialog_pages.front_mut() {
+                    *devices = value;
+                }
+            }
+            Message::SendToDialog(entity_opt) => {
+                let paths = self.selected_paths(entity_opt);
+                if paths.is_empty() {
+                    return Task::none();
+                }
+                self.dialog_pages.push_back(DialogPage::SendTo {
+                    paths,
+                    devices: Vec::new(),
+                });
+                #[cfg(feature = "dbus")]
+                return Task::perform(
+                    async move {
+                        let devices = match send_to::bluetooth_devices().await {
+                            Ok(devices) => devices,
+                            Err(err) => {
+                                log::info!("failed to list Bluetooth devices: {}", err);
+                                Vec::new()
+                            }
+                        };
+                        message::app(Message::SendToDevices(devices))
+                    },
+                    |x| x,
+                );
+            }
+            Message::SentTo(message) => {
+                return self
+                    .toasts
+                    .push(widget::toaster::Toast::new(message))
+                    .map(cosmic::app::Message::App);
+            }
             Message::SetSort(_entity_opt, sort, dir) => {
                 if self.active_panel == PaneType::LeftPane {
                     let entity = self.tab_model1.active();
                     return self.update(Message::TabMessage(
                         Some(entity),
-                        tab1::Message::SetSort(sort, dir),
+                        tab::Message::SetSort(sort, dir),
                     ));
                 } else {
                     let entity = self.tab_model1.active();
                     let newsort = match sort {
-                        tab1::HeadingOptions::Modified => tab2::HeadingOptions::Modified,
-                        tab1::HeadingOptions::Name => tab2::HeadingOptions::Name,
-                        tab1::HeadingOptions::TrashedOn => tab2::HeadingOptions::TrashedOn,
-                        tab1::HeadingOptions::Size => tab2::HeadingOptions::Size,
+                        tab::HeadingOptions::Modified => tab::HeadingOptions::Modified,
+                        tab::HeadingOptions::Name => tab::HeadingOptions::Name,
+                        tab::HeadingOptions::TrashedOn => tab::HeadingOptions::TrashedOn,
+                        tab::HeadingOptions::Size => tab::HeadingOptions::Size,
                     };
                     return self.update(Message::TabMessageRight(
                         Some(entity),
-                        tab2::Message::SetSort(newsort, dir),
+                        tab::Message::SetSort(newsort, dir),
                     ));
                 }
             }
             Message::SetSortRight(entity_opt, sort, dir) => {
                 return self.update(Message::TabMessageRight(
                     entity_opt,
-                    tab2::Message::SetSort(sort, dir),
+                    tab::Message::SetSort(sort, dir),
                 ));
             }
             Message::SetShowDetails(show_details) => {
                 config_set!(show_details, show_details);
                 return self.update_config();
             }
+            Message::ShowAllInFolder(entity_opt) => {
+                let paths = self.selected_paths(entity_opt);
+                let Some(ancestor) = common_ancestor(&paths) else {
+                    return Task::none();
+                };
+                // There is no branch view yet, items in subfolders are represented by the
+                // subfolder of the ancestor containing them
+                let mut selection_paths = Vec::with_capacity(paths.len());
+                for path in paths.iter() {
+                    if let Some(component) = path
+                        .strip_prefix(&ancestor)
+                        .ok()
+                        .and_then(|relative| relative.components().next())
+                    {
+                        let selection_path = ancestor.join(component);
+                        if !selection_paths.contains(&selection_path) {
+                            selection_paths.push(selection_path);
+                        }
+                    }
+                }
+                if self.active_panel == PaneType::LeftPane {
+                    let entity = entity_opt.unwrap_or_else(|| self.tab_model1.active());
+                    let location = Location::Path(ancestor);
+                    let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) else {
+                        return Task::none();
+                    };
+                    tab.change_location(&location, None);
+                    let title = tab.title();
+                    self.activate_nav_model_location(&location);
+                    self.tab_model1.text_set(entity, title);
+                    return Task::batch([
+                        self.update_title(),
+                        self.update_watcher_left(),
+                        self.update_tab_left(entity, location, Some(selection_paths)),
+                    ]);
+                } else {
+                    let entity = entity_opt.unwrap_or_else(|| self.tab_model2.active());
+                    let location = Location::Path(ancestor);
+                    let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) else {
+                        return Task::none();
+                    };
+                    tab.change_location(&location, None);
+                    let title = tab.title();
+                    self.activate_nav_model_location(&location);
+                    self.tab_model2.text_set(entity, title);
+                    return Task::batch([
+                        self.update_title(),
+                        self.update_watcher_right(),
+                        self.update_tab_right(entity, location, Some(selection_paths)),
+                    ]);
+                }
+            }
+            Message::ShareClickSettings(share) => {
+                if share {
+                    // The right pane uses the settings of the left pane again
+                    let mut config_right = self.config.tab_right;
+                    config_right.single_click_open = self.config.tab_left.single_click_open;
+                    config_right.double_click_open = self.config.tab_left.double_click_open;
+                    config_right.click_empty_clears_selection =
+                        self.config.tab_left.click_empty_clears_selection;
+                    config_set!(tab_right, config_right);
+                }
+                self.config.share_click_settings = share;
+                config_set!(share_click_settings, self.config.share_click_settings);
+                return self.update_config();
+            }
+            Message::SettingsPage(page) => {
+                self.settings_page = page;
+            }
+            Message::SettingsSearch(search) => {
+                self.settings_search = search;
+            }
             Message::ShowButtonRow(show) => {
                 self.config.show_button_row = show;
                 config_set!(show_button_row, self.config.show_button_row);
                 return self.update_config();
             }
+            Message::ShowToolbar(show) => {
+                config_set!(show_toolbar, show);
+            }
             Message::ShowEmbeddedTerminal(show) => {
                 self.config.show_embedded_terminal = show;
                 config_set!(show_embedded_terminal, self.config.show_embedded_terminal);
@@ -5283,71 +9518,188 @@ impl Application for App {
                 config_set!(show_second_panel, self.config.show_second_panel);
                 return self.update_config();
             }
+            Message::PreviewMaxSize(index) => {
+                if let Some(max_size_mb) = config::PREVIEW_MAX_SIZES_MB.get(index) {
+                    let mut config_left = self.config.tab_left;
+                    config_left.preview_limits.max_size_mb = *max_size_mb;
+                    config_set!(tab_left, config_left);
+                    let mut config_right = self.config.tab_right;
+                    config_right.preview_limits.max_size_mb = *max_size_mb;
+                    config_set!(tab_right, config_right);
+                    return self.update_config();
+                }
+            }
+            Message::PreviewTimeout(index) => {
+                if let Some(timeout_secs) = config::PREVIEW_TIMEOUTS_SECS.get(index) {
+                    let mut config_left = self.config.tab_left;
+                    config_left.preview_limits.timeout_secs = *timeout_secs;
+                    config_set!(tab_left, config_left);
+                    let mut config_right = self.config.tab_right;
+                    config_right.preview_limits.timeout_secs = *timeout_secs;
+                    config_set!(tab_right, config_right);
+                    return self.update_config();
+                }
+            }
+            Message::SingleClickOpen(pane_opt, single_click_open) => {
+                if pane_opt != Some(PaneType::RightPane) {
+                    let mut config_left = self.config.tab_left;
+                    config_left.single_click_open = single_click_open;
+                    config_set!(tab_left, config_left);
+                }
+                if pane_opt != Some(PaneType::LeftPane) {
+                    let mut config_right = self.config.tab_right;
+                    config_right.single_click_open = single_click_open;
+                    config_set!(tab_right, config_right);
+                }
+                return self.update_config();
+            }
+            Message::MouseGestures(mouse_gestures) => {
+                let mut config_left = self.config.tab_left;
+                config_left.mouse_gestures = mouse_gestures;
+                config_set!(tab_left, config_left);
+                let mut config_right = self.config.tab_right;
+                config_right.mouse_gestures = mouse_gestures;
+                config_set!(tab_right, config_right);
+                return self.update_config();
+            }
+            Message::RockerGestures(rocker_gestures) => {
+                let mut config_left = self.config.tab_left;
+                config_left.rocker_gestures = rocker_gestures;
+                config_set!(tab_left, config_left);
+                let mut config_right = self.config.tab_right;
+                config_right.rocker_gestures = rocker_gestures;
+                config_set!(tab_right, config_right);
+                return self.update_config();
+            }
+            Message::SizeOneFileSystem(one_file_system) => {
+                let mut config_left = self.config.tab_left;
+                config_left.size_one_file_system = one_file_system;
+                config_set!(tab_left, config_left);
+                let mut config_right = self.config.tab_right;
+                config_right.size_one_file_system = one_file_system;
+                config_set!(tab_right, config_right);
+                return self.update_config();
+            }
+            Message::SizeUnits(size_units) => {
+                config_set!(
+                    formats,
+                    FormatConfig {
+                        size_units,
+                        ..self.config.formats
+                    }
+                );
+                return self.update_config();
+            }
+            Message::SlideshowInterval(index) => {
+                if let Some(interval_secs) = config::SLIDESHOW_INTERVALS_SECS.get(index) {
+                    let mut config_left = self.config.tab_left;
+                    config_left.slideshow_interval_secs = *interval_secs;
+                    config_set!(tab_left, config_left);
+                    let mut config_right = self.config.tab_right;
+                    config_right.slideshow_interval_secs = *interval_secs;
+                    config_set!(tab_right, config_right);
+                    return self.update_config();
+                }
+            }
             Message::StoreOpenPaths => {
                 let mut left = Vec::new();
                 let mut right = Vec::new();
+                let mut states_left = Vec::new();
+                let mut states_right = Vec::new();
                 for entity in self.tab_model1.iter() {
-                    if let Some(tab) = self.tab_model1.data::<Tab1>(entity) {
+                    if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
                         if let Some(path) = tab.location.path_opt() {
                             left.push(osstr_to_string(path.clone().into_os_string()));
+                            states_left.push(self.tab_state(PaneType::LeftPane, entity));
                         }
                     }
                 }
                 for entity in self.tab_model2.iter() {
-                    if let Some(tab) = self.tab_model2.data::<Tab2>(entity) {
+                    if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
                         if let Some(path) = tab.location.path_opt() {
                             right.push(osstr_to_string(path.clone().into_os_string()));
+                            states_right.push(self.tab_state(PaneType::RightPane, entity));
                         }
                     }
                 }
                 config_set!(paths_left, left);
                 config_set!(paths_right, right);
+                config_set!(tab_states_left, states_left);
+                config_set!(tab_states_right, states_right);
                 return self.update_config();
             }
             Message::SystemThemeModeChange(_theme_mode) => {
                 return self.update_config();
             }
+            #[cfg(feature = "dbus")]
+            Message::ScreenReader(screen_reader) => {
+                self.screen_reader = screen_reader;
+            }
+            #[cfg(feature = "dbus")]
+            Message::SystemColorScheme(prefer_dark_opt) => {
+                self.system_prefer_dark = prefer_dark_opt;
+                if self.config.app_theme == AppTheme::FollowSystem {
+                    return self.update_config();
+                }
+            }
             Message::SwapPanels => {
                 if !self.show_second_panel {
                     return Task::none();
                 }
-                if self.active_panel == PaneType::LeftPane {
-                    let pane = self.pane_by_type(PaneType::RightPane);
-                    self.pane_model.focus = pane;
-                    self.active_panel = PaneType::RightPane;
-                    let entity = self.tab_model2.active();
-                    return self.update(Message::TabActivate(entity));
-                } else {
-                    let pane = self.pane_by_type(PaneType::RightPane);
-                    self.pane_model.focus = pane;
-                    self.active_panel = PaneType::LeftPane;
-                    let entity = self.tab_model1.active();
-                    return self.update(Message::TabActivate(entity));
+                let pane_type = self.other_pane();
+                self.pane_model.focus = self.pane_by_type(pane_type);
+                self.active_panel = pane_type;
+                let entity = self.tab_model(pane_type).active();
+                return self.update(Message::TabActivate(entity));
+            }
+            Message::SyntaxTheme(color_scheme_kind, index) => {
+                match color_scheme_kind {
+                    ColorSchemeKind::Dark => {
+                        if let Some(name) = self.theme_names_dark.get(index) {
+                            let name = name.clone();
+                            config_set!(syntax_theme_dark, name);
+                        }
+                    }
+                    ColorSchemeKind::Light => {
+                        if let Some(name) = self.theme_names_light.get(index) {
+                            let name = name.clone();
+                            config_set!(syntax_theme_light, name);
+                        }
+                    }
                 }
+                return self.update_config();
             }
             Message::TabActivate(entity) => {
-                if self.active_panel == PaneType::LeftPane {
+                // Tabs keep their scroll position when switching between them
+                let scroll_message = if self.active_panel == PaneType::LeftPane {
                     self.tab_model1.activate(entity);
                     self.active_panel = PaneType::LeftPane;
-                    if let Some(tab) = self.tab_model1.data::<Tab1>(entity) {
-                        self.activate_nav_model_location_left(&tab.location.clone());
+                    if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
+                        self.activate_nav_model_location(&tab.location.clone());
                     }
+                    Message::TabMessage(Some(entity), tab::Message::ScrollRestore)
                 } else {
                     self.tab_model2.activate(entity);
                     self.active_panel = PaneType::RightPane;
-                    if let Some(tab) = self.tab_model2.data::<Tab2>(entity) {
-                        self.activate_nav_model_location_right(&tab.location.clone());
+                    if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
+                        self.activate_nav_model_location(&tab.location.clone());
                     }
-                }
-                return self.update_title();
+                    Message::TabMessageRight(Some(entity), tab::Message::ScrollRestore)
+                };
+                return Task::batch([self.update_title(), self.update(scroll_message)]);
             }
             Message::TabActivateLeft => {
+                self.pane_model.focus = self.pane_by_type(PaneType::LeftPane);
                 self.active_panel = PaneType::LeftPane;
                 let entity = self.tab_model1.active();
                 self.active_panel = PaneType::LeftPane;
                 return self.update(Message::TabActivate(entity));
             }
             Message::TabActivateRight => {
+                if !self.show_second_panel {
+                    return Task::none();
+                }
+                self.pane_model.focus = self.pane_by_type(PaneType::RightPane);
                 self.active_panel = PaneType::RightPane;
                 let entity = self.tab_model2.active();
                 self.active_panel = PaneType::RightPane;
@@ -5434,7 +9786,7 @@ impl Application for App {
             Message::TabRescan => {
                 if self.active_panel == PaneType::LeftPane {
                     let entity = self.tab_model1.active();
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                    if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                         let location = tab.location.clone();
 
                         return self.update(Message::TabRescanLeft(
@@ -5447,7 +9799,7 @@ impl Application for App {
                     }
                 } else {
                     let entity = self.tab_model2.active();
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                    if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                         let location = tab.location.clone();
 
                         return self.update(Message::TabRescanRight(
@@ -5471,6 +9823,10 @@ impl Application for App {
                         }
                     }
                 };
+                if self.tab_state(self.active_panel, entity).pinned {
+                    return Task::none();
+                }
+                self.remember_closed_tab(self.active_panel, entity);
                 if self.active_panel == PaneType::LeftPane {
                     if let Some(position) = self.tab_model1.position(entity) {
                         let new_position = if position > 0 {
@@ -5481,8 +9837,8 @@ impl Application for App {
 
                         if self.tab_model1.activate_position(new_position) {
                             if let Some(new_entity) = self.tab_model1.entity_at(new_position) {
-                                if let Some(tab) = self.tab_model1.data::<Tab1>(new_entity) {
-                                    self.activate_nav_model_location_left(&tab.location.clone());
+                                if let Some(tab) = self.tab_model1.data::<Tab>(new_entity) {
+                                    self.activate_nav_model_location(&tab.location.clone());
                                 }
                             }
                         }
@@ -5510,8 +9866,8 @@ impl Application for App {
 
                         if self.tab_model2.activate_position(new_position) {
                             if let Some(new_entity) = self.tab_model2.entity_at(new_position) {
-                                if let Some(tab) = self.tab_model2.data::<Tab2>(new_entity) {
-                                    self.activate_nav_model_location_right(&tab.location.clone());
+                                if let Some(tab) = self.tab_model2.data::<Tab>(new_entity) {
+                                    self.activate_nav_model_location(&tab.location.clone());
                                 }
                             }
                         }
@@ -5537,6 +9893,10 @@ impl Application for App {
                     Some(entity) => entity,
                     None => self.tab_model1.active(),
                 };
+                if self.tab_state(PaneType::LeftPane, entity).pinned {
+                    return Task::none();
+                }
+                self.remember_closed_tab(PaneType::LeftPane, entity);
                 if let Some(position) = self.tab_model1.position(entity) {
                     let new_position = if position > 0 {
                         position - 1
@@ -5546,8 +9906,8 @@ impl Application for App {
 
                     if self.tab_model1.activate_position(new_position) {
                         if let Some(new_entity) = self.tab_model1.entity_at(new_position) {
-                            if let Some(tab) = self.tab_model1.data::<Tab1>(new_entity) {
-                                self.activate_nav_model_location_left(&tab.location.clone());
+                            if let Some(tab) = self.tab_model1.data::<Tab>(new_entity) {
+                                self.activate_nav_model_location(&tab.location.clone());
                             }
                         }
                     }
@@ -5570,6 +9930,10 @@ impl Application for App {
                     Some(entity) => entity,
                     None => self.tab_model2.active(),
                 };
+                if self.tab_state(PaneType::RightPane, entity).pinned {
+                    return Task::none();
+                }
+                self.remember_closed_tab(PaneType::RightPane, entity);
                 if let Some(position) = self.tab_model2.position(entity) {
                     let new_position = if position > 0 {
                         position - 1
@@ -5579,8 +9943,8 @@ impl Application for App {
 
                     if self.tab_model2.activate_position(new_position) {
                         if let Some(new_entity) = self.tab_model2.entity_at(new_position) {
-                            if let Some(tab) = self.tab_model2.data::<Tab2>(new_entity) {
-                                self.activate_nav_model_location_right(&tab.location.clone());
+                            if let Some(tab) = self.tab_model2.data::<Tab>(new_entity) {
+                                self.activate_nav_model_location(&tab.location.clone());
                             }
                         }
                     }
@@ -5615,9 +9979,9 @@ impl Application for App {
                     return self.open_tab(location, true, None);
                 } else {
                     let entity = self.tab_model2.active();
-                    let location = match self.tab_model1.data_mut::<Tab1>(entity) {
+                    let location = match self.tab_model1.data_mut::<Tab>(entity) {
                         Some(tab) => tab.location.clone(),
-                        None => Location1::Path(home_dir()),
+                        None => Location::Path(home_dir()),
                     };
                     let _ = self.update(Message::StoreOpenPaths);
                     return self.open_tab(location, true, None);
@@ -5629,14 +9993,103 @@ impl Application for App {
                     return self.open_tab_right(location, true, None);
                 } else {
                     let entity = self.tab_model2.active();
-                    let location = match self.tab_model2.data_mut::<Tab2>(entity) {
+                    let location = match self.tab_model2.data_mut::<Tab>(entity) {
                         Some(tab) => tab.location.clone(),
-                        None => Location2::Path(home_dir()),
+                        None => Location::Path(home_dir()),
                     };
                     let _ = self.update(Message::StoreOpenPaths);
                     return self.open_tab_right(location, true, None);
                 }
             }
+            Message::TabDuplicate => {
+                // The copy is opened next to the active tab
+                if self.active_panel == PaneType::LeftPane {
+                    let entity = self.tab_model1.active();
+                    if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
+                        let location = tab.location.clone();
+                        let (new_entity, task) = self.open_tab_entity_left(location, true, None);
+                        if let Some(position) = self.tab_model1.position(entity) {
+                            self.tab_model1.position_set(new_entity, position + 1);
+                        }
+                        let _ = self.update(Message::StoreOpenPaths);
+                        return task;
+                    }
+                } else {
+                    let entity = self.tab_model2.active();
+                    if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
+                        let location = tab.location.clone();
+                        let (new_entity, task) = self.open_tab_entity_right(location, true, None);
+                        if let Some(position) = self.tab_model2.position(entity) {
+                            self.tab_model2.position_set(new_entity, position + 1);
+                        }
+                        let _ = self.update(Message::StoreOpenPaths);
+                        return task;
+                    }
+                }
+            }
+            Message::TabLock => {
+                let entity = if self.active_panel == PaneType::LeftPane {
+                    self.tab_model1.active()
+                } else {
+                    self.tab_model2.active()
+                };
+                let mut state = self.tab_state(self.active_panel, entity);
+                state.locked = !state.locked;
+                self.set_tab_state(self.active_panel, entity, state);
+                return self.update(Message::StoreOpenPaths);
+            }
+            Message::TabMoveBackward => {
+                self.move_tab_position(false);
+                let _ = self.update(Message::StoreOpenPaths);
+            }
+            Message::TabMoveForward => {
+                self.move_tab_position(true);
+                let _ = self.update(Message::StoreOpenPaths);
+            }
+            Message::TabPin => {
+                let entity = if self.active_panel == PaneType::LeftPane {
+                    self.tab_model1.active()
+                } else {
+                    self.tab_model2.active()
+                };
+                let mut state = self.tab_state(self.active_panel, entity);
+                state.pinned = !state.pinned;
+                self.set_tab_state(self.active_panel, entity, state);
+                if state.pinned {
+                    // Pinned tabs are kept in front of the other tabs
+                    if self.active_panel == PaneType::LeftPane {
+                        let position = self
+                            .tab_model1
+                            .iter()
+                            .filter(|other| {
+                                *other != entity
+                                    && self.tab_state(PaneType::LeftPane, *other).pinned
+                            })
+                            .count();
+                        self.tab_model1.position_set(entity, position as u16);
+                    } else {
+                        let position = self
+                            .tab_model2
+                            .iter()
+                            .filter(|other| {
+                                *other != entity
+                                    && self.tab_state(PaneType::RightPane, *other).pinned
+                            })
+                            .count();
+                        self.tab_model2.position_set(entity, position as u16);
+                    }
+                }
+                return self.update(Message::StoreOpenPaths);
+            }
+            Message::TabReopen => {
+                if let Some((pane_type, location)) = self.closed_tabs.pop_back() {
+                    let _ = self.update(Message::StoreOpenPaths);
+                    if pane_type == PaneType::RightPane && self.show_second_panel {
+                        return self.open_tab_right(location.clone(), true, None);
+                    }
+                    return self.open_tab(location, true, None);
+                }
+            }
             Message::ToggleFoldersFirst => {
                 if self.active_panel == PaneType::LeftPane {
                     let mut config = self.config.tab_left;
@@ -5648,31 +10101,66 @@ impl Application for App {
                     return self.update(Message::TabConfigRight(config));
                 }
             }
+            Message::ToggleReadOnly => {
+                if self.active_panel == PaneType::LeftPane {
+                    let mut config = self.config.tab_left;
+                    config.read_only = !config.read_only;
+                    return self.update(Message::TabConfigLeft(config));
+                } else {
+                    let mut config = self.config.tab_right;
+                    config.read_only = !config.read_only;
+                    return self.update(Message::TabConfigRight(config));
+                }
+            }
+            Message::ToggleDimHidden(entity_opt) => {
+                if self.active_panel == PaneType::LeftPane {
+                    return self.update(Message::TabMessage(
+                        entity_opt,
+                        tab::Message::ToggleDimHidden,
+                    ));
+                } else {
+                    return self.update(Message::TabMessageRight(
+                        entity_opt,
+                        tab::Message::ToggleDimHidden,
+                    ));
+                }
+            }
             Message::ToggleShowHidden(entity_opt) => {
                 if self.active_panel == PaneType::LeftPane {
                     return self.update(Message::TabMessage(
                         entity_opt,
-                        tab1::Message::ToggleShowHidden,
+                        tab::Message::ToggleShowHidden,
                     ));
                 } else {
                     return self.update(Message::TabMessageRight(
                         entity_opt,
-                        tab2::Message::ToggleShowHidden,
+                        tab::Message::ToggleShowHidden,
                     ));
                 }
             }
             Message::ToggleSortLeft(entity_opt, sort) => {
                 return self.update(Message::TabMessage(
                     entity_opt,
-                    tab1::Message::ToggleSort(sort),
+                    tab::Message::ToggleSort(sort),
                 ));
             }
             Message::ToggleSortRight(entity_opt, sort) => {
                 return self.update(Message::TabMessageRight(
                     entity_opt,
-                    tab2::Message::ToggleSort(sort),
+                    tab::Message::ToggleSort(sort),
                 ));
             }
+            Message::ToggleTerminal => {
+                let show = !self.config.show_embedded_terminal;
+                config_set!(show_embedded_terminal, show);
+                let task = self.update_config();
+                let pane_type = if show {
+                    PaneType::TerminalPane
+                } else {
+                    self.active_panel
+                };
+                return Task::batch([task, self.focus_pane(pane_type)]);
+            }
             Message::TabMessage(entity_opt, tab_message) => {
                 let entity = match entity_opt {
                     Some(entity) => entity,
@@ -5680,29 +10168,42 @@ impl Application for App {
                 };
 
                 //TODO: move to Task?
-                if let tab1::Message::ContextMenu(_point_opt) = tab_message {
+                if matches!(
+                    tab_message,
+                    tab::Message::ContextMenu(_) | tab::Message::ContextMenuFocus
+                ) {
                     // Disable side context page
                     self.set_show_context(false);
                 }
 
-                let tab_commands = match { self.tab_model1.data_mut::<Tab1>(entity) } {
+                let focus = focuses_pane(&tab_message);
+                let selection_opt = self
+                    .tab_model1
+                    .data::<Tab>(entity)
+                    .filter(|_| self.screen_reader)
+                    .and_then(selection_announcement);
+                let tab_commands = match { self.tab_model1.data_mut::<Tab>(entity) } {
                     Some(tab) => tab.update(tab_message, self.modifiers),
                     _ => Vec::new(),
                 };
+                self.announce_selection(self.tab_model1.data::<Tab>(entity), selection_opt);
 
                 let active_panel = self.active_panel;
                 self.active_panel = PaneType::LeftPane;
                 let mut commands = Vec::new();
                 for tab_command in tab_commands {
                     match tab_command {
-                        tab1::Command::Action(action) => {
+                        tab::Command::Action(action) => {
                             commands.push(self.update(action.message(Some(entity))));
                         }
-                        tab1::Command::AddNetworkDrive => {
+                        tab::Command::AddNetworkDrive => {
                             self.context_page = ContextPage::NetworkDrive;
                             self.set_show_context(true);
                         }
-                        tab1::Command::AddToSidebar(path) => {
+                        tab::Command::AddToArchive(paths, archive) => {
+                            self.operation(Operation::AddToArchive { paths, archive });
+                        }
+                        tab::Command::AddToSidebar(path) => {
                             let mut favorites = self.config.favorites.clone();
                             let favorite = Favorite::from_path(path);
                             if !favorites.iter().any(|f| f == &favorite) {
@@ -5711,8 +10212,11 @@ impl Application for App {
                             config_set!(favorites, favorites);
                             commands.push(self.update_config());
                         }
-                        tab1::Command::ChangeLocation(tab_title, tab_path, selection_paths) => {
-                            self.activate_nav_model_location_left(&tab_path);
+                        tab::Command::ChangeLocation(tab_title, tab_path, selection_paths) => {
+                            self.activate_nav_model_location(&tab_path);
+                            if let Some(path) = tab_path.path_opt() {
+                                self.visit_dir(path, PaneType::LeftPane);
+                            }
                             self.tab_model1.text_set(entity, tab_title);
                             commands.push(Task::batch([
                                 self.update_title(),
@@ -5720,36 +10224,51 @@ impl Application for App {
                                 self.update_tab_left(entity, tab_path, selection_paths),
                             ]));
                         }
-                        tab1::Command::DropFiles(to, from) => {
-                            commands.push(self.update(Message::PasteContents(to, from)));
+                        tab::Command::DesktopLayout(key, layout) => {
+                            commands.push(self.update(Message::DesktopLayout(key, layout)));
+                        }
+                        tab::Command::DropFiles(to, from) => {
+                            commands.push(self.drop_contents(to, from));
                         }
-                        tab1::Command::EmptyTrash => {
-                            self.dialog_pages.push_back(DialogPage::EmptyTrash);
+                        tab::Command::EmptyTrash => {
+                            self.empty_trash();
                         }
                         #[cfg(feature = "desktop")]
-                        tab1::Command::ExecEntryAction(entry, action) => {
+                        tab::Command::ExecEntryAction(entry, action) => {
                             App::exec_entry_action(entry, action);
                         }
-                        tab1::Command::Iced(iced_command) => {
+                        tab::Command::Iced(iced_command) => {
                             commands.push(
                                 iced_command.0.map(move |x| {
                                     message::app(Message::TabMessage(Some(entity), x))
                                 }),
                             );
                         }
-                        tab1::Command::MoveToTrash(paths) => {
-                            self.operation(Operation::Delete { paths });
+                        tab::Command::MoveToTrash(paths) => {
+                            let paths = self.trashable_paths(paths);
+                            if !paths.is_empty() {
+                                commands
+                                    .push(self.operation_previewed(Operation::Delete { paths }));
+                            }
                         }
-                        tab1::Command::OpenFile(path) => self.open_file(&path),
-                        tab1::Command::OpenInNewTab(path) => {
-                            commands.push(self.open_tab(
-                                Location1::Path(path.clone()),
-                                false,
-                                None,
-                            ));
+                        tab::Command::Rename(from, to) => {
+                            self.operation(Operation::Rename { from, to });
+                        }
+                        tab::Command::OpenFile(path) => commands.push(self.open_file(&path)),
+                        tab::Command::OpenFiles(paths) => commands.push(self.open_files(paths)),
+                        tab::Command::OpenInNewTab(path) => {
+                            commands.push(self.open_tab(Location::Path(path.clone()), false, None));
                         }
-                        tab1::Command::OpenInNewWindow(path) => match env::current_exe() {
-                            Ok(exe) => match process::Command::new(&exe).arg(path).spawn() {
+                        tab::Command::OpenLocationInNewTab(location, selection_paths) => {
+                            commands.push(self.open_tab(location, true, selection_paths));
+                            let _ = self.update(Message::StoreOpenPaths);
+                        }
+                        tab::Command::OpenInNewWindow(path) => match env::current_exe() {
+                            Ok(exe) => match process::Command::new(&exe)
+                                .arg("--new-window")
+                                .arg(path)
+                                .spawn()
+                            {
                                 Ok(_child) => {}
                                 Err(err) => {
                                     log::error!("failed to execute {:?}: {}", exe, err);
@@ -5759,7 +10278,7 @@ impl Application for App {
                                 log::error!("failed to get current executable path: {}", err);
                             }
                         },
-                        tab1::Command::OpenTrash => {
+                        tab::Command::OpenTrash => {
                             //TODO: use handler for x-scheme-handler/trash and open trash:///
                             let mut command = process::Command::new("commander");
                             command.arg("--trash");
@@ -5770,27 +10289,38 @@ impl Application for App {
                                 }
                             }
                         }
-                        tab1::Command::Preview(kind) => {
+                        tab::Command::Preview(kind) => {
                             self.context_page = ContextPage::Preview(Some(entity), kind);
                             self.set_show_context(true);
                         }
-                        tab1::Command::SetOpenWith(mime, id) => {
+                        tab::Command::SetOpenWith(mime, id) => {
                             //TODO: this will block for a few ms, run in background?
                             self.mime_app_cache.set_default(mime, id);
                         }
-                        tab1::Command::WindowDrag => {
+                        tab::Command::Toast(text) => {
+                            commands.push(
+                                self.toasts_left
+                                    .push(widget::toaster::Toast::new(text))
+                                    .map(cosmic::app::Message::App),
+                            );
+                        }
+                        tab::Command::WindowDrag => {
                             if let Some(window_id) = &self.window_id_opt {
                                 commands.push(window::drag(*window_id));
                             }
                         }
-                        tab1::Command::WindowToggleMaximize => {
+                        tab::Command::WindowToggleMaximize => {
                             if let Some(window_id) = &self.window_id_opt {
                                 commands.push(window::toggle_maximize(*window_id));
                             }
                         }
                     }
                 }
-                self.active_panel = active_panel;
+                if focus {
+                    self.pane_model.focus = self.pane_by_type(PaneType::LeftPane);
+                } else {
+                    self.active_panel = active_panel;
+                }
                 return Task::batch(commands);
             }
             Message::TabMessageRight(entity_opt, tab_message) => {
@@ -5800,28 +10330,41 @@ impl Application for App {
                 };
 
                 //TODO: move to Task?
-                if let tab2::Message::ContextMenu(_point_opt) = tab_message {
+                if matches!(
+                    tab_message,
+                    tab::Message::ContextMenu(_) | tab::Message::ContextMenuFocus
+                ) {
                     // Disable side context page
                     self.set_show_context(false);
                 }
 
-                let tab_commands = match { self.tab_model2.data_mut::<Tab2>(entity) } {
+                let focus = focuses_pane(&tab_message);
+                let selection_opt = self
+                    .tab_model2
+                    .data::<Tab>(entity)
+                    .filter(|_| self.screen_reader)
+                    .and_then(selection_announcement);
+                let tab_commands = match { self.tab_model2.data_mut::<Tab>(entity) } {
                     Some(tab) => tab.update(tab_message, self.modifiers),
                     _ => Vec::new(),
                 };
+                self.announce_selection(self.tab_model2.data::<Tab>(entity), selection_opt);
                 let active_panel = self.active_panel;
                 self.active_panel = PaneType::RightPane;
                 let mut commands = Vec::new();
                 for tab_command in tab_commands {
                     match tab_command {
-                        tab2::Command::Action(action) => {
+                        tab::Command::Action(action) => {
                             commands.push(self.update(action.message(Some(entity))));
                         }
-                        tab2::Command::AddNetworkDrive => {
+                        tab::Command::AddNetworkDrive => {
                             self.context_page = ContextPage::NetworkDrive;
                             self.set_show_context(true);
                         }
-                        tab2::Command::AddToSidebar(path) => {
+                        tab::Command::AddToArchive(paths, archive) => {
+                            self.operation(Operation::AddToArchive { paths, archive });
+                        }
+                        tab::Command::AddToSidebar(path) => {
                             let mut favorites = self.config.favorites.clone();
                             let favorite = Favorite::from_path(path);
                             if !favorites.iter().any(|f| f == &favorite) {
@@ -5830,8 +10373,11 @@ impl Application for App {
                             config_set!(favorites, favorites);
                             commands.push(self.update_config());
                         }
-                        tab2::Command::ChangeLocation(tab_title, tab_path, selection_paths) => {
-                            self.activate_nav_model_location_right(&tab_path);
+                        tab::Command::ChangeLocation(tab_title, tab_path, selection_paths) => {
+                            if let Some(path) = tab_path.path_opt() {
+                                self.visit_dir(path, PaneType::RightPane);
+                            }
+                            self.activate_nav_model_location(&tab_path);
                             self.tab_model2.text_set(entity, tab_title);
                             commands.push(Task::batch([
                                 self.update_title(),
@@ -5839,34 +10385,53 @@ impl Application for App {
                                 self.update_tab_right(entity, tab_path, selection_paths),
                             ]));
                         }
-                        tab2::Command::DropFiles(to, from) => {
-                            commands.push(self.update(Message::PasteContents(to, from)));
+                        tab::Command::DesktopLayout(key, layout) => {
+                            commands.push(self.update(Message::DesktopLayout(key, layout)));
+                        }
+                        tab::Command::DropFiles(to, from) => {
+                            commands.push(self.drop_contents(to, from));
                         }
-                        tab2::Command::EmptyTrash => {
-                            self.dialog_pages.push_back(DialogPage::EmptyTrash);
+                        tab::Command::EmptyTrash => {
+                            self.empty_trash();
                         }
                         #[cfg(feature = "desktop")]
-                        tab2::Command::ExecEntryAction(entry, action) => {
+                        tab::Command::ExecEntryAction(entry, action) => {
                             App::exec_entry_action(entry, action);
                         }
-                        tab2::Command::Iced(iced_command) => {
+                        tab::Command::Iced(iced_command) => {
                             commands.push(iced_command.0.map(move |x| {
                                 message::app(Message::TabMessageRight(Some(entity), x))
                             }));
                         }
-                        tab2::Command::MoveToTrash(paths) => {
-                            self.operation(Operation::Delete { paths });
+                        tab::Command::MoveToTrash(paths) => {
+                            let paths = self.trashable_paths(paths);
+                            if !paths.is_empty() {
+                                commands
+                                    .push(self.operation_previewed(Operation::Delete { paths }));
+                            }
+                        }
+                        tab::Command::Rename(from, to) => {
+                            self.operation(Operation::Rename { from, to });
                         }
-                        tab2::Command::OpenFile(path) => self.open_file(&path),
-                        tab2::Command::OpenInNewTab(path) => {
+                        tab::Command::OpenFile(path) => commands.push(self.open_file(&path)),
+                        tab::Command::OpenFiles(paths) => commands.push(self.open_files(paths)),
+                        tab::Command::OpenInNewTab(path) => {
                             commands.push(self.open_tab_right(
-                                Location2::Path(path.clone()),
+                                Location::Path(path.clone()),
                                 false,
                                 None,
                             ));
                         }
-                        tab2::Command::OpenInNewWindow(path) => match env::current_exe() {
-                            Ok(exe) => match process::Command::new(&exe).arg(path).spawn() {
+                        tab::Command::OpenLocationInNewTab(location, selection_paths) => {
+                            commands.push(self.open_tab_right(location, true, selection_paths));
+                            let _ = self.update(Message::StoreOpenPaths);
+                        }
+                        tab::Command::OpenInNewWindow(path) => match env::current_exe() {
+                            Ok(exe) => match process::Command::new(&exe)
+                                .arg("--new-window")
+                                .arg(path)
+                                .spawn()
+                            {
                                 Ok(_child) => {}
                                 Err(err) => {
                                     log::error!("failed to execute {:?}: {}", exe, err);
@@ -5876,7 +10441,7 @@ impl Application for App {
                                 log::error!("failed to get current executable path: {}", err);
                             }
                         },
-                        tab2::Command::OpenTrash => {
+                        tab::Command::OpenTrash => {
                             //TODO: use handler for x-scheme-handler/trash and open trash:///
                             let mut command = process::Command::new("commander");
                             command.arg("--trash");
@@ -5887,74 +10452,141 @@ impl Application for App {
                                 }
                             }
                         }
-                        tab2::Command::Preview(kind) => {
+                        tab::Command::Preview(kind) => {
                             self.context_page = ContextPage::Preview(Some(entity), kind);
                             self.set_show_context(true);
                         }
-                        tab2::Command::SetOpenWith(mime, id) => {
+                        tab::Command::SetOpenWith(mime, id) => {
                             //TODO: this will block for a few ms, run in background?
                             self.mime_app_cache.set_default(mime, id);
                         }
-                        tab2::Command::WindowDrag => {
+                        tab::Command::Toast(text) => {
+                            commands.push(
+                                self.toasts_right
+                                    .push(widget::toaster::Toast::new(text))
+                                    .map(cosmic::app::Message::App),
+                            );
+                        }
+                        tab::Command::WindowDrag => {
                             if let Some(window_id) = &self.window_id_opt {
                                 commands.push(window::drag(*window_id));
                             }
                         }
-                        tab2::Command::WindowToggleMaximize => {
+                        tab::Command::WindowToggleMaximize => {
                             if let Some(window_id) = &self.window_id_opt {
                                 commands.push(window::toggle_maximize(*window_id));
                             }
                         }
                     }
                 }
-                self.active_panel = active_panel;
+                if focus {
+                    self.pane_model.focus = self.pane_by_type(PaneType::RightPane);
+                } else {
+                    self.active_panel = active_panel;
+                }
                 return Task::batch(commands);
             }
             Message::TabNew => {
                 if self.active_panel == PaneType::LeftPane {
                     let entity = self.tab_model1.active();
-                    let location = match self.tab_model1.data_mut::<Tab1>(entity) {
+                    let location = match self.tab_model1.data_mut::<Tab>(entity) {
                         Some(tab) => tab.location.clone(),
-                        None => Location1::Path(home_dir()),
+                        None => Location::Path(home_dir()),
                     };
                     let _ = self.update(Message::StoreOpenPaths);
                     return self.open_tab(location, true, None);
                 } else {
                     let entity = self.tab_model2.active();
-                    let location = match self.tab_model2.data_mut::<Tab2>(entity) {
+                    let location = match self.tab_model2.data_mut::<Tab>(entity) {
                         Some(tab) => tab.location.clone(),
-                        None => Location2::Path(home_dir()),
+                        None => Location::Path(home_dir()),
                     };
                     let _ = self.update(Message::StoreOpenPaths);
                     return self.open_tab_right(location, true, None);
                 }
             }
-            Message::TabRescanLeft(entity, location, parent_item_opt, items, selection_paths) => {
-                if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
-                    if location == tab.location {
-                        tab.parent_item_opt = parent_item_opt;
-                        tab.set_items(items);
-                        if let Some(selection_paths) = selection_paths {
-                            tab.select_paths(selection_paths);
-                        }
-                    }
+            Message::TabRescanLeft(
+                entity,
+                location,
+                parent_item_opt,
+                mut items,
+                selection_paths,
+            ) => {
+                self.add_seen_mimes(items.iter().map(|item| &item.mime));
+                tab::hide_matching(&mut items, &self.hide_patterns());
+                tab::color_matching(&mut items, &self.config.color_rules);
+                let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) else {
+                    return Task::none();
+                };
+                if location != tab.location {
+                    return Task::none();
                 }
-            }
-            Message::TabRescanRight(entity, location, parent_item_opt, items, selection_paths) => {
-                if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
-                    if location == tab.location {
-                        tab.parent_item_opt = parent_item_opt;
-                        tab.set_items(items);
-                        if let Some(selection_paths) = selection_paths {
-                            tab.select_paths(selection_paths);
-                        }
-                    }
+                tab.parent_item_opt = parent_item_opt;
+                tab.set_items(items);
+                let mut tasks = vec![self.update(Message::TabMessage(
+                    Some(entity),
+                    tab::Message::ScrollRestore,
+                ))];
+                if let Some(selection_paths) = selection_paths {
+                    if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
+                        tab.select_paths(selection_paths);
+                    }
+                    // Pasted or renamed items are scrolled into view once they are laid out
+                    tasks.push(Task::perform(
+                        async move {
+                            message::app(Message::TabMessage(
+                                Some(entity),
+                                tab::Message::ScrollToFocus,
+                            ))
+                        },
+                        |x| x,
+                    ));
+                }
+                return Task::batch(tasks);
+            }
+            Message::TabRescanRight(
+                entity,
+                location,
+                parent_item_opt,
+                mut items,
+                selection_paths,
+            ) => {
+                self.add_seen_mimes(items.iter().map(|item| &item.mime));
+                tab::hide_matching(&mut items, &self.hide_patterns());
+                tab::color_matching(&mut items, &self.config.color_rules);
+                let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) else {
+                    return Task::none();
+                };
+                if location != tab.location {
+                    return Task::none();
+                }
+                tab.parent_item_opt = parent_item_opt;
+                tab.set_items(items);
+                let mut tasks = vec![self.update(Message::TabMessageRight(
+                    Some(entity),
+                    tab::Message::ScrollRestore,
+                ))];
+                if let Some(selection_paths) = selection_paths {
+                    if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
+                        tab.select_paths(selection_paths);
+                    }
+                    // Pasted or renamed items are scrolled into view once they are laid out
+                    tasks.push(Task::perform(
+                        async move {
+                            message::app(Message::TabMessageRight(
+                                Some(entity),
+                                tab::Message::ScrollToFocus,
+                            ))
+                        },
+                        |x| x,
+                    ));
                 }
+                return Task::batch(tasks);
             }
             Message::TabView(_entity_opt, view) => {
                 if self.active_panel == PaneType::LeftPane {
                     let entity = self.tab_model1.active();
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                    if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                         tab.config.view = view;
                         let mut config = self.config.tab_left;
                         config.view = view;
@@ -5962,11 +10594,11 @@ impl Application for App {
                     }
                 } else {
                     let newview = match view {
-                        tab1::View::Grid => tab2::View::Grid,
-                        tab1::View::List => tab2::View::List,
+                        tab::View::Grid => tab::View::Grid,
+                        tab::View::List => tab::View::List,
                     };
                     let entity = self.tab_model2.active();
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                    if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                         tab.config.view = newview;
                         let mut config = self.config.tab_right;
                         config.view = newview;
@@ -5974,8 +10606,55 @@ impl Application for App {
                     }
                 }
             }
+            Message::TabViewToggle(entity_opt) => {
+                // Views of the right pane are converted by the view message
+                let grid_opt = if self.active_panel == PaneType::LeftPane {
+                    self.tab_model1
+                        .active_data::<Tab>()
+                        .map(|tab| tab.config.view == tab::View::Grid)
+                } else {
+                    self.tab_model2
+                        .active_data::<Tab>()
+                        .map(|tab| tab.config.view == tab::View::Grid)
+                };
+                if let Some(grid) = grid_opt {
+                    let view = if grid {
+                        tab::View::List
+                    } else {
+                        tab::View::Grid
+                    };
+                    return self.update(Message::TabView(entity_opt, view));
+                }
+            }
+            Message::TermActivate(entity) => {
+                self.terminal_model.activate(entity);
+            }
+            Message::TermClose(entity_opt) => {
+                let entity = match entity_opt {
+                    Some(entity) => entity,
+                    None => self.terminal_model.active(),
+                };
+                if self.terminal_model.is_active(entity) {
+                    if let Some(position) = self.terminal_model.position(entity) {
+                        let new_position = if position > 0 {
+                            position - 1
+                        } else {
+                            position + 1
+                        };
+                        self.terminal_model.activate_position(new_position);
+                    }
+                }
+
+                // Remove item, which shuts down the shell of the terminal
+                self.terminal_model.remove(entity);
+
+                // If that was the last terminal, open a new one
+                if self.terminal_model.iter().next().is_none() {
+                    return self.update(Message::TermNew);
+                }
+            }
             Message::TermContextAction(action) => {
-                if let Some(terminal) = self.terminal.as_mut() {
+                if let Some(terminal) = self.terminal() {
                     // Update context menu position
                     let mut terminal = terminal.lock().unwrap();
                     terminal.context_menu = None;
@@ -5985,13 +10664,13 @@ impl Application for App {
             }
             Message::TermContextMenu(_pane, position_opt) => {
                 // Show the context menu on the correct pane / terminal
-                if let Some(terminal) = self.terminal.as_mut() {
+                if let Some(terminal) = self.terminal() {
                     // Update context menu position
                     let mut terminal = terminal.lock().unwrap();
                     terminal.context_menu = position_opt;
                 }
             }
-            Message::TermEvent(_pane, _entity, event) => {
+            Message::TermEvent(_pane, entity, event) => {
                 match event {
                     TermEvent::Bell => {
                         //TODO: audible or visible bell options?
@@ -6022,7 +10701,10 @@ impl Application for App {
                         }
                     },
                     TermEvent::ColorRequest(index, f) => {
-                        if let Some(terminal) = &self.terminal {
+                        if let Some(terminal) = self
+                            .terminal_model
+                            .data::<Mutex<crate::terminal::Terminal>>(entity)
+                        {
                             let terminal = terminal.lock().unwrap();
                             let rgb = terminal.colors()[index].unwrap_or_default();
                             let text = f(rgb);
@@ -6032,24 +10714,53 @@ impl Application for App {
                     TermEvent::CursorBlinkingChange => {
                         //TODO: should we blink the cursor?
                     }
-                    TermEvent::Exit => {}
+                    TermEvent::Exit => {
+                        return self.update(Message::TermClose(Some(entity)));
+                    }
                     TermEvent::PtyWrite(text) => {
-                        if let Some(terminal) = &self.terminal {
+                        if let Some(terminal) = self
+                            .terminal_model
+                            .data::<Mutex<crate::terminal::Terminal>>(entity)
+                        {
                             let terminal = terminal.lock().unwrap();
                             terminal.input_no_scroll(text.into_bytes());
                         }
                     }
-                    TermEvent::ResetTitle => {}
+                    TermEvent::ResetTitle => {
+                        if let Some(terminal) = self
+                            .terminal_model
+                            .data::<Mutex<crate::terminal::Terminal>>(entity)
+                        {
+                            if terminal.lock().unwrap().tab_title_override.is_none() {
+                                self.terminal_model.text_set(entity, fl!("terminal"));
+                            }
+                        }
+                    }
                     TermEvent::TextAreaSizeRequest(f) => {
-                        if let Some(terminal) = &self.terminal {
+                        if let Some(terminal) = self
+                            .terminal_model
+                            .data::<Mutex<crate::terminal::Terminal>>(entity)
+                        {
                             let terminal = terminal.lock().unwrap();
                             let text = f(terminal.size().into());
                             terminal.input_no_scroll(text.into_bytes());
                         }
                     }
-                    TermEvent::Title(_title) => {}
+                    TermEvent::Title(title) => {
+                        if let Some(terminal) = self
+                            .terminal_model
+                            .data::<Mutex<crate::terminal::Terminal>>(entity)
+                        {
+                            if terminal.lock().unwrap().tab_title_override.is_none() {
+                                self.terminal_model.text_set(entity, title);
+                            }
+                        }
+                    }
                     TermEvent::MouseCursorDirty | TermEvent::Wakeup => {
-                        if let Some(terminal) = &self.terminal {
+                        if let Some(terminal) = self
+                            .terminal_model
+                            .data::<Mutex<crate::terminal::Terminal>>(entity)
+                        {
                             let mut terminal = terminal.lock().unwrap();
                             terminal.needs_update = true;
                         }
@@ -6064,7 +10775,7 @@ impl Application for App {
                 if self.term_event_tx_opt.is_some() {
                     // Close tabs using old terminal event channel
                     log::warn!("terminal event channel reset, closing tabs");
-                    self.terminal = None;
+                    self.terminal_model = segmented_button::ModelBuilder::default().build();
                 }
 
                 self.term_event_tx_opt = Some(term_event_tx);
@@ -6085,7 +10796,23 @@ impl Application for App {
             }
             Message::TermNew => {
                 let pane = self.pane_model.pane_by_type[&PaneType::TerminalPane];
-                return self.create_and_focus_new_terminal(pane);
+                return self.create_and_focus_new_terminal(pane, self.config.default_profile, None);
+            }
+            Message::TermNewProfile(profile_id) => {
+                let pane = self.pane_model.pane_by_type[&PaneType::TerminalPane];
+                return self.create_and_focus_new_terminal(pane, Some(profile_id), None);
+            }
+            Message::TermRename(entity_opt) => {
+                let entity = match entity_opt {
+                    Some(entity) => entity,
+                    None => self.terminal_model.active(),
+                };
+                if let Some(name) = self.terminal_model.text(entity) {
+                    let name = name.to_string();
+                    self.dialog_pages
+                        .push_back(DialogPage::TermRename { entity, name });
+                    return widget::text_input::focus(self.dialog_text_input.clone());
+                }
             }
             Message::ToggleContextPage(context_page) => {
                 //TODO: ensure context menus are closed
@@ -6121,13 +10848,13 @@ impl Application for App {
                 }
 
                 return cosmic::task::future(async move {
-                    match tokio::task::spawn_blocking(move || Location1::Trash.scan(icon_sizes))
+                    match tokio::task::spawn_blocking(move || Location::Trash.scan(icon_sizes))
                         .await
                     {
                         Ok((_parent_item_opt, items)) => {
                             for path in &*recently_trashed {
                                 for item in &items {
-                                    if let ItemMetadata1::Trash { ref entry, .. } = item.metadata {
+                                    if let ItemMetadata::Trash { ref entry, .. } = item.metadata {
                                         let original_path = entry.original_path();
                                         if &original_path == path {
                                             paths.push(entry.clone());
@@ -6147,6 +10874,50 @@ impl Application for App {
             Message::UndoTrashStart(items) => {
                 self.operation(Operation::Restore { items });
             }
+            Message::UnlockContainer(entity_opt) => {
+                let mounter_key = MounterKey("crypt");
+                if let (Some(item), Some(mounter)) = (
+                    self.selected_paths(entity_opt)
+                        .first()
+                        .and_then(|path| MounterItem::container(path)),
+                    MOUNTERS.get(&mounter_key),
+                ) {
+                    return mounter.mount(item).map(|_| message::none());
+                }
+            }
+            Message::VerifyChecksums(entity_opt) => {
+                if let Some(path) = self
+                    .selected_paths(entity_opt)
+                    .into_iter()
+                    .find(|path| checksum::is_sums_file(path))
+                {
+                    return Task::perform(
+                        async move {
+                            let result = {
+                                let path = path.clone();
+                                tokio::task::spawn_blocking(move || {
+                                    checksum::verify(&path, &Controller::default())
+                                })
+                                .await
+                                .unwrap()
+                            };
+                            message::app(Message::VerifyChecksumsResult(path, result))
+                        },
+                        |x| x,
+                    );
+                }
+            }
+            Message::VerifyChecksumsResult(path, result) => {
+                self.dialog_pages
+                    .push_back(DialogPage::VerifyChecksums { path, result });
+            }
+            Message::ViKeys(vi_keys) => {
+                self.vi_pending.clear();
+                self.vi_visual = false;
+                self.config.vi_keys = vi_keys;
+                config_set!(vi_keys, self.config.vi_keys);
+                return self.update_config();
+            }
             Message::WindowClose => {
                 if let Some(window_id) = self.window_id_opt.take() {
                     return Task::batch([
@@ -6155,15 +10926,19 @@ impl Application for App {
                     ]);
                 }
             }
+            Message::WindowFocus => {
+                self.window_focused = true;
+            }
             Message::WindowUnfocus => {
+                self.window_focused = false;
                 if self.active_panel == PaneType::LeftPane {
                     let tab_entity = self.tab_model1.active();
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(tab_entity) {
+                    if let Some(tab) = self.tab_model1.data_mut::<Tab>(tab_entity) {
                         tab.context_menu = None;
                     }
                 } else {
                     let tab_entity = self.tab_model2.active();
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(tab_entity) {
+                    if let Some(tab) = self.tab_model2.data_mut::<Tab>(tab_entity) {
                         tab.context_menu = None;
                     }
                 }
@@ -6187,7 +10962,7 @@ impl Application for App {
                     && self.pane_model.focus
                         == self.pane_model.pane_by_type[&PaneType::TerminalPane]
                 {
-                    if let Some(terminal) = self.terminal.as_mut() {
+                    if let Some(terminal) = self.terminal() {
                         if let Ok(mut term) = terminal.lock() {
                             term.set_zoom_adj(0);
                         }
@@ -6197,27 +10972,19 @@ impl Application for App {
                     if self.active_panel == PaneType::LeftPane {
                         entity = self.tab_model1.active();
                         let mut config = self.config.tab_left;
-                        if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                        if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                             match tab.config.view {
-                                tab1::View::List => {
-                                    config.icon_sizes.list = 100.try_into().unwrap()
-                                }
-                                tab1::View::Grid => {
-                                    config.icon_sizes.grid = 100.try_into().unwrap()
-                                }
+                                tab::View::List => config.icon_sizes.list = 100.try_into().unwrap(),
+                                tab::View::Grid => config.icon_sizes.grid = 100.try_into().unwrap(),
                             }
                         }
                     } else {
                         entity = self.tab_model2.active();
                         let mut config = self.config.tab_left;
-                        if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                        if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                             match tab.config.view {
-                                tab2::View::List => {
-                                    config.icon_sizes.list = 100.try_into().unwrap()
-                                }
-                                tab2::View::Grid => {
-                                    config.icon_sizes.grid = 100.try_into().unwrap()
-                                }
+                                tab::View::List => config.icon_sizes.list = 100.try_into().unwrap(),
+                                tab::View::Grid => config.icon_sizes.grid = 100.try_into().unwrap(),
                             }
                         }
                     }
@@ -6242,19 +11009,19 @@ impl Application for App {
                 if self.active_panel == PaneType::LeftPane {
                     entity = self.tab_model1.active();
                     let mut config = self.config.tab_left;
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                    if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                         match tab.config.view {
-                            tab1::View::List => config.icon_sizes.list = 100.try_into().unwrap(),
-                            tab1::View::Grid => config.icon_sizes.grid = 100.try_into().unwrap(),
+                            tab::View::List => config.icon_sizes.list = 100.try_into().unwrap(),
+                            tab::View::Grid => config.icon_sizes.grid = 100.try_into().unwrap(),
                         }
                     }
                 } else {
                     entity = self.tab_model2.active();
                     let mut config = self.config.tab_right;
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                    if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                         match tab.config.view {
-                            tab2::View::List => zoom_in(&mut config.icon_sizes.list, 50, 500),
-                            tab2::View::Grid => zoom_in(&mut config.icon_sizes.grid, 50, 500),
+                            tab::View::List => zoom_in(&mut config.icon_sizes.list, 50, 500),
+                            tab::View::Grid => zoom_in(&mut config.icon_sizes.grid, 50, 500),
                         }
                     }
                 }
@@ -6278,7 +11045,7 @@ impl Application for App {
                     && self.pane_model.focus
                         == self.pane_model.pane_by_type[&PaneType::TerminalPane]
                 {
-                    if let Some(terminal) = self.terminal.as_mut() {
+                    if let Some(terminal) = self.terminal() {
                         if let Ok(mut term) = terminal.lock() {
                             let cur_val = term.zoom_adj();
                             term.set_zoom_adj(cur_val.saturating_sub(1));
@@ -6289,19 +11056,19 @@ impl Application for App {
                     if self.active_panel == PaneType::LeftPane {
                         entity = self.tab_model1.active();
                         let mut config = self.config.tab_left;
-                        if let Some(tab) = self.tab_model1.data_mut::<Tab1>(entity) {
+                        if let Some(tab) = self.tab_model1.data_mut::<Tab>(entity) {
                             match tab.config.view {
-                                tab1::View::List => zoom_out(&mut config.icon_sizes.list, 50, 500),
-                                tab1::View::Grid => zoom_out(&mut config.icon_sizes.grid, 50, 500),
+                                tab::View::List => zoom_out(&mut config.icon_sizes.list, 50, 500),
+                                tab::View::Grid => zoom_out(&mut config.icon_sizes.grid, 50, 500),
                             }
                         }
                     } else {
                         entity = self.tab_model2.active();
                         let mut config = self.config.tab_right;
-                        if let Some(tab) = self.tab_model2.data_mut::<Tab2>(entity) {
+                        if let Some(tab) = self.tab_model2.data_mut::<Tab>(entity) {
                             match tab.config.view {
-                                tab2::View::List => zoom_out(&mut config.icon_sizes.list, 50, 500),
-                                tab2::View::Grid => zoom_out(&mut config.icon_sizes.grid, 50, 500),
+                                tab::View::List => zoom_out(&mut config.icon_sizes.list, 50, 500),
+                                tab::View::Grid => zoom_out(&mut config.icon_sizes.grid, 50, 500),
                             }
                         }
                     }
@@ -6309,10 +11076,10 @@ impl Application for App {
                 }
             }
             Message::DndEnterNav(entity) => {
-                if let Some(location) = self.nav_model.data::<Location1>(entity) {
+                if let Some(location) = self.nav_model.data::<Location>(entity) {
                     self.nav_dnd_hover = Some((location.clone(), Instant::now()));
                     let location = location.clone();
-                    return Task::perform(tokio::time::sleep(HOVER_DURATION1), move |_| {
+                    return Task::perform(tokio::time::sleep(HOVER_DURATION), move |_| {
                         cosmic::app::Message::App(Message::DndHoverLocTimeoutLeft(location.clone()))
                     });
                 }
@@ -6322,22 +11089,18 @@ impl Application for App {
             }
             Message::DndDropNav(entity, data, action) => {
                 self.nav_dnd_hover = None;
-                if let Some((location, data)) = self.nav_model.data::<Location1>(entity).zip(data) {
-                    let kind = match action {
-                        DndAction::Move => ClipboardKind::Cut,
-                        _ => ClipboardKind::Copy,
-                    };
+                if let Some((location, data)) = self.nav_model.data::<Location>(entity).zip(data) {
+                    let kind = ClipboardKind::for_drop(self.modifiers);
                     let ret = match location {
-                        Location1::Path(p) => self.update(Message::PasteContents(
+                        Location::Path(p) => self.drop_contents(
                             p.clone(),
                             ClipboardPaste {
                                 kind,
                                 paths: data.paths,
                             },
-                        )),
-                        Location1::Trash if matches!(action, DndAction::Move) => {
-                            self.operation(Operation::Delete { paths: data.paths });
-                            Task::none()
+                        ),
+                        Location::Trash if matches!(action, DndAction::Move) => {
+                            self.operation_previewed(Operation::Delete { paths: data.paths })
                         }
                         _ => {
                             log::warn!("Copy to trash is not supported.");
@@ -6351,11 +11114,11 @@ impl Application for App {
                 if self
                     .nav_dnd_hover
                     .as_ref()
-                    .is_some_and(|(loc, i)| *loc == location && i.elapsed() >= HOVER_DURATION1)
+                    .is_some_and(|(loc, i)| *loc == location && i.elapsed() >= HOVER_DURATION)
                 {
                     self.nav_dnd_hover = None;
                     let entity = self.tab_model1.active();
-                    let title_opt = match self.tab_model1.data_mut::<Tab1>(entity) {
+                    let title_opt = match self.tab_model1.data_mut::<Tab>(entity) {
                         Some(tab) => {
                             tab.change_location(&location, None);
                             Some(tab.title())
@@ -6376,11 +11139,11 @@ impl Application for App {
                 if self
                     .nav_dnd_hover_right
                     .as_ref()
-                    .is_some_and(|(loc, i)| *loc == location && i.elapsed() >= HOVER_DURATION2)
+                    .is_some_and(|(loc, i)| *loc == location && i.elapsed() >= HOVER_DURATION)
                 {
                     self.nav_dnd_hover_right = None;
                     let entity = self.tab_model1.active();
-                    let title_opt = match self.tab_model2.data_mut::<Tab2>(entity) {
+                    let title_opt = match self.tab_model2.data_mut::<Tab>(entity) {
                         Some(tab) => {
                             tab.change_location(&location, None);
                             Some(tab.title())
@@ -6400,25 +11163,25 @@ impl Application for App {
             Message::DndEnterTab(entity) => {
                 if self.active_panel == PaneType::LeftPane {
                     self.tab_dnd_hover_left = Some((entity, Instant::now()));
-                    return Task::perform(tokio::time::sleep(HOVER_DURATION1), move |_| {
+                    return Task::perform(tokio::time::sleep(HOVER_DURATION), move |_| {
                         cosmic::app::Message::App(Message::DndHoverTabTimeout(entity))
                     });
                 } else {
                     self.tab_dnd_hover_right = Some((entity, Instant::now()));
-                    return Task::perform(tokio::time::sleep(HOVER_DURATION2), move |_| {
+                    return Task::perform(tokio::time::sleep(HOVER_DURATION), move |_| {
                         cosmic::app::Message::App(Message::DndHoverTabTimeout(entity))
                     });
                 }
             }
             Message::DndEnterTabLeft(entity) => {
                 self.tab_dnd_hover_left = Some((entity, Instant::now()));
-                return Task::perform(tokio::time::sleep(HOVER_DURATION1), move |_| {
+                return Task::perform(tokio::time::sleep(HOVER_DURATION), move |_| {
                     cosmic::app::Message::App(Message::DndHoverTabTimeout(entity))
                 });
             }
             Message::DndEnterTabRight(entity) => {
                 self.tab_dnd_hover_right = Some((entity, Instant::now()));
-                return Task::perform(tokio::time::sleep(HOVER_DURATION2), move |_| {
+                return Task::perform(tokio::time::sleep(HOVER_DURATION), move |_| {
                     cosmic::app::Message::App(Message::DndHoverTabTimeout(entity))
                 });
             }
@@ -6443,13 +11206,13 @@ impl Application for App {
                 } else if self.active_panel == PaneType::LeftPane {
                     let entity = self.tab_model1.active();
                     self.tab_dnd_hover_left = Some((entity, Instant::now()));
-                    return Task::perform(tokio::time::sleep(HOVER_DURATION1), move |_| {
+                    return Task::perform(tokio::time::sleep(HOVER_DURATION), move |_| {
                         cosmic::app::Message::App(Message::DndHoverTabTimeout(entity))
                     });
                 } else {
                     let entity = self.tab_model2.active();
                     self.tab_dnd_hover_right = Some((entity, Instant::now()));
-                    return Task::perform(tokio::time::sleep(HOVER_DURATION2), move |_| {
+                    return Task::perform(tokio::time::sleep(HOVER_DURATION), move |_| {
                         cosmic::app::Message::App(Message::DndHoverTabTimeout(entity))
                     });
                 }
@@ -6488,7 +11251,7 @@ impl Application for App {
                 if self.pane_model.focus == left_pane {
                     let entity = self.tab_model1.active();
                     self.tab_dnd_hover = Some((entity, Instant::now()));
-                    return Task::perform(tokio::time::sleep(HOVER_DURATION1), move |_| {
+                    return Task::perform(tokio::time::sleep(HOVER_DURATION), move |_| {
                         cosmic::app::Message::App(Message::DndHoverTabTimeout(entity))
                     });
                 } else if self.config.show_second_panel
@@ -6496,7 +11259,7 @@ impl Application for App {
                 {
                     let entity = self.tab_model2.active();
                     self.tab_dnd_hover = Some((entity, Instant::now()));
-                    return Task::perform(tokio::time::sleep(HOVER_DURATION2), move |_| {
+                    return Task::perform(tokio::time::sleep(HOVER_DURATION), move |_| {
                         cosmic::app::Message::App(Message::DndHoverTabTimeout(entity))
                     });
                 } else {
@@ -6551,10 +11314,7 @@ impl Application for App {
                         Some(action) => action,
                         None => DndAction::Copy,
                     };
-                    let cbkind = match action {
-                        DndAction::Move => ClipboardKind::Cut,
-                        _ => ClipboardKind::Copy,
-                    };
+                    let cbkind = ClipboardKind::for_drop(self.modifiers);
                     let c = ClipboardPaste {
                         kind: cbkind,
                         paths: dnd_drop.paths,
@@ -6580,7 +11340,7 @@ impl Application for App {
                 if self.pane_model.focus == left_pane {
                     let entity = self.tab_model1.active();
                     self.tab_dnd_hover = Some((entity, Instant::now()));
-                    return Task::perform(tokio::time::sleep(HOVER_DURATION1), move |_| {
+                    return Task::perform(tokio::time::sleep(HOVER_DURATION), move |_| {
                         cosmic::app::Message::App(Message::DndHoverTabTimeout(entity))
                     });
                 } else if self.config.show_second_panel
@@ -6588,7 +11348,7 @@ impl Application for App {
                 {
                     let entity = self.tab_model2.active();
                     self.tab_dnd_hover = Some((entity, Instant::now()));
-                    return Task::perform(tokio::time::sleep(HOVER_DURATION2), move |_| {
+                    return Task::perform(tokio::time::sleep(HOVER_DURATION), move |_| {
                         cosmic::app::Message::App(Message::DndHoverTabTimeout(entity))
                     });
                 } else {
@@ -6677,7 +11437,7 @@ impl Application for App {
                     };
                     let _ = self.update(Message::DndDropTabLeft(entity, Some(c), DndAction::Copy));
                 } else {
-                    let entity = self.tab_model1.active();
+                    let entity = self.tab_model2.active();
                     let v = vec![path];
                     let c = ClipboardPaste {
                         kind: ClipboardKind::Copy,
@@ -6688,22 +11448,18 @@ impl Application for App {
             }
             Message::DndDropTabLeft(entity, data, action) => {
                 self.tab_dnd_hover_left = None;
-                if let Some((tab, data)) = self.tab_model1.data::<Tab1>(entity).zip(data) {
-                    let kind = match action {
-                        DndAction::Move => ClipboardKind::Cut,
-                        _ => ClipboardKind::Copy,
-                    };
+                if let Some((tab, data)) = self.tab_model1.data::<Tab>(entity).zip(data) {
+                    let kind = ClipboardKind::for_drop(self.modifiers);
                     let ret = match &tab.location {
-                        Location1::Path(p) => self.update(Message::PasteContents(
+                        Location::Path(p) => self.drop_contents(
                             p.clone(),
                             ClipboardPaste {
                                 kind,
                                 paths: data.paths,
                             },
-                        )),
-                        Location1::Trash if matches!(action, DndAction::Move) => {
-                            self.operation(Operation::Delete { paths: data.paths });
-                            Task::none()
+                        ),
+                        Location::Trash if matches!(action, DndAction::Move) => {
+                            self.operation_previewed(Operation::Delete { paths: data.paths })
                         }
                         _ => {
                             log::warn!("Copy to trash is not supported.");
@@ -6715,22 +11471,18 @@ impl Application for App {
             }
             Message::DndDropTabRight(entity, data, action) => {
                 self.tab_dnd_hover_right = None;
-                if let Some((tab, data)) = self.tab_model2.data::<Tab2>(entity).zip(data) {
-                    let kind = match action {
-                        DndAction::Move => ClipboardKind::Cut,
-                        _ => ClipboardKind::Copy,
-                    };
+                if let Some((tab, data)) = self.tab_model2.data::<Tab>(entity).zip(data) {
+                    let kind = ClipboardKind::for_drop(self.modifiers);
                     let ret = match &tab.location {
-                        Location2::Path(p) => self.update(Message::PasteContents(
+                        Location::Path(p) => self.drop_contents(
                             p.clone(),
                             ClipboardPaste {
                                 kind,
                                 paths: data.paths,
                             },
-                        )),
-                        Location2::Trash if matches!(action, DndAction::Move) => {
-                            self.operation(Operation::Delete { paths: data.paths });
-                            Task::none()
+                        ),
+                        Location::Trash if matches!(action, DndAction::Move) => {
+                            self.operation_previewed(Operation::Delete { paths: data.paths })
                         }
                         _ => {
                             log::warn!("Copy to trash is not supported.");
@@ -6745,7 +11497,7 @@ impl Application for App {
                     if self
                         .tab_dnd_hover_left
                         .as_ref()
-                        .is_some_and(|(e, i)| *e == entity && i.elapsed() >= HOVER_DURATION1)
+                        .is_some_and(|(e, i)| *e == entity && i.elapsed() >= HOVER_DURATION)
                     {
                         self.tab_dnd_hover_left = None;
                     }
@@ -6753,7 +11505,7 @@ impl Application for App {
                     if self
                         .tab_dnd_hover_right
                         .as_ref()
-                        .is_some_and(|(e, i)| *e == entity && i.elapsed() >= HOVER_DURATION2)
+                        .is_some_and(|(e, i)| *e == entity && i.elapsed() >= HOVER_DURATION)
                     {
                         self.tab_dnd_hover_right = None;
                     }
@@ -6761,25 +11513,17 @@ impl Application for App {
                 return self.update(Message::TabActivate(entity));
             }
 
-            Message::NavBarClose(entity) => {
-                if let Some(data) = self.nav_model.data::<MounterData>(entity) {
-                    if let Some(mounter) = MOUNTERS.get(&data.0) {
-                        return mounter.unmount(data.1.clone()).map(|_| message::none());
-                    }
-                }
-            }
-
             // Tracks which nav bar item to show a context menu for.
             Message::NavBarContext(entity) => {
                 // Close location editing if enabled
                 if self.active_panel == PaneType::LeftPane {
                     let tab_entity = self.tab_model1.active();
-                    if let Some(tab) = self.tab_model1.data_mut::<Tab1>(tab_entity) {
+                    if let Some(tab) = self.tab_model1.data_mut::<Tab>(tab_entity) {
                         tab.edit_location = None;
                     }
                 } else {
                     let tab_entity = self.tab_model2.active();
-                    if let Some(tab) = self.tab_model2.data_mut::<Tab2>(tab_entity) {
+                    if let Some(tab) = self.tab_model2.data_mut::<Tab>(tab_entity) {
                         tab.edit_location = None;
                     }
                 }
@@ -6790,21 +11534,21 @@ impl Application for App {
                 NavMenuAction::Open(entity) => {
                     if let Some(path) = self
                         .nav_model
-                        .data::<Location1>(entity)
+                        .data::<Location>(entity)
                         .and_then(|x| x.path_opt())
                         .map(|x| x.to_path_buf())
                     {
-                        self.open_file(&path);
+                        return self.open_file(&path);
                     }
                 }
                 NavMenuAction::OpenWith(entity) => {
                     if let Some(path) = self
                         .nav_model
-                        .data::<Location1>(entity)
+                        .data::<Location>(entity)
                         .and_then(|x| x.path_opt())
                         .map(|x| x.to_path_buf())
                     {
-                        match tab1::item_from_path(&path, IconSizes::default()) {
+                        match tab::item_from_path(&path, IconSizes::default()) {
                             Ok(item) => {
                                 return self.update(Message::DialogPush(DialogPage::OpenWith {
                                     path: path.to_path_buf(),
@@ -6816,6 +11560,9 @@ impl Application for App {
                                         .and_then(|mime| {
                                             self.mime_app_cache.get(&mime).first().cloned()
                                         }),
+                                    search: String::new(),
+                                    custom_command: String::new(),
+                                    set_default: false,
                                 }));
                             }
                             Err(err) => {
@@ -6825,23 +11572,23 @@ impl Application for App {
                     }
                 }
                 NavMenuAction::OpenInNewTab(entity) => {
-                    match self.nav_model.data::<Location1>(entity) {
-                        Some(Location1::Path(ref path)) => {
+                    match self.nav_model.data::<Location>(entity) {
+                        Some(Location::Path(ref path)) => {
                             if self.active_panel == PaneType::LeftPane {
-                                return self.open_tab(Location1::Path(path.clone()), false, None);
+                                return self.open_tab(Location::Path(path.clone()), false, None);
                             } else {
                                 return self.open_tab_right(
-                                    Location2::Path(path.clone()),
+                                    Location::Path(path.clone()),
                                     false,
                                     None,
                                 );
                             }
                         }
-                        Some(Location1::Trash) => {
+                        Some(Location::Trash) => {
                             if self.active_panel == PaneType::LeftPane {
-                                return self.open_tab(Location1::Trash, false, None);
+                                return self.open_tab(Location::Trash, false, None);
                             } else {
-                                return self.open_tab_right(Location2::Trash, false, None);
+                                return self.open_tab_right(Location::Trash, false, None);
                             }
                         }
                         _ => {}
@@ -6849,9 +11596,13 @@ impl Application for App {
                 }
                 // Open the selected path in a new commander window.
                 NavMenuAction::OpenInNewWindow(entity) => {
-                    if let Some(Location1::Path(path)) = self.nav_model.data::<Location1>(entity) {
+                    if let Some(Location::Path(path)) = self.nav_model.data::<Location>(entity) {
                         match env::current_exe() {
-                            Ok(exe) => match process::Command::new(&exe).arg(path).spawn() {
+                            Ok(exe) => match process::Command::new(&exe)
+                                .arg("--new-window")
+                                .arg(path)
+                                .spawn()
+                            {
                                 Ok(_child) => {}
                                 Err(err) => {
                                     log::error!("failed to execute {:?}: {}", exe, err);
@@ -6867,14 +11618,14 @@ impl Application for App {
                 NavMenuAction::Preview(entity) => {
                     if let Some(path) = self
                         .nav_model
-                        .data::<Location1>(entity)
+                        .data::<Location>(entity)
                         .and_then(|location| location.path_opt())
                     {
-                        match tab1::item_from_path(path, IconSizes::default()) {
+                        match tab::item_from_path(path, IconSizes::default()) {
                             Ok(item) => {
                                 self.context_page = ContextPage::Preview(
                                     None,
-                                    PreviewKind::Custom1(PreviewItem1(item)),
+                                    PreviewKind::Custom(PreviewItem(item)),
                                 );
                                 self.set_show_context(true);
                             }
@@ -6897,14 +11648,18 @@ impl Application for App {
                 }
 
                 NavMenuAction::EmptyTrash => {
-                    self.dialog_pages.push_front(DialogPage::EmptyTrash);
+                    if self.config.confirm.empty_trash {
+                        self.dialog_pages.push_front(DialogPage::EmptyTrash);
+                    } else {
+                        self.operation(Operation::EmptyTrash);
+                    }
                 }
             },
             Message::Recents => {
                 if self.active_panel == PaneType::LeftPane {
-                    return self.open_tab(Location1::Recents, false, None);
+                    return self.open_tab(Location::Recents, false, None);
                 } else {
-                    return self.open_tab_right(Location2::Recents, false, None);
+                    return self.open_tab_right(Location::Recents, false, None);
                 }
             }
             #[cfg(feature = "wayland")]
@@ -7085,7 +11840,7 @@ impl Application for App {
                     }
                 };
                 if self.active_panel == PaneType::LeftPane {
-                    if let Some(tab) = self.tab_model1.data::<Tab1>(entity) {
+                    if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
                         if let Some(items) = tab.items_opt() {
                             for item in items.iter() {
                                 if item.selected {
@@ -7109,7 +11864,7 @@ impl Application for App {
                     )
                     .header_actions(actions)
                 } else {
-                    if let Some(tab) = self.tab_model2.data::<Tab2>(entity) {
+                    if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
                         if let Some(items) = tab.items_opt() {
                             for item in items.iter() {
                                 if item.selected {
@@ -7147,7 +11902,7 @@ impl Application for App {
         //TODO: should gallery view just be a dialog?
         if self.active_panel == PaneType::LeftPane {
             let entity = self.tab_model1.active();
-            if let Some(tab) = self.tab_model1.data::<Tab1>(entity) {
+            if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
                 {
                     if tab.gallery {
                         return Some(
@@ -7159,7 +11914,7 @@ impl Application for App {
             }
         } else {
             let entity = self.tab_model2.active();
-            if let Some(tab) = self.tab_model2.data::<Tab2>(entity) {
+            if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
                 {
                     if tab.gallery {
                         return Some(
@@ -7171,6 +11926,10 @@ impl Application for App {
             }
         }
 
+        if let Some(text_diff) = &self.compare_opt {
+            return Some(text_diff.view(Message::CompareClose));
+        }
+
         let dialog_page = match self.dialog_pages.front() {
             Some(some) => some,
             None => return None,
@@ -7180,7 +11939,141 @@ impl Application for App {
             space_xxs, space_s, ..
         } = theme::active().cosmic().spacing;
 
+        // New and renamed items are checked against the file system of the active tab
+        let fs_type = if self.active_panel == PaneType::LeftPane {
+            self.tab_model1
+                .active_data::<Tab>()
+                .and_then(|tab| tab.filesystem.as_ref())
+                .map_or("", |filesystem| filesystem.fs_type.as_str())
+        } else {
+            self.tab_model2
+                .active_data::<Tab>()
+                .and_then(|tab| tab.filesystem.as_ref())
+                .map_or("", |filesystem| filesystem.fs_type.as_str())
+        };
+
         let dialog = match dialog_page {
+            DialogPage::AddToArchive { paths, archive } => {
+                let complete_maybe = operation::archive_appendable(Path::new(archive))
+                    .then_some(Message::DialogComplete);
+                widget::dialog()
+                    .title(fl!("add-to-archive"))
+                    .body(fl!("add-to-archive-body", items = paths.len()))
+                    .primary_action(
+                        widget::button::suggested(fl!("add"))
+                            .on_press_maybe(complete_maybe.clone()),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::text_input(fl!("archive-path"), archive.as_str())
+                            .id(self.dialog_text_input.clone())
+                            .on_input(move |archive| {
+                                Message::DialogUpdate(DialogPage::AddToArchive {
+                                    paths: paths.clone(),
+                                    archive,
+                                })
+                            })
+                            .on_submit_maybe(complete_maybe),
+                    )
+            }
+            DialogPage::Bookmarks { bookmarks } => {
+                let update = |bookmarks| Message::DialogUpdate(DialogPage::Bookmarks { bookmarks });
+                let mut column = widget::column::with_capacity(bookmarks.len()).spacing(space_s);
+                for (i, bookmark) in bookmarks.iter().enumerate() {
+                    let mut removed = bookmarks.clone();
+                    removed.remove(i);
+                    let moved_up = (i > 0).then(|| {
+                        let mut moved = bookmarks.clone();
+                        moved.swap(i - 1, i);
+                        update(moved)
+                    });
+                    let moved_down = (i + 1 < bookmarks.len()).then(|| {
+                        let mut moved = bookmarks.clone();
+                        moved.swap(i, i + 1);
+                        update(moved)
+                    });
+                    column = column.push(
+                        widget::column::with_children(vec![
+                            widget::text::caption(bookmark.path.display().to_string()).into(),
+                            widget::row::with_children(vec![
+                                widget::text_input(fl!("bookmark-name"), bookmark.name.as_str())
+                                    .width(Length::Fixed(160.0))
+                                    .on_input(move |name| {
+                                        let mut bookmarks = bookmarks.clone();
+                                        bookmarks[i].name = name;
+                                        update(bookmarks)
+                                    })
+                                    .into(),
+                                widget::text_input(
+                                    fl!("bookmark-folder"),
+                                    bookmark.folder.as_str(),
+                                )
+                                .width(Length::Fixed(120.0))
+                                .on_input(move |folder| {
+                                    let mut bookmarks = bookmarks.clone();
+                                    bookmarks[i].folder = folder;
+                                    update(bookmarks)
+                                })
+                                .into(),
+                                widget::text_input(fl!("bookmark-icon"), bookmark.icon.as_str())
+                                    .width(Length::Fixed(160.0))
+                                    .on_input(move |icon| {
+                                        let mut bookmarks = bookmarks.clone();
+                                        bookmarks[i].icon = icon;
+                                        update(bookmarks)
+                                    })
+                                    .into(),
+                                widget::dropdown(
+                                    &self.bookmark_shortcuts,
+                                    Some(bookmark.shortcut.map_or(0, usize::from)),
+                                    move |index| {
+                                        let mut bookmarks = bookmarks.clone();
+                                        let shortcut = (index > 0).then_some(index as u8);
+                                        // A shortcut opens only one bookmark
+                                        for bookmark in bookmarks.iter_mut() {
+                                            if shortcut.is_some() && bookmark.shortcut == shortcut {
+                                                bookmark.shortcut = None;
+                                            }
+                                        }
+                                        bookmarks[i].shortcut = shortcut;
+                                        update(bookmarks)
+                                    },
+                                )
+                                .into(),
+                                widget::button::icon(widget::icon::from_name("go-up-symbolic"))
+                                    .on_press_maybe(moved_up)
+                                    .into(),
+                                widget::button::icon(widget::icon::from_name("go-down-symbolic"))
+                                    .on_press_maybe(moved_down)
+                                    .into(),
+                                widget::button::icon(widget::icon::from_name(
+                                    "edit-delete-symbolic",
+                                ))
+                                .on_press(update(removed))
+                                .into(),
+                            ])
+                            .align_y(Alignment::Center)
+                            .spacing(space_xxs)
+                            .into(),
+                        ])
+                        .spacing(space_xxs),
+                    );
+                }
+                if bookmarks.is_empty() {
+                    column = column.push(widget::text::body(fl!("no-bookmarks")));
+                }
+                widget::dialog()
+                    .title(fl!("edit-bookmarks"))
+                    .primary_action(
+                        widget::button::suggested(fl!("save")).on_press(Message::DialogComplete),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(widget::scrollable(column).height(Length::Fixed(480.0)))
+            }
             DialogPage::Compress {
                 paths,
                 to,
@@ -7284,62 +12177,484 @@ impl Application for App {
 
                 dialog
             }
-            DialogPage::EmptyTrash => widget::dialog()
-                .title(fl!("empty-trash"))
-                .body(fl!("empty-trash-warning"))
-                .primary_action(
-                    widget::button::suggested(fl!("empty-trash")).on_press(Message::DialogComplete),
-                )
-                .secondary_action(
-                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
-                ),
-            DialogPage::FailedOperation(id) => {
-                //TODO: try next dialog page (making sure index is used by Dialog messages)?
-                let (operation, _, err) = self.failed_operations.get(id)?;
+            DialogPage::CreateLauncher { launcher, dir } => {
+                let dir = *dir;
+                let update = move |launcher| {
+                    Message::DialogUpdate(DialogPage::CreateLauncher { launcher, dir })
+                };
+                let validation = launcher.validate();
+                let complete_maybe = validation.is_ok().then_some(Message::DialogComplete);
+                let mut dialog = widget::dialog().title(fl!("create-launcher")).body(fl!(
+                    "create-launcher-body",
+                    program = launcher.program.display().to_string()
+                ));
+                if let Err(err) = validation {
+                    dialog = dialog.tertiary_action(widget::text::body(err));
+                }
 
-                //TODO: nice description of error
-                widget::dialog()
-                    .title("Failed operation")
-                    .body(format!("{:#?}\n{}", operation, err))
-                    .icon(widget::icon::from_name("dialog-error").size(64))
-                    //TODO: retry action
-                    .primary_action(
-                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
-                    )
-            }
-            DialogPage::ExtractPassword { id, password } => {
-                widget::dialog()
-                    .title(fl!("extract-password-required"))
-                    .icon(widget::icon::from_name("dialog-error").size(64))
-                    .control(widget::text_input("", password).password().on_input(
-                        move |password| {
-                            Message::DialogUpdate(DialogPage::ExtractPassword { id: *id, password })
-                        },
-                    ))
+                let icon_path = Path::new(launcher.icon.trim());
+                let icon_preview = if icon_path.is_absolute() {
+                    widget::icon::icon(widget::icon::from_path(icon_path.to_path_buf())).size(32)
+                } else {
+                    widget::icon::from_name(launcher.icon.trim())
+                        .size(32)
+                        .icon()
+                };
+                let mut icons =
+                    widget::row::with_capacity(desktop_entry::ICONS.len()).spacing(space_xxs);
+                for icon in desktop_entry::ICONS {
+                    icons = icons.push(
+                        widget::button::icon(widget::icon::from_name(*icon).size(24)).on_press(
+                            update(Launcher {
+                                icon: icon.to_string(),
+                                ..launcher.clone()
+                            }),
+                        ),
+                    );
+                }
+
+                let mut categories = widget::column::with_capacity(3).spacing(space_xxs);
+                for chunk in desktop_entry::CATEGORIES.chunks(4) {
+                    let mut row = widget::row::with_capacity(chunk.len()).spacing(space_s);
+                    for category in chunk {
+                        let checked = launcher.categories.contains(category);
+                        let launcher = launcher.clone();
+                        row = row.push(widget::checkbox(*category, checked).on_toggle(
+                            move |checked| {
+                                let mut launcher = launcher.clone();
+                                if checked {
+                                    launcher.categories.push(*category);
+                                } else {
+                                    launcher.categories.retain(|x| x != category);
+                                }
+                                update(launcher)
+                            },
+                        ));
+                    }
+                    categories = categories.push(row);
+                }
+
+                dialog
                     .primary_action(
-                        widget::button::suggested(fl!("extract-here"))
-                            .on_press(Message::DialogComplete),
+                        widget::button::suggested(fl!("create"))
+                            .on_press_maybe(complete_maybe.clone()),
                     )
                     .secondary_action(
                         widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
                     )
-            }
-            DialogPage::MountError {
-                mounter_key: _,
-                item: _,
-                error,
-            } => widget::dialog()
-                .title(fl!("mount-error"))
-                .body(error)
-                .icon(widget::icon::from_name("dialog-error").size(64))
-                .primary_action(
-                    widget::button::standard(fl!("try-again")).on_press(Message::DialogComplete),
-                )
-                .secondary_action(
-                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
-                ),
-            DialogPage::NetworkAuth {
-                mounter_key,
+                    .control(
+                        widget::column::with_children(vec![
+                            widget::text::body(fl!("name")).into(),
+                            widget::text_input("", launcher.name.as_str())
+                                .id(self.dialog_text_input.clone())
+                                .on_input(move |name| {
+                                    update(Launcher {
+                                        name,
+                                        ..launcher.clone()
+                                    })
+                                })
+                                .on_submit_maybe(complete_maybe)
+                                .into(),
+                        ])
+                        .spacing(space_xxs),
+                    )
+                    .control(
+                        widget::column::with_children(vec![
+                            widget::text::body(fl!("launcher-icon")).into(),
+                            widget::row::with_children(vec![
+                                icon_preview.into(),
+                                widget::text_input(
+                                    fl!("launcher-icon-placeholder"),
+                                    launcher.icon.as_str(),
+                                )
+                                .on_input(move |icon| {
+                                    update(Launcher {
+                                        icon,
+                                        ..launcher.clone()
+                                    })
+                                })
+                                .into(),
+                            ])
+                            .align_y(Alignment::Center)
+                            .spacing(space_xxs)
+                            .into(),
+                            icons.into(),
+                        ])
+                        .spacing(space_xxs),
+                    )
+                    .control(
+                        widget::column::with_children(vec![
+                            widget::text::body(fl!("launcher-categories")).into(),
+                            categories.into(),
+                        ])
+                        .spacing(space_xxs),
+                    )
+                    .control(
+                        widget::row::with_children(vec![
+                            widget::checkbox(fl!("launcher-terminal"), launcher.terminal)
+                                .on_toggle(move |terminal| {
+                                    update(Launcher {
+                                        terminal,
+                                        ..launcher.clone()
+                                    })
+                                })
+                                .into(),
+                            widget::horizontal_space().into(),
+                            widget::dropdown(
+                                &self.launcher_dirs,
+                                LauncherDir::all().iter().position(|x| *x == dir),
+                                move |index| {
+                                    Message::DialogUpdate(DialogPage::CreateLauncher {
+                                        launcher: launcher.clone(),
+                                        dir: LauncherDir::all()[index],
+                                    })
+                                },
+                            )
+                            .into(),
+                        ])
+                        .align_y(Alignment::Center),
+                    )
+            }
+            DialogPage::ConfirmExecute {
+                path,
+                binary,
+                remember,
+            } => {
+                let name = path.file_name().map_or_else(
+                    || path.display().to_string(),
+                    |name| name.to_string_lossy().to_string(),
+                );
+                let mut choices = widget::row::with_capacity(2).spacing(space_xxs).push(
+                    widget::button::standard(fl!("run-in-terminal"))
+                        .on_press(Message::RunChoice(RunChoice::RunInTerminal)),
+                );
+                if !binary {
+                    choices = choices.push(
+                        widget::button::standard(fl!("open-in-editor"))
+                            .on_press(Message::RunChoice(RunChoice::Open)),
+                    );
+                }
+                widget::dialog()
+                    .title(fl!("confirm-execute-title", name = name))
+                    .body(fl!("confirm-execute-warning"))
+                    .control(choices)
+                    .control(
+                        widget::checkbox(fl!("remember-for-type"), *remember).on_toggle(
+                            |remember| {
+                                Message::DialogUpdate(DialogPage::ConfirmExecute {
+                                    path: path.clone(),
+                                    binary: *binary,
+                                    remember,
+                                })
+                            },
+                        ),
+                    )
+                    .primary_action(
+                        widget::button::suggested(fl!("confirm-execute"))
+                            .on_press(Message::RunChoice(RunChoice::Run)),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+            }
+            DialogPage::ConfirmMove { to, contents } => widget::dialog()
+                .title(fl!("confirm-move"))
+                .body(fl!(
+                    "confirm-move-warning",
+                    items = contents.paths.len(),
+                    to = to.display().to_string()
+                ))
+                .primary_action(
+                    widget::button::suggested(fl!("confirm-move"))
+                        .on_press(Message::DialogComplete),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                ),
+            DialogPage::ConfirmTrash { paths } => widget::dialog()
+                .title(fl!("move-to-trash"))
+                .body(fl!("confirm-trash-warning", items = paths.len()))
+                .primary_action(
+                    widget::button::destructive(fl!("move-to-trash"))
+                        .on_press(Message::DialogComplete),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                ),
+            DialogPage::EmptyTrash => widget::dialog()
+                .title(fl!("empty-trash"))
+                .body(fl!("empty-trash-warning"))
+                .primary_action(
+                    widget::button::suggested(fl!("empty-trash")).on_press(Message::DialogComplete),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                ),
+            DialogPage::ExportListing {
+                dir,
+                to,
+                recursive,
+                format,
+                columns,
+            } => {
+                let update = move |to, recursive, format, columns| {
+                    Message::DialogUpdate(DialogPage::ExportListing {
+                        dir: dir.clone(),
+                        to,
+                        recursive,
+                        format,
+                        columns,
+                    })
+                };
+                let path = Path::new(to);
+                let complete_maybe = (path.file_name().is_some()
+                    && path.parent().is_some_and(Path::is_dir))
+                .then_some(Message::DialogComplete);
+                let mut dialog = widget::dialog().title(fl!("export-listing")).body(fl!(
+                    "export-listing-body",
+                    folder = dir.display().to_string()
+                ));
+                if path.is_file() {
+                    dialog = dialog.tertiary_action(widget::text::body(fl!("file-already-exists")));
+                }
+                let formats = ListingFormat::all();
+                let columns = *columns;
+                let recursive = *recursive;
+                let format = *format;
+                dialog
+                    .primary_action(
+                        widget::button::suggested(fl!("export"))
+                            .on_press_maybe(complete_maybe.clone()),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::row::with_children(vec![
+                            widget::text_input(fl!("listing-path"), to.as_str())
+                                .id(self.dialog_text_input.clone())
+                                .on_input(move |to| update(to, recursive, format, columns))
+                                .on_submit_maybe(complete_maybe)
+                                .into(),
+                            widget::dropdown(
+                                formats,
+                                formats.iter().position(|x| *x == format),
+                                move |index| {
+                                    // The extension follows the format unless it was changed
+                                    let new_format = formats[index];
+                                    let mut path = PathBuf::from(to);
+                                    if path.extension().is_some_and(|x| x == format.extension()) {
+                                        path.set_extension(new_format.extension());
+                                    }
+                                    update(
+                                        path.to_string_lossy().to_string(),
+                                        recursive,
+                                        new_format,
+                                        columns,
+                                    )
+                                },
+                            )
+                            .into(),
+                        ])
+                        .align_y(Alignment::Center)
+                        .spacing(space_xxs),
+                    )
+                    .control(
+                        widget::column::with_children(vec![
+                            widget::checkbox(fl!("listing-recursive"), recursive)
+                                .on_toggle(move |recursive| {
+                                    update(to.clone(), recursive, format, columns)
+                                })
+                                .into(),
+                            widget::checkbox(fl!("size"), columns.size)
+                                .on_toggle(move |size| {
+                                    update(
+                                        to.clone(),
+                                        recursive,
+                                        format,
+                                        ListingColumns { size, ..columns },
+                                    )
+                                })
+                                .into(),
+                            widget::checkbox(fl!("modified"), columns.modified)
+                                .on_toggle(move |modified| {
+                                    update(
+                                        to.clone(),
+                                        recursive,
+                                        format,
+                                        ListingColumns {
+                                            modified,
+                                            ..columns
+                                        },
+                                    )
+                                })
+                                .into(),
+                            widget::checkbox(fl!("listing-checksum"), columns.checksum)
+                                .on_toggle(move |checksum| {
+                                    update(
+                                        to.clone(),
+                                        recursive,
+                                        format,
+                                        ListingColumns {
+                                            checksum,
+                                            ..columns
+                                        },
+                                    )
+                                })
+                                .into(),
+                        ])
+                        .spacing(space_xxs),
+                    )
+            }
+            DialogPage::InsufficientSpace { operation, check } => {
+                let (title, to) = match operation {
+                    Operation::Move { to, .. } => (fl!("insufficient-space-move"), to),
+                    Operation::Copy { to, .. } => (fl!("insufficient-space-copy"), to),
+                    _ => return None,
+                };
+                let dialog = widget::dialog()
+                    .title(title)
+                    .body(fl!(
+                        "insufficient-space-body",
+                        required = tab::format_size(check.required),
+                        available = tab::format_size(check.available),
+                        missing = tab::format_size(check.required - check.available),
+                        destination = to.display().to_string()
+                    ))
+                    .icon(widget::icon::from_name("dialog-warning").size(64));
+                if self.config.refuse_insufficient_space {
+                    dialog.primary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                } else {
+                    dialog
+                        .primary_action(
+                            widget::button::destructive(fl!("continue-anyway"))
+                                .on_press(Message::DialogComplete),
+                        )
+                        .secondary_action(
+                            widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                        )
+                }
+            }
+            DialogPage::FailedOperation(id) => {
+                //TODO: try next dialog page (making sure index is used by Dialog messages)?
+                let (operation, _, err) = self.failed_operations.get(id)?;
+
+                //TODO: nice description of error
+                let dialog = widget::dialog()
+                    .title("Failed operation")
+                    .body(format!("{:#?}\n{}", operation, err))
+                    .icon(widget::icon::from_name("dialog-error").size(64));
+                if err.is_permission_denied() && operation.elevated_command().is_some() {
+                    dialog
+                        .primary_action(
+                            widget::button::suggested(fl!("retry-as-administrator"))
+                                .on_press(Message::DialogComplete),
+                        )
+                        .secondary_action(
+                            widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                        )
+                } else {
+                    //TODO: retry action
+                    dialog.primary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                }
+            }
+            DialogPage::ExtractPassword { id, password } => {
+                widget::dialog()
+                    .title(fl!("extract-password-required"))
+                    .icon(widget::icon::from_name("dialog-error").size(64))
+                    .control(widget::text_input("", password).password().on_input(
+                        move |password| {
+                            Message::DialogUpdate(DialogPage::ExtractPassword { id: *id, password })
+                        },
+                    ))
+                    .primary_action(
+                        widget::button::suggested(fl!("extract-here"))
+                            .on_press(Message::DialogComplete),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+            }
+            DialogPage::ItemFailed {
+                path,
+                error,
+                apply_to_all,
+                tx,
+            } => {
+                let name = path.file_name().map_or_else(
+                    || path.display().to_string(),
+                    |name| name.to_string_lossy().to_string(),
+                );
+                widget::dialog()
+                    .title(fl!("item-failed-title", name = name))
+                    .body(error.as_str())
+                    .icon(widget::icon::from_name("dialog-error").size(64))
+                    .control(
+                        widget::checkbox(fl!("apply-to-all"), *apply_to_all).on_toggle(
+                            |apply_to_all| {
+                                Message::DialogUpdate(DialogPage::ItemFailed {
+                                    path: path.clone(),
+                                    error: error.clone(),
+                                    apply_to_all,
+                                    tx: tx.clone(),
+                                })
+                            },
+                        ),
+                    )
+                    .primary_action(
+                        widget::button::suggested(fl!("retry"))
+                            .on_press(Message::ErrorResult(ErrorResult::Retry)),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("skip"))
+                            .on_press(Message::ErrorResult(ErrorResult::Skip(*apply_to_all))),
+                    )
+                    .tertiary_action(
+                        widget::button::text(fl!("abort"))
+                            .on_press(Message::ErrorResult(ErrorResult::Abort)),
+                    )
+            }
+            DialogPage::LoadSelection { path } => {
+                let complete_maybe = Path::new(path).is_file().then_some(Message::DialogComplete);
+                widget::dialog()
+                    .title(fl!("load-selection"))
+                    .body(fl!("load-selection-body"))
+                    .primary_action(
+                        widget::button::suggested(fl!("load"))
+                            .on_press_maybe(complete_maybe.clone()),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::text_input(fl!("selection-list-path"), path.as_str())
+                            .id(self.dialog_text_input.clone())
+                            .on_input(move |path| {
+                                Message::DialogUpdate(DialogPage::LoadSelection { path })
+                            })
+                            .on_submit_maybe(complete_maybe),
+                    )
+            }
+            DialogPage::MountError {
+                mounter_key: _,
+                item: _,
+                error,
+            } => widget::dialog()
+                .title(fl!("mount-error"))
+                .body(error)
+                .icon(widget::icon::from_name("dialog-error").size(64))
+                .primary_action(
+                    widget::button::standard(fl!("try-again")).on_press(Message::DialogComplete),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                ),
+            DialogPage::NetworkAuth {
+                mounter_key,
                 uri,
                 auth,
                 auth_tx,
@@ -7438,12 +12753,19 @@ impl Application for App {
                 let title = parts.next().unwrap_or_default();
                 let body = parts.next().unwrap_or_default();
 
+                // Passphrases for encrypted volumes are asked for without a username
+                let label = if auth.username_opt.is_none() && auth.domain_opt.is_none() {
+                    fl!("unlock")
+                } else {
+                    fl!("connect")
+                };
+
                 let mut widget = widget::dialog()
                     .title(title)
                     .body(body)
                     .control(widget::column::with_children(controls).spacing(space_s))
                     .primary_action(
-                        widget::button::suggested(fl!("connect")).on_press(Message::DialogComplete),
+                        widget::button::suggested(label).on_press(Message::DialogComplete),
                     )
                     .secondary_action(
                         widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
@@ -7481,41 +12803,30 @@ impl Application for App {
                 .secondary_action(
                     widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
                 ),
-            DialogPage::NewItem { parent, name, dir } => {
+            DialogPage::NewItem {
+                parent,
+                name,
+                dir,
+                contents,
+            } => {
                 let mut dialog = widget::dialog().title(if *dir {
                     fl!("create-new-folder")
+                } else if contents.is_some() {
+                    fl!("paste-as-new-file")
                 } else {
                     fl!("create-new-file")
                 });
 
                 let complete_maybe = if name.is_empty() {
                     None
-                } else if name == "." || name == ".." {
-                    dialog = dialog.tertiary_action(widget::text::body(fl!(
-                        "name-invalid",
-                        filename = name.as_str()
-                    )));
-                    None
-                } else if name.contains('/') {
-                    dialog = dialog.tertiary_action(widget::text::body(fl!("name-no-slashes")));
+                } else if let Some(error) = tab::name_error(parent, None, name, fs_type) {
+                    dialog = dialog.tertiary_action(widget::text::body(error));
                     None
                 } else {
-                    let path = parent.join(name);
-                    if path.exists() {
-                        if path.is_dir() {
-                            dialog = dialog
-                                .tertiary_action(widget::text::body(fl!("folder-already-exists")));
-                        } else {
-                            dialog = dialog
-                                .tertiary_action(widget::text::body(fl!("file-already-exists")));
-                        }
-                        None
-                    } else {
-                        if name.starts_with('.') {
-                            dialog = dialog.tertiary_action(widget::text::body(fl!("name-hidden")));
-                        }
-                        Some(Message::DialogComplete)
+                    if name.starts_with('.') {
+                        dialog = dialog.tertiary_action(widget::text::body(fl!("name-hidden")));
                     }
+                    Some(Message::DialogComplete)
                 };
 
                 dialog
@@ -7541,6 +12852,7 @@ impl Application for App {
                                         parent: parent.clone(),
                                         name,
                                         dir: *dir,
+                                        contents: contents.clone(),
                                     })
                                 })
                                 .on_submit_maybe(complete_maybe)
@@ -7554,15 +12866,29 @@ impl Application for App {
                 mime,
                 selected,
                 store_opt,
-                ..
+                search,
+                custom_command,
+                set_default,
             } => {
                 let name = match path.file_name() {
                     Some(file_name) => file_name.to_str(),
                     None => path.as_os_str().to_str(),
                 };
 
+                let search_lowercase = search.to_lowercase();
+                let registered = self.mime_app_cache.get(mime).len();
+                let mut other_heading = registered > 0;
                 let mut column = widget::list_column();
-                for (i, app) in self.mime_app_cache.get(mime).iter().enumerate() {
+                for (i, app) in self.mime_app_cache.open_with(mime).enumerate() {
+                    if !search_lowercase.is_empty()
+                        && !app.name.to_lowercase().contains(&search_lowercase)
+                    {
+                        continue;
+                    }
+                    if i >= registered && other_heading {
+                        column = column.add(widget::text::heading(fl!("other-applications")));
+                        other_heading = false;
+                    }
                     column = column.add(
                         widget::button::custom(
                             widget::row::with_children(vec![
@@ -7577,7 +12903,7 @@ impl Application for App {
                                     widget::text::body(app.name.to_string()).into()
                                 },
                                 widget::horizontal_space().into(),
-                                if *selected == i {
+                                if *selected == i && custom_command.is_empty() {
                                     widget::icon::from_name("checkbox-checked-symbolic")
                                         .size(16)
                                         .into()
@@ -7603,16 +12929,127 @@ impl Application for App {
                     .secondary_action(
                         widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
                     )
-                    .control(column);
-
-                if let Some(app) = store_opt {
-                    dialog = dialog.tertiary_action(
-                        widget::button::text(fl!("browse-store", store = app.name.as_str()))
-                            .on_press(Message::OpenWithBrowse),
-                    );
-                }
-
-                dialog
+                    .control(
+                        widget::text_input::search_input(fl!("search-applications"), search)
+                            .on_input(Message::OpenWithSearch),
+                    )
+                    .control(widget::scrollable(column).height(Length::Fixed(320.0)))
+                    .control(
+                        widget::column::with_children(vec![
+                            widget::text::body(fl!("custom-command")).into(),
+                            widget::text_input("", custom_command)
+                                .on_input(Message::OpenWithCustomCommand)
+                                .on_submit(Message::DialogComplete)
+                                .into(),
+                        ])
+                        .spacing(space_xxs),
+                    )
+                    .control(
+                        widget::checkbox(
+                            fl!("always-use-for-type", mime = mime.to_string()),
+                            *set_default,
+                        )
+                        .on_toggle(Message::OpenWithSetDefault),
+                    );
+
+                if let Some(app) = store_opt {
+                    dialog = dialog.tertiary_action(
+                        widget::button::text(fl!("browse-store", store = app.name.as_str()))
+                            .on_press(Message::OpenWithBrowse),
+                    );
+                }
+
+                dialog
+            }
+            DialogPage::Print { job, options } => {
+                let update = move |options| {
+                    Message::DialogUpdate(DialogPage::Print {
+                        job: job.clone(),
+                        options,
+                    })
+                };
+                let options = *options;
+                widget::dialog()
+                    .title(fl!("print"))
+                    .body(match job {
+                        PrintJob::Listing(dir) => {
+                            fl!("print-listing-body", folder = dir.display().to_string())
+                        }
+                        PrintJob::Files(paths) => fl!("print-files-body", items = paths.len()),
+                    })
+                    .primary_action(
+                        widget::button::suggested(fl!("print")).on_press(Message::DialogComplete),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::column::with_children(vec![
+                            widget::checkbox(fl!("print-header"), options.header)
+                                .on_toggle(move |header| update(PrintOptions { header, ..options }))
+                                .into(),
+                            widget::checkbox(fl!("print-footer"), options.footer)
+                                .on_toggle(move |footer| update(PrintOptions { footer, ..options }))
+                                .into(),
+                        ])
+                        .spacing(space_xxs),
+                    )
+            }
+            DialogPage::PreviewChanges { operation, preview } => {
+                let mut body = Vec::with_capacity(2);
+                if !preview.deleted.is_empty() {
+                    body.push(fl!(
+                        "preview-changes-trash",
+                        count = preview.deleted.len(),
+                        size = tab::format_size(preview.deleted_size())
+                    ));
+                }
+                if !preview.overwritten.is_empty() {
+                    body.push(fl!(
+                        "preview-changes-overwrite",
+                        count = preview.overwritten.len(),
+                        size = tab::format_size(preview.overwritten_size())
+                    ));
+                }
+                let items: Vec<_> = preview
+                    .deleted
+                    .iter()
+                    .chain(preview.overwritten.iter())
+                    .collect();
+                let mut column = widget::column::with_capacity(PREVIEW_CHANGES_MAX + 1);
+                for (path, size) in items.iter().take(PREVIEW_CHANGES_MAX) {
+                    column = column.push(
+                        widget::row::with_children(vec![
+                            widget::text::body(path.display().to_string())
+                                .width(Length::Fill)
+                                .into(),
+                            widget::text::caption(tab::format_size(*size)).into(),
+                        ])
+                        .spacing(space_xxs),
+                    );
+                }
+                if items.len() > PREVIEW_CHANGES_MAX {
+                    column = column.push(widget::text::caption(fl!(
+                        "preview-changes-more",
+                        count = items.len() - PREVIEW_CHANGES_MAX
+                    )));
+                }
+                let title = match operation {
+                    Operation::Delete { .. } => fl!("move-to-trash"),
+                    _ => fl!("preview-changes-title"),
+                };
+                widget::dialog()
+                    .title(title)
+                    .body(body.join("\n"))
+                    .icon(widget::icon::from_name("dialog-warning").size(64))
+                    .control(widget::scrollable(column).height(Length::Fixed(320.0)))
+                    .primary_action(
+                        widget::button::destructive(fl!("continue-anyway"))
+                            .on_press(Message::DialogComplete),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
             }
             DialogPage::RenameItem {
                 from,
@@ -7629,32 +13066,16 @@ impl Application for App {
 
                 let complete_maybe = if name.is_empty() {
                     None
-                } else if name == "." || name == ".." {
-                    dialog = dialog.tertiary_action(widget::text::body(fl!(
-                        "name-invalid",
-                        filename = name.as_str()
-                    )));
-                    None
-                } else if name.contains('/') {
-                    dialog = dialog.tertiary_action(widget::text::body(fl!("name-no-slashes")));
+                } else if let Some(error) =
+                    tab::name_error(parent, Some(from.as_path()), name, fs_type)
+                {
+                    dialog = dialog.tertiary_action(widget::text::body(error));
                     None
                 } else {
-                    let path = parent.join(name);
-                    if from != &path && path.exists() {
-                        if path.is_dir() {
-                            dialog = dialog
-                                .tertiary_action(widget::text::body(fl!("folder-already-exists")));
-                        } else {
-                            dialog = dialog
-                                .tertiary_action(widget::text::body(fl!("file-already-exists")));
-                        }
-                        None
-                    } else {
-                        if name.starts_with('.') {
-                            dialog = dialog.tertiary_action(widget::text::body(fl!("name-hidden")));
-                        }
-                        Some(Message::DialogComplete)
+                    if name.starts_with('.') {
+                        dialog = dialog.tertiary_action(widget::text::body(fl!("name-hidden")));
                     }
+                    Some(Message::DialogComplete)
                 };
 
                 dialog
@@ -7703,127 +13124,761 @@ impl Application for App {
                         to.replace_view(fl!("original-file"), IconSizes::default())
                             .map(|x| Message::TabMessage(None, x)),
                     )
-                    .control(
-                        from.replace_view(fl!("replace-with"), IconSizes::default())
-                            .map(|x| Message::TabMessage(None, x)),
+                    .control(
+                        from.replace_view(fl!("replace-with"), IconSizes::default())
+                            .map(|x| Message::TabMessage(None, x)),
+                    )
+                    .primary_action(widget::button::suggested(fl!("replace")).on_press(
+                        Message::ReplaceResult(ReplaceResult::Replace(*apply_to_all)),
+                    ));
+                if *multiple {
+                    dialog
+                        .control(
+                            widget::checkbox(fl!("apply-to-all"), *apply_to_all).on_toggle(
+                                |apply_to_all| {
+                                    Message::DialogUpdate(DialogPage::Replace1 {
+                                        from: from.clone(),
+                                        to: to.clone(),
+                                        multiple: *multiple,
+                                        apply_to_all,
+                                        tx: tx.clone(),
+                                    })
+                                },
+                            ),
+                        )
+                        .secondary_action(
+                            widget::button::standard(fl!("skip")).on_press(Message::ReplaceResult(
+                                ReplaceResult::Skip(*apply_to_all),
+                            )),
+                        )
+                        .tertiary_action(
+                            widget::button::text(fl!("cancel"))
+                                .on_press(Message::ReplaceResult(ReplaceResult::Cancel)),
+                        )
+                } else {
+                    dialog
+                        .secondary_action(
+                            widget::button::standard(fl!("cancel"))
+                                .on_press(Message::ReplaceResult(ReplaceResult::Cancel)),
+                        )
+                        .tertiary_action(
+                            widget::button::text(fl!("keep-both"))
+                                .on_press(Message::ReplaceResult(ReplaceResult::KeepBoth)),
+                        )
+                }
+            }
+            DialogPage::Replace2 {
+                from,
+                to,
+                multiple,
+                apply_to_all,
+                tx,
+            } => {
+                let dialog = widget::dialog()
+                    .title(fl!("replace-title", filename = to.name.as_str()))
+                    .body(fl!("replace-warning-operation"))
+                    .control(
+                        to.replace_view(fl!("original-file"), IconSizes::default())
+                            .map(|x| Message::TabMessageRight(None, x)),
+                    )
+                    .control(
+                        from.replace_view(fl!("replace-with"), IconSizes::default())
+                            .map(|x| Message::TabMessageRight(None, x)),
+                    )
+                    .primary_action(widget::button::suggested(fl!("replace")).on_press(
+                        Message::ReplaceResult(ReplaceResult::Replace(*apply_to_all)),
+                    ));
+                if *multiple {
+                    dialog
+                        .control(
+                            widget::checkbox(fl!("apply-to-all"), *apply_to_all).on_toggle(
+                                |apply_to_all| {
+                                    Message::DialogUpdate(DialogPage::Replace2 {
+                                        from: from.clone(),
+                                        to: to.clone(),
+                                        multiple: *multiple,
+                                        apply_to_all,
+                                        tx: tx.clone(),
+                                    })
+                                },
+                            ),
+                        )
+                        .secondary_action(
+                            widget::button::standard(fl!("skip")).on_press(Message::ReplaceResult(
+                                ReplaceResult::Skip(*apply_to_all),
+                            )),
+                        )
+                        .tertiary_action(
+                            widget::button::text(fl!("cancel"))
+                                .on_press(Message::ReplaceResult(ReplaceResult::Cancel)),
+                        )
+                } else {
+                    dialog
+                        .secondary_action(
+                            widget::button::standard(fl!("cancel"))
+                                .on_press(Message::ReplaceResult(ReplaceResult::Cancel)),
+                        )
+                        .tertiary_action(
+                            widget::button::text(fl!("keep-both"))
+                                .on_press(Message::ReplaceResult(ReplaceResult::KeepBoth)),
+                        )
+                }
+            }
+            DialogPage::QuickJump { search } => {
+                let mut column = widget::list_column();
+                for path in self.quick_jump_results(search).into_iter().take(50) {
+                    let name = path.file_name().map_or_else(
+                        || path.display().to_string(),
+                        |name| name.to_string_lossy().to_string(),
+                    );
+                    column = column.add(
+                        widget::button::custom(widget::column::with_children(vec![
+                            widget::text::body(name).into(),
+                            widget::text::caption(path.display().to_string()).into(),
+                        ]))
+                        .width(Length::Fill)
+                        .class(theme::Button::MenuItem)
+                        .on_press(Message::QuickJumpOpen(path)),
+                    );
+                }
+
+                widget::dialog()
+                    .title(fl!("quick-jump"))
+                    .primary_action(
+                        widget::button::suggested(fl!("open")).on_press(Message::DialogComplete),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::text_input::search_input(fl!("quick-jump-hint"), search.as_str())
+                            .id(self.dialog_text_input.clone())
+                            .on_input(move |search| {
+                                Message::DialogUpdate(DialogPage::QuickJump { search })
+                            })
+                            .on_submit(Message::DialogComplete),
+                    )
+                    .control(widget::scrollable(column).height(Length::Fixed(320.0)))
+            }
+            DialogPage::SendTo { devices, .. } => {
+                let item = |icon: widget::icon::Icon, label: String, target: SendTo| {
+                    widget::button::custom(
+                        widget::row::with_children(vec![
+                            icon.into(),
+                            widget::text::body(label).into(),
+                        ])
+                        .align_y(Alignment::Center)
+                        .spacing(space_xxs),
+                    )
+                    .width(Length::Fill)
+                    .class(theme::Button::MenuItem)
+                    .on_press(Message::SendTo(target))
+                };
+
+                let mut column = widget::column::with_capacity(7).spacing(space_xxs);
+                column = column.push(
+                    widget::list_column().add(item(
+                        widget::icon::from_name("mail-send-symbolic")
+                            .size(16)
+                            .icon(),
+                        fl!("send-to-email"),
+                        SendTo::Email,
+                    )),
+                );
+                #[cfg(feature = "dbus")]
+                if !devices.is_empty() {
+                    let mut list = widget::list_column();
+                    for device in devices.iter() {
+                        list = list.add(item(
+                            widget::icon::from_name("bluetooth-symbolic")
+                                .size(16)
+                                .icon(),
+                            device.name.clone(),
+                            SendTo::Bluetooth(device.clone()),
+                        ));
+                    }
+                    column = column
+                        .push(widget::text::heading(fl!("send-to-bluetooth")))
+                        .push(list);
+                }
+                #[cfg(not(feature = "dbus"))]
+                let _ = devices;
+                if !self.config.bookmarks.is_empty() {
+                    let mut list = widget::list_column();
+                    for bookmark in self.config.bookmarks.iter() {
+                        let icon = if bookmark.icon.is_empty() {
+                            widget::icon::icon(tab::folder_icon_symbolic(&bookmark.path, 16))
+                                .size(16)
+                        } else {
+                            widget::icon::from_name(bookmark.icon.clone())
+                                .size(16)
+                                .icon()
+                        };
+                        list = list.add(item(
+                            icon,
+                            bookmark.display_name(),
+                            SendTo::Folder(bookmark.path.clone()),
+                        ));
+                    }
+                    column = column
+                        .push(widget::text::heading(fl!("send-to-folders")))
+                        .push(list);
+                }
+                if !self.config.send_to.is_empty() {
+                    let mut list = widget::list_column();
+                    for (i, tool) in self.config.send_to.iter().enumerate() {
+                        list = list.add(item(
+                            widget::icon::from_name("system-run-symbolic")
+                                .size(16)
+                                .icon(),
+                            tool.name.clone(),
+                            SendTo::Command(i),
+                        ));
+                    }
+                    column = column
+                        .push(widget::text::heading(fl!("send-to-commands")))
+                        .push(list);
+                }
+
+                widget::dialog()
+                    .title(fl!("send-to"))
+                    .primary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(widget::scrollable(column))
+            }
+            DialogPage::SetExecutableAndLaunch { path } => {
+                let name = match path.file_name() {
+                    Some(file_name) => file_name.to_str(),
+                    None => path.as_os_str().to_str(),
+                };
+                widget::dialog()
+                    .title(fl!("set-executable-and-launch"))
+                    .primary_action(
+                        widget::button::text(fl!("set-and-launch"))
+                            .class(theme::Button::Suggested)
+                            .on_press(Message::DialogComplete),
+                    )
+                    .secondary_action(
+                        widget::button::text(fl!("cancel"))
+                            .class(theme::Button::Standard)
+                            .on_press(Message::DialogCancel),
+                    )
+                    .control(widget::text::text(fl!(
+                        "set-executable-and-launch-description",
+                        name = name
+                    )))
+            }
+            DialogPage::SkippedItems { items } => {
+                let mut column = widget::column::with_capacity(items.len()).spacing(space_xxs);
+                for (path, error) in items {
+                    column = column.push(widget::column::with_children(vec![
+                        widget::text::body(path.display().to_string()).into(),
+                        widget::text::caption(error.as_str()).into(),
+                    ]));
+                }
+                widget::dialog()
+                    .title(fl!("skipped-items", count = items.len()))
+                    .icon(widget::icon::from_name("dialog-warning").size(64))
+                    .control(widget::scrollable(column).height(Length::Fixed(320.0)))
+                    .primary_action(
+                        widget::button::standard(fl!("close")).on_press(Message::DialogCancel),
+                    )
+                    .secondary_action(
+                        widget::button::text(fl!("copy-report")).on_press(Message::DialogComplete),
+                    )
+            }
+            DialogPage::SyncProfiles { profiles } => {
+                let mut column = widget::column::with_capacity(profiles.len()).spacing(space_s);
+                for (i, profile) in profiles.iter().enumerate() {
+                    let update = move |profile: config::SyncProfile| {
+                        let mut profiles = profiles.clone();
+                        profiles[i] = profile;
+                        Message::DialogUpdate(DialogPage::SyncProfiles { profiles })
+                    };
+                    let mut removed = profiles.clone();
+                    removed.remove(i);
+                    column = column.push(
+                        widget::column::with_children(vec![
+                            widget::row::with_children(vec![
+                                widget::text_input(fl!("sync-name"), profile.name.as_str())
+                                    .width(Length::Fixed(160.0))
+                                    .on_input(move |name| {
+                                        update(config::SyncProfile {
+                                            name,
+                                            ..profile.clone()
+                                        })
+                                    })
+                                    .into(),
+                                widget::checkbox(
+                                    fl!("sync-two-way"),
+                                    profile.direction == config::SyncDirection::TwoWay,
+                                )
+                                .on_toggle(move |two_way| {
+                                    update(config::SyncProfile {
+                                        direction: if two_way {
+                                            config::SyncDirection::TwoWay
+                                        } else {
+                                            config::SyncDirection::OneWay
+                                        },
+                                        ..profile.clone()
+                                    })
+                                })
+                                .into(),
+                                widget::checkbox(
+                                    fl!("sync-checksum"),
+                                    profile.compare == config::SyncCompare::Checksum,
+                                )
+                                .on_toggle(move |checksum| {
+                                    update(config::SyncProfile {
+                                        compare: if checksum {
+                                            config::SyncCompare::Checksum
+                                        } else {
+                                            config::SyncCompare::SizeAndModified
+                                        },
+                                        ..profile.clone()
+                                    })
+                                })
+                                .into(),
+                                widget::checkbox(
+                                    fl!("sync-delete-orphans"),
+                                    profile.delete_orphans,
+                                )
+                                .on_toggle(move |delete_orphans| {
+                                    update(config::SyncProfile {
+                                        delete_orphans,
+                                        ..profile.clone()
+                                    })
+                                })
+                                .into(),
+                                widget::horizontal_space().into(),
+                                widget::button::icon(widget::icon::from_name(
+                                    "edit-delete-symbolic",
+                                ))
+                                .on_press(Message::DialogUpdate(DialogPage::SyncProfiles {
+                                    profiles: removed,
+                                }))
+                                .into(),
+                            ])
+                            .align_y(Alignment::Center)
+                            .spacing(space_xxs)
+                            .into(),
+                            widget::row::with_children(vec![
+                                widget::text_input(
+                                    fl!("sync-source"),
+                                    profile.source.to_string_lossy(),
+                                )
+                                .on_input(move |source| {
+                                    update(config::SyncProfile {
+                                        source: PathBuf::from(source),
+                                        ..profile.clone()
+                                    })
+                                })
+                                .into(),
+                                widget::text_input(
+                                    fl!("sync-destination"),
+                                    profile.destination.to_string_lossy(),
+                                )
+                                .on_input(move |destination| {
+                                    update(config::SyncProfile {
+                                        destination: PathBuf::from(destination),
+                                        ..profile.clone()
+                                    })
+                                })
+                                .into(),
+                            ])
+                            .spacing(space_xxs)
+                            .into(),
+                            widget::row::with_children(vec![
+                                widget::text_input(
+                                    fl!("sync-excludes"),
+                                    profile.excludes.join(" "),
+                                )
+                                .on_input(move |excludes| {
+                                    update(config::SyncProfile {
+                                        excludes: excludes.split(' ').map(str::to_string).collect(),
+                                        ..profile.clone()
+                                    })
+                                })
+                                .into(),
+                                widget::checkbox(fl!("sync-mirror"), profile.mirror)
+                                    .on_toggle(move |mirror| {
+                                        update(config::SyncProfile {
+                                            mirror,
+                                            ..profile.clone()
+                                        })
+                                    })
+                                    .into(),
+                            ])
+                            .align_y(Alignment::Center)
+                            .spacing(space_xxs)
+                            .into(),
+                        ])
+                        .spacing(space_xxs),
+                    );
+                }
+                // New profiles synchronize the folder of this pane to the folder of the other
+                let (source_opt, destination_opt) = self.pane_dirs();
+                let mut added = profiles.clone();
+                added.push(config::SyncProfile {
+                    source: source_opt.unwrap_or_default(),
+                    destination: destination_opt.unwrap_or_default(),
+                    ..config::SyncProfile::default()
+                });
+                widget::dialog()
+                    .title(fl!("edit-sync-profiles"))
+                    .primary_action(
+                        widget::button::suggested(fl!("save")).on_press(Message::DialogComplete),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .tertiary_action(widget::button::text(fl!("add-sync-profile")).on_press(
+                        Message::DialogUpdate(DialogPage::SyncProfiles { profiles: added }),
+                    ))
+                    .control(widget::scrollable(column).height(Length::Fixed(480.0)))
+            }
+            DialogPage::TermRename { entity, name } => {
+                let entity = *entity;
+                widget::dialog()
+                    .title(fl!("rename-terminal"))
+                    .primary_action(
+                        widget::button::suggested(fl!("rename")).on_press(Message::DialogComplete),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::column::with_children(vec![
+                            widget::text::body(fl!("terminal-name")).into(),
+                            widget::text_input("", name.as_str())
+                                .id(self.dialog_text_input.clone())
+                                .on_input(move |name| {
+                                    Message::DialogUpdate(DialogPage::TermRename { entity, name })
+                                })
+                                .on_submit(Message::DialogComplete)
+                                .into(),
+                        ])
+                        .spacing(space_xxs),
+                    )
+            }
+            DialogPage::TerminalProfiles {
+                profiles,
+                default_profile,
+            } => {
+                let default_profile = *default_profile;
+                let update = |profiles: BTreeMap<ProfileId, Profile>,
+                              default_profile: Option<ProfileId>| {
+                    Message::DialogUpdate(DialogPage::TerminalProfiles {
+                        profiles,
+                        default_profile,
+                    })
+                };
+                let mut column = widget::column::with_capacity(profiles.len()).spacing(space_s);
+                for (&id, profile) in profiles.iter() {
+                    let edit = move |f: &dyn Fn(&mut Profile)| {
+                        let mut profiles = profiles.clone();
+                        if let Some(profile) = profiles.get_mut(&id) {
+                            f(profile);
+                        }
+                        update(profiles, default_profile)
+                    };
+                    let mut removed = profiles.clone();
+                    removed.remove(&id);
+                    let cursor_style = CursorStyle::all()
+                        .iter()
+                        .position(|x| *x == profile.cursor_style);
+                    column = column.push(
+                        widget::column::with_children(vec![
+                            widget::row::with_children(vec![
+                                widget::text_input(fl!("profile-name"), profile.name.as_str())
+                                    .on_input(move |name| {
+                                        edit(&|profile| profile.name = name.clone())
+                                    })
+                                    .into(),
+                                widget::checkbox(
+                                    fl!("default-profile"),
+                                    default_profile == Some(id),
+                                )
+                                .on_toggle(move |checked| {
+                                    update(profiles.clone(), checked.then_some(id))
+                                })
+                                .into(),
+                                widget::button::icon(widget::icon::from_name(
+                                    "edit-delete-symbolic",
+                                ))
+                                .on_press(update(removed, default_profile))
+                                .into(),
+                            ])
+                            .align_y(Alignment::Center)
+                            .spacing(space_xxs)
+                            .into(),
+                            widget::text_input(fl!("profile-command"), profile.command.as_str())
+                                .on_input(move |command| {
+                                    edit(&|profile| profile.command = command.clone())
+                                })
+                                .into(),
+                            widget::row::with_children(vec![
+                                widget::text_input(
+                                    fl!("profile-working-directory"),
+                                    profile.working_directory.as_str(),
+                                )
+                                .on_input(move |working_directory| {
+                                    edit(&|profile| {
+                                        profile.working_directory = working_directory.clone()
+                                    })
+                                })
+                                .into(),
+                                widget::text_input(
+                                    fl!("profile-tab-title"),
+                                    profile.tab_title.as_str(),
+                                )
+                                .on_input(move |tab_title| {
+                                    edit(&|profile| profile.tab_title = tab_title.clone())
+                                })
+                                .into(),
+                            ])
+                            .spacing(space_xxs)
+                            .into(),
+                            widget::text_input(fl!("profile-env"), profile.env.as_str())
+                                .on_input(move |env| edit(&|profile| profile.env = env.clone()))
+                                .into(),
+                            widget::text_input(
+                                fl!("profile-startup-command"),
+                                profile.startup_command.as_str(),
+                            )
+                            .on_input(move |startup_command| {
+                                edit(&|profile| profile.startup_command = startup_command.clone())
+                            })
+                            .into(),
+                            widget::row::with_children(vec![
+                                widget::text::body(fl!("profile-scrollback")).into(),
+                                widget::text_input("", profile.scrollback.to_string())
+                                    .width(Length::Fixed(96.0))
+                                    .on_input(move |scrollback| {
+                                        edit(&|profile| {
+                                            if scrollback.is_empty() {
+                                                profile.scrollback = 0;
+                                            } else if let Ok(value) = scrollback.parse() {
+                                                profile.scrollback = value;
+                                            }
+                                        })
+                                    })
+                                    .into(),
+                                widget::dropdown(&self.cursor_styles, cursor_style, move |index| {
+                                    let cursor_style = CursorStyle::all()[index];
+                                    edit(&|profile| profile.cursor_style = cursor_style)
+                                })
+                                .into(),
+                                widget::checkbox(fl!("cursor-blinking"), profile.cursor_blinking)
+                                    .on_toggle(move |cursor_blinking| {
+                                        edit(&|profile| profile.cursor_blinking = cursor_blinking)
+                                    })
+                                    .into(),
+                                widget::checkbox(fl!("profile-hold"), profile.hold)
+                                    .on_toggle(move |hold| edit(&|profile| profile.hold = hold))
+                                    .into(),
+                            ])
+                            .align_y(Alignment::Center)
+                            .spacing(space_xxs)
+                            .into(),
+                        ])
+                        .spacing(space_xxs),
+                    );
+                }
+                let mut added = profiles.clone();
+                let added_id = ProfileId(
+                    profiles
+                        .keys()
+                        .next_back()
+                        .map_or(0, |profile_id| profile_id.0 + 1),
+                );
+                added.insert(added_id, Profile::default());
+                widget::dialog()
+                    .title(fl!("terminal-profiles"))
+                    .primary_action(
+                        widget::button::suggested(fl!("save")).on_press(Message::DialogComplete),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .tertiary_action(
+                        widget::button::text(fl!("add-profile"))
+                            .on_press(update(added, default_profile)),
                     )
-                    .primary_action(widget::button::suggested(fl!("replace")).on_press(
-                        Message::ReplaceResult(ReplaceResult::Replace(*apply_to_all)),
-                    ));
-                if *multiple {
-                    dialog
-                        .control(
-                            widget::checkbox(fl!("apply-to-all"), *apply_to_all).on_toggle(
-                                |apply_to_all| {
-                                    Message::DialogUpdate(DialogPage::Replace1 {
-                                        from: from.clone(),
-                                        to: to.clone(),
-                                        multiple: *multiple,
-                                        apply_to_all,
-                                        tx: tx.clone(),
-                                    })
-                                },
-                            ),
-                        )
-                        .secondary_action(
-                            widget::button::standard(fl!("skip")).on_press(Message::ReplaceResult(
-                                ReplaceResult::Skip(*apply_to_all),
-                            )),
-                        )
-                        .tertiary_action(
-                            widget::button::text(fl!("cancel"))
-                                .on_press(Message::ReplaceResult(ReplaceResult::Cancel)),
-                        )
-                } else {
-                    dialog
-                        .secondary_action(
-                            widget::button::standard(fl!("cancel"))
-                                .on_press(Message::ReplaceResult(ReplaceResult::Cancel)),
-                        )
-                        .tertiary_action(
-                            widget::button::text(fl!("keep-both"))
-                                .on_press(Message::ReplaceResult(ReplaceResult::KeepBoth)),
-                        )
+                    .control(widget::scrollable(column).height(Length::Fixed(480.0)))
+            }
+            DialogPage::Toolbar { actions } => {
+                let update = |actions| Message::DialogUpdate(DialogPage::Toolbar { actions });
+                let mut column =
+                    widget::column::with_capacity(actions.len() + 1).spacing(space_xxs);
+                for (i, action) in actions.iter().enumerate() {
+                    let (name, icon_name) = menu::toolbar_button(action);
+                    let mut removed = actions.clone();
+                    removed.remove(i);
+                    let moved_up = (i > 0).then(|| {
+                        let mut moved = actions.clone();
+                        moved.swap(i - 1, i);
+                        update(moved)
+                    });
+                    let moved_down = (i + 1 < actions.len()).then(|| {
+                        let mut moved = actions.clone();
+                        moved.swap(i, i + 1);
+                        update(moved)
+                    });
+                    column = column.push(
+                        widget::row::with_children(vec![
+                            widget::icon::from_name(icon_name).size(16).into(),
+                            widget::text::body(name).width(Length::Fill).into(),
+                            widget::button::icon(widget::icon::from_name("go-up-symbolic"))
+                                .on_press_maybe(moved_up)
+                                .into(),
+                            widget::button::icon(widget::icon::from_name("go-down-symbolic"))
+                                .on_press_maybe(moved_down)
+                                .into(),
+                            widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                                .on_press(update(removed))
+                                .into(),
+                        ])
+                        .align_y(Alignment::Center)
+                        .spacing(space_xxs),
+                    );
                 }
-            }
-            DialogPage::Replace2 {
-                from,
-                to,
-                multiple,
-                apply_to_all,
-                tx,
-            } => {
-                let dialog = widget::dialog()
-                    .title(fl!("replace-title", filename = to.name.as_str()))
-                    .body(fl!("replace-warning-operation"))
-                    .control(
-                        to.replace_view(fl!("original-file"), IconSizes::default())
-                            .map(|x| Message::TabMessageRight(None, x)),
+                column = column.push(
+                    widget::row::with_children(vec![
+                        widget::text::body(fl!("add-toolbar-button"))
+                            .width(Length::Fill)
+                            .into(),
+                        widget::dropdown(&self.toolbar_actions, None, move |index| {
+                            let mut added = actions.clone();
+                            added.push(menu::TOOLBAR_ACTIONS[index]);
+                            update(added)
+                        })
+                        .into(),
+                    ])
+                    .align_y(Alignment::Center)
+                    .spacing(space_xxs),
+                );
+                widget::dialog()
+                    .title(fl!("edit-toolbar"))
+                    .primary_action(
+                        widget::button::suggested(fl!("save")).on_press(Message::DialogComplete),
                     )
-                    .control(
-                        from.replace_view(fl!("replace-with"), IconSizes::default())
-                            .map(|x| Message::TabMessageRight(None, x)),
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
                     )
-                    .primary_action(widget::button::suggested(fl!("replace")).on_press(
-                        Message::ReplaceResult(ReplaceResult::Replace(*apply_to_all)),
-                    ));
-                if *multiple {
-                    dialog
-                        .control(
-                            widget::checkbox(fl!("apply-to-all"), *apply_to_all).on_toggle(
-                                |apply_to_all| {
-                                    Message::DialogUpdate(DialogPage::Replace2 {
-                                        from: from.clone(),
-                                        to: to.clone(),
-                                        multiple: *multiple,
-                                        apply_to_all,
-                                        tx: tx.clone(),
-                                    })
-                                },
-                            ),
-                        )
-                        .secondary_action(
-                            widget::button::standard(fl!("skip")).on_press(Message::ReplaceResult(
-                                ReplaceResult::Skip(*apply_to_all),
-                            )),
-                        )
-                        .tertiary_action(
-                            widget::button::text(fl!("cancel"))
-                                .on_press(Message::ReplaceResult(ReplaceResult::Cancel)),
-                        )
-                } else {
-                    dialog
-                        .secondary_action(
-                            widget::button::standard(fl!("cancel"))
-                                .on_press(Message::ReplaceResult(ReplaceResult::Cancel)),
-                        )
-                        .tertiary_action(
-                            widget::button::text(fl!("keep-both"))
-                                .on_press(Message::ReplaceResult(ReplaceResult::KeepBoth)),
-                        )
+                    .tertiary_action(
+                        widget::button::text(fl!("reset-toolbar"))
+                            .on_press(update(Config::default().toolbar)),
+                    )
+                    .control(widget::scrollable(column).height(Length::Fixed(480.0)))
+            }
+            DialogPage::Tools { tools } => {
+                let mut column = widget::column::with_capacity(tools.len() + 1).spacing(space_xxs);
+                column = column.push(widget::text::caption(fl!("tools-placeholders")));
+                for (i, tool) in tools.iter().enumerate() {
+                    let mut removed = tools.clone();
+                    removed.remove(i);
+                    column = column.push(
+                        widget::row::with_children(vec![
+                            widget::text_input(fl!("tool-name"), tool.name.as_str())
+                                .width(Length::Fixed(160.0))
+                                .on_input(move |name| {
+                                    let mut tools = tools.clone();
+                                    tools[i].name = name;
+                                    Message::DialogUpdate(DialogPage::Tools { tools })
+                                })
+                                .into(),
+                            widget::text_input(fl!("tool-command"), tool.command.as_str())
+                                .on_input(move |command| {
+                                    let mut tools = tools.clone();
+                                    tools[i].command = command;
+                                    Message::DialogUpdate(DialogPage::Tools { tools })
+                                })
+                                .into(),
+                            widget::checkbox(fl!("tool-terminal"), tool.terminal)
+                                .on_toggle(move |terminal| {
+                                    let mut tools = tools.clone();
+                                    tools[i].terminal = terminal;
+                                    Message::DialogUpdate(DialogPage::Tools { tools })
+                                })
+                                .into(),
+                            widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                                .on_press(Message::DialogUpdate(DialogPage::Tools {
+                                    tools: removed,
+                                }))
+                                .into(),
+                        ])
+                        .align_y(Alignment::Center)
+                        .spacing(space_xxs),
+                    );
                 }
-            }
-            DialogPage::SetExecutableAndLaunch { path } => {
-                let name = match path.file_name() {
-                    Some(file_name) => file_name.to_str(),
-                    None => path.as_os_str().to_str(),
-                };
+                let mut added = tools.clone();
+                added.push(config::UserTool::default());
                 widget::dialog()
-                    .title(fl!("set-executable-and-launch"))
+                    .title(fl!("edit-tools"))
                     .primary_action(
-                        widget::button::text(fl!("set-and-launch"))
-                            .class(theme::Button::Suggested)
-                            .on_press(Message::DialogComplete),
+                        widget::button::suggested(fl!("save")).on_press(Message::DialogComplete),
                     )
                     .secondary_action(
-                        widget::button::text(fl!("cancel"))
-                            .class(theme::Button::Standard)
-                            .on_press(Message::DialogCancel),
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
                     )
-                    .control(widget::text::text(fl!(
-                        "set-executable-and-launch-description",
-                        name = name
-                    )))
+                    .tertiary_action(
+                        widget::button::text(fl!("add-tool"))
+                            .on_press(Message::DialogUpdate(DialogPage::Tools { tools: added })),
+                    )
+                    .control(column)
+            }
+            DialogPage::VerifyChecksums { path, result } => {
+                let dialog = widget::dialog()
+                    .title(fl!("verify-checksums"))
+                    .primary_action(
+                        widget::button::standard(fl!("close")).on_press(Message::DialogComplete),
+                    );
+                match result {
+                    Ok(results) => {
+                        let passed = results
+                            .iter()
+                            .filter(|(_, result)| *result == VerifyResult::Pass)
+                            .count();
+                        let mut column = widget::column::with_capacity(results.len());
+                        for (file_path, result) in results.iter() {
+                            let name = file_path
+                                .strip_prefix(path.parent().unwrap_or(path))
+                                .unwrap_or(file_path)
+                                .display()
+                                .to_string();
+                            let (icon, status) = match result {
+                                VerifyResult::Pass => ("emblem-ok-symbolic", fl!("checksum-pass")),
+                                VerifyResult::Fail => {
+                                    ("dialog-error-symbolic", fl!("checksum-fail"))
+                                }
+                                VerifyResult::Error(err) => {
+                                    ("dialog-warning-symbolic", err.clone())
+                                }
+                            };
+                            column = column.push(
+                                widget::row::with_children(vec![
+                                    widget::icon::from_name(icon).size(16).into(),
+                                    widget::text::body(name).width(Length::Fill).into(),
+                                    widget::text::body(status).into(),
+                                ])
+                                .align_y(Alignment::Center)
+                                .spacing(space_xxs),
+                            );
+                        }
+                        dialog
+                            .body(fl!(
+                                "checksums-verified",
+                                passed = passed,
+                                total = results.len()
+                            ))
+                            .control(widget::scrollable(column).height(Length::Fixed(240.0)))
+                    }
+                    Err(err) => dialog
+                        .icon(widget::icon::from_name("dialog-error").size(64))
+                        .body(err.clone()),
+                }
             }
         };
 
@@ -7840,6 +13895,7 @@ impl Application for App {
         } = theme::active().cosmic().spacing;
 
         let mut title = String::new();
+        let mut details_opt = None;
         let mut total_progress = 0.0;
         let mut count = 0;
         let mut all_paused = true;
@@ -7851,6 +13907,7 @@ impl Application for App {
                 let progress = controller.progress();
                 if title.is_empty() {
                     title = op.pending_text(progress, controller.state());
+                    details_opt = controller.details().text();
                 }
                 total_progress += progress;
                 count += 1;
@@ -7867,6 +13924,7 @@ impl Application for App {
         let finished = count - running;
         total_progress /= count as f32;
         if running > 1 {
+            details_opt = None;
             if finished > 0 {
                 title = fl!(
                     "operations-running-finished",
@@ -7893,9 +13951,12 @@ impl Application for App {
                 progress_bar.into(),
                 if all_paused {
                     widget::tooltip(
-                        widget::button::icon(widget::icon::from_name(
-                            "media-playback-start-symbolic",
-                        ))
+                        a11y::name(
+                            widget::button::icon(widget::icon::from_name(
+                                "media-playback-start-symbolic",
+                            )),
+                            fl!("resume"),
+                        )
                         .on_press(Message::PendingPauseAll(false))
                         .padding(8),
                         widget::text::body(fl!("resume")),
@@ -7904,9 +13965,12 @@ impl Application for App {
                     .into()
                 } else {
                     widget::tooltip(
-                        widget::button::icon(widget::icon::from_name(
-                            "media-playback-pause-symbolic",
-                        ))
+                        a11y::name(
+                            widget::button::icon(widget::icon::from_name(
+                                "media-playback-pause-symbolic",
+                            )),
+                            fl!("pause"),
+                        )
                         .on_press(Message::PendingPauseAll(true))
                         .padding(8),
                         widget::text::body(fl!("pause")),
@@ -7915,9 +13979,16 @@ impl Application for App {
                     .into()
                 },
                 widget::tooltip(
-                    widget::button::icon(widget::icon::from_name("window-close-symbolic"))
-                        .on_press(Message::PendingCancelAll)
-                        .padding(8),
+                    // Screen readers read the progress with the cancel button
+                    a11y::describe(
+                        a11y::name(
+                            widget::button::icon(widget::icon::from_name("window-close-symbolic")),
+                            fl!("cancel"),
+                        ),
+                        title.clone(),
+                    )
+                    .on_press(Message::PendingCancelAll)
+                    .padding(8),
                     widget::text::body(fl!("cancel")),
                     widget::tooltip::Position::Top,
                 )
@@ -7925,7 +13996,14 @@ impl Application for App {
             ])
             .align_y(Alignment::Center)
             .into(),
-            widget::text::body(title).into(),
+            match details_opt {
+                Some(details) => widget::column::with_children(vec![
+                    widget::text::body(title).into(),
+                    widget::text::caption(details).into(),
+                ])
+                .into(),
+                None => widget::text::body(title).into(),
+            },
             widget::Space::with_height(space_s).into(),
             widget::row::with_children(vec![
                 widget::button::link(fl!("details"))
@@ -7948,11 +14026,15 @@ impl Application for App {
     }
 
     fn header_start(&self) -> Vec<Element<Self::Message>> {
-        vec![menu::menu_bar(
-            self.tab_model1.active_data::<Tab1>(),
+        let mut elements = vec![menu::menu_bar(
+            self.tab_model1.active_data::<Tab>(),
             &self.config,
             &self.key_binds,
-        )]
+        )];
+        if self.config.show_toolbar && !self.config.toolbar.is_empty() {
+            elements.push(menu::toolbar(&self.config));
+        }
+        elements
     }
 
     fn header_end(&self) -> Vec<Element<Self::Message>> {
@@ -8008,6 +14090,7 @@ impl Application for App {
         .height(Length::Fill)
         .spacing(space_xxs)
         .on_click(Message::PaneClicked)
+        .on_resize(space_xxs, Message::PaneResized)
         .drag_id(self.panegrid_drag_id)
         .on_enter(|x, y, v| Message::DndEnterDndDestination(x, y, v))
         .on_leave(|| Message::DndExitDndDestination)
@@ -8017,7 +14100,6 @@ impl Application for App {
         //.on_dnd_enter(|v| Message::DndEnterPanegrid(v))
         //.on_dnd_leave(|| Message::DndExitPanegrid)
         //.on_dnd_drop(|drag_id, data, action| Message::DndDropPanegrid(drag_id, data, action))
-        //.on_resize(space_s, Message::PaneResized);
         for p in self.pane_model.panes.iter() {
             pane_grid.panes.push(p.to_owned());
             pane_grid
@@ -8053,7 +14135,7 @@ impl Application for App {
                 let mut tab_column = widget::column::with_capacity(3);
                 let entity = entity.to_owned();
                 if self.active_panel == PaneType::LeftPane {
-                    let tab_view = match self.tab_model1.data::<Tab1>(entity) {
+                    let tab_view = match self.tab_model1.data::<Tab>(entity) {
                         Some(tab) => tab
                             .view(&self.key_binds)
                             .map(move |message| Message::TabMessage(Some(entity), message)),
@@ -8065,7 +14147,7 @@ impl Application for App {
                     }
                     tab_column = tab_column.push(popover);
                 } else {
-                    let tab_view = match self.tab_model2.data::<Tab2>(entity) {
+                    let tab_view = match self.tab_model2.data::<Tab>(entity) {
                         Some(tab) => tab
                             .view(&self.key_binds)
                             .map(move |message| Message::TabMessageRight(Some(entity), message)),
@@ -8103,6 +14185,20 @@ impl Application for App {
                 };
             }
             Some(WindowKind::DesktopViewOptions) => self.desktop_view_options(),
+            Some(WindowKind::Editor) => {
+                let content = match self.editors.get(&id) {
+                    Some(editor) => editor
+                        .view()
+                        .map(move |message| Message::Editor(id, message)),
+                    None => widget::horizontal_space().into(),
+                };
+                // The text editor scrolls by itself
+                return widget::container(content)
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .class(theme::Container::WindowBackground)
+                    .into();
+            }
             Some(WindowKind::Preview1(entity_opt, kind)) => {
                 let ret = self
                     .preview_left(entity_opt, kind, false)
@@ -8139,6 +14235,14 @@ impl Application for App {
         struct WatcherSubscriptionRight;
         struct TrashWatcherSubscription;
 
+        // The trash bins are watched again when volumes are mounted or unmounted
+        let mounted = self
+            .mounter_items
+            .values()
+            .flat_map(|items| items.iter())
+            .filter(|item| item.is_mounted())
+            .count();
+
         let mut subscriptions = vec![
             event::listen_with(|event, status, _window_id| match event {
                 Event::Keyboard(KeyEvent::KeyPressed { key, modifiers, .. }) => match status {
@@ -8148,6 +14252,7 @@ impl Application for App {
                 Event::Keyboard(KeyEvent::ModifiersChanged(modifiers)) => {
                     Some(Message::Modifiers(modifiers))
                 }
+                Event::Window(WindowEvent::Focused) => Some(Message::WindowFocus),
                 Event::Window(WindowEvent::Unfocused) => Some(Message::WindowUnfocus),
                 Event::Window(WindowEvent::CloseRequested) => Some(Message::WindowClose),
                 Event::Window(WindowEvent::Opened { position: _, size }) => {
@@ -8176,6 +14281,10 @@ impl Application for App {
                 )) => Some(Message::CopyPrimary(None)),
                 _ => None,
             }),
+            #[cfg(feature = "dbus")]
+            appearance_subscription(self.config.app_theme),
+            #[cfg(feature = "dbus")]
+            a11y::subscription().map(Message::ScreenReader),
             Config::subscription().map(|update| {
                 if !update.errors.is_empty() {
                     log::info!(
@@ -8370,7 +14479,7 @@ impl Application for App {
                 }),
             ),
             Subscription::run_with_id(
-                TypeId::of::<TrashWatcherSubscription>(),
+                (TypeId::of::<TrashWatcherSubscription>(), mounted),
                 stream::channel(25, |mut output| async move {
                     let watcher_res = new_debouncer(
                         time::Duration::from_millis(250),
@@ -8408,7 +14517,7 @@ impl Application for App {
                     ))]
                     match (watcher_res, trash::os_limited::trash_folders()) {
                         (Ok(mut watcher), Ok(trash_bins)) => {
-                            for path in trash_bins {
+                            for path in trash_bins.into_iter().chain(volume_trash::trash_dirs()) {
                                 if let Err(e) = watcher
                                     .watcher()
                                     .watch(&path, notify::RecursiveMode::Recursive)
@@ -8436,6 +14545,29 @@ impl Application for App {
             ),
         ];
 
+        // Only the file manager windows reveal files for other applications, not the desktop
+        #[cfg(feature = "dbus")]
+        if matches!(self.mode, Mode::App) {
+            struct FileManager1Subscription;
+            subscriptions.push(
+                Subscription::run_with_id(
+                    TypeId::of::<FileManager1Subscription>(),
+                    stream::channel(16, |tx| async move {
+                        // The name is released when the connection is dropped
+                        let _connection = match file_manager1::serve(tx).await {
+                            Ok(connection) => connection,
+                            Err(err) => {
+                                log::info!("not serving {}: {}", file_manager1::NAME, err);
+                                return std::future::pending().await;
+                            }
+                        };
+                        std::future::pending().await
+                    }),
+                )
+                .map(Message::FileManager1),
+            );
+        }
+
         for (key, mounter) in MOUNTERS.iter() {
             subscriptions.push(
                 mounter.subscription().with(*key).map(
@@ -8455,6 +14587,34 @@ impl Application for App {
             );
         }
 
+        // Mirrors run in the background of the file manager windows, restarted when edited
+        if matches!(self.mode, Mode::App) {
+            struct MirrorSubscription;
+            for profile in self.config.sync_profiles.iter() {
+                if !profile.mirror {
+                    continue;
+                }
+                // Mirroring stops while the destination is in a read-only pane
+                let sync = Operation::Sync {
+                    profile: profile.clone(),
+                };
+                if self.read_only_dir(&sync).is_some() {
+                    continue;
+                }
+                let profile = profile.clone();
+                subscriptions.push(Subscription::run_with_id(
+                    (TypeId::of::<MirrorSubscription>(), profile.clone()),
+                    stream::channel(
+                        1,
+                        move |_msg_tx: futures::channel::mpsc::Sender<Message>| async move {
+                            sync::watch(profile).await;
+                            std::future::pending().await
+                        },
+                    ),
+                ));
+            }
+        }
+
         if !self.pending_operations.is_empty() {
             //TODO: inhibit suspend/shutdown?
 
@@ -8502,16 +14662,100 @@ impl Application for App {
             }
         }
 
+        #[cfg(feature = "dbus")]
+        if !self.pending_operations.is_empty() {
+            // Show the progress on the icon in docks, restarted when operations are added or removed
+            struct LauncherEntrySubscription;
+            let ids: Vec<u64> = self.pending_operations.keys().copied().collect();
+            let controllers: Vec<Controller> = self
+                .pending_operations
+                .values()
+                .filter(|(op, _)| op.show_progress_notification())
+                .map(|(_, controller)| controller.clone())
+                .collect();
+            let count = ids.len();
+            subscriptions.push(Subscription::run_with_id(
+                (TypeId::of::<LauncherEntrySubscription>(), ids),
+                stream::channel(
+                    1,
+                    move |_msg_tx: futures::channel::mpsc::Sender<Message>| async move {
+                        loop {
+                            let progress_opt = (!controllers.is_empty()).then(|| {
+                                controllers
+                                    .iter()
+                                    .map(|controller| controller.progress())
+                                    .sum::<f32>()
+                                    / controllers.len() as f32
+                            });
+                            if let Err(err) = launcher_entry::update(progress_opt, count).await {
+                                log::warn!("failed to update launcher entry: {}", err);
+                                break;
+                            }
+                            tokio::time::sleep(time::Duration::from_millis(500)).await;
+                        }
+
+                        std::future::pending().await
+                    },
+                ),
+            ));
+        }
+
+        if self.screen_reader && !self.progress_operations.is_empty() {
+            // Speak the progress in steps of ten percent, restarted when operations change
+            struct AnnounceProgressSubscription;
+            let ids: Vec<u64> = self.pending_operations.keys().copied().collect();
+            let operations: Vec<(Operation, Controller)> = self
+                .pending_operations
+                .values()
+                .filter(|(op, _)| op.show_progress_notification())
+                .cloned()
+                .collect();
+            subscriptions.push(Subscription::run_with_id(
+                (TypeId::of::<AnnounceProgressSubscription>(), ids),
+                stream::channel(
+                    1,
+                    move |_msg_tx: futures::channel::mpsc::Sender<Message>| async move {
+                        let mut announced = vec![None; operations.len()];
+                        loop {
+                            for ((op, controller), step_opt) in
+                                operations.iter().zip(announced.iter_mut())
+                            {
+                                let progress = controller.progress();
+                                let step = (progress * 10.0) as u32;
+                                if *step_opt != Some(step) {
+                                    *step_opt = Some(step);
+                                    a11y::announce(
+                                        &op.pending_text(progress, controller.state()),
+                                        a11y::Priority::Progress,
+                                    );
+                                }
+                            }
+                            tokio::time::sleep(time::Duration::from_secs(1)).await;
+                        }
+                    },
+                ),
+            ));
+        }
+
         for (id, (pending_operation, controller)) in self.pending_operations.iter() {
             //TODO: use recipe?
             let id = *id;
             let pending_operation = pending_operation.clone();
             let controller = controller.clone();
+            let options = operation::OperationOptions {
+                workers: self.config.copy_workers,
+                direct_io: self.config.direct_io,
+                remote_trash: self.config.remote_trash,
+                confirm_overwrite: self.config.confirm.overwrite,
+            };
             subscriptions.push(Subscription::run_with_id(
                 id,
                 stream::channel(16, move |msg_tx| async move {
                     let msg_tx = Arc::new(tokio::sync::Mutex::new(msg_tx));
-                    match pending_operation.perform(&msg_tx, controller).await {
+                    match pending_operation
+                        .perform(&msg_tx, controller, options)
+                        .await
+                    {
                         Ok(result_paths) => {
                             let _ = msg_tx
                                 .lock()
@@ -8523,7 +14767,7 @@ impl Application for App {
                             let _ = msg_tx
                                 .lock()
                                 .await
-                                .send(Message::PendingError(id, err.to_string()))
+                                .send(Message::PendingError(id, err))
                                 .await;
                         }
                     }
@@ -8560,7 +14804,7 @@ impl Application for App {
         };
         for entity in entities {
             if self.active_panel == PaneType::LeftPane {
-                if let Some(tab) = self.tab_model1.data::<Tab1>(entity) {
+                if let Some(tab) = self.tab_model1.data::<Tab>(entity) {
                     subscriptions.push(
                         tab.subscription(selected_preview == Some(entity))
                             .with(entity)
@@ -8568,7 +14812,7 @@ impl Application for App {
                     );
                 }
             } else {
-                if let Some(tab) = self.tab_model2.data::<Tab2>(entity) {
+                if let Some(tab) = self.tab_model2.data::<Tab>(entity) {
                     subscriptions.push(
                         tab.subscription(selected_preview == Some(entity))
                             .with(entity)
@@ -8583,6 +14827,47 @@ impl Application for App {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::common_ancestor;
+
+    fn paths(paths: &[&str]) -> Vec<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn common_ancestor_of_paths() {
+        assert_eq!(common_ancestor(&[]), None);
+        // A single item is shown in its folder
+        assert_eq!(
+            common_ancestor(&paths(&["/home/ferris/notes.txt"])),
+            Some(PathBuf::from("/home/ferris"))
+        );
+        assert_eq!(
+            common_ancestor(&paths(&["/home/ferris/a/1.txt", "/home/ferris/b/c/2.txt"])),
+            Some(PathBuf::from("/home/ferris"))
+        );
+        // A selected folder is shown in its parent, not opened
+        assert_eq!(
+            common_ancestor(&paths(&["/home/ferris", "/home/ferris/notes.txt"])),
+            Some(PathBuf::from("/home"))
+        );
+        // Only the root is shared
+        assert_eq!(
+            common_ancestor(&paths(&["/home/ferris/notes.txt", "/srv/crab.txt"])),
+            Some(PathBuf::from("/"))
+        );
+        // Nothing is shared
+        assert_eq!(
+            common_ancestor(&paths(&["ferris/notes.txt", "crab/notes.txt"])),
+            None
+        );
+        assert_eq!(common_ancestor(&paths(&["/"])), None);
+    }
+}
+
 // Utilities to build a temporary file hierarchy for tests.
 //
 // Ideally, tests would use the cap-std crate which limits path traversal.
@@ -8600,8 +14885,8 @@ pub(crate) mod test_utils {
     use tempfile::{tempdir, TempDir};
 
     use crate::{
-        config::{IconSizes, TabConfig1},
-        tab1::Item,
+        config::{IconSizes, TabConfig},
+        tab::Item,
     };
 
     use super::*;
@@ -8757,38 +15042,14 @@ pub(crate) mod test_utils {
         dirs: usize,
         nested: usize,
         name_len: usize,
-    ) -> io::Result<(TempDir, Tab1)> {
-        let fs = simple_fs(files, hidden, dirs, nested, name_len)?;
-        let path = fs.path();
-
-        // New tab with items
-        let location = Location1::Path(path.to_owned());
-        let (parent_item_opt, items) = location.scan(IconSizes::default());
-        let mut tab = Tab1::new(location, TabConfig1::default());
-        tab.parent_item_opt = parent_item_opt;
-        tab.set_items(items);
-
-        // Ensure correct number of directories as a sanity check
-        let items = tab.items_opt().expect("tab should be populated with Items");
-        assert_eq!(NUM_DIRS, items.len());
-
-        Ok((fs, tab))
-    }
-
-    pub fn _tab_click_new2(
-        files: usize,
-        hidden: usize,
-        dirs: usize,
-        nested: usize,
-        name_len: usize,
-    ) -> io::Result<(TempDir, Tab2)> {
+    ) -> io::Result<(TempDir, Tab)> {
         let fs = simple_fs(files, hidden, dirs, nested, name_len)?;
         let path = fs.path();
 
         // New tab with items
-        let location = Location2::Path(path.to_owned());
+        let location = Location::Path(path.to_owned());
         let (parent_item_opt, items) = location.scan(IconSizes::default());
-        let mut tab = Tab2::new(location, TabConfig2::default());
+        let mut tab = Tab::new(location, TabConfig::default());
         tab.parent_item_opt = parent_item_opt;
         tab.set_items(items);
 
@@ -8827,7 +15088,7 @@ pub(crate) mod test_utils {
             && is_hidden == item.hidden
     }
 
-    pub fn _eq_path_item2(path: &Path, item: &crate::tab2::Item) -> bool {
+    pub fn _eq_path_item2(path: &Path, item: &crate::tab::Item) -> bool {
         let name = path
             .file_name()
             .expect("temp entries should have names")
@@ -8855,7 +15116,7 @@ pub(crate) mod test_utils {
     }
 
     /// Asserts `tab`'s location changed to `path`
-    pub fn _assert_eq_tab_path2(tab: &Tab2, path: &Path) {
+    pub fn _assert_eq_tab_path2(tab: &Tab, path: &Path) {
         // Paths should be the same
         let Some(tab_path) = tab.location.path_opt() else {
             panic!("Expected tab's location to be a path");
@@ -8870,7 +15131,7 @@ pub(crate) mod test_utils {
         );
     }
 
-    pub fn assert_eq_tab_path(tab: &Tab1, path: &Path) {
+    pub fn assert_eq_tab_path(tab: &Tab, path: &Path) {
         // Paths should be the same
         let Some(tab_path) = tab.location.path_opt() else {
             panic!("Expected tab's location to be a path");
@@ -8886,7 +15147,7 @@ pub(crate) mod test_utils {
     }
 
     /// Assert that tab's items are equal to a path's entries.
-    pub fn _assert_eq_tab_path_contents(tab: &Tab1, path: &Path) {
+    pub fn _assert_eq_tab_path_contents(tab: &Tab, path: &Path) {
         let Some(tab_path) = tab.location.path_opt() else {
             panic!("Expected tab's location to be a path");
         };
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Color scheme of the desktop from the settings portal, for
+//! [`crate::config::AppTheme::FollowSystem`]
+
+use cosmic::iced::{
+    futures::{channel::mpsc, SinkExt, StreamExt},
+    stream, Subscription,
+};
+use std::any::TypeId;
+use zbus::zvariant::{OwnedValue, Value};
+
+const NAMESPACE: &str = "org.freedesktop.appearance";
+const KEY: &str = "color-scheme";
+
+#[zbus::proxy(
+    interface = "org.freedesktop.portal.Settings",
+    default_service = "org.freedesktop.portal.Desktop",
+    default_path = "/org/freedesktop/portal/desktop"
+)]
+trait Settings {
+    fn read_one(&self, namespace: &str, key: &str) -> zbus::Result<OwnedValue>;
+
+    #[zbus(signal)]
+    fn setting_changed(&self, namespace: &str, key: &str, value: Value<'_>) -> zbus::Result<()>;
+}
+
+/// Dark is preferred with 1 and light with 2, 0 has no preference
+fn prefer_dark(value: &Value<'_>) -> Option<bool> {
+    match u32::try_from(value) {
+        Ok(1) => Some(true),
+        Ok(2) => Some(false),
+        _ => None,
+    }
+}
+
+async fn watch(output: &mut mpsc::Sender<Option<bool>>) -> zbus::Result<()> {
+    let connection = zbus::Connection::session().await?;
+    let settings = SettingsProxy::new(&connection).await?;
+    // Listen before reading so that no change is missed
+    let mut changes = settings
+        .receive_setting_changed_with_args(&[(0, NAMESPACE), (1, KEY)])
+        .await?;
+    let value = settings.read_one(NAMESPACE, KEY).await?;
+    let _ = output.send(prefer_dark(&value)).await;
+    while let Some(change) = changes.next().await {
+        let args = change.args()?;
+        let _ = output.send(prefer_dark(args.value())).await;
+    }
+    Ok(())
+}
+
+/// Whether dark is preferred, sent at the start and whenever the setting changes
+pub fn subscription() -> Subscription<Option<bool>> {
+    struct ColorSchemeSubscription;
+    Subscription::run_with_id(
+        TypeId::of::<ColorSchemeSubscription>(),
+        stream::channel(1, |mut output| async move {
+            if let Err(err) = watch(&mut output).await {
+                log::warn!("failed to watch color scheme of the desktop: {}", err);
+            }
+            std::future::pending().await
+        }),
+    )
+}
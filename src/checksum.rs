@@ -0,0 +1,289 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use sha1::Digest;
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::operation::Controller;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ChecksumKind {
+    Md5,
+    Sha1,
+    Sha256,
+    Blake3,
+}
+
+impl ChecksumKind {
+    pub fn all() -> &'static [Self] {
+        &[Self::Md5, Self::Sha1, Self::Sha256, Self::Blake3]
+    }
+
+    /// Guess the kind of checksum from the extension of a sums file and the length of a hash
+    fn guess(path: &Path, hash: &str) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+        if name.ends_with(".b3") || name.ends_with(".blake3") || name.starts_with("b3sums") {
+            return Some(Self::Blake3);
+        }
+        match hash.len() {
+            32 => Some(Self::Md5),
+            40 => Some(Self::Sha1),
+            64 => Some(Self::Sha256),
+            _ => None,
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag.to_uppercase().as_str() {
+            "MD5" => Some(Self::Md5),
+            "SHA1" => Some(Self::Sha1),
+            "SHA256" => Some(Self::Sha256),
+            "BLAKE3" => Some(Self::Blake3),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ChecksumKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Md5 => write!(f, "MD5"),
+            Self::Sha1 => write!(f, "SHA-1"),
+            Self::Sha256 => write!(f, "SHA-256"),
+            Self::Blake3 => write!(f, "BLAKE3"),
+        }
+    }
+}
+
+/// Checksums of a single file, in the order of [`ChecksumKind::all`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checksums(pub Vec<(ChecksumKind, String)>);
+
+impl Checksums {
+    pub fn get(&self, kind: ChecksumKind) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(x, _)| *x == kind)
+            .map(|(_, hash)| hash.as_str())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ChecksumState {
+    Calculating(Controller),
+    Done(Arc<Checksums>),
+    Error(String),
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Calculate all checksums of `reader` in one pass
+pub fn calculate_reader<R: Read>(
+    mut reader: R,
+    controller: &Controller,
+) -> Result<Checksums, String> {
+    let mut md5 = md5::Md5::new();
+    let mut sha1 = sha1::Sha1::new();
+    let mut sha256 = sha2::Sha256::new();
+    let mut blake3 = blake3::Hasher::new();
+    let mut buf = vec![0; 1024 * 1024];
+    loop {
+        controller.check()?;
+        let count = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(count) => count,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.to_string()),
+        };
+        let data = &buf[..count];
+        md5.update(data);
+        sha1.update(data);
+        sha256.update(data);
+        blake3.update(data);
+    }
+    Ok(Checksums(vec![
+        (ChecksumKind::Md5, hex(&md5.finalize())),
+        (ChecksumKind::Sha1, hex(&sha1.finalize())),
+        (ChecksumKind::Sha256, hex(&sha256.finalize())),
+        (ChecksumKind::Blake3, blake3.finalize().to_hex().to_string()),
+    ]))
+}
+
+pub fn calculate(path: &Path, controller: &Controller) -> Result<Checksums, String> {
+    let file = File::open(path).map_err(|err| format!("failed to open {:?}: {}", path, err))?;
+    calculate_reader(file, controller)
+        .map_err(|err| format!("failed to calculate checksums of {:?}: {}", path, err))
+}
+
+/// Check if the file at `path` looks like a list of checksums
+pub fn is_sums_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let name = name.to_lowercase();
+    [".md5", ".sha1", ".sha256", ".b3", ".blake3"]
+        .iter()
+        .any(|extension| name.ends_with(extension))
+        || ["md5sums", "sha1sums", "sha256sums", "b3sums"]
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+}
+
+/// Parse lines of a sums file, in either the coreutils `hash  name` format
+/// or the BSD `SHA256 (name) = hash` format
+pub fn parse_sums(path: &Path, text: &str) -> Vec<(ChecksumKind, String, String)> {
+    let mut entries = Vec::new();
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some((tag, rest)) = line.split_once(" (") {
+            if let Some((name, hash)) = rest.rsplit_once(") = ") {
+                if let Some(kind) = ChecksumKind::from_tag(&tag.replace('-', "")) {
+                    entries.push((kind, hash.to_lowercase(), name.to_string()));
+                    continue;
+                }
+            }
+        }
+        if let Some((hash, name)) = line.split_once(' ') {
+            // The name is prefixed by a space for text mode, or an asterisk for binary mode
+            let name = name
+                .strip_prefix(' ')
+                .or_else(|| name.strip_prefix('*'))
+                .unwrap_or(name);
+            if let Some(kind) = ChecksumKind::guess(path, hash) {
+                if hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                    entries.push((kind, hash.to_lowercase(), name.to_string()));
+                }
+            }
+        }
+    }
+    entries
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VerifyResult {
+    Pass,
+    Fail,
+    Error(String),
+}
+
+/// Verify all files listed in the sums file at `path`, relative to its directory
+pub fn verify(
+    path: &Path,
+    controller: &Controller,
+) -> Result<Vec<(PathBuf, VerifyResult)>, String> {
+    let text =
+        fs::read_to_string(path).map_err(|err| format!("failed to read {:?}: {}", path, err))?;
+    let parent = path.parent().unwrap_or(Path::new("."));
+    let mut results = Vec::new();
+    for (kind, hash, name) in parse_sums(path, &text) {
+        let file_path = parent.join(&name);
+        let result = match calculate(&file_path, controller) {
+            Ok(checksums) => {
+                if checksums.get(kind) == Some(hash.as_str()) {
+                    VerifyResult::Pass
+                } else {
+                    VerifyResult::Fail
+                }
+            }
+            Err(err) => {
+                controller.check()?;
+                VerifyResult::Error(err)
+            }
+        };
+        results.push((file_path, result));
+    }
+    if results.is_empty() {
+        return Err(format!("no checksums found in {:?}", path));
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::{calculate_reader, parse_sums, verify, ChecksumKind, VerifyResult};
+    use crate::operation::Controller;
+
+    #[test]
+    fn known_vectors() {
+        let checksums = calculate_reader(&b"abc"[..], &Controller::default()).unwrap();
+        assert_eq!(
+            checksums.get(ChecksumKind::Md5),
+            Some("900150983cd24fb0d6963f7d28e17f72")
+        );
+        assert_eq!(
+            checksums.get(ChecksumKind::Sha1),
+            Some("a9993e364706816aba3e25717850c26c9cd0d89d")
+        );
+        assert_eq!(
+            checksums.get(ChecksumKind::Sha256),
+            Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(
+            checksums.get(ChecksumKind::Blake3),
+            Some("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85")
+        );
+    }
+
+    #[test]
+    fn sums_formats() {
+        let entries = parse_sums(
+            Path::new("SHA256SUMS"),
+            "# comment\n\
+             ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  a file.txt\n\
+             900150983CD24FB0D6963F7D28E17F72 *binary.bin\n\
+             SHA1 (bsd name) = a9993e364706816aba3e25717850c26c9cd0d89d\n\
+             not a checksum\n",
+        );
+        assert_eq!(
+            entries,
+            [
+                (
+                    ChecksumKind::Sha256,
+                    "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string(),
+                    "a file.txt".to_string()
+                ),
+                (
+                    ChecksumKind::Md5,
+                    "900150983cd24fb0d6963f7d28e17f72".to_string(),
+                    "binary.bin".to_string()
+                ),
+                (
+                    ChecksumKind::Sha1,
+                    "a9993e364706816aba3e25717850c26c9cd0d89d".to_string(),
+                    "bsd name".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn verify_sums_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("good"), "abc").unwrap();
+        fs::write(dir.path().join("bad"), "abd").unwrap();
+        let sums = dir.path().join("files.md5");
+        fs::write(
+            &sums,
+            "900150983cd24fb0d6963f7d28e17f72  good\n\
+             900150983cd24fb0d6963f7d28e17f72  bad\n\
+             900150983cd24fb0d6963f7d28e17f72  missing\n",
+        )
+        .unwrap();
+        let results = verify(&sums, &Controller::default()).unwrap();
+        assert_eq!(results[0], (dir.path().join("good"), VerifyResult::Pass));
+        assert_eq!(results[1], (dir.path().join("bad"), VerifyResult::Fail));
+        assert!(matches!(results[2].1, VerifyResult::Error(_)));
+    }
+}
@@ -1,7 +1,10 @@
 // Copyright 2024 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use cosmic::iced::clipboard::mime::{AllowedMimeTypes, AsMimeTypes};
+use cosmic::iced::{
+    clipboard::mime::{AllowedMimeTypes, AsMimeTypes},
+    keyboard::Modifiers,
+};
 use std::{
     borrow::Cow,
     error::Error,
@@ -14,6 +17,81 @@ use url::Url;
 pub enum ClipboardKind {
     Copy,
     Cut,
+    /// Create symbolic links, only used for drag and drop
+    Link,
+}
+
+impl ClipboardKind {
+    /// Effect of dropping items: copy by default, move with Shift and link with Ctrl+Shift
+    pub fn for_drop(modifiers: Modifiers) -> Self {
+        if modifiers.shift() && modifiers.control() {
+            Self::Link
+        } else if modifiers.shift() {
+            Self::Cut
+        } else {
+            Self::Copy
+        }
+    }
+}
+
+/// Text that can be copied for the selected items
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CopyText {
+    /// Full paths of the selected items
+    Paths,
+    /// File names of the selected items
+    Names,
+    /// Path of the directory shown in the tab
+    Directory,
+}
+
+impl CopyText {
+    /// One entry per line, or separated by spaces when quoted for a shell
+    pub fn text<P: AsRef<Path>>(self, paths: &[P], quote: bool) -> String {
+        let mut words = Vec::new();
+        for path in paths.iter() {
+            let path = path.as_ref();
+            let word = match self {
+                Self::Paths | Self::Directory => path.to_string_lossy(),
+                Self::Names => match path.file_name() {
+                    Some(name) => name.to_string_lossy(),
+                    None => continue,
+                },
+            };
+            if quote {
+                match shlex::try_quote(&word) {
+                    Ok(word) => words.push(word.into_owned()),
+                    Err(err) => log::warn!("failed to quote {:?}: {}", word, err),
+                }
+            } else {
+                words.push(word.into_owned());
+            }
+        }
+        words.join(if quote { " " } else { "\n" })
+    }
+}
+
+/// Parse a path pasted as text: plain or shell quoted, a file URL or starting with ~
+pub fn path_from_text(text: &str) -> Option<PathBuf> {
+    let text = text.trim();
+    let text = match shlex::split(text) {
+        Some(mut words) if words.len() == 1 => words.remove(0),
+        _ => text.to_string(),
+    };
+    let path = if text.starts_with("file://") {
+        Url::parse(&text).ok()?.to_file_path().ok()?
+    } else if text == "~" {
+        dirs::home_dir()?
+    } else if let Some(rest) = text.strip_prefix("~/") {
+        dirs::home_dir()?.join(rest)
+    } else {
+        PathBuf::from(text)
+    };
+    if path.is_absolute() && path.exists() {
+        Some(path)
+    } else {
+        None
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -22,6 +100,7 @@ pub struct ClipboardCopy {
     pub text_plain: Cow<'static, [u8]>,
     pub text_uri_list: Cow<'static, [u8]>,
     pub x_special_gnome_copied_files: Cow<'static, [u8]>,
+    pub application_x_kde_cutselection: Cow<'static, [u8]>,
 }
 
 impl ClipboardCopy {
@@ -32,11 +111,18 @@ impl ClipboardCopy {
             "UTF8_STRING".to_string(),
             "text/uri-list".to_string(),
             "x-special/gnome-copied-files".to_string(),
+            "application/x-kde-cutselection".to_string(),
         ];
+        // Dolphin and other KDE applications use a separate mime type to mark cut files
+        let application_x_kde_cutselection = match kind {
+            ClipboardKind::Cut => "1",
+            ClipboardKind::Copy | ClipboardKind::Link => "0",
+        };
         let mut text_plain = String::new();
         let mut text_uri_list = String::new();
         let mut x_special_gnome_copied_files = match kind {
-            ClipboardKind::Copy => "copy",
+            // Other file managers do not know about links
+            ClipboardKind::Copy | ClipboardKind::Link => "copy",
             ClipboardKind::Cut => "cut",
         }
         .to_string();
@@ -85,6 +171,7 @@ impl ClipboardCopy {
             text_plain: Cow::from(text_plain.into_bytes()),
             text_uri_list: Cow::from(text_uri_list.into_bytes()),
             x_special_gnome_copied_files: Cow::from(x_special_gnome_copied_files.into_bytes()),
+            application_x_kde_cutselection: Cow::from(application_x_kde_cutselection.as_bytes()),
         }
     }
 }
@@ -101,6 +188,7 @@ impl AsMimeTypes for ClipboardCopy {
             }
             "text/uri-list" => Some(self.text_uri_list.clone()),
             "x-special/gnome-copied-files" => Some(self.x_special_gnome_copied_files.clone()),
+            "application/x-kde-cutselection" => Some(self.application_x_kde_cutselection.clone()),
             _ => None,
         }
     }
@@ -117,10 +205,35 @@ impl AllowedMimeTypes for ClipboardPaste {
         Cow::from(vec![
             "x-special/gnome-copied-files".to_string(),
             "text/uri-list".to_string(),
+            "UTF8_STRING".to_string(),
         ])
     }
 }
 
+/// Parse the lines of x-special/gnome-copied-files, the operation followed by file URLs
+fn parse_gnome_copied_files<'a>(
+    lines: impl Iterator<Item = &'a str>,
+) -> Result<(ClipboardKind, Vec<PathBuf>), Box<dyn Error>> {
+    let mut kind = ClipboardKind::Copy;
+    let mut paths = Vec::new();
+    for (i, line) in lines.enumerate() {
+        if i == 0 {
+            kind = match line {
+                "copy" => ClipboardKind::Copy,
+                "cut" => ClipboardKind::Cut,
+                _ => Err(format!("unsupported clipboard operation {:?}", line))?,
+            };
+        } else if !line.is_empty() {
+            let url = Url::parse(line)?;
+            match url.to_file_path() {
+                Ok(path) => paths.push(path),
+                Err(()) => Err(format!("invalid file URL {:?}", url))?,
+            }
+        }
+    }
+    Ok((kind, paths))
+}
+
 impl TryFrom<(Vec<u8>, String)> for ClipboardPaste {
     type Error = Box<dyn Error>;
     fn try_from(value: (Vec<u8>, String)) -> Result<Self, Self::Error> {
@@ -131,34 +244,79 @@ impl TryFrom<(Vec<u8>, String)> for ClipboardPaste {
         match mime.as_str() {
             "text/uri-list" => {
                 let text = str::from_utf8(&data)?;
-                for line in text.lines() {
-                    let url = Url::parse(line)?;
-                    match url.to_file_path() {
-                        Ok(path) => paths.push(path),
-                        Err(()) => Err(format!("invalid file URL {:?}", url))?,
-                    }
-                }
+                paths = crate::dnd::parse_uri_list(text)?;
             }
             "x-special/gnome-copied-files" => {
                 let text = str::from_utf8(&data)?;
-                for (i, line) in text.lines().enumerate() {
-                    if i == 0 {
-                        kind = match line {
-                            "copy" => ClipboardKind::Copy,
-                            "cut" => ClipboardKind::Cut,
-                            _ => Err(format!("unsupported clipboard operation {:?}", line))?,
-                        };
-                    } else {
-                        let url = Url::parse(line)?;
-                        match url.to_file_path() {
-                            Ok(path) => paths.push(path),
-                            Err(()) => Err(format!("invalid file URL {:?}", url))?,
-                        }
-                    }
+                (kind, paths) = parse_gnome_copied_files(text.lines())?;
+            }
+            "UTF8_STRING" => {
+                // GNOME Files also puts the copied files into the text clipboard with a header
+                let text = str::from_utf8(&data)?;
+                let mut lines = text.lines();
+                if lines.next() != Some("x-special/nautilus-clipboard") {
+                    Err("clipboard text does not contain files")?;
                 }
+                (kind, paths) = parse_gnome_copied_files(lines)?;
             }
             _ => Err(format!("unsupported mime type {:?}", mime))?,
         }
         Ok(Self { kind, paths })
     }
 }
+
+/// Whether the files in the clipboard were cut in a KDE application
+#[derive(Clone, Copy, Debug)]
+pub struct KdeCutSelection(pub bool);
+
+impl AllowedMimeTypes for KdeCutSelection {
+    fn allowed() -> Cow<'static, [String]> {
+        Cow::from(vec!["application/x-kde-cutselection".to_string()])
+    }
+}
+
+impl TryFrom<(Vec<u8>, String)> for KdeCutSelection {
+    type Error = Box<dyn Error>;
+    fn try_from(value: (Vec<u8>, String)) -> Result<Self, Self::Error> {
+        let (data, _mime) = value;
+        Ok(Self(data.first() == Some(&b'1')))
+    }
+}
+
+/// Raw image data, like a screenshot or an image copied in a browser
+#[derive(Clone, Debug)]
+pub struct ClipboardImage {
+    pub data: Vec<u8>,
+    pub extension: &'static str,
+}
+
+impl AllowedMimeTypes for ClipboardImage {
+    fn allowed() -> Cow<'static, [String]> {
+        Cow::from(vec![
+            "image/png".to_string(),
+            "image/jpeg".to_string(),
+            "image/webp".to_string(),
+            "image/gif".to_string(),
+            "image/bmp".to_string(),
+        ])
+    }
+}
+
+impl TryFrom<(Vec<u8>, String)> for ClipboardImage {
+    type Error = Box<dyn Error>;
+    fn try_from(value: (Vec<u8>, String)) -> Result<Self, Self::Error> {
+        let (data, mime) = value;
+        let extension = match mime.as_str() {
+            "image/png" => "png",
+            "image/jpeg" => "jpg",
+            "image/webp" => "webp",
+            "image/gif" => "gif",
+            "image/bmp" => "bmp",
+            _ => Err(format!("unsupported mime type {:?}", mime))?,
+        };
+        if data.is_empty() {
+            Err("empty image data")?;
+        }
+        Ok(Self { data, extension })
+    }
+}
@@ -17,6 +17,7 @@ pub struct CommanderPaneGrid {
     pub focus: pane_grid::Pane,
     pub panes: Vec<pane_grid::Pane>,
     pub splits: Vec<pane_grid::Split>,
+    pub split_by_type: BTreeMap<PaneType, pane_grid::Split>,
     pub drag_id_by_pane: BTreeMap<pane_grid::Pane, DragId>,
     pub entity_by_pane: BTreeMap<pane_grid::Pane, segmented_button::Entity>,
     pub entity_by_type: BTreeMap<PaneType, segmented_button::Entity>,
@@ -46,6 +47,7 @@ impl CommanderPaneGrid {
             focus: pane,
             panes: vec![pane],
             splits: Vec::new(),
+            split_by_type: BTreeMap::new(),
             drag_id_by_pane: BTreeMap::new(),
             entity_by_pane: BTreeMap::new(),
             entity_by_type: BTreeMap::new(),
@@ -108,6 +110,7 @@ impl CommanderPaneGrid {
                 .id();
             self.panes.push(pane);
             self.splits.push(split);
+            self.split_by_type.insert(pane_type, split);
             self.focus = pane;
             self.panes_created += 1;
             self.drag_id_by_pane.insert(pane, drag_id);
@@ -1,16 +1,23 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::{any::TypeId, num::NonZeroU16, path::PathBuf};
+use std::{
+    any::TypeId, collections::HashMap, num::NonZeroU16, path::PathBuf, sync::Arc, time::Duration,
+};
 
 use cosmic::{
     cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry},
+    cosmic_theme::{palette::Srgb, Spacing, ThemeBuilder},
     iced::Subscription,
     theme, Application,
 };
 use hex_color::HexColor;
 use serde::{Deserialize, Serialize};
 
-use crate::{app::App, tab1::View as View1, tab2::View as View2};
+use crate::{
+    app::{Action, App},
+    tab::View,
+};
+use crate::frecency::RecentDir;
 use crate::localize::LANGUAGE_SORTER;
 
 pub const CONFIG_VERSION: u64 = 1;
@@ -24,6 +31,13 @@ pub const ICON_SIZE_GRID: u16 = 64;
 // TODO: 5 is an arbitrary number. Maybe there's a better icon size max
 pub const ICON_SCALE_MAX: u16 = 5;
 
+// Choices for preview limits in the settings
+pub const PREVIEW_MAX_SIZES_MB: [u32; 5] = [8, 64, 256, 1024, 4096];
+pub const PREVIEW_TIMEOUTS_SECS: [u32; 4] = [5, 10, 30, 60];
+pub const SLIDESHOW_INTERVALS_SECS: [u32; 4] = [2, 5, 10, 30];
+// Choices for the threads that copy small files in the settings
+pub const COPY_WORKERS: [usize; 5] = [1, 2, 4, 8, 16];
+
 macro_rules! percent {
     ($perc:expr, $pixel:ident) => {
         (($perc.get() as f32 * $pixel as f32) / 100.).clamp(1., ($pixel * ICON_SCALE_MAX) as _)
@@ -35,6 +49,9 @@ pub enum AppTheme {
     Dark,
     Light,
     System,
+    /// Dark or light like the color scheme of the desktop portal, which is changed at runtime
+    /// and also set by other desktops than COSMIC
+    FollowSystem,
 }
 
 impl AppTheme {
@@ -50,14 +67,242 @@ impl AppTheme {
                 t.theme_type.prefer_dark(Some(false));
                 t
             }
-            Self::System => theme::system_preference(),
+            Self::System | Self::FollowSystem => theme::system_preference(),
+        }
+    }
+
+    /// Theme with the accent color and spacing of the density, `prefer_dark_opt` is the color
+    /// scheme of the desktop portal for [`Self::FollowSystem`]
+    pub fn custom_theme(
+        &self,
+        accent_opt: Option<HexColor>,
+        density: Density,
+        prefer_dark_opt: Option<bool>,
+    ) -> theme::Theme {
+        let app_theme = match (self, prefer_dark_opt) {
+            (Self::FollowSystem, Some(true)) => Self::Dark,
+            (Self::FollowSystem, Some(false)) => Self::Light,
+            _ => *self,
+        };
+        let t = app_theme.theme();
+        if accent_opt.is_none() && density == Density::Comfortable {
+            return t;
+        }
+        // The other colors come from the theme of the desktop
+        let is_dark = t.cosmic().is_dark;
+        let builder_config = if is_dark {
+            ThemeBuilder::dark_config()
+        } else {
+            ThemeBuilder::light_config()
+        };
+        let builder = match builder_config {
+            Ok(builder_config) => {
+                ThemeBuilder::get_entry(&builder_config).unwrap_or_else(|(_errs, builder)| builder)
+            }
+            Err(_) if is_dark => ThemeBuilder::dark(),
+            Err(_) => ThemeBuilder::light(),
+        };
+        let spacing = density.spacing(builder.spacing);
+        let mut builder = builder.spacing(spacing);
+        if let Some(accent) = accent_opt {
+            builder = builder.accent(Srgb::new(accent.r, accent.g, accent.b).into_format());
+        }
+        theme::Theme::custom(Arc::new(builder.build()))
+    }
+}
+
+/// Spacing and icon sizes of the interface, denser layouts fit more rows on small screens
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Density {
+    #[default]
+    Comfortable,
+    Compact,
+    Dense,
+}
+
+impl Density {
+    pub fn all() -> &'static [Self] {
+        &[Self::Comfortable, Self::Compact, Self::Dense]
+    }
+
+    /// Spacing of the theme scaled down for the density, used for the padding of rows,
+    /// grid items and menus
+    pub fn spacing(&self, spacing: Spacing) -> Spacing {
+        let scale = |space: u16| match self {
+            Self::Comfortable => space,
+            Self::Compact => space * 3 / 4,
+            Self::Dense => space / 2,
+        };
+        Spacing {
+            space_none: scale(spacing.space_none),
+            space_xxxs: scale(spacing.space_xxxs),
+            space_xxs: scale(spacing.space_xxs),
+            space_xs: scale(spacing.space_xs),
+            space_s: scale(spacing.space_s),
+            space_m: scale(spacing.space_m),
+            space_l: scale(spacing.space_l),
+            space_xl: scale(spacing.space_xl),
+            space_xxl: scale(spacing.space_xxl),
+            space_xxxl: scale(spacing.space_xxxl),
+        }
+    }
+
+    /// Icon zoom that the panes are reset to when the density is chosen
+    pub fn icon_sizes(&self) -> IconSizes {
+        let (list, grid) = match self {
+            Self::Comfortable => return IconSizes::default(),
+            Self::Compact => (50, 200),
+            Self::Dense => (40, 160),
+        };
+        IconSizes {
+            list: NonZeroU16::new(list).unwrap(),
+            grid: NonZeroU16::new(grid).unwrap(),
         }
     }
 }
 
+/// Units of file sizes
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum SizeUnits {
+    /// Powers of 1000, like kB and MB
+    #[default]
+    Si,
+    /// Powers of 1024, like KiB and MiB
+    Iec,
+    /// Exact number of bytes
+    Bytes,
+}
+
+impl SizeUnits {
+    pub fn all() -> &'static [Self] {
+        &[Self::Si, Self::Iec, Self::Bytes]
+    }
+}
+
+/// Format of modification and other dates
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum DateFormat {
+    /// Date and time in the format of the language
+    #[default]
+    Locale,
+    /// Time since the date, like 2 hours ago, for the last week
+    Relative,
+    /// ISO 8601, like 2024-05-31 14:05
+    Iso,
+}
+
+impl DateFormat {
+    pub fn all() -> &'static [Self] {
+        &[Self::Locale, Self::Relative, Self::Iso]
+    }
+}
+
+/// First day of the week for the this week filter
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum FirstDayOfWeek {
+    #[default]
+    Monday,
+    Saturday,
+    Sunday,
+}
+
+impl FirstDayOfWeek {
+    pub fn all() -> &'static [Self] {
+        &[Self::Monday, Self::Saturday, Self::Sunday]
+    }
+
+    /// Days between the first day of the week and `weekday`
+    pub fn days_since(&self, weekday: chrono::Weekday) -> u32 {
+        let first = match self {
+            Self::Monday => chrono::Weekday::Mon,
+            Self::Saturday => chrono::Weekday::Sat,
+            Self::Sunday => chrono::Weekday::Sun,
+        };
+        (weekday.num_days_from_monday() + 7 - first.num_days_from_monday()) % 7
+    }
+}
+
+/// Formats of sizes and dates in the panes, properties and operation dialogs
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(default)]
+pub struct FormatConfig {
+    pub size_units: SizeUnits,
+    pub date_format: DateFormat,
+    pub first_day_of_week: FirstDayOfWeek,
+}
+
+/// Actions that ask before they are done
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ConfirmConfig {
+    /// Moving items to the trash
+    pub trash: bool,
+    /// Emptying the trash, which deletes its items permanently
+    pub empty_trash: bool,
+    /// Replacing existing items when copying or moving, they are replaced without it
+    pub overwrite: bool,
+    /// Moving dropped items to another device, which copies them and deletes the originals
+    pub move_across_devices: bool,
+    /// Choosing between running executable files and opening them
+    pub execute: bool,
+}
+
+impl Default for ConfirmConfig {
+    fn default() -> Self {
+        Self {
+            trash: false,
+            empty_trash: true,
+            overwrite: true,
+            move_across_devices: false,
+            execute: true,
+        }
+    }
+}
+
+/// What is done with a runnable file when it is opened
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum RunChoice {
+    Run,
+    RunInTerminal,
+    /// Open the file in the editor, like a document
+    Open,
+}
+
+/// Accent colors and pane tints that are offered in the settings
+pub const PRESET_COLORS: [HexColor; 9] = [
+    HexColor::rgb(0x48, 0x9c, 0xe8),
+    HexColor::rgb(0x3b, 0xb5, 0xa8),
+    HexColor::rgb(0x5c, 0xb8, 0x5c),
+    HexColor::rgb(0xe5, 0xb7, 0x30),
+    HexColor::rgb(0xf0, 0x84, 0x3a),
+    HexColor::rgb(0xe0, 0x4f, 0x5f),
+    HexColor::rgb(0xe8, 0x6f, 0xb0),
+    HexColor::rgb(0x9b, 0x6b, 0xd8),
+    HexColor::rgb(0x8f, 0x8f, 0x8f),
+];
+
+/// Color of a settings dropdown index, the first entry has no color
+pub fn preset_color(index: usize) -> Option<HexColor> {
+    index
+        .checked_sub(1)
+        .and_then(|index| PRESET_COLORS.get(index).copied())
+}
+
+/// Settings dropdown index of the color, if it is a preset
+pub fn preset_color_index(color_opt: Option<HexColor>) -> Option<usize> {
+    match color_opt {
+        Some(color) => PRESET_COLORS
+            .iter()
+            .position(|preset| *preset == color)
+            .map(|index| index + 1),
+        None => Some(0),
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Favorite {
     Home,
+    Desktop,
     Documents,
     Downloads,
     Music,
@@ -71,6 +316,7 @@ impl Favorite {
         // Ensure that special folders are handled properly
         for favorite in &[
             Self::Home,
+            Self::Desktop,
             Self::Documents,
             Self::Downloads,
             Self::Music,
@@ -89,6 +335,7 @@ impl Favorite {
     pub fn path_opt(&self) -> Option<PathBuf> {
         match self {
             Self::Home => dirs::home_dir(),
+            Self::Desktop => dirs::desktop_dir(),
             Self::Documents => dirs::document_dir(),
             Self::Downloads => dirs::download_dir(),
             Self::Music => dirs::audio_dir(),
@@ -99,6 +346,104 @@ impl Favorite {
     }
 }
 
+/// Color of the names of the items that match, like `LS_COLORS`
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ColorRule {
+    /// Glob of names like `*.rs`, of MIME types like `image/*`, or one of the attributes
+    /// `folder`, `executable`, `symlink` and `hidden`
+    pub pattern: String,
+    pub color: Option<HexColor>,
+}
+
+/// Which way a sync profile copies files
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum SyncDirection {
+    /// The destination becomes a copy of the source
+    #[default]
+    OneWay,
+    /// Newer files are copied both ways
+    TwoWay,
+}
+
+/// How a sync profile finds files that changed
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum SyncCompare {
+    #[default]
+    SizeAndModified,
+    /// Files of the same size are compared by their BLAKE3 hashes
+    Checksum,
+}
+
+/// Named folder synchronization from the sync profiles menu, or `--run-sync <name>`
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(default)]
+pub struct SyncProfile {
+    pub name: String,
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    pub direction: SyncDirection,
+    /// Glob patterns of names that are not synchronized
+    pub excludes: Vec<String>,
+    /// Items that are only in the destination of a one way sync are moved to the trash
+    pub delete_orphans: bool,
+    pub compare: SyncCompare,
+    /// Changes of the source are mirrored one way to the destination while the file manager
+    /// runs, overwritten changes of the destination are logged
+    pub mirror: bool,
+}
+
+/// A command from the tools menu, see [`crate::tool::expand`] for placeholders
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct UserTool {
+    pub name: String,
+    pub command: String,
+    /// Run in the embedded terminal instead of in the background
+    pub terminal: bool,
+}
+
+/// A bookmarked folder, shown in the sidebar and the go menu
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Bookmark {
+    pub name: String,
+    pub path: PathBuf,
+    /// Name of the icon, the folder icon is used if empty
+    pub icon: String,
+    /// Bookmarks with the same folder are shown together
+    pub folder: String,
+    /// Digit from 1 to 9 that opens the bookmark together with Ctrl
+    pub shortcut: Option<u8>,
+}
+
+impl Bookmark {
+    pub fn display_name(&self) -> String {
+        if !self.name.is_empty() {
+            return self.name.clone();
+        }
+        match self.path.file_name() {
+            Some(file_name) => file_name.to_string_lossy().to_string(),
+            None => self.path.display().to_string(),
+        }
+    }
+}
+
+/// Bookmarks with their index grouped by folder, in the order the folders first appear
+pub fn bookmark_groups(bookmarks: &[Bookmark]) -> Vec<(&str, Vec<(usize, &Bookmark)>)> {
+    let mut groups: Vec<(&str, Vec<(usize, &Bookmark)>)> = Vec::new();
+    for (i, bookmark) in bookmarks.iter().enumerate() {
+        match groups
+            .iter_mut()
+            .find(|(folder, _)| *folder == bookmark.folder)
+        {
+            Some((_, group)) => group.push((i, bookmark)),
+            None => groups.push((bookmark.folder.as_str(), vec![(i, bookmark)])),
+        }
+    }
+    groups
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum ColorSchemeKind {
     Dark,
@@ -242,13 +587,29 @@ pub struct ColorScheme {
 #[serde(transparent)]
 pub struct ProfileId(pub u64);
 
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum CursorStyle {
+    #[default]
+    Block,
+    Underline,
+    Beam,
+}
+
+impl CursorStyle {
+    pub fn all() -> &'static [Self] {
+        &[Self::Block, Self::Underline, Self::Beam]
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Profile {
     pub name: String,
     #[serde(default)]
     pub command: String,
+    /// Color scheme for dark themes, the one from the settings is used if empty
     #[serde(default)]
     pub syntax_theme_dark: String,
+    /// Color scheme for light themes, the one from the settings is used if empty
     #[serde(default)]
     pub syntax_theme_light: String,
     #[serde(default)]
@@ -257,6 +618,22 @@ pub struct Profile {
     pub working_directory: String,
     #[serde(default)]
     pub hold: bool,
+    /// Environment variables as `NAME=value` words
+    #[serde(default)]
+    pub env: String,
+    /// Typed into the shell after it started
+    #[serde(default)]
+    pub startup_command: String,
+    #[serde(default = "default_scrollback")]
+    pub scrollback: usize,
+    #[serde(default)]
+    pub cursor_style: CursorStyle,
+    #[serde(default)]
+    pub cursor_blinking: bool,
+}
+
+fn default_scrollback() -> usize {
+    10_000
 }
 
 impl Default for Profile {
@@ -264,32 +641,132 @@ impl Default for Profile {
         Self {
             name: "new-profile".to_string(),
             command: String::new(),
-            syntax_theme_dark: COSMIC_THEME_DARK.to_string(),
-            syntax_theme_light: COSMIC_THEME_LIGHT.to_string(),
+            syntax_theme_dark: String::new(),
+            syntax_theme_light: String::new(),
             tab_title: String::new(),
             working_directory: String::new(),
             hold: false,
+            env: String::new(),
+            startup_command: String::new(),
+            scrollback: default_scrollback(),
+            cursor_style: CursorStyle::default(),
+            cursor_blinking: false,
+        }
+    }
+}
+
+impl Profile {
+    /// Parse the environment variables, ignoring words without `=`
+    pub fn env_vars(&self) -> HashMap<String, String> {
+        let mut env_vars = HashMap::new();
+        for word in shlex::split(&self.env).unwrap_or_default() {
+            match word.split_once('=') {
+                Some((name, value)) if !name.is_empty() => {
+                    env_vars.insert(name.to_string(), value.to_string());
+                }
+                _ => {
+                    log::warn!(
+                        "invalid environment variable {:?} in profile {:?}",
+                        word,
+                        self.name
+                    );
+                }
+            }
         }
+        env_vars
     }
 }
 
+/// Pinned and locked state of a restored tab
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TabState {
+    pub pinned: bool,
+    pub locked: bool,
+}
+
 #[derive(Clone, CosmicConfigEntry, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(default)]
 pub struct Config {
     pub app_theme: AppTheme,
+    /// Accent color instead of the one of the desktop theme
+    pub accent_color: Option<HexColor>,
+    /// Background tint of the left pane, to tell the panes apart
+    pub pane_tint_left: Option<HexColor>,
+    /// Background tint of the right pane
+    pub pane_tint_right: Option<HexColor>,
+    pub density: Density,
+    pub formats: FormatConfig,
     pub color_schemes_dark: std::collections::BTreeMap<ColorSchemeId, ColorScheme>,
     pub color_schemes_light: std::collections::BTreeMap<ColorSchemeId, ColorScheme>,
     pub desktop: DesktopConfig,
+    /// Icon positions on the desktop by [`desktop_layout_key`], then by file name
+    pub desktop_layouts: std::collections::BTreeMap<String, DesktopLayout>,
     pub favorites: Vec<Favorite>,
+    pub bookmarks: Vec<Bookmark>,
     pub show_details: bool,
     pub show_button_row: bool,
     pub show_embedded_terminal: bool,
     pub show_second_panel: bool,
+    pub show_toolbar: bool,
+    /// Buttons of the toolbar, in order
+    pub toolbar: Vec<Action>,
     pub queue_file_operations: bool,
-    pub tab_left: TabConfig1,
-    pub tab_right: TabConfig2,
+    /// Both panes use the click settings of the left pane
+    pub share_click_settings: bool,
+    /// Selected files with the same default app are opened with one launch of it
+    pub open_together: bool,
+    /// Navigate with vi keys like hjkl, gg and yy when no text input is focused
+    pub vi_keys: bool,
+    /// Copied paths and names are quoted for a shell
+    pub quote_copied_paths: bool,
+    /// A copy or move that does not fit on the destination is refused instead of asking
+    pub refuse_insufficient_space: bool,
+    /// Files that deletes and overwrites would change are listed before they start
+    pub preview_changes: bool,
+    pub confirm: ConfirmConfig,
+    /// Remembered choices for runnable files, by MIME type
+    pub run_choices: std::collections::BTreeMap<String, RunChoice>,
+    /// Threads that copy small files at the same time
+    pub copy_workers: usize,
+    /// Large files are read past the page cache when they are copied
+    pub direct_io: bool,
+    /// Deleted items on remote volumes are moved to a trash folder on the server
+    pub remote_trash: bool,
+    /// Sounds are played when items are trashed and when operations finish or fail
+    pub sounds: bool,
+    /// Volume of the sounds in percent
+    pub sound_volume: u8,
+    pub tab_left: TabConfig,
+    pub tab_right: TabConfig,
     pub paths_left: Vec<String>,
     pub paths_right: Vec<String>,
+    /// State of the tabs in the order of `paths_left`
+    pub tab_states_left: Vec<TabState>,
+    /// State of the tabs in the order of `paths_right`
+    pub tab_states_right: Vec<TabState>,
+    /// Visited directories for the quick jump
+    pub recent_dirs: Vec<RecentDir>,
+    /// Directories last visited in the left pane, the latest first
+    pub pane_history_left: Vec<PathBuf>,
+    /// Directories last visited in the right pane, the latest first
+    pub pane_history_right: Vec<PathBuf>,
+    pub tools: Vec<UserTool>,
+    pub sync_profiles: Vec<SyncProfile>,
+    /// Commands of the send to dialog, with the placeholders of the tools
+    pub send_to: Vec<UserTool>,
+    /// External command to compare files with, the built-in diff is used if empty
+    pub diff_tool: String,
+    /// Glob patterns for names that are hidden like dot files, such as backup files
+    pub hide_patterns: Vec<String>,
+    /// Colors of names, the first matching rule is used
+    pub color_rules: Vec<ColorRule>,
+    pub profiles: std::collections::BTreeMap<ProfileId, Profile>,
+    pub default_profile: Option<ProfileId>,
+    pub syntax_theme_dark: String,
+    pub syntax_theme_light: String,
+    /// Height of the embedded terminal in percent, a default for the layout is used if unset
+    pub terminal_height: Option<u16>,
 }
 
 impl Config {
@@ -321,6 +798,12 @@ impl Config {
         )
     }
 
+    /// Theme of the settings, see [`AppTheme::custom_theme`]
+    pub fn theme(&self, prefer_dark_opt: Option<bool>) -> theme::Theme {
+        self.app_theme
+            .custom_theme(self.accent_color, self.density, prefer_dark_opt)
+    }
+
     pub fn color_schemes(
         &self,
         color_scheme_kind: ColorSchemeKind,
@@ -362,15 +845,40 @@ impl Config {
         color_scheme_names
     }
 
+    /// Name and kind of the terminal color scheme, the profile overrides the settings
+    pub fn syntax_theme(&self, profile_opt: Option<&Profile>) -> (String, ColorSchemeKind) {
+        let color_scheme_kind = self.color_scheme_kind();
+        let (name, profile_name_opt) = match color_scheme_kind {
+            ColorSchemeKind::Dark => (
+                &self.syntax_theme_dark,
+                profile_opt.map(|profile| &profile.syntax_theme_dark),
+            ),
+            ColorSchemeKind::Light => (
+                &self.syntax_theme_light,
+                profile_opt.map(|profile| &profile.syntax_theme_light),
+            ),
+        };
+        let name = profile_name_opt
+            .filter(|profile_name| !profile_name.is_empty())
+            .unwrap_or(name);
+        (name.clone(), color_scheme_kind)
+    }
+
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             app_theme: AppTheme::System,
+            accent_color: None,
+            pane_tint_left: None,
+            pane_tint_right: None,
+            density: Density::Comfortable,
+            formats: FormatConfig::default(),
             color_schemes_dark: std::collections::BTreeMap::new(),
             color_schemes_light: std::collections::BTreeMap::new(),
             desktop: DesktopConfig::default(),
+            desktop_layouts: std::collections::BTreeMap::new(),
             favorites: vec![
                 Favorite::Home,
                 Favorite::Documents,
@@ -379,15 +887,58 @@ impl Default for Config {
                 Favorite::Pictures,
                 Favorite::Videos,
             ],
+            bookmarks: Vec::new(),
             show_details: false,
             show_button_row: true,
             show_embedded_terminal: true,
             show_second_panel: true,
+            show_toolbar: false,
+            toolbar: vec![
+                Action::HistoryPrevious,
+                Action::HistoryNext,
+                Action::LocationUp,
+                Action::TabRescan,
+                Action::NewFolder,
+                Action::TabViewToggle,
+                Action::ToggleTerminal,
+            ],
             queue_file_operations: true,
-            tab_left: TabConfig1::default(),
-            tab_right: TabConfig2::default(),
+            share_click_settings: true,
+            open_together: true,
+            vi_keys: false,
+            quote_copied_paths: false,
+            refuse_insufficient_space: false,
+            preview_changes: false,
+            confirm: ConfirmConfig::default(),
+            run_choices: std::collections::BTreeMap::new(),
+            copy_workers: 4,
+            direct_io: false,
+            remote_trash: true,
+            sounds: false,
+            sound_volume: 80,
+            tab_left: TabConfig::default(),
+            tab_right: TabConfig::default(),
             paths_left: Vec::new(),
             paths_right: Vec::new(),
+            tab_states_left: Vec::new(),
+            tab_states_right: Vec::new(),
+            recent_dirs: Vec::new(),
+            pane_history_left: Vec::new(),
+            pane_history_right: Vec::new(),
+            tools: Vec::new(),
+            sync_profiles: Vec::new(),
+            send_to: Vec::new(),
+            diff_tool: String::new(),
+            hide_patterns: ["*~", "*.bak", ".#*"]
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+            color_rules: Vec::new(),
+            profiles: std::collections::BTreeMap::new(),
+            default_profile: None,
+            syntax_theme_dark: COSMIC_THEME_DARK.to_string(),
+            syntax_theme_light: COSMIC_THEME_LIGHT.to_string(),
+            terminal_height: None,
         }
     }
 }
@@ -397,7 +948,13 @@ impl Default for Config {
 pub struct DesktopConfig {
     pub grid_spacing: NonZeroU16,
     pub icon_size: NonZeroU16,
+    /// Moved icons snap to the nearest grid cell
+    pub align_to_grid: bool,
+    /// Icons are placed in sort order and moved icons are not remembered
+    pub auto_arrange: bool,
+    pub show_computer: bool,
     pub show_content: bool,
+    pub show_home: bool,
     pub show_mounted_drives: bool,
     pub show_trash: bool,
 }
@@ -407,7 +964,11 @@ impl Default for DesktopConfig {
         Self {
             grid_spacing: 100.try_into().unwrap(),
             icon_size: 100.try_into().unwrap(),
+            align_to_grid: true,
+            auto_arrange: false,
+            show_computer: false,
             show_content: true,
+            show_home: false,
             show_mounted_drives: false,
             show_trash: false,
         }
@@ -420,62 +981,109 @@ impl DesktopConfig {
     }
 }
 
+/// Position of a desktop icon in pixels from the top left of the icon area
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct DesktopPosition {
+    pub x: u32,
+    pub y: u32,
+}
+
+pub type DesktopLayout = std::collections::BTreeMap<String, DesktopPosition>;
+
+/// Icons are remembered per output and resolution, so that they stay in place when
+/// the same monitor comes back and are laid out anew on a different one
+pub fn desktop_layout_key(output: &str, width: u32, height: u32) -> String {
+    format!("{output} {width}x{height}")
+}
+
 /// Global and local [`crate::tab::Tab`] config.
 ///
-/// [`TabConfig1`] contains options that are passed to each instance of [`crate::tab::Tab`].
+/// [`TabConfig`] contains options that are passed to each instance of [`crate::tab::Tab`].
 /// These options are set globally through the main config, but each tab may change options
 /// locally. Local changes aren't saved to the main config.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, CosmicConfigEntry, Deserialize, Serialize)]
 #[serde(default)]
-pub struct TabConfig1 {
-    pub view: View1,
+pub struct TabConfig {
+    pub view: View,
+    /// Open items with a single click instead of selecting them
+    pub single_click_open: bool,
+    /// Open items with a double click
+    pub double_click_open: bool,
+    /// Clicking the empty space between the items clears the selection
+    pub click_empty_clears_selection: bool,
     /// Show folders before files
     pub folders_first: bool,
     /// Show hidden files and folders
     pub show_hidden: bool,
+    /// Draw shown hidden files and folders dimmed
+    pub dim_hidden: bool,
     /// Icon zoom
     pub icon_sizes: IconSizes,
+    /// Do not cross file system boundaries when calculating folder sizes
+    pub size_one_file_system: bool,
+    /// Limits for thumbnails and previews
+    pub preview_limits: PreviewLimits,
+    /// Seconds between items in the gallery slideshow
+    pub slideshow_interval_secs: u32,
+    /// Navigate by dragging with the right button held
+    pub mouse_gestures: bool,
+    /// Go back and forward by clicking one button while holding the other
+    pub rocker_gestures: bool,
+    /// Items in the pane cannot be deleted, renamed or changed, and nothing can be dropped
+    /// or pasted into it
+    pub read_only: bool,
 }
 
-impl Default for TabConfig1 {
+impl Default for TabConfig {
     fn default() -> Self {
         Self {
-            view: View1::List,
+            view: View::List,
+            single_click_open: false,
+            double_click_open: true,
+            click_empty_clears_selection: true,
             folders_first: true,
             show_hidden: false,
+            dim_hidden: false,
             icon_sizes: IconSizes::default(),
+            size_one_file_system: false,
+            preview_limits: PreviewLimits::default(),
+            slideshow_interval_secs: 5,
+            mouse_gestures: false,
+            rocker_gestures: false,
+            read_only: false,
         }
     }
 }
 
-/// Global and local [`crate::tab::Tab`] config.
-///
-/// [`TabConfig2`] contains options that are passed to each instance of [`crate::tab::Tab`].
-/// These options are set globally through the main config, but each tab may change options
-/// locally. Local changes aren't saved to the main config.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, CosmicConfigEntry, Deserialize, Serialize)]
+/// Limits that keep a single bad file from stalling thumbnails and previews
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, CosmicConfigEntry, Deserialize, Serialize)]
 #[serde(default)]
-pub struct TabConfig2 {
-    pub view: View2,
-    /// Show folders before files
-    pub folders_first: bool,
-    /// Show hidden files and folders
-    pub show_hidden: bool,
-    /// Icon zoom
-    pub icon_sizes: IconSizes,
+pub struct PreviewLimits {
+    /// Largest file in megabytes that built-in thumbnailers and previews will read
+    pub max_size_mb: u32,
+    /// Seconds before an external thumbnailer or probe is stopped
+    pub timeout_secs: u32,
 }
 
-impl Default for TabConfig2 {
+impl Default for PreviewLimits {
     fn default() -> Self {
         Self {
-            view: View2::List,
-            folders_first: true,
-            show_hidden: false,
-            icon_sizes: IconSizes::default(),
+            max_size_mb: 64,
+            timeout_secs: 10,
         }
     }
 }
 
+impl PreviewLimits {
+    pub fn max_size(&self) -> u64 {
+        u64::from(self.max_size_mb) * 1000 * 1000
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs.into())
+    }
+}
+
 macro_rules! percent {
     ($perc:expr, $pixel:ident) => {
         (($perc.get() as f32 * $pixel as f32) / 100.).clamp(1., ($pixel * ICON_SCALE_MAX) as _)
@@ -0,0 +1,277 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Desktop entries that launch a program, created for binaries and AppImages. The entries
+//! follow the freedesktop Desktop Entry Specification, so that menus and desktops show them.
+
+use std::{
+    fs,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use crate::fl;
+
+/// Main categories of the freedesktop menu specification, Audio and Video need AudioVideo so
+/// only the latter is offered
+pub const CATEGORIES: &[&str] = &[
+    "AudioVideo",
+    "Development",
+    "Education",
+    "Game",
+    "Graphics",
+    "Network",
+    "Office",
+    "Science",
+    "Settings",
+    "System",
+    "Utility",
+];
+
+/// Icon of launchers until another one is chosen
+pub const DEFAULT_ICON: &str = "application-x-executable";
+
+/// Icons of the theme that are offered for launchers
+pub const ICONS: &[&str] = &[
+    DEFAULT_ICON,
+    "utilities-terminal",
+    "applications-development",
+    "applications-games",
+    "applications-graphics",
+    "applications-internet",
+    "applications-multimedia",
+    "applications-office",
+    "applications-science",
+    "applications-system",
+    "applications-utilities",
+];
+
+/// Folder that the launcher is written to
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum LauncherDir {
+    /// Applications of the user, shown in the menus and the app library
+    #[default]
+    Applications,
+    Desktop,
+}
+
+impl LauncherDir {
+    pub fn all() -> &'static [Self] {
+        &[Self::Applications, Self::Desktop]
+    }
+
+    pub fn path(&self) -> Option<PathBuf> {
+        match self {
+            Self::Applications => dirs::data_dir().map(|dir| dir.join("applications")),
+            Self::Desktop => Some(crate::desktop_dir()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Launcher {
+    pub program: PathBuf,
+    pub name: String,
+    /// Icon name of the theme or path of an image
+    pub icon: String,
+    pub categories: Vec<&'static str>,
+    /// Run the program in a terminal
+    pub terminal: bool,
+}
+
+impl Launcher {
+    pub fn new(program: &Path) -> Self {
+        let stem = program
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // AppImages are usually named like Name-1.2-x86_64.AppImage
+        let name = match stem
+            .match_indices('-')
+            .find(|(i, _)| stem[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            Some((i, _)) => stem[..i].to_string(),
+            None => stem,
+        };
+        Self {
+            program: program.to_path_buf(),
+            name,
+            icon: DEFAULT_ICON.to_string(),
+            categories: Vec::new(),
+            terminal: false,
+        }
+    }
+
+    /// Check the values against the specification, returning the reason if they are invalid
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err(fl!("launcher-name-missing"));
+        }
+        if !self.program.is_absolute() || !self.program.is_file() {
+            return Err(fl!(
+                "launcher-program-missing",
+                program = self.program.display().to_string()
+            ));
+        }
+        // Icons are absolute paths of images or names of the icon theme without an extension
+        let icon = Path::new(self.icon.trim());
+        let icon_valid = if icon.is_absolute() {
+            icon.is_file()
+        } else {
+            !self.icon.trim().is_empty()
+                && !self.icon.contains('/')
+                && !matches!(
+                    icon.extension().and_then(|extension| extension.to_str()),
+                    Some("png" | "svg" | "xpm")
+                )
+        };
+        if !icon_valid {
+            return Err(fl!("launcher-icon-invalid"));
+        }
+        if self
+            .categories
+            .iter()
+            .any(|category| !CATEGORIES.contains(category))
+        {
+            return Err(fl!("launcher-category-invalid"));
+        }
+        Ok(())
+    }
+
+    /// Contents of the desktop entry
+    pub fn entry(&self) -> String {
+        let mut entry = String::from("[Desktop Entry]\nType=Application\nVersion=1.0\n");
+        entry.push_str(&format!("Name={}\n", escape(self.name.trim())));
+        entry.push_str(&format!("Exec={}\n", escape(&quote_exec(&self.program))));
+        entry.push_str(&format!(
+            "TryExec={}\n",
+            escape(&self.program.to_string_lossy())
+        ));
+        entry.push_str(&format!("Icon={}\n", escape(self.icon.trim())));
+        if !self.categories.is_empty() {
+            entry.push_str("Categories=");
+            for category in self.categories.iter() {
+                entry.push_str(category);
+                entry.push(';');
+            }
+            entry.push('\n');
+        }
+        entry.push_str(&format!("Terminal={}\n", self.terminal));
+        entry
+    }
+
+    /// Write the launcher into `dir`, returning its path. Existing launchers are not replaced
+    /// and the program is made executable if it is not yet.
+    pub fn create(&self, dir: &Path) -> Result<PathBuf, String> {
+        self.validate()?;
+        fs::create_dir_all(dir).map_err(|err| format!("failed to create {:?}: {}", dir, err))?;
+        let stem: String = self
+            .name
+            .trim()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect();
+        let mut path = dir.join(format!("{}.desktop", stem));
+        let mut i = 2;
+        while path.exists() {
+            path = dir.join(format!("{}-{}.desktop", stem, i));
+            i += 1;
+        }
+        fs::write(&path, self.entry())
+            .map_err(|err| format!("failed to write {:?}: {}", path, err))?;
+        // Desktops only start launchers that are executable
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .map_err(|err| format!("failed to set permissions of {:?}: {}", path, err))?;
+
+        let mut permissions = fs::metadata(&self.program)
+            .map_err(|err| format!("failed to read {:?}: {}", self.program, err))?
+            .permissions();
+        if permissions.mode() & 0o111 == 0 {
+            permissions.set_mode(permissions.mode() | 0o111);
+            fs::set_permissions(&self.program, permissions).map_err(|err| {
+                format!("failed to set permissions of {:?}: {}", self.program, err)
+            })?;
+        }
+        Ok(path)
+    }
+}
+
+/// Escape a string value of a desktop entry
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Quote the program for the Exec key, which has its own rules before the string is escaped
+fn quote_exec(program: &Path) -> String {
+    let program = program.to_string_lossy().replace('%', "%%");
+    let reserved = [
+        ' ', '\t', '\n', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(',
+        ')', '`',
+    ];
+    if !program.contains(reserved) {
+        return program;
+    }
+    let mut quoted = String::from('"');
+    for c in program.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io, os::unix::fs::PermissionsExt};
+
+    use super::{quote_exec, Launcher};
+
+    #[test]
+    fn exec_is_quoted_and_escaped() {
+        assert_eq!(quote_exec("/opt/app".as_ref()), "/opt/app");
+        assert_eq!(
+            quote_exec("/opt/My App/100%$".as_ref()),
+            "\"/opt/My App/100%%\\$\""
+        );
+    }
+
+    #[test]
+    fn create_writes_valid_launcher() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let program = dir.path().join("Tool-1.2-x86_64.AppImage");
+        fs::write(&program, "")?;
+
+        let mut launcher = Launcher::new(&program);
+        assert_eq!(launcher.name, "Tool");
+        launcher.categories.push("Utility");
+        launcher.terminal = true;
+
+        let path = launcher.create(dir.path()).unwrap();
+        assert_eq!(path, dir.path().join("Tool.desktop"));
+        let entry = fs::read_to_string(&path)?;
+        assert!(entry.starts_with("[Desktop Entry]\nType=Application\n"));
+        assert!(entry.contains("\nCategories=Utility;\nTerminal=true\n"));
+        assert_ne!(fs::metadata(&program)?.permissions().mode() & 0o111, 0);
+
+        // Existing launchers are kept
+        assert_eq!(
+            launcher.create(dir.path()).unwrap(),
+            dir.path().join("Tool-2.desktop")
+        );
+
+        launcher.icon = "icons/tool.png".to_string();
+        assert!(launcher.validate().is_err());
+        Ok(())
+    }
+}
@@ -23,7 +23,6 @@ use notify_debouncer_full::{
     notify::{self, RecommendedWatcher, Watcher},
     DebouncedEvent, Debouncer, FileIdMap,
 };
-use recently_used_xbel::update_recently_used;
 use std::{
     any::TypeId,
     collections::{HashMap, HashSet, VecDeque},
@@ -35,14 +34,14 @@ use std::{
 };
 
 use crate::{
-    app::{Action, ContextPage, Message as AppMessage, PreviewItem1, PreviewKind},
-    config::{Config, Favorite, IconSizes, TabConfig1},
+    app::{Action, ContextPage, Message as AppMessage, PreviewItem, PreviewKind},
+    config::{Config, Favorite, IconSizes, TabConfig},
     fl, home_dir,
     key_bind::key_binds,
     localize::LANGUAGE_SORTER,
     menu,
     mounter::{MounterItem, MounterItems, MounterKey, MounterMessage, MOUNTERS},
-    tab1,
+    recents, tab,
 };
 
 #[derive(Clone, Debug)]
@@ -328,9 +327,9 @@ enum Message {
     SearchClear,
     SearchInput(String),
     #[allow(clippy::enum_variant_names)]
-    TabMessage(tab1::Message),
-    TabRescan(tab1::Location, Option<tab1::Item>, Vec<tab1::Item>),
-    TabViewLeft(tab1::View),
+    TabMessage(tab::Message),
+    TabRescan(tab::Location, Option<tab::Item>, Vec<tab::Item>),
+    TabViewLeft(tab::View),
     ToggleFoldersFirst,
     ZoomDefault(Option<cosmic::widget::segmented_button::Entity>),
     ZoomIn(Option<cosmic::widget::segmented_button::Entity>),
@@ -400,7 +399,7 @@ struct App {
     nav_model: segmented_button::SingleSelectModel,
     result_opt: Option<DialogResult>,
     search_id: widget::Id,
-    tab: tab1::Tab,
+    tab: tab::Tab,
     key_binds: HashMap<KeyBind, Action>,
     watcher_opt: Option<(Debouncer<RecommendedWatcher, FileIdMap>, HashSet<PathBuf>)>,
 }
@@ -471,13 +470,13 @@ impl App {
             .into()
     }
 
-    fn preview<'a>(&'a self, kind: &'a PreviewKind) -> Element<'a, tab1::Message> {
+    fn preview<'a>(&'a self, kind: &'a PreviewKind) -> Element<'a, tab::Message> {
         let mut children = Vec::with_capacity(1);
         match kind {
-            PreviewKind::Custom1(PreviewItem1(item)) => {
+            PreviewKind::Custom(PreviewItem(item)) => {
                 children.push(item.preview_view(None, IconSizes::default()).into());
             }
-            PreviewKind::Location1(location) => {
+            PreviewKind::Location(location) => {
                 if let Some(items) = self.tab.items_opt() {
                     for item in items.iter() {
                         if item.location_opt.as_ref() == Some(location) {
@@ -533,27 +532,23 @@ impl App {
 
     fn search_get(&self) -> Option<&str> {
         match &self.tab.location {
-            tab1::Location::Search(_, term, ..) => Some(term),
+            tab::Location::Search(_, term, ..) => Some(term),
             _ => None,
         }
     }
 
     fn search_set(&mut self, term_opt: Option<String>) -> Task<Message> {
         let location_opt = match term_opt {
-            Some(term) => match &self.tab.location {
-                tab1::Location::Path(path) | tab1::Location::Search(path, ..) => Some((
-                    tab1::Location::Search(
-                        path.to_path_buf(),
-                        term,
-                        self.tab.config.show_hidden,
-                        Instant::now(),
-                    ),
+            Some(term) => self.tab.location.search_path().map(|path| {
+                (
+                    tab::Location::Search(path, term, self.tab.config.show_hidden, Instant::now()),
                     true,
-                )),
-                _ => None,
-            },
+                )
+            }),
             None => match &self.tab.location {
-                tab1::Location::Search(path, ..) => Some((tab1::Location::Path(path.to_path_buf()), false)),
+                tab::Location::Search(path, ..) => {
+                    Some((tab::Location::Path(path.to_path_buf()), false))
+                }
                 _ => None,
             },
         };
@@ -578,10 +573,10 @@ impl App {
         Task::none()
     }
 
-    fn activate_nav_model_location(&mut self, location: &tab1::Location) {
+    fn activate_nav_model_location(&mut self, location: &tab::Location) {
         let nav_bar_id = self.nav_model.iter().find(|&id| {
             self.nav_model
-                .data::<tab1::Location>(id)
+                .data::<tab::Location>(id)
                 .map(|l| l == location)
                 .unwrap_or_default()
         });
@@ -600,7 +595,7 @@ impl App {
         nav_model = nav_model.insert(|b| {
             b.text(fl!("recents"))
                 .icon(widget::icon::from_name("document-open-recent-symbolic"))
-                .data(tab1::Location::Recents)
+                .data(tab::Location::Recents)
         });
 
         for favorite in self.flags.config.favorites.iter() {
@@ -616,7 +611,7 @@ impl App {
                     b.text(name.clone())
                         .icon(
                             widget::icon::icon(if path.is_dir() {
-                                tab1::folder_icon_symbolic(&path, 16)
+                                tab::folder_icon_symbolic(&path, 16)
                             } else {
                                 widget::icon::from_name("text-x-generic-symbolic")
                                     .size(16)
@@ -624,7 +619,7 @@ impl App {
                             })
                             .size(16),
                         )
-                        .data(tab1::Location::Path(path.clone()))
+                        .data(tab::Location::Path(path.clone()))
                 });
             }
         }
@@ -643,7 +638,7 @@ impl App {
             nav_model = nav_model.insert(|mut b| {
                 b = b.text(item.name()).data(MounterData(key, item.clone()));
                 if let Some(path) = item.path() {
-                    b = b.data(tab1::Location::Path(path.clone()));
+                    b = b.data(tab::Location::Path(path.clone()));
                 }
                 if let Some(icon) = item.icon(true) {
                     b = b.icon(widget::icon::icon(icon).size(16));
@@ -749,7 +744,7 @@ impl Application for App {
         let title = flags.kind.title();
         let accept_label = flags.kind.accept_label();
 
-        let location = tab1::Location::Path(match &flags.path_opt {
+        let location = tab::Location::Path(match &flags.path_opt {
             Some(path) => path.to_path_buf(),
             None => match env::current_dir() {
                 Ok(path) => path,
@@ -757,14 +752,14 @@ impl Application for App {
             },
         });
 
-        let tab_config = TabConfig1 {
-            view: tab1::View::List,
+        let tab_config = TabConfig {
+            view: tab::View::List,
             folders_first: false,
             ..Default::default()
         };
-        let mut tab = tab1::Tab::new(location, tab_config);
-        tab.mode = tab1::Mode::Dialog(flags.kind.clone());
-        tab.sort_name = tab1::HeadingOptions::Modified;
+        let mut tab = tab::Tab::new(location, tab_config);
+        tab.mode = tab::Mode::Dialog(flags.kind.clone());
+        tab.sort_name = tab::HeadingOptions::Modified;
         tab.sort_direction = false;
 
         let key_binds = key_binds(&tab.mode);
@@ -980,7 +975,7 @@ impl Application for App {
             _ => false,
         };
         elements
-            .push(menu::dialog_menu1(&self.tab, &self.key_binds, show_details).map(Message::from));
+            .push(menu::dialog_menu(&self.tab, &self.key_binds, show_details).map(Message::from));
 
         elements
     }
@@ -1024,8 +1019,8 @@ impl Application for App {
 
     fn on_nav_select(&mut self, entity: segmented_button::Entity) -> Task<Message> {
         self.nav_model.activate(entity);
-        if let Some(location) = self.nav_model.data::<tab1::Location>(entity) {
-            let message = Message::TabMessage(tab1::Message::Location(location.clone()));
+        if let Some(location) = self.nav_model.data::<tab::Location>(entity) {
+            let message = Message::TabMessage(tab::Message::Location(location.clone()));
             return self.update(message);
         }
 
@@ -1114,8 +1109,8 @@ impl Application for App {
                             match fs::create_dir(&path) {
                                 Ok(()) => {
                                     // cd to directory
-                                    let message = Message::TabMessage(tab1::Message::Location(
-                                        tab1::Location::Path(path.clone()),
+                                    let message = Message::TabMessage(tab::Message::Location(
+                                        tab::Location::Path(path.clone()),
                                     ));
                                     return self.update(message);
                                 }
@@ -1178,7 +1173,7 @@ impl Application for App {
                                     }
                                 }
                                 if !still_mounted {
-                                    unmounted.push(tab1::Location::Path(old_path));
+                                    unmounted.push(tab::Location::Path(old_path));
                                 }
                             }
                         }
@@ -1188,7 +1183,7 @@ impl Application for App {
                 // Go back to home in any tabs that were unmounted
                 let mut commands = Vec::new();
                 {
-                    let home_location = tab1::Location::Path(home_dir());
+                    let home_location = tab::Location::Path(home_dir());
                     if unmounted.contains(&self.tab.location) {
                         self.tab.change_location(&home_location, None);
                         commands.push(self.update_watcher());
@@ -1237,7 +1232,7 @@ impl Application for App {
                                                     //TODO: reload more, like mime types?
                                                     match fs::metadata(event_path) {
                                                         Ok(new_metadata) => {
-                                                            if let tab1::ItemMetadata::Path {
+                                                            if let tab::ItemMetadata::Path {
                                                                 metadata,
                                                                 ..
                                                             } = &mut item.metadata
@@ -1285,12 +1280,7 @@ impl Application for App {
                         if item.selected {
                             if let Some(path) = item.path_opt() {
                                 paths.push(path.clone());
-                                let _ = update_recently_used(
-                                    &path.clone(),
-                                    App::APP_ID.to_string(),
-                                    "commander".to_string(),
-                                    None,
-                                );
+                                recents::add(path, App::APP_ID, "commander");
                             }
                         }
                     }
@@ -1303,8 +1293,8 @@ impl Application for App {
                     if path_is_dir != self.flags.kind.is_dir() {
                         if path_is_dir && paths.len() == 1 {
                             // If the only selected item is a directory and we are selecting files, cd to it
-                            let message = Message::TabMessage(tab1::Message::Location(
-                                tab1::Location::Path(path.clone()),
+                            let message = Message::TabMessage(tab::Message::Location(
+                                tab::Location::Path(path.clone()),
                             ));
                             return self.update(message);
                         } else {
@@ -1322,7 +1312,7 @@ impl Application for App {
 
                 // If we are in directory mode, return the current directory
                 if self.flags.kind.is_dir() {
-                    if let tab1::Location::Path(tab_path) = &self.tab.location {
+                    if let tab::Location::Path(tab_path) = &self.tab.location {
                         self.result_opt = Some(DialogResult::Open(vec![tab_path.clone()]));
                         return window::close(self.flags.window_id);
                     }
@@ -1344,8 +1334,8 @@ impl Application for App {
                             let path = tab_path.join(filename);
                             if path.is_dir() {
                                 // cd to directory
-                                let message = Message::TabMessage(tab1::Message::Location(
-                                    tab1::Location::Path(path.clone()),
+                                let message = Message::TabMessage(tab::Message::Location(
+                                    tab::Location::Path(path.clone()),
                                 ));
                                 return self.update(message);
                             } else if !replace && path.exists() {
@@ -1375,7 +1365,7 @@ impl Application for App {
             }
             Message::TabMessage(tab_message) => {
                 let click_i_opt = match tab_message {
-                    tab1::Message::Click(click_i_opt) => click_i_opt,
+                    tab::Message::Click(click_i_opt) => click_i_opt,
                     _ => None,
                 };
 
@@ -1397,34 +1387,34 @@ impl Application for App {
                 let mut commands = Vec::new();
                 for tab_command in tab_commands {
                     match tab_command {
-                        tab1::Command::Action(action) => {
+                        tab::Command::Action(action) => {
                             commands.push(self.update(Message::from(action.message())));
                         }
-                        tab1::Command::ChangeLocation(_tab_title, _tab_path, _selection_paths) => {
+                        tab::Command::ChangeLocation(_tab_title, _tab_path, _selection_paths) => {
                             commands.push(Task::batch([self.update_watcher(), self.rescan_tab()]));
                         }
-                        tab1::Command::Iced(iced_command) => {
+                        tab::Command::Iced(iced_command) => {
                             commands.push(
                                 iced_command.0.map(|tab_message| {
                                     message::app(Message::TabMessage(tab_message))
                                 }),
                             );
                         }
-                        tab1::Command::OpenFile(_item_path) => {
+                        tab::Command::OpenFile(_) | tab::Command::OpenFiles(_) => {
                             if self.flags.kind.save() {
                                 commands.push(self.update(Message::Save(false)));
                             } else {
                                 commands.push(self.update(Message::Open));
                             }
                         }
-                        tab1::Command::Preview(kind) => {
+                        tab::Command::Preview(kind) => {
                             self.context_page = ContextPage::Preview(None, kind);
                             self.set_show_context(true);
                         }
-                        tab1::Command::WindowDrag => {
+                        tab::Command::WindowDrag => {
                             commands.push(window::drag(self.flags.window_id));
                         }
-                        tab1::Command::WindowToggleMaximize => {
+                        tab::Command::WindowToggleMaximize => {
                             commands.push(window::toggle_maximize(self.flags.window_id));
                         }
                         unsupported => {
@@ -1522,8 +1512,8 @@ impl Application for App {
                 self.tab.config.folders_first = !self.tab.config.folders_first;
             }
             Message::ZoomDefault(_entityopt) => match self.tab.config.view {
-                tab1::View::List => self.tab.config.icon_sizes.list = 100.try_into().unwrap(),
-                tab1::View::Grid => self.tab.config.icon_sizes.grid = 100.try_into().unwrap(),
+                tab::View::List => self.tab.config.icon_sizes.list = 100.try_into().unwrap(),
+                tab::View::Grid => self.tab.config.icon_sizes.grid = 100.try_into().unwrap(),
             },
             Message::ZoomIn(_entityopt) => {
                 let zoom_in = |size: &mut NonZeroU16, min: u16, max: u16| {
@@ -1540,8 +1530,8 @@ impl Application for App {
                     }
                 };
                 match self.tab.config.view {
-                    tab1::View::List => zoom_in(&mut self.tab.config.icon_sizes.list, 50, 500),
-                    tab1::View::Grid => zoom_in(&mut self.tab.config.icon_sizes.grid, 50, 500),
+                    tab::View::List => zoom_in(&mut self.tab.config.icon_sizes.list, 50, 500),
+                    tab::View::Grid => zoom_in(&mut self.tab.config.icon_sizes.grid, 50, 500),
                 }
             }
             Message::ZoomOut(_entityopt) => {
@@ -1559,8 +1549,8 @@ impl Application for App {
                     }
                 };
                 match self.tab.config.view {
-                    tab1::View::List => zoom_out(&mut self.tab.config.icon_sizes.list, 50, 500),
-                    tab1::View::Grid => zoom_out(&mut self.tab.config.icon_sizes.grid, 50, 500),
+                    tab::View::List => zoom_out(&mut self.tab.config.icon_sizes.list, 50, 500),
+                    tab::View::Grid => zoom_out(&mut self.tab.config.icon_sizes.grid, 50, 500),
                 }
             }
         }
@@ -0,0 +1,274 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    cosmic_theme, font,
+    iced::{
+        widget::{rich_text, span},
+        Alignment, Color, Length,
+    },
+    theme, widget, Element,
+};
+use similar::{ChangeTag, DiffOp};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::fl;
+
+/// Largest file that is compared with the built-in diff
+const MAX_FILE_SIZE: u64 = 4 * 1024 * 1024;
+
+/// A line of one side of the diff, split into parts that are emphasized if
+/// they changed within the line
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiffLine {
+    /// Line number, starting at one
+    pub number: usize,
+    pub parts: Vec<(bool, String)>,
+}
+
+/// A row of the side by side diff
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiffRow {
+    pub changed: bool,
+    pub left_opt: Option<DiffLine>,
+    pub right_opt: Option<DiffLine>,
+}
+
+fn diff_line(index: usize, parts: Vec<(bool, String)>) -> DiffLine {
+    let mut parts = parts;
+    // The line ending is part of the last part
+    if let Some((_, text)) = parts.last_mut() {
+        let len = text.trim_end_matches(['\r', '\n']).len();
+        text.truncate(len);
+    }
+    parts.retain(|(_, text)| !text.is_empty());
+    DiffLine {
+        number: index + 1,
+        parts,
+    }
+}
+
+/// Compare two texts line by line
+pub fn diff_texts(left: &str, right: &str) -> Vec<DiffRow> {
+    let diff = similar::TextDiff::from_lines(left, right);
+    let mut rows = Vec::new();
+    for op in diff.ops() {
+        if let DiffOp::Equal { .. } = op {
+            for change in diff.iter_changes(op) {
+                let line = |index: Option<usize>| {
+                    index.map(|index| {
+                        diff_line(index, vec![(false, change.to_string_lossy().into())])
+                    })
+                };
+                rows.push(DiffRow {
+                    changed: false,
+                    left_opt: line(change.old_index()),
+                    right_opt: line(change.new_index()),
+                });
+            }
+            continue;
+        }
+
+        // Removed and added lines of a change are shown next to each other
+        let mut removed = Vec::new();
+        let mut added = Vec::new();
+        for change in diff.iter_inline_changes(op) {
+            let parts = change
+                .iter_strings_lossy()
+                .map(|(emphasized, text)| (emphasized, text.into_owned()))
+                .collect();
+            match (change.tag(), change.old_index(), change.new_index()) {
+                (ChangeTag::Delete, Some(index), _) => removed.push(diff_line(index, parts)),
+                (ChangeTag::Insert, _, Some(index)) => added.push(diff_line(index, parts)),
+                _ => {}
+            }
+        }
+        let mut removed = removed.into_iter();
+        let mut added = added.into_iter();
+        loop {
+            let left_opt = removed.next();
+            let right_opt = added.next();
+            if left_opt.is_none() && right_opt.is_none() {
+                break;
+            }
+            rows.push(DiffRow {
+                changed: true,
+                left_opt,
+                right_opt,
+            });
+        }
+    }
+    rows
+}
+
+fn read_text(path: &Path) -> Result<String, String> {
+    let metadata = fs::metadata(path)
+        .map_err(|err| format!("failed to read metadata of {:?}: {}", path, err))?;
+    if metadata.len() > MAX_FILE_SIZE {
+        return Err(fl!("compare-too-large", name = path.display().to_string()));
+    }
+    let data = fs::read(path).map_err(|err| format!("failed to read {:?}: {}", path, err))?;
+    if data.contains(&0) {
+        return Err(fl!("compare-binary", name = path.display().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&data).into_owned())
+}
+
+/// Side by side diff of two text files
+#[derive(Clone, Debug)]
+pub struct TextDiff {
+    pub left: PathBuf,
+    pub right: PathBuf,
+    pub rows: Vec<DiffRow>,
+}
+
+impl TextDiff {
+    pub fn new(left: &Path, right: &Path) -> Result<Self, String> {
+        let rows = diff_texts(&read_text(left)?, &read_text(right)?);
+        Ok(Self {
+            left: left.to_path_buf(),
+            right: right.to_path_buf(),
+            rows,
+        })
+    }
+
+    pub fn changes(&self) -> usize {
+        self.rows.iter().filter(|row| row.changed).count()
+    }
+
+    fn side_view<'a, Message: 'static>(
+        line_opt: &'a Option<DiffLine>,
+        changed: bool,
+        digits: usize,
+        line_color: Color,
+    ) -> Element<'a, Message> {
+        let theme = theme::active();
+        let cosmic = theme.cosmic();
+        let number_color = Color::from(cosmic.palette.neutral_6);
+        let emphasis_color = Color {
+            a: 0.35,
+            ..line_color
+        };
+
+        let mut spans = Vec::new();
+        match line_opt {
+            Some(line) => {
+                spans.push(
+                    span(format!("{:>digits$}  ", line.number))
+                        .color(number_color)
+                        .font(font::mono()),
+                );
+                for (emphasized, text) in line.parts.iter() {
+                    let mut part = span(text.as_str()).font(font::mono());
+                    if changed {
+                        part = part.color(line_color);
+                    }
+                    if *emphasized {
+                        part = part.background(emphasis_color);
+                    }
+                    spans.push(part);
+                }
+            }
+            None => spans.push(span(" ").font(font::mono())),
+        }
+        widget::container(rich_text(spans).size(12))
+            .width(Length::FillPortion(1))
+            .into()
+    }
+
+    pub fn view<'a, Message: Clone + 'static>(&'a self, on_close: Message) -> Element<'a, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_m, ..
+        } = theme::active().cosmic().spacing;
+        let theme = theme::active();
+        let cosmic = theme.cosmic();
+        let removed_color = Color::from(cosmic.palette.bright_red);
+        let added_color = Color::from(cosmic.palette.bright_green);
+        let digits = self.rows.len().max(1).to_string().len();
+
+        let mut column = widget::column::with_capacity(self.rows.len());
+        for row in self.rows.iter() {
+            column = column.push(
+                widget::row::with_children(vec![
+                    Self::side_view(&row.left_opt, row.changed, digits, removed_color),
+                    Self::side_view(&row.right_opt, row.changed, digits, added_color),
+                ])
+                .spacing(space_m),
+            );
+        }
+
+        let header = widget::row::with_children(vec![
+            widget::text::heading(self.left.display().to_string())
+                .width(Length::FillPortion(1))
+                .into(),
+            widget::text::heading(self.right.display().to_string())
+                .width(Length::FillPortion(1))
+                .into(),
+            widget::text::body(fl!("compare-changes", changes = self.changes())).into(),
+            widget::button::icon(widget::icon::from_name("window-close-symbolic"))
+                .class(theme::Button::Standard)
+                .on_press(on_close)
+                .into(),
+        ])
+        .align_y(Alignment::Center)
+        .spacing(space_m);
+
+        widget::container(
+            widget::column::with_children(vec![
+                header.into(),
+                widget::scrollable(column.padding(space_xxs))
+                    .height(Length::Fill)
+                    .into(),
+            ])
+            .spacing(space_xxs)
+            .padding(space_m),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .class(theme::Container::Background)
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_texts, DiffLine};
+
+    #[test]
+    fn side_by_side() {
+        let rows = diff_texts("a\nb\nc\n", "a\nB b\nc\nd\n");
+        assert_eq!(rows.len(), 4);
+        assert!(!rows[0].changed);
+        assert_eq!(
+            rows[0].right_opt,
+            Some(DiffLine {
+                number: 1,
+                parts: vec![(false, "a".to_string())]
+            })
+        );
+
+        // The changed line is shown on both sides, with the addition emphasized
+        assert!(rows[1].changed);
+        assert_eq!(rows[1].left_opt.as_ref().map(|x| x.number), Some(2));
+        let right = rows[1].right_opt.as_ref().unwrap();
+        assert_eq!(right.number, 2);
+        assert!(right
+            .parts
+            .iter()
+            .any(|(emphasized, text)| *emphasized && text.contains('B')));
+
+        assert!(!rows[2].changed);
+        assert!(rows[3].changed);
+        assert_eq!(rows[3].left_opt, None);
+        assert_eq!(rows[3].right_opt.as_ref().map(|x| x.number), Some(4));
+    }
+
+    #[test]
+    fn identical() {
+        let rows = diff_texts("same\n", "same\n");
+        assert_eq!(rows.len(), 1);
+        assert!(rows.iter().all(|row| !row.changed));
+    }
+}
@@ -1,7 +1,37 @@
-use cosmic::iced::clipboard::mime::AllowedMimeTypes;
+use cosmic::iced::clipboard::mime::{AllowedMimeTypes, AsMimeTypes};
 use std::{borrow::Cow, error::Error, path::PathBuf, str};
 use url::Url;
 
+use crate::clipboard::{ClipboardCopy, ClipboardKind};
+
+/// Items dragged out of a pane, into a pane, the terminal or other applications.
+/// text/uri-list is offered first, so that applications that also take text, such as mail
+/// clients and browsers, attach or upload the files instead of inserting their paths.
+#[derive(Clone, Debug)]
+pub struct DndDrag(ClipboardCopy);
+
+impl DndDrag {
+    pub fn new(paths: &[PathBuf]) -> Self {
+        Self(ClipboardCopy::new(ClipboardKind::Copy, paths))
+    }
+}
+
+impl AsMimeTypes for DndDrag {
+    fn available(&self) -> Cow<'static, [String]> {
+        Cow::from(vec![
+            "text/uri-list".to_string(),
+            "x-special/gnome-copied-files".to_string(),
+            "text/plain;charset=utf-8".to_string(),
+            "text/plain".to_string(),
+            "UTF8_STRING".to_string(),
+        ])
+    }
+
+    fn as_bytes(&self, mime_type: &str) -> Option<Cow<'static, [u8]>> {
+        self.0.as_bytes(mime_type)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct DndDrop {
     pub paths: Vec<PathBuf>,
@@ -12,10 +42,47 @@ impl AllowedMimeTypes for DndDrop {
         Cow::from(vec![
             "x-special/gnome-copied-files".to_string(),
             "text/uri-list".to_string(),
+            "text/plain;charset=utf-8".to_string(),
+            "text/plain".to_string(),
         ])
     }
 }
 
+/// Parse a text/uri-list as sent by other applications, see RFC 2483
+pub fn parse_uri_list(text: &str) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut paths = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        // Lines starting with # are comments
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let url = Url::parse(line)?;
+        match url.to_file_path() {
+            Ok(path) => paths.push(path),
+            Err(()) => Err(format!("invalid file URL {:?}", url))?,
+        }
+    }
+    Ok(paths)
+}
+
+/// Parse plain text containing one absolute path or file URL per line
+fn parse_text_plain(text: &str) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut paths = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('/') {
+            paths.push(PathBuf::from(line));
+        } else {
+            paths.extend(parse_uri_list(line)?);
+        }
+    }
+    Ok(paths)
+}
+
 impl TryFrom<(Vec<u8>, String)> for DndDrop {
     type Error = Box<dyn Error>;
     fn try_from(value: (Vec<u8>, String)) -> Result<Self, Self::Error> {
@@ -24,13 +91,11 @@ impl TryFrom<(Vec<u8>, String)> for DndDrop {
         match mime.as_str() {
             "text/uri-list" => {
                 let text = str::from_utf8(&data)?;
-                for line in text.lines() {
-                    let url = Url::parse(line)?;
-                    match url.to_file_path() {
-                        Ok(path) => paths.push(path),
-                        Err(()) => Err(format!("invalid file URL {:?}", url))?,
-                    }
-                }
+                paths = parse_uri_list(text)?;
+            }
+            "text/plain" | "text/plain;charset=utf-8" => {
+                let text = str::from_utf8(&data)?;
+                paths = parse_text_plain(text)?;
             }
             "x-special/gnome-copied-files" => {
                 let text = str::from_utf8(&data)?;
@@ -46,6 +111,9 @@ impl TryFrom<(Vec<u8>, String)> for DndDrop {
             }
             _ => Err(format!("unsupported mime type {:?}", mime))?,
         }
+        if paths.is_empty() {
+            Err("no paths in drop")?;
+        }
         Ok(Self { paths })
     }
 }
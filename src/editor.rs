@@ -0,0 +1,377 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    cosmic_theme, font,
+    iced::{widget::text::Wrapping, Alignment, Length},
+    theme,
+    widget::{self, text_editor},
+    Element,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::fl;
+
+/// Files larger than this are not opened in the editor
+pub const MAX_FILE_LEN: u64 = 8 * 1024 * 1024;
+
+/// Text encoding of a file in the editor
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Latin1,
+}
+
+impl Encoding {
+    pub fn all() -> &'static [Self] {
+        &[Self::Utf8, Self::Utf16Le, Self::Latin1]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Latin1 => "ISO-8859-1",
+        }
+    }
+
+    /// UTF-16 if the data starts with its byte order mark, UTF-8 if it is valid, Latin-1
+    /// otherwise, which can decode anything
+    pub fn detect(data: &[u8]) -> Self {
+        if data.starts_with(&[0xFF, 0xFE]) {
+            Self::Utf16Le
+        } else if std::str::from_utf8(data).is_ok() {
+            Self::Utf8
+        } else {
+            Self::Latin1
+        }
+    }
+
+    /// Decode the data, a byte order mark is kept so that saving writes it again
+    pub fn decode(&self, data: &[u8]) -> Option<String> {
+        match self {
+            Self::Utf8 => String::from_utf8(data.to_vec()).ok(),
+            Self::Utf16Le => {
+                if data.len() % 2 != 0 {
+                    return None;
+                }
+                let units: Vec<u16> = data
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .collect();
+                String::from_utf16(&units).ok()
+            }
+            Self::Latin1 => Some(data.iter().map(|b| char::from(*b)).collect()),
+        }
+    }
+
+    /// Encode the text, fails if it has characters that the encoding cannot represent
+    pub fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Utf8 => Some(text.as_bytes().to_vec()),
+            Self::Utf16Le => Some(
+                text.encode_utf16()
+                    .flat_map(|unit| unit.to_le_bytes())
+                    .collect(),
+            ),
+            Self::Latin1 => text
+                .chars()
+                .map(|c| u8::try_from(u32::from(c)).ok())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Action(text_editor::Action),
+    Encoding(usize),
+    Find(String),
+    FindNext,
+    Replace(String),
+    ReplaceAll,
+    ReplaceOne,
+    Save,
+}
+
+/// Built-in text editor for small files, shown in its own window
+pub struct Editor {
+    path: PathBuf,
+    content: text_editor::Content,
+    encoding: Encoding,
+    encoding_names: Vec<String>,
+    find: String,
+    replace: String,
+    modified: bool,
+    status: String,
+}
+
+impl Editor {
+    pub fn new(path: &Path) -> Result<Self, String> {
+        let len = fs::metadata(path)
+            .map_err(|err| format!("failed to read metadata of {:?}: {}", path, err))?
+            .len();
+        if len > MAX_FILE_LEN {
+            return Err(format!("{:?} is too large to edit", path));
+        }
+        let data = fs::read(path).map_err(|err| format!("failed to read {:?}: {}", path, err))?;
+        if data.contains(&0) && !data.starts_with(&[0xFF, 0xFE]) {
+            return Err(format!("{:?} is not a text file", path));
+        }
+        let encoding = Encoding::detect(&data);
+        let text = encoding
+            .decode(&data)
+            .ok_or_else(|| format!("failed to decode {:?} as {}", path, encoding.name()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            content: text_editor::Content::with_text(&text),
+            encoding,
+            encoding_names: Encoding::all()
+                .iter()
+                .map(|encoding| encoding.name().to_string())
+                .collect(),
+            find: String::new(),
+            replace: String::new(),
+            modified: false,
+            status: String::new(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Show a message below the text, such as why it was not saved
+    pub fn set_status(&mut self, status: String) {
+        self.status = status;
+    }
+
+    pub fn title(&self) -> String {
+        let name = self.path.file_name().map_or_else(
+            || self.path.display().to_string(),
+            |name| name.to_string_lossy().to_string(),
+        );
+        if self.modified {
+            format!("• {}", name)
+        } else {
+            name
+        }
+    }
+
+    /// Use another encoding. Unchanged text is read again with it, changed text is only
+    /// saved with it.
+    fn set_encoding(&mut self, encoding: Encoding) {
+        if !self.modified {
+            let decoded = fs::read(&self.path)
+                .ok()
+                .and_then(|data| encoding.decode(&data));
+            match decoded {
+                Some(text) => self.content = text_editor::Content::with_text(&text),
+                None => {
+                    self.status = fl!("editor-cannot-decode", encoding = encoding.name());
+                    return;
+                }
+            }
+        }
+        self.encoding = encoding;
+        self.status.clear();
+    }
+
+    /// Select the characters from `start`, a byte offset in `text`, moving the cursor there
+    /// line by line. Lines are not wrapped, so moving down moves to the next line.
+    fn select(&mut self, text: &str, start: usize, len: usize) {
+        use text_editor::{Action, Motion};
+
+        let before = &text[..start];
+        let line = before.matches('\n').count();
+        let column = before.rsplit('\n').next().map_or(0, |x| x.chars().count());
+        self.content.perform(Action::Move(Motion::DocumentStart));
+        for _ in 0..line {
+            self.content.perform(Action::Move(Motion::Down));
+        }
+        for _ in 0..column {
+            self.content.perform(Action::Move(Motion::Right));
+        }
+        for _ in 0..len {
+            self.content.perform(Action::Select(Motion::Right));
+        }
+    }
+
+    /// Select the next match after the cursor, starting again at the top after the last one
+    fn find_next(&mut self) {
+        if self.find.is_empty() {
+            return;
+        }
+        let text = self.content.text();
+        let (line, column) = self.content.cursor_position();
+        let line_start: usize = text.split('\n').take(line).map(|x| x.len() + 1).sum();
+        let cursor = text[line_start..]
+            .char_indices()
+            .nth(column)
+            .map_or(text.len(), |(offset, _)| line_start + offset);
+        match text[cursor..]
+            .find(&self.find)
+            .map(|offset| cursor + offset)
+            .or_else(|| text.find(&self.find))
+        {
+            Some(start) => {
+                self.select(&text, start, self.find.chars().count());
+                self.status.clear();
+            }
+            None => self.status = fl!("editor-not-found"),
+        }
+    }
+
+    /// Replace the selection if it is a match, and select the next one
+    fn replace_one(&mut self) {
+        if !self.find.is_empty() && self.content.selection().as_deref() == Some(self.find.as_str())
+        {
+            self.content
+                .perform(text_editor::Action::Edit(text_editor::Edit::Paste(
+                    Arc::new(self.replace.clone()),
+                )));
+            self.modified = true;
+        }
+        self.find_next();
+    }
+
+    fn replace_all(&mut self) {
+        if self.find.is_empty() {
+            return;
+        }
+        let text = self.content.text();
+        let count = text.matches(&self.find).count();
+        if count > 0 {
+            self.content =
+                text_editor::Content::with_text(&text.replace(&self.find, &self.replace));
+            self.modified = true;
+        }
+        self.status = fl!("editor-replaced", count = count);
+    }
+
+    fn save(&mut self) {
+        let Some(data) = self.encoding.encode(&self.content.text()) else {
+            self.status = fl!("editor-cannot-encode", encoding = self.encoding.name());
+            return;
+        };
+        match fs::write(&self.path, data) {
+            Ok(()) => {
+                self.modified = false;
+                self.status = fl!("editor-saved");
+            }
+            Err(err) => {
+                log::warn!("failed to save {:?}: {}", self.path, err);
+                self.status = err.to_string();
+            }
+        }
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Action(action) => {
+                if action.is_edit() {
+                    self.modified = true;
+                }
+                self.content.perform(action);
+            }
+            Message::Encoding(index) => {
+                if let Some(encoding) = Encoding::all().get(index) {
+                    self.set_encoding(*encoding);
+                }
+            }
+            Message::Find(find) => self.find = find,
+            Message::FindNext => self.find_next(),
+            Message::Replace(replace) => self.replace = replace,
+            Message::ReplaceAll => self.replace_all(),
+            Message::ReplaceOne => self.replace_one(),
+            Message::Save => self.save(),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let toolbar = widget::row::with_capacity(7)
+            .push(
+                widget::text_input::search_input(fl!("editor-find"), &self.find)
+                    .on_input(Message::Find)
+                    .on_submit(Message::FindNext)
+                    .width(Length::Fixed(200.0)),
+            )
+            .push(widget::button::standard(fl!("editor-find-next")).on_press(Message::FindNext))
+            .push(
+                widget::text_input(fl!("replace-with"), &self.replace)
+                    .on_input(Message::Replace)
+                    .on_submit(Message::ReplaceOne)
+                    .width(Length::Fixed(200.0)),
+            )
+            .push(widget::button::standard(fl!("replace")).on_press(Message::ReplaceOne))
+            .push(widget::button::standard(fl!("editor-replace-all")).on_press(Message::ReplaceAll))
+            .push(widget::horizontal_space())
+            .push(widget::dropdown(
+                &self.encoding_names,
+                Encoding::all().iter().position(|x| *x == self.encoding),
+                Message::Encoding,
+            ))
+            .push(
+                widget::button::suggested(fl!("save"))
+                    .on_press_maybe(self.modified.then_some(Message::Save)),
+            )
+            .align_y(Alignment::Center)
+            .spacing(space_xxs);
+
+        let status = if self.status.is_empty() {
+            self.path.display().to_string()
+        } else {
+            self.status.clone()
+        };
+
+        widget::column::with_capacity(3)
+            .push(toolbar)
+            .push(
+                widget::text_editor(&self.content)
+                    .on_action(Message::Action)
+                    .font(font::mono())
+                    .wrapping(Wrapping::None)
+                    .height(Length::Fill)
+                    .padding(space_xxs),
+            )
+            .push(widget::text::caption(status))
+            .padding(space_s)
+            .spacing(space_xxs)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Encoding;
+
+    #[test]
+    fn encodings_round_trip() {
+        let text = "Grüße\nnew line\n";
+        for encoding in Encoding::all() {
+            let data = encoding.encode(text).unwrap();
+            assert_eq!(encoding.decode(&data).as_deref(), Some(text));
+        }
+    }
+
+    #[test]
+    fn detect_encoding() {
+        assert_eq!(Encoding::detect("Grüße".as_bytes()), Encoding::Utf8);
+        assert_eq!(Encoding::detect(b"Gr\xfc\xdfe"), Encoding::Latin1);
+        assert_eq!(Encoding::detect(b"\xff\xfeG\0r\0"), Encoding::Utf16Le);
+    }
+
+    #[test]
+    fn latin1_cannot_encode_everything() {
+        assert_eq!(Encoding::Latin1.encode("€"), None);
+        assert_eq!(Encoding::Utf16Le.decode(b"odd"), None);
+    }
+}
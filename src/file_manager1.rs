@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::futures::{channel::mpsc, SinkExt},
+    Application,
+};
+use std::path::PathBuf;
+use zbus::fdo::{RequestNameFlags, RequestNameReply};
+
+use crate::app::App;
+
+pub const NAME: &str = "org.freedesktop.FileManager1";
+pub const PATH: &str = "/org/freedesktop/FileManager1";
+
+/// Request of another application, for example a browser revealing a download
+#[derive(Clone, Debug)]
+pub enum Request {
+    /// Open the folders
+    ShowFolders(Vec<PathBuf>),
+    /// Open the folders containing the items with the items selected
+    ShowItems(Vec<PathBuf>),
+    /// Like [`Request::ShowItems`], with the details of the items shown
+    ShowItemProperties(Vec<PathBuf>),
+}
+
+/// The `org.freedesktop.FileManager1` interface, see
+/// <https://www.freedesktop.org/wiki/Specifications/file-manager-interface/>
+struct FileManager1 {
+    tx: mpsc::Sender<Request>,
+}
+
+impl FileManager1 {
+    async fn send(&self, request: Request) {
+        if let Err(err) = self.tx.clone().send(request).await {
+            log::warn!("failed to handle {} request: {}", NAME, err);
+        }
+    }
+}
+
+#[zbus::interface(name = "org.freedesktop.FileManager1")]
+impl FileManager1 {
+    async fn show_folders(&self, uris: Vec<String>, _startup_id: String) {
+        self.send(Request::ShowFolders(paths(uris))).await;
+    }
+
+    async fn show_items(&self, uris: Vec<String>, _startup_id: String) {
+        self.send(Request::ShowItems(paths(uris))).await;
+    }
+
+    async fn show_item_properties(&self, uris: Vec<String>, _startup_id: String) {
+        self.send(Request::ShowItemProperties(paths(uris))).await;
+    }
+}
+
+/// Local paths of the `file://` URIs, other URIs are skipped
+fn paths(uris: Vec<String>) -> Vec<PathBuf> {
+    uris.into_iter()
+        .filter_map(
+            |uri| match url::Url::parse(&uri).map(|url| url.to_file_path()) {
+                Ok(Ok(path)) => Some(path),
+                _ => {
+                    log::warn!("unsupported {} URI {:?}", NAME, uri);
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+/// Serve the interface on the session bus, for as long as the connection is kept.
+/// Besides the common name it is served under the application ID, so that commander
+/// can be reached when another file manager owns the common name.
+pub async fn serve(tx: mpsc::Sender<Request>) -> zbus::Result<zbus::Connection> {
+    let connection = zbus::connection::Builder::session()?
+        .serve_at(PATH, FileManager1 { tx })?
+        .build()
+        .await?;
+    // The common name is not taken from another file manager, and given up when one asks
+    let flags = RequestNameFlags::AllowReplacement | RequestNameFlags::DoNotQueue;
+    match connection.request_name_with_flags(NAME, flags).await {
+        Ok(RequestNameReply::PrimaryOwner | RequestNameReply::AlreadyOwner) => {}
+        Ok(reply) => log::info!("not owning {}: {:?}", NAME, reply),
+        Err(err) => log::info!("failed to own {}: {}", NAME, err),
+    }
+    if let Err(err) = connection.request_name(App::APP_ID).await {
+        log::info!("failed to own {}: {}", App::APP_ID, err);
+    }
+    Ok(connection)
+}
+
+/// Open the folders in the running instance, fails if there is none
+pub async fn show_folders(paths: &[PathBuf]) -> zbus::Result<()> {
+    let uris: Vec<String> = paths
+        .iter()
+        .filter_map(|path| url::Url::from_file_path(path).ok())
+        .map(String::from)
+        .collect();
+    let connection = zbus::Connection::session().await?;
+    connection
+        .call_method(
+            Some(App::APP_ID),
+            PATH,
+            Some(NAME),
+            "ShowFolders",
+            &(uris, ""),
+        )
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Most directories that are remembered, the ones with the lowest score are forgotten first
+const MAX_RECENT_DIRS: usize = 250;
+/// Directories in the location history of a pane
+const MAX_PANE_HISTORY: usize = 20;
+
+/// Directory that was visited, ranked by how often and how recently it was visited
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct RecentDir {
+    pub path: PathBuf,
+    pub visits: u32,
+    /// Seconds since the Unix epoch
+    pub last_visit: u64,
+}
+
+impl RecentDir {
+    /// Visits weighted by the time since the last visit
+    pub fn score(&self, now: u64) -> f64 {
+        let age = now.saturating_sub(self.last_visit);
+        let weight = if age < 60 * 60 {
+            4.0
+        } else if age < 24 * 60 * 60 {
+            2.0
+        } else if age < 7 * 24 * 60 * 60 {
+            0.5
+        } else {
+            0.25
+        };
+        f64::from(self.visits) * weight
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Count a visit of the directory at `path`
+pub fn visit(recent_dirs: &mut Vec<RecentDir>, path: &Path, now: u64) {
+    match recent_dirs.iter_mut().find(|dir| dir.path == path) {
+        Some(dir) => {
+            dir.visits = dir.visits.saturating_add(1);
+            dir.last_visit = now;
+        }
+        None => recent_dirs.push(RecentDir {
+            path: path.to_path_buf(),
+            visits: 1,
+            last_visit: now,
+        }),
+    }
+    if recent_dirs.len() > MAX_RECENT_DIRS {
+        recent_dirs.sort_by(|a, b| b.score(now).total_cmp(&a.score(now)));
+        recent_dirs.truncate(MAX_RECENT_DIRS);
+    }
+}
+
+/// Move the directory at `path` to the front of the location history of a pane
+pub fn visit_pane_history(history: &mut Vec<PathBuf>, path: &Path) {
+    history.retain(|dir| dir != path);
+    history.insert(0, path.to_path_buf());
+    history.truncate(MAX_PANE_HISTORY);
+}
+
+/// Match the characters of `pattern` in order against `text`, ignoring case.
+/// Consecutive matches and matches at the start of words score higher.
+fn subsequence_score(pattern: &str, text: &str) -> Option<u32> {
+    let mut pattern_chars = pattern.chars().peekable();
+    let mut score = 0;
+    let mut prev_opt = None;
+    let mut prev_matched = false;
+    for c in text.chars() {
+        let Some(p) = pattern_chars.peek() else {
+            break;
+        };
+        if c.to_lowercase().eq(p.to_lowercase()) {
+            pattern_chars.next();
+            score += 1;
+            if prev_matched {
+                score += 4;
+            }
+            if prev_opt.map_or(true, |prev| matches!(prev, '/' | '-' | '_' | '.' | ' ')) {
+                score += 6;
+            }
+            prev_matched = true;
+        } else {
+            prev_matched = false;
+        }
+        prev_opt = Some(c);
+    }
+    if pattern_chars.peek().is_some() {
+        None
+    } else {
+        Some(score)
+    }
+}
+
+/// Fuzzy match of `pattern` against a path, matches within the last component rank first
+pub fn fuzzy_score(pattern: &str, text: &str) -> Option<u32> {
+    let name = text.rsplit('/').next().unwrap_or(text);
+    match subsequence_score(pattern, name) {
+        Some(score) => Some(score + 2 * pattern.chars().count() as u32),
+        None => subsequence_score(pattern, text),
+    }
+}
+
+/// Directories matching `pattern`, best match first and the higher frecency first for equal matches
+pub fn search(candidates: Vec<(PathBuf, f64)>, pattern: &str) -> Vec<PathBuf> {
+    let mut results: Vec<_> = candidates
+        .into_iter()
+        .filter_map(|(path, frecency)| {
+            let score = fuzzy_score(pattern, &path.to_string_lossy())?;
+            Some((score, frecency, path))
+        })
+        .collect();
+    results.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.total_cmp(&a.1)));
+    results.into_iter().map(|(_, _, path)| path).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{fuzzy_score, search, visit, visit_pane_history, MAX_PANE_HISTORY};
+
+    #[test]
+    fn visits() {
+        let mut recent_dirs = Vec::new();
+        visit(&mut recent_dirs, Path::new("/a"), 0);
+        visit(&mut recent_dirs, Path::new("/b"), 0);
+        visit(&mut recent_dirs, Path::new("/a"), 10);
+        assert_eq!(recent_dirs.len(), 2);
+        assert_eq!(recent_dirs[0].visits, 2);
+        assert_eq!(recent_dirs[0].last_visit, 10);
+
+        // Old visits count less than recent ones
+        assert!(recent_dirs[0].score(10) > recent_dirs[1].score(10));
+        assert!(recent_dirs[0].score(30 * 24 * 60 * 60) < recent_dirs[1].score(0));
+    }
+
+    #[test]
+    fn pane_history() {
+        let mut history = Vec::new();
+        for i in 0..MAX_PANE_HISTORY + 5 {
+            visit_pane_history(&mut history, &PathBuf::from(format!("/{}", i)));
+        }
+        visit_pane_history(&mut history, Path::new("/10"));
+        assert_eq!(history.len(), MAX_PANE_HISTORY);
+        assert_eq!(history[0], Path::new("/10"));
+        assert_eq!(
+            history[1],
+            PathBuf::from(format!("/{}", MAX_PANE_HISTORY + 4))
+        );
+        assert_eq!(
+            history
+                .iter()
+                .filter(|dir| *dir == Path::new("/10"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn fuzzy_matches() {
+        assert_eq!(fuzzy_score("xyz", "/home/user/src"), None);
+        assert_eq!(fuzzy_score("", "/home/user/src"), Some(0));
+        // Word starts in the last component beat scattered characters
+        assert!(
+            fuzzy_score("dl", "/home/user/Downloads").unwrap()
+                < fuzzy_score("dl", "/home/user/doc-lib").unwrap()
+        );
+        assert!(
+            fuzzy_score("src", "/home/user/src").unwrap()
+                > fuzzy_score("src", "/home/user/scratch/c").unwrap()
+        );
+    }
+
+    #[test]
+    fn ranking() {
+        let results = search(
+            vec![
+                (PathBuf::from("/home/user/Music"), 1.0),
+                (PathBuf::from("/home/user/projects/commander"), 0.5),
+                (PathBuf::from("/usr/share/commander"), 8.0),
+            ],
+            "comm",
+        );
+        assert_eq!(
+            results,
+            [
+                PathBuf::from("/usr/share/commander"),
+                PathBuf::from("/home/user/projects/commander"),
+            ]
+        );
+    }
+}
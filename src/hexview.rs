@@ -0,0 +1,295 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    cosmic_theme, font,
+    iced::{
+        widget::{rich_text, span},
+        Color, Length,
+    },
+    theme, widget, Element,
+};
+use std::{
+    fs::{self, File},
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+/// Number of bytes shown on each line
+pub const BYTES_PER_LINE: u64 = 16;
+/// Number of bytes loaded and shown at once
+pub const WINDOW_BYTES: u64 = 8 * 1024;
+/// Size of the chunks read while searching
+const SEARCH_CHUNK: usize = 64 * 1024;
+
+/// Parse an offset as decimal, or as hexadecimal with a 0x prefix
+pub fn parse_offset(input: &str) -> Option<u64> {
+    let input = input.trim();
+    match input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => input.parse().ok(),
+    }
+}
+
+/// Parse a search pattern as hex bytes like `de ad be ef`, or as text.
+///
+/// Text that would also be valid hex can be searched for by quoting it.
+pub fn parse_pattern(input: &str) -> Option<Vec<u8>> {
+    if input.is_empty() {
+        return None;
+    }
+    if let Some(text) = input
+        .strip_prefix('"')
+        .and_then(|input| input.strip_suffix('"'))
+    {
+        return (!text.is_empty()).then(|| text.as_bytes().to_vec());
+    }
+    let digits: Vec<u8> = input.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if !digits.is_empty() && digits.len() % 2 == 0 && digits.iter().all(u8::is_ascii_hexdigit) {
+        let mut bytes = Vec::with_capacity(digits.len() / 2);
+        for pair in digits.chunks(2) {
+            // Both digits were checked above
+            let pair = std::str::from_utf8(pair).unwrap();
+            bytes.push(u8::from_str_radix(pair, 16).unwrap());
+        }
+        return Some(bytes);
+    }
+    Some(input.as_bytes().to_vec())
+}
+
+/// Find the first occurrence of `pattern` in `reader`, which starts at `start`
+pub fn find_in_reader<R: Read>(
+    mut reader: R,
+    pattern: &[u8],
+    start: u64,
+) -> io::Result<Option<u64>> {
+    if pattern.is_empty() {
+        return Ok(None);
+    }
+    // Keep the end of the previous chunk so matches across chunks are found
+    let mut buf = Vec::with_capacity(SEARCH_CHUNK + pattern.len());
+    let mut buf_offset = start;
+    let mut chunk = vec![0; SEARCH_CHUNK];
+    loop {
+        let count = reader.read(&mut chunk)?;
+        if count == 0 {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk[..count]);
+        if let Some(index) = buf
+            .windows(pattern.len())
+            .position(|window| window == pattern)
+        {
+            return Ok(Some(buf_offset + index as u64));
+        }
+        let keep = (pattern.len() - 1).min(buf.len());
+        let drain = buf.len() - keep;
+        buf.drain(..drain);
+        buf_offset += drain as u64;
+    }
+}
+
+/// Find the first occurrence of `pattern` in the file at `path`, starting at `start`
+pub fn find(path: &Path, pattern: &[u8], start: u64) -> Result<Option<u64>, String> {
+    let mut file = File::open(path).map_err(|err| format!("failed to open {:?}: {}", path, err))?;
+    file.seek(SeekFrom::Start(start))
+        .map_err(|err| format!("failed to seek {:?}: {}", path, err))?;
+    find_in_reader(io::BufReader::new(file), pattern, start)
+        .map_err(|err| format!("failed to search {:?}: {}", path, err))
+}
+
+/// Read-only hex view of a window of a file.
+pub struct HexView {
+    path: PathBuf,
+    file_len: u64,
+    offset: u64,
+    data: Vec<u8>,
+    /// Offset and length of the current search match
+    match_opt: Option<(u64, u64)>,
+}
+
+impl HexView {
+    pub fn new(path: &Path) -> Result<Self, String> {
+        let mut hex_view = Self {
+            path: path.to_path_buf(),
+            file_len: 0,
+            offset: 0,
+            data: Vec::new(),
+            match_opt: None,
+        };
+        hex_view.load_at(0)?;
+        Ok(hex_view)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn file_len(&self) -> u64 {
+        self.file_len
+    }
+
+    /// Offset where the next search should start
+    pub fn search_start(&self) -> u64 {
+        match self.match_opt {
+            Some((offset, _)) => offset + 1,
+            None => self.offset,
+        }
+    }
+
+    /// Load the window containing `offset`, aligned to the start of its line
+    pub fn load_at(&mut self, offset: u64) -> Result<(), String> {
+        let mut file = File::open(&self.path)
+            .map_err(|err| format!("failed to open {:?}: {}", self.path, err))?;
+        self.file_len = fs::metadata(&self.path)
+            .map_err(|err| format!("failed to read metadata of {:?}: {}", self.path, err))?
+            .len();
+        let offset = offset.min(self.file_len.saturating_sub(1));
+        let offset = offset - offset % BYTES_PER_LINE;
+        file.seek(SeekFrom::Start(offset))
+            .map_err(|err| format!("failed to seek {:?}: {}", self.path, err))?;
+        let mut data = Vec::with_capacity(WINDOW_BYTES as usize);
+        file.take(WINDOW_BYTES)
+            .read_to_end(&mut data)
+            .map_err(|err| format!("failed to read {:?}: {}", self.path, err))?;
+        self.offset = offset;
+        self.data = data;
+        Ok(())
+    }
+
+    pub fn previous_page(&mut self) -> Result<(), String> {
+        self.load_at(self.offset.saturating_sub(WINDOW_BYTES))
+    }
+
+    pub fn next_page(&mut self) -> Result<(), String> {
+        if self.offset + WINDOW_BYTES < self.file_len {
+            self.load_at(self.offset + WINDOW_BYTES)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Show a search match, loading the window that contains it
+    pub fn show_match(&mut self, offset: u64, len: u64) -> Result<(), String> {
+        self.match_opt = Some((offset, len));
+        // Show a few lines before the match for context
+        self.load_at(offset.saturating_sub(4 * BYTES_PER_LINE))
+    }
+
+    pub fn view<'a, Message: 'static>(&'a self) -> Element<'a, Message> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+        let theme = theme::active();
+        let cosmic = theme.cosmic();
+        let offset_color = Color::from(cosmic.palette.neutral_6);
+        let match_color = Color::from(cosmic.accent_text_color());
+        let text_color = Color::from(cosmic.background.on);
+
+        let end = self.offset + self.data.len() as u64;
+        let digits = format!("{:x}", end.max(1)).len().max(8);
+        let is_match = |offset: u64| {
+            self.match_opt.map_or(false, |(start, len)| {
+                offset >= start && offset < start + len
+            })
+        };
+
+        let mut column =
+            widget::column::with_capacity(self.data.len() / BYTES_PER_LINE as usize + 1);
+        for (line, bytes) in self.data.chunks(BYTES_PER_LINE as usize).enumerate() {
+            let line_offset = self.offset + line as u64 * BYTES_PER_LINE;
+            let mut spans = Vec::with_capacity(2 * bytes.len() + 2);
+            spans.push(
+                span(format!("{:0digits$x}  ", line_offset))
+                    .color(offset_color)
+                    .font(font::mono()),
+            );
+            for i in 0..BYTES_PER_LINE as usize {
+                let text = match bytes.get(i) {
+                    Some(byte) => format!("{:02x} ", byte),
+                    None => "   ".to_string(),
+                };
+                let text = if i + 1 == BYTES_PER_LINE as usize / 2 {
+                    format!("{} ", text)
+                } else {
+                    text
+                };
+                let color = if is_match(line_offset + i as u64) {
+                    match_color
+                } else {
+                    text_color
+                };
+                spans.push(span(text).color(color).font(font::mono()));
+            }
+            spans.push(span(" ").font(font::mono()));
+            for (i, byte) in bytes.iter().enumerate() {
+                let c = if byte.is_ascii_graphic() || *byte == b' ' {
+                    *byte as char
+                } else {
+                    '.'
+                };
+                let color = if is_match(line_offset + i as u64) {
+                    match_color
+                } else {
+                    offset_color
+                };
+                spans.push(span(c.to_string()).color(color).font(font::mono()));
+            }
+            column = column.push(rich_text(spans).size(12));
+        }
+
+        widget::container(widget::scrollable(column.padding(space_xxs)).direction(
+            widget::scrollable::Direction::Both {
+                vertical: widget::scrollable::Scrollbar::default(),
+                horizontal: widget::scrollable::Scrollbar::default(),
+            },
+        ))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_in_reader, parse_offset, parse_pattern};
+
+    #[test]
+    fn offsets() {
+        assert_eq!(parse_offset("4096"), Some(4096));
+        assert_eq!(parse_offset(" 0x1F "), Some(31));
+        assert_eq!(parse_offset("0Xff"), Some(255));
+        assert_eq!(parse_offset("1f"), None);
+        assert_eq!(parse_offset(""), None);
+    }
+
+    #[test]
+    fn patterns() {
+        assert_eq!(
+            parse_pattern("de ad BE ef"),
+            Some(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(parse_pattern("PNG"), Some(b"PNG".to_vec()));
+        assert_eq!(parse_pattern("\"cafe\""), Some(b"cafe".to_vec()));
+        assert_eq!(parse_pattern("abc"), Some(b"abc".to_vec()));
+        assert_eq!(parse_pattern("\"\""), None);
+        assert_eq!(parse_pattern(""), None);
+    }
+
+    #[test]
+    fn find_across_chunks() {
+        let mut data = vec![0u8; 200 * 1024];
+        // Straddle the boundary between the first and second chunk
+        let index = super::SEARCH_CHUNK - 2;
+        data[index..index + 4].copy_from_slice(&[1, 2, 3, 4]);
+        assert_eq!(
+            find_in_reader(&data[..], &[1, 2, 3, 4], 0).unwrap(),
+            Some(index as u64)
+        );
+        assert_eq!(
+            find_in_reader(&data[100..], &[1, 2, 3, 4], 100).unwrap(),
+            Some(index as u64)
+        );
+        assert_eq!(find_in_reader(&data[..], &[5], 0).unwrap(), None);
+    }
+}
@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    cosmic_theme, font,
+    iced::{
+        widget::{rich_text, span},
+        Color, Length,
+    },
+    theme, widget, Element,
+};
+use mime_guess::{mime, Mime};
+use once_cell::sync::Lazy;
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{self, ThemeSet},
+    parsing::{SyntaxReference, SyntaxSet},
+    util::LinesWithEndings,
+};
+
+/// Amount of text shown in the details panel
+pub const PREVIEW_MAX_BYTES: u64 = 64 * 1024;
+/// Amount of text shown in the full viewer
+pub const VIEWER_MAX_BYTES: u64 = 1024 * 1024;
+
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+#[derive(Clone, Debug)]
+pub struct HighlightedText {
+    /// Name of the detected language
+    pub syntax_name: String,
+    /// Colored spans for each line, without line endings
+    pub lines: Vec<Vec<(String, Color)>>,
+    pub background: Color,
+    pub gutter: Color,
+    /// Number of bytes that were requested
+    pub max_bytes: u64,
+    /// File is longer than what was read
+    pub truncated: bool,
+}
+
+/// Check if a file with this mime type can be shown as text
+pub fn is_text(mime: &Mime) -> bool {
+    if mime.type_() == mime::TEXT {
+        return true;
+    }
+    if mime.type_() != mime::APPLICATION {
+        return false;
+    }
+    let subtype = mime.subtype().as_str();
+    matches!(
+        subtype,
+        "json"
+            | "javascript"
+            | "toml"
+            | "xml"
+            | "x-desktop"
+            | "x-perl"
+            | "x-php"
+            | "x-python"
+            | "x-ruby"
+            | "x-shellscript"
+            | "x-yaml"
+            | "yaml"
+    ) || mime
+        .suffix()
+        .map_or(false, |suffix| suffix == mime::JSON || suffix == mime::XML)
+}
+
+fn color(color: highlighting::Color) -> Color {
+    Color::from_rgba8(color.r, color.g, color.b, color.a as f32 / 255.0)
+}
+
+fn find_syntax(path: &Path, text: &str) -> &'static SyntaxReference {
+    // File names like Makefile are matched as extensions by syntect
+    let by_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| SYNTAX_SET.find_syntax_by_extension(name));
+    let by_extension = || {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| SYNTAX_SET.find_syntax_by_extension(extension))
+    };
+    let by_first_line = || {
+        text.lines()
+            .next()
+            .and_then(|line| SYNTAX_SET.find_syntax_by_first_line(line))
+    };
+    by_name
+        .or_else(by_extension)
+        .or_else(by_first_line)
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text())
+}
+
+/// Read up to `max_bytes` of the text file at `path` and highlight it.
+pub fn highlight_file(path: &Path, max_bytes: u64, dark: bool) -> Result<HighlightedText, String> {
+    let mut data = Vec::new();
+    File::open(path)
+        .and_then(|file| file.take(max_bytes + 1).read_to_end(&mut data))
+        .map_err(|err: io::Error| format!("failed to read {:?}: {}", path, err))?;
+
+    let truncated = data.len() as u64 > max_bytes;
+    if truncated {
+        // Only show complete lines
+        data.truncate(max_bytes as usize);
+        if let Some(end) = data.iter().rposition(|&b| b == b'\n') {
+            data.truncate(end + 1);
+        }
+    }
+    if data.contains(&0) {
+        return Err(format!("{:?} is not a text file", path));
+    }
+    let text = String::from_utf8_lossy(&data);
+
+    let theme = &THEME_SET.themes[if dark {
+        "base16-ocean.dark"
+    } else {
+        "InspiredGitHub"
+    }];
+    let syntax = find_syntax(path, &text);
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let mut lines = Vec::new();
+    for line in LinesWithEndings::from(&text) {
+        let ranges = highlighter
+            .highlight_line(line, &SYNTAX_SET)
+            .map_err(|err| format!("failed to highlight {:?}: {}", path, err))?;
+        lines.push(
+            ranges
+                .into_iter()
+                .map(|(style, text)| {
+                    (
+                        text.trim_end_matches(['\r', '\n']).to_string(),
+                        color(style.foreground),
+                    )
+                })
+                .filter(|(text, _)| !text.is_empty())
+                .collect(),
+        );
+    }
+
+    Ok(HighlightedText {
+        syntax_name: syntax.name.clone(),
+        lines,
+        background: theme.settings.background.map_or(Color::TRANSPARENT, color),
+        gutter: theme
+            .settings
+            .gutter_foreground
+            .or(theme.settings.foreground)
+            .map_or(Color::from_rgb(0.5, 0.5, 0.5), color),
+        max_bytes,
+        truncated,
+    })
+}
+
+/// Highlighted text with line numbers.
+pub fn view<'a, Message: 'static>(
+    highlighted: &'a HighlightedText,
+    height: Length,
+) -> Element<'a, Message> {
+    let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+
+    let digits = highlighted.lines.len().to_string().len();
+    let mut column = widget::column::with_capacity(highlighted.lines.len());
+    for (i, line) in highlighted.lines.iter().enumerate() {
+        let mut spans = Vec::with_capacity(line.len() + 1);
+        spans.push(
+            span(format!("{:>digits$}  ", i + 1))
+                .color(highlighted.gutter)
+                .font(font::mono()),
+        );
+        for (text, color) in line.iter() {
+            spans.push(span(text.as_str()).color(*color).font(font::mono()));
+        }
+        column = column.push(rich_text(spans).size(12));
+    }
+
+    let background = highlighted.background;
+    widget::container(widget::scrollable(column.padding(space_xxs)).direction(
+        widget::scrollable::Direction::Both {
+            vertical: widget::scrollable::Scrollbar::default(),
+            horizontal: widget::scrollable::Scrollbar::default(),
+        },
+    ))
+    .width(Length::Fill)
+    .height(height)
+    .style(move |_theme| widget::container::Style {
+        background: Some(background.into()),
+        ..Default::default()
+    })
+    .into()
+}
@@ -5,10 +5,10 @@ use cosmic::{
 };
 use std::collections::HashMap;
 
-use crate::{app::Action, tab1};
+use crate::{app::Action, tab};
 
 //TODO: load from config
-pub fn key_binds(mode: &tab1::Mode) -> HashMap<KeyBind, Action> {
+pub fn key_binds(mode: &tab::Mode) -> HashMap<KeyBind, Action> {
     let mut key_binds = HashMap::new();
 
     macro_rules! bind {
@@ -24,7 +24,12 @@ pub fn key_binds(mode: &tab1::Mode) -> HashMap<KeyBind, Action> {
     }
 
     // Common keys
-    bind!([], Key::Named(Named::Space), Gallery);
+    bind!([], Key::Named(Named::Space), SelectToggle);
+    bind!([Shift], Key::Named(Named::Space), Gallery);
+    bind!([], Key::Named(Named::Insert), SelectToggleDown);
+    bind!([], Key::Character("*".into()), SelectInvert);
+    bind!([Shift], Key::Character("*".into()), SelectInvert);
+    bind!([], Key::Named(Named::Tab), SwapPanels);
     bind!([Shift], Key::Named(Named::Tab), SwapPanels);
     bind!([], Key::Named(Named::F2), F2Rename);
     bind!([], Key::Named(Named::F3), F3View);
@@ -60,45 +65,102 @@ pub fn key_binds(mode: &tab1::Mode) -> HashMap<KeyBind, Action> {
     bind!([Ctrl], Key::Character("_".into()), ZoomOut);
 
     // App-only keys
-    if matches!(mode, tab1::Mode::App) {
-        bind!([Ctrl], Key::Character("d".into()), AddToSidebar);
-        bind!([Ctrl], Key::Named(Named::Enter), OpenInNewTab);
+    if matches!(mode, tab::Mode::App) {
+        bind!([Ctrl, Shift], Key::Character("D".into()), AddToSidebar);
+        bind!([Ctrl], Key::Named(Named::Enter), InsertNameTerminal);
+        bind!([Ctrl, Shift], Key::Named(Named::Enter), InsertPathsTerminal);
         bind!([Ctrl], Key::Named(Named::F5), TabRescan);
+        bind!([Ctrl], Key::Named(Named::F6), FocusNextPane);
+        bind!([Alt], Key::Named(Named::F5), PackToOtherPane);
+        bind!([Alt], Key::Character("1".into()), TabActivateLeft);
+        bind!([Alt], Key::Character("2".into()), TabActivateRight);
+        bind!([Ctrl], Key::Character("`".into()), ToggleTerminal);
         bind!([Ctrl], Key::Character("r".into()), TabRescan);
         bind!([Ctrl], Key::Character(",".into()), Settings);
+        bind!([Ctrl], Key::Character("p".into()), QuickJump);
         bind!([Ctrl], Key::Character("w".into()), TabClose);
         bind!([Ctrl], Key::Character("s".into()), SwapPanels);
         bind!([Ctrl], Key::Character("t".into()), TabNew);
         bind!([Ctrl], Key::Named(Named::Tab), TabNext);
         bind!([Ctrl, Shift], Key::Named(Named::Tab), TabPrev);
-        bind!([Ctrl], Key::Character("q".into()), WindowClose);
+        bind!([Ctrl, Shift], Key::Named(Named::PageUp), TabMoveBackward);
+        bind!([Ctrl, Shift], Key::Named(Named::PageDown), TabMoveForward);
+        bind!([Ctrl, Shift], Key::Character("T".into()), TabReopen);
+        bind!([Ctrl], Key::Character("q".into()), QuickView);
         bind!([Ctrl], Key::Character("n".into()), WindowNew);
+        bind!([Ctrl], Key::Character("b".into()), AddBookmark);
+        // Ctrl and a digit open the bookmark with that shortcut
+        for digit in 1..=9 {
+            key_binds.insert(
+                KeyBind {
+                    modifiers: vec![Modifier::Ctrl],
+                    key: Key::Character(digit.to_string().into()),
+                },
+                Action::BookmarkShortcut(digit),
+            );
+        }
         //bind!([Ctrl], Key::Character("r".into()), TabReload);
     }
 
     // App and desktop only keys
-    if matches!(mode, tab1::Mode::App | tab1::Mode::Desktop) {
+    if matches!(mode, tab::Mode::App | tab::Mode::Desktop) {
         bind!([Ctrl], Key::Character("c".into()), Copy);
         bind!([Ctrl], Key::Character("x".into()), Cut);
+        bind!([Ctrl], Key::Character("d".into()), Duplicate);
         bind!([], Key::Named(Named::Delete), MoveToTrash);
+        bind!([], Key::Named(Named::ContextMenu), ContextMenu);
+        bind!([Shift], Key::Named(Named::F10), ContextMenu);
         bind!([Shift], Key::Named(Named::Enter), OpenInNewWindow);
         bind!([Ctrl], Key::Character("v".into()), Paste);
         bind!([], Key::Named(Named::F2), Rename);
     }
 
     // App and dialog only keys
-    if matches!(mode, tab1::Mode::App | tab1::Mode::Dialog(_)) {
+    if matches!(mode, tab::Mode::App | tab::Mode::Dialog(_)) {
         bind!([Ctrl], Key::Character("l".into()), EditLocation);
         bind!([Alt], Key::Named(Named::ArrowRight), HistoryNext);
         bind!([Alt], Key::Named(Named::ArrowLeft), HistoryPrevious);
         bind!([], Key::Named(Named::Backspace), HistoryPrevious);
         bind!([Alt], Key::Named(Named::ArrowUp), LocationUp);
+        bind!([Alt], Key::Named(Named::ArrowDown), PaneHistory);
+        bind!([Alt], Key::Named(Named::Home), GoHome);
         bind!([Ctrl], Key::Character("f".into()), SearchActivate);
     }
 
     key_binds
 }
 
+/// Result of the keys typed in the vi navigation mode
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ViKey {
+    Action(Action),
+    /// Start of a sequence like `gg`, wait for the next key
+    Pending,
+    /// Enter or leave the visual selection mode
+    ToggleVisual,
+}
+
+/// Layer of the vi navigation mode, checked before the regular key bindings.
+/// `keys` are the characters typed since the last complete sequence.
+pub fn key_binds_vi(keys: &str, visual: bool) -> Option<ViKey> {
+    let action = match (keys, visual) {
+        ("h", false) => Action::LocationUp,
+        ("j", _) => Action::ItemDown,
+        ("k", _) => Action::ItemUp,
+        ("l", false) => Action::Open,
+        ("gg", _) => Action::SelectFirst,
+        ("G", _) => Action::SelectLast,
+        ("yy", false) | ("y", true) => Action::Copy,
+        ("dd", false) | ("d", true) => Action::Cut,
+        ("p", false) => Action::Paste,
+        ("/", _) => Action::SearchActivate,
+        ("v", _) | ("V", _) => return Some(ViKey::ToggleVisual),
+        ("g", _) | ("y", false) | ("d", false) => return Some(ViKey::Pending),
+        _ => return None,
+    };
+    Some(ViKey::Action(action))
+}
+
 pub fn key_binds_terminal() -> HashMap<KeyBind, Action> {
     let mut key_binds = HashMap::new();
 
@@ -137,6 +199,14 @@ pub fn key_binds_terminal() -> HashMap<KeyBind, Action> {
     bind!([Ctrl], Key::Named(Named::Tab), TabNext);
     bind!([Ctrl, Shift], Key::Named(Named::Tab), TabPrev);
 
+    // Ctrl+F6 moves the focus to the file panes, Ctrl+` hides the terminal
+    bind!([Ctrl], Key::Named(Named::F6), FocusNextPane);
+    bind!([Ctrl], Key::Character("`".into()), ToggleTerminal);
+
+    // Ctrl+Shift+T and Ctrl+Shift+W open and close terminal tabs
+    bind!([Ctrl, Shift], Key::Character("T".into()), TermNew);
+    bind!([Ctrl, Shift], Key::Character("W".into()), TermClose);
+
     // Ctrl+0, Ctrl+-, and Ctrl+= are not special keys for terminals and are free to use
     bind!([Ctrl], Key::Character("-".into()), ZoomOut);
     bind!([Ctrl], Key::Character("=".into()), ZoomIn);
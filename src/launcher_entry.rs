@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Progress of the file operations on the icon in docks and taskbars, with the
+//! `com.canonical.Unity.LauncherEntry` signal that Plank, Dash to Dock, the KDE task
+//! manager and others listen to.
+
+use cosmic::Application;
+use std::collections::HashMap;
+use tokio::sync::OnceCell;
+use zbus::zvariant::Value;
+
+use crate::app::App;
+
+const INTERFACE: &str = "com.canonical.Unity.LauncherEntry";
+const PATH: &str = "/eu/fangornsrealm/commander/LauncherEntry";
+
+/// Docks match the entries by the sender too, so all updates use the same connection
+static CONNECTION: OnceCell<zbus::Connection> = OnceCell::const_new();
+
+/// Show the progress between 0 and 1 and the number of operations, or hide them with `None`
+/// and 0
+pub async fn update(progress_opt: Option<f32>, count: usize) -> zbus::Result<()> {
+    let connection = CONNECTION
+        .get_or_try_init(zbus::Connection::session)
+        .await?;
+    let app_uri = format!("application://{}.desktop", App::APP_ID);
+    let properties = HashMap::from([
+        (
+            "progress",
+            Value::from(f64::from(progress_opt.unwrap_or(0.0))),
+        ),
+        ("progress-visible", Value::from(progress_opt.is_some())),
+        ("count", Value::from(count as i64)),
+        ("count-visible", Value::from(count > 0)),
+    ]);
+    connection
+        .emit_signal(
+            None::<&str>,
+            PATH,
+            INTERFACE,
+            "Update",
+            &(app_uri, properties),
+        )
+        .await
+}
@@ -5,16 +5,36 @@ use cosmic::{app::Settings, iced::Limits};
 use std::{env, fs, path::PathBuf, process};
 
 use app::{App, Flags};
+use config::Config;
+use tab::Location;
+
+mod a11y;
 pub mod app;
+#[cfg(feature = "dbus")]
+mod appearance;
+mod checksum;
 pub mod clipboard;
-use config::Config;
 mod commanderpanegrid;
 pub mod config;
+mod desktop_entry;
 pub mod dialog;
+mod diff;
 pub mod dnd;
+mod editor;
+#[cfg(feature = "dbus")]
+mod file_manager1;
+mod frecency;
+mod hexview;
+mod highlight;
 mod key_bind;
+#[cfg(feature = "dbus")]
+mod launcher_entry;
+mod listing;
 mod localize;
+#[cfg(feature = "gstreamer")]
+mod media;
 mod menu;
+mod metadata;
 mod mime_app;
 pub mod mime_icon;
 mod mounter;
@@ -22,14 +42,27 @@ mod mouse_area;
 mod mouse_reporter;
 pub mod operation;
 mod pane_grid;
+#[cfg(feature = "pdf")]
+mod pdf;
+mod plugin;
+#[cfg(feature = "dbus")]
+mod portal;
+mod print;
+mod quick_action;
+mod recents;
+mod send_to;
+mod sound;
 mod spawn_detached;
-use tab1::Location;
-pub mod tab1;
-pub mod tab2;
+mod sync;
+pub mod tab;
+mod template;
+mod terminal;
 mod terminal_box;
 mod terminal_theme;
-mod terminal;
 mod thumbnailer;
+mod tool;
+mod transform;
+mod volume_trash;
 //pub mod terminal;
 
 pub(crate) fn err_str<T: ToString>(err: T) -> String {
@@ -68,7 +101,7 @@ pub fn desktop() -> Result<(), Box<dyn std::error::Error>> {
     let (config_handler, config) = Config::load();
 
     let mut settings = Settings::default();
-    settings = settings.theme(config.app_theme.theme());
+    settings = settings.theme(config.theme(None));
     settings = settings.size_limits(Limits::NONE.min_width(360.0).min_height(180.0));
     settings = settings.exit_on_close(false);
     settings = settings.transparent(true);
@@ -85,12 +118,37 @@ pub fn desktop() -> Result<(), Box<dyn std::error::Error>> {
         mode: app::Mode::Desktop,
         locations1,
         locations2,
+        selected: Vec::new(),
+        view: None,
+        show_settings: false,
     };
     cosmic::app::run::<App>(settings, flags)?;
 
     Ok(())
 }
 
+/// Absolute path of a command line argument, which is a path or a file URL
+fn arg_path(arg: &str) -> Option<PathBuf> {
+    //TODO: support more URLs
+    let path = match url::Url::parse(arg) {
+        Ok(url) => match url.to_file_path() {
+            Ok(path) => path,
+            Err(()) => {
+                log::warn!("invalid argument {:?}", arg);
+                return None;
+            }
+        },
+        Err(_) => PathBuf::from(arg),
+    };
+    match fs::canonicalize(&path) {
+        Ok(absolute) => Some(absolute),
+        Err(err) => {
+            log::warn!("failed to canonicalize {:?}: {}", path, err);
+            None
+        }
+    }
+}
+
 /// Runs application with these settings
 #[rustfmt::skip]
 pub fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -101,36 +159,105 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (config_handler, config) = Config::load();
 
     let mut daemonize = false;
-    let mut locations = Vec::new();
-    for arg in env::args().skip(1) {
-        let location = if &arg == "--no-daemon" {
-            daemonize = false;
-            continue;
-        } else if &arg == "--trash" {
-            Location::Trash
-        } else {
-            //TODO: support more URLs
-            let path = match url::Url::parse(&arg) {
-                Ok(url) => match url.to_file_path() {
-                    Ok(path) => path,
-                    Err(()) => {
-                        log::warn!("invalid argument {:?}", arg);
-                        continue;
+    let mut new_window = false;
+    let mut locations1 = Vec::new();
+    let mut locations2 = Vec::new();
+    // --tab opens a tab in the pane of the last --left or --right
+    let mut right = false;
+    let mut selected = Vec::new();
+    let mut view = None;
+    let mut show_settings = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--no-daemon" => daemonize = false,
+            "--new-window" => new_window = true,
+            "--trash" => locations1.push(Location::Trash),
+            "--grid" => view = Some(tab::View::Grid),
+            "--list" => view = Some(tab::View::List),
+            "--settings" => show_settings = true,
+            // Started by D-Bus activation to serve the file chooser portal
+            #[cfg(feature = "dbus")]
+            "--portal" => return portal::run(config.theme(None)),
+            // Runs a sync profile without opening a window
+            "--run-sync" => {
+                let Some(name) = args.next() else {
+                    eprintln!("missing profile name after {}", arg);
+                    process::exit(1);
+                };
+                let Some(profile) = config.sync_profiles.iter().find(|profile| profile.name == name) else {
+                    eprintln!("no sync profile named {:?}", name);
+                    process::exit(1);
+                };
+                match sync::run(profile, &operation::Controller::default()) {
+                    Ok(report) => {
+                        println!(
+                            "synced {:?}: {} files copied, {} items moved to the trash",
+                            name, report.copied, report.deleted
+                        );
+                        process::exit(0);
+                    }
+                    Err(err) => {
+                        eprintln!("failed to sync {:?}: {}", name, err);
+                        process::exit(1);
                     }
-                },
-                Err(_) => PathBuf::from(arg),
-            };
-            match fs::canonicalize(&path) {
-                Ok(absolute) => Location::Path(absolute),
-                Err(err) => {
-                    log::warn!("failed to canonicalize {:?}: {}", path, err);
+                }
+            }
+            "--left" | "--right" | "--tab" | "--select" => {
+                let Some(value) = args.next() else {
+                    eprintln!("missing path after {}", arg);
+                    process::exit(1);
+                };
+                let Some(path) = arg_path(&value) else {
                     continue;
+                };
+                match arg.as_str() {
+                    "--select" => selected.push(path),
+                    "--left" => {
+                        right = false;
+                        locations1.push(Location::Path(path));
+                    }
+                    "--right" => {
+                        right = true;
+                        locations2.push(Location::Path(path));
+                    }
+                    _ if right => locations2.push(Location::Path(path)),
+                    _ => locations1.push(Location::Path(path)),
                 }
             }
-        };
-        locations.push(location);
+            _ => {
+                if let Some(path) = arg_path(&arg) {
+                    locations1.push(Location::Path(path));
+                }
+            }
+        }
     }
 
+    // Paths are opened in new tabs of a running instance, unless a new window is requested
+    // or the paths come with options for the window
+    #[cfg(feature = "dbus")]
+    if !new_window
+        && !locations1.is_empty()
+        && locations2.is_empty()
+        && selected.is_empty()
+        && view.is_none()
+        && !show_settings
+    {
+        let paths: Vec<PathBuf> = locations1.iter().filter_map(|location| match location {
+            Location::Path(path) => Some(path.clone()),
+            _ => None,
+        }).collect();
+        if paths.len() == locations1.len() {
+            let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+            match runtime.block_on(file_manager1::show_folders(&paths)) {
+                Ok(()) => return Ok(()),
+                Err(err) => log::info!("no running instance to open {:?} in: {}", paths, err),
+            }
+        }
+    }
+    #[cfg(not(feature = "dbus"))]
+    let _ = new_window;
+
     if daemonize {
         #[cfg(all(unix, not(target_os = "redox")))]
         match fork::daemon(true, true) {
@@ -144,7 +271,7 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     let mut settings = Settings::default();
-    settings = settings.theme(config.app_theme.theme());
+    settings = settings.theme(config.theme(None));
     settings = settings.size_limits(Limits::NONE.min_width(360.0).min_height(180.0));
     settings = settings.exit_on_close(false);
 
@@ -152,8 +279,11 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
         config_handler,
         config,
         mode: app::Mode::App,
-        locations1: locations,
-        locations2: Vec::new(),
+        locations1,
+        locations2,
+        selected,
+        view,
+        show_settings,
     };
     cosmic::app::run::<App>(settings, flags)?;
 
@@ -0,0 +1,330 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Export of folder listings as plain text, CSV or JSON, for inventories and audits
+
+use std::{fs, path::Path, time::SystemTime};
+use walkdir::WalkDir;
+
+use crate::{
+    checksum::{self, ChecksumKind},
+    err_str,
+    operation::Controller,
+};
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ListingFormat {
+    #[default]
+    Text,
+    Csv,
+    Json,
+}
+
+impl ListingFormat {
+    pub fn all() -> &'static [Self] {
+        &[Self::Text, Self::Csv, Self::Json]
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl AsRef<str> for ListingFormat {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Text => "TXT",
+            Self::Csv => "CSV",
+            Self::Json => "JSON",
+        }
+    }
+}
+
+/// Columns written besides the name
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ListingColumns {
+    pub size: bool,
+    pub modified: bool,
+    /// SHA-256 of the files, which reads all of them
+    pub checksum: bool,
+}
+
+impl Default for ListingColumns {
+    fn default() -> Self {
+        Self {
+            size: true,
+            modified: true,
+            checksum: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    /// Path relative to the listed folder, folders end with a slash
+    name: String,
+    size: Option<u64>,
+    modified: Option<SystemTime>,
+    checksum: Option<String>,
+}
+
+fn scan(
+    dir: &Path,
+    recursive: bool,
+    columns: ListingColumns,
+    controller: &Controller,
+) -> Result<Vec<Entry>, String> {
+    let walker = WalkDir::new(dir)
+        .min_depth(1)
+        .max_depth(if recursive { usize::MAX } else { 1 })
+        .sort_by_file_name();
+    let mut entries = Vec::new();
+    for entry in walker {
+        controller.check()?;
+        let entry = entry.map_err(err_str)?;
+        let metadata = entry.metadata().map_err(err_str)?;
+        let Ok(relative) = entry.path().strip_prefix(dir) else {
+            continue;
+        };
+        let mut name = relative.to_string_lossy().to_string();
+        if metadata.is_dir() {
+            name.push('/');
+        }
+        let checksum = if columns.checksum && metadata.is_file() {
+            checksum::calculate(entry.path(), controller)?
+                .get(ChecksumKind::Sha256)
+                .map(str::to_string)
+        } else {
+            None
+        };
+        entries.push(Entry {
+            name,
+            size: (!metadata.is_dir()).then_some(metadata.len()),
+            modified: metadata.modified().ok(),
+            checksum,
+        });
+    }
+    Ok(entries)
+}
+
+fn format_modified(modified: SystemTime) -> String {
+    chrono::DateTime::<chrono::Local>::from(modified)
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn json_string(string: &str) -> String {
+    let mut json = String::with_capacity(string.len() + 2);
+    json.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn format(entries: &[Entry], format: ListingFormat, columns: ListingColumns) -> String {
+    let mut output = String::new();
+    match format {
+        ListingFormat::Text => {
+            // The name comes last so that names with spaces stay readable
+            let size_width = entries
+                .iter()
+                .filter_map(|entry| entry.size)
+                .map(|size| size.to_string().len())
+                .max()
+                .unwrap_or(0);
+            for entry in entries {
+                let mut fields = Vec::new();
+                if columns.size {
+                    fields.push(format!(
+                        "{:>size_width$}",
+                        entry.size.map(|size| size.to_string()).unwrap_or_default()
+                    ));
+                }
+                if columns.modified {
+                    fields.push(entry.modified.map(format_modified).unwrap_or_default());
+                }
+                if columns.checksum {
+                    fields.push(format!(
+                        "{:64}",
+                        entry.checksum.as_deref().unwrap_or_default()
+                    ));
+                }
+                fields.push(entry.name.clone());
+                output.push_str(&fields.join("  "));
+                output.push('\n');
+            }
+        }
+        ListingFormat::Csv => {
+            let mut header = vec!["name"];
+            if columns.size {
+                header.push("size");
+            }
+            if columns.modified {
+                header.push("modified");
+            }
+            if columns.checksum {
+                header.push("sha256");
+            }
+            output.push_str(&header.join(","));
+            output.push('\n');
+            for entry in entries {
+                let mut fields = vec![csv_field(&entry.name)];
+                if columns.size {
+                    fields.push(entry.size.map(|size| size.to_string()).unwrap_or_default());
+                }
+                if columns.modified {
+                    fields.push(entry.modified.map(format_modified).unwrap_or_default());
+                }
+                if columns.checksum {
+                    fields.push(entry.checksum.clone().unwrap_or_default());
+                }
+                output.push_str(&fields.join(","));
+                output.push('\n');
+            }
+        }
+        ListingFormat::Json => {
+            output.push('[');
+            for (i, entry) in entries.iter().enumerate() {
+                let mut fields = vec![format!("\"name\": {}", json_string(&entry.name))];
+                if columns.size {
+                    fields.push(format!(
+                        "\"size\": {}",
+                        entry
+                            .size
+                            .map_or_else(|| "null".to_string(), |size| size.to_string())
+                    ));
+                }
+                if columns.modified {
+                    fields.push(format!(
+                        "\"modified\": {}",
+                        entry.modified.map_or_else(
+                            || "null".to_string(),
+                            |modified| json_string(&format_modified(modified))
+                        )
+                    ));
+                }
+                if columns.checksum {
+                    fields.push(format!(
+                        "\"sha256\": {}",
+                        entry
+                            .checksum
+                            .as_deref()
+                            .map_or_else(|| "null".to_string(), json_string)
+                    ));
+                }
+                if i > 0 {
+                    output.push(',');
+                }
+                output.push_str("\n  {");
+                output.push_str(&fields.join(", "));
+                output.push('}');
+            }
+            output.push_str("\n]\n");
+        }
+    }
+    output
+}
+
+/// Listing of the items directly in `dir` as plain text, for printing
+pub fn text(dir: &Path, controller: &Controller) -> Result<String, String> {
+    let columns = ListingColumns::default();
+    let entries = scan(dir, false, columns, controller)?;
+    Ok(format(&entries, ListingFormat::Text, columns))
+}
+
+/// Write the listing of `dir` to `to`, returning the number of listed items
+pub fn export(
+    dir: &Path,
+    to: &Path,
+    recursive: bool,
+    listing_format: ListingFormat,
+    columns: ListingColumns,
+    controller: &Controller,
+) -> Result<usize, String> {
+    let mut entries = scan(dir, recursive, columns, controller)?;
+    // The listing does not list itself
+    if let Ok(relative) = to.strip_prefix(dir) {
+        let name = relative.to_string_lossy();
+        entries.retain(|entry| entry.name != name);
+    }
+    fs::write(to, format(&entries, listing_format, columns))
+        .map_err(|err| format!("failed to write {:?}: {}", to, err))?;
+    Ok(entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io};
+
+    use super::{export, ListingColumns, ListingFormat};
+    use crate::operation::Controller;
+
+    #[test]
+    fn export_lists_nested_items() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::create_dir(dir.path().join("sub"))?;
+        fs::write(dir.path().join("sub").join("a \"b\", c"), "abc")?;
+        fs::write(dir.path().join("notes.txt"), "notes")?;
+        let columns = ListingColumns {
+            size: true,
+            modified: false,
+            checksum: true,
+        };
+
+        let to = dir.path().join("listing.csv");
+        let count = export(
+            dir.path(),
+            &to,
+            true,
+            ListingFormat::Csv,
+            columns,
+            &Controller::default(),
+        )
+        .unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(
+            fs::read_to_string(&to)?,
+            "name,size,sha256\n\
+             notes.txt,5,ab5aa97074c454a0632057e704220d9a6678fbf773a0a5806fc09b8173b07309\n\
+             sub/,,\n\
+             \"sub/a \"\"b\"\", c\",3,ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\n"
+        );
+
+        let to = dir.path().join("listing.json");
+        export(
+            dir.path(),
+            &to,
+            false,
+            ListingFormat::Json,
+            ListingColumns::default(),
+            &Controller::default(),
+        )
+        .unwrap();
+        let json = fs::read_to_string(&to)?;
+        assert!(json.starts_with("[\n  {\"name\": \"listing.csv\", \"size\": "));
+        assert!(json.contains("{\"name\": \"sub/\", \"size\": null, \"modified\": \""));
+        assert!(!json.contains("listing.json"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,334 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    cosmic_theme,
+    iced::{Alignment, Length},
+    theme, widget, Element,
+};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use mime_guess::{mime, Mime};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::fl;
+
+/// Number of audio levels kept for the waveform
+const WAVEFORM_LEN: usize = 200;
+/// Interval of audio level messages
+const LEVEL_INTERVAL: Duration = Duration::from_millis(50);
+
+pub fn is_media(mime: &Mime) -> bool {
+    mime.type_() == mime::AUDIO || mime.type_() == mime::VIDEO
+}
+
+fn format_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Audio and video player for the gallery, built on playbin.
+///
+/// Video frames are converted to RGBA by an appsink and audio levels are
+/// collected from a level element to draw a simple waveform.
+pub struct MediaPlayer {
+    path: PathBuf,
+    playbin: gst::Element,
+    frame: Arc<Mutex<Option<widget::image::Handle>>>,
+    levels: VecDeque<f32>,
+    tags: Vec<(String, String)>,
+    error_opt: Option<String>,
+    playing: bool,
+}
+
+impl MediaPlayer {
+    pub fn new(path: &Path) -> Result<Self, String> {
+        gst::init().map_err(|err| format!("failed to initialize gstreamer: {}", err))?;
+
+        let uri = url::Url::from_file_path(path)
+            .map_err(|()| format!("failed to convert {:?} to URI", path))?;
+        let playbin = gst::ElementFactory::make("playbin")
+            .property("uri", uri.as_str())
+            .build()
+            .map_err(|err| format!("failed to create playbin: {}", err))?;
+
+        let frame = Arc::new(Mutex::new(None));
+        let appsink = gst_app::AppSink::builder()
+            .caps(
+                &gst::Caps::builder("video/x-raw")
+                    .field("format", "RGBA")
+                    .field("pixel-aspect-ratio", gst::Fraction::new(1, 1))
+                    .build(),
+            )
+            .max_buffers(1)
+            .drop(true)
+            .build();
+        {
+            let frame = frame.clone();
+            appsink.set_callbacks(
+                gst_app::AppSinkCallbacks::builder()
+                    .new_sample(move |sink| {
+                        let sample = sink.pull_sample().map_err(|_| gst::FlowError::Eos)?;
+                        let structure = sample
+                            .caps()
+                            .and_then(|caps| caps.structure(0))
+                            .ok_or(gst::FlowError::Error)?;
+                        let width = structure
+                            .get::<i32>("width")
+                            .map_err(|_| gst::FlowError::Error)?;
+                        let height = structure
+                            .get::<i32>("height")
+                            .map_err(|_| gst::FlowError::Error)?;
+                        let buffer = sample.buffer().ok_or(gst::FlowError::Error)?;
+                        let map = buffer.map_readable().map_err(|_| gst::FlowError::Error)?;
+                        *frame.lock().unwrap() = Some(widget::image::Handle::from_rgba(
+                            width as u32,
+                            height as u32,
+                            map.as_slice().to_vec(),
+                        ));
+                        Ok(gst::FlowSuccess::Ok)
+                    })
+                    .build(),
+            );
+        }
+        playbin.set_property("video-sink", &appsink);
+
+        match gst::ElementFactory::make("level")
+            .property("post-messages", true)
+            .property("interval", LEVEL_INTERVAL.as_nanos() as u64)
+            .build()
+        {
+            Ok(level) => playbin.set_property("audio-filter", &level),
+            Err(err) => log::warn!("failed to create level element: {}", err),
+        }
+
+        playbin
+            .set_state(gst::State::Playing)
+            .map_err(|err| format!("failed to play {:?}: {}", path, err))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            playbin,
+            frame,
+            levels: VecDeque::with_capacity(WAVEFORM_LEN),
+            tags: Vec::new(),
+            error_opt: None,
+            playing: true,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn set_playing(&mut self, playing: bool) {
+        let state = if playing {
+            gst::State::Playing
+        } else {
+            gst::State::Paused
+        };
+        match self.playbin.set_state(state) {
+            Ok(_) => self.playing = playing,
+            Err(err) => log::warn!("failed to set {:?} to {:?}: {}", self.path, state, err),
+        }
+    }
+
+    pub fn position(&self) -> Option<Duration> {
+        self.playbin
+            .query_position::<gst::ClockTime>()
+            .map(|time| Duration::from_nanos(time.nseconds()))
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        self.playbin
+            .query_duration::<gst::ClockTime>()
+            .map(|time| Duration::from_nanos(time.nseconds()))
+    }
+
+    pub fn seek(&mut self, secs: f64) {
+        let time = gst::ClockTime::from_nseconds((secs.max(0.0) * 1_000_000_000.0) as u64);
+        if let Err(err) = self
+            .playbin
+            .seek_simple(gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT, time)
+        {
+            log::warn!("failed to seek {:?} to {}: {}", self.path, secs, err);
+        }
+    }
+
+    /// Handle pending bus messages, should be called regularly while playing
+    pub fn poll(&mut self) {
+        let Some(bus) = self.playbin.bus() else {
+            return;
+        };
+        while let Some(message) = bus.pop() {
+            match message.view() {
+                gst::MessageView::Element(element) => {
+                    let Some(structure) = element.structure() else {
+                        continue;
+                    };
+                    if structure.name() != "level" {
+                        continue;
+                    }
+                    let Ok(rms) = structure.get::<gst::glib::ValueArray>("rms") else {
+                        continue;
+                    };
+                    // Average the channels and convert from decibels
+                    let (sum, count) = rms
+                        .iter()
+                        .filter_map(|value| value.get::<f64>().ok())
+                        .fold((0.0, 0), |(sum, count), db| (sum + db, count + 1));
+                    if count > 0 {
+                        let level = 10f64.powf(sum / count as f64 / 20.0) as f32;
+                        if self.levels.len() >= WAVEFORM_LEN {
+                            self.levels.pop_front();
+                        }
+                        self.levels.push_back(level.clamp(0.0, 1.0));
+                    }
+                }
+                gst::MessageView::Tag(tag) => {
+                    let tags = tag.tags();
+                    let mut push = |name: String, value_opt: Option<String>| {
+                        if let Some(value) = value_opt {
+                            if let Some(entry) = self.tags.iter_mut().find(|x| x.0 == name) {
+                                entry.1 = value;
+                            } else {
+                                self.tags.push((name, value));
+                            }
+                        }
+                    };
+                    push(
+                        fl!("media-title"),
+                        tags.get::<gst::tags::Title>().map(|x| x.get().to_string()),
+                    );
+                    push(
+                        fl!("media-artist"),
+                        tags.get::<gst::tags::Artist>().map(|x| x.get().to_string()),
+                    );
+                    push(
+                        fl!("media-album"),
+                        tags.get::<gst::tags::Album>().map(|x| x.get().to_string()),
+                    );
+                    push(
+                        fl!("media-video-codec"),
+                        tags.get::<gst::tags::VideoCodec>()
+                            .map(|x| x.get().to_string()),
+                    );
+                    push(
+                        fl!("media-audio-codec"),
+                        tags.get::<gst::tags::AudioCodec>()
+                            .map(|x| x.get().to_string()),
+                    );
+                }
+                gst::MessageView::Eos(_) => {
+                    self.set_playing(false);
+                    self.seek(0.0);
+                }
+                gst::MessageView::Error(err) => {
+                    log::warn!("failed to play {:?}: {}", self.path, err.error());
+                    self.error_opt = Some(err.error().to_string());
+                    self.playing = false;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn view<'a, Message: Clone + 'static>(
+        &'a self,
+        on_play_pause: Message,
+        on_seek: impl Fn(f64) -> Message + 'a,
+    ) -> Element<'a, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let mut column = widget::column::with_capacity(4)
+            .spacing(space_s)
+            .align_x(Alignment::Center);
+
+        if let Some(handle) = self.frame.lock().unwrap().clone() {
+            column = column.push(
+                widget::container(widget::image(handle))
+                    .center(Length::Fill)
+                    .height(Length::Fill),
+            );
+        } else {
+            // Audio only, show the recent levels as a waveform
+            let height = 128.0;
+            let mut waveform = widget::row::with_capacity(WAVEFORM_LEN)
+                .spacing(1)
+                .height(Length::Fixed(height))
+                .align_y(Alignment::Center);
+            for level in self.levels.iter() {
+                waveform = waveform.push(
+                    widget::container(widget::Space::new(
+                        Length::Fixed(2.0),
+                        Length::Fixed((level * height).max(1.0)),
+                    ))
+                    .class(theme::Container::Primary),
+                );
+            }
+            column = column.push(widget::container(waveform).center(Length::Fill));
+        }
+
+        let mut details = widget::column::with_capacity(self.tags.len() + 1);
+        for (name, value) in self.tags.iter() {
+            details = details.push(widget::text::body(format!("{}: {}", name, value)));
+        }
+        if let Some(error) = &self.error_opt {
+            details = details.push(widget::text::body(error.clone()));
+        }
+        column = column.push(details);
+
+        let position = self.position().unwrap_or_default();
+        let duration = self.duration().unwrap_or_default();
+        column = column.push(
+            widget::row::with_children(vec![
+                widget::button::icon(widget::icon::from_name(if self.playing {
+                    "media-playback-pause-symbolic"
+                } else {
+                    "media-playback-start-symbolic"
+                }))
+                .on_press(on_play_pause)
+                .into(),
+                widget::slider(
+                    0.0..=duration.as_secs_f64().max(1.0),
+                    position.as_secs_f64(),
+                    on_seek,
+                )
+                .into(),
+                widget::text::body(format!(
+                    "{} / {}",
+                    format_time(position),
+                    format_time(duration)
+                ))
+                .into(),
+            ])
+            .align_y(Alignment::Center)
+            .spacing(space_xxs),
+        );
+
+        column.into()
+    }
+}
+
+impl Drop for MediaPlayer {
+    fn drop(&mut self) {
+        if let Err(err) = self.playbin.set_state(gst::State::Null) {
+            log::warn!("failed to stop {:?}: {}", self.path, err);
+        }
+    }
+}
@@ -5,21 +5,25 @@ use cosmic::{
     theme,
     widget::{
         self, button, column, container, divider, horizontal_space,
-        menu::{self, key_bind::KeyBind, ItemHeight, ItemWidth, MenuBar},
+        menu::{self, action::MenuAction, key_bind::KeyBind, ItemHeight, ItemWidth, MenuBar},
         text, Row,
     },
     Element,
 };
 use i18n_embed::LanguageLoader;
 use mime_guess::Mime;
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 use crate::{
-    app::{Action, Message},
-    config::Config,
+    app::{Action, Message, PaneType},
+    checksum,
+    config::{self, Config},
     fl,
-    tab1::{self, HeadingOptions as HeadingOptions1, Location as Location1, LocationMenuAction as LocationMenuAction1, Tab as Tab1},
-    tab2::{self, HeadingOptions as HeadingOptions2, Location as Location2, LocationMenuAction as LocationMenuAction2, Tab as Tab2},
+    mounter::{self, MounterItem},
+    plugin,
+    quick_action::QuickAction,
+    tab::{self, HeadingOptions, Location, LocationMenuAction, Tab},
+    template, terminal_theme, tool,
 };
 
 macro_rules! menu_button {
@@ -49,10 +53,10 @@ fn menu_button_optional(
     }
 }
 
-pub fn context_menu1<'a>(
-    tab: &Tab1,
+pub fn context_menu<'a>(
+    tab: &Tab,
     key_binds: &HashMap<KeyBind, Action>,
-) -> Element<'a, tab1::Message> {
+) -> Element<'a, tab::Message> {
     let find_key = |action: &Action| -> String {
         for (key_bind, key_action) in key_binds.iter() {
             if action == key_action {
@@ -62,10 +66,12 @@ pub fn context_menu1<'a>(
         String::new()
     };
 
-    let menu_item = |label, action| {
+    // Actions that change files are disabled in read-only panes
+    let menu_item = |label, action: Action| {
         let key = find_key(&action);
+        let enabled = !tab.config.read_only || !action.changes_files();
         menu_button!(text::body(label), horizontal_space(), text::body(key))
-            .on_press(tab1::Message::ContextAction(action))
+            .on_press_maybe(enabled.then_some(tab::Message::ContextAction(action)))
     };
 
     let (sort_name, sort_direction, _) = tab.sort_options();
@@ -80,7 +86,10 @@ pub fn context_menu1<'a>(
                     _ => "",
                 }
             ),
-            Action::ToggleSortLeft(variant),
+            match tab.pane {
+                PaneType::RightPane => Action::ToggleSortRight(variant),
+                _ => Action::ToggleSortLeft(variant),
+            },
         )
         .into()
     };
@@ -89,7 +98,13 @@ pub fn context_menu1<'a>(
     let mut selected = 0;
     let mut selected_trash_only = false;
     let mut selected_desktop_entry = None;
+    let mut selected_sums_file = false;
+    let mut selected_color_scheme = false;
+    let mut selected_runnable = false;
+    let mut selected_binary = false;
+    let mut selected_container = false;
     let mut selected_types: Vec<Mime> = vec![];
+    let mut selected_plugin_items = Vec::new();
     if let Some(items) = tab.items_opt() {
         for item in items.iter() {
             if item.selected {
@@ -98,13 +113,22 @@ pub fn context_menu1<'a>(
                     selected_dir += 1;
                 }
                 match &item.location_opt {
-                    Some(Location1::Trash) => selected_trash_only = true,
-                    Some(Location1::Path(path)) => {
+                    Some(Location::Trash) => selected_trash_only = true,
+                    Some(Location::Path(path)) => {
+                        selected_plugin_items.push((path, &item.mime));
                         if selected == 1
                             && path.extension().and_then(|s| s.to_str()) == Some("desktop")
                         {
                             selected_desktop_entry = Some(&**path);
                         }
+                        selected_sums_file = selected == 1 && checksum::is_sums_file(path);
+                        selected_color_scheme =
+                            selected == 1 && terminal_theme::is_color_scheme_file(path);
+                        selected_runnable =
+                            selected == 1 && tool::run_command(path, &item.mime).is_some();
+                        selected_binary = selected == 1 && tool::is_binary(&item.mime);
+                        selected_container =
+                            selected == 1 && MounterItem::container(path).is_some();
                     }
                     _ => (),
                 }
@@ -115,6 +139,11 @@ pub fn context_menu1<'a>(
     selected_types.sort_unstable();
     selected_types.dedup();
     selected_trash_only = selected_trash_only && selected == 1;
+    selected_sums_file = selected_sums_file && selected == 1;
+    selected_color_scheme = selected_color_scheme && selected == 1;
+    selected_runnable = selected_runnable && selected == 1;
+    selected_binary = selected_binary && selected == 1;
+    selected_container = selected_container && selected == 1;
     // Parse the desktop entry if it is the only selection
     #[cfg(feature = "desktop")]
     let selected_desktop_entry = selected_desktop_entry.and_then(|path| {
@@ -131,12 +160,12 @@ pub fn context_menu1<'a>(
     let mut children: Vec<Element<_>> = Vec::new();
     match (&tab.mode, &tab.location) {
         (
-            tab1::Mode::App | tab1::Mode::Desktop,
-            Location1::Desktop(..) | Location1::Path(..) | Location1::Search(..) | Location1::Recents,
+            tab::Mode::App | tab::Mode::Desktop,
+            Location::Desktop(..) | Location::Path(..) | Location::Search(..) | Location::Recents,
         ) => {
             if selected_trash_only {
                 children.push(menu_item(fl!("open"), Action::Open).into());
-                if tab1::trash_entries() > 0 {
+                if tab::trash_entries() > 0 {
                     children.push(menu_item(fl!("empty-trash"), Action::EmptyTrash).into());
                 }
             } else if let Some(entry) = selected_desktop_entry {
@@ -162,23 +191,41 @@ pub fn context_menu1<'a>(
                     if selected_dir == 1 {
                         children
                             .push(menu_item(fl!("open-in-terminal"), Action::OpenTerminal).into());
+                    } else if selected_runnable {
+                        children
+                            .push(menu_item(fl!("run-in-terminal"), Action::RunInTerminal).into());
+                    }
+                    if selected_binary {
+                        children
+                            .push(menu_item(fl!("create-launcher"), Action::CreateLauncher).into());
                     }
                 }
-                if matches!(tab.location, Location1::Search(..) | Location1::Recents) {
+                if matches!(tab.location, Location::Search(..) | Location::Recents) {
                     children.push(
                         menu_item(fl!("open-item-location"), Action::OpenItemLocation).into(),
                     );
+                    if selected > 1 {
+                        children.push(
+                            menu_item(fl!("show-all-in-folder"), Action::ShowAllInFolder).into(),
+                        );
+                    }
                 }
                 // All selected items are directories
-                if selected == selected_dir && matches!(tab.mode, tab1::Mode::App) {
+                if selected == selected_dir && matches!(tab.mode, tab::Mode::App) {
                     children.push(menu_item(fl!("open-in-new-tab"), Action::OpenInNewTab).into());
                     children
                         .push(menu_item(fl!("open-in-new-window"), Action::OpenInNewWindow).into());
+                    children.push(
+                        menu_item(fl!("open-as-administrator"), Action::OpenAsAdministrator).into(),
+                    );
                 }
                 children.push(divider::horizontal::light().into());
                 children.push(menu_item(fl!("rename"), Action::Rename).into());
                 children.push(menu_item(fl!("cut"), Action::Cut).into());
                 children.push(menu_item(fl!("copy"), Action::Copy).into());
+                children.push(menu_item(fl!("duplicate"), Action::Duplicate).into());
+                children.push(menu_item(fl!("copy-paths"), Action::CopyPaths).into());
+                children.push(menu_item(fl!("copy-names"), Action::CopyNames).into());
 
                 children.push(divider::horizontal::light().into());
                 let supported_archive_types = [
@@ -202,340 +249,70 @@ pub fn context_menu1<'a>(
                 if selected_types.is_empty() {
                     children.push(menu_item(fl!("extract-here"), Action::ExtractHere).into());
                 }
-                children.push(menu_item(fl!("compress"), Action::Compress).into());
-                children.push(divider::horizontal::light().into());
-
-                //TODO: Print?
-                children.push(menu_item(fl!("show-details"), Action::Preview).into());
-                if matches!(tab.mode, tab1::Mode::App) {
-                    children.push(divider::horizontal::light().into());
-                    children.push(menu_item(fl!("add-to-sidebar"), Action::AddToSidebar).into());
-                }
-                children.push(divider::horizontal::light().into());
-                children.push(menu_item(fl!("move-to-trash"), Action::MoveToTrash).into());
-                children.push(divider::horizontal::light().into());
-                children.push(menu_item(fl!("new-tab"), Action::TabNew).into());
-                children.push(menu_item(fl!("copy-tab"), Action::CopyTab).into());
-                children.push(menu_item(fl!("move-tab"), Action::MoveTab).into());
-                // zoom does not work!
-                children.push(divider::horizontal::light().into());
-                children.push(menu_item(fl!("zoom-in"), Action::ZoomIn).into());
-                children.push(menu_item(fl!("default-size"), Action::ZoomDefault).into());                
-                children.push(menu_item(fl!("zoom-out"), Action::ZoomOut).into());
-                children.push(divider::horizontal::light().into());
-                children.push(menu_item(fl!("grid-view"), Action::TabViewGrid).into());
-                children.push(menu_item(fl!("list-view"), Action::TabViewList).into());
-                children.push(divider::horizontal::light().into());
-                // TODO: Nested menu
-                children.push(sort_item(fl!("sort-by-name"), HeadingOptions1::Name));
-                children.push(sort_item(fl!("sort-by-modified"), HeadingOptions1::Modified));
-                children.push(sort_item(fl!("sort-by-size"), HeadingOptions1::Size));
-            } else {
-                //TODO: need better designs for menu with no selection
-                //TODO: have things like properties but they apply to the folder?
-                children.push(menu_item(fl!("new-folder"), Action::NewFolder).into());
-                children.push(menu_item(fl!("new-file"), Action::NewFile).into());
-                children.push(menu_item(fl!("open-in-terminal"), Action::OpenTerminal).into());
-                children.push(divider::horizontal::light().into());
-                if tab.mode.multiple() {
-                    children.push(menu_item(fl!("select-all"), Action::SelectAll).into());
-                }
-                children.push(menu_item(fl!("paste"), Action::Paste).into());
-
-                //TODO: only show if cosmic-settings is found?
-                if matches!(tab.mode, tab1::Mode::Desktop) {
-                    children.push(divider::horizontal::light().into());
-                    children.push(
-                        menu_item(fl!("change-wallpaper"), Action::CosmicSettingsWallpaper).into(),
-                    );
-                    children.push(
-                        menu_item(fl!("desktop-appearance"), Action::CosmicSettingsAppearance)
-                            .into(),
-                    );
-                    children.push(
-                        menu_item(fl!("display-settings"), Action::CosmicSettingsDisplays).into(),
-                    );
-                }
-                // zoom does not work!
-                children.push(divider::horizontal::light().into());
-                children.push(menu_item(fl!("zoom-in"), Action::ZoomIn).into());
-                children.push(menu_item(fl!("default-size"), Action::ZoomDefault).into());                
-                children.push(menu_item(fl!("zoom-out"), Action::ZoomOut).into());
-                children.push(divider::horizontal::light().into());
-                children.push(menu_item(fl!("grid-view"), Action::TabViewGrid).into());
-                children.push(menu_item(fl!("list-view"), Action::TabViewList).into());
-                children.push(divider::horizontal::light().into());
-                children.push(menu_item(fl!("new-tab"), Action::TabNew).into());
-                children.push(menu_item(fl!("copy-tab"), Action::CopyTab).into());
-                children.push(menu_item(fl!("move-tab"), Action::MoveTab).into());
-
-                children.push(divider::horizontal::light().into());
-                // TODO: Nested menu
-                children.push(sort_item(fl!("sort-by-name"), HeadingOptions1::Name));
-                children.push(sort_item(fl!("sort-by-modified"), HeadingOptions1::Modified));
-                children.push(sort_item(fl!("sort-by-size"), HeadingOptions1::Size));
-                if matches!(tab.location, Location1::Desktop(..)) {
-                    children.push(divider::horizontal::light().into());
-                    children.push(
-                        menu_item(fl!("desktop-view-options"), Action::DesktopViewOptions).into(),
-                    );
+                if selected_dir == 0 && (selected == 1 || selected == 2) {
+                    children.push(menu_item(fl!("compare-files"), Action::Compare).into());
                 }
-            }
-        }
-        (
-            tab1::Mode::Dialog(dialog_kind),
-            Location1::Desktop(..) | Location1::Path(..) | Location1::Search(..) | Location1::Recents,
-        ) => {
-            if selected > 0 {
-                if selected_dir == 1 && selected == 1 || selected_dir == 0 {
-                    children.push(menu_item(fl!("open"), Action::Open).into());
+                children.push(menu_item(fl!("compress"), Action::Compress).into());
+                children.push(menu_item(fl!("pack-to-other-pane"), Action::PackToOtherPane).into());
+                children.push(menu_item(fl!("add-to-archive"), Action::AddToArchive).into());
+                if selected_sums_file {
+                    children
+                        .push(menu_item(fl!("verify-checksums"), Action::VerifyChecksums).into());
                 }
-                if matches!(tab.location, Location1::Search(..) | Location1::Recents) {
+                if selected_color_scheme {
                     children.push(
-                        menu_item(fl!("open-item-location"), Action::OpenItemLocation).into(),
+                        menu_item(fl!("import-color-scheme"), Action::ImportColorScheme).into(),
                     );
                 }
-                children.push(divider::horizontal::light().into());
-                children.push(menu_item(fl!("show-details"), Action::Preview).into());
-            } else {
-                if dialog_kind.save() {
-                    children.push(menu_item(fl!("new-folder"), Action::NewFolder).into());
-                }
-                if tab.mode.multiple() {
-                    children.push(menu_item(fl!("select-all"), Action::SelectAll).into());
-                }
-                if !children.is_empty() {
-                    children.push(divider::horizontal::light().into());
-                }
-                children.push(sort_item(fl!("sort-by-name"), HeadingOptions1::Name));
-                children.push(sort_item(fl!("sort-by-modified"), HeadingOptions1::Modified));
-                children.push(sort_item(fl!("sort-by-size"), HeadingOptions1::Size));
-            }
-        }
-        (_, Location1::Network(..)) => {
-            if selected > 0 {
-                if selected_dir == 1 && selected == 1 || selected_dir == 0 {
-                    children.push(menu_item(fl!("open"), Action::Open).into());
-                }
-            } else {
-                if tab.mode.multiple() {
-                    children.push(menu_item(fl!("select-all"), Action::SelectAll).into());
-                }
-                if !children.is_empty() {
-                    children.push(divider::horizontal::light().into());
+                if selected_container {
+                    children
+                        .push(menu_item(fl!("unlock-container"), Action::UnlockContainer).into());
                 }
-                children.push(sort_item(fl!("sort-by-name"), HeadingOptions1::Name));
-                children.push(sort_item(fl!("sort-by-modified"), HeadingOptions1::Modified));
-                children.push(sort_item(fl!("sort-by-size"), HeadingOptions1::Size));
-            }
-        }
-        (_, Location1::Trash) => {
-            if tab.mode.multiple() {
-                children.push(menu_item(fl!("select-all"), Action::SelectAll).into());
-            }
-            if !children.is_empty() {
+                children.push(menu_item(fl!("send-to"), Action::SendTo).into());
                 children.push(divider::horizontal::light().into());
-            }
-            if selected > 0 {
-                children.push(menu_item(fl!("show-details"), Action::Preview).into());
-                children.push(divider::horizontal::light().into());
-                children
-                    .push(menu_item(fl!("restore-from-trash"), Action::RestoreFromTrash).into());
-            } else {
-                // TODO: Nested menu
-                children.push(sort_item(fl!("sort-by-name"), HeadingOptions1::Name));
-                children.push(sort_item(fl!("sort-by-trashed"), HeadingOptions1::TrashedOn));
-                children.push(sort_item(fl!("sort-by-size"), HeadingOptions1::Size));
-            }
-        }
-    }
 
-    container(column::with_children(children))
-        .padding(1)
-        //TODO: move style to libcosmic
-        .style(|theme| {
-            let cosmic = theme.cosmic();
-            let component = &cosmic.background.component;
-            container::Style {
-                icon_color: Some(component.on.into()),
-                text_color: Some(component.on.into()),
-                background: Some(Background::Color(component.base.into())),
-                border: Border {
-                    radius: cosmic.radius_s().map(|x| x + 1.0).into(),
-                    width: 1.0,
-                    color: component.divider.into(),
-                },
-                ..Default::default()
-            }
-        })
-        .width(Length::Fixed(360.0))
-        .into()
-}
-
-pub fn context_menu2<'a>(
-    tab: &Tab2,
-    key_binds: &HashMap<KeyBind, Action>,
-) -> Element<'a, tab2::Message> {
-    let find_key = |action: &Action| -> String {
-        for (key_bind, key_action) in key_binds.iter() {
-            if action == key_action {
-                return key_bind.to_string();
-            }
-        }
-        String::new()
-    };
-
-    let menu_item = |label, action| {
-        let key = find_key(&action);
-        menu_button!(text::body(label), horizontal_space(), text::body(key))
-            .on_press(tab2::Message::ContextAction(action))
-    };
-
-    let (sort_name, sort_direction, _) = tab.sort_options();
-    let sort_item = |label, variant| {
-        menu_item(
-            format!(
-                "{} {}",
-                label,
-                match (sort_name == variant, sort_direction) {
-                    (true, true) => "\u{2B07}",
-                    (true, false) => "\u{2B06}",
-                    _ => "",
-                }
-            ),
-            Action::ToggleSortRight(variant),
-        )
-        .into()
-    };
-
-    let mut selected_dir = 0;
-    let mut selected = 0;
-    let mut selected_trash_only = false;
-    let mut selected_desktop_entry = None;
-    let mut selected_types: Vec<Mime> = vec![];
-    if let Some(items) = tab.items_opt() {
-        for item in items.iter() {
-            if item.selected {
-                selected += 1;
-                if item.metadata.is_dir() {
-                    selected_dir += 1;
-                }
-                match &item.location_opt {
-                    Some(Location2::Trash) => selected_trash_only = true,
-                    Some(Location2::Path(path)) => {
-                        if selected == 1
-                            && path.extension().and_then(|s| s.to_str()) == Some("desktop")
+                // Quick actions and plugins are only shown if they apply to all selected items
+                if selected_plugin_items.len() == selected {
+                    let mut has_plugins = false;
+                    for action in QuickAction::ALL {
+                        if (selected == 1 || action.multiple())
+                            && selected_plugin_items
+                                .iter()
+                                .all(|(_path, mime)| action.matches(mime))
                         {
-                            selected_desktop_entry = Some(&**path);
+                            children.push(
+                                menu_item(action.name(), Action::QuickAction(*action)).into(),
+                            );
+                            has_plugins = true;
                         }
                     }
-                    _ => (),
-                }
-                selected_types.push(item.mime.clone());
-            }
-        }
-    };
-    selected_types.sort_unstable();
-    selected_types.dedup();
-    selected_trash_only = selected_trash_only && selected == 1;
-    // Parse the desktop entry if it is the only selection
-    #[cfg(feature = "desktop")]
-    let selected_desktop_entry = selected_desktop_entry.and_then(|path| {
-        if selected == 1 {
-            let lang_id = crate::localize::LANGUAGE_LOADER.current_language();
-            let language = lang_id.language.as_str();
-            // Cache?
-            cosmic::desktop::load_desktop_file(Some(language), path)
-        } else {
-            None
-        }
-    });
-
-    let mut children: Vec<Element<_>> = Vec::new();
-    match (&tab.mode, &tab.location) {
-        (
-            tab2::Mode::App | tab2::Mode::Desktop,
-            Location2::Desktop(..) | Location2::Path(..) | Location2::Search(..) | Location2::Recents,
-        ) => {
-            if selected_trash_only {
-                children.push(menu_item(fl!("open"), Action::Open).into());
-                if tab2::trash_entries() > 0 {
-                    children.push(menu_item(fl!("empty-trash"), Action::EmptyTrash).into());
-                }
-            } else if let Some(entry) = selected_desktop_entry {
-                children.push(menu_item(fl!("open"), Action::Open).into());
-                #[cfg(feature = "desktop")]
-                {
-                    for (i, action) in entry.desktop_actions.into_iter().enumerate() {
-                        children.push(menu_item(action.name, Action::ExecEntryAction(i)).into())
+                    for (i, plugin) in plugin::plugins().iter().enumerate() {
+                        if selected_plugin_items
+                            .iter()
+                            .all(|(path, mime)| plugin.matches(path, mime))
+                        {
+                            children
+                                .push(menu_item(plugin.name.clone(), Action::RunPlugin(i)).into());
+                            has_plugins = true;
+                        }
                     }
-                }
-                children.push(divider::horizontal::light().into());
-                children.push(menu_item(fl!("rename"), Action::Rename).into());
-                children.push(menu_item(fl!("cut"), Action::Cut).into());
-                children.push(menu_item(fl!("copy"), Action::Copy).into());
-                // Should this simply bypass trash and remove the shortcut?
-                children.push(menu_item(fl!("move-to-trash"), Action::MoveToTrash).into());
-            } else if selected > 0 {
-                if selected_dir == 1 && selected == 1 || selected_dir == 0 {
-                    children.push(menu_item(fl!("open"), Action::Open).into());
-                }
-                if selected == 1 {
-                    children.push(menu_item(fl!("menu-open-with"), Action::OpenWith).into());
-                    if selected_dir == 1 {
-                        children
-                            .push(menu_item(fl!("open-in-terminal"), Action::OpenTerminal).into());
+                    if has_plugins {
+                        children.push(divider::horizontal::light().into());
                     }
                 }
-                if matches!(tab.location, Location2::Search(..) | Location2::Recents) {
-                    children.push(
-                        menu_item(fl!("open-item-location"), Action::OpenItemLocation).into(),
-                    );
-                }
-                // All selected items are directories
-                if selected == selected_dir && matches!(tab.mode, tab2::Mode::App) {
-                    children.push(menu_item(fl!("open-in-new-tab"), Action::OpenInNewTab).into());
-                    children
-                        .push(menu_item(fl!("open-in-new-window"), Action::OpenInNewWindow).into());
-                }
-                children.push(divider::horizontal::light().into());
-                children.push(menu_item(fl!("rename"), Action::Rename).into());
-                children.push(menu_item(fl!("cut"), Action::Cut).into());
-                children.push(menu_item(fl!("copy"), Action::Copy).into());
-
-                children.push(divider::horizontal::light().into());
-                let supported_archive_types = [
-                    "application/gzip",
-                    "application/x-compressed-tar",
-                    "application/x-tar",
-                    "application/zip",
-                    #[cfg(feature = "bzip2")]
-                    "application/x-bzip",
-                    #[cfg(feature = "bzip2")]
-                    "application/x-bzip-compressed-tar",
-                    #[cfg(feature = "liblzma")]
-                    "application/x-xz",
-                    #[cfg(feature = "liblzma")]
-                    "application/x-xz-compressed-tar",
-                ]
-                .iter()
-                .filter_map(|mime_type| mime_type.parse::<Mime>().ok())
-                .collect::<Vec<_>>();
-                selected_types.retain(|t| !supported_archive_types.contains(t));
-                if selected_types.is_empty() {
-                    children.push(menu_item(fl!("extract-here"), Action::ExtractHere).into());
-                }
-                children.push(menu_item(fl!("compress"), Action::Compress).into());
-                children.push(divider::horizontal::light().into());
 
                 //TODO: Print?
                 children.push(menu_item(fl!("show-details"), Action::Preview).into());
-                if matches!(tab.mode, tab2::Mode::App) {
+                if matches!(tab.mode, tab::Mode::App) {
                     children.push(divider::horizontal::light().into());
                     children.push(menu_item(fl!("add-to-sidebar"), Action::AddToSidebar).into());
                 }
                 children.push(divider::horizontal::light().into());
                 children.push(menu_item(fl!("move-to-trash"), Action::MoveToTrash).into());
+                children.push(divider::horizontal::light().into());
+                children.push(menu_item(fl!("new-tab"), Action::TabNew).into());
+                children.push(menu_item(fl!("copy-tab"), Action::CopyTab).into());
+                children.push(menu_item(fl!("move-tab"), Action::MoveTab).into());
                 // zoom does not work!
                 children.push(divider::horizontal::light().into());
                 children.push(menu_item(fl!("zoom-in"), Action::ZoomIn).into());
@@ -546,27 +323,31 @@ pub fn context_menu2<'a>(
                 children.push(menu_item(fl!("list-view"), Action::TabViewList).into());
                 children.push(divider::horizontal::light().into());
                 // TODO: Nested menu
-                children.push(sort_item(fl!("sort-by-name"), HeadingOptions2::Name));
-                children.push(sort_item(fl!("sort-by-modified"), HeadingOptions2::Modified));
-                children.push(sort_item(fl!("sort-by-size"), HeadingOptions2::Size));
-                children.push(divider::horizontal::light().into());
-                children.push(menu_item(fl!("new-tab"), Action::TabNew).into());
-                children.push(menu_item(fl!("copy-tab"), Action::CopyTab).into());
-                children.push(menu_item(fl!("move-tab"), Action::MoveTab).into());
+                children.push(sort_item(fl!("sort-by-name"), HeadingOptions::Name));
+                children.push(sort_item(fl!("sort-by-modified"), HeadingOptions::Modified));
+                children.push(sort_item(fl!("sort-by-size"), HeadingOptions::Size));
             } else {
                 //TODO: need better designs for menu with no selection
                 //TODO: have things like properties but they apply to the folder?
                 children.push(menu_item(fl!("new-folder"), Action::NewFolder).into());
                 children.push(menu_item(fl!("new-file"), Action::NewFile).into());
+                for (i, template) in template::templates().into_iter().enumerate() {
+                    children.push(menu_item(template.name, Action::NewFromTemplate(i)).into());
+                }
                 children.push(menu_item(fl!("open-in-terminal"), Action::OpenTerminal).into());
+                children.push(
+                    menu_item(fl!("open-as-administrator"), Action::OpenAsAdministrator).into(),
+                );
                 children.push(divider::horizontal::light().into());
                 if tab.mode.multiple() {
                     children.push(menu_item(fl!("select-all"), Action::SelectAll).into());
                 }
                 children.push(menu_item(fl!("paste"), Action::Paste).into());
+                children
+                    .push(menu_item(fl!("copy-directory-path"), Action::CopyDirectoryPath).into());
 
                 //TODO: only show if cosmic-settings is found?
-                if matches!(tab.mode, tab2::Mode::Desktop) {
+                if matches!(tab.mode, tab::Mode::Desktop) {
                     children.push(divider::horizontal::light().into());
                     children.push(
                         menu_item(fl!("change-wallpaper"), Action::CosmicSettingsWallpaper).into(),
@@ -579,10 +360,6 @@ pub fn context_menu2<'a>(
                         menu_item(fl!("display-settings"), Action::CosmicSettingsDisplays).into(),
                     );
                 }
-                children.push(divider::horizontal::light().into());
-                children.push(menu_item(fl!("new-tab"), Action::TabNew).into());
-                children.push(menu_item(fl!("copy-tab"), Action::CopyTab).into());
-                children.push(menu_item(fl!("move-tab"), Action::MoveTab).into());
                 // zoom does not work!
                 children.push(divider::horizontal::light().into());
                 children.push(menu_item(fl!("zoom-in"), Action::ZoomIn).into());
@@ -591,13 +368,33 @@ pub fn context_menu2<'a>(
                 children.push(divider::horizontal::light().into());
                 children.push(menu_item(fl!("grid-view"), Action::TabViewGrid).into());
                 children.push(menu_item(fl!("list-view"), Action::TabViewList).into());
+                children.push(divider::horizontal::light().into());
+                children.push(menu_item(fl!("new-tab"), Action::TabNew).into());
+                children.push(menu_item(fl!("copy-tab"), Action::CopyTab).into());
+                children.push(menu_item(fl!("move-tab"), Action::MoveTab).into());
+                if matches!(tab.mode, tab::Mode::App) {
+                    let pin_label = if tab.pinned {
+                        fl!("unpin-tab")
+                    } else {
+                        fl!("pin-tab")
+                    };
+                    children.push(menu_item(pin_label, Action::TabPin).into());
+                    let lock_label = if tab.locked {
+                        fl!("unlock-tab")
+                    } else {
+                        fl!("lock-tab")
+                    };
+                    children.push(menu_item(lock_label, Action::TabLock).into());
+                }
+
                 children.push(divider::horizontal::light().into());
                 // TODO: Nested menu
-                children.push(sort_item(fl!("sort-by-name"), HeadingOptions2::Name));
-                children.push(sort_item(fl!("sort-by-modified"), HeadingOptions2::Modified));
-                children.push(sort_item(fl!("sort-by-size"), HeadingOptions2::Size));
-                if matches!(tab.location, Location2::Desktop(..)) {
+                children.push(sort_item(fl!("sort-by-name"), HeadingOptions::Name));
+                children.push(sort_item(fl!("sort-by-modified"), HeadingOptions::Modified));
+                children.push(sort_item(fl!("sort-by-size"), HeadingOptions::Size));
+                if matches!(tab.location, Location::Desktop(..)) {
                     children.push(divider::horizontal::light().into());
+                    children.push(menu_item(fl!("arrange-icons"), Action::ArrangeDesktop).into());
                     children.push(
                         menu_item(fl!("desktop-view-options"), Action::DesktopViewOptions).into(),
                     );
@@ -605,14 +402,14 @@ pub fn context_menu2<'a>(
             }
         }
         (
-            tab2::Mode::Dialog(dialog_kind),
-            Location2::Desktop(..) | Location2::Path(..) | Location2::Search(..) | Location2::Recents,
+            tab::Mode::Dialog(dialog_kind),
+            Location::Desktop(..) | Location::Path(..) | Location::Search(..) | Location::Recents,
         ) => {
             if selected > 0 {
                 if selected_dir == 1 && selected == 1 || selected_dir == 0 {
                     children.push(menu_item(fl!("open"), Action::Open).into());
                 }
-                if matches!(tab.location, Location2::Search(..) | Location2::Recents) {
+                if matches!(tab.location, Location::Search(..) | Location::Recents) {
                     children.push(
                         menu_item(fl!("open-item-location"), Action::OpenItemLocation).into(),
                     );
@@ -629,12 +426,12 @@ pub fn context_menu2<'a>(
                 if !children.is_empty() {
                     children.push(divider::horizontal::light().into());
                 }
-                children.push(sort_item(fl!("sort-by-name"), HeadingOptions2::Name));
-                children.push(sort_item(fl!("sort-by-modified"), HeadingOptions2::Modified));
-                children.push(sort_item(fl!("sort-by-size"), HeadingOptions2::Size));
+                children.push(sort_item(fl!("sort-by-name"), HeadingOptions::Name));
+                children.push(sort_item(fl!("sort-by-modified"), HeadingOptions::Modified));
+                children.push(sort_item(fl!("sort-by-size"), HeadingOptions::Size));
             }
         }
-        (_, Location2::Network(..)) => {
+        (_, Location::Network(..)) => {
             if selected > 0 {
                 if selected_dir == 1 && selected == 1 || selected_dir == 0 {
                     children.push(menu_item(fl!("open"), Action::Open).into());
@@ -646,12 +443,12 @@ pub fn context_menu2<'a>(
                 if !children.is_empty() {
                     children.push(divider::horizontal::light().into());
                 }
-                children.push(sort_item(fl!("sort-by-name"), HeadingOptions2::Name));
-                children.push(sort_item(fl!("sort-by-modified"), HeadingOptions2::Modified));
-                children.push(sort_item(fl!("sort-by-size"), HeadingOptions2::Size));
+                children.push(sort_item(fl!("sort-by-name"), HeadingOptions::Name));
+                children.push(sort_item(fl!("sort-by-modified"), HeadingOptions::Modified));
+                children.push(sort_item(fl!("sort-by-size"), HeadingOptions::Size));
             }
         }
-        (_, Location2::Trash) => {
+        (_, Location::Trash) => {
             if tab.mode.multiple() {
                 children.push(menu_item(fl!("select-all"), Action::SelectAll).into());
             }
@@ -665,9 +462,9 @@ pub fn context_menu2<'a>(
                     .push(menu_item(fl!("restore-from-trash"), Action::RestoreFromTrash).into());
             } else {
                 // TODO: Nested menu
-                children.push(sort_item(fl!("sort-by-name"), HeadingOptions2::Name));
-                children.push(sort_item(fl!("sort-by-trashed"), HeadingOptions2::TrashedOn));
-                children.push(sort_item(fl!("sort-by-size"), HeadingOptions2::Size));
+                children.push(sort_item(fl!("sort-by-name"), HeadingOptions::Name));
+                children.push(sort_item(fl!("sort-by-trashed"), HeadingOptions::TrashedOn));
+                children.push(sort_item(fl!("sort-by-size"), HeadingOptions::Size));
             }
         }
     }
@@ -695,7 +492,7 @@ pub fn context_menu2<'a>(
 }
 
 pub fn context_menu_term<'a>(
-    _config: &Config,
+    config: &Config,
     key_binds: &HashMap<KeyBind, Action>,
 ) -> Element<'a, Message> {
     use cosmic::widget::menu::menu_button;
@@ -726,33 +523,50 @@ pub fn context_menu_term<'a>(
         .on_press(Message::TermContextAction(action))
     };
 
-    widget::container(column!(
-        menu_item(fl!("copy"), Action::CopyTerminal),
-        menu_item(fl!("paste"), Action::PasteTerminal),
-    ))
-    .padding(1)
-    //TODO: move style to libcosmic
-    .style(|theme| {
-        let cosmic = theme.cosmic();
-        let component = &cosmic.background.component;
-        widget::container::Style {
-            icon_color: Some(component.on.into()),
-            text_color: Some(component.on.into()),
-            background: Some(Background::Color(component.base.into())),
-            border: Border {
-                radius: cosmic.radius_s().map(|x| x + 1.0).into(),
-                width: 1.0,
-                color: component.divider.into(),
-            },
-            ..Default::default()
-        }
-    })
-    .width(Length::Fixed(240.0))
-    .into()
+    let mut children: Vec<Element<_>> = vec![
+        menu_item(fl!("copy"), Action::CopyTerminal).into(),
+        menu_item(fl!("paste"), Action::PasteTerminal).into(),
+        divider::horizontal::light().into(),
+        menu_item(fl!("new-terminal"), Action::TermNew).into(),
+    ];
+    for (profile_id, profile) in config.profiles.iter() {
+        children.push(
+            menu_item(
+                fl!("new-terminal-profile", profile = profile.name.as_str()),
+                Action::TermNewProfile(*profile_id),
+            )
+            .into(),
+        );
+    }
+    children.push(menu_item(fl!("rename-terminal"), Action::TermRename).into());
+    children.push(menu_item(fl!("close-terminal"), Action::TermClose).into());
+    children.push(divider::horizontal::light().into());
+    children.push(menu_item(fl!("terminal-profiles"), Action::EditTerminalProfiles).into());
+
+    widget::container(column(children))
+        .padding(1)
+        //TODO: move style to libcosmic
+        .style(|theme| {
+            let cosmic = theme.cosmic();
+            let component = &cosmic.background.component;
+            widget::container::Style {
+                icon_color: Some(component.on.into()),
+                text_color: Some(component.on.into()),
+                background: Some(Background::Color(component.base.into())),
+                border: Border {
+                    radius: cosmic.radius_s().map(|x| x + 1.0).into(),
+                    width: 1.0,
+                    color: component.divider.into(),
+                },
+                ..Default::default()
+            }
+        })
+        .width(Length::Fixed(240.0))
+        .into()
 }
 
-pub fn dialog_menu1(
-    tab: &Tab1,
+pub fn dialog_menu(
+    tab: &Tab,
     key_binds: &HashMap<KeyBind, Action>,
     show_details: bool,
 ) -> Element<'static, Message> {
@@ -765,7 +579,7 @@ pub fn dialog_menu1(
             Action::SetSort(sort, dir),
         )
     };
-    let in_trash = tab.location == Location1::Trash;
+    let in_trash = tab.location == Location::Trash;
 
     let mut selected_gallery = 0;
     if let Some(items) = tab.items_opt() {
@@ -779,8 +593,8 @@ pub fn dialog_menu1(
     MenuBar::new(vec![
         menu::Tree::with_children(
             widget::button::icon(widget::icon::from_name(match tab.config.view {
-                tab1::View::Grid => "view-grid-symbolic",
-                tab1::View::List => "view-list-symbolic",
+                tab::View::Grid => "view-grid-symbolic",
+                tab::View::List => "view-list-symbolic",
             }))
             // This prevents the button from being shown as insensitive
             .on_press(Message::None)
@@ -791,13 +605,13 @@ pub fn dialog_menu1(
                     menu::Item::CheckBox(
                         fl!("grid-view"),
                         None,
-                        matches!(tab.config.view, tab1::View::Grid),
+                        matches!(tab.config.view, tab::View::Grid),
                         Action::TabViewGrid,
                     ),
                     menu::Item::CheckBox(
                         fl!("list-view"),
                         None,
-                        matches!(tab.config.view, tab1::View::List),
+                        matches!(tab.config.view, tab::View::List),
                         Action::TabViewList,
                     ),
                 ],
@@ -815,34 +629,34 @@ pub fn dialog_menu1(
             menu::items(
                 key_binds,
                 vec![
-                    sort_item(fl!("sort-a-z"), tab1::HeadingOptions::Name, true),
-                    sort_item(fl!("sort-z-a"), tab1::HeadingOptions::Name, false),
+                    sort_item(fl!("sort-a-z"), tab::HeadingOptions::Name, true),
+                    sort_item(fl!("sort-z-a"), tab::HeadingOptions::Name, false),
                     sort_item(
                         fl!("sort-newest-first"),
                         if in_trash {
-                            tab1::HeadingOptions::TrashedOn
+                            tab::HeadingOptions::TrashedOn
                         } else {
-                            tab1::HeadingOptions::Modified
+                            tab::HeadingOptions::Modified
                         },
                         false,
                     ),
                     sort_item(
                         fl!("sort-oldest-first"),
                         if in_trash {
-                            tab1::HeadingOptions::TrashedOn
+                            tab::HeadingOptions::TrashedOn
                         } else {
-                            tab1::HeadingOptions::Modified
+                            tab::HeadingOptions::Modified
                         },
                         true,
                     ),
                     sort_item(
                         fl!("sort-smallest-to-largest"),
-                        tab1::HeadingOptions::Size,
+                        tab::HeadingOptions::Size,
                         true,
                     ),
                     sort_item(
                         fl!("sort-largest-to-smallest"),
-                        tab1::HeadingOptions::Size,
+                        tab::HeadingOptions::Size,
                         false,
                     ),
                     //TODO: sort by type
@@ -867,6 +681,12 @@ pub fn dialog_menu1(
                         tab.config.show_hidden,
                         Action::ToggleShowHidden,
                     ),
+                    menu::Item::CheckBox(
+                        fl!("dim-hidden-files"),
+                        None,
+                        tab.config.dim_hidden,
+                        Action::ToggleDimHidden,
+                    ),
                     menu::Item::CheckBox(
                         fl!("list-directories-first"),
                         None,
@@ -891,7 +711,7 @@ pub fn dialog_menu1(
 }
 
 pub fn menu_bar<'a>(
-    tab_opt: Option<&Tab1>,
+    tab_opt: Option<&Tab>,
     config: &Config,
     key_binds: &HashMap<KeyBind, Action>,
 ) -> Element<'a, Message> {
@@ -906,7 +726,8 @@ pub fn menu_bar<'a>(
             Action::SetSort(sort, dir),
         )
     };
-    let in_trash = tab_opt.map_or(false, |tab| tab.location == Location1::Trash);
+    let in_trash = tab_opt.map_or(false, |tab| tab.location == Location::Trash);
+    let writable = !tab_opt.map_or(false, |tab| tab.config.read_only);
 
     let mut selected_dir = 0;
     let mut selected = 0;
@@ -932,26 +753,71 @@ pub fn menu_bar<'a>(
                 key_binds,
                 vec![
                     menu::Item::Button(fl!("new-tab"), None, Action::TabNew),
-                    menu::Item::Button(fl!("copy-tab"), None, Action::TabNew),
-                    menu::Item::Button(fl!("move-tab"), None, Action::TabNew),
+                    menu::Item::Button(fl!("duplicate-tab"), None, Action::TabDuplicate),
+                    menu::Item::Button(fl!("copy-tab"), None, Action::CopyTab),
+                    menu::Item::Button(fl!("move-tab"), None, Action::MoveTab),
+                    menu::Item::Button(fl!("reopen-closed-tab"), None, Action::TabReopen),
                     menu::Item::Divider,
                     menu::Item::Button(fl!("new-window"), None, Action::WindowNew),
-                    menu::Item::Button(fl!("new-folder"), None, Action::NewFolder),
-                    menu::Item::Button(fl!("new-file"), None, Action::NewFile),
+                    menu_button_optional(fl!("new-folder"), Action::NewFolder, writable),
+                    menu::Item::Folder(fl!("new-file"), {
+                        let mut items = vec![
+                            menu_button_optional(fl!("empty-file"), Action::NewFile, writable),
+                            menu::Item::Divider,
+                        ];
+                        for (i, template) in template::templates().into_iter().enumerate() {
+                            items.push(menu_button_optional(
+                                template.name,
+                                Action::NewFromTemplate(i),
+                                writable,
+                            ));
+                        }
+                        items
+                    }),
                     menu_button_optional(
                         fl!("open"),
                         Action::Open,
                         (selected > 0 && selected_dir == 0) || (selected_dir == 1 && selected == 1),
                     ),
                     menu_button_optional(fl!("menu-open-with"), Action::OpenWith, selected == 1),
+                    menu_button_optional(
+                        fl!("compare-files"),
+                        Action::Compare,
+                        selected_dir == 0 && (selected == 1 || selected == 2),
+                    ),
                     menu::Item::Divider,
-                    menu_button_optional(fl!("rename"), Action::F2Rename, selected > 0),
+                    menu_button_optional(
+                        fl!("rename"),
+                        Action::F2Rename,
+                        selected > 0 && writable,
+                    ),
                     menu_button_optional(fl!("f5-copy"), Action::F5Copy, selected > 0),
-                    menu_button_optional(fl!("f6-move"), Action::F6Move, selected > 0),
+                    menu_button_optional(
+                        fl!("f6-move"),
+                        Action::F6Move,
+                        selected > 0 && writable,
+                    ),
+                    menu_button_optional(
+                        fl!("pack-to-other-pane"),
+                        Action::PackToOtherPane,
+                        selected > 0,
+                    ),
+                    menu_button_optional(
+                        fl!("add-to-archive"),
+                        Action::AddToArchive,
+                        selected > 0 && writable,
+                    ),
+                    menu::Item::Button(fl!("export-listing"), None, Action::ExportListing),
+                    menu_button_optional(fl!("print"), Action::Print, selected > 0),
+                    menu::Item::Button(fl!("print-listing"), None, Action::PrintListing),
                     menu::Item::Divider,
                     menu_button_optional(fl!("add-to-sidebar"), Action::AddToSidebar, selected > 0),
                     menu::Item::Divider,
-                    menu_button_optional(fl!("move-to-trash"), Action::MoveToTrash, selected > 0),
+                    menu_button_optional(
+                        fl!("move-to-trash"),
+                        Action::MoveToTrash,
+                        selected > 0 && writable,
+                    ),
                     menu::Item::Divider,
                     menu::Item::Button(fl!("close-tab"), None, Action::TabClose),
                     menu::Item::Button(fl!("quit"), None, Action::WindowClose),
@@ -963,10 +829,37 @@ pub fn menu_bar<'a>(
             menu::items(
                 key_binds,
                 vec![
-                    menu_button_optional(fl!("cut"), Action::Cut, selected > 0),
+                    menu_button_optional(fl!("cut"), Action::Cut, selected > 0 && writable),
                     menu_button_optional(fl!("copy"), Action::Copy, selected > 0),
-                    menu_button_optional(fl!("paste"), Action::Paste, selected > 0),
+                    menu_button_optional(
+                        fl!("duplicate"),
+                        Action::Duplicate,
+                        selected > 0 && writable,
+                    ),
+                    menu_button_optional(fl!("copy-paths"), Action::CopyPaths, selected > 0),
+                    menu_button_optional(fl!("copy-names"), Action::CopyNames, selected > 0),
+                    menu::Item::Button(fl!("copy-directory-path"), None, Action::CopyDirectoryPath),
+                    menu_button_optional(fl!("paste"), Action::Paste, selected > 0 && writable),
                     menu::Item::Button(fl!("select-all"), None, Action::SelectAll),
+                    menu_button_optional(
+                        fl!("save-selection"),
+                        Action::SaveSelection,
+                        selected > 0,
+                    ),
+                    menu::Item::Button(fl!("restore-selection"), None, Action::RestoreSelection),
+                    menu::Item::Button(fl!("load-selection"), None, Action::LoadSelection),
+                    menu::Item::Button(fl!("open-context-menu"), None, Action::ContextMenu),
+                    menu::Item::Divider,
+                    menu_button_optional(
+                        fl!("insert-name-terminal"),
+                        Action::InsertNameTerminal,
+                        selected > 0 && config.show_embedded_terminal,
+                    ),
+                    menu_button_optional(
+                        fl!("insert-paths-terminal"),
+                        Action::InsertPathsTerminal,
+                        selected > 0 && config.show_embedded_terminal,
+                    ),
                     menu::Item::Divider,
                     menu::Item::Button(fl!("history"), None, Action::EditHistory),
                 ],
@@ -984,13 +877,13 @@ pub fn menu_bar<'a>(
                     menu::Item::CheckBox(
                         fl!("grid-view"),
                         None,
-                        tab_opt.map_or(false, |tab| matches!(tab.config.view, tab1::View::Grid)),
+                        tab_opt.map_or(false, |tab| matches!(tab.config.view, tab::View::Grid)),
                         Action::TabViewGrid,
                     ),
                     menu::Item::CheckBox(
                         fl!("list-view"),
                         None,
-                        tab_opt.map_or(false, |tab| matches!(tab.config.view, tab1::View::List)),
+                        tab_opt.map_or(false, |tab| matches!(tab.config.view, tab::View::List)),
                         Action::TabViewList,
                     ),
                     menu::Item::Divider,
@@ -1000,18 +893,49 @@ pub fn menu_bar<'a>(
                         tab_opt.map_or(false, |tab| tab.config.show_hidden),
                         Action::ToggleShowHidden,
                     ),
+                    menu::Item::CheckBox(
+                        fl!("dim-hidden-files"),
+                        None,
+                        tab_opt.map_or(false, |tab| tab.config.dim_hidden),
+                        Action::ToggleDimHidden,
+                    ),
                     menu::Item::CheckBox(
                         fl!("list-directories-first"),
                         None,
                         tab_opt.map_or(false, |tab| tab.config.folders_first),
                         Action::ToggleFoldersFirst,
                     ),
+                    menu::Item::CheckBox(
+                        fl!("read-only-pane"),
+                        None,
+                        !writable,
+                        Action::ToggleReadOnly,
+                    ),
                     menu::Item::CheckBox(
                         fl!("show-details"),
                         None,
                         config.show_details,
                         Action::Preview,
                     ),
+                    menu::Item::CheckBox(
+                        fl!("show-embedded-terminal"),
+                        None,
+                        config.show_embedded_terminal,
+                        Action::ToggleTerminal,
+                    ),
+                    menu::Item::Divider,
+                    menu::Item::Button(fl!("left-pane"), None, Action::TabActivateLeft),
+                    menu_button_optional(
+                        fl!("right-pane"),
+                        Action::TabActivateRight,
+                        config.show_second_panel,
+                    ),
+                    menu_button_optional(
+                        fl!("quick-view"),
+                        Action::QuickView,
+                        config.show_second_panel,
+                    ),
+                    menu::Item::Button(fl!("pane-history"), None, Action::PaneHistory),
                     menu::Item::Divider,
                     menu_button_optional(
                         fl!("gallery-preview"),
@@ -1025,45 +949,150 @@ pub fn menu_bar<'a>(
                 ],
             ),
         ),
+        menu::Tree::with_children(
+            menu::root(fl!("go")),
+            menu::items(key_binds, {
+                let mut items = vec![
+                    menu::Item::Button(fl!("home"), None, Action::GoHome),
+                    menu::Item::Button(fl!("desktop"), None, Action::GoDesktop),
+                    menu::Item::Button(fl!("documents"), None, Action::GoDocuments),
+                    menu::Item::Button(fl!("downloads"), None, Action::GoDownloads),
+                    menu::Item::Button(fl!("music"), None, Action::GoMusic),
+                    menu::Item::Button(fl!("pictures"), None, Action::GoPictures),
+                    menu::Item::Button(fl!("videos"), None, Action::GoVideos),
+                    menu::Item::Divider,
+                    menu::Item::Button(fl!("computer"), None, Action::GoComputer),
+                    menu::Item::Button(fl!("recents"), None, Action::GoRecents),
+                    menu::Item::Button(fl!("trash"), None, Action::GoTrash),
+                ];
+                if mounter::network_supported() {
+                    items.push(menu::Item::Button(fl!("networks"), None, Action::GoNetwork));
+                }
+                items.push(menu::Item::Button(
+                    fl!("enter-location"),
+                    None,
+                    Action::EditLocation,
+                ));
+                items.push(menu::Item::Divider);
+                for (folder, bookmarks) in config::bookmark_groups(&config.bookmarks) {
+                    let bookmark_items: Vec<_> = bookmarks
+                        .into_iter()
+                        .map(|(i, bookmark)| {
+                            // Bookmarks with a shortcut show it in the menu
+                            let action = match bookmark.shortcut {
+                                Some(digit) => Action::BookmarkShortcut(digit),
+                                None => Action::OpenBookmark(i),
+                            };
+                            menu::Item::Button(bookmark.display_name(), None, action)
+                        })
+                        .collect();
+                    if folder.is_empty() {
+                        items.extend(bookmark_items);
+                    } else {
+                        items.push(menu::Item::Folder(folder.to_string(), bookmark_items));
+                    }
+                }
+                if !config.bookmarks.is_empty() {
+                    items.push(menu::Item::Divider);
+                }
+                items.push(menu::Item::Button(
+                    fl!("add-bookmark"),
+                    None,
+                    Action::AddBookmark,
+                ));
+                items.push(menu::Item::Button(
+                    fl!("edit-bookmarks"),
+                    None,
+                    Action::EditBookmarks,
+                ));
+                items
+            }),
+        ),
         menu::Tree::with_children(
             menu::root(fl!("sort")),
             menu::items(
                 key_binds,
                 vec![
-                    sort_item(fl!("sort-a-z"), tab1::HeadingOptions::Name, true),
-                    sort_item(fl!("sort-z-a"), tab1::HeadingOptions::Name, false),
+                    sort_item(fl!("sort-a-z"), tab::HeadingOptions::Name, true),
+                    sort_item(fl!("sort-z-a"), tab::HeadingOptions::Name, false),
                     sort_item(
                         fl!("sort-newest-first"),
                         if in_trash {
-                            tab1::HeadingOptions::TrashedOn
+                            tab::HeadingOptions::TrashedOn
                         } else {
-                            tab1::HeadingOptions::Modified
+                            tab::HeadingOptions::Modified
                         },
                         false,
                     ),
                     sort_item(
                         fl!("sort-oldest-first"),
                         if in_trash {
-                            tab1::HeadingOptions::TrashedOn
+                            tab::HeadingOptions::TrashedOn
                         } else {
-                            tab1::HeadingOptions::Modified
+                            tab::HeadingOptions::Modified
                         },
                         true,
                     ),
                     sort_item(
                         fl!("sort-smallest-to-largest"),
-                        tab1::HeadingOptions::Size,
+                        tab::HeadingOptions::Size,
                         true,
                     ),
                     sort_item(
                         fl!("sort-largest-to-smallest"),
-                        tab1::HeadingOptions::Size,
+                        tab::HeadingOptions::Size,
                         false,
                     ),
                     //TODO: sort by type
                 ],
             ),
         ),
+        menu::Tree::with_children(
+            menu::root(fl!("tools")),
+            menu::items(key_binds, {
+                let mut items: Vec<_> = config
+                    .tools
+                    .iter()
+                    .enumerate()
+                    .map(|(i, tool)| {
+                        menu::Item::Button(tool.name.clone(), None, Action::RunTool(i))
+                    })
+                    .collect();
+                if !items.is_empty() {
+                    items.push(menu::Item::Divider);
+                }
+                items.push(menu::Item::Folder(fl!("sync-profiles"), {
+                    let mut sync_items: Vec<_> = config
+                        .sync_profiles
+                        .iter()
+                        .enumerate()
+                        .map(|(i, profile)| {
+                            menu::Item::Button(profile.name.clone(), None, Action::RunSync(i))
+                        })
+                        .collect();
+                    if !sync_items.is_empty() {
+                        sync_items.push(menu::Item::Divider);
+                    }
+                    sync_items.push(menu::Item::Button(
+                        fl!("edit-sync-profiles"),
+                        None,
+                        Action::EditSyncProfiles,
+                    ));
+                    sync_items
+                }));
+                items.push(menu::Item::Button(
+                    fl!("edit-tools"),
+                    None,
+                    Action::EditTools,
+                ));
+                items.push(menu::Item::Button(
+                    fl!("edit-toolbar"),
+                    None,
+                    Action::EditToolbar,
+                ));
+                items
+            }),
+        ),
     ])
     .item_height(ItemHeight::Dynamic(40))
     .item_width(ItemWidth::Uniform(360))
@@ -1071,32 +1100,109 @@ pub fn menu_bar<'a>(
     .into()
 }
 
-pub fn location_context_menu1<'a>(ancestor_index: usize) -> Element<'a, tab1::Message> {
-    //TODO: only add some of these when in App mode
-    let children = vec![
-        menu_button!(text::body(fl!("open-in-new-tab")))
-            .on_press(tab1::Message::LocationMenuAction(
-                LocationMenuAction1::OpenInNewTab(ancestor_index),
-            ))
-            .into(),
-        menu_button!(text::body(fl!("open-in-new-window")))
-            .on_press(tab1::Message::LocationMenuAction(
-                LocationMenuAction1::OpenInNewWindow(ancestor_index),
-            ))
-            .into(),
-        divider::horizontal::light().into(),
-        menu_button!(text::body(fl!("show-details")))
-            .on_press(tab1::Message::LocationMenuAction(
-                LocationMenuAction1::Preview(ancestor_index),
-            ))
-            .into(),
-        divider::horizontal::light().into(),
-        menu_button!(text::body(fl!("add-to-sidebar")))
-            .on_press(tab1::Message::LocationMenuAction(
-                LocationMenuAction1::AddToSidebar(ancestor_index),
-            ))
-            .into(),
-    ];
+/// Actions that can be added to the toolbar in the settings
+pub const TOOLBAR_ACTIONS: &[Action] = &[
+    Action::HistoryPrevious,
+    Action::HistoryNext,
+    Action::LocationUp,
+    Action::GoHome,
+    Action::TabRescan,
+    Action::TabNew,
+    Action::NewFolder,
+    Action::NewFile,
+    Action::Cut,
+    Action::Copy,
+    Action::Paste,
+    Action::Rename,
+    Action::MoveToTrash,
+    Action::SearchActivate,
+    Action::TabViewToggle,
+    Action::ToggleShowHidden,
+    Action::Preview,
+    Action::ZoomIn,
+    Action::ZoomOut,
+    Action::ToggleTerminal,
+    Action::OpenTerminal,
+    Action::SwapPanels,
+    Action::EditHistory,
+    Action::Settings,
+];
+
+/// Name and icon of the toolbar button of the action. Other actions than the offered ones
+/// can be added in the config and are shown with their identifier.
+pub fn toolbar_button(action: &Action) -> (String, &'static str) {
+    match action {
+        Action::HistoryPrevious => (fl!("toolbar-back"), "go-previous-symbolic"),
+        Action::HistoryNext => (fl!("toolbar-forward"), "go-next-symbolic"),
+        Action::LocationUp => (fl!("toolbar-up"), "go-up-symbolic"),
+        Action::GoHome => (fl!("home"), "go-home-symbolic"),
+        Action::TabRescan => (fl!("toolbar-reload"), "view-refresh-symbolic"),
+        Action::TabNew => (fl!("new-tab"), "tab-new-symbolic"),
+        Action::NewFolder => (fl!("new-folder"), "folder-new-symbolic"),
+        Action::NewFile => (fl!("new-file"), "document-new-symbolic"),
+        Action::Cut => (fl!("cut"), "edit-cut-symbolic"),
+        Action::Copy => (fl!("copy"), "edit-copy-symbolic"),
+        Action::Paste => (fl!("paste"), "edit-paste-symbolic"),
+        Action::Rename => (fl!("rename"), "document-edit-symbolic"),
+        Action::MoveToTrash => (fl!("move-to-trash"), "user-trash-symbolic"),
+        Action::SearchActivate => (fl!("toolbar-search"), "system-search-symbolic"),
+        Action::TabViewToggle => (fl!("toolbar-view"), "view-grid-symbolic"),
+        Action::ToggleShowHidden => (fl!("show-hidden-files"), "view-reveal-symbolic"),
+        Action::Preview => (fl!("show-details"), "document-properties-symbolic"),
+        Action::ZoomIn => (fl!("zoom-in"), "zoom-in-symbolic"),
+        Action::ZoomOut => (fl!("zoom-out"), "zoom-out-symbolic"),
+        Action::ToggleTerminal => (fl!("show-embedded-terminal"), "utilities-terminal-symbolic"),
+        Action::OpenTerminal => (fl!("open-in-terminal"), "utilities-terminal-symbolic"),
+        Action::SwapPanels => (
+            fl!("toolbar-swap-panels"),
+            "object-flip-horizontal-symbolic",
+        ),
+        Action::EditHistory => (fl!("history"), "document-open-recent-symbolic"),
+        Action::Settings => (fl!("settings"), "preferences-system-symbolic"),
+        _ => (format!("{:?}", action), "system-run-symbolic"),
+    }
+}
+
+/// Buttons of the actions in the config, shown after the menu bar
+pub fn toolbar<'a>(config: &Config) -> Element<'a, Message> {
+    let mut row = widget::row::with_capacity(config.toolbar.len()).align_y(Alignment::Center);
+    for action in config.toolbar.iter() {
+        let (name, icon_name) = toolbar_button(action);
+        row = row.push(widget::tooltip(
+            widget::button::icon(widget::icon::from_name(icon_name))
+                .on_press(MenuAction::message(action))
+                .padding(8),
+            widget::text::body(name),
+            widget::tooltip::Position::Bottom,
+        ));
+    }
+    row.into()
+}
+
+/// Dropdown of the back and forward buttons, with the closest locations first
+pub fn history_menu<'a>(tab: &Tab, forward: bool) -> Element<'a, tab::Message> {
+    let indices: Vec<usize> = if forward {
+        (tab.history_i + 1..tab.history.len()).take(12).collect()
+    } else {
+        (0..tab.history_i.min(tab.history.len()))
+            .rev()
+            .take(12)
+            .collect()
+    };
+    let mut children: Vec<Element<_>> = Vec::with_capacity(indices.len());
+    for history_i in indices {
+        let label = match &tab.history[history_i] {
+            Location::Recents => fl!("recents"),
+            Location::Trash => fl!("trash"),
+            Location::Network(_uri, display_name) => display_name.clone(),
+            location => location.to_string(),
+        };
+        children.push(
+            menu_button!(text::body(label))
+                .on_press(tab::Message::GoHistory(history_i))
+                .into(),
+        );
+    }
 
     container(column::with_children(children))
         .padding(1)
@@ -1119,29 +1225,132 @@ pub fn location_context_menu1<'a>(ancestor_index: usize) -> Element<'a, tab1::Me
         .into()
 }
 
-pub fn location_context_menu2<'a>(ancestor_index: usize) -> Element<'a, tab2::Message> {
+/// Dropdown of the tab bar with the directories last visited in the pane, the highlighted
+/// one is opened with the enter key
+pub fn pane_history_menu<'a>(
+    pane: PaneType,
+    history: &[PathBuf],
+    selected: usize,
+) -> Element<'a, Message> {
+    let mut children: Vec<Element<_>> = Vec::with_capacity(history.len());
+    for (index, path) in history.iter().enumerate() {
+        let label = if index == selected {
+            text::heading(path.display().to_string())
+        } else {
+            text::body(path.display().to_string())
+        };
+        children.push(
+            menu_button!(
+                widget::icon::from_name("folder-symbolic").size(16).icon(),
+                horizontal_space().width(Length::Fixed(8.0)),
+                label
+            )
+            .on_press(Message::PaneHistoryOpen(pane, index))
+            .into(),
+        );
+    }
+
+    container(widget::scrollable(column::with_children(children)).height(Length::Shrink))
+        .padding(1)
+        .style(|theme| {
+            let cosmic = theme.cosmic();
+            let component = &cosmic.background.component;
+            container::Style {
+                icon_color: Some(component.on.into()),
+                text_color: Some(component.on.into()),
+                background: Some(Background::Color(component.base.into())),
+                border: Border {
+                    radius: cosmic.radius_s().map(|x| x + 1.0).into(),
+                    width: 1.0,
+                    color: component.divider.into(),
+                },
+                ..Default::default()
+            }
+        })
+        .width(Length::Fixed(360.0))
+        .max_height(400.0)
+        .into()
+}
+
+pub fn siblings_menu<'a>(tab: &Tab) -> Element<'a, tab::Message> {
+    let mut children: Vec<Element<_>> = Vec::new();
+    if let Some((ancestor_index, folders)) = &tab.siblings_menu {
+        // The folder that leads to the current location is emphasized
+        let current_opt = ancestor_index
+            .checked_sub(1)
+            .and_then(|index| tab.location.path_opt()?.ancestors().nth(index));
+        for folder in folders.iter() {
+            let name = folder.file_name().map_or_else(
+                || folder.display().to_string(),
+                |x| x.to_string_lossy().to_string(),
+            );
+            let label = if current_opt == Some(folder.as_path()) {
+                text::heading(name)
+            } else {
+                text::body(name)
+            };
+            children.push(
+                menu_button!(
+                    widget::icon::from_name("folder-symbolic").size(16).icon(),
+                    horizontal_space().width(Length::Fixed(8.0)),
+                    label
+                )
+                .on_press(tab::Message::Location(
+                    tab.location.with_path(folder.clone()),
+                ))
+                .into(),
+            );
+        }
+    }
+    if children.is_empty() {
+        children.push(menu_button!(text::body(fl!("empty-folder"))).into());
+    }
+
+    container(widget::scrollable(column::with_children(children)).height(Length::Shrink))
+        .padding(1)
+        .style(|theme| {
+            let cosmic = theme.cosmic();
+            let component = &cosmic.background.component;
+            container::Style {
+                icon_color: Some(component.on.into()),
+                text_color: Some(component.on.into()),
+                background: Some(Background::Color(component.base.into())),
+                border: Border {
+                    radius: cosmic.radius_s().map(|x| x + 1.0).into(),
+                    width: 1.0,
+                    color: component.divider.into(),
+                },
+                ..Default::default()
+            }
+        })
+        .width(Length::Fixed(240.0))
+        .max_height(400.0)
+        .into()
+}
+
+pub fn location_context_menu<'a>(ancestor_index: usize) -> Element<'a, tab::Message> {
     //TODO: only add some of these when in App mode
     let children = vec![
         menu_button!(text::body(fl!("open-in-new-tab")))
-            .on_press(tab2::Message::LocationMenuAction(
-                LocationMenuAction2::OpenInNewTab(ancestor_index),
+            .on_press(tab::Message::LocationMenuAction(
+                LocationMenuAction::OpenInNewTab(ancestor_index),
             ))
             .into(),
         menu_button!(text::body(fl!("open-in-new-window")))
-            .on_press(tab2::Message::LocationMenuAction(
-                LocationMenuAction2::OpenInNewWindow(ancestor_index),
+            .on_press(tab::Message::LocationMenuAction(
+                LocationMenuAction::OpenInNewWindow(ancestor_index),
             ))
             .into(),
         divider::horizontal::light().into(),
         menu_button!(text::body(fl!("show-details")))
-            .on_press(tab2::Message::LocationMenuAction(
-                LocationMenuAction2::Preview(ancestor_index),
+            .on_press(tab::Message::LocationMenuAction(
+                LocationMenuAction::Preview(ancestor_index),
             ))
             .into(),
         divider::horizontal::light().into(),
         menu_button!(text::body(fl!("add-to-sidebar")))
-            .on_press(tab2::Message::LocationMenuAction(
-                LocationMenuAction2::AddToSidebar(ancestor_index),
+            .on_press(tab::Message::LocationMenuAction(
+                LocationMenuAction::AddToSidebar(ancestor_index),
             ))
             .into(),
     ];
@@ -0,0 +1,341 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{cosmic_theme, theme, widget, Element};
+use lofty::{
+    file::{AudioFile, TaggedFileExt},
+    tag::Accessor,
+};
+use mime_guess::{mime, Mime};
+use std::{
+    fs::{self, File},
+    io::{BufReader, Read},
+    path::Path,
+    process,
+    time::Duration,
+};
+
+use crate::{config::PreviewLimits, fl, thumbnailer};
+
+/// Camera, audio tag, and video stream details of a file
+#[derive(Clone, Debug, Default)]
+pub struct MediaMetadata {
+    /// Localized label and value pairs
+    pub entries: Vec<(String, String)>,
+    /// Latitude and longitude in degrees
+    pub gps_opt: Option<(f64, f64)>,
+}
+
+impl MediaMetadata {
+    fn push<T: ToString>(&mut self, label: String, value_opt: Option<T>) {
+        if let Some(value) = value_opt {
+            let value = value.to_string();
+            if !value.trim().is_empty() {
+                self.entries.push((label, value));
+            }
+        }
+    }
+
+    pub fn maps_url(&self) -> Option<String> {
+        let (lat, lon) = self.gps_opt?;
+        Some(format!(
+            "https://www.openstreetmap.org/?mlat={lat:.6}&mlon={lon:.6}#map=15/{lat:.6}/{lon:.6}"
+        ))
+    }
+
+    pub fn view<'a, Message: Clone + 'static>(
+        &'a self,
+        on_open_url: impl Fn(String) -> Message,
+    ) -> Element<'a, Message> {
+        let cosmic_theme::Spacing { space_xxxs, .. } = theme::active().cosmic().spacing;
+
+        let mut column = widget::column::with_capacity(self.entries.len() + 1).spacing(space_xxxs);
+        for (label, value) in self.entries.iter() {
+            column = column.push(widget::text::body(format!("{}: {}", label, value)));
+        }
+        if let Some(url) = self.maps_url() {
+            column = column.push(
+                widget::button::link(fl!("open-in-maps"))
+                    .padding(0)
+                    .trailing_icon(true)
+                    .on_press(on_open_url(url)),
+            );
+        }
+        column.into()
+    }
+}
+
+/// Check if metadata can be loaded for this mime type
+pub fn supported(mime: &Mime) -> bool {
+    mime.type_() == mime::IMAGE || mime.type_() == mime::AUDIO || mime.type_() == mime::VIDEO
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+fn gps_degrees(exif: &exif::Exif, tag: exif::Tag, ref_tag: exif::Tag) -> Option<f64> {
+    let field = exif.get_field(tag, exif::In::PRIMARY)?;
+    let exif::Value::Rational(parts) = &field.value else {
+        return None;
+    };
+    let mut degrees = 0.0;
+    for (part, scale) in parts.iter().zip([1.0, 60.0, 3600.0]) {
+        degrees += part.to_f64() / scale;
+    }
+    let reference = exif
+        .get_field(ref_tag, exif::In::PRIMARY)?
+        .display_value()
+        .to_string();
+    if reference.contains('S') || reference.contains('W') {
+        degrees = -degrees;
+    }
+    Some(degrees)
+}
+
+fn load_exif(path: &Path, metadata: &mut MediaMetadata) -> Result<(), String> {
+    let file = File::open(path).map_err(|err| format!("failed to open {:?}: {}", path, err))?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut BufReader::new(file))
+        .map_err(|err| format!("failed to read EXIF of {:?}: {}", path, err))?;
+    let value = |tag| {
+        exif.get_field(tag, exif::In::PRIMARY)
+            .map(|field| field.display_value().with_unit(&exif).to_string())
+    };
+
+    let camera = match (value(exif::Tag::Make), value(exif::Tag::Model)) {
+        (Some(make), Some(model)) => {
+            // Many models already start with the make
+            let make = make.trim_matches('"').to_string();
+            let model = model.trim_matches('"').to_string();
+            if model.starts_with(&make) {
+                Some(model)
+            } else {
+                Some(format!("{} {}", make, model))
+            }
+        }
+        (make, model) => model.or(make).map(|x| x.trim_matches('"').to_string()),
+    };
+    metadata.push(fl!("metadata-camera"), camera);
+    metadata.push(
+        fl!("metadata-lens"),
+        value(exif::Tag::LensModel).map(|x| x.trim_matches('"').to_string()),
+    );
+    metadata.push(fl!("metadata-exposure"), value(exif::Tag::ExposureTime));
+    metadata.push(fl!("metadata-aperture"), value(exif::Tag::FNumber));
+    metadata.push(
+        fl!("metadata-iso"),
+        value(exif::Tag::PhotographicSensitivity),
+    );
+    metadata.push(fl!("metadata-focal-length"), value(exif::Tag::FocalLength));
+    metadata.push(fl!("metadata-taken"), value(exif::Tag::DateTimeOriginal));
+
+    if let (Some(lat), Some(lon)) = (
+        gps_degrees(&exif, exif::Tag::GPSLatitude, exif::Tag::GPSLatitudeRef),
+        gps_degrees(&exif, exif::Tag::GPSLongitude, exif::Tag::GPSLongitudeRef),
+    ) {
+        metadata.push(
+            fl!("metadata-location"),
+            Some(format!("{lat:.5}, {lon:.5}")),
+        );
+        metadata.gps_opt = Some((lat, lon));
+    }
+
+    Ok(())
+}
+
+fn load_audio(path: &Path, metadata: &mut MediaMetadata) -> Result<(), String> {
+    let tagged_file = lofty::read_from_path(path)
+        .map_err(|err| format!("failed to read tags of {:?}: {}", path, err))?;
+
+    if let Some(tag) = tagged_file
+        .primary_tag()
+        .or_else(|| tagged_file.first_tag())
+    {
+        metadata.push(fl!("media-title"), tag.title());
+        metadata.push(fl!("media-artist"), tag.artist());
+        metadata.push(fl!("media-album"), tag.album());
+        metadata.push(fl!("metadata-track"), tag.track());
+        metadata.push(fl!("metadata-year"), tag.year());
+        metadata.push(fl!("metadata-genre"), tag.genre());
+    }
+
+    let properties = tagged_file.properties();
+    metadata.push(
+        fl!("metadata-duration"),
+        Some(format_duration(properties.duration())),
+    );
+    metadata.push(
+        fl!("metadata-bitrate"),
+        properties
+            .audio_bitrate()
+            .map(|bitrate| format!("{} kbps", bitrate)),
+    );
+    metadata.push(
+        fl!("metadata-sample-rate"),
+        properties
+            .sample_rate()
+            .map(|sample_rate| format!("{} Hz", sample_rate)),
+    );
+    metadata.push(fl!("metadata-channels"), properties.channels());
+
+    Ok(())
+}
+
+// Parse the key=value output of ffprobe
+fn parse_ffprobe(output: &str, metadata: &mut MediaMetadata) {
+    let mut width_opt = None;
+    let mut height_opt = None;
+    let mut codec_opt = None;
+    let mut duration_opt = None;
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if value == "N/A" {
+            continue;
+        }
+        match key {
+            "width" => width_opt = value.parse::<u32>().ok(),
+            "height" => height_opt = value.parse::<u32>().ok(),
+            "codec_long_name" => codec_opt = Some(value.to_string()),
+            "codec_name" => {
+                if codec_opt.is_none() {
+                    codec_opt = Some(value.to_string());
+                }
+            }
+            "duration" => {
+                duration_opt = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|secs| secs.is_finite() && *secs >= 0.0)
+                    .map(Duration::from_secs_f64)
+            }
+            _ => {}
+        }
+    }
+
+    if let (Some(width), Some(height)) = (width_opt, height_opt) {
+        metadata.push(
+            fl!("metadata-resolution"),
+            Some(format!("{}x{}", width, height)),
+        );
+    }
+    metadata.push(fl!("media-video-codec"), codec_opt);
+    metadata.push(fl!("metadata-duration"), duration_opt.map(format_duration));
+}
+
+fn load_video(
+    path: &Path,
+    metadata: &mut MediaMetadata,
+    limits: PreviewLimits,
+) -> Result<(), String> {
+    let mut child = process::Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-select_streams")
+        .arg("v:0")
+        .arg("-show_entries")
+        .arg("stream=codec_name,codec_long_name,width,height:format=duration")
+        .arg("-of")
+        .arg("default=noprint_wrappers=1")
+        .arg(path)
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::null())
+        .spawn()
+        .map_err(|err| format!("failed to run ffprobe for {:?}: {}", path, err))?;
+    // Corrupted files can keep ffprobe busy indefinitely
+    let status = thumbnailer::wait_timeout(&mut child, limits.timeout())
+        .map_err(|err| format!("failed to run ffprobe for {:?}: {}", path, err))?
+        .ok_or_else(|| {
+            thumbnailer::record_skip(
+                path,
+                fs::metadata(path).and_then(|x| x.modified()).ok(),
+                fl!("preview-timed-out", seconds = limits.timeout_secs),
+            );
+            format!("ffprobe timed out for {:?}", path)
+        })?;
+    if !status.success() {
+        return Err(format!("failed to run ffprobe for {:?}: {}", path, status));
+    }
+    let mut output = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        stdout
+            .read_to_string(&mut output)
+            .map_err(|err| format!("failed to read ffprobe output for {:?}: {}", path, err))?;
+    }
+    parse_ffprobe(&output, metadata);
+    Ok(())
+}
+
+/// Load metadata of the file at `path`, errors result in empty metadata
+pub fn load(path: &Path, mime: &Mime, limits: PreviewLimits) -> MediaMetadata {
+    let mut metadata = MediaMetadata::default();
+    let modified_opt = fs::metadata(path).and_then(|x| x.modified()).ok();
+    let res = if let Some(reason) = thumbnailer::skip_reason(path, modified_opt) {
+        Err(format!("not loading metadata of {:?}: {}", path, reason))
+    } else if mime.type_() == mime::IMAGE {
+        load_exif(path, &mut metadata)
+    } else if mime.type_() == mime::AUDIO {
+        load_audio(path, &mut metadata)
+    } else if mime.type_() == mime::VIDEO {
+        load_video(path, &mut metadata, limits)
+    } else {
+        Ok(())
+    };
+    if let Err(err) = res {
+        // Most files simply have no metadata
+        log::debug!("{}", err);
+    }
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_duration, parse_ffprobe, MediaMetadata};
+
+    #[test]
+    fn ffprobe_output() {
+        let mut metadata = MediaMetadata::default();
+        parse_ffprobe(
+            "codec_name=h264\ncodec_long_name=H.264 / AVC / MPEG-4 AVC / MPEG-4 part 10\nwidth=1920\nheight=1080\nduration=3725.500000\n",
+            &mut metadata,
+        );
+        let values: Vec<&str> = metadata.entries.iter().map(|x| x.1.as_str()).collect();
+        assert_eq!(
+            values,
+            [
+                "1920x1080",
+                "H.264 / AVC / MPEG-4 AVC / MPEG-4 part 10",
+                "1:02:05"
+            ]
+        );
+    }
+
+    #[test]
+    fn ffprobe_output_unavailable() {
+        let mut metadata = MediaMetadata::default();
+        parse_ffprobe("codec_name=vp9\nduration=N/A\n", &mut metadata);
+        assert_eq!(1, metadata.entries.len());
+        assert_eq!("vp9", metadata.entries[0].1);
+        assert_eq!("0:59", format_duration(Duration::from_secs(59)));
+    }
+
+    #[test]
+    fn maps_url() {
+        let metadata = MediaMetadata {
+            entries: Vec::new(),
+            gps_opt: Some((52.516275, -13.377704)),
+        };
+        assert_eq!(
+            metadata.maps_url().as_deref(),
+            Some("https://www.openstreetmap.org/?mlat=52.516275&mlon=-13.377704#map=15/52.516275/-13.377704")
+        );
+    }
+}
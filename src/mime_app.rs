@@ -34,6 +34,97 @@ pub fn exec_to_command(exec: &str, path_opt: Option<OsString>) -> Option<process
     Some(command)
 }
 
+/// Build the commands that open `paths` with `exec`, which is a single command if it takes a
+/// list of files with %F or %U and one command per path otherwise
+pub fn exec_to_commands(exec: &str, paths: &[OsString]) -> Option<Vec<process::Command>> {
+    let takes_list = ["%F", "%U"]
+        .iter()
+        .any(|code| exec.split_whitespace().any(|arg| arg == *code));
+    if !takes_list || paths.len() < 2 {
+        return paths
+            .iter()
+            .map(|path| exec_to_command(exec, Some(path.clone())))
+            .collect();
+    }
+    let args_vec: Vec<String> = shlex::split(exec)?;
+    let mut args = args_vec.iter();
+    let mut command = process::Command::new(args.next()?);
+    for arg in args {
+        match arg.as_str() {
+            "%F" | "%U" => {
+                command.args(paths);
+            }
+            "%f" | "%u" => {
+                command.args(paths.first());
+            }
+            _ if arg.starts_with('%') => {
+                log::warn!("unsupported Exec code {:?} in {:?}", arg, exec);
+                return None;
+            }
+            _ => {
+                command.arg(arg);
+            }
+        }
+    }
+    Some(vec![command])
+}
+
+/// Build a command from a command line typed by the user, adding the path
+/// at the end if the command line has no field code for it
+pub fn custom_command(exec: &str, path: OsString) -> Option<process::Command> {
+    if has_field_code(exec) {
+        exec_to_command(exec, Some(path))
+    } else {
+        let mut command = exec_to_command(exec, None)?;
+        command.arg(path);
+        Some(command)
+    }
+}
+
+fn has_field_code(exec: &str) -> bool {
+    ["%f", "%F", "%u", "%U"]
+        .iter()
+        .any(|code| exec.split_whitespace().any(|arg| arg == *code))
+}
+
+/// Create a desktop entry for a custom command line so it can be set as
+/// the default application of `mime`, returning its id
+pub fn create_custom_app(exec: &str, mime: &Mime) -> io::Result<String> {
+    let args = shlex::split(exec)
+        .filter(|args| !args.is_empty())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid command"))?;
+    let program = PathBuf::from(&args[0]);
+    let program_name = program
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let id = format!(
+        "commander-custom-{}",
+        program_name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect::<String>()
+    );
+
+    let dir = dirs::data_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?
+        .join("applications");
+    fs::create_dir_all(&dir)?;
+    let exec = if has_field_code(exec) {
+        exec.to_string()
+    } else {
+        format!("{} %f", exec)
+    };
+    fs::write(
+        dir.join(format!("{}.desktop", id)),
+        format!(
+            "[Desktop Entry]\nType=Application\nName={}\nExec={}\nMimeType={};\nTerminal=false\n",
+            program_name, exec, mime
+        ),
+    )?;
+    Ok(id)
+}
+
 #[derive(Clone, Debug)]
 pub struct MimeApp {
     pub id: String,
@@ -49,6 +140,11 @@ impl MimeApp {
     pub fn command(&self, path_opt: Option<OsString>) -> Option<process::Command> {
         exec_to_command(self.exec.as_deref()?, path_opt)
     }
+
+    /// Commands that open all of `paths` with this app
+    pub fn commands(&self, paths: &[OsString]) -> Option<Vec<process::Command>> {
+        exec_to_commands(self.exec.as_deref()?, paths)
+    }
 }
 
 // This allows usage of MimeApp in a dropdown
@@ -87,6 +183,7 @@ fn filename_eq(path_opt: &Option<PathBuf>, filename: &str) -> bool {
 }
 
 pub struct MimeAppCache {
+    apps: Vec<MimeApp>,
     cache: HashMap<Mime, Vec<MimeApp>>,
     icons: HashMap<Mime, Vec<widget::icon::Handle>>,
     terminals: Vec<MimeApp>,
@@ -95,6 +192,7 @@ pub struct MimeAppCache {
 impl MimeAppCache {
     pub fn new() -> Self {
         let mut mime_app_cache = Self {
+            apps: Vec::new(),
             cache: HashMap::new(),
             icons: HashMap::new(),
             terminals: Vec::new(),
@@ -113,6 +211,7 @@ impl MimeAppCache {
 
         let start = Instant::now();
 
+        self.apps.clear();
         self.cache.clear();
         self.icons.clear();
         self.terminals.clear();
@@ -124,6 +223,9 @@ impl MimeAppCache {
         //TODO: hashmap for all apps by id?
         let all_apps = desktop::load_applications(locale, false);
         for app in all_apps.iter() {
+            if app.exec.is_some() {
+                self.apps.push(MimeApp::from(app));
+            }
             for mime in app.mime_types.iter() {
                 let apps = self
                     .cache
@@ -255,6 +357,8 @@ impl MimeAppCache {
         }
 
         // Sort apps by name
+        self.apps
+            .sort_by(|a, b| LANGUAGE_SORTER.compare(&a.name, &b.name));
         for apps in self.cache.values_mut() {
             apps.sort_by(|a, b| match (a.is_default, b.is_default) {
                 (true, false) => Ordering::Less,
@@ -281,6 +385,16 @@ impl MimeAppCache {
         self.cache.get(key).unwrap_or(&EMPTY)
     }
 
+    /// Applications registered for `key`, followed by all other applications
+    pub fn open_with(&self, key: &Mime) -> impl Iterator<Item = &MimeApp> {
+        let registered = self.get(key);
+        registered.iter().chain(
+            self.apps
+                .iter()
+                .filter(move |app| !registered.iter().any(|x| x.id == app.id)),
+        )
+    }
+
     pub fn icons(&self, key: &Mime) -> &[widget::icon::Handle] {
         static EMPTY: Vec<widget::icon::Handle> = Vec::new();
         self.icons.get(key).unwrap_or(&EMPTY)
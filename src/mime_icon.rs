@@ -3,7 +3,14 @@
 use cosmic::widget::icon;
 use mime_guess::Mime;
 use once_cell::sync::Lazy;
-use std::{collections::HashMap, path::Path, sync::Mutex};
+use std::{
+    collections::HashMap,
+    ffi::CString,
+    fs::{self, Metadata},
+    os::unix::{ffi::OsStrExt, fs::MetadataExt},
+    path::Path,
+    sync::Mutex,
+};
 
 pub const FALLBACK_MIME_ICON: &str = "text-x-generic";
 
@@ -73,3 +80,64 @@ pub fn mime_icon(mime: Mime, size: u16) -> icon::Handle {
         None => icon::from_name(FALLBACK_MIME_ICON).size(size).handle(),
     }
 }
+
+/// Small icon drawn over the corner of an item icon
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Emblem {
+    BrokenSymlink,
+    Launcher,
+    MountPoint,
+    Symlink,
+    Unreadable,
+}
+
+impl Emblem {
+    pub fn icon_name(self) -> &'static str {
+        match self {
+            Self::BrokenSymlink => "action-unavailable-symbolic",
+            Self::Launcher => "system-run-symbolic",
+            Self::MountPoint => "view-pin-symbolic",
+            Self::Symlink => "emblem-symbolic-link-symbolic",
+            Self::Unreadable => "changes-prevent-symbolic",
+        }
+    }
+}
+
+/// Emblems of the item at `path`, where `metadata` belongs to the target of a symlink unless the
+/// link is broken
+pub fn emblems(path: &Path, metadata: &Metadata, mime: &Mime) -> Vec<Emblem> {
+    let mut emblems = Vec::new();
+    let symlink = path.is_symlink();
+    if symlink {
+        if path.exists() {
+            emblems.push(Emblem::Symlink);
+        } else {
+            emblems.push(Emblem::BrokenSymlink);
+            return emblems;
+        }
+    }
+    if !readable(path) {
+        emblems.push(Emblem::Unreadable);
+    }
+    // Folders on another device than their parent are mount points
+    if metadata.is_dir()
+        && !symlink
+        && path
+            .parent()
+            .and_then(|parent| fs::metadata(parent).ok())
+            .map_or(false, |parent| parent.dev() != metadata.dev())
+    {
+        emblems.push(Emblem::MountPoint);
+    }
+    if *mime == "application/x-desktop" {
+        emblems.push(Emblem::Launcher);
+    }
+    emblems
+}
+
+fn readable(path: &Path) -> bool {
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return true;
+    };
+    unsafe { libc::access(path.as_ptr(), libc::R_OK) == 0 }
+}
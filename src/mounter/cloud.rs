@@ -0,0 +1,458 @@
+//! Mounts rclone remotes of Google Drive, OneDrive and S3 with `rclone mount`. Signing in to
+//! the providers (OAuth, access keys) is not implemented, the remotes have to be set up with
+//! `rclone config` first. Only the password of an encrypted rclone config is asked for and can
+//! be kept in the keyring.
+
+use cosmic::{
+    iced::{futures::SinkExt, stream, Subscription},
+    widget, Task,
+};
+use std::{
+    any::TypeId,
+    fs,
+    future::pending,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{self, Arc},
+};
+use tokio::sync::{mpsc, Mutex};
+
+use super::{
+    filesystem_type, mount_point, run, Mounter, MounterAuth, MounterItem, MounterItems,
+    MounterMessage,
+};
+use crate::{config::IconSizes, err_str, fl, tab};
+
+/// Cloud storage provider of an rclone remote
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Provider {
+    GoogleDrive,
+    OneDrive,
+    S3,
+}
+
+impl Provider {
+    fn from_rclone_type(kind: &str) -> Option<Self> {
+        match kind {
+            "drive" => Some(Self::GoogleDrive),
+            "onedrive" => Some(Self::OneDrive),
+            "s3" => Some(Self::S3),
+            _ => None,
+        }
+    }
+
+    fn icon_name(self) -> &'static str {
+        match self {
+            Self::GoogleDrive => "goa-account-google",
+            Self::OneDrive => "goa-account-msn",
+            Self::S3 => "folder-remote",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Item {
+    remote: String,
+    provider: Provider,
+    mount_point_opt: Option<PathBuf>,
+}
+
+impl Item {
+    pub fn name(&self) -> String {
+        self.remote.clone()
+    }
+
+    pub fn is_mounted(&self) -> bool {
+        self.mount_point_opt.is_some()
+    }
+
+    pub fn icon(&self, symbolic: bool) -> Option<widget::icon::Handle> {
+        Some(if symbolic {
+            widget::icon::from_name("folder-remote-symbolic")
+                .size(16)
+                .handle()
+        } else {
+            widget::icon::from_name(self.provider.icon_name()).handle()
+        })
+    }
+
+    pub fn path(&self) -> Option<PathBuf> {
+        self.mount_point_opt.clone()
+    }
+
+    pub fn is_remote(&self) -> bool {
+        true
+    }
+
+    pub fn can_eject(&self) -> bool {
+        false
+    }
+}
+
+// OAuth tokens and keys are kept by rclone in its configuration, which may be encrypted with a
+// password that is kept in the keyring
+const KEYRING_ATTRIBUTES: [&str; 4] = ["application", "commander", "service", "rclone"];
+
+fn keyring_password() -> Option<String> {
+    run(
+        Command::new("secret-tool")
+            .arg("lookup")
+            .args(KEYRING_ATTRIBUTES),
+        None,
+    )
+    .ok()
+    .filter(|password| !password.is_empty())
+}
+
+fn store_keyring_password(password: &str) -> Result<(), String> {
+    run(
+        Command::new("secret-tool")
+            .arg("store")
+            .arg("--label")
+            .arg(fl!("cloud-keyring-label"))
+            .args(KEYRING_ATTRIBUTES),
+        Some(password),
+    )
+    .map(|_| ())
+}
+
+fn rclone(password_opt: Option<&str>) -> Command {
+    let mut command = Command::new("rclone");
+    // Never prompt on the terminal, the password is asked for with a dialog instead
+    command.arg("--ask-password=false");
+    if let Some(password) = password_opt {
+        command.env("RCLONE_CONFIG_PASS", password);
+    }
+    command
+}
+
+fn is_password_error(err: &str) -> bool {
+    err.contains("decrypt") || err.contains("RCLONE_CONFIG_PASS")
+}
+
+/// Parse `rclone listremotes --long` output like "gdrive:  drive"
+fn parse_remotes(output: &str) -> Vec<(String, Provider)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (remote, kind) = line.split_once(':')?;
+            Some((remote.to_string(), Provider::from_rclone_type(kind.trim())?))
+        })
+        .collect()
+}
+
+fn scan(password_opt: Option<&str>) -> Result<Vec<Item>, String> {
+    let output = run(rclone(password_opt).arg("listremotes").arg("--long"), None)?;
+    let mut items = Vec::new();
+    for (remote, provider) in parse_remotes(&output) {
+        let mount_point = mount_point("cloud", &remote);
+        let mount_point_opt = (filesystem_type(&mount_point).as_deref() == Some("fuse.rclone"))
+            .then_some(mount_point);
+        items.push(Item {
+            remote,
+            provider,
+            mount_point_opt,
+        });
+    }
+    Ok(items)
+}
+
+fn mount(item: &Item, password_opt: Option<&str>) -> Result<PathBuf, String> {
+    let mount_point = mount_point("cloud", &item.remote);
+    fs::create_dir_all(&mount_point).map_err(err_str)?;
+    // Without a VFS cache files are streamed, so transfers of the operation queue are not buffered
+    // on disk before they are uploaded
+    if let Err(err) = run(
+        rclone(password_opt)
+            .arg("mount")
+            .arg(format!("{}:", item.remote))
+            .arg(&mount_point)
+            .arg("--daemon")
+            .arg("--vfs-cache-mode")
+            .arg("off"),
+        None,
+    ) {
+        let _ = fs::remove_dir(&mount_point);
+        return Err(err);
+    }
+    Ok(mount_point)
+}
+
+/// Escape the characters that have a meaning in rclone filter globs
+fn glob_escape(term: &str) -> String {
+    let mut escaped = String::with_capacity(term.len());
+    for c in term.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '{' | '}' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// List the names containing `term` below `relative` on the remote, which the providers filter
+/// on the server instead of the mount listing every directory
+fn search(
+    remote: &str,
+    relative: &Path,
+    term: &str,
+    password_opt: Option<&str>,
+) -> Result<Vec<PathBuf>, String> {
+    let output = run(
+        rclone(password_opt)
+            .arg("lsf")
+            .arg("--recursive")
+            .arg("--ignore-case")
+            .arg("--include")
+            .arg(format!("*{}*", glob_escape(term)))
+            .arg(format!("{}:{}", remote, relative.display())),
+        None,
+    )?;
+    Ok(output
+        .lines()
+        .map(|line| PathBuf::from(line.trim_end_matches('/')))
+        .collect())
+}
+
+fn unmount(item: &Item) -> Result<(), String> {
+    let Some(mount_point) = &item.mount_point_opt else {
+        return Ok(());
+    };
+    run(Command::new("fusermount").arg("-u").arg(mount_point), None)?;
+    let _ = fs::remove_dir(mount_point);
+    Ok(())
+}
+
+enum Event {
+    Items(MounterItems),
+    MountResult(MounterItem, Result<bool, String>),
+    NetworkAuth(String, MounterAuth, mpsc::Sender<MounterAuth>),
+}
+
+struct State {
+    items: Vec<Item>,
+    password_opt: Option<String>,
+}
+
+impl State {
+    fn rescan(&mut self, event_tx: &mpsc::UnboundedSender<Event>) {
+        match scan(self.password_opt.as_deref()) {
+            Ok(items) => self.items = items,
+            Err(err) => log::warn!("failed to list rclone remotes: {}", err),
+        }
+        let items = self.items.iter().cloned().map(MounterItem::Cloud).collect();
+        event_tx.send(Event::Items(items)).unwrap();
+    }
+}
+
+/// Mounts Google Drive, OneDrive and S3 remotes configured in rclone
+pub struct Cloud {
+    state: Arc<sync::Mutex<State>>,
+    event_tx: mpsc::UnboundedSender<Event>,
+    event_rx: Arc<Mutex<mpsc::UnboundedReceiver<Event>>>,
+}
+
+impl Cloud {
+    pub fn new() -> Self {
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        Self {
+            state: Arc::new(sync::Mutex::new(State {
+                items: Vec::new(),
+                password_opt: None,
+            })),
+            event_tx,
+            event_rx: Arc::new(Mutex::new(event_rx)),
+        }
+    }
+}
+
+impl Mounter for Cloud {
+    fn items(&self, _sizes: IconSizes) -> Option<MounterItems> {
+        Some(
+            self.state
+                .lock()
+                .unwrap()
+                .items
+                .iter()
+                .cloned()
+                .map(MounterItem::Cloud)
+                .collect(),
+        )
+    }
+
+    fn mount(&self, item: MounterItem) -> Task<()> {
+        let MounterItem::Cloud(item) = item else {
+            return Task::none();
+        };
+        let state = self.state.clone();
+        let event_tx = self.event_tx.clone();
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    log::info!("mount {:?}", item.remote);
+                    let password_opt = state.lock().unwrap().password_opt.clone();
+                    let mut res = mount(&item, password_opt.as_deref());
+                    if matches!(&res, Err(err) if is_password_error(err)) {
+                        let auth = MounterAuth {
+                            message: format!(
+                                "{}\n{}",
+                                fl!("cloud-password-title"),
+                                fl!("cloud-password-body", remote = item.remote.as_str())
+                            ),
+                            username_opt: None,
+                            domain_opt: None,
+                            password_opt: Some(String::new()),
+                            remember_opt: Some(false),
+                            anonymous_opt: None,
+                        };
+                        let (auth_tx, mut auth_rx) = mpsc::channel(1);
+                        event_tx
+                            .send(Event::NetworkAuth(item.remote.clone(), auth, auth_tx))
+                            .unwrap();
+                        let Some(auth) = auth_rx.blocking_recv() else {
+                            event_tx
+                                .send(Event::MountResult(MounterItem::Cloud(item), Ok(false)))
+                                .unwrap();
+                            return;
+                        };
+                        let password = auth.password_opt.unwrap_or_default();
+                        res = mount(&item, Some(&password));
+                        if res.is_ok() {
+                            if auth.remember_opt == Some(true) {
+                                if let Err(err) = store_keyring_password(&password) {
+                                    log::warn!("failed to store rclone password: {}", err);
+                                }
+                            }
+                            state.lock().unwrap().password_opt = Some(password);
+                        }
+                    }
+
+                    let mut state = state.lock().unwrap();
+                    state.rescan(&event_tx);
+                    event_tx
+                        .send(Event::MountResult(
+                            MounterItem::Cloud(item),
+                            res.map(|_| true),
+                        ))
+                        .unwrap();
+                })
+                .await
+                .unwrap();
+            },
+            |x| x,
+        )
+    }
+
+    fn eject(&self, item: MounterItem) -> Task<()> {
+        self.unmount(item)
+    }
+
+    fn network_drive(&self, _uri: String) -> Task<()> {
+        Task::none()
+    }
+
+    fn network_scan(
+        &self,
+        _uri: &str,
+        _sizes: IconSizes,
+    ) -> Option<Result<Vec<tab::Item>, String>> {
+        None
+    }
+
+    fn network_supported(&self) -> bool {
+        false
+    }
+
+    fn search(&self, path: &Path, term: &str) -> Option<Result<Vec<PathBuf>, String>> {
+        // Do not hold the lock while rclone is searching
+        let (remote, relative, password_opt) = {
+            let state = self.state.lock().unwrap();
+            let (item, relative) = state.items.iter().find_map(|item| {
+                let relative = path.strip_prefix(item.mount_point_opt.as_ref()?).ok()?;
+                Some((item, relative))
+            })?;
+            (
+                item.remote.clone(),
+                relative.to_path_buf(),
+                state.password_opt.clone(),
+            )
+        };
+        Some(
+            search(&remote, &relative, term, password_opt.as_deref())
+                .map(|paths| paths.into_iter().map(|result| path.join(result)).collect()),
+        )
+    }
+
+    fn unmount(&self, item: MounterItem) -> Task<()> {
+        let MounterItem::Cloud(item) = item else {
+            return Task::none();
+        };
+        let state = self.state.clone();
+        let event_tx = self.event_tx.clone();
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    log::info!("unmount {:?}", item.remote);
+                    if let Err(err) = unmount(&item) {
+                        log::warn!("failed to unmount {:?}: {}", item.remote, err);
+                    }
+                    state.lock().unwrap().rescan(&event_tx);
+                })
+                .await
+                .unwrap();
+            },
+            |x| x,
+        )
+    }
+
+    fn subscription(&self) -> Subscription<MounterMessage> {
+        let state = self.state.clone();
+        let event_tx = self.event_tx.clone();
+        let event_rx = self.event_rx.clone();
+        Subscription::run_with_id(
+            TypeId::of::<Self>(),
+            stream::channel(1, |mut output| async move {
+                tokio::task::spawn_blocking(move || {
+                    let mut state = state.lock().unwrap();
+                    state.password_opt = keyring_password();
+                    state.rescan(&event_tx);
+                });
+                while let Some(event) = event_rx.lock().await.recv().await {
+                    let message = match event {
+                        Event::Items(items) => MounterMessage::Items(items),
+                        Event::MountResult(item, res) => MounterMessage::MountResult(item, res),
+                        Event::NetworkAuth(uri, auth, auth_tx) => {
+                            MounterMessage::NetworkAuth(uri, auth, auth_tx)
+                        }
+                    };
+                    output.send(message).await.unwrap();
+                }
+                pending().await
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glob_escape, parse_remotes, Provider};
+
+    #[test]
+    fn parse_rclone_remotes() {
+        let output = "gdrive:   drive\nwork:     onedrive\nbucket:   s3\nbox:      box\n";
+        assert_eq!(
+            parse_remotes(output),
+            vec![
+                ("gdrive".to_string(), Provider::GoogleDrive),
+                ("work".to_string(), Provider::OneDrive),
+                ("bucket".to_string(), Provider::S3),
+            ]
+        );
+    }
+
+    #[test]
+    fn escape_search_globs() {
+        assert_eq!(glob_escape("report"), "report");
+        assert_eq!(glob_escape("a*b?[c]{d}"), "a\\*b\\?\\[c\\]\\{d\\}");
+    }
+}
@@ -0,0 +1,461 @@
+use cosmic::{
+    iced::{futures::SinkExt, stream, Subscription},
+    widget, Task,
+};
+use std::{
+    any::TypeId,
+    fs,
+    future::pending,
+    io::Read,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{self, Arc},
+};
+use tokio::sync::{mpsc, Mutex};
+
+use super::{mount_point, run, Mounter, MounterAuth, MounterItem, MounterItems, MounterMessage};
+use crate::{config::IconSizes, err_str, fl, tab};
+
+const LUKS_MAGIC: &[u8; 6] = b"LUKS\xba\xbe";
+
+/// Kind of an encrypted container file or directory
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContainerKind {
+    Luks,
+    VeraCrypt,
+    Gocryptfs,
+}
+
+impl ContainerKind {
+    pub fn detect(path: &Path) -> Option<Self> {
+        if path.is_dir() {
+            return path
+                .join("gocryptfs.conf")
+                .is_file()
+                .then_some(Self::Gocryptfs);
+        }
+
+        let mut magic = [0; LUKS_MAGIC.len()];
+        if fs::File::open(path)
+            .and_then(|mut file| file.read_exact(&mut magic))
+            .is_ok()
+            && &magic == LUKS_MAGIC
+        {
+            return Some(Self::Luks);
+        }
+
+        // VeraCrypt containers are indistinguishable from random data, so use the extension
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("hc" | "tc") => Some(Self::VeraCrypt),
+            _ => None,
+        }
+    }
+}
+
+/// Block devices set up by udisks2 for an unlocked LUKS or VeraCrypt container
+#[derive(Clone, Debug)]
+struct Devices {
+    loop_device: String,
+    cleartext_device: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Item {
+    path: PathBuf,
+    kind: ContainerKind,
+    name: String,
+    mount_point_opt: Option<PathBuf>,
+    devices_opt: Option<Devices>,
+}
+
+impl Item {
+    pub fn new(path: PathBuf, kind: ContainerKind) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        Self {
+            path,
+            kind,
+            name,
+            mount_point_opt: None,
+            devices_opt: None,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn is_mounted(&self) -> bool {
+        self.mount_point_opt.is_some()
+    }
+
+    pub fn icon(&self, symbolic: bool) -> Option<widget::icon::Handle> {
+        Some(if symbolic {
+            widget::icon::from_name("drive-harddisk-encrypted-symbolic")
+                .size(16)
+                .handle()
+        } else {
+            widget::icon::from_name("drive-harddisk-encrypted").handle()
+        })
+    }
+
+    pub fn path(&self) -> Option<PathBuf> {
+        self.mount_point_opt.clone()
+    }
+
+    pub fn is_remote(&self) -> bool {
+        false
+    }
+
+    pub fn can_eject(&self) -> bool {
+        false
+    }
+}
+
+/// Last word of udisksctl output like "Mapped file x as /dev/loop0."
+fn udisksctl_device(output: &str) -> Result<String, String> {
+    output
+        .split_whitespace()
+        .last()
+        .map(|word| word.trim_end_matches('.').to_string())
+        .filter(|device| device.starts_with("/dev/"))
+        .ok_or_else(|| format!("unexpected udisksctl output {:?}", output))
+}
+
+fn unlock(item: &mut Item, passphrase: &str) -> Result<(), String> {
+    match item.kind {
+        ContainerKind::Luks | ContainerKind::VeraCrypt => {
+            // udisks2 unlocks both LUKS and VeraCrypt through cryptsetup
+            let loop_device = udisksctl_device(&run(
+                Command::new("udisksctl")
+                    .arg("loop-setup")
+                    .arg("--file")
+                    .arg(&item.path),
+                None,
+            )?)?;
+            let mut devices = Devices {
+                loop_device,
+                cleartext_device: None,
+            };
+            let res = run(
+                Command::new("udisksctl")
+                    .arg("unlock")
+                    .arg("--block-device")
+                    .arg(&devices.loop_device)
+                    .arg("--key-file")
+                    .arg("/dev/stdin"),
+                Some(passphrase),
+            )
+            .and_then(|output| udisksctl_device(&output))
+            .and_then(|cleartext_device| {
+                devices.cleartext_device = Some(cleartext_device.clone());
+                run(
+                    Command::new("udisksctl")
+                        .arg("mount")
+                        .arg("--block-device")
+                        .arg(&cleartext_device),
+                    None,
+                )
+            });
+            match res {
+                Ok(output) => {
+                    // Output is "Mounted /dev/dm-0 at /media/user/label"
+                    let mount_point = output
+                        .trim()
+                        .split_once(" at ")
+                        .map(|(_, path)| PathBuf::from(path.trim_end_matches('.')))
+                        .ok_or_else(|| format!("unexpected udisksctl output {:?}", output))?;
+                    item.mount_point_opt = Some(mount_point);
+                    item.devices_opt = Some(devices);
+                    Ok(())
+                }
+                Err(err) => {
+                    // Do not leave the container unlocked or attached after a failure
+                    if let Err(lock_err) = lock_devices(&devices) {
+                        log::warn!("failed to clean up {:?}: {}", item.path, lock_err);
+                    }
+                    Err(err)
+                }
+            }
+        }
+        ContainerKind::Gocryptfs => {
+            let mount_point = mount_point("crypt", &item.name);
+            fs::create_dir_all(&mount_point).map_err(err_str)?;
+            // gocryptfs reads the password from stdin when it is not a terminal
+            if let Err(err) = run(
+                Command::new("gocryptfs")
+                    .arg("-q")
+                    .arg("--")
+                    .arg(&item.path)
+                    .arg(&mount_point),
+                Some(passphrase),
+            ) {
+                let _ = fs::remove_dir(&mount_point);
+                return Err(err);
+            }
+            item.mount_point_opt = Some(mount_point);
+            Ok(())
+        }
+    }
+}
+
+fn lock_devices(devices: &Devices) -> Result<(), String> {
+    if let Some(cleartext_device) = &devices.cleartext_device {
+        // The cleartext device may not be mounted if mounting failed
+        let _ = run(
+            Command::new("udisksctl")
+                .arg("unmount")
+                .arg("--block-device")
+                .arg(cleartext_device),
+            None,
+        );
+        run(
+            Command::new("udisksctl")
+                .arg("lock")
+                .arg("--block-device")
+                .arg(&devices.loop_device),
+            None,
+        )?;
+    }
+    run(
+        Command::new("udisksctl")
+            .arg("loop-delete")
+            .arg("--block-device")
+            .arg(&devices.loop_device),
+        None,
+    )?;
+    Ok(())
+}
+
+fn lock(item: &Item) -> Result<(), String> {
+    match item.kind {
+        ContainerKind::Luks | ContainerKind::VeraCrypt => match &item.devices_opt {
+            Some(devices) => lock_devices(devices),
+            None => Ok(()),
+        },
+        ContainerKind::Gocryptfs => {
+            let Some(mount_point) = &item.mount_point_opt else {
+                return Ok(());
+            };
+            run(Command::new("fusermount").arg("-u").arg(mount_point), None)?;
+            let _ = fs::remove_dir(mount_point);
+            Ok(())
+        }
+    }
+}
+
+enum Event {
+    Items(MounterItems),
+    MountResult(MounterItem, Result<bool, String>),
+    NetworkAuth(String, MounterAuth, mpsc::Sender<MounterAuth>),
+}
+
+/// Unlocks encrypted containers, listing them as devices until they are locked again
+pub struct Crypt {
+    unlocked: Arc<sync::Mutex<Vec<Item>>>,
+    event_tx: mpsc::UnboundedSender<Event>,
+    event_rx: Arc<Mutex<mpsc::UnboundedReceiver<Event>>>,
+}
+
+impl Crypt {
+    pub fn new() -> Self {
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        Self {
+            unlocked: Arc::new(sync::Mutex::new(Vec::new())),
+            event_tx,
+            event_rx: Arc::new(Mutex::new(event_rx)),
+        }
+    }
+
+    fn lock_task(&self, item: MounterItem) -> Task<()> {
+        let MounterItem::Crypt(item) = item else {
+            return Task::none();
+        };
+        let unlocked = self.unlocked.clone();
+        let event_tx = self.event_tx.clone();
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    log::info!("lock {:?}", item.path);
+                    if let Err(err) = lock(&item) {
+                        log::warn!("failed to lock {:?}: {}", item.path, err);
+                        return;
+                    }
+                    let mut unlocked = unlocked.lock().unwrap();
+                    unlocked.retain(|other| other.path != item.path);
+                    let items = unlocked.iter().cloned().map(MounterItem::Crypt).collect();
+                    event_tx.send(Event::Items(items)).unwrap();
+                })
+                .await
+                .unwrap();
+            },
+            |x| x,
+        )
+    }
+}
+
+impl Mounter for Crypt {
+    fn items(&self, _sizes: IconSizes) -> Option<MounterItems> {
+        Some(
+            self.unlocked
+                .lock()
+                .unwrap()
+                .iter()
+                .cloned()
+                .map(MounterItem::Crypt)
+                .collect(),
+        )
+    }
+
+    fn mount(&self, item: MounterItem) -> Task<()> {
+        let MounterItem::Crypt(mut item) = item else {
+            return Task::none();
+        };
+        let unlocked = self.unlocked.clone();
+        let event_tx = self.event_tx.clone();
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    let auth = MounterAuth {
+                        message: format!(
+                            "{}\n{}",
+                            fl!("unlock-container-title", name = item.name.as_str()),
+                            item.path.display()
+                        ),
+                        username_opt: None,
+                        domain_opt: None,
+                        password_opt: Some(String::new()),
+                        remember_opt: None,
+                        anonymous_opt: None,
+                    };
+                    let (auth_tx, mut auth_rx) = mpsc::channel(1);
+                    event_tx
+                        .send(Event::NetworkAuth(item.name.clone(), auth, auth_tx))
+                        .unwrap();
+                    let Some(auth) = auth_rx.blocking_recv() else {
+                        event_tx
+                            .send(Event::MountResult(MounterItem::Crypt(item), Ok(false)))
+                            .unwrap();
+                        return;
+                    };
+
+                    log::info!("unlock {:?}", item.path);
+                    let passphrase = auth.password_opt.unwrap_or_default();
+                    let res = unlock(&mut item, &passphrase).map(|()| true);
+                    if res.is_ok() {
+                        let mut unlocked = unlocked.lock().unwrap();
+                        unlocked.push(item.clone());
+                        let items = unlocked.iter().cloned().map(MounterItem::Crypt).collect();
+                        event_tx.send(Event::Items(items)).unwrap();
+                    }
+                    event_tx
+                        .send(Event::MountResult(MounterItem::Crypt(item), res))
+                        .unwrap();
+                })
+                .await
+                .unwrap();
+            },
+            |x| x,
+        )
+    }
+
+    fn eject(&self, item: MounterItem) -> Task<()> {
+        self.lock_task(item)
+    }
+
+    fn network_drive(&self, _uri: String) -> Task<()> {
+        Task::none()
+    }
+
+    fn network_scan(
+        &self,
+        _uri: &str,
+        _sizes: IconSizes,
+    ) -> Option<Result<Vec<tab::Item>, String>> {
+        None
+    }
+
+    fn network_supported(&self) -> bool {
+        false
+    }
+
+    fn unmount(&self, item: MounterItem) -> Task<()> {
+        self.lock_task(item)
+    }
+
+    fn subscription(&self) -> Subscription<MounterMessage> {
+        let event_rx = self.event_rx.clone();
+        Subscription::run_with_id(
+            TypeId::of::<Self>(),
+            stream::channel(1, |mut output| async move {
+                while let Some(event) = event_rx.lock().await.recv().await {
+                    let message = match event {
+                        Event::Items(items) => MounterMessage::Items(items),
+                        Event::MountResult(item, res) => MounterMessage::MountResult(item, res),
+                        Event::NetworkAuth(uri, auth, auth_tx) => {
+                            MounterMessage::NetworkAuth(uri, auth, auth_tx)
+                        }
+                    };
+                    output.send(message).await.unwrap();
+                }
+                pending().await
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{udisksctl_device, ContainerKind};
+
+    #[test]
+    fn detect_container_kinds() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let luks = dir.path().join("secret.img");
+        fs::write(&luks, b"LUKS\xba\xbe\x00\x02").unwrap();
+        assert_eq!(ContainerKind::detect(&luks), Some(ContainerKind::Luks));
+
+        let veracrypt = dir.path().join("secret.HC");
+        fs::write(&veracrypt, [0x5a; 64]).unwrap();
+        assert_eq!(
+            ContainerKind::detect(&veracrypt),
+            Some(ContainerKind::VeraCrypt)
+        );
+
+        let gocryptfs = dir.path().join("vault");
+        fs::create_dir(&gocryptfs).unwrap();
+        assert_eq!(ContainerKind::detect(&gocryptfs), None);
+        fs::write(gocryptfs.join("gocryptfs.conf"), "{}").unwrap();
+        assert_eq!(
+            ContainerKind::detect(&gocryptfs),
+            Some(ContainerKind::Gocryptfs)
+        );
+
+        let plain = dir.path().join("notes.txt");
+        fs::write(&plain, "notes").unwrap();
+        assert_eq!(ContainerKind::detect(&plain), None);
+    }
+
+    #[test]
+    fn parse_udisksctl_devices() {
+        assert_eq!(
+            udisksctl_device("Mapped file /home/user/secret.img as /dev/loop0.\n"),
+            Ok("/dev/loop0".to_string())
+        );
+        assert_eq!(
+            udisksctl_device("Unlocked /dev/loop0 as /dev/dm-3.\n"),
+            Ok("/dev/dm-3".to_string())
+        );
+        assert!(udisksctl_device("Error setting up loop device").is_err());
+    }
+}
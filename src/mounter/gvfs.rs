@@ -10,7 +10,7 @@ use super::{Mounter, MounterAuth, MounterItem, MounterItems, MounterMessage};
 use crate::{
     config::IconSizes,
     err_str,
-    tab1::{self, DirSize, ItemMetadata, ItemThumbnail, Location},
+    tab::{self, DirSize, ItemMetadata, ItemThumbnail, Location},
 };
 
 fn gio_icon_to_path(icon: &gio::Icon, size: u16) -> Option<PathBuf> {
@@ -37,6 +37,10 @@ fn items(monitor: &gio::VolumeMonitor, sizes: IconSizes) -> MounterItems {
             icon_opt: gio_icon_to_path(&MountExt::icon(&mount), sizes.grid()),
             icon_symbolic_opt: gio_icon_to_path(&MountExt::symbolic_icon(&mount), 16),
             path_opt: MountExt::root(&mount).path(),
+            is_remote: MountExt::root(&mount)
+                .uri_scheme()
+                .map_or(false, |scheme| scheme != "file"),
+            can_eject: MountExt::can_eject(&mount),
         }));
     }
     for (i, volume) in monitor.volumes().into_iter().enumerate() {
@@ -52,12 +56,14 @@ fn items(monitor: &gio::VolumeMonitor, sizes: IconSizes) -> MounterItems {
             icon_opt: gio_icon_to_path(&VolumeExt::icon(&volume), sizes.grid()),
             icon_symbolic_opt: gio_icon_to_path(&VolumeExt::symbolic_icon(&volume), 16),
             path_opt: None,
+            is_remote: false,
+            can_eject: VolumeExt::can_eject(&volume),
         }));
     }
     items
 }
 
-fn network_scan(uri: &str, sizes: IconSizes) -> Result<Vec<tab1::Item>, String> {
+fn network_scan(uri: &str, sizes: IconSizes) -> Result<Vec<tab::Item>, String> {
     let file = gio::File::for_uri(uri);
     let mut items = Vec::new();
     for info_res in file
@@ -117,7 +123,7 @@ fn network_scan(uri: &str, sizes: IconSizes) -> Result<Vec<tab1::Item>, String>
             )
         };
 
-        items.push(tab1::Item {
+        items.push(tab::Item {
             name,
             display_name,
             metadata,
@@ -134,6 +140,7 @@ fn network_scan(uri: &str, sizes: IconSizes) -> Result<Vec<tab1::Item>, String>
             selected: false,
             highlighted: false,
             overlaps_drag_rect: false,
+            size_requested: false,
             //TODO: scan directory size on gvfs mounts?
             dir_size: DirSize::NotDirectory,
         });
@@ -201,12 +208,13 @@ fn mount_op(uri: String, event_tx: mpsc::UnboundedSender<Event>) -> gio::MountOp
 enum Cmd {
     Items(IconSizes, mpsc::Sender<MounterItems>),
     Rescan,
+    Eject(MounterItem),
     Mount(MounterItem),
     NetworkDrive(String),
     NetworkScan(
         String,
         IconSizes,
-        mpsc::Sender<Result<Vec<tab1::Item>, String>>,
+        mpsc::Sender<Result<Vec<tab::Item>, String>>,
     ),
     Unmount(MounterItem),
 }
@@ -235,6 +243,8 @@ pub struct Item {
     icon_opt: Option<PathBuf>,
     icon_symbolic_opt: Option<PathBuf>,
     path_opt: Option<PathBuf>,
+    is_remote: bool,
+    can_eject: bool,
 }
 
 impl Item {
@@ -258,6 +268,14 @@ impl Item {
     pub fn path(&self) -> Option<PathBuf> {
         self.path_opt.clone()
     }
+
+    pub fn is_remote(&self) -> bool {
+        self.is_remote
+    }
+
+    pub fn can_eject(&self) -> bool {
+        self.can_eject
+    }
 }
 
 pub struct Gvfs {
@@ -326,6 +344,49 @@ impl Gvfs {
                         Cmd::Rescan => {
                             event_tx.send(Event::Items(items(&monitor, IconSizes::default()))).unwrap();
                         }
+                        Cmd::Eject(mounter_item) => {
+                            let MounterItem::Gvfs(item) = mounter_item else { continue };
+                            match item.kind {
+                                ItemKind::Mount => {
+                                    for (i, mount) in monitor.mounts().into_iter().enumerate() {
+                                        if i != item.index || item.name != MountExt::name(&mount) {
+                                            continue;
+                                        }
+
+                                        let name = item.name.clone();
+                                        log::info!("eject mount {}", name);
+                                        MountExt::eject_with_operation(
+                                            &mount,
+                                            gio::MountUnmountFlags::NONE,
+                                            gio::MountOperation::NONE,
+                                            gio::Cancellable::NONE,
+                                            move |result| {
+                                                log::info!("eject mount {}: result {:?}", name, result);
+                                            },
+                                        );
+                                    }
+                                }
+                                ItemKind::Volume => {
+                                    for (i, volume) in monitor.volumes().into_iter().enumerate() {
+                                        if i != item.index || item.name != VolumeExt::name(&volume) {
+                                            continue;
+                                        }
+
+                                        let name = item.name.clone();
+                                        log::info!("eject volume {}", name);
+                                        VolumeExt::eject_with_operation(
+                                            &volume,
+                                            gio::MountUnmountFlags::NONE,
+                                            gio::MountOperation::NONE,
+                                            gio::Cancellable::NONE,
+                                            move |result| {
+                                                log::info!("eject volume {}: result {:?}", name, result);
+                                            },
+                                        );
+                                    }
+                                }
+                            }
+                        }
                         Cmd::Mount(mounter_item) => {
                             let MounterItem::Gvfs(ref item) = mounter_item else { continue };
                             let ItemKind::Volume = item.kind else { continue };
@@ -429,7 +490,7 @@ impl Gvfs {
                                 }
 
                                 log::info!("unmount {}", name);
-                                MountExt::eject_with_operation(
+                                MountExt::unmount_with_operation(
                                     &mount,
                                     gio::MountUnmountFlags::NONE,
                                     gio::MountOperation::NONE,
@@ -469,6 +530,16 @@ impl Mounter for Gvfs {
         )
     }
 
+    fn eject(&self, item: MounterItem) -> Task<()> {
+        let command_tx = self.command_tx.clone();
+        Task::perform(
+            async move {
+                command_tx.send(Cmd::Eject(item)).unwrap();
+            },
+            |x| x,
+        )
+    }
+
     fn network_drive(&self, uri: String) -> Task<()> {
         let command_tx = self.command_tx.clone();
         Task::perform(
@@ -479,7 +550,7 @@ impl Mounter for Gvfs {
         )
     }
 
-    fn network_scan(&self, uri: &str, sizes: IconSizes) -> Option<Result<Vec<tab1::Item>, String>> {
+    fn network_scan(&self, uri: &str, sizes: IconSizes) -> Option<Result<Vec<tab::Item>, String>> {
         let (items_tx, mut items_rx) = mpsc::channel(1);
         self.command_tx
             .send(Cmd::NetworkScan(uri.to_string(), sizes, items_tx))
@@ -487,6 +558,11 @@ impl Mounter for Gvfs {
         items_rx.blocking_recv()
     }
 
+    fn network_path(&self, uri: &str) -> Option<PathBuf> {
+        // GVfs makes mounted locations available through its FUSE mount
+        gio::File::for_uri(uri).path().filter(|path| path.exists())
+    }
+
     fn unmount(&self, item: MounterItem) -> Task<()> {
         let command_tx = self.command_tx.clone();
         Task::perform(
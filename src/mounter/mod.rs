@@ -1,10 +1,24 @@
 use cosmic::{iced::Subscription, widget, Task};
 use once_cell::sync::Lazy;
-use std::{collections::BTreeMap, fmt, path::PathBuf, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    env,
+    ffi::CString,
+    fmt, fs,
+    io::Write,
+    mem,
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::Arc,
+};
 use tokio::sync::mpsc;
 
-use crate::{config::IconSizes, tab1};
+use crate::{config::IconSizes, err_str, tab};
 
+#[cfg(feature = "cloud")]
+mod cloud;
+mod crypt;
 #[cfg(feature = "gvfs")]
 mod gvfs;
 
@@ -40,6 +54,9 @@ impl fmt::Debug for MounterAuth {
 
 #[derive(Clone, Debug)]
 pub enum MounterItem {
+    #[cfg(feature = "cloud")]
+    Cloud(cloud::Item),
+    Crypt(crypt::Item),
     #[cfg(feature = "gvfs")]
     Gvfs(gvfs::Item),
     #[allow(dead_code)]
@@ -47,8 +64,17 @@ pub enum MounterItem {
 }
 
 impl MounterItem {
+    /// Encrypted container at `path` that can be unlocked by the crypt mounter
+    pub fn container(path: &Path) -> Option<Self> {
+        crypt::ContainerKind::detect(path)
+            .map(|kind| Self::Crypt(crypt::Item::new(path.to_path_buf(), kind)))
+    }
+
     pub fn name(&self) -> String {
         match self {
+            #[cfg(feature = "cloud")]
+            Self::Cloud(item) => item.name(),
+            Self::Crypt(item) => item.name(),
             #[cfg(feature = "gvfs")]
             Self::Gvfs(item) => item.name(),
             Self::None => unreachable!(),
@@ -57,6 +83,9 @@ impl MounterItem {
 
     pub fn is_mounted(&self) -> bool {
         match self {
+            #[cfg(feature = "cloud")]
+            Self::Cloud(item) => item.is_mounted(),
+            Self::Crypt(item) => item.is_mounted(),
             #[cfg(feature = "gvfs")]
             Self::Gvfs(item) => item.is_mounted(),
             Self::None => unreachable!(),
@@ -65,6 +94,9 @@ impl MounterItem {
 
     pub fn icon(&self, symbolic: bool) -> Option<widget::icon::Handle> {
         match self {
+            #[cfg(feature = "cloud")]
+            Self::Cloud(item) => item.icon(symbolic),
+            Self::Crypt(item) => item.icon(symbolic),
             #[cfg(feature = "gvfs")]
             Self::Gvfs(item) => item.icon(symbolic),
             Self::None => unreachable!(),
@@ -73,11 +105,126 @@ impl MounterItem {
 
     pub fn path(&self) -> Option<PathBuf> {
         match self {
+            #[cfg(feature = "cloud")]
+            Self::Cloud(item) => item.path(),
+            Self::Crypt(item) => item.path(),
             #[cfg(feature = "gvfs")]
             Self::Gvfs(item) => item.path(),
             Self::None => unreachable!(),
         }
     }
+
+    /// Mounted over the network, like SMB shares or SFTP servers
+    pub fn is_remote(&self) -> bool {
+        match self {
+            #[cfg(feature = "cloud")]
+            Self::Cloud(item) => item.is_remote(),
+            Self::Crypt(item) => item.is_remote(),
+            #[cfg(feature = "gvfs")]
+            Self::Gvfs(item) => item.is_remote(),
+            Self::None => unreachable!(),
+        }
+    }
+
+    pub fn can_eject(&self) -> bool {
+        match self {
+            #[cfg(feature = "cloud")]
+            Self::Cloud(item) => item.can_eject(),
+            Self::Crypt(item) => item.can_eject(),
+            #[cfg(feature = "gvfs")]
+            Self::Gvfs(item) => item.can_eject(),
+            Self::None => unreachable!(),
+        }
+    }
+}
+
+/// Total and available bytes of the file system containing `path`
+pub fn disk_usage(path: &Path) -> Option<(u64, u64)> {
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        log::warn!(
+            "failed to get disk usage of {:?}: {}",
+            path,
+            std::io::Error::last_os_error()
+        );
+        return None;
+    }
+    let block_size = stat.f_frsize as u64;
+    Some((
+        stat.f_blocks as u64 * block_size,
+        stat.f_bavail as u64 * block_size,
+    ))
+}
+
+/// Type of the file system containing `path`, from the mount with the longest matching mount point
+pub fn filesystem_type(path: &Path) -> Option<String> {
+    let mounts = match fs::read_to_string("/proc/self/mounts") {
+        Ok(ok) => ok,
+        Err(err) => {
+            log::warn!("failed to read mounts: {}", err);
+            return None;
+        }
+    };
+    let mut best: Option<(usize, &str)> = None;
+    for line in mounts.lines() {
+        let mut fields = line.split(' ');
+        let (Some(_device), Some(mount_point), Some(fs_type)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        // Spaces and other special characters are escaped as octal numbers
+        let mount_point = mount_point
+            .replace("\\040", " ")
+            .replace("\\011", "\t")
+            .replace("\\012", "\n")
+            .replace("\\134", "\\");
+        let depth = Path::new(&mount_point).components().count();
+        if path.starts_with(&mount_point)
+            && best.map_or(true, |(best_depth, _)| depth >= best_depth)
+        {
+            best = Some((depth, fs_type));
+        }
+    }
+    best.map(|(_, fs_type)| fs_type.to_string())
+}
+
+/// Run a command, writing `stdin` to it, and return its output or its error message
+pub(super) fn run(command: &mut Command, stdin: Option<&str>) -> Result<String, String> {
+    log::info!("running {:?}", command);
+    let mut child = command
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("failed to run {:?}: {}", command.get_program(), err))?;
+    if let (Some(input), Some(mut child_stdin)) = (stdin, child.stdin.take()) {
+        child_stdin.write_all(input.as_bytes()).map_err(err_str)?;
+    }
+    let output = child.wait_with_output().map_err(err_str)?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(match stderr.trim() {
+            "" => format!("{:?} failed with {}", command.get_program(), output.status),
+            stderr => stderr.to_string(),
+        })
+    }
+}
+
+/// Directory in the runtime directory to mount `name` of mounter `kind` on
+pub(super) fn mount_point(kind: &str, name: &str) -> PathBuf {
+    dirs::runtime_dir()
+        .unwrap_or_else(env::temp_dir)
+        .join("commander")
+        .join(kind)
+        .join(name)
 }
 
 pub type MounterItems = Vec<MounterItem>;
@@ -94,8 +241,22 @@ pub trait Mounter: Send + Sync {
     fn items(&self, sizes: IconSizes) -> Option<MounterItems>;
     //TODO: send result
     fn mount(&self, item: MounterItem) -> Task<()>;
+    fn eject(&self, item: MounterItem) -> Task<()>;
     fn network_drive(&self, uri: String) -> Task<()>;
-    fn network_scan(&self, uri: &str, sizes: IconSizes) -> Option<Result<Vec<tab1::Item>, String>>;
+    fn network_scan(&self, uri: &str, sizes: IconSizes) -> Option<Result<Vec<tab::Item>, String>>;
+    /// Local path of a network location, if it is mounted by this mounter
+    fn network_path(&self, _uri: &str) -> Option<PathBuf> {
+        None
+    }
+    /// Whether network drives can be connected and browsed with this mounter
+    fn network_supported(&self) -> bool {
+        true
+    }
+    /// Search on the server for names containing `term` below `path`, if it is on a mount of this
+    /// mounter with a protocol that supports it
+    fn search(&self, _path: &Path, _term: &str) -> Option<Result<Vec<PathBuf>, String>> {
+        None
+    }
     fn unmount(&self, item: MounterItem) -> Task<()>;
     fn subscription(&self) -> Subscription<MounterMessage>;
 }
@@ -106,9 +267,15 @@ pub type MounterMap = BTreeMap<MounterKey, Box<dyn Mounter>>;
 pub type Mounters = Arc<MounterMap>;
 
 pub fn mounters() -> Mounters {
-    #[allow(unused_mut)]
     let mut mounters = MounterMap::new();
 
+    #[cfg(feature = "cloud")]
+    {
+        mounters.insert(MounterKey("cloud"), Box::new(cloud::Cloud::new()));
+    }
+
+    mounters.insert(MounterKey("crypt"), Box::new(crypt::Crypt::new()));
+
     #[cfg(feature = "gvfs")]
     {
         mounters.insert(MounterKey("gvfs"), Box::new(gvfs::Gvfs::new()));
@@ -118,3 +285,22 @@ pub fn mounters() -> Mounters {
 }
 
 pub static MOUNTERS: Lazy<Mounters> = Lazy::new(mounters);
+
+/// Whether any mounter can connect to network drives
+pub fn network_supported() -> bool {
+    MOUNTERS.values().any(|mounter| mounter.network_supported())
+}
+
+/// Local path of a network location, like the FUSE path of a GVfs share
+pub fn network_path(uri: &str) -> Option<PathBuf> {
+    MOUNTERS
+        .values()
+        .find_map(|mounter| mounter.network_path(uri))
+}
+
+/// Server-side search below `path`, if a mounter supports it there
+pub fn search(path: &Path, term: &str) -> Option<Result<Vec<PathBuf>, String>> {
+    MOUNTERS
+        .values()
+        .find_map(|mounter| mounter.search(path, term))
+}
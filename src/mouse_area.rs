@@ -1,6 +1,9 @@
 //! A container for capturing mouse events.
 
-use std::time::Instant;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
 
 use cosmic::{
     iced_core::{
@@ -24,7 +27,60 @@ use cosmic::{
     Element, Renderer, Theme,
 };
 
-use crate::tab1::DOUBLE_CLICK_DURATION;
+use crate::tab::DOUBLE_CLICK_DURATION;
+
+/// Distance the cursor has to move with the right button held for a gesture
+const GESTURE_DISTANCE: f32 = 50.0;
+/// Distance a finger may move and still tap
+const TAP_DISTANCE: f32 = 10.0;
+/// Touches held at least this long open the context menu instead of tapping
+const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+/// Change of the distance between two fingers that zooms one step
+const PINCH_RATIO: f32 = 1.25;
+/// Distance two fingers have to move sideways for a swipe
+const SWIPE_DISTANCE: f32 = 100.0;
+
+/// Mouse gestures, made by dragging with the right button held or by rocking between the
+/// left and the right button
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Gesture {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// Right button clicked while the left button is held
+    RockerBack,
+    /// Left button clicked while the right button is held
+    RockerForward,
+    /// Two fingers moved together
+    PinchIn,
+    /// Two fingers moved apart
+    PinchOut,
+    /// Two fingers swiped to the right
+    SwipeBack,
+    /// Two fingers swiped to the left
+    SwipeForward,
+}
+
+impl Gesture {
+    fn from_drag(start: Point, end: Point) -> Option<Self> {
+        if start.distance(end) < GESTURE_DISTANCE {
+            return None;
+        }
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        Some(if dy.abs() > dx.abs() {
+            if dy < 0.0 {
+                Self::Up
+            } else {
+                Self::Down
+            }
+        } else if dx < 0.0 {
+            Self::Left
+        } else {
+            Self::Right
+        })
+    }
+}
 
 /// Emit messages on mouse events.
 #[allow(missing_debug_implementations)]
@@ -49,6 +105,13 @@ pub struct MouseArea<'a, Message> {
     on_scroll: Option<Box<dyn OnScroll<'a, Message>>>,
     on_enter: Option<Box<dyn OnEnterExit<'a, Message>>>,
     on_exit: Option<Box<dyn OnEnterExit<'a, Message>>>,
+    on_gesture: Option<Box<dyn OnGesture<'a, Message>>>,
+    on_tap: Option<Box<dyn OnMouseButton<'a, Message>>>,
+    on_long_press: Option<Box<dyn OnMouseButton<'a, Message>>>,
+    on_touch_scroll: Option<Box<dyn OnTouchScroll<'a, Message>>>,
+    on_touch_scroll_end: Option<Box<dyn OnEnterExit<'a, Message>>>,
+    drag_gestures: bool,
+    rocker_gestures: bool,
     show_drag_rect: bool,
 }
 
@@ -179,6 +242,57 @@ impl<'a, Message> MouseArea<'a, Message> {
         self
     }
 
+    /// The message to emit when a mouse gesture is made.
+    #[must_use]
+    pub fn on_gesture(mut self, message: impl OnGesture<'a, Message>) -> Self {
+        self.on_gesture = Some(Box::new(message));
+        self
+    }
+
+    /// The message to emit when the area is tapped. Touches are not passed to the content
+    /// when any of the touch messages is set.
+    #[must_use]
+    pub fn on_tap(mut self, message: impl OnMouseButton<'a, Message>) -> Self {
+        self.on_tap = Some(Box::new(message));
+        self
+    }
+
+    /// The message to emit when a finger is held on the area without moving.
+    #[must_use]
+    pub fn on_long_press(mut self, message: impl OnMouseButton<'a, Message>) -> Self {
+        self.on_long_press = Some(Box::new(message));
+        self
+    }
+
+    /// The message to emit when two fingers move over the area.
+    #[must_use]
+    pub fn on_touch_scroll(mut self, message: impl OnTouchScroll<'a, Message>) -> Self {
+        self.on_touch_scroll = Some(Box::new(message));
+        self
+    }
+
+    /// The message to emit when two fingers stop touching the area.
+    #[must_use]
+    pub fn on_touch_scroll_end(mut self, message: impl OnEnterExit<'a, Message>) -> Self {
+        self.on_touch_scroll_end = Some(Box::new(message));
+        self
+    }
+
+    /// Recognize drags with the right button held as gestures. The right press message is
+    /// then emitted when the button is released without a drag.
+    #[must_use]
+    pub fn drag_gestures(mut self, drag_gestures: bool) -> Self {
+        self.drag_gestures = drag_gestures;
+        self
+    }
+
+    /// Recognize clicks of one button while the other one is held as gestures
+    #[must_use]
+    pub fn rocker_gestures(mut self, rocker_gestures: bool) -> Self {
+        self.rocker_gestures = rocker_gestures;
+        self
+    }
+
     #[must_use]
     pub fn show_drag_rect(mut self, show_drag_rect: bool) -> Self {
         self.show_drag_rect = show_drag_rect;
@@ -211,6 +325,12 @@ impl<'a, Message, F> OnScroll<'a, Message> for F where
 pub trait OnEnterExit<'a, Message>: Fn() -> Message + 'a {}
 impl<'a, Message, F> OnEnterExit<'a, Message> for F where F: Fn() -> Message + 'a {}
 
+pub trait OnGesture<'a, Message>: Fn(Gesture) -> Message + 'a {}
+impl<'a, Message, F> OnGesture<'a, Message> for F where F: Fn(Gesture) -> Message + 'a {}
+
+pub trait OnTouchScroll<'a, Message>: Fn(Vector) -> Message + 'a {}
+impl<'a, Message, F> OnTouchScroll<'a, Message> for F where F: Fn(Vector) -> Message + 'a {}
+
 /// Local state of the [`MouseArea`].
 #[derive(Default)]
 struct State {
@@ -219,6 +339,20 @@ struct State {
     modifiers: Modifiers,
     prev_click: Option<(mouse::Click, Instant)>,
     size: Option<Size>,
+    /// Position where the right button was pressed for a drag gesture
+    gesture_start: Option<Point>,
+    left_pressed: bool,
+    right_pressed: bool,
+    /// A rocker gesture was made, so releasing the right button does nothing
+    rocker: bool,
+    /// Positions of the fingers touching the area
+    fingers: HashMap<touch::Finger, Point>,
+    /// Position and time of a single touch, until the finger moves
+    touch_start: Option<(Point, Instant)>,
+    /// Distance between two fingers when they touched or last zoomed
+    pinch_distance: Option<f32>,
+    /// Center of two fingers when they touched and where it was last
+    two_finger_center: Option<(Point, Point)>,
 }
 
 impl State {
@@ -240,6 +374,16 @@ impl State {
         None
     }
 
+    /// Center of and distance between two fingers
+    fn two_fingers(&self) -> Option<(Point, f32)> {
+        let mut positions = self.fingers.values();
+        let (a, b) = (positions.next()?, positions.next()?);
+        Some((
+            Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0),
+            a.distance(*b),
+        ))
+    }
+
     fn click(&mut self, pos: Point) -> mouse::Click {
         let now = Instant::now();
 
@@ -291,6 +435,13 @@ impl<'a, Message> MouseArea<'a, Message> {
             on_enter: None,
             on_exit: None,
             on_scroll: None,
+            on_gesture: None,
+            on_tap: None,
+            on_long_press: None,
+            on_touch_scroll: None,
+            on_touch_scroll_end: None,
+            drag_gestures: false,
+            rocker_gestures: false,
             show_drag_rect: false,
         }
     }
@@ -356,6 +507,17 @@ where
     ) -> event::Status {
         match event {
             Event::Mouse(_mouseevent) => {
+                // Gestures are recognized before the content can capture the buttons
+                if let Some(status) = gesture(
+                    self,
+                    &event,
+                    layout,
+                    cursor,
+                    shell,
+                    tree.state.downcast_mut::<State>(),
+                ) {
+                    return status;
+                }
                 if let event::Status::Captured = self.content.as_widget_mut().on_event(
                     &mut tree.children[0],
                     event.clone(),
@@ -370,6 +532,16 @@ where
                 }
 
             },
+            Event::Touch(_) => {
+                return touch(
+                    self,
+                    &event,
+                    layout,
+                    shell,
+                    tree.state.downcast_mut::<State>(),
+                )
+                .unwrap_or(event::Status::Ignored);
+            }
             _ => return event::Status::Ignored,
         }
         update(
@@ -489,6 +661,191 @@ where
     }
 }
 
+/// Recognizes mouse gestures, returning a status if the event was used by one
+fn gesture<Message: Clone>(
+    widget: &MouseArea<'_, Message>,
+    event: &Event,
+    layout: Layout<'_>,
+    cursor: mouse::Cursor,
+    shell: &mut Shell<'_, Message>,
+    state: &mut State,
+) -> Option<event::Status> {
+    let on_gesture = widget.on_gesture.as_ref()?;
+    let layout_bounds = layout.bounds();
+    match event {
+        Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+            if widget.rocker_gestures && state.right_pressed && cursor.is_over(layout_bounds) {
+                state.rocker = true;
+                state.gesture_start = None;
+                shell.publish(on_gesture(Gesture::RockerForward));
+                return Some(event::Status::Captured);
+            }
+            state.left_pressed = true;
+        }
+        Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+            state.left_pressed = false;
+        }
+        Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+            state.right_pressed = true;
+            if !cursor.is_over(layout_bounds) {
+                return None;
+            }
+            if widget.rocker_gestures && state.left_pressed {
+                state.rocker = true;
+                shell.publish(on_gesture(Gesture::RockerBack));
+                return Some(event::Status::Captured);
+            }
+            if widget.drag_gestures {
+                state.gesture_start = cursor.position();
+            }
+        }
+        Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Right)) => {
+            state.right_pressed = false;
+            if state.rocker {
+                state.rocker = false;
+                return Some(event::Status::Captured);
+            }
+            let start = state.gesture_start.take()?;
+            match Gesture::from_drag(start, cursor.position().unwrap_or(start)) {
+                Some(gesture) => shell.publish(on_gesture(gesture)),
+                None => {
+                    if let Some(message) = widget.on_right_press.as_ref() {
+                        shell.publish(message(cursor.position_in(layout_bounds)));
+                    }
+                }
+            }
+            return Some(event::Status::Captured);
+        }
+        _ => {}
+    }
+    None
+}
+
+/// Recognizes taps, long presses and gestures with two fingers, returning a status if
+/// touches are handled by the area
+fn touch<Message: Clone>(
+    widget: &MouseArea<'_, Message>,
+    event: &Event,
+    layout: Layout<'_>,
+    shell: &mut Shell<'_, Message>,
+    state: &mut State,
+) -> Option<event::Status> {
+    if widget.on_tap.is_none() && widget.on_long_press.is_none() && widget.on_touch_scroll.is_none()
+    {
+        return None;
+    }
+    let Event::Touch(touch_event) = event else {
+        return None;
+    };
+    let layout_bounds = layout.bounds();
+    let position_in = |position: Point| {
+        layout_bounds
+            .contains(position)
+            .then(|| Point::new(position.x - layout_bounds.x, position.y - layout_bounds.y))
+    };
+    // Single fingers are not captured, so that they still scroll the view around the area
+    match touch_event {
+        touch::Event::FingerPressed { id, position } => {
+            if state.fingers.is_empty() && !layout_bounds.contains(*position) {
+                return Some(event::Status::Ignored);
+            }
+            state.fingers.insert(*id, *position);
+            if state.fingers.len() == 1 {
+                state.touch_start = Some((*position, Instant::now()));
+                return Some(event::Status::Ignored);
+            }
+            state.touch_start = None;
+            if let Some((center, distance)) = state.two_fingers() {
+                state.pinch_distance = Some(distance);
+                state.two_finger_center = Some((center, center));
+            }
+            Some(event::Status::Captured)
+        }
+        touch::Event::FingerMoved { id, position } => {
+            if !state.fingers.contains_key(id) {
+                return Some(event::Status::Ignored);
+            }
+            state.fingers.insert(*id, *position);
+            let Some((center, distance)) = state.two_fingers() else {
+                if let Some((start, _)) = state.touch_start {
+                    if start.distance(*position) > TAP_DISTANCE {
+                        state.touch_start = None;
+                    }
+                }
+                return Some(event::Status::Ignored);
+            };
+            if let Some((pinch_distance, on_gesture)) =
+                state.pinch_distance.zip(widget.on_gesture.as_ref())
+            {
+                let ratio = distance / pinch_distance.max(1.0);
+                if ratio > PINCH_RATIO {
+                    state.pinch_distance = Some(distance);
+                    shell.publish(on_gesture(Gesture::PinchOut));
+                } else if ratio < 1.0 / PINCH_RATIO {
+                    state.pinch_distance = Some(distance);
+                    shell.publish(on_gesture(Gesture::PinchIn));
+                }
+            }
+            if let Some((start, last)) = state.two_finger_center {
+                if let Some(on_touch_scroll) = widget.on_touch_scroll.as_ref() {
+                    shell.publish(on_touch_scroll(center - last));
+                }
+                state.two_finger_center = Some((start, center));
+            }
+            Some(event::Status::Captured)
+        }
+        touch::Event::FingerLifted { id, position } => {
+            if state.fingers.remove(id).is_none() {
+                return Some(event::Status::Ignored);
+            }
+            if let Some((start, last)) = state.two_finger_center {
+                if state.fingers.len() < 2 {
+                    state.two_finger_center = None;
+                    state.pinch_distance = None;
+                    if let Some(message) = widget.on_touch_scroll_end.as_ref() {
+                        shell.publish(message());
+                    }
+                    let delta = last - start;
+                    if let Some(on_gesture) = widget.on_gesture.as_ref() {
+                        if delta.x.abs() > SWIPE_DISTANCE && delta.x.abs() > 2.0 * delta.y.abs() {
+                            shell.publish(on_gesture(if delta.x > 0.0 {
+                                Gesture::SwipeBack
+                            } else {
+                                Gesture::SwipeForward
+                            }));
+                        }
+                    }
+                }
+                return Some(event::Status::Captured);
+            }
+            let Some((start, time)) = state.touch_start.take() else {
+                return Some(event::Status::Ignored);
+            };
+            if !state.fingers.is_empty() || start.distance(*position) > TAP_DISTANCE {
+                return Some(event::Status::Ignored);
+            }
+            let message_opt = if time.elapsed() >= LONG_PRESS_DURATION {
+                widget.on_long_press.as_ref()
+            } else {
+                widget.on_tap.as_ref()
+            };
+            if let Some(message) = message_opt {
+                shell.publish(message(position_in(*position)));
+            }
+            Some(event::Status::Captured)
+        }
+        touch::Event::FingerLost { id, .. } => {
+            state.fingers.remove(id);
+            if state.fingers.len() < 2 {
+                state.two_finger_center = None;
+                state.pinch_distance = None;
+            }
+            state.touch_start = None;
+            Some(event::Status::Ignored)
+        }
+    }
+}
+
 /// Processes the given [`Event`] and updates the [`State`] of an [`MouseArea`]
 /// accordingly.
 fn update<Message: Clone>(
@@ -602,7 +959,12 @@ fn update<Message: Clone>(
         }
     }
 
-    if let Some(message) = widget.on_right_press.as_ref() {
+    // With drag gestures, the right press is handled when the button is released
+    if let Some(message) = widget
+        .on_right_press
+        .as_ref()
+        .filter(|_| !(widget.drag_gestures && widget.on_gesture.is_some()))
+    {
         if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) = event {
             shell.publish(message(cursor.position_in(layout_bounds)));
 
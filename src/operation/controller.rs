@@ -1,6 +1,12 @@
-use crate::fl;
+use crate::{fl, tab};
 
-use std::sync::{Arc, Condvar, Mutex};
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The current speed is measured over at least this time, so it does not jump around
+const SPEED_SAMPLE: Duration = Duration::from_millis(500);
 
 #[derive(Clone, Copy, Debug)]
 pub enum ControllerState {
@@ -9,10 +15,59 @@ pub enum ControllerState {
     Running,
 }
 
+/// Progress of operations that copy bytes, shown below their progress bars
+#[derive(Clone, Debug, Default)]
+pub struct ProgressDetails {
+    /// Name of the item that is being processed
+    pub current_name: Option<String>,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub files_remaining: usize,
+    /// Bytes per second over the last sample
+    pub speed: f64,
+    /// Bytes per second since the start
+    pub average_speed: f64,
+}
+
+impl ProgressDetails {
+    pub fn text(&self) -> Option<String> {
+        if self.bytes_total == 0 {
+            return None;
+        }
+        let mut parts = vec![
+            fl!(
+                "progress-bytes",
+                done = tab::format_size(self.bytes_done),
+                total = tab::format_size(self.bytes_total)
+            ),
+            fl!("progress-files-remaining", files = self.files_remaining),
+        ];
+        if self.speed > 0.0 {
+            parts.push(fl!(
+                "progress-speed",
+                speed = tab::format_size(self.speed as u64),
+                average = tab::format_size(self.average_speed as u64)
+            ));
+        }
+        let text = parts.join(", ");
+        Some(match &self.current_name {
+            Some(name) => format!("{}: {}", name, text),
+            None => text,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct Speed {
+    started: Option<Instant>,
+    sample: Option<(Instant, u64)>,
+}
+
 #[derive(Debug)]
 struct ControllerInner {
     state: Mutex<ControllerState>,
     progress: Mutex<f32>,
+    details: Mutex<(ProgressDetails, Speed)>,
     condvar: Condvar,
 }
 
@@ -29,6 +84,7 @@ impl Default for Controller {
             inner: Arc::new(ControllerInner {
                 state: Mutex::new(ControllerState::Running),
                 progress: Mutex::new(0.0),
+                details: Mutex::new(Default::default()),
                 condvar: Condvar::new(),
             }),
         }
@@ -57,6 +113,41 @@ impl Controller {
         *self.inner.progress.lock().unwrap() = progress;
     }
 
+    pub fn details(&self) -> ProgressDetails {
+        self.inner.details.lock().unwrap().0.clone()
+    }
+
+    /// Update the details and the speeds calculated from the bytes that are done
+    pub fn set_details(
+        &self,
+        current_name: Option<String>,
+        bytes_done: u64,
+        bytes_total: u64,
+        files_remaining: usize,
+    ) {
+        let now = Instant::now();
+        let mut guard = self.inner.details.lock().unwrap();
+        let (details, speed) = &mut *guard;
+        let started = *speed.started.get_or_insert(now);
+        let elapsed = now.duration_since(started).as_secs_f64();
+        if elapsed > 0.0 {
+            details.average_speed = bytes_done as f64 / elapsed;
+        }
+        match speed.sample {
+            Some((time, bytes)) if now.duration_since(time) >= SPEED_SAMPLE => {
+                details.speed = bytes_done.saturating_sub(bytes) as f64
+                    / now.duration_since(time).as_secs_f64();
+                speed.sample = Some((now, bytes_done));
+            }
+            Some(_) => {}
+            None => speed.sample = Some((now, bytes_done)),
+        }
+        details.current_name = current_name;
+        details.bytes_done = bytes_done;
+        details.bytes_total = bytes_total;
+        details.files_remaining = files_remaining;
+    }
+
     pub fn state(&self) -> ControllerState {
         *self.inner.state.lock().unwrap()
     }
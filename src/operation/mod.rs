@@ -1,18 +1,21 @@
 use crate::{
     app::{ArchiveType, DialogPage, Message},
-    config::IconSizes,
+    config::{IconSizes, SyncDirection, SyncProfile},
     fl,
+    listing::{self, ListingColumns, ListingFormat},
     mime_icon::mime_for_path,
+    mounter,
     spawn_detached::spawn_detached,
-    tab1,
+    tab, volume_trash,
 };
 use cosmic::iced::futures::{channel::mpsc::Sender, executor, SinkExt};
 use std::collections::VecDeque;
 use std::fmt::Formatter;
 use std::{
     borrow::Cow,
+    ffi::OsString,
     fs,
-    io::{self, Read, Write},
+    io::{self, Read, Seek, Write},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -36,7 +39,7 @@ fn handle_replace(
     file_to: PathBuf,
     multiple: bool,
 ) -> ReplaceResult {
-    let item_from = match tab1::item_from_path(file_from, IconSizes::default()) {
+    let item_from = match tab::item_from_path(file_from, IconSizes::default()) {
         Ok(ok) => ok,
         Err(err) => {
             log::warn!("{}", err);
@@ -44,7 +47,7 @@ fn handle_replace(
         }
     };
 
-    let item_to = match tab1::item_from_path(file_to, IconSizes::default()) {
+    let item_to = match tab::item_from_path(file_to, IconSizes::default()) {
         Ok(ok) => ok,
         Err(err) => {
             log::warn!("{}", err);
@@ -69,6 +72,27 @@ fn handle_replace(
     })
 }
 
+fn handle_error(
+    msg_tx: &Arc<TokioMutex<Sender<Message>>>,
+    path: PathBuf,
+    error: String,
+) -> ErrorResult {
+    executor::block_on(async {
+        let (tx, mut rx) = mpsc::channel(1);
+        let _ = msg_tx
+            .lock()
+            .await
+            .send(Message::DialogPush(DialogPage::ItemFailed {
+                path,
+                error,
+                apply_to_all: false,
+                tx,
+            }))
+            .await;
+        rx.recv().await.unwrap_or(ErrorResult::Abort)
+    })
+}
+
 fn get_directory_name(file_name: &str) -> &str {
     // TODO: Chain with COMPOUND_EXTENSIONS once more formats are supported
     const SUPPORTED_EXTENSIONS: &[&str] = &[
@@ -260,12 +284,46 @@ pub enum ReplaceResult {
     Cancel,
 }
 
+/// What to do when an item of an operation fails
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorResult {
+    Retry,
+    /// Continue without the item, and with all further failed items if true
+    Skip(bool),
+    Abort,
+}
+
+/// Tuning of the operations, from the settings
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OperationOptions {
+    /// Threads that copy small files at the same time
+    pub workers: usize,
+    /// Read large files with `O_DIRECT`, so they do not push everything out of the page cache
+    pub direct_io: bool,
+    /// Move deleted items on remote volumes to a trash folder on the server
+    pub remote_trash: bool,
+    /// Ask before existing items are replaced, otherwise they are replaced
+    pub confirm_overwrite: bool,
+}
+
+impl Default for OperationOptions {
+    fn default() -> Self {
+        Self {
+            workers: 1,
+            direct_io: false,
+            remote_trash: true,
+            confirm_overwrite: true,
+        }
+    }
+}
+
 async fn copy_or_move(
     paths: Vec<PathBuf>,
     to: PathBuf,
     moving: bool,
     msg_tx: &Arc<TokioMutex<Sender<Message>>>,
     controller: Controller,
+    options: OperationOptions,
 ) -> Result<OperationSelection, OperationError> {
     let msg_tx = msg_tx.clone();
     tokio::task::spawn_blocking(move || -> Result<OperationSelection, OperationError> {
@@ -296,10 +354,10 @@ async fn copy_or_move(
             })
             .collect();
 
-        let mut context = Context::new(controller.clone());
+        let mut context = Context::new(controller.clone()).options(options);
 
         {
-            context = context.on_progress(move |_op, progress| {
+            context = context.on_progress(move |op, progress| {
                 let item_progress = match progress.total_bytes {
                     Some(total_bytes) => {
                         if total_bytes == 0 {
@@ -313,19 +371,43 @@ async fn copy_or_move(
                 let total_progress =
                     (item_progress + progress.current_ops as f32) / progress.total_ops as f32;
                 controller.set_progress(total_progress);
+                controller.set_details(
+                    op.from
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string()),
+                    progress.bytes_done + progress.current_bytes,
+                    progress.bytes_total,
+                    progress.files_remaining,
+                );
             });
         }
 
         {
             let msg_tx = msg_tx.clone();
             context = context.on_replace(move |op| {
+                if !options.confirm_overwrite {
+                    return ReplaceResult::Replace(true);
+                }
                 handle_replace(&msg_tx, op.from.clone(), op.to.clone(), true)
             });
         }
 
-        context
-            .recursive_copy_or_move(from_to_pairs, moving)
-            .map_err(OperationError::from_str)?;
+        {
+            let msg_tx = msg_tx.clone();
+            context = context.on_error(move |op, error| {
+                handle_error(&msg_tx, op.from.clone(), error.to_string())
+            });
+        }
+
+        if let Err(err) = context.recursive_copy_or_move(from_to_pairs, moving) {
+            return Err(OperationError {
+                kind: if context.permission_denied {
+                    OperationErrorType::PermissionDenied(err)
+                } else {
+                    OperationErrorType::Generic(err)
+                },
+            });
+        }
 
         Ok(context.op_sel)
     })
@@ -334,6 +416,235 @@ async fn copy_or_move(
     //.map_err(OperationError::from_str)
 }
 
+/// Archive types that items are added to in place
+pub const APPENDABLE_ARCHIVE_TYPES: &[&str] = &["application/x-tar", "application/zip"];
+
+/// Whether items can be added to the archive without repacking it, which compressed tar
+/// archives do not allow
+pub fn archive_appendable(path: &Path) -> bool {
+    path.is_file() && APPENDABLE_ARCHIVE_TYPES.contains(&mime_for_path(path).essence_str())
+}
+
+/// Files and folders below `paths` with their names in an archive, relative to their parents
+fn archive_entries(paths: &[PathBuf]) -> Result<Vec<(PathBuf, String)>, OperationError> {
+    let mut entries = Vec::new();
+    for path in paths {
+        let Some(relative_root) = path.parent() else {
+            return Err(OperationError::from_str(format!(
+                "path {:?} has no parent directory",
+                path
+            )));
+        };
+        for entry in WalkDir::new(path) {
+            let entry = entry.map_err(OperationError::from_str)?;
+            let Some(name) = entry
+                .path()
+                .strip_prefix(relative_root)
+                .map_err(OperationError::from_str)?
+                .to_str()
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            entries.push((entry.into_path(), name));
+        }
+    }
+    Ok(entries)
+}
+
+/// Bytes of `archive` from `offset` to its end, which appending items writes over
+fn archive_tail(archive: &Path, offset: u64) -> Result<Vec<u8>, OperationError> {
+    let mut file = fs::File::open(archive).map_err(OperationError::from_str)?;
+    file.seek(io::SeekFrom::Start(offset))
+        .map_err(OperationError::from_str)?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail)
+        .map_err(OperationError::from_str)?;
+    Ok(tail)
+}
+
+/// Undo a failed append by cutting `archive` at `offset` and writing the old end back
+fn restore_archive_tail(archive: &Path, offset: u64, tail: &[u8]) {
+    let res = fs::File::options()
+        .write(true)
+        .open(archive)
+        .and_then(|mut file| {
+            file.set_len(offset)?;
+            file.seek(io::SeekFrom::Start(offset))?;
+            file.write_all(tail)
+        });
+    if let Err(err) = res {
+        log::warn!("failed to restore archive {:?}: {}", archive, err);
+    }
+}
+
+/// Append items to a tar archive after its last member. Members with the names of earlier
+/// ones replace them when extracted, like with `tar --update`.
+fn add_to_tar(
+    entries: &[(PathBuf, String)],
+    archive: &Path,
+    controller: &Controller,
+) -> Result<(), OperationError> {
+    let mut end = 0;
+    {
+        let mut reader =
+            tar::Archive::new(fs::File::open(archive).map_err(OperationError::from_str)?);
+        for entry in reader.entries().map_err(OperationError::from_str)? {
+            let entry = entry.map_err(OperationError::from_str)?;
+            end = entry.raw_file_position() + entry.size().div_ceil(512) * 512;
+        }
+    }
+
+    // The end of archive blocks are written again after the new members
+    let tail = archive_tail(archive, end)?;
+    let res = (|| -> Result<(), OperationError> {
+        let mut file = fs::File::options()
+            .read(true)
+            .write(true)
+            .open(archive)
+            .map_err(OperationError::from_str)?;
+        file.seek(io::SeekFrom::Start(end))
+            .map_err(OperationError::from_str)?;
+        let mut builder = tar::Builder::new(file);
+        for (i, (path, name)) in entries.iter().enumerate() {
+            controller.check().map_err(OperationError::from_str)?;
+            controller.set_progress(i as f32 / entries.len() as f32);
+            builder
+                .append_path_with_name(path, name)
+                .map_err(OperationError::from_str)?;
+        }
+        let mut file = builder.into_inner().map_err(OperationError::from_str)?;
+        let len = file.stream_position().map_err(OperationError::from_str)?;
+        file.set_len(len).map_err(OperationError::from_str)
+    })();
+    if res.is_err() {
+        restore_archive_tail(archive, end, &tail);
+    }
+    res
+}
+
+fn zip_add_entry<W: Write + io::Seek>(
+    writer: &mut zip::ZipWriter<W>,
+    path: &Path,
+    name: &str,
+) -> Result<(), OperationError> {
+    let mut options = zip::write::SimpleFileOptions::default();
+    if path.is_dir() {
+        return writer
+            .add_directory(name, options)
+            .map_err(OperationError::from_str);
+    }
+    let mut file = fs::File::open(path).map_err(OperationError::from_str)?;
+    let metadata = file.metadata().map_err(OperationError::from_str)?;
+    if metadata.len() >= 4 * 1024 * 1024 * 1024 {
+        // The large file option must be enabled for files above 4 GiB
+        options = options.large_file(true);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        options = options.unix_permissions(metadata.mode());
+    }
+    writer
+        .start_file(name, options)
+        .map_err(OperationError::from_str)?;
+    io::copy(&mut file, writer).map_err(OperationError::from_str)?;
+    Ok(())
+}
+
+/// Add items to a zip archive. New items are appended in place, replacing members requires
+/// writing the archive again, which copies the other members without recompressing them.
+fn add_to_zip(
+    entries: &[(PathBuf, String)],
+    archive: &Path,
+    controller: &Controller,
+) -> Result<(), OperationError> {
+    let mut reader = fs::File::open(archive)
+        .map(io::BufReader::new)
+        .map(zip::ZipArchive::new)
+        .map_err(OperationError::from_str)?
+        .map_err(OperationError::from_str)?;
+    // Folders that are in the archive already are left alone
+    let entries: Vec<&(PathBuf, String)> = entries
+        .iter()
+        .filter(|(path, name)| {
+            !(path.is_dir() && reader.index_for_name(&format!("{}/", name)).is_some())
+        })
+        .collect();
+    let replaced: Vec<&str> = entries
+        .iter()
+        .map(|(_path, name)| name.as_str())
+        .filter(|name| reader.index_for_name(name).is_some())
+        .collect();
+
+    if replaced.is_empty() {
+        // New members are written over the central directory, which follows the data of the
+        // last member
+        let mut end = 0;
+        for i in 0..reader.len() {
+            let member = reader.by_index_raw(i).map_err(OperationError::from_str)?;
+            end = end.max(member.data_start() + member.compressed_size());
+        }
+        let tail = archive_tail(archive, end)?;
+        let res = (|| -> Result<(), OperationError> {
+            let file = fs::File::options()
+                .read(true)
+                .write(true)
+                .open(archive)
+                .map_err(OperationError::from_str)?;
+            let mut writer = zip::ZipWriter::new_append(file).map_err(OperationError::from_str)?;
+            for (i, (path, name)) in entries.iter().enumerate() {
+                controller.check().map_err(OperationError::from_str)?;
+                controller.set_progress(i as f32 / entries.len() as f32);
+                zip_add_entry(&mut writer, path, name)?;
+            }
+            writer.finish().map_err(OperationError::from_str)?;
+            Ok(())
+        })();
+        if res.is_err() {
+            // The writer has been dropped, it writes a central directory on drop
+            restore_archive_tail(archive, end, &tail);
+        }
+        return res;
+    }
+
+    let (Some(parent), Some(file_name)) = (archive.parent(), archive.file_name()) else {
+        return Err(OperationError::from_str(format!(
+            "invalid archive {:?}",
+            archive
+        )));
+    };
+    let mut partial_name = std::ffi::OsStr::new(".").to_os_string();
+    partial_name.push(file_name);
+    partial_name.push(".partial");
+    let partial = parent.join(partial_name);
+    let res = (|| -> Result<(), OperationError> {
+        let file = fs::File::create(&partial).map_err(OperationError::from_str)?;
+        let mut writer = zip::ZipWriter::new(io::BufWriter::new(file));
+        for i in 0..reader.len() {
+            controller.check().map_err(OperationError::from_str)?;
+            let member = reader.by_index_raw(i).map_err(OperationError::from_str)?;
+            if replaced.contains(&member.name()) {
+                continue;
+            }
+            writer
+                .raw_copy_file(member)
+                .map_err(OperationError::from_str)?;
+        }
+        for (i, (path, name)) in entries.iter().enumerate() {
+            controller.check().map_err(OperationError::from_str)?;
+            controller.set_progress(i as f32 / entries.len() as f32);
+            zip_add_entry(&mut writer, path, name)?;
+        }
+        writer.finish().map_err(OperationError::from_str)?;
+        fs::rename(&partial, archive).map_err(OperationError::from_str)
+    })();
+    if res.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    res
+}
+
 fn copy_unique_path(from: &Path, to: &Path) -> PathBuf {
     // List of compound extensions to check
     const COMPOUND_EXTENSIONS: &[&str] = &[
@@ -440,6 +751,8 @@ pub struct OperationSelection {
     pub ignored: Vec<PathBuf>,
     // Paths to select
     pub selected: Vec<PathBuf>,
+    // Paths that failed and were skipped, with their errors
+    pub skipped: Vec<(PathBuf, String)>,
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -451,6 +764,11 @@ pub enum Operation {
         archive_type: ArchiveType,
         password: Option<String>,
     },
+    /// Add items to an existing archive
+    AddToArchive {
+        paths: Vec<PathBuf>,
+        archive: PathBuf,
+    },
     /// Copy items
     Copy {
         paths: Vec<PathBuf>,
@@ -460,8 +778,20 @@ pub enum Operation {
     Delete {
         paths: Vec<PathBuf>,
     },
+    /// Retry an operation with administrator privileges through pkexec
+    Elevated {
+        operation: Box<Operation>,
+    },
     /// Empty the trash
     EmptyTrash,
+    /// Write the listing of a folder to a file
+    ExportListing {
+        dir: PathBuf,
+        to: PathBuf,
+        recursive: bool,
+        format: ListingFormat,
+        columns: ListingColumns,
+    },
     /// Uncompress files
     Extract {
         paths: Vec<PathBuf>,
@@ -475,6 +805,8 @@ pub enum Operation {
     },
     NewFile {
         path: PathBuf,
+        /// Initial contents, like pasted text or image data
+        contents: Vec<u8>,
     },
     NewFolder {
         path: PathBuf,
@@ -491,30 +823,211 @@ pub enum Operation {
     SetExecutableAndLaunch {
         path: PathBuf,
     },
+    /// Create symbolic links to items
+    Symlink {
+        paths: Vec<PathBuf>,
+        to: PathBuf,
+    },
+    /// Synchronize the folders of a sync profile
+    Sync {
+        profile: SyncProfile,
+    },
 }
 
 #[derive(Clone, Debug)]
 pub enum OperationErrorType {
     Generic(String),
     PasswordRequired,
+    /// Missing permissions, the operation may succeed when elevated
+    PermissionDenied(String),
 }
 #[derive(Clone, Debug)]
 pub struct OperationError {
     pub kind: OperationErrorType,
 }
 
+/// Space that a copy or move needs at the destination, and the space that is free there
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SpaceCheck {
+    pub required: u64,
+    pub available: u64,
+}
+
+impl SpaceCheck {
+    pub fn sufficient(&self) -> bool {
+        self.required <= self.available
+    }
+}
+
+/// Add up the sizes of the files that a copy or move of `paths` writes to `to`. Moves on the
+/// same device are renames that write nothing, moves to other devices are copies. Files that
+/// are replaced free their space again.
+/// Returns `None` if the free space of the destination is unknown.
+pub fn space_check(paths: &[PathBuf], to: &Path, moving: bool) -> Option<SpaceCheck> {
+    use std::os::unix::fs::MetadataExt;
+
+    let (_total, available) = mounter::disk_usage(to)?;
+    let to_dev = fs::metadata(to).ok()?.dev();
+    let mut required = 0;
+    let mut copied = Vec::with_capacity(paths.len());
+    for path in paths {
+        if moving && fs::symlink_metadata(path).map_or(false, |x| x.dev() == to_dev) {
+            continue;
+        }
+        for entry in WalkDir::new(path).into_iter().flatten() {
+            if let Ok(metadata) = entry.metadata() {
+                if metadata.is_file() {
+                    required += metadata.len();
+                }
+            }
+        }
+        copied.push(path.clone());
+    }
+    let replaced = preview_changes(&Operation::Copy {
+        paths: copied,
+        to: to.to_path_buf(),
+    })
+    .overwritten_size();
+    Some(SpaceCheck {
+        required: required.saturating_sub(replaced),
+        available,
+    })
+}
+
+/// Whether any of `paths` is on another device than `to`, so that moving it copies it
+pub fn crosses_devices(paths: &[PathBuf], to: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    let Ok(to_dev) = fs::metadata(to).map(|metadata| metadata.dev()) else {
+        return false;
+    };
+    paths
+        .iter()
+        .any(|path| fs::symlink_metadata(path).is_ok_and(|metadata| metadata.dev() != to_dev))
+}
+
+/// Files that an operation deletes or overwrites, with their sizes, listed before it starts
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ChangePreview {
+    pub deleted: Vec<(PathBuf, u64)>,
+    pub overwritten: Vec<(PathBuf, u64)>,
+}
+
+impl ChangePreview {
+    pub fn is_empty(&self) -> bool {
+        self.deleted.is_empty() && self.overwritten.is_empty()
+    }
+
+    pub fn deleted_size(&self) -> u64 {
+        self.deleted.iter().map(|(_path, size)| size).sum()
+    }
+
+    pub fn overwritten_size(&self) -> u64 {
+        self.overwritten.iter().map(|(_path, size)| size).sum()
+    }
+}
+
+/// List the files that the operation would delete or overwrite, without changing anything.
+/// Deletes list every file in the folders, copies and moves the files of the destination that
+/// have the names of the items.
+pub fn preview_changes(operation: &Operation) -> ChangePreview {
+    let mut preview = ChangePreview::default();
+    match operation {
+        Operation::Delete { paths } => {
+            for path in paths {
+                for entry in WalkDir::new(path).into_iter().flatten() {
+                    if let Ok(metadata) = entry.metadata() {
+                        if !metadata.is_dir() {
+                            preview.deleted.push((entry.into_path(), metadata.len()));
+                        }
+                    }
+                }
+            }
+        }
+        Operation::Copy { paths, to } | Operation::Move { paths, to } => {
+            for path in paths {
+                // Copies to the same folder get unique names
+                if path.parent() == Some(to.as_path()) {
+                    continue;
+                }
+                let Some(name) = path.file_name() else {
+                    continue;
+                };
+                let to_path = to.join(name);
+                for entry in WalkDir::new(path).into_iter().flatten() {
+                    let Ok(relative) = entry.path().strip_prefix(path) else {
+                        continue;
+                    };
+                    let target = if relative.as_os_str().is_empty() {
+                        to_path.clone()
+                    } else {
+                        to_path.join(relative)
+                    };
+                    if let Ok(metadata) = fs::symlink_metadata(&target) {
+                        if !metadata.is_dir() && !entry.file_type().is_dir() {
+                            preview.overwritten.push((target, metadata.len()));
+                        }
+                    }
+                }
+            }
+        }
+        Operation::Sync { profile } => {
+            let Ok(plan) = crate::sync::plan(profile, &Controller::default()) else {
+                return preview;
+            };
+            for (_from, to, _len) in plan.copies {
+                if let Ok(metadata) = fs::symlink_metadata(&to) {
+                    preview.overwritten.push((to, metadata.len()));
+                }
+            }
+            for orphan in plan.orphans {
+                for entry in WalkDir::new(orphan).into_iter().flatten() {
+                    if let Ok(metadata) = entry.metadata() {
+                        if !metadata.is_dir() {
+                            preview.deleted.push((entry.into_path(), metadata.len()));
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    preview
+}
+
+/// Whether the error is EACCES or EPERM, which elevated operations may not run into
+pub fn io_permission_denied(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::PermissionDenied || err.raw_os_error() == Some(libc::EPERM)
+}
+
 impl OperationError {
     pub fn from_str<T: ToString>(err: T) -> Self {
         OperationError {
             kind: OperationErrorType::Generic(err.to_string()),
         }
     }
+
+    /// Keep whether the error was caused by missing permissions
+    pub fn from_io(err: io::Error) -> Self {
+        let kind = if io_permission_denied(&err) {
+            OperationErrorType::PermissionDenied(err.to_string())
+        } else {
+            OperationErrorType::Generic(err.to_string())
+        };
+        OperationError { kind }
+    }
+
+    /// Whether the operation failed because of missing permissions and may succeed when
+    /// elevated
+    pub fn is_permission_denied(&self) -> bool {
+        matches!(self.kind, OperationErrorType::PermissionDenied(_))
+    }
 }
 
 impl std::fmt::Display for OperationError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match &self.kind {
-            OperationErrorType::Generic(s) => s.fmt(f),
+            OperationErrorType::Generic(s) | OperationErrorType::PermissionDenied(s) => s.fmt(f),
             OperationErrorType::PasswordRequired => f.write_str("Password required"),
         }
     }
@@ -529,6 +1042,13 @@ impl Operation {
             ControllerState::Cancelled => fl!("progress-cancelled", percent = percent),
         };
         match self {
+            Self::AddToArchive { paths, archive } => fl!(
+                "adding-to-archive",
+                items = paths.len(),
+                from = paths_parent_name(paths),
+                to = file_name(archive),
+                progress = progress()
+            ),
             Self::Compress { paths, to, .. } => fl!(
                 "compressing",
                 items = paths.len(),
@@ -550,7 +1070,14 @@ impl Operation {
                 to = fl!("trash"),
                 progress = progress()
             ),
+            Self::Elevated { operation } => operation.pending_text(ratio, state),
             Self::EmptyTrash => fl!("emptying-trash", progress = progress()),
+            Self::ExportListing { dir, to, .. } => fl!(
+                "exporting-listing",
+                from = file_name(dir),
+                to = file_name(to),
+                progress = progress()
+            ),
             Self::Extract {
                 paths,
                 to,
@@ -569,7 +1096,7 @@ impl Operation {
                 to = file_name(to),
                 progress = progress()
             ),
-            Self::NewFile { path } => fl!(
+            Self::NewFile { path, .. } => fl!(
                 "creating",
                 name = file_name(path),
                 parent = parent_name(path)
@@ -586,11 +1113,29 @@ impl Operation {
             Self::SetExecutableAndLaunch { path } => {
                 fl!("setting-executable-and-launching", name = file_name(path))
             }
+            Self::Symlink { paths, to } => fl!(
+                "linking",
+                items = paths.len(),
+                from = paths_parent_name(paths),
+                to = file_name(to),
+                progress = progress()
+            ),
+            Self::Sync { profile } => fl!(
+                "syncing",
+                name = profile.name.as_str(),
+                progress = progress()
+            ),
         }
     }
 
     pub fn completed_text(&self) -> String {
         match self {
+            Self::AddToArchive { paths, archive } => fl!(
+                "added-to-archive",
+                items = paths.len(),
+                from = paths_parent_name(paths),
+                to = file_name(archive)
+            ),
             Self::Compress { paths, to, .. } => fl!(
                 "compressed",
                 items = paths.len(),
@@ -609,7 +1154,13 @@ impl Operation {
                 from = paths_parent_name(paths),
                 to = fl!("trash")
             ),
+            Self::Elevated { operation } => operation.completed_text(),
             Self::EmptyTrash => fl!("emptied-trash"),
+            Self::ExportListing { dir, to, .. } => fl!(
+                "exported-listing",
+                from = file_name(dir),
+                to = file_name(to)
+            ),
             Self::Extract {
                 paths,
                 to,
@@ -626,7 +1177,7 @@ impl Operation {
                 from = paths_parent_name(paths),
                 to = file_name(to)
             ),
-            Self::NewFile { path } => fl!(
+            Self::NewFile { path, .. } => fl!(
                 "created",
                 name = file_name(path),
                 parent = parent_name(path)
@@ -641,46 +1192,138 @@ impl Operation {
             Self::SetExecutableAndLaunch { path } => {
                 fl!("set-executable-and-launched", name = file_name(path))
             }
+            Self::Symlink { paths, to } => fl!(
+                "linked",
+                items = paths.len(),
+                from = paths_parent_name(paths),
+                to = file_name(to)
+            ),
+            Self::Sync { profile } => fl!("synced", name = profile.name.as_str()),
         }
     }
 
     pub fn show_progress_notification(&self) -> bool {
         // Long running operations show a progress notification
         match self {
-            Self::Compress { .. }
+            Self::AddToArchive { .. }
+            | Self::Compress { .. }
             | Self::Copy { .. }
             | Self::Delete { .. }
             | Self::EmptyTrash
+            | Self::ExportListing { .. }
             | Self::Extract { .. }
             | Self::Move { .. }
-            | Self::Restore { .. } => true,
-            Self::NewFile { .. }
+            | Self::Restore { .. }
+            | Self::Symlink { .. }
+            | Self::Sync { .. } => true,
+            Self::Elevated { .. }
+            | Self::NewFile { .. }
             | Self::NewFolder { .. }
             | Self::Rename { .. }
             | Self::SetExecutableAndLaunch { .. } => false,
         }
     }
 
+    /// Whether the operation changes items in `dir` or below it. Removing or moving a parent of
+    /// `dir` changes it as well.
+    pub fn changes_dir(&self, dir: &Path) -> bool {
+        let removes = |path: &Path| path.starts_with(dir) || dir.starts_with(path);
+        let any_in_dir = |paths: &[PathBuf]| paths.iter().any(|path| removes(path));
+        match self {
+            Self::AddToArchive { archive, .. } => archive.starts_with(dir),
+            Self::Compress { to, .. }
+            | Self::Copy { to, .. }
+            | Self::ExportListing { to, .. }
+            | Self::Extract { to, .. }
+            | Self::Symlink { to, .. } => to.starts_with(dir),
+            Self::Delete { paths } => any_in_dir(paths),
+            Self::Elevated { operation } => operation.changes_dir(dir),
+            Self::EmptyTrash => false,
+            Self::Move { paths, to } => any_in_dir(paths) || to.starts_with(dir),
+            Self::NewFile { path, .. }
+            | Self::NewFolder { path }
+            | Self::SetExecutableAndLaunch { path } => path.starts_with(dir),
+            Self::Rename { from, to } => removes(from) || to.starts_with(dir),
+            Self::Restore { items } => items
+                .iter()
+                .any(|item| item.original_parent.starts_with(dir)),
+            Self::Sync { profile } => match profile.direction {
+                SyncDirection::OneWay => profile.destination.starts_with(dir),
+                SyncDirection::TwoWay => {
+                    profile.source.starts_with(dir) || profile.destination.starts_with(dir)
+                }
+            },
+        }
+    }
+
     pub fn toast(&self) -> Option<String> {
         match self {
+            Self::AddToArchive { .. } => Some(self.completed_text()),
             Self::Compress { .. } => Some(self.completed_text()),
             Self::Delete { .. } => Some(self.completed_text()),
+            Self::ExportListing { .. } => Some(self.completed_text()),
             Self::Extract { .. } => Some(self.completed_text()),
+            Self::Sync { .. } => Some(self.completed_text()),
             //TODO: more toasts
             _ => None,
         }
     }
 
+    /// Command line doing the same as the operation, used to retry it with pkexec
+    pub fn elevated_command(&self) -> Option<Vec<OsString>> {
+        let (args, to, paths): (&[&str], Option<&PathBuf>, Vec<PathBuf>) = match self {
+            Self::Copy { paths, to } => (&["cp", "-a", "-n", "-t"], Some(to), paths.clone()),
+            Self::Move { paths, to } => (&["mv", "-n", "-t"], Some(to), paths.clone()),
+            Self::NewFile { path, contents } if contents.is_empty() => {
+                (&["touch"], None, vec![path.clone()])
+            }
+            Self::NewFolder { path } => (&["mkdir"], None, vec![path.clone()]),
+            Self::Rename { from, to } => {
+                (&["mv", "-n", "-T"], None, vec![from.clone(), to.clone()])
+            }
+            Self::Symlink { paths, to } => (&["ln", "-s", "-t"], Some(to), paths.clone()),
+            _ => return None,
+        };
+        let mut command: Vec<OsString> = args.iter().map(OsString::from).collect();
+        command.extend(to.map(|to| to.clone().into_os_string()));
+        command.push("--".into());
+        command.extend(paths.into_iter().map(PathBuf::into_os_string));
+        Some(command)
+    }
+
     /// Perform the operation
     pub async fn perform(
         self,
         msg_tx: &Arc<TokioMutex<Sender<Message>>>,
         controller: Controller,
+        options: OperationOptions,
     ) -> Result<OperationSelection, OperationError> {
         let controller_clone = controller.clone();
 
         //TODO: IF ERROR, RETURN AN Operation THAT CAN UNDO THE CURRENT STATE
         let paths = match self {
+            Self::AddToArchive { paths, archive } => tokio::task::spawn_blocking(
+                move || -> Result<OperationSelection, OperationError> {
+                    let entries = archive_entries(&paths)?;
+                    match mime_for_path(&archive).essence_str() {
+                        "application/x-tar" => add_to_tar(&entries, &archive, &controller)?,
+                        "application/zip" => add_to_zip(&entries, &archive, &controller)?,
+                        _ => {
+                            return Err(OperationError::from_str(format!(
+                                "items cannot be added to {:?} without repacking it",
+                                archive
+                            )));
+                        }
+                    }
+                    Ok(OperationSelection {
+                        ignored: paths,
+                        selected: vec![archive],
+                        skipped: Vec::new(),
+                    })
+                },
+            )
+            .await
+            .map_err(OperationError::from_str)?,
             Self::Compress {
                 paths,
                 to,
@@ -699,6 +1342,7 @@ impl Operation {
                         let op_sel = OperationSelection {
                             ignored: paths.clone(),
                             selected: vec![to.clone()],
+                            skipped: Vec::new(),
                         };
 
                         let mut paths = paths;
@@ -830,7 +1474,9 @@ impl Operation {
                 .map_err(OperationError::from_str)?
                 //.map_err(|e| e)?
             }
-            Self::Copy { paths, to } => copy_or_move(paths, to, false, msg_tx, controller).await,
+            Self::Copy { paths, to } => {
+                copy_or_move(paths, to, false, msg_tx, controller, options).await
+            }
             Self::Delete { paths } => {
                 let total = paths.len();
                 for (i, path) in paths.into_iter().enumerate() {
@@ -838,11 +1484,26 @@ impl Operation {
 
                     controller.set_progress((i as f32) / (total as f32));
 
-                    let _items_opt = tokio::task::spawn_blocking(|| trash::delete(path))
-                        .await
-                        .map_err(OperationError::from_str)?
-                        .map_err(OperationError::from_str)?;
-                    //TODO: items_opt allows for easy restore
+                    tokio::task::spawn_blocking(move || {
+                        //TODO: items_opt allows for easy restore
+                        trash::delete(&path).map(|_items_opt| ()).or_else(|err| {
+                            if !options.remote_trash {
+                                return Err(OperationError::from_str(err));
+                            }
+                            // Volumes without a trash bin the trash crate can use, like
+                            // network shares, get one of their own
+                            volume_trash::trash(&path).map_err(|volume_err| {
+                                log::info!(
+                                    "failed to move {:?} to the trash of its volume: {}",
+                                    path,
+                                    volume_err
+                                );
+                                OperationError::from_str(err)
+                            })
+                        })
+                    })
+                    .await
+                    .map_err(OperationError::from_str)??;
                 }
                 Ok(OperationSelection::default())
             }
@@ -858,7 +1519,7 @@ impl Operation {
                 ))]
                 {
                     tokio::task::spawn_blocking(move || -> Result<(), OperationError> {
-                        let items = trash::os_limited::list().map_err(OperationError::from_str)?;
+                        let items = volume_trash::list().map_err(OperationError::from_str)?;
                         let count = items.len();
                         for (i, item) in items.into_iter().enumerate() {
                             controller.check().map_err(OperationError::from_str)?;
@@ -973,26 +1634,31 @@ impl Operation {
                 .map_err(OperationError::from_str)?
                 //.map_err(OperationError::from_str)?
             }
-            Self::Move { paths, to } => copy_or_move(paths, to, true, msg_tx, controller).await,
+            Self::Move { paths, to } => {
+                copy_or_move(paths, to, true, msg_tx, controller, options).await
+            }
             Self::NewFolder { path } => tokio::task::spawn_blocking(
                 move || -> Result<OperationSelection, OperationError> {
                     controller.check().map_err(OperationError::from_str)?;
-                    fs::create_dir(&path).map_err(OperationError::from_str)?;
+                    fs::create_dir(&path).map_err(OperationError::from_io)?;
                     Ok(OperationSelection {
                         ignored: Vec::new(),
                         selected: vec![path],
+                        skipped: Vec::new(),
                     })
                 },
             )
             .await
             .map_err(OperationError::from_str)?,
-            Self::NewFile { path } => tokio::task::spawn_blocking(
+            Self::NewFile { path, contents } => tokio::task::spawn_blocking(
                 move || -> Result<OperationSelection, OperationError> {
                     controller.check().map_err(OperationError::from_str)?;
-                    fs::File::create(&path).map_err(OperationError::from_str)?;
+                    let mut file = fs::File::create(&path).map_err(OperationError::from_io)?;
+                    file.write_all(&contents).map_err(OperationError::from_io)?;
                     Ok(OperationSelection {
                         ignored: Vec::new(),
                         selected: vec![path],
+                        skipped: Vec::new(),
                     })
                 },
             )
@@ -1001,10 +1667,11 @@ impl Operation {
             Self::Rename { from, to } => tokio::task::spawn_blocking(
                 move || -> Result<OperationSelection, OperationError> {
                     controller.check().map_err(OperationError::from_str)?;
-                    fs::rename(&from, &to).map_err(OperationError::from_str)?;
+                    fs::rename(&from, &to).map_err(OperationError::from_io)?;
                     Ok(OperationSelection {
                         ignored: vec![from],
                         selected: vec![to],
+                        skipped: Vec::new(),
                     })
                 },
             )
@@ -1034,6 +1701,7 @@ impl Operation {
                 Ok(OperationSelection {
                     ignored: Vec::new(),
                     selected: paths,
+                    skipped: Vec::new(),
                 })
             }
             Self::SetExecutableAndLaunch { path } => {
@@ -1066,6 +1734,77 @@ impl Operation {
                 .map_err(|e| e)?;
                 Ok(OperationSelection::default())
             }
+            Self::Elevated { operation } => {
+                let Some(args) = operation.elevated_command() else {
+                    return Err(OperationError::from_str(format!(
+                        "{:?} cannot be run with administrator privileges",
+                        operation
+                    )));
+                };
+                let output = tokio::process::Command::new("pkexec")
+                    .args(&args)
+                    .output()
+                    .await
+                    .map_err(OperationError::from_str)?;
+                if !output.status.success() {
+                    return Err(OperationError::from_str(
+                        String::from_utf8_lossy(&output.stderr).trim(),
+                    ));
+                }
+                // The watchers pick up the changes made by the other process
+                Ok(OperationSelection::default())
+            }
+            Self::Symlink { paths, to } => tokio::task::spawn_blocking(
+                move || -> Result<OperationSelection, OperationError> {
+                    let total = paths.len();
+                    let mut op_sel = OperationSelection::default();
+                    for (i, path) in paths.into_iter().enumerate() {
+                        controller.check().map_err(OperationError::from_str)?;
+
+                        controller.set_progress((i as f32) / (total as f32));
+
+                        let Some(file_name) = path.file_name() else {
+                            continue;
+                        };
+                        let mut link = to.join(file_name);
+                        if link.symlink_metadata().is_ok() {
+                            link = copy_unique_path(&link, &to);
+                        }
+                        std::os::unix::fs::symlink(&path, &link)
+                            .map_err(OperationError::from_io)?;
+                        op_sel.selected.push(link);
+                    }
+                    Ok(op_sel)
+                },
+            )
+            .await
+            .map_err(OperationError::from_str)?,
+            Self::ExportListing {
+                dir,
+                to,
+                recursive,
+                format,
+                columns,
+            } => tokio::task::spawn_blocking(
+                move || -> Result<OperationSelection, OperationError> {
+                    listing::export(&dir, &to, recursive, format, columns, &controller)
+                        .map_err(OperationError::from_str)?;
+                    Ok(OperationSelection {
+                        ignored: Vec::new(),
+                        selected: vec![to],
+                        skipped: Vec::new(),
+                    })
+                },
+            )
+            .await
+            .map_err(OperationError::from_str)?,
+            Self::Sync { profile } => {
+                tokio::task::spawn_blocking(move || crate::sync::run(&profile, &controller))
+                    .await
+                    .map_err(OperationError::from_str)?
+                    .map_err(OperationError::from_str)?;
+                Ok(OperationSelection::default())
+            }
         };
 
         controller_clone.set_progress(100.0);
@@ -1080,6 +1819,7 @@ mod tests {
         fs::{self, File},
         io,
         path::PathBuf,
+        sync::{Arc, Mutex},
     };
 
     use cosmic::iced::futures::{channel::mpsc, StreamExt};
@@ -1087,7 +1827,11 @@ mod tests {
     use test_log::test;
     use tokio::sync;
 
-    use super::{Controller, Operation, OperationError, OperationSelection, ReplaceResult};
+    use super::{
+        add_to_tar, add_to_zip, archive_entries, preview_changes, recursive::Context, space_check,
+        Controller, ErrorResult, Operation, OperationError, OperationOptions, OperationSelection,
+        ReplaceResult,
+    };
     use crate::{
         app::{
             test_utils::{
@@ -1116,7 +1860,11 @@ mod tests {
                 paths: paths_clone,
                 to: to_clone,
             }
-            .perform(&sync::Mutex::new(tx).into(), Controller::default())
+            .perform(
+                &sync::Mutex::new(tx).into(),
+                Controller::default(),
+                OperationOptions::default(),
+            )
             .await
         });
 
@@ -1302,4 +2050,402 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn changes_dir_covers_sources_and_destinations() {
+        let dir = PathBuf::from("/data/reference");
+        let inside = dir.join("photos");
+        let outside = PathBuf::from("/data/work");
+
+        let copy_out = Operation::Copy {
+            paths: vec![inside.clone()],
+            to: outside.clone(),
+        };
+        assert!(!copy_out.changes_dir(&dir));
+        let move_out = Operation::Move {
+            paths: vec![inside.clone()],
+            to: outside.clone(),
+        };
+        assert!(move_out.changes_dir(&dir));
+        let copy_in = Operation::Copy {
+            paths: vec![outside.clone()],
+            to: inside.clone(),
+        };
+        assert!(copy_in.changes_dir(&dir));
+        let elevated = Operation::Elevated {
+            operation: Box::new(Operation::NewFolder { path: inside }),
+        };
+        assert!(elevated.changes_dir(&dir));
+        assert!(!Operation::Delete {
+            paths: vec![outside]
+        }
+        .changes_dir(&dir));
+    }
+
+    #[test]
+    fn changes_dir_covers_parents_of_dir() {
+        let dir = PathBuf::from("/data/reference");
+        let parent = PathBuf::from("/data");
+        let sibling = PathBuf::from("/data/work");
+
+        assert!(Operation::Delete {
+            paths: vec![parent.clone()]
+        }
+        .changes_dir(&dir));
+        assert!(Operation::Move {
+            paths: vec![parent.clone()],
+            to: PathBuf::from("/backup"),
+        }
+        .changes_dir(&dir));
+        assert!(Operation::Rename {
+            from: parent.clone(),
+            to: PathBuf::from("/old-data"),
+        }
+        .changes_dir(&dir));
+        // Copying a parent leaves it in place
+        assert!(!Operation::Copy {
+            paths: vec![parent],
+            to: PathBuf::from("/backup"),
+        }
+        .changes_dir(&dir));
+        // Names that only share a prefix are not parents
+        assert!(!Operation::Delete {
+            paths: vec![PathBuf::from("/data/ref")]
+        }
+        .changes_dir(&dir));
+        assert!(!Operation::Delete {
+            paths: vec![sibling]
+        }
+        .changes_dir(&dir));
+    }
+
+    #[test]
+    fn permission_errors_are_detected_by_kind() {
+        let denied = io::Error::from_raw_os_error(libc::EACCES);
+        assert!(OperationError::from_io(denied).is_permission_denied());
+        let not_permitted = io::Error::from_raw_os_error(libc::EPERM);
+        assert!(OperationError::from_io(not_permitted).is_permission_denied());
+        let missing = io::Error::from_raw_os_error(libc::ENOENT);
+        assert!(!OperationError::from_io(missing).is_permission_denied());
+        // Messages that only mention the error are not enough
+        assert!(!OperationError::from_str("Permission denied (os error 13)").is_permission_denied());
+    }
+
+    #[test]
+    fn space_check_skips_moves_on_same_device() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+
+        let dir_path = path.join("cosmic");
+        fs::create_dir(&dir_path)?;
+        fs::write(dir_path.join("ferris"), [0; 100])?;
+        fs::write(path.join("crab"), [0; 50])?;
+        let to = path.join("to");
+        fs::create_dir(&to)?;
+        let paths = vec![dir_path, path.join("crab")];
+
+        let check = space_check(&paths, &to, false).expect("Free space should be known");
+        assert_eq!(check.required, 150);
+        let check = space_check(&paths, &to, true).expect("Free space should be known");
+        assert_eq!(check.required, 0);
+        assert!(check.sufficient());
+
+        Ok(())
+    }
+
+    #[test]
+    fn space_check_subtracts_replaced_files() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+
+        let dir_path = path.join("cosmic");
+        fs::create_dir(&dir_path)?;
+        fs::write(dir_path.join("ferris"), [0; 100])?;
+        fs::write(dir_path.join("crab"), [0; 50])?;
+        let to = path.join("to");
+        fs::create_dir_all(to.join("cosmic"))?;
+        fs::write(to.join("cosmic").join("ferris"), [0; 80])?;
+        // Files with other names are not replaced
+        fs::write(to.join("cosmic").join("other"), [0; 1000])?;
+
+        let check =
+            space_check(&[dir_path.clone()], &to, false).expect("Free space should be known");
+        assert_eq!(check.required, 70);
+
+        // Copies to the same folder get new names and replace nothing
+        let check = space_check(&[dir_path], path, false).expect("Free space should be known");
+        assert_eq!(check.required, 150);
+
+        Ok(())
+    }
+
+    #[test]
+    fn preview_lists_deleted_and_overwritten_files() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+
+        let dir_path = path.join("cosmic");
+        fs::create_dir_all(dir_path.join("nested"))?;
+        fs::write(dir_path.join("ferris"), [0; 100])?;
+        fs::write(dir_path.join("nested").join("crab"), [0; 50])?;
+        let to = path.join("to");
+        fs::create_dir_all(to.join("cosmic"))?;
+        fs::write(to.join("cosmic").join("ferris"), [0; 10])?;
+
+        let preview = preview_changes(&Operation::Delete {
+            paths: vec![dir_path.clone()],
+        });
+        assert_eq!(preview.deleted.len(), 2);
+        assert_eq!(preview.deleted_size(), 150);
+        assert!(preview.overwritten.is_empty());
+
+        let preview = preview_changes(&Operation::Copy {
+            paths: vec![dir_path.clone()],
+            to: to.clone(),
+        });
+        assert!(preview.deleted.is_empty());
+        assert_eq!(
+            preview.overwritten,
+            vec![(to.join("cosmic").join("ferris"), 10)]
+        );
+
+        // Duplicating in the same folder overwrites nothing
+        let preview = preview_changes(&Operation::Copy {
+            paths: vec![dir_path],
+            to: path.to_path_buf(),
+        });
+        assert!(preview.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn move_keeps_skipped_sources() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+
+        let from_dir = path.join("cosmic");
+        fs::create_dir(&from_dir)?;
+        fs::write(from_dir.join("ferris"), "moved")?;
+        fs::write(from_dir.join("crab"), "skipped")?;
+        let to = path.join("to");
+        fs::create_dir_all(to.join("cosmic"))?;
+        fs::write(to.join("cosmic").join("crab"), "existing")?;
+
+        let mut context =
+            Context::new(Controller::default()).on_replace(|_op| ReplaceResult::Skip(false));
+        context
+            .recursive_copy_or_move(vec![(from_dir.clone(), to.join("cosmic"))], true)
+            .expect("Move should have succeeded");
+
+        let to_dir = to.join("cosmic");
+        assert!(
+            !from_dir.join("ferris").exists(),
+            "Moved file should be removed"
+        );
+        assert_eq!(fs::read_to_string(to_dir.join("ferris"))?, "moved");
+        assert_eq!(fs::read_to_string(from_dir.join("crab"))?, "skipped");
+        assert_eq!(fs::read_to_string(to_dir.join("crab"))?, "existing");
+
+        Ok(())
+    }
+
+    #[test]
+    fn move_skips_failed_items() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+
+        let from_dir = path.join("cosmic");
+        fs::create_dir(&from_dir)?;
+        fs::write(from_dir.join("ferris"), "moved")?;
+        fs::write(from_dir.join("crab"), "failed")?;
+        let to_dir = path.join("to").join("cosmic");
+        // A folder in the way cannot be replaced by the file
+        fs::create_dir_all(to_dir.join("crab"))?;
+
+        let mut context =
+            Context::new(Controller::default()).on_error(|_op, _error| ErrorResult::Skip(false));
+        context
+            .recursive_copy_or_move(vec![(from_dir.clone(), to_dir.clone())], true)
+            .expect("Move should have continued after the failed item");
+
+        assert_eq!(fs::read_to_string(to_dir.join("ferris"))?, "moved");
+        assert!(!from_dir.join("ferris").exists());
+        assert_eq!(fs::read_to_string(from_dir.join("crab"))?, "failed");
+        assert_eq!(context.op_sel.skipped.len(), 1);
+        assert_eq!(context.op_sel.skipped[0].0, from_dir.join("crab"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parallel_copy_copies_all_files() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+
+        let from_dir = path.join("cosmic");
+        fs::create_dir_all(from_dir.join("nested"))?;
+        for i in 0..100 {
+            fs::write(from_dir.join(format!("ferris{i}")), format!("crab {i}"))?;
+            fs::write(from_dir.join("nested").join(format!("crab{i}")), [i; 1000])?;
+        }
+        let to_dir = path.join("to");
+
+        let mut context = Context::new(Controller::default()).options(OperationOptions {
+            workers: 4,
+            direct_io: true,
+            ..OperationOptions::default()
+        });
+        context
+            .recursive_copy_or_move(vec![(from_dir.clone(), to_dir.clone())], false)
+            .expect("Copy should have succeeded");
+
+        for i in 0..100 {
+            assert_eq!(
+                fs::read_to_string(to_dir.join(format!("ferris{i}")))?,
+                format!("crab {i}")
+            );
+            assert_eq!(
+                fs::read(to_dir.join("nested").join(format!("crab{i}")))?,
+                [i; 1000]
+            );
+        }
+        assert_eq!(context.op_sel.selected, vec![to_dir]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn progress_counts_bytes_and_files() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+
+        let from_dir = path.join("cosmic");
+        fs::create_dir(&from_dir)?;
+        fs::write(from_dir.join("ferris"), [0; 100])?;
+        fs::write(from_dir.join("crab"), [0; 50])?;
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let mut context = Context::new(Controller::default()).on_progress({
+            let reports = reports.clone();
+            move |_op, progress| {
+                reports.lock().unwrap().push((
+                    progress.bytes_done + progress.current_bytes,
+                    progress.bytes_total,
+                    progress.files_remaining,
+                ))
+            }
+        });
+        context
+            .recursive_copy_or_move(vec![(from_dir, path.join("to"))], false)
+            .expect("Copy should have succeeded");
+
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.first(), Some(&(0, 150, 2)));
+        assert_eq!(reports.last().map(|x| (x.0, x.1)), Some((150, 150)));
+        assert!(reports.iter().any(|x| x.2 == 1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_to_zip_appends_and_replaces_members() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+
+        let archive = path.join("archive.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive)?);
+        for (name, contents) in [("ferris", "old"), ("crab", "kept")] {
+            writer.start_file(name, zip::write::SimpleFileOptions::default())?;
+            io::Write::write_all(&mut writer, contents.as_bytes())?;
+        }
+        writer.finish()?;
+
+        let dir_path = path.join("cosmic");
+        fs::create_dir(&dir_path)?;
+        fs::write(dir_path.join("nested"), "new")?;
+        fs::write(path.join("ferris"), "new")?;
+        let entries =
+            archive_entries(&[path.join("ferris"), dir_path]).expect("Entries should be listed");
+        add_to_zip(&entries, &archive, &Controller::default()).expect("Add should have succeeded");
+
+        let mut reader = zip::ZipArchive::new(File::open(&archive)?)?;
+        let mut contents = |name: &str| -> io::Result<String> {
+            let mut contents = String::new();
+            io::Read::read_to_string(&mut reader.by_name(name)?, &mut contents)?;
+            Ok(contents)
+        };
+        assert_eq!(contents("ferris")?, "new");
+        assert_eq!(contents("crab")?, "kept");
+        assert_eq!(contents("cosmic/nested")?, "new");
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_to_tar_appends_members() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+
+        fs::write(path.join("ferris"), "old")?;
+        let archive = path.join("archive.tar");
+        let mut builder = tar::Builder::new(File::create(&archive)?);
+        builder.append_path_with_name(path.join("ferris"), "ferris")?;
+        builder.finish()?;
+        drop(builder);
+
+        fs::write(path.join("crab"), "new")?;
+        let entries = archive_entries(&[path.join("crab")]).expect("Entries should be listed");
+        add_to_tar(&entries, &archive, &Controller::default()).expect("Add should have succeeded");
+
+        let mut reader = tar::Archive::new(File::open(&archive)?);
+        let names: Vec<String> = reader
+            .entries()?
+            .map(|entry| Ok(entry?.path()?.to_string_lossy().to_string()))
+            .collect::<io::Result<_>>()?;
+        assert_eq!(names, vec!["ferris", "crab"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn failed_add_to_archive_restores_it() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+
+        fs::write(path.join("ferris"), "old")?;
+        let tar_archive = path.join("archive.tar");
+        let mut builder = tar::Builder::new(File::create(&tar_archive)?);
+        builder.append_path_with_name(path.join("ferris"), "ferris")?;
+        builder.finish()?;
+        drop(builder);
+
+        let zip_archive = path.join("archive.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_archive)?);
+        writer.start_file("ferris", zip::write::SimpleFileOptions::default())?;
+        io::Write::write_all(&mut writer, b"old")?;
+        writer.finish()?;
+
+        // The second item fails after the first one was written
+        fs::write(path.join("crab"), "new")?;
+        let entries = vec![
+            (path.join("crab"), "crab".to_string()),
+            (path.join("missing"), "missing".to_string()),
+        ];
+
+        let tar_contents = fs::read(&tar_archive)?;
+        add_to_tar(&entries, &tar_archive, &Controller::default())
+            .expect_err("Add should have failed");
+        assert_eq!(fs::read(&tar_archive)?, tar_contents);
+
+        let zip_contents = fs::read(&zip_archive)?;
+        add_to_zip(&entries, &zip_archive, &Controller::default())
+            .expect_err("Add should have failed");
+        assert_eq!(fs::read(&zip_archive)?, zip_contents);
+        let mut reader = zip::ZipArchive::new(File::open(&zip_archive)?)?;
+        assert_eq!(reader.len(), 1);
+        assert!(reader.by_name("ferris").is_ok());
+
+        Ok(())
+    }
 }
@@ -1,21 +1,51 @@
 use std::{
+    collections::HashSet,
     error::Error,
     fs,
-    io::{Read, Write},
+    io::{self, Read, Write},
     ops::ControlFlow,
-    path::PathBuf,
+    os::unix::fs::{MetadataExt, OpenOptionsExt},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
 };
 use walkdir::WalkDir;
 
-use super::{copy_unique_path, Controller, OperationSelection, ReplaceResult};
+use super::{
+    copy_unique_path, io_permission_denied, Controller, ErrorResult, OperationOptions,
+    OperationSelection, ReplaceResult,
+};
+
+/// Size of the buffer of large copies, a multiple of [`DIRECT_IO_ALIGN`]
+const BUF_LEN: usize = 16 * 1024 * 1024;
+/// Alignment of the buffer and of the reads of files opened with `O_DIRECT`
+const DIRECT_IO_ALIGN: usize = 4096;
+/// Files up to this size are copied by the workers, and bigger ones one after the other
+const SMALL_FILE_LEN: u64 = 1024 * 1024;
+/// Files from this size are read with `O_DIRECT` if it is enabled, bypassing the page cache
+const DIRECT_IO_MIN_LEN: u64 = 64 * 1024 * 1024;
+/// Small files that each worker copies before the progress is updated
+const FILES_PER_WORKER: usize = 16;
 
 pub struct Context {
     buf: Vec<u8>,
     controller: Controller,
+    options: OperationOptions,
     on_progress: Box<dyn OnProgress>,
     on_replace: Box<dyn OnReplace>,
+    on_error: Box<dyn OnError>,
     pub(crate) op_sel: OperationSelection,
     replace_result_opt: Option<ReplaceResult>,
+    error_result_opt: Option<ErrorResult>,
+    /// Copies are read back and compared before their sources are removed by a move
+    verify_copies: bool,
+    /// Sources that were skipped, and must not be removed by a move
+    kept: HashSet<PathBuf>,
+    /// The op that aborted the operation failed because of missing permissions
+    pub(crate) permission_denied: bool,
 }
 
 pub trait OnProgress: Fn(&Op, &Progress) + 'static {}
@@ -24,15 +54,25 @@ impl<F> OnProgress for F where F: Fn(&Op, &Progress) + 'static {}
 pub trait OnReplace: Fn(&Op) -> ReplaceResult + 'static {}
 impl<F> OnReplace for F where F: Fn(&Op) -> ReplaceResult + 'static {}
 
+pub trait OnError: Fn(&Op, &str) -> ErrorResult + 'static {}
+impl<F> OnError for F where F: Fn(&Op, &str) -> ErrorResult + 'static {}
+
 impl Context {
     pub fn new(controller: Controller) -> Self {
         Self {
-            buf: vec![0; 4 * 1024 * 1024],
+            // Extra space so that an aligned slice of `BUF_LEN` fits in it
+            buf: vec![0; BUF_LEN + DIRECT_IO_ALIGN],
             controller,
+            options: OperationOptions::default(),
             on_progress: Box::new(|_op, _progress| {}),
             on_replace: Box::new(|_op| ReplaceResult::Cancel),
+            on_error: Box::new(|_op, _error| ErrorResult::Abort),
             op_sel: OperationSelection::default(),
             replace_result_opt: None,
+            error_result_opt: None,
+            verify_copies: false,
+            kept: HashSet::new(),
+            permission_denied: false,
         }
     }
 
@@ -43,6 +83,7 @@ impl Context {
     ) -> Result<bool, String> {
         let mut ops = Vec::new();
         let mut cleanup_ops = Vec::new();
+        self.verify_copies = moving;
         for (from_parent, to_parent) in from_to_pairs {
             self.controller.check()?;
 
@@ -51,6 +92,16 @@ impl Context {
                 continue;
             }
 
+            // Moves to other file systems copy the files, which are removed after all of
+            // them are copied and verified
+            let from_dev = fs::symlink_metadata(&from_parent).ok().map(|x| x.dev());
+            let to_dev = to_parent
+                .parent()
+                .and_then(|x| fs::metadata(x).ok())
+                .map(|x| x.dev());
+            let cross_device =
+                moving && from_dev.is_some() && to_dev.is_some() && from_dev != to_dev;
+
             for entry in WalkDir::new(&from_parent).into_iter() {
                 self.controller.check()?;
 
@@ -58,11 +109,16 @@ impl Context {
                     format!("failed to walk directory {:?}: {}", from_parent, err)
                 })?;
                 let file_type = entry.file_type();
+                let len = if file_type.is_file() {
+                    entry.metadata().map_or(0, |metadata| metadata.len())
+                } else {
+                    0
+                };
                 let from = entry.into_path();
                 let kind = if file_type.is_dir() {
                     OpKind::Mkdir
                 } else if file_type.is_file() {
-                    if moving {
+                    if moving && !cross_device {
                         OpKind::Move
                     } else {
                         OpKind::Copy
@@ -88,7 +144,12 @@ impl Context {
                     //TODO: ensure to is inside of to_parent?
                     to_parent.join(relative)
                 };
-                let op = Op { kind, from, to };
+                let op = Op {
+                    kind,
+                    from,
+                    to,
+                    len,
+                };
                 if moving {
                     if let Some(cleanup_op) = op.move_cleanup_op() {
                         cleanup_ops.push(cleanup_op);
@@ -106,7 +167,12 @@ impl Context {
         }
 
         let total_ops = ops.len();
-        for (current_ops, mut op) in ops.into_iter().enumerate() {
+        let bytes_total = ops.iter().map(Op::bytes).sum();
+        let mut bytes_done = 0;
+        let mut files_remaining = ops.iter().filter(|op| op.is_file()).count();
+        let mut cleaning_up = false;
+        let mut ops = ops.into_iter().enumerate().peekable();
+        while let Some((current_ops, mut op)) = ops.next() {
             self.controller.check()?;
 
             let progress = Progress {
@@ -114,28 +180,208 @@ impl Context {
                 total_ops,
                 current_bytes: 0,
                 total_bytes: None,
+                bytes_done,
+                bytes_total,
+                files_remaining,
             };
             (self.on_progress)(&op, &progress);
-            if op.run(self, progress).map_err(|err| {
-                format!(
-                    "failed to {:?} {:?} to {:?}: {}",
-                    op.kind, op.from, op.to, err
-                )
-            })? {
-                // The from path is ignored in the operation selection if it is a top level item
-                if self.op_sel.ignored.contains(&op.from) {
-                    // So add the to path to the selection
-                    self.op_sel.selected.push(op.to.clone());
+
+            if self.options.workers > 1 && op.is_small_copy() {
+                let mut batch = vec![op];
+                while batch.len() < self.options.workers * FILES_PER_WORKER {
+                    match ops.next_if(|(_, next)| next.is_small_copy()) {
+                        Some((_, next)) => batch.push(next),
+                        None => break,
+                    }
+                }
+                let failed = self
+                    .copy_parallel(&batch)
+                    .map_err(|err| format!("{} ({})", err, failure_state(moving, false)))?;
+                bytes_done += batch.iter().map(Op::bytes).sum::<u64>();
+                files_remaining -= batch.len();
+                let progress = Progress {
+                    current_ops: current_ops + batch.len() - 1,
+                    total_ops,
+                    current_bytes: 0,
+                    total_bytes: None,
+                    bytes_done,
+                    bytes_total,
+                    files_remaining,
+                };
+                if let Some(last) = batch.last() {
+                    (self.on_progress)(last, &progress);
+                }
+                // Ask about the files that failed, retries run one after the other
+                let mut skipped = HashSet::new();
+                for (index, err, denied) in failed {
+                    let op = &mut batch[index];
+                    let retry = self
+                        .handle_error(op, err, denied)
+                        .map_err(|err| format!("{} ({})", err, failure_state(moving, false)))?;
+                    let result = if retry {
+                        self.run_op(op, &progress)
+                            .map_err(|err| format!("{} ({})", err, failure_state(moving, false)))?
+                    } else {
+                        None
+                    };
+                    match result {
+                        Some(true) => {}
+                        Some(false) => return Ok(false),
+                        None => {
+                            skipped.insert(index);
+                        }
+                    }
+                }
+                for (index, op) in batch.into_iter().enumerate() {
+                    if !skipped.contains(&index) && self.op_sel.ignored.contains(&op.from) {
+                        self.op_sel.selected.push(op.to);
+                    }
+                }
+                continue;
+            }
+
+            let cleanup = matches!(op.kind, OpKind::Remove | OpKind::Rmdir);
+            if cleanup && !cleaning_up {
+                cleaning_up = true;
+                log::info!("all items were moved, removing the sources");
+            }
+            let result = self
+                .run_op(&mut op, &progress)
+                .map_err(|err| format!("{} ({})", err, failure_state(moving, cleaning_up)))?;
+            bytes_done += op.bytes();
+            if op.is_file() {
+                files_remaining -= 1;
+            }
+            match result {
+                Some(true) => {
+                    // The from path is ignored in the operation selection if it is a top level item
+                    if self.op_sel.ignored.contains(&op.from) {
+                        // So add the to path to the selection
+                        self.op_sel.selected.push(op.to.clone());
+                    }
+                }
+                Some(false) => {
+                    // Cancelled
+                    return Ok(false);
                 }
-            } else {
-                // Cancelled
-                return Ok(false);
+                // Skipped after an error
+                None => {}
             }
         }
 
         Ok(true)
     }
 
+    /// Run the op and ask what to do if it fails. Returns false if it was cancelled and
+    /// `None` if it was skipped.
+    fn run_op(&mut self, op: &mut Op, progress: &Progress) -> Result<Option<bool>, String> {
+        loop {
+            let (err, denied) = match op.run(self, progress.clone()) {
+                Ok(ret) => return Ok(Some(ret)),
+                Err(err) => (
+                    format!(
+                        "failed to {:?} {:?} to {:?}: {}",
+                        op.kind, op.from, op.to, err
+                    ),
+                    err.downcast_ref::<io::Error>()
+                        .is_some_and(io_permission_denied),
+                ),
+            };
+            if !self.handle_error(op, err, denied)? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Ask what to do about the failed op, returns true to retry it and false to skip it.
+    /// Skipped sources are kept by moves and reported when the operation is done. `denied` is
+    /// whether it failed because of missing permissions.
+    fn handle_error(&mut self, op: &Op, err: String, denied: bool) -> Result<bool, String> {
+        // Cancelling is not a failure of the item
+        if self.controller.is_cancelled() {
+            return Err(err);
+        }
+        let error_result = self
+            .error_result_opt
+            .unwrap_or_else(|| (self.on_error)(op, &err));
+        match error_result {
+            ErrorResult::Retry => Ok(true),
+            ErrorResult::Skip(apply_to_all) => {
+                if apply_to_all {
+                    self.error_result_opt = Some(error_result);
+                }
+                log::warn!("skipped {:?}: {}", op.from, err);
+                self.kept.insert(op.from.clone());
+                self.op_sel.skipped.push((op.from.clone(), err));
+                Ok(false)
+            }
+            ErrorResult::Abort => {
+                self.permission_denied = denied;
+                Err(err)
+            }
+        }
+    }
+
+    /// Copy small files that do not exist at the destination with the workers, each with its
+    /// own buffer. The replace dialog is never needed for them. Returns the indexes of the
+    /// files that failed with their errors, and an error if the operation was cancelled.
+    fn copy_parallel(&self, ops: &[Op]) -> Result<Vec<(usize, String, bool)>, String> {
+        // The callbacks of the context cannot be shared with the workers
+        let controller = &self.controller;
+        let verify = self.verify_copies;
+        let next = AtomicUsize::new(0);
+        let failed = Mutex::new(Vec::new());
+        let error_opt = Mutex::new(None);
+        thread::scope(|scope| {
+            for _ in 0..self.options.workers.min(ops.len()) {
+                scope.spawn(|| {
+                    let mut buf = vec![0; SMALL_FILE_LEN as usize];
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(op) = ops.get(index) else {
+                            break;
+                        };
+                        if let Err(err) = controller.check() {
+                            error_opt.lock().unwrap().get_or_insert(err);
+                            // The other workers stop after their current file
+                            next.store(ops.len(), Ordering::Relaxed);
+                            break;
+                        }
+                        if let Err(err) = copy_file(
+                            &op.from,
+                            &op.to,
+                            &mut buf,
+                            controller,
+                            false,
+                            verify,
+                            |_| {},
+                        ) {
+                            let denied = err
+                                .downcast_ref::<io::Error>()
+                                .is_some_and(io_permission_denied);
+                            let err = format!(
+                                "failed to {:?} {:?} to {:?}: {}",
+                                op.kind, op.from, op.to, err
+                            );
+                            failed.lock().unwrap().push((index, err, denied));
+                        }
+                    }
+                });
+            }
+        });
+        if let Some(err) = error_opt.into_inner().unwrap() {
+            return Err(err);
+        }
+        let mut failed = failed.into_inner().unwrap();
+        failed.sort_by_key(|(index, _, _)| *index);
+        Ok(failed)
+    }
+
+    pub fn options(mut self, options: OperationOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     pub fn on_progress<F: OnProgress>(mut self, f: F) -> Self {
         self.on_progress = Box::new(f);
         self
@@ -146,6 +392,11 @@ impl Context {
         self
     }
 
+    pub fn on_error<F: OnError>(mut self, f: F) -> Self {
+        self.on_error = Box::new(f);
+        self
+    }
+
     fn replace(&mut self, op: &Op) -> Result<ControlFlow<bool, PathBuf>, Box<dyn Error>> {
         let replace_result = self
             .replace_result_opt
@@ -166,6 +417,7 @@ impl Context {
                 if apply_to_all {
                     self.replace_result_opt = Some(replace_result);
                 }
+                self.kept.insert(op.from.clone());
                 Ok(ControlFlow::Break(true))
             }
             ReplaceResult::Cancel => Ok(ControlFlow::Break(false)),
@@ -173,12 +425,130 @@ impl Context {
     }
 }
 
-#[derive(Debug)]
+/// What is left of the items when an operation fails
+fn failure_state(moving: bool, cleaning_up: bool) -> &'static str {
+    // Sources are only removed after everything was copied, so tell what is left
+    if cleaning_up {
+        "all items were moved, but some sources could not be removed"
+    } else if moving {
+        "no sources were removed, items that were already moved exist twice"
+    } else {
+        "items that were already copied are kept"
+    }
+}
+
+/// Slice of the buffer that is aligned for `O_DIRECT`, with a length that is a multiple of
+/// [`DIRECT_IO_ALIGN`]
+fn aligned(buf: &mut [u8]) -> &mut [u8] {
+    let offset = buf.as_ptr().align_offset(DIRECT_IO_ALIGN).min(buf.len());
+    let len = (buf.len() - offset) / DIRECT_IO_ALIGN * DIRECT_IO_ALIGN;
+    &mut buf[offset..offset + len]
+}
+
+/// Open the file for reading, with `O_DIRECT` if requested and the file system supports it
+fn open_source(path: &Path, direct_io: bool) -> io::Result<fs::File> {
+    if direct_io {
+        match fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_DIRECT)
+            .open(path)
+        {
+            Err(err) if err.raw_os_error() == Some(libc::EINVAL) => {
+                log::info!("{:?} does not support direct I/O", path);
+            }
+            result => return result,
+        }
+    }
+    fs::File::open(path)
+}
+
+/// Copy the contents and permissions of the file to a new file, calling `on_chunk` with the
+/// bytes copied so far. With `direct_io`, large files are read past the page cache, so the
+/// buffer has to be aligned.
+fn copy_file(
+    from: &Path,
+    to: &Path,
+    buf: &mut [u8],
+    controller: &Controller,
+    direct_io: bool,
+    verify: bool,
+    mut on_chunk: impl FnMut(u64),
+) -> Result<(), Box<dyn Error>> {
+    let metadata = fs::metadata(from)?;
+    let mut from_file = open_source(from, direct_io && metadata.len() >= DIRECT_IO_MIN_LEN)?;
+    // This is atomic and ensures `to` is not created by any other process
+    let mut to_file = fs::OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(to)?;
+    let mut copy = || -> Result<(), Box<dyn Error>> {
+        to_file.set_permissions(metadata.permissions())?;
+        let mut hasher = blake3::Hasher::new();
+        let mut current_bytes = 0;
+        loop {
+            controller.check()?;
+
+            let count = from_file.read(buf)?;
+            if count == 0 {
+                break;
+            }
+            to_file.write_all(&buf[..count])?;
+            if verify {
+                hasher.update(&buf[..count]);
+            }
+            current_bytes += count as u64;
+            on_chunk(current_bytes);
+        }
+        to_file.sync_all()?;
+        if verify {
+            verify_file(to, hasher.finalize(), buf, controller)?;
+        }
+        Ok(())
+    };
+    let result = copy();
+    if result.is_err() {
+        // Partial copies are removed, so the copy can be retried
+        let _ = fs::remove_file(to);
+    }
+    result
+}
+
+/// Read the copy back and compare it with the hash of the data that was written
+fn verify_file(
+    path: &Path,
+    hash: blake3::Hash,
+    buf: &mut [u8],
+    controller: &Controller,
+) -> Result<(), Box<dyn Error>> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    loop {
+        controller.check()?;
+
+        let count = file.read(buf)?;
+        if count == 0 {
+            break;
+        }
+        hasher.update(&buf[..count]);
+    }
+    if hasher.finalize() != hash {
+        return Err(format!("copy {:?} differs from the original", path).into());
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
 pub struct Progress {
     pub current_ops: usize,
     pub total_ops: usize,
     pub current_bytes: u64,
     pub total_bytes: Option<u64>,
+    /// Bytes copied by the previous ops, add `current_bytes` for the bytes done
+    pub bytes_done: u64,
+    /// Bytes to copy by all ops
+    pub bytes_total: u64,
+    /// Files, links included, that are not done yet, the current one included
+    pub files_remaining: usize,
 }
 
 #[derive(Debug)]
@@ -196,6 +566,8 @@ pub struct Op {
     pub kind: OpKind,
     pub from: PathBuf,
     pub to: PathBuf,
+    /// Size of the file, zero for other items
+    pub len: u64,
 }
 
 impl Op {
@@ -210,14 +582,37 @@ impl Op {
             from: self.from.clone(),
             //TODO: it is strange to have `to` here
             to: self.to.clone(),
+            len: 0,
         })
     }
 
+    /// Bytes that this op copies, moves within a file system are instant
+    fn bytes(&self) -> u64 {
+        match self.kind {
+            OpKind::Copy => self.len,
+            _ => 0,
+        }
+    }
+
+    fn is_file(&self) -> bool {
+        matches!(
+            self.kind,
+            OpKind::Copy | OpKind::Move | OpKind::Symlink { .. }
+        )
+    }
+
+    /// Copy of a small file that does not exist at the destination yet, which the workers
+    /// can do without asking to replace it
+    fn is_small_copy(&self) -> bool {
+        matches!(self.kind, OpKind::Copy)
+            && !self.to.exists()
+            && fs::metadata(&self.from).is_ok_and(|metadata| metadata.len() <= SMALL_FILE_LEN)
+    }
+
     fn run(&mut self, ctx: &mut Context, mut progress: Progress) -> Result<bool, Box<dyn Error>> {
         match self.kind {
             OpKind::Copy => {
-                let mut from_file = fs::OpenOptions::new().read(true).open(&self.from)?;
-                let metadata = from_file.metadata()?;
+                let metadata = fs::metadata(&self.from)?;
                 // Remove `to` if overwriting and it is an existing file
                 if self.to.is_file() {
                     match ctx.replace(self)? {
@@ -231,24 +626,19 @@ impl Op {
                 }
                 progress.total_bytes = Some(metadata.len());
                 (ctx.on_progress)(self, &progress);
-                // This is atomic and ensures `to` is not created by any other process
-                let mut to_file = fs::OpenOptions::new()
-                    .create_new(true)
-                    .write(true)
-                    .open(&self.to)?;
-                to_file.set_permissions(metadata.permissions())?;
-                loop {
-                    ctx.controller.check()?;
-
-                    let count = from_file.read(&mut ctx.buf)?;
-                    if count == 0 {
-                        break;
-                    }
-                    to_file.write_all(&ctx.buf[..count])?;
-                    progress.current_bytes += count as u64;
-                    (ctx.on_progress)(self, &progress);
-                }
-                to_file.sync_all()?;
+                let on_progress = &ctx.on_progress;
+                copy_file(
+                    &self.from,
+                    &self.to,
+                    aligned(&mut ctx.buf),
+                    &ctx.controller,
+                    ctx.options.direct_io,
+                    ctx.verify_copies,
+                    |current_bytes| {
+                        progress.current_bytes = current_bytes;
+                        on_progress(self, &progress);
+                    },
+                )?;
             }
             OpKind::Move => {
                 // Remove `to` if overwriting and it is an existing file
@@ -273,6 +663,7 @@ impl Op {
                                 kind: OpKind::Copy,
                                 from: self.from.clone(),
                                 to: self.to.clone(),
+                                len: self.len,
                             };
                             copy_op.run(ctx, progress)?;
                         } else {
@@ -285,11 +676,17 @@ impl Op {
                 fs::create_dir_all(&self.to)?;
             }
             OpKind::Remove => {
-                fs::remove_file(&self.from)?;
-            }
-            OpKind::Rmdir => {
-                fs::remove_dir(&self.from)?;
+                if !ctx.kept.contains(&self.from) {
+                    fs::remove_file(&self.from)?;
+                }
             }
+            OpKind::Rmdir => match fs::remove_dir(&self.from) {
+                Ok(()) => {}
+                // Folders with skipped items are kept
+                Err(err) if err.raw_os_error() == Some(libc::ENOTEMPTY) && !ctx.kept.is_empty() => {
+                }
+                Err(err) => return Err(err.into()),
+            },
             OpKind::Symlink { ref target } => {
                 // Remove `to` if overwriting and it is an existing file
                 if self.to.is_file() {
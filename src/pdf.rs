@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::widget;
+use mime_guess::{mime, Mime};
+use once_cell::sync::Lazy;
+use pdfium_render::prelude::*;
+use std::{path::Path, sync::Mutex};
+
+// Pdfium is not thread safe, so only one page is rendered at a time
+static PDFIUM_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+pub fn is_pdf(mime: &Mime) -> bool {
+    mime.type_() == mime::APPLICATION && mime.subtype() == "pdf"
+}
+
+/// A rendered page of a PDF document
+#[derive(Clone, Debug)]
+pub struct PdfPage {
+    /// Index of the rendered page, starting at zero
+    pub index: u16,
+    pub page_count: u16,
+    pub handle: widget::image::Handle,
+}
+
+/// Render page `index` of the PDF at `path`, scaled to `width` pixels.
+pub fn render_page(path: &Path, index: u16, width: u32) -> Result<PdfPage, String> {
+    let _guard = PDFIUM_LOCK.lock().unwrap();
+
+    let bindings = Pdfium::bind_to_system_library()
+        .map_err(|err| format!("failed to load pdfium: {}", err))?;
+    let pdfium = Pdfium::new(bindings);
+    let document = pdfium
+        .load_pdf_from_file(path, None)
+        .map_err(|err| format!("failed to open {:?}: {}", path, err))?;
+    let pages = document.pages();
+    let page_count = pages.len();
+    if page_count == 0 {
+        return Err(format!("{:?} has no pages", path));
+    }
+    let index = index.min(page_count - 1);
+    let page = pages
+        .get(index)
+        .map_err(|err| format!("failed to get page {} of {:?}: {}", index, path, err))?;
+    let image = page
+        .render_with_config(
+            &PdfRenderConfig::new()
+                .set_target_width(width as i32)
+                .render_form_data(true),
+        )
+        .map_err(|err| format!("failed to render page {} of {:?}: {}", index, path, err))?
+        .as_image()
+        .into_rgba8();
+
+    Ok(PdfPage {
+        index,
+        page_count,
+        handle: widget::image::Handle::from_rgba(image.width(), image.height(), image.into_raw()),
+    })
+}
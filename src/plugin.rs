@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use mime_guess::Mime;
+use once_cell::sync::Lazy;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+/// Context menu action loaded from a `.plugin` manifest.
+///
+/// Manifests use the desktop entry format:
+///
+/// ```ini
+/// [Commander Plugin]
+/// Name=Optimize images
+/// MimeType=image/png;image/jpeg;
+/// Glob=*.png;*.jpg;
+/// Exec=optipng %f
+/// Terminal=true
+/// ```
+///
+/// `Exec` uses the placeholders of the tools menu, see [`crate::tool::expand`].
+/// A plugin without `MimeType` and `Glob` is shown for all items. A plugin with
+/// `TryExec` is skipped if that program is not installed.
+///
+/// Plugins only run commands, WebAssembly modules are not supported.
+#[derive(Clone, Debug)]
+pub struct Plugin {
+    pub name: String,
+    pub exec: String,
+    pub terminal: bool,
+    mime_types: Vec<String>,
+    globs: Vec<glob::Pattern>,
+}
+
+impl Plugin {
+    pub fn from_entry(path: &Path, entry: &freedesktop_entry_parser::Entry) -> Option<Self> {
+        let section = entry.section("Commander Plugin");
+        let Some(name) = section.attr("Name") else {
+            log::warn!("missing Name attribute for plugin {:?}", path);
+            return None;
+        };
+        let Some(exec) = section.attr("Exec") else {
+            log::warn!("missing Exec attribute for plugin {:?}", path);
+            return None;
+        };
+        if let Some(try_exec) = section.attr("TryExec") {
+            if !program_exists(try_exec) {
+                log::info!(
+                    "skipping plugin {:?}: {:?} is not installed",
+                    path,
+                    try_exec
+                );
+                return None;
+            }
+        }
+
+        let mut globs = Vec::new();
+        for glob in section
+            .attr("Glob")
+            .unwrap_or_default()
+            .split_terminator(';')
+        {
+            match glob::Pattern::new(glob) {
+                Ok(pattern) => globs.push(pattern),
+                Err(err) => {
+                    log::warn!(
+                        "failed to parse glob {:?} of plugin {:?}: {}",
+                        glob,
+                        path,
+                        err
+                    );
+                }
+            }
+        }
+
+        Some(Self {
+            name: name.to_string(),
+            exec: exec.to_string(),
+            terminal: section.attr("Terminal") == Some("true"),
+            mime_types: section
+                .attr("MimeType")
+                .unwrap_or_default()
+                .split_terminator(';')
+                .map(|mime_type| mime_type.to_string())
+                .collect(),
+            globs,
+        })
+    }
+
+    /// Check if this plugin applies to the item at `path`
+    pub fn matches(&self, path: &Path, mime: &Mime) -> bool {
+        if self.mime_types.is_empty() && self.globs.is_empty() {
+            return true;
+        }
+        let mime_matches =
+            self.mime_types
+                .iter()
+                .any(|mime_type| match mime_type.split_once('/') {
+                    Some((type_, "*")) => mime.type_() == type_,
+                    _ => mime.essence_str() == mime_type,
+                });
+        mime_matches
+            || path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map_or(false, |name| {
+                    self.globs.iter().any(|glob| glob.matches(name))
+                })
+    }
+}
+
+/// Check if the program is an existing path or found in `PATH`
+fn program_exists(program: &str) -> bool {
+    let path = Path::new(program);
+    if path.is_absolute() {
+        return path.is_file();
+    }
+    env::var_os("PATH").map_or(false, |paths| {
+        env::split_paths(&paths).any(|dir| dir.join(path).is_file())
+    })
+}
+
+pub fn plugin_dirs() -> Vec<PathBuf> {
+    let mut search_dirs = Vec::new();
+    if let Some(data_dir) = dirs::data_dir() {
+        search_dirs.push(data_dir.join("commander").join("plugins"));
+    }
+    search_dirs.push(PathBuf::from("/usr/share/commander/plugins"));
+    search_dirs
+}
+
+fn load(search_dirs: &[PathBuf]) -> Vec<Plugin> {
+    let start = Instant::now();
+
+    let mut paths = Vec::new();
+    for dir in search_dirs {
+        match fs::read_dir(dir) {
+            Ok(entries) => {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    if path.extension().and_then(|x| x.to_str()) == Some("plugin") {
+                        paths.push(path);
+                    }
+                }
+            }
+            Err(err) => {
+                log::debug!("failed to read plugin directory {:?}: {}", dir, err);
+            }
+        }
+    }
+    paths.sort();
+
+    let mut plugins = Vec::with_capacity(paths.len());
+    for path in paths {
+        match freedesktop_entry_parser::parse_entry(&path) {
+            Ok(entry) => plugins.extend(Plugin::from_entry(&path, &entry)),
+            Err(err) => {
+                log::warn!("failed to parse {:?}: {}", path, err);
+            }
+        }
+    }
+
+    let elapsed = start.elapsed();
+    log::info!("loaded {} plugins in {:?}", plugins.len(), elapsed);
+    plugins
+}
+
+static PLUGINS: Lazy<Arc<Vec<Plugin>>> = Lazy::new(|| Arc::new(load(&plugin_dirs())));
+
+pub fn plugins() -> Arc<Vec<Plugin>> {
+    PLUGINS.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::load;
+
+    #[test]
+    fn load_and_match() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("b-images.plugin"),
+            "[Commander Plugin]\nName=Images\nMimeType=image/*;text/x-rust;\nExec=echo %f\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("a-logs.plugin"),
+            "[Commander Plugin]\nName=Logs\nGlob=*.log;\nExec=tail -f %f\nTerminal=true\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("c-no-exec.plugin"),
+            "[Commander Plugin]\nName=No Exec\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("d-missing.plugin"),
+            "[Commander Plugin]\nName=Missing\nTryExec=/nonexistent/commander-test\nExec=commander-test %f\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("ignored.txt"), "").unwrap();
+
+        let plugins = load(&[dir.path().to_path_buf()]);
+        let names: Vec<_> = plugins.iter().map(|x| x.name.as_str()).collect();
+        assert_eq!(names, ["Logs", "Images"]);
+        assert!(plugins[0].terminal);
+
+        let png = "image/png".parse().unwrap();
+        let rust = "text/x-rust".parse().unwrap();
+        let text = "text/plain".parse().unwrap();
+        assert!(plugins[1].matches(Path::new("a.png"), &png));
+        assert!(plugins[1].matches(Path::new("main.rs"), &rust));
+        assert!(!plugins[1].matches(Path::new("a.txt"), &text));
+        assert!(plugins[0].matches(Path::new("/var/log/syslog.log"), &text));
+        assert!(!plugins[0].matches(Path::new("a.txt"), &text));
+    }
+}
@@ -0,0 +1,528 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    app::{self, Core, Settings, Task},
+    executor,
+    iced::{
+        futures::{
+            channel::{mpsc, oneshot},
+            SinkExt,
+        },
+        stream, window, Subscription,
+    },
+    widget, Application, Element,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    any::TypeId, collections::VecDeque, ffi::OsStr, fmt, os::unix::ffi::OsStrExt, path::PathBuf,
+};
+use zbus::zvariant::{self, DeserializeDict, SerializeDict, Type};
+
+use crate::dialog::{
+    Dialog, DialogChoice, DialogChoiceOption, DialogFilter, DialogFilterPattern, DialogKind,
+    DialogMessage, DialogResult,
+};
+
+/// Name of the portal backend, see `res/commander.portal`
+pub const NAME: &str = "eu.fangornsrealm.commander.Portal";
+const PATH: &str = "/org/freedesktop/portal/desktop";
+
+// Response codes of the portal
+const RESPONSE_SUCCESS: u32 = 0;
+const RESPONSE_CANCELLED: u32 = 1;
+const RESPONSE_OTHER: u32 = 2;
+
+/// Filter with a label and patterns that are globs (0) or MIME types (1)
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, Type)]
+struct Filter(String, Vec<(u32, String)>);
+
+impl Filter {
+    fn dialog_filter(&self) -> DialogFilter {
+        DialogFilter {
+            label: self.0.clone(),
+            patterns: self
+                .1
+                .iter()
+                .map(|(kind, pattern)| match kind {
+                    1 => DialogFilterPattern::Mime(pattern.clone()),
+                    _ => DialogFilterPattern::Glob(pattern.clone()),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Choice with an ID, a label, options of IDs and labels and the initial option.
+/// A choice without options is a check box that is "true" or "false".
+#[derive(Clone, Debug, Deserialize, Serialize, Type)]
+struct Choice(String, String, Vec<(String, String)>, String);
+
+impl Choice {
+    fn dialog_choice(&self) -> DialogChoice {
+        let Self(id, label, options, initial) = self.clone();
+        if options.is_empty() {
+            DialogChoice::CheckBox {
+                id,
+                label,
+                value: initial == "true",
+            }
+        } else {
+            DialogChoice::ComboBox {
+                id,
+                label,
+                selected: options.iter().position(|(id, _)| *id == initial),
+                options: options
+                    .into_iter()
+                    .map(|(id, label)| DialogChoiceOption { id, label })
+                    .collect(),
+            }
+        }
+    }
+}
+
+/// ID and chosen option of a choice, as returned to the portal
+fn choice_result(choice: &DialogChoice) -> (String, String) {
+    match choice {
+        DialogChoice::CheckBox { id, value, .. } => (id.clone(), value.to_string()),
+        DialogChoice::ComboBox {
+            id,
+            options,
+            selected,
+            ..
+        } => (
+            id.clone(),
+            selected
+                .and_then(|i| options.get(i))
+                .map(|option| option.id.clone())
+                .unwrap_or_default(),
+        ),
+    }
+}
+
+/// Path of a byte string of the portal, which ends with a nul byte
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+    PathBuf::from(OsStr::from_bytes(bytes))
+}
+
+#[derive(Debug, DeserializeDict, Type)]
+#[zvariant(signature = "dict")]
+struct OpenFileOptions {
+    accept_label: Option<String>,
+    multiple: Option<bool>,
+    directory: Option<bool>,
+    filters: Option<Vec<Filter>>,
+    current_filter: Option<Filter>,
+    choices: Option<Vec<Choice>>,
+    current_folder: Option<Vec<u8>>,
+}
+
+#[derive(Debug, DeserializeDict, Type)]
+#[zvariant(signature = "dict")]
+struct SaveFileOptions {
+    accept_label: Option<String>,
+    filters: Option<Vec<Filter>>,
+    current_filter: Option<Filter>,
+    choices: Option<Vec<Choice>>,
+    current_name: Option<String>,
+    current_folder: Option<Vec<u8>>,
+    current_file: Option<Vec<u8>>,
+}
+
+#[derive(Debug, DeserializeDict, Type)]
+#[zvariant(signature = "dict")]
+struct SaveFilesOptions {
+    accept_label: Option<String>,
+    choices: Option<Vec<Choice>>,
+    current_folder: Option<Vec<u8>>,
+    files: Option<Vec<Vec<u8>>>,
+}
+
+#[derive(Debug, Default, SerializeDict, Type)]
+#[zvariant(signature = "dict")]
+struct FileChooserResult {
+    uris: Vec<String>,
+    choices: Option<Vec<(String, String)>>,
+    current_filter: Option<Filter>,
+}
+
+/// What was chosen in a dialog
+#[derive(Debug)]
+struct Response {
+    result: DialogResult,
+    filter: Option<Filter>,
+    choices: Vec<(String, String)>,
+}
+
+/// Sends the [`Response`] back to the D-Bus call, it cannot be cloned
+struct ResponseSender(Option<oneshot::Sender<Response>>);
+
+impl Clone for ResponseSender {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+impl fmt::Debug for ResponseSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseSender").finish()
+    }
+}
+
+/// Dialog requested by an application through the portal
+#[derive(Clone, Debug)]
+pub struct Request {
+    kind: DialogKind,
+    title: String,
+    accept_label: Option<String>,
+    path_opt: Option<PathBuf>,
+    filters: Vec<Filter>,
+    filter_selected: Option<usize>,
+    choices: Vec<DialogChoice>,
+    response_tx: ResponseSender,
+}
+
+/// The `org.freedesktop.impl.portal.FileChooser` interface, see
+/// <https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.FileChooser.html>
+struct FileChooser {
+    tx: mpsc::Sender<Request>,
+}
+
+impl FileChooser {
+    #[allow(clippy::too_many_arguments)]
+    async fn request(
+        &self,
+        kind: DialogKind,
+        title: &str,
+        accept_label: Option<String>,
+        path_opt: Option<PathBuf>,
+        mut filters: Vec<Filter>,
+        current_filter: Option<Filter>,
+        choices: Option<Vec<Choice>>,
+    ) -> Option<Response> {
+        // The current filter is added if it is not one of the filters
+        let filter_selected = current_filter.map(|current| {
+            filters
+                .iter()
+                .position(|filter| *filter == current)
+                .unwrap_or_else(|| {
+                    filters.push(current);
+                    filters.len() - 1
+                })
+        });
+        let (response_tx, response_rx) = oneshot::channel();
+        let request = Request {
+            kind,
+            title: title.to_string(),
+            accept_label,
+            path_opt,
+            filters,
+            filter_selected,
+            choices: choices
+                .unwrap_or_default()
+                .iter()
+                .map(Choice::dialog_choice)
+                .collect(),
+            response_tx: ResponseSender(Some(response_tx)),
+        };
+        if let Err(err) = self.tx.clone().send(request).await {
+            log::warn!("failed to request dialog: {}", err);
+            return None;
+        }
+        response_rx.await.ok()
+    }
+}
+
+/// Reply to the portal for the paths that were chosen
+fn reply(response_opt: Option<Response>) -> (u32, FileChooserResult) {
+    match response_opt {
+        Some(Response {
+            result: DialogResult::Open(paths),
+            filter,
+            choices,
+        }) => (
+            RESPONSE_SUCCESS,
+            FileChooserResult {
+                uris: paths
+                    .iter()
+                    .filter_map(|path| url::Url::from_file_path(path).ok())
+                    .map(String::from)
+                    .collect(),
+                choices: Some(choices),
+                current_filter: filter,
+            },
+        ),
+        Some(Response {
+            result: DialogResult::Cancel,
+            ..
+        }) => (RESPONSE_CANCELLED, FileChooserResult::default()),
+        None => (RESPONSE_OTHER, FileChooserResult::default()),
+    }
+}
+
+#[zbus::interface(name = "org.freedesktop.impl.portal.FileChooser")]
+impl FileChooser {
+    async fn open_file(
+        &self,
+        _handle: zvariant::ObjectPath<'_>,
+        _app_id: &str,
+        _parent_window: &str,
+        title: &str,
+        options: OpenFileOptions,
+    ) -> (u32, FileChooserResult) {
+        let kind = match (
+            options.directory.unwrap_or(false),
+            options.multiple.unwrap_or(false),
+        ) {
+            (false, false) => DialogKind::OpenFile,
+            (false, true) => DialogKind::OpenMultipleFiles,
+            (true, false) => DialogKind::OpenFolder,
+            (true, true) => DialogKind::OpenMultipleFolders,
+        };
+        reply(
+            self.request(
+                kind,
+                title,
+                options.accept_label,
+                options.current_folder.as_deref().map(path_from_bytes),
+                options.filters.unwrap_or_default(),
+                options.current_filter,
+                options.choices,
+            )
+            .await,
+        )
+    }
+
+    async fn save_file(
+        &self,
+        _handle: zvariant::ObjectPath<'_>,
+        _app_id: &str,
+        _parent_window: &str,
+        title: &str,
+        options: SaveFileOptions,
+    ) -> (u32, FileChooserResult) {
+        // An existing file is saved again in its folder under its name
+        let current_file = options.current_file.as_deref().map(path_from_bytes);
+        let filename = options
+            .current_name
+            .or_else(|| {
+                current_file
+                    .as_ref()
+                    .and_then(|path| path.file_name())
+                    .map(|name| name.to_string_lossy().to_string())
+            })
+            .unwrap_or_default();
+        let path_opt = options
+            .current_folder
+            .as_deref()
+            .map(path_from_bytes)
+            .or_else(|| current_file.and_then(|path| path.parent().map(PathBuf::from)));
+        reply(
+            self.request(
+                DialogKind::SaveFile { filename },
+                title,
+                options.accept_label,
+                path_opt,
+                options.filters.unwrap_or_default(),
+                options.current_filter,
+                options.choices,
+            )
+            .await,
+        )
+    }
+
+    async fn save_files(
+        &self,
+        _handle: zvariant::ObjectPath<'_>,
+        _app_id: &str,
+        _parent_window: &str,
+        title: &str,
+        options: SaveFilesOptions,
+    ) -> (u32, FileChooserResult) {
+        // A folder is chosen and the files are saved in it under their names
+        let mut response_opt = self
+            .request(
+                DialogKind::OpenFolder,
+                title,
+                options.accept_label,
+                options.current_folder.as_deref().map(path_from_bytes),
+                Vec::new(),
+                None,
+                options.choices,
+            )
+            .await;
+        if let Some(Response {
+            result: DialogResult::Open(paths),
+            ..
+        }) = &mut response_opt
+        {
+            if let Some(folder) = paths.pop() {
+                *paths = options
+                    .files
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|file| folder.join(path_from_bytes(file)))
+                    .collect();
+            }
+        }
+        reply(response_opt)
+    }
+}
+
+/// Serve the interface on the session bus, for as long as the connection is kept
+async fn serve(tx: mpsc::Sender<Request>) -> zbus::Result<zbus::Connection> {
+    zbus::connection::Builder::session()?
+        .serve_at(PATH, FileChooser { tx })?
+        .name(NAME)?
+        .build()
+        .await
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    DialogMessage(DialogMessage),
+    DialogResult(DialogResult),
+    Request(Request),
+}
+
+/// Application without a main window that shows the dialogs requested through the portal,
+/// one after the other
+pub struct Portal {
+    core: Core,
+    requests: VecDeque<Request>,
+    dialog_opt: Option<(Dialog<Message>, Request)>,
+}
+
+impl Portal {
+    fn next_dialog(&mut self) -> Task<Message> {
+        if self.dialog_opt.is_some() {
+            return Task::none();
+        }
+        let Some(request) = self.requests.pop_front() else {
+            return Task::none();
+        };
+
+        let (mut dialog, command) = Dialog::new(
+            request.kind.clone(),
+            request.path_opt.clone(),
+            Message::DialogMessage,
+            Message::DialogResult,
+        );
+        let mut commands = vec![command];
+        if !request.title.is_empty() {
+            commands.push(dialog.set_title(request.title.clone()));
+        }
+        if let Some(accept_label) = &request.accept_label {
+            dialog.set_accept_label(accept_label.clone());
+        }
+        dialog.set_choices(request.choices.clone());
+        let filters: Vec<_> = request.filters.iter().map(Filter::dialog_filter).collect();
+        commands.push(dialog.set_filters(filters, request.filter_selected));
+        self.dialog_opt = Some((dialog, request));
+        Task::batch(commands)
+    }
+}
+
+impl Application for Portal {
+    type Executor = executor::Default;
+    type Flags = ();
+    type Message = Message;
+
+    const APP_ID: &'static str = NAME;
+
+    fn core(&self) -> &Core {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut Core {
+        &mut self.core
+    }
+
+    fn init(core: Core, _flags: Self::Flags) -> (Self, Task<Message>) {
+        (
+            Self {
+                core,
+                requests: VecDeque::new(),
+                dialog_opt: None,
+            },
+            Task::none(),
+        )
+    }
+
+    fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::DialogMessage(dialog_message) => {
+                if let Some((dialog, _request)) = &mut self.dialog_opt {
+                    return dialog.update(dialog_message);
+                }
+            }
+            Message::DialogResult(result) => {
+                if let Some((dialog, request)) = self.dialog_opt.take() {
+                    let (_filters, filter_selected) = dialog.filters();
+                    let response = Response {
+                        result,
+                        filter: filter_selected.and_then(|i| request.filters.get(i).cloned()),
+                        choices: dialog.choices().iter().map(choice_result).collect(),
+                    };
+                    if let Some(response_tx) = request.response_tx.0 {
+                        if response_tx.send(response).is_err() {
+                            log::warn!("portal request was dropped before the dialog closed");
+                        }
+                    }
+                }
+                return self.next_dialog();
+            }
+            Message::Request(request) => {
+                self.requests.push_back(request);
+                return self.next_dialog();
+            }
+        }
+        Task::none()
+    }
+
+    fn view_window(&self, window_id: window::Id) -> Element<Message> {
+        match &self.dialog_opt {
+            Some((dialog, _request)) => dialog.view(window_id),
+            None => widget::horizontal_space().into(),
+        }
+    }
+
+    fn view(&self) -> Element<Message> {
+        // There is no main window
+        widget::horizontal_space().into()
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        struct PortalSubscription;
+
+        let mut subscriptions = vec![Subscription::run_with_id(
+            TypeId::of::<PortalSubscription>(),
+            stream::channel(16, |tx| async move {
+                // The name is released when the connection is dropped
+                let _connection = match serve(tx).await {
+                    Ok(connection) => connection,
+                    Err(err) => {
+                        log::error!("failed to serve {}: {}", NAME, err);
+                        return std::future::pending().await;
+                    }
+                };
+                std::future::pending().await
+            }),
+        )
+        .map(Message::Request)];
+        if let Some((dialog, _request)) = &self.dialog_opt {
+            subscriptions.push(dialog.subscription());
+        }
+        Subscription::batch(subscriptions)
+    }
+}
+
+/// Runs as the file chooser backend of the XDG desktop portal
+pub fn run(theme: cosmic::Theme) -> Result<(), Box<dyn std::error::Error>> {
+    let settings = Settings::default()
+        .theme(theme)
+        .no_main_window(true)
+        .exit_on_close(false);
+    app::run::<Portal>(settings, ())?;
+    Ok(())
+}
@@ -0,0 +1,180 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Printing of folder listings and files with the `lp` command of CUPS. Listings and text
+//! files are split into pages here so that they can get a header and a footer, other files
+//! are converted by the filters of CUPS.
+
+use mime_guess::mime;
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{self, Stdio},
+};
+
+use crate::{fl, listing, mime_icon::mime_for_path, operation::Controller};
+
+/// Lines of text on a page, including the header and the footer
+const LINES_PER_PAGE: usize = 64;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PrintOptions {
+    /// Name and date at the top of text pages
+    pub header: bool,
+    /// Page numbers at the bottom of text pages
+    pub footer: bool,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            header: true,
+            footer: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PrintJob {
+    /// Listing of the items in a folder
+    Listing(PathBuf),
+    Files(Vec<PathBuf>),
+}
+
+/// Files that the filters of CUPS can print
+pub fn can_print(path: &Path) -> bool {
+    if !path.is_file() {
+        return false;
+    }
+    let mime = mime_for_path(path);
+    mime.type_() == mime::TEXT
+        || mime.type_() == mime::IMAGE
+        || matches!(
+            mime.essence_str(),
+            "application/pdf" | "application/postscript"
+        )
+}
+
+/// Split text into pages separated by form feeds, with the header and footer lines
+fn paginate(title: &str, text: &str, options: PrintOptions) -> String {
+    let mut body_lines = LINES_PER_PAGE;
+    if options.header {
+        body_lines -= 2;
+    }
+    if options.footer {
+        body_lines -= 2;
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    let pages: Vec<&[&str]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(body_lines).collect()
+    };
+    let date = chrono::Local::now().format("%Y-%m-%d %H:%M");
+    let mut output = String::new();
+    for (i, page) in pages.iter().enumerate() {
+        if i > 0 {
+            output.push('\x0c');
+        }
+        if options.header {
+            output.push_str(&format!("{}    {}\n\n", title, date));
+        }
+        for line in page.iter() {
+            output.push_str(line);
+            output.push('\n');
+        }
+        if options.footer {
+            // The footer stays at the bottom of short pages
+            for _ in page.len()..body_lines {
+                output.push('\n');
+            }
+            output.push('\n');
+            output.push_str(&fl!("print-page", page = i + 1, pages = pages.len()));
+            output.push('\n');
+        }
+    }
+    output
+}
+
+fn lp(title: &str) -> process::Command {
+    let mut command = process::Command::new("lp");
+    command.arg("-t").arg(title);
+    command
+}
+
+fn run(mut command: process::Command, stdin_opt: Option<String>) -> Result<(), String> {
+    command.stdout(Stdio::null()).stderr(Stdio::piped());
+    if stdin_opt.is_some() {
+        command.stdin(Stdio::piped());
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("failed to run lp: {}", err))?;
+    if let (Some(text), Some(mut stdin)) = (stdin_opt, child.stdin.take()) {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|err| format!("failed to send the print job: {}", err))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("failed to run lp: {}", err))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+fn print_text(title: &str, text: &str, options: PrintOptions) -> Result<(), String> {
+    run(lp(title), Some(paginate(title, text, options)))
+}
+
+pub fn print(job: &PrintJob, options: PrintOptions) -> Result<(), String> {
+    match job {
+        PrintJob::Listing(dir) => {
+            let text = listing::text(dir, &Controller::default())?;
+            print_text(&dir.display().to_string(), &text, options)
+        }
+        PrintJob::Files(paths) => {
+            for path in paths {
+                let title = path.file_name().map_or_else(
+                    || path.display().to_string(),
+                    |name| name.to_string_lossy().to_string(),
+                );
+                if mime_for_path(path).type_() == mime::TEXT {
+                    let text = fs::read_to_string(path)
+                        .map_err(|err| format!("failed to read {:?}: {}", path, err))?;
+                    print_text(&title, &text, options)?;
+                } else {
+                    let mut command = lp(&title);
+                    command.arg("-o").arg("fit-to-page").arg("--").arg(path);
+                    run(command, None)?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{paginate, PrintOptions, LINES_PER_PAGE};
+
+    #[test]
+    fn paginate_adds_header_and_footer_to_every_page() {
+        let text: String = (0..100).map(|i| format!("line {i}\n")).collect();
+        let output = paginate("folder", &text, PrintOptions::default());
+        let pages: Vec<&str> = output.split('\x0c').collect();
+        assert_eq!(pages.len(), 2);
+        for page in pages {
+            assert_eq!(page.lines().count(), LINES_PER_PAGE);
+            assert!(page.starts_with("folder    "));
+        }
+
+        let options = PrintOptions {
+            header: false,
+            footer: false,
+        };
+        assert_eq!(paginate("folder", &text, options).replace('\x0c', ""), text);
+    }
+}
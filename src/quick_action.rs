@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use mime_guess::{mime, Mime};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf, process};
+
+use crate::fl;
+
+/// MIME types of fonts that are not in the `font` top level type
+const FONT_MIME_TYPES: &[&str] = &[
+    "application/font-sfnt",
+    "application/vnd.ms-opentype",
+    "application/x-font-otf",
+    "application/x-font-ttf",
+    "application/x-font-type1",
+];
+
+/// Built-in context menu action for items of some MIME types. Actions that need other
+/// programs, like extracting the audio track of a video, are plugins instead, see
+/// [`crate::plugin::Plugin`].
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum QuickAction {
+    /// Set an image as the wallpaper with the desktop portal
+    #[cfg(feature = "dbus")]
+    SetWallpaper,
+    /// Copy fonts to the font directory of the user
+    InstallFont,
+}
+
+impl QuickAction {
+    pub const ALL: &'static [Self] = &[
+        #[cfg(feature = "dbus")]
+        Self::SetWallpaper,
+        Self::InstallFont,
+    ];
+
+    pub fn name(&self) -> String {
+        match self {
+            #[cfg(feature = "dbus")]
+            Self::SetWallpaper => fl!("set-as-wallpaper"),
+            Self::InstallFont => fl!("install-font"),
+        }
+    }
+
+    /// Check if the action applies to an item of this MIME type
+    pub fn matches(&self, mime: &Mime) -> bool {
+        match self {
+            #[cfg(feature = "dbus")]
+            Self::SetWallpaper => mime.type_() == mime::IMAGE,
+            Self::InstallFont => {
+                mime.type_() == mime::FONT || FONT_MIME_TYPES.contains(&mime.essence_str())
+            }
+        }
+    }
+
+    /// Check if the action can be applied to more than one item at once
+    pub fn multiple(&self) -> bool {
+        match self {
+            #[cfg(feature = "dbus")]
+            Self::SetWallpaper => false,
+            Self::InstallFont => true,
+        }
+    }
+}
+
+/// Copy the fonts to the font directory and refresh the font cache, returns the directory
+pub fn install_fonts(paths: &[PathBuf]) -> io::Result<PathBuf> {
+    let font_dir = dirs::font_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no font directory"))?;
+    fs::create_dir_all(&font_dir)?;
+    for path in paths {
+        if let Some(name) = path.file_name() {
+            fs::copy(path, font_dir.join(name))?;
+        }
+    }
+    // Without a cache refresh, applications only find the fonts after the next login
+    match process::Command::new("fc-cache")
+        .arg("-f")
+        .arg(&font_dir)
+        .status()
+    {
+        Ok(status) if !status.success() => log::warn!("fc-cache failed with {}", status),
+        Ok(_) => {}
+        Err(err) => log::warn!("failed to run fc-cache: {}", err),
+    }
+    Ok(font_dir)
+}
+
+#[cfg(feature = "dbus")]
+pub use wallpaper::set_wallpaper;
+
+#[cfg(feature = "dbus")]
+mod wallpaper {
+    use std::{collections::HashMap, path::Path};
+    use zbus::zvariant::{OwnedObjectPath, Value};
+
+    #[zbus::proxy(
+        interface = "org.freedesktop.portal.Wallpaper",
+        default_service = "org.freedesktop.portal.Desktop",
+        default_path = "/org/freedesktop/portal/desktop"
+    )]
+    trait Wallpaper {
+        #[zbus(name = "SetWallpaperURI")]
+        fn set_wallpaper_uri(
+            &self,
+            parent_window: &str,
+            uri: &str,
+            options: HashMap<&str, Value<'_>>,
+        ) -> zbus::Result<OwnedObjectPath>;
+    }
+
+    /// Set the image as the wallpaper of all outputs, with the desktop portal
+    pub async fn set_wallpaper(path: &Path) -> zbus::Result<()> {
+        let uri = url::Url::from_file_path(path)
+            .map_err(|()| zbus::Error::Failure(format!("invalid path {:?}", path)))?;
+        let connection = zbus::Connection::session().await?;
+        WallpaperProxy::new(&connection)
+            .await?
+            .set_wallpaper_uri(
+                "",
+                uri.as_str(),
+                HashMap::from([
+                    ("show-preview", Value::from(false)),
+                    ("set-on", Value::from("background")),
+                ]),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QuickAction;
+
+    #[test]
+    fn install_font_matches_fonts() {
+        for mime_type in ["font/ttf", "font/otf", "application/x-font-ttf"] {
+            assert!(QuickAction::InstallFont.matches(&mime_type.parse().unwrap()));
+        }
+        assert!(!QuickAction::InstallFont.matches(&"image/png".parse().unwrap()));
+        assert!(!QuickAction::InstallFont.matches(&"text/plain".parse().unwrap()));
+    }
+}
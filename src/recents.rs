@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+
+use crate::mounter;
+
+/// The recently used files, shared with GTK and GIO applications
+pub fn xbel_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("recently-used.xbel"))
+}
+
+/// Record that the file was opened with the application
+pub fn add(path: &Path, app_name: &str, exec: &str) {
+    if let Err(err) = recently_used_xbel::update_recently_used(
+        &path.to_path_buf(),
+        app_name.to_string(),
+        exec.to_string(),
+        None,
+    ) {
+        log::warn!("failed to add {:?} to recent files: {:?}", path, err);
+    }
+}
+
+/// Files that were used recently, the latest first. Remote files are listed by the local path
+/// of their mount. Files that do not exist anymore or are on unmounted volumes are skipped.
+pub fn recent_files() -> Vec<PathBuf> {
+    let recent_files = match recently_used_xbel::parse_file() {
+        Ok(ok) => ok,
+        Err(err) => {
+            log::warn!("failed to read recent files: {:?}", err);
+            return Vec::new();
+        }
+    };
+
+    let mut recents = Vec::new();
+    for bookmark in recent_files.bookmarks {
+        // Other applications also add URIs of remote files, like those on SFTP servers
+        let Some(path) = url::Url::parse(&bookmark.href).ok().and_then(|url| {
+            if url.scheme() == "file" {
+                url.to_file_path().ok()
+            } else {
+                mounter::network_path(url.as_str())
+            }
+        }) else {
+            continue;
+        };
+        // Modified is updated when a file is added again, visited when it is opened
+        let Some(used) = [&bookmark.modified, &bookmark.visited]
+            .into_iter()
+            .filter_map(|date_time| date_time.parse::<DateTime<Utc>>().ok())
+            .max()
+        else {
+            continue;
+        };
+        if path.exists() {
+            recents.push((path, used));
+        }
+    }
+    recents.sort_by(|a, b| b.1.cmp(&a.1));
+    recents.into_iter().map(|(path, _used)| path).collect()
+}
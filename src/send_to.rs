@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{path::PathBuf, process};
+
+/// Target of the send to dialog
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SendTo {
+    /// Compose an email with the items attached
+    Email,
+    /// Push the items to a paired Bluetooth device
+    #[cfg(feature = "dbus")]
+    Bluetooth(BluetoothDevice),
+    /// Copy the items to a bookmarked folder
+    Folder(PathBuf),
+    /// Run a send to command of the config, by index
+    Command(usize),
+}
+
+/// Paired Bluetooth device that accepts files
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BluetoothDevice {
+    pub name: String,
+    pub address: String,
+}
+
+/// Command line that composes an email with the items attached, with `xdg-email`
+pub fn email_command(paths: &[PathBuf]) -> process::Command {
+    let mut command = process::Command::new("xdg-email");
+    for path in paths {
+        command.arg("--attach").arg(path);
+    }
+    command
+}
+
+#[cfg(feature = "dbus")]
+pub use bluetooth::{bluetooth_devices, bluetooth_send};
+
+#[cfg(feature = "dbus")]
+mod bluetooth {
+    use cosmic::iced::futures::StreamExt;
+    use std::{
+        collections::HashMap,
+        path::{Path, PathBuf},
+    };
+    use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+    use super::BluetoothDevice;
+
+    /// UUID of the OBEX object push profile
+    const OBJECT_PUSH_UUID: &str = "00001105-0000-1000-8000-00805f9b34fb";
+
+    #[zbus::proxy(interface = "org.bluez.Device1", default_service = "org.bluez")]
+    trait Device1 {
+        #[zbus(property)]
+        fn address(&self) -> zbus::Result<String>;
+
+        #[zbus(property)]
+        fn alias(&self) -> zbus::Result<String>;
+
+        #[zbus(property)]
+        fn paired(&self) -> zbus::Result<bool>;
+
+        #[zbus(property, name = "UUIDs")]
+        fn uuids(&self) -> zbus::Result<Vec<String>>;
+    }
+
+    #[zbus::proxy(
+        interface = "org.bluez.obex.Client1",
+        default_service = "org.bluez.obex",
+        default_path = "/org/bluez/obex"
+    )]
+    trait ObexClient {
+        fn create_session(
+            &self,
+            destination: &str,
+            args: HashMap<&str, Value<'_>>,
+        ) -> zbus::Result<OwnedObjectPath>;
+
+        fn remove_session(&self, session: &ObjectPath<'_>) -> zbus::Result<()>;
+    }
+
+    #[zbus::proxy(
+        interface = "org.bluez.obex.ObjectPush1",
+        default_service = "org.bluez.obex"
+    )]
+    trait ObexObjectPush {
+        fn send_file(
+            &self,
+            sourcefile: &str,
+        ) -> zbus::Result<(OwnedObjectPath, HashMap<String, OwnedValue>)>;
+    }
+
+    #[zbus::proxy(
+        interface = "org.bluez.obex.Transfer1",
+        default_service = "org.bluez.obex"
+    )]
+    trait ObexTransfer {
+        #[zbus(property)]
+        fn status(&self) -> zbus::Result<String>;
+    }
+
+    /// Paired devices that support the object push profile, sorted by name
+    pub async fn bluetooth_devices() -> zbus::Result<Vec<BluetoothDevice>> {
+        let connection = zbus::Connection::system().await?;
+        let objects = zbus::fdo::ObjectManagerProxy::builder(&connection)
+            .destination("org.bluez")?
+            .path("/")?
+            .build()
+            .await?
+            .get_managed_objects()
+            .await?;
+        let mut devices = Vec::new();
+        for (path, interfaces) in objects {
+            if !interfaces
+                .keys()
+                .any(|interface| interface.as_str() == "org.bluez.Device1")
+            {
+                continue;
+            }
+            let device = Device1Proxy::builder(&connection)
+                .path(path)?
+                .build()
+                .await?;
+            if !device.paired().await.unwrap_or(false)
+                || !device
+                    .uuids()
+                    .await
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|uuid| uuid.eq_ignore_ascii_case(OBJECT_PUSH_UUID))
+            {
+                continue;
+            }
+            let address = device.address().await?;
+            let name = device.alias().await.unwrap_or_else(|_| address.clone());
+            devices.push(BluetoothDevice { name, address });
+        }
+        devices.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(devices)
+    }
+
+    /// Push the file in the session and wait until it is transferred
+    async fn send_file(
+        connection: &zbus::Connection,
+        object_push: &ObexObjectPushProxy<'_>,
+        path: &Path,
+    ) -> zbus::Result<()> {
+        let (transfer_path, _properties) = object_push.send_file(&path.to_string_lossy()).await?;
+        let transfer = ObexTransferProxy::builder(connection)
+            .path(transfer_path)?
+            .build()
+            .await?;
+        let mut changes = transfer.receive_status_changed().await;
+        let mut status = transfer.status().await?;
+        loop {
+            match status.as_str() {
+                "complete" => return Ok(()),
+                "error" => return Err(zbus::Error::Failure(format!("failed to send {:?}", path))),
+                _ => {}
+            }
+            match changes.next().await {
+                Some(change) => status = change.get().await?,
+                // The transfer is removed when it is done
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Push the files to the device with the OBEX daemon, one after the other. The session
+    /// is removed by the daemon when the connection is closed, so this has to be awaited
+    /// until the end.
+    pub async fn bluetooth_send(device: &BluetoothDevice, paths: &[PathBuf]) -> zbus::Result<()> {
+        let connection = zbus::Connection::session().await?;
+        let client = ObexClientProxy::new(&connection).await?;
+        let session = client
+            .create_session(
+                &device.address,
+                HashMap::from([("Target", Value::from("opp"))]),
+            )
+            .await?;
+        let object_push = ObexObjectPushProxy::builder(&connection)
+            .path(session.clone())?
+            .build()
+            .await?;
+        let mut result = Ok(());
+        for path in paths {
+            result = send_file(&connection, &object_push, path).await;
+            if result.is_err() {
+                break;
+            }
+        }
+        if let Err(err) = client.remove_session(&session).await {
+            log::warn!(
+                "failed to remove OBEX session {}: {}",
+                session.as_str(),
+                err
+            );
+        }
+        result
+    }
+}
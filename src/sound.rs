@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Sound effects of operations from the freedesktop sound theme. They are played with
+//! `canberra-gtk-play` so that the sound theme of the desktop is used, without it the files
+//! of the freedesktop theme are played with `paplay`.
+
+use std::{env, io, path::PathBuf, process};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Sound {
+    /// Items were moved to the trash or the trash was emptied
+    Trash,
+    /// A long running operation is finished
+    Complete,
+    /// An operation failed
+    Error,
+}
+
+impl Sound {
+    /// Name of the sound in the freedesktop sound naming specification
+    pub fn event_id(&self) -> &'static str {
+        match self {
+            Self::Trash => "trash-empty",
+            Self::Complete => "complete",
+            // The freedesktop theme has no dialog-error
+            Self::Error => "dialog-warning",
+        }
+    }
+}
+
+/// File of the sound in the freedesktop theme
+fn theme_file(event_id: &str) -> Option<PathBuf> {
+    let data_dirs = env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|data_dirs| !data_dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    data_dirs
+        .split(':')
+        .flat_map(|data_dir| {
+            ["oga", "ogg", "wav"].map(|extension| {
+                PathBuf::from(data_dir)
+                    .join("sounds/freedesktop/stereo")
+                    .join(format!("{}.{}", event_id, extension))
+            })
+        })
+        .find(|path| path.is_file())
+}
+
+/// Volume in percent as decibels, like canberra expects it
+fn decibels(volume: u8) -> f32 {
+    20.0 * (f32::from(volume) / 100.0).log10()
+}
+
+/// Play the sound at `volume` percent and wait until it is finished
+pub fn play(sound: Sound, volume: u8) -> Result<(), String> {
+    if volume == 0 {
+        return Ok(());
+    }
+    let status = match process::Command::new("canberra-gtk-play")
+        .arg("-i")
+        .arg(sound.event_id())
+        .arg("-V")
+        .arg(format!("{:.1}", decibels(volume)))
+        .status()
+    {
+        Ok(status) => status,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            let path = theme_file(sound.event_id())
+                .ok_or_else(|| format!("no sound file for {}", sound.event_id()))?;
+            process::Command::new("paplay")
+                .arg(format!("--volume={}", u32::from(volume) * 65536 / 100))
+                .arg(path)
+                .status()
+                .map_err(|err| format!("failed to run paplay: {}", err))?
+        }
+        Err(err) => return Err(format!("failed to run canberra-gtk-play: {}", err)),
+    };
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("failed to play {}: {}", sound.event_id(), status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decibels;
+
+    #[test]
+    fn volume_as_decibels() {
+        assert_eq!(decibels(100), 0.0);
+        assert!((decibels(10) + 20.0).abs() < 0.001);
+        assert!(decibels(50) < -6.0);
+    }
+}
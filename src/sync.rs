@@ -0,0 +1,565 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Folder synchronization of the sync profiles. A sync first plans every change by comparing
+//! the two folders, so the plan can be previewed, and then copies and removes the files.
+//! Profiles that mirror their source are synchronized in the background when it changes.
+
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{RecursiveMode, Watcher},
+    DebounceEventResult,
+};
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+use walkdir::WalkDir;
+
+use crate::{
+    config::{SyncCompare, SyncDirection, SyncProfile},
+    err_str,
+    operation::Controller,
+};
+
+/// Modified times closer than this are the same, for file systems like FAT with coarse times
+const MODIFIED_TOLERANCE: Duration = Duration::from_secs(2);
+
+/// Mirrors also synchronize this often, for changes that were missed while watching
+const MIRROR_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    is_dir: bool,
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+/// Changes of a sync, found before anything is changed
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SyncPlan {
+    /// Folders that are created, so empty folders are synchronized too
+    pub folders: Vec<PathBuf>,
+    /// Files that are copied, from and to, with their sizes
+    pub copies: Vec<(PathBuf, PathBuf, u64)>,
+    /// Items that are only in the destination of a one way sync, moved to the trash
+    pub orphans: Vec<PathBuf>,
+    /// Files of the destination of a one way sync that are newer than their source, so they
+    /// were changed in the destination and the sync overwrites these changes
+    pub conflicts: Vec<PathBuf>,
+}
+
+impl SyncPlan {
+    pub fn bytes(&self) -> u64 {
+        self.copies.iter().map(|(_from, _to, len)| len).sum()
+    }
+}
+
+/// Counts of a finished sync
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SyncReport {
+    pub copied: usize,
+    pub deleted: usize,
+    pub bytes: u64,
+}
+
+fn excluded(name: &OsStr, excludes: &[glob::Pattern]) -> bool {
+    let name = name.to_string_lossy();
+    excludes.iter().any(|pattern| pattern.matches(&name))
+}
+
+/// Items below `root` by their relative paths. A missing root has no items, so a sync can
+/// create its destination, [`plan`] refuses a missing source.
+fn scan(
+    root: &Path,
+    excludes: &[glob::Pattern],
+    controller: &Controller,
+) -> Result<BTreeMap<PathBuf, Entry>, String> {
+    let mut entries = BTreeMap::new();
+    if !root.exists() {
+        return Ok(entries);
+    }
+    for entry_res in WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| !excluded(entry.file_name(), excludes))
+    {
+        controller.check()?;
+        let entry = entry_res.map_err(err_str)?;
+        let metadata = entry.metadata().map_err(err_str)?;
+        let relative = entry.path().strip_prefix(root).map_err(err_str)?;
+        entries.insert(
+            relative.to_path_buf(),
+            Entry {
+                is_dir: metadata.is_dir(),
+                len: metadata.len(),
+                modified: metadata.modified().ok(),
+            },
+        );
+    }
+    Ok(entries)
+}
+
+fn hash_file(path: &Path, controller: &Controller) -> Result<blake3::Hash, String> {
+    let mut file =
+        fs::File::open(path).map_err(|err| format!("failed to open {:?}: {}", path, err))?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = vec![0; 1024 * 1024];
+    loop {
+        controller.check()?;
+        let count = match file.read(&mut buf) {
+            Ok(0) => break,
+            Ok(count) => count,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(format!("failed to read {:?}: {}", path, err)),
+        };
+        hasher.update(&buf[..count]);
+    }
+    Ok(hasher.finalize())
+}
+
+fn same_modified(a: Option<SystemTime>, b: Option<SystemTime>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            a.duration_since(b).unwrap_or_else(|err| err.duration()) <= MODIFIED_TOLERANCE
+        }
+        _ => false,
+    }
+}
+
+fn changed(
+    a: (&Path, Entry),
+    b: (&Path, Entry),
+    compare: SyncCompare,
+    controller: &Controller,
+) -> Result<bool, String> {
+    if a.1.len != b.1.len {
+        return Ok(true);
+    }
+    match compare {
+        SyncCompare::SizeAndModified => Ok(!same_modified(a.1.modified, b.1.modified)),
+        SyncCompare::Checksum => Ok(hash_file(a.0, controller)? != hash_file(b.0, controller)?),
+    }
+}
+
+fn type_conflict(relative: &Path) -> String {
+    format!(
+        "{:?} is a folder on one side and a file on the other",
+        relative
+    )
+}
+
+/// Path with symbolic links and `..` resolved. Of a path that does not exist yet, the
+/// existing part is resolved and the rest is appended.
+fn resolve(path: &Path) -> Result<PathBuf, String> {
+    for ancestor in path.ancestors() {
+        if let Ok(resolved) = fs::canonicalize(ancestor) {
+            let rest = path.strip_prefix(ancestor).map_err(err_str)?;
+            return Ok(resolved.join(rest));
+        }
+    }
+    Err(format!("failed to resolve {:?}", path))
+}
+
+/// Compare the folders of the profile and list the changes that the sync makes
+pub fn plan(profile: &SyncProfile, controller: &Controller) -> Result<SyncPlan, String> {
+    let excludes: Vec<glob::Pattern> = profile
+        .excludes
+        .iter()
+        .filter(|pattern| !pattern.is_empty())
+        .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+        .collect();
+    // A missing source is usually an unmounted drive or share, syncing it would remove
+    // everything from the destination
+    if !profile.source.is_dir() {
+        return Err(format!("source {:?} is not a folder", profile.source));
+    }
+    let (source, destination) = (resolve(&profile.source)?, resolve(&profile.destination)?);
+    if destination.starts_with(&source) || source.starts_with(&destination) {
+        return Err(format!(
+            "{:?} and {:?} are inside each other",
+            profile.source, profile.destination
+        ));
+    }
+    let sources = scan(&profile.source, &excludes, controller)?;
+    let destinations = scan(&profile.destination, &excludes, controller)?;
+
+    let mut plan = SyncPlan::default();
+    if !profile.destination.exists() {
+        plan.folders.push(profile.destination.clone());
+    }
+    for (relative, source) in sources.iter() {
+        let from = profile.source.join(relative);
+        let to = profile.destination.join(relative);
+        match destinations.get(relative) {
+            Some(destination) if destination.is_dir != source.is_dir => {
+                return Err(type_conflict(relative));
+            }
+            Some(destination) => {
+                if !source.is_dir
+                    && changed(
+                        (&from, *source),
+                        (&to, *destination),
+                        profile.compare,
+                        controller,
+                    )?
+                {
+                    // Two way syncs keep the newer file
+                    if profile.direction == SyncDirection::TwoWay
+                        && destination.modified > source.modified
+                    {
+                        plan.copies.push((to, from, destination.len));
+                    } else {
+                        if destination.modified > source.modified {
+                            plan.conflicts.push(to.clone());
+                        }
+                        plan.copies.push((from, to, source.len));
+                    }
+                }
+            }
+            None if source.is_dir => plan.folders.push(to),
+            None => plan.copies.push((from, to, source.len)),
+        }
+    }
+    for (relative, destination) in destinations.iter() {
+        if sources.contains_key(relative) {
+            continue;
+        }
+        let from = profile.destination.join(relative);
+        match profile.direction {
+            SyncDirection::TwoWay if destination.is_dir => {
+                plan.folders.push(profile.source.join(relative));
+            }
+            SyncDirection::TwoWay => {
+                plan.copies
+                    .push((from, profile.source.join(relative), destination.len));
+            }
+            SyncDirection::OneWay if profile.delete_orphans => {
+                // Items of orphaned folders go to the trash with their folder
+                let parent_synced = relative.parent().map_or(true, |parent| {
+                    parent.as_os_str().is_empty() || sources.contains_key(parent)
+                });
+                if parent_synced {
+                    plan.orphans.push(from);
+                }
+            }
+            SyncDirection::OneWay => {}
+        }
+    }
+    Ok(plan)
+}
+
+/// Copy through a temporary name, so an interrupted sync leaves no partial file behind that
+/// looks complete, and keep the modified time for comparing the next time
+fn copy_file(from: &Path, to: &Path) -> Result<(), String> {
+    let (Some(parent), Some(name)) = (to.parent(), to.file_name()) else {
+        return Err(format!("invalid destination {:?}", to));
+    };
+    fs::create_dir_all(parent).map_err(|err| format!("failed to create {:?}: {}", parent, err))?;
+    let mut partial_name = OsStr::new(".").to_os_string();
+    partial_name.push(name);
+    partial_name.push(".sync-partial");
+    let partial = parent.join(partial_name);
+    let res = (|| -> io::Result<()> {
+        fs::copy(from, &partial)?;
+        let modified = fs::metadata(from)?.modified()?;
+        fs::File::options()
+            .write(true)
+            .open(&partial)?
+            .set_modified(modified)?;
+        fs::rename(&partial, to)
+    })();
+    if let Err(err) = res {
+        let _ = fs::remove_file(&partial);
+        return Err(format!("failed to copy {:?} to {:?}: {}", from, to, err));
+    }
+    Ok(())
+}
+
+/// Make the changes of a plan
+pub fn execute(plan: &SyncPlan, controller: &Controller) -> Result<SyncReport, String> {
+    let total = plan.bytes().max(1);
+    let mut report = SyncReport::default();
+    for folder in plan.folders.iter() {
+        controller.check()?;
+        fs::create_dir_all(folder)
+            .map_err(|err| format!("failed to create {:?}: {}", folder, err))?;
+    }
+    for (from, to, len) in plan.copies.iter() {
+        controller.check()?;
+        controller.set_progress(report.bytes as f32 / total as f32);
+        copy_file(from, to)?;
+        report.copied += 1;
+        report.bytes += len;
+    }
+    for orphan in plan.orphans.iter() {
+        controller.check()?;
+        trash::delete(orphan)
+            .map_err(|err| format!("failed to move {:?} to the trash: {}", orphan, err))?;
+        report.deleted += 1;
+    }
+    controller.set_progress(1.0);
+    Ok(report)
+}
+
+/// Synchronize the folders of the profile
+pub fn run(profile: &SyncProfile, controller: &Controller) -> Result<SyncReport, String> {
+    let plan = plan(profile, controller)?;
+    execute(&plan, controller)
+}
+
+/// Log of the changes in the destinations of mirrors that were overwritten
+pub fn conflict_log_path() -> Option<PathBuf> {
+    dirs::state_dir().map(|dir| dir.join("commander").join("sync-conflicts.log"))
+}
+
+fn log_conflicts(profile: &SyncProfile, conflicts: &[PathBuf]) -> io::Result<()> {
+    let Some(path) = conflict_log_path() else {
+        return Ok(());
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::File::options().create(true).append(true).open(path)?;
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+    for conflict in conflicts {
+        writeln!(
+            file,
+            "{}\t{}\toverwrote changes of {}",
+            now,
+            profile.name,
+            conflict.display()
+        )?;
+    }
+    Ok(())
+}
+
+/// Synchronize the source of the profile to its destination one way, logging the changes of
+/// the destination that are overwritten
+pub fn mirror(profile: &SyncProfile, controller: &Controller) -> Result<SyncReport, String> {
+    let profile = SyncProfile {
+        direction: SyncDirection::OneWay,
+        ..profile.clone()
+    };
+    let plan = plan(&profile, controller)?;
+    if !plan.conflicts.is_empty() {
+        log::warn!(
+            "sync profile {:?} overwrites changes of {:?}",
+            profile.name,
+            plan.conflicts
+        );
+        if let Err(err) = log_conflicts(&profile, &plan.conflicts) {
+            log::warn!("failed to log sync conflicts: {}", err);
+        }
+    }
+    execute(&plan, controller)
+}
+
+/// Mirror the profile whenever its source changes, until the future is dropped
+pub async fn watch(profile: SyncProfile) {
+    let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+    let debouncer_res = new_debouncer(
+        Duration::from_secs(2),
+        None,
+        move |events_res: DebounceEventResult| match events_res {
+            Ok(events) => {
+                if !events.is_empty() {
+                    let _ = event_tx.send(());
+                }
+            }
+            Err(errs) => log::warn!("failed to watch sync source: {:?}", errs),
+        },
+    );
+    // Dropping the watcher stops watching
+    let _debouncer_opt = match debouncer_res {
+        Ok(mut debouncer) => {
+            if let Err(err) = debouncer
+                .watcher()
+                .watch(&profile.source, RecursiveMode::Recursive)
+            {
+                log::warn!("failed to watch {:?}: {}", profile.source, err);
+            }
+            Some(debouncer)
+        }
+        Err(err) => {
+            log::warn!("failed to create sync watcher: {}", err);
+            None
+        }
+    };
+
+    loop {
+        let mirror_profile = profile.clone();
+        match tokio::task::spawn_blocking(move || mirror(&mirror_profile, &Controller::default()))
+            .await
+        {
+            Ok(Ok(report)) => {
+                if report.copied > 0 || report.deleted > 0 {
+                    log::info!("mirrored sync profile {:?}: {:?}", profile.name, report);
+                }
+            }
+            Ok(Err(err)) => log::warn!("failed to mirror sync profile {:?}: {}", profile.name, err),
+            Err(err) => log::warn!("failed to mirror sync profile {:?}: {}", profile.name, err),
+        }
+
+        // Without a watcher, the source is only synchronized from time to time
+        if let Ok(None) = tokio::time::timeout(MIRROR_INTERVAL, event_rx.recv()).await {
+            tokio::time::sleep(MIRROR_INTERVAL).await;
+        }
+        // Changes that come in together are mirrored at once
+        while event_rx.try_recv().is_ok() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs, io,
+        path::Path,
+        time::{Duration, SystemTime},
+    };
+
+    use super::{execute, plan};
+    use crate::{
+        config::{SyncCompare, SyncDirection, SyncProfile},
+        operation::Controller,
+    };
+
+    fn write(path: &Path, contents: &str, age: u64) -> io::Result<()> {
+        fs::write(path, contents)?;
+        fs::File::options()
+            .write(true)
+            .open(path)?
+            .set_modified(SystemTime::now() - Duration::from_secs(age))
+    }
+
+    fn profile(root: &Path) -> io::Result<SyncProfile> {
+        let source = root.join("source");
+        let destination = root.join("destination");
+        fs::create_dir_all(source.join("nested"))?;
+        fs::create_dir_all(destination.join("orphaned"))?;
+        write(&source.join("same"), "same", 100)?;
+        write(&destination.join("same"), "same", 100)?;
+        write(&source.join("newer"), "newer", 10)?;
+        write(&destination.join("newer"), "older", 100)?;
+        write(&source.join("nested").join("new"), "new", 10)?;
+        write(&source.join("ignored.tmp"), "ignored", 10)?;
+        write(&destination.join("orphaned").join("old"), "old", 100)?;
+        write(&destination.join("only-here"), "only here", 10)?;
+        Ok(SyncProfile {
+            name: "test".to_string(),
+            source,
+            destination,
+            excludes: vec!["*.tmp".to_string()],
+            ..SyncProfile::default()
+        })
+    }
+
+    #[test]
+    fn one_way_sync_mirrors_source() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut profile = profile(dir.path())?;
+        profile.delete_orphans = true;
+        let controller = Controller::default();
+
+        let plan = plan(&profile, &controller).unwrap();
+        assert_eq!(plan.copies.len(), 2);
+        assert_eq!(
+            plan.orphans,
+            vec![
+                profile.destination.join("only-here"),
+                profile.destination.join("orphaned"),
+            ]
+        );
+
+        // Orphans are left alone here, they would go to the trash of the test machine
+        let mut plan = plan;
+        plan.orphans.clear();
+        let report = execute(&plan, &controller).unwrap();
+        assert_eq!(report.copied, 2);
+        assert_eq!(
+            fs::read_to_string(profile.destination.join("newer"))?,
+            "newer"
+        );
+        assert_eq!(
+            fs::read_to_string(profile.destination.join("nested").join("new"))?,
+            "new"
+        );
+        assert!(!profile.destination.join("ignored.tmp").exists());
+
+        // Nothing is left to do after a sync
+        let plan = super::plan(&profile, &controller).unwrap();
+        assert!(plan.copies.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn one_way_sync_finds_conflicts() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut profile = profile(dir.path())?;
+        write(&profile.destination.join("same"), "changed", 1)?;
+
+        let controller = Controller::default();
+        let plan = plan(&profile, &controller).unwrap();
+        assert_eq!(plan.conflicts, vec![profile.destination.join("same")]);
+
+        profile.destination = profile.source.join("nested");
+        assert!(super::plan(&profile, &controller).is_err());
+        // Also through .. and symbolic links
+        profile.destination = dir.path().join("destination").join("..").join("source");
+        assert!(super::plan(&profile, &controller).is_err());
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&profile.source, &link)?;
+        profile.destination = link.join("new");
+        assert!(super::plan(&profile, &controller).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn missing_source_is_refused() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut profile = profile(dir.path())?;
+        profile.delete_orphans = true;
+        fs::remove_dir_all(&profile.source)?;
+
+        let controller = Controller::default();
+        assert!(plan(&profile, &controller).is_err());
+        profile.direction = SyncDirection::TwoWay;
+        assert!(plan(&profile, &controller).is_err());
+        assert!(profile.destination.join("only-here").exists());
+
+        // Only the destination may be missing
+        fs::create_dir(&profile.source)?;
+        fs::remove_dir_all(&profile.destination)?;
+        let plan = plan(&profile, &controller).unwrap();
+        assert_eq!(plan.folders, vec![profile.destination.clone()]);
+        Ok(())
+    }
+
+    #[test]
+    fn two_way_sync_keeps_newer_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut profile = profile(dir.path())?;
+        profile.direction = SyncDirection::TwoWay;
+        profile.compare = SyncCompare::Checksum;
+        write(&profile.destination.join("same"), "changed", 1)?;
+
+        let controller = Controller::default();
+        let plan = plan(&profile, &controller).unwrap();
+        assert!(plan.orphans.is_empty());
+        execute(&plan, &controller).unwrap();
+        assert_eq!(fs::read_to_string(profile.source.join("same"))?, "changed");
+        assert_eq!(
+            fs::read_to_string(profile.destination.join("newer"))?,
+            "newer"
+        );
+        assert_eq!(
+            fs::read_to_string(profile.source.join("only-here"))?,
+            "only here"
+        );
+        assert!(profile.source.join("orphaned").join("old").exists());
+        Ok(())
+    }
+}
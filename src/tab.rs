@@ -6,7 +6,7 @@ use cosmic::{
             text::{self, Paragraph},
         },
         alignment::{Horizontal, Vertical},
-        clipboard::dnd::DndAction,
+        clipboard::{self, dnd::DndAction},
         event,
         futures,
         futures::SinkExt,
@@ -16,16 +16,19 @@ use cosmic::{
         widget::{
             horizontal_rule, rule,
             scrollable::{self, AbsoluteOffset, Viewport},
+            Stack,
         },
         Alignment,
         Border,
         Color,
         ContentFit,
         Length,
+        Padding,
         Point,
         Rectangle,
         Size,
         Subscription,
+        Vector,
     },
     iced_core::{mouse::ScrollDelta, widget::tree},
     theme,
@@ -37,7 +40,7 @@ use cosmic::{
     Element,
 };
 
-use chrono::{DateTime, Utc};
+use hex_color::HexColor;
 use i18n_embed::LanguageLoader;
 use mime_guess::{mime, Mime};
 use once_cell::sync::Lazy;
@@ -45,12 +48,14 @@ use serde::{Deserialize, Serialize};
 use std::{
     cell::Cell,
     cmp::Ordering,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
+    env,
     error::Error,
+    ffi::OsString,
     fmt::{self, Display},
     fs::{self, File, Metadata},
     io::{BufRead, BufReader},
-    os::unix::fs::MetadataExt,
+    os::unix::fs::{MetadataExt, PermissionsExt},
     path::{Path, PathBuf},
     sync::{atomic, Arc, Mutex, RwLock},
     time::{Duration, Instant, SystemTime},
@@ -58,25 +63,51 @@ use std::{
 use tokio::sync::mpsc;
 use walkdir::WalkDir;
 
+#[cfg(feature = "gstreamer")]
+use crate::media::{self, MediaPlayer};
+#[cfg(feature = "pdf")]
+use crate::pdf::{self, PdfPage};
 use crate::{
-    app::{Action, PreviewItem1, PreviewKind},
-    clipboard::{ClipboardCopy, ClipboardKind, ClipboardPaste},
-    config::{DesktopConfig, IconSizes, TabConfig1, ICON_SCALE_MAX, ICON_SIZE_GRID},
+    a11y,
+    app::{Action, PaneType, PreviewItem, PreviewKind},
+    checksum::{self, ChecksumState, Checksums},
+    clipboard::{ClipboardKind, ClipboardPaste},
+    config::{
+        desktop_layout_key, ColorRule, DateFormat, DesktopConfig, DesktopLayout, DesktopPosition,
+        FormatConfig, IconSizes, PreviewLimits, SizeUnits, TabConfig, ICON_SCALE_MAX,
+        ICON_SIZE_GRID,
+    },
     dialog::DialogKind,
+    dnd::DndDrag,
     fl,
+    hexview::{self, HexView},
+    highlight::{self, HighlightedText},
     localize::{LANGUAGE_CHRONO, LANGUAGE_SORTER},
-    menu, mime_app,
-    mime_icon::{mime_for_path, mime_icon},
-    mounter::MOUNTERS,
-    mouse_area,
-    operation::Controller,
-    thumbnailer::thumbnailer,
+    menu,
+    metadata::{self, MediaMetadata},
+    mime_app,
+    mime_icon::{self, mime_for_path, mime_icon, Emblem},
+    mounter::{self, MOUNTERS},
+    mouse_area::{self, Gesture},
+    operation::{self, Controller},
+    recents,
+    thumbnailer::{self, thumbnailer},
+    transform::{self, ImageTransform},
+    volume_trash,
 };
 use unix_permissions_ext::UNIXPermissionsExt;
 use uzers::{get_group_by_gid, get_user_by_uid};
 
 pub const DOUBLE_CLICK_DURATION: Duration = Duration::from_millis(500);
+/// Average width of a character of body text, used to guess if a name is cut off
+const NAME_CHAR_WIDTH: f32 = 7.5;
+/// Part of the kinetic scrolling speed that is kept each frame
+const KINETIC_SCROLL_FRICTION: f32 = 0.95;
+/// Pixels per frame below which kinetic scrolling stops
+const KINETIC_SCROLL_MIN_VELOCITY: f32 = 0.5;
 pub const HOVER_DURATION: Duration = Duration::from_millis(1600);
+/// Time a folder has to be hovered during a drag before it is opened
+const SPRING_LOAD_DURATION: Duration = Duration::from_millis(1000);
 //TODO: best limit for search items
 const MAX_SEARCH_LATENCY: Duration = Duration::from_millis(20);
 const MAX_SEARCH_RESULTS: usize = 200;
@@ -86,6 +117,10 @@ const THUMBNAIL_SIZE: u32 = (ICON_SIZE_GRID as u32) * (ICON_SCALE_MAX as u32);
 //TODO: adjust for locales?
 const DATE_TIME_FORMAT: &str = "%b %-d, %-Y, %-I:%M %p";
 const TIME_FORMAT: &str = "%-I:%M %p";
+const ISO_DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+/// Formats of sizes and dates, set from the config by the app
+static FORMAT_CONFIG: Lazy<RwLock<FormatConfig>> =
+    Lazy::new(|| RwLock::new(FormatConfig::default()));
 static SPECIAL_DIRS: Lazy<HashMap<PathBuf, &'static str>> = Lazy::new(|| {
     let mut special_dirs = HashMap::new();
     if let Some(dir) = dirs::document_dir() {
@@ -126,6 +161,7 @@ fn button_appearance(
     accent: bool,
     condensed_radius: bool,
     desktop: bool,
+    dimmed: bool,
 ) -> widget::button::Style {
     let cosmic = theme.cosmic();
     let mut appearance = widget::button::Style::new();
@@ -150,6 +186,14 @@ fn button_appearance(
         appearance.icon_color = Some(Color::from(cosmic.on_bg_color()));
         appearance.text_color = Some(Color::from(cosmic.on_bg_color()));
     }
+    if dimmed && !(selected && accent) {
+        let mut color = appearance
+            .text_color
+            .unwrap_or_else(|| Color::from(cosmic.on_bg_color()));
+        color.a *= 0.5;
+        appearance.icon_color = Some(color);
+        appearance.text_color = Some(color);
+    }
     if focused && accent {
         appearance.outline_width = 1.0;
         appearance.outline_color = Color::from(cosmic.accent_color());
@@ -170,6 +214,7 @@ fn button_style(
     accent: bool,
     condensed_radius: bool,
     desktop: bool,
+    dimmed: bool,
 ) -> theme::Button {
     //TODO: move to libcosmic?
     theme::Button::Custom {
@@ -182,6 +227,7 @@ fn button_style(
                 accent,
                 condensed_radius,
                 desktop,
+                dimmed,
             )
         }),
         disabled: Box::new(move |theme| {
@@ -193,6 +239,7 @@ fn button_style(
                 accent,
                 condensed_radius,
                 desktop,
+                dimmed,
             )
         }),
         hovered: Box::new(move |focused, theme| {
@@ -204,6 +251,7 @@ fn button_style(
                 accent,
                 condensed_radius,
                 desktop,
+                dimmed,
             )
         }),
         pressed: Box::new(move |focused, theme| {
@@ -215,6 +263,7 @@ fn button_style(
                 accent,
                 condensed_radius,
                 desktop,
+                dimmed,
             )
         }),
     }
@@ -235,20 +284,68 @@ pub fn folder_icon_symbolic(path: &PathBuf, icon_size: u16) -> widget::icon::Han
     .handle()
 }
 
+/// Expand a leading `~` and environment variables like `$HOME` or `${HOME}` in a typed path.
+/// Unknown variables are kept as typed.
+fn expand_path(input: &str) -> PathBuf {
+    let mut expanded = OsString::with_capacity(input.len());
+    let mut rest = input;
+    if rest == "~" || rest.starts_with("~/") {
+        if let Some(home_dir) = dirs::home_dir() {
+            expanded.push(home_dir);
+            rest = &rest[1..];
+        }
+    }
+    while let Some(index) = rest.find('$') {
+        expanded.push(&rest[..index]);
+        rest = &rest[index + 1..];
+        let (name, remainder) = match rest.strip_prefix('{') {
+            Some(braced) => match braced.split_once('}') {
+                Some(split) => split,
+                None => ("", rest),
+            },
+            None => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                rest.split_at(end)
+            }
+        };
+        match env::var_os(name).filter(|_| !name.is_empty()) {
+            Some(value) => {
+                expanded.push(value);
+                rest = remainder;
+            }
+            None => expanded.push("$"),
+        }
+    }
+    expanded.push(rest);
+    PathBuf::from(expanded)
+}
+
 fn tab_complete(path: &Path) -> Result<Vec<(String, PathBuf)>, Box<dyn Error>> {
-    let parent = if path.exists() {
-        // Do not show completion if already on an existing path
-        return Ok(Vec::new());
+    let path = match path.to_str() {
+        Some(input) => expand_path(input),
+        None => path.to_path_buf(),
+    };
+    let (parent, child) = if path.exists() {
+        // Complete the contents of a directory once it is followed by a slash
+        if path.is_dir() && path.as_os_str().as_encoded_bytes().ends_with(b"/") {
+            (path.as_path(), "")
+        } else {
+            // Do not show completion if already on an existing path
+            return Ok(Vec::new());
+        }
     } else {
-        path.parent()
-            .ok_or_else(|| format!("path has no parent {:?}", path))?
+        let parent = path
+            .parent()
+            .ok_or_else(|| format!("path has no parent {:?}", path))?;
+        let child_os = path.strip_prefix(&parent)?;
+        let child = child_os
+            .to_str()
+            .ok_or_else(|| format!("invalid UTF-8 {:?}", child_os))?;
+        (parent, child)
     };
 
-    let child_os = path.strip_prefix(&parent)?;
-    let child = child_os
-        .to_str()
-        .ok_or_else(|| format!("invalid UTF-8 {:?}", child_os))?;
-
     let pattern = format!("^{}", regex::escape(&child));
     let regex = regex::RegexBuilder::new(&pattern)
         .case_insensitive(true)
@@ -272,6 +369,33 @@ fn tab_complete(path: &Path) -> Result<Vec<(String, PathBuf)>, Box<dyn Error>> {
     Ok(completions)
 }
 
+/// Folders inside of `path`, sorted by name
+fn sub_folders(path: &Path, show_hidden: bool) -> Vec<PathBuf> {
+    let mut folders = Vec::new();
+    match fs::read_dir(path) {
+        Ok(entries) => {
+            for entry in entries.flatten() {
+                let file_name = entry.file_name();
+                let Some(name) = file_name.to_str() else {
+                    continue;
+                };
+                if !show_hidden && name.starts_with('.') {
+                    continue;
+                }
+                let entry_path = entry.path();
+                if entry_path.is_dir() {
+                    folders.push((name.to_string(), entry_path));
+                }
+            }
+        }
+        Err(err) => {
+            log::warn!("failed to read directory {:?}: {}", path, err);
+        }
+    }
+    folders.sort_by(|a, b| LANGUAGE_SORTER.compare(&a.0, &b.0));
+    folders.into_iter().map(|(_name, path)| path).collect()
+}
+
 #[cfg(target_os = "macos")]
 pub fn trash_entries() -> usize {
     0
@@ -279,7 +403,7 @@ pub fn trash_entries() -> usize {
 
 #[cfg(not(target_os = "macos"))]
 pub fn trash_entries() -> usize {
-    match trash::os_limited::list() {
+    match volume_trash::list() {
         Ok(entries) => entries.len(),
         Err(_err) => 0,
     }
@@ -306,22 +430,58 @@ pub fn trash_icon_symbolic(icon_size: u16) -> widget::icon::Handle {
 }
 
 //TODO: translate, add more levels?
-fn format_size(size: u64) -> String {
-    const KB: u64 = 1000;
-    const MB: u64 = 1000 * KB;
-    const GB: u64 = 1000 * MB;
-    const TB: u64 = 1000 * GB;
-
-    if size >= TB {
-        format!("{:.1} TB", size as f64 / TB as f64)
-    } else if size >= GB {
-        format!("{:.1} GB", size as f64 / GB as f64)
-    } else if size >= MB {
-        format!("{:.1} MB", size as f64 / MB as f64)
-    } else if size >= KB {
-        format!("{:.1} KB", size as f64 / KB as f64)
-    } else {
-        format!("{} B", size)
+/// Type and size of a file system
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Filesystem {
+    pub fs_type: String,
+    pub total: u64,
+    pub free: u64,
+}
+
+impl Filesystem {
+    pub fn used_ratio(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.total.saturating_sub(self.free) as f32 / self.total as f32
+    }
+}
+
+pub fn format_config() -> FormatConfig {
+    FORMAT_CONFIG
+        .read()
+        .map_or_else(|err| *err.into_inner(), |config| *config)
+}
+
+pub fn set_format_config(config: FormatConfig) {
+    match FORMAT_CONFIG.write() {
+        Ok(mut format_config) => *format_config = config,
+        Err(err) => *err.into_inner() = config,
+    }
+}
+
+pub fn format_size(size: u64) -> String {
+    format_size_units(size, format_config().size_units)
+}
+
+fn format_size_units(size: u64, units: SizeUnits) -> String {
+    let (base, names) = match units {
+        SizeUnits::Si => (1000, ["KB", "MB", "GB", "TB"]),
+        SizeUnits::Iec => (1024, ["KiB", "MiB", "GiB", "TiB"]),
+        SizeUnits::Bytes => return format!("{} B", size),
+    };
+    let mut unit = base;
+    let mut name = None;
+    for next_name in names {
+        if size < unit {
+            break;
+        }
+        name = Some((unit, next_name));
+        unit = unit.saturating_mul(base);
+    }
+    match name {
+        Some((unit, name)) => format!("{:.1} {}", size as f64 / unit as f64, name),
+        None => format!("{} B", size),
     }
 }
 enum PermissionOwner {
@@ -378,6 +538,24 @@ fn format_permissions(metadata: &Metadata, owner: PermissionOwner) -> String {
     }
 }
 
+/// Permissions like `ls -l` does, followed by the owner and group
+fn format_mode(metadata: &Metadata) -> String {
+    let mode = metadata.permissions().mode();
+    let mut permissions = String::with_capacity(9);
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        permissions.push(if bits & 4 != 0 { 'r' } else { '-' });
+        permissions.push(if bits & 2 != 0 { 'w' } else { '-' });
+        permissions.push(if bits & 1 != 0 { 'x' } else { '-' });
+    }
+    format!(
+        "{} {}:{}",
+        permissions,
+        format_permissions_owner(metadata, PermissionOwner::Owner),
+        format_permissions_owner(metadata, PermissionOwner::Group)
+    )
+}
+
 struct FormatTime(SystemTime);
 
 impl FormatTime {
@@ -395,10 +573,38 @@ impl FormatTime {
     }
 }
 
+/// Time since `date_time` if it was during the last week
+fn format_relative(
+    date_time: chrono::DateTime<chrono::Local>,
+    now: chrono::DateTime<chrono::Local>,
+) -> Option<String> {
+    let since = now.signed_duration_since(date_time);
+    if since < chrono::TimeDelta::zero() || since >= chrono::TimeDelta::days(7) {
+        None
+    } else if since < chrono::TimeDelta::minutes(1) {
+        Some(fl!("just-now"))
+    } else if since < chrono::TimeDelta::hours(1) {
+        Some(fl!("minutes-ago", count = since.num_minutes()))
+    } else if since < chrono::TimeDelta::days(1) {
+        Some(fl!("hours-ago", count = since.num_hours()))
+    } else {
+        Some(fl!("days-ago", count = since.num_days()))
+    }
+}
+
 impl Display for FormatTime {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let date_time = chrono::DateTime::<chrono::Local>::from(self.0);
         let now = chrono::Local::now();
+        match format_config().date_format {
+            DateFormat::Locale => {}
+            DateFormat::Relative => {
+                if let Some(relative) = format_relative(date_time, now) {
+                    return f.write_str(&relative);
+                }
+            }
+            DateFormat::Iso => return date_time.format(ISO_DATE_TIME_FORMAT).fmt(f),
+        }
         if date_time.date_naive() == now.date_naive() {
             write!(
                 f,
@@ -524,6 +730,8 @@ pub fn item_from_entry(
         DirSize::NotDirectory
     };
 
+    let emblems = mime_icon::emblems(&path, &metadata, &mime);
+
     Item {
         name,
         display_name,
@@ -534,6 +742,7 @@ pub fn item_from_entry(
         icon_handle_grid,
         icon_handle_list,
         icon_handle_list_condensed,
+        emblems,
         thumbnail_opt: None,
         button_id: widget::Id::unique(),
         pos_opt: Cell::new(None),
@@ -541,7 +750,17 @@ pub fn item_from_entry(
         selected: false,
         highlighted: false,
         overlaps_drag_rect: false,
+        size_requested: false,
+        size_hovered: false,
         dir_size,
+        text_preview_opt: None,
+        media_metadata_opt: None,
+        checksums_opt: None,
+        color_opt: None,
+        #[cfg(feature = "pdf")]
+        pdf_page_index: 0,
+        #[cfg(feature = "pdf")]
+        pdf_page_opt: None,
     }
 }
 
@@ -596,7 +815,13 @@ pub fn scan_path(tab_path: &PathBuf, sizes: IconSizes) -> Vec<Item> {
                     hidden_files = parse_hidden_file(&path);
                 }
 
-                let metadata = match fs::metadata(&path) {
+                // Broken symlinks are shown with the metadata of the link itself
+                let metadata = match fs::metadata(&path).or_else(|err| {
+                    fs::symlink_metadata(&path)
+                        .ok()
+                        .filter(|metadata| metadata.is_symlink())
+                        .ok_or(err)
+                }) {
                     Ok(ok) => ok,
                     Err(err) => {
                         log::warn!("failed to read metadata for entry at {:?}: {}", path, err);
@@ -646,6 +871,44 @@ pub fn scan_search<F: Fn(&Path, &str, Metadata) -> bool + Sync>(
         }
     };
 
+    // Remote backends that can search on the server do not need every directory listed
+    match mounter::search(tab_path, term) {
+        Some(Ok(paths)) => {
+            for path in paths {
+                let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                    continue;
+                };
+                let hidden = path.strip_prefix(tab_path).map_or(false, |relative| {
+                    relative
+                        .components()
+                        .any(|component| component.as_os_str().as_encoded_bytes().starts_with(b"."))
+                });
+                if (hidden && !show_hidden) || !regex.is_match(file_name) {
+                    continue;
+                }
+                let metadata = match fs::symlink_metadata(&path) {
+                    Ok(ok) => ok,
+                    Err(err) => {
+                        log::warn!("failed to read metadata for entry at {:?}: {}", path, err);
+                        continue;
+                    }
+                };
+                if !callback(&path, file_name, metadata) {
+                    break;
+                }
+            }
+            return;
+        }
+        Some(Err(err)) => {
+            log::warn!(
+                "failed to search {:?} on the server, searching locally: {}",
+                tab_path,
+                err
+            );
+        }
+        None => {}
+    }
+
     ignore::WalkBuilder::new(tab_path)
         .standard_filters(false)
         .hidden(!show_hidden)
@@ -713,7 +976,7 @@ pub fn scan_trash(_sizes: IconSizes) -> Vec<Item> {
 ))]
 pub fn scan_trash(sizes: IconSizes) -> Vec<Item> {
     let mut items: Vec<Item> = Vec::new();
-    match trash::os_limited::list() {
+    match volume_trash::list() {
         Ok(entries) => {
             for entry in entries {
                 let metadata = match trash::os_limited::metadata(&entry) {
@@ -759,6 +1022,7 @@ pub fn scan_trash(sizes: IconSizes) -> Vec<Item> {
                     icon_handle_grid,
                     icon_handle_list,
                     icon_handle_list_condensed,
+                    emblems: Vec::new(),
                     thumbnail_opt: Some(ItemThumbnail::NotImage),
                     button_id: widget::Id::unique(),
                     pos_opt: Cell::new(None),
@@ -766,7 +1030,17 @@ pub fn scan_trash(sizes: IconSizes) -> Vec<Item> {
                     selected: false,
                     highlighted: false,
                     overlaps_drag_rect: false,
+                    size_requested: false,
+                    size_hovered: false,
                     dir_size: DirSize::NotDirectory,
+                    text_preview_opt: None,
+                    media_metadata_opt: None,
+                    checksums_opt: None,
+                    color_opt: None,
+                    #[cfg(feature = "pdf")]
+                    pdf_page_index: 0,
+                    #[cfg(feature = "pdf")]
+                    pdf_page_opt: None,
                 });
             }
         }
@@ -782,73 +1056,23 @@ pub fn scan_trash(sizes: IconSizes) -> Vec<Item> {
     items
 }
 
-fn uri_to_path(uri: String) -> Option<PathBuf> {
-    //TODO support for external drive or cloud?
-    uri.strip_prefix("file://").map(PathBuf::from)
-}
-
 pub fn scan_recents(sizes: IconSizes) -> Vec<Item> {
     let mut recents = Vec::new();
-
-    match recently_used_xbel::parse_file() {
-        Ok(recent_files) => {
-            for bookmark in recent_files.bookmarks {
-                let uri = bookmark.href;
-                let path = match uri_to_path(uri) {
-                    None => continue,
-                    Some(path) => path,
-                };
-                let last_edit = match bookmark.modified.parse::<DateTime<Utc>>() {
-                    Ok(last_edit) => last_edit,
-                    Err(_) => continue,
-                };
-                let last_visit = match bookmark.visited.parse::<DateTime<Utc>>() {
-                    Ok(last_visit) => last_visit,
-                    Err(_) => continue,
-                };
-                let path_exist = path.exists();
-
-                if path_exist {
-                    let file_name = path.file_name();
-
-                    if let Some(name) = file_name {
-                        let name = name.to_string_lossy().to_string();
-
-                        let metadata = match path.metadata() {
-                            Ok(ok) => ok,
-                            Err(err) => {
-                                log::warn!(
-                                    "failed to read metadata for entry at {:?}: {}",
-                                    path,
-                                    err
-                                );
-                                continue;
-                            }
-                        };
-
-                        let item = item_from_entry(path, name, metadata, sizes);
-                        recents.push((
-                            item,
-                            if last_edit.le(&last_visit) {
-                                last_edit
-                            } else {
-                                last_visit
-                            },
-                        ))
-                    }
-                } else {
-                    log::warn!("recent file path not exist: {:?}", path);
-                }
+    for path in recents::recent_files().into_iter().take(50) {
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let name = name.to_string_lossy().to_string();
+        let metadata = match path.metadata() {
+            Ok(ok) => ok,
+            Err(err) => {
+                log::warn!("failed to read metadata for entry at {:?}: {}", path, err);
+                continue;
             }
-        }
-        Err(err) => {
-            log::warn!("Error reading recent files: {:?}", err);
-        }
+        };
+        recents.push(item_from_entry(path, name, metadata, sizes));
     }
-
-    recents.sort_by(|a, b| b.1.cmp(&a.1));
-
-    recents.into_iter().take(50).map(|(item, _)| item).collect()
+    recents
 }
 
 pub fn scan_network(uri: &str, sizes: IconSizes) -> Vec<Item> {
@@ -864,6 +1088,38 @@ pub fn scan_network(uri: &str, sizes: IconSizes) -> Vec<Item> {
     Vec::new()
 }
 
+/// Folder shown on the desktop with its own name and icon, like the home folder
+fn desktop_shortcut(
+    path: PathBuf,
+    name: String,
+    icon_name: &str,
+    sizes: IconSizes,
+) -> Option<Item> {
+    let mut item = match item_from_path(&path, sizes) {
+        Ok(item) => item,
+        Err(err) => {
+            log::warn!(
+                "failed to get item for desktop shortcut {:?}: {}",
+                path,
+                err
+            );
+            return None;
+        }
+    };
+    item.name = name;
+    item.display_name = Item::display_name(&item.name);
+    item.icon_handle_grid = widget::icon::from_name(icon_name)
+        .size(sizes.grid())
+        .handle();
+    item.icon_handle_list = widget::icon::from_name(icon_name)
+        .size(sizes.list())
+        .handle();
+    item.icon_handle_list_condensed = widget::icon::from_name(icon_name)
+        .size(sizes.list_condensed())
+        .handle();
+    Some(item)
+}
+
 //TODO: organize desktop items based on display
 pub fn scan_desktop(
     tab_path: &PathBuf,
@@ -879,6 +1135,24 @@ pub fn scan_desktop(
         items.extend(scan_path(tab_path, sizes));
     }
 
+    if desktop_config.show_computer {
+        items.extend(desktop_shortcut(
+            PathBuf::from("/"),
+            fl!("computer"),
+            "computer",
+            sizes,
+        ));
+    }
+
+    if desktop_config.show_home {
+        items.extend(desktop_shortcut(
+            crate::home_dir(),
+            fl!("home"),
+            "user-home",
+            sizes,
+        ));
+    }
+
     if desktop_config.show_mounted_drives {
         for (_mounter_key, mounter) in MOUNTERS.iter() {
             for mounter_item in mounter.items(sizes).unwrap_or_default() {
@@ -938,6 +1212,7 @@ pub fn scan_desktop(
             icon_handle_grid,
             icon_handle_list,
             icon_handle_list_condensed,
+            emblems: Vec::new(),
             thumbnail_opt: Some(ItemThumbnail::NotImage),
             button_id: widget::Id::unique(),
             pos_opt: Cell::new(None),
@@ -945,7 +1220,17 @@ pub fn scan_desktop(
             selected: false,
             highlighted: false,
             overlaps_drag_rect: false,
+            size_requested: false,
+            size_hovered: false,
             dir_size: DirSize::NotDirectory,
+            text_preview_opt: None,
+            media_metadata_opt: None,
+            checksums_opt: None,
+            color_opt: None,
+            #[cfg(feature = "pdf")]
+            pdf_page_index: 0,
+            #[cfg(feature = "pdf")]
+            pdf_page_opt: None,
         })
     }
 
@@ -969,6 +1254,79 @@ impl EditLocation {
         Some(self.location.with_path(completion.1.clone()))
     }
 
+    /// Location to change to, with `~` and environment variables expanded and URLs like
+    /// `file:///tmp`, `trash:///` or `sftp://host/` resolved
+    pub fn submit(&self) -> Option<Location> {
+        let location = self.resolve()?;
+        let Some(input) = location.path_opt().and_then(|path| path.to_str()) else {
+            return Some(location);
+        };
+        if input.contains("://") {
+            match url::Url::parse(input) {
+                Ok(url) => {
+                    return match url.scheme() {
+                        "file" => url.to_file_path().ok().map(|path| location.with_path(path)),
+                        "network" => Some(Location::Network(input.to_string(), fl!("networks"))),
+                        "recent" => Some(Location::Recents),
+                        "trash" => Some(Location::Trash),
+                        _ => Some(Location::Network(input.to_string(), input.to_string())),
+                    };
+                }
+                Err(err) => {
+                    log::warn!("failed to parse URL {:?}: {}", input, err);
+                }
+            }
+        }
+        Some(location.with_path(expand_path(input)))
+    }
+
+    /// Complete the typed path inline with the selected or only completion, or else with
+    /// the start that all completions share. Directories get a trailing slash so that
+    /// their contents are completed next.
+    pub fn complete_inline(&mut self) -> bool {
+        let Some(completions) = self.completions.as_ref().filter(|x| !x.is_empty()) else {
+            return false;
+        };
+        let completion_opt = match self.selected {
+            Some(selected) => completions.get(selected),
+            None if completions.len() == 1 => completions.first(),
+            None => None,
+        };
+        let path = match completion_opt {
+            Some((_name, path)) => {
+                let mut path = path.clone().into_os_string();
+                if Path::new(&path).is_dir() {
+                    path.push("/");
+                }
+                PathBuf::from(path)
+            }
+            None => {
+                let (first, others) = completions.split_first().unwrap();
+                let mut prefix = first.0.as_str();
+                for (name, _path) in others {
+                    let mut name_chars = name.chars();
+                    let len = prefix
+                        .char_indices()
+                        .find(|(_, c)| {
+                            name_chars
+                                .next()
+                                .map_or(true, |n| !n.to_lowercase().eq(c.to_lowercase()))
+                        })
+                        .map_or(prefix.len(), |(i, _)| i);
+                    prefix = &prefix[..len];
+                }
+                match first.1.parent() {
+                    Some(parent) if !prefix.is_empty() => parent.join(prefix),
+                    _ => return false,
+                }
+            }
+        };
+        self.location = self.location.with_path(path);
+        self.completions = None;
+        self.selected = None;
+        true
+    }
+
     pub fn select(&mut self, forwards: bool) {
         if let Some(completions) = &self.completions {
             if completions.is_empty() {
@@ -1037,6 +1395,16 @@ impl Location {
         }
     }
 
+    /// Directory that a search from this location starts in. Network locations are searched
+    /// through their local path, if they are mounted.
+    pub fn search_path(&self) -> Option<PathBuf> {
+        match self {
+            Self::Path(path) | Self::Search(path, ..) => Some(path.clone()),
+            Self::Network(uri, ..) => mounter::network_path(uri),
+            _ => None,
+        }
+    }
+
     pub fn with_path(&self, path: PathBuf) -> Self {
         match self {
             Self::Desktop(_, display, desktop_config) => {
@@ -1097,8 +1465,10 @@ impl fmt::Debug for TaskWrapper {
 pub enum Command {
     Action(Action),
     AddNetworkDrive,
+    AddToArchive(Vec<PathBuf>, PathBuf),
     AddToSidebar(PathBuf),
     ChangeLocation(String, Location, Option<Vec<PathBuf>>),
+    DesktopLayout(String, DesktopLayout),
     DropFiles(PathBuf, ClipboardPaste),
     EmptyTrash,
     #[cfg(feature = "desktop")]
@@ -1106,11 +1476,16 @@ pub enum Command {
     Iced(TaskWrapper),
     MoveToTrash(Vec<PathBuf>),
     OpenFile(PathBuf),
+    OpenFiles(Vec<PathBuf>),
     OpenInNewTab(PathBuf),
     OpenInNewWindow(PathBuf),
+    OpenLocationInNewTab(Location, Option<Vec<PathBuf>>),
     OpenTrash,
     Preview(PreviewKind),
+    Rename(PathBuf, PathBuf),
     SetOpenWith(Mime, String),
+    /// Show a message over the pane
+    Toast(String),
     WindowDrag,
     WindowToggleMaximize,
 }
@@ -1118,58 +1493,101 @@ pub enum Command {
 #[derive(Clone, Debug)]
 pub enum Message {
     AddNetworkDrive,
+    ArrangeDesktop,
     Click(Option<usize>),
     DoubleClick(Option<usize>),
     ClickRelease(Option<usize>),
     DragEnd(Option<usize>),
-    Config(TabConfig1),
+    Config(TabConfig),
     ContextAction(Action),
     ContextMenu(Option<Point>),
+    /// Open the context menu at the focused item
+    ContextMenuFocus,
     LocationContextMenuPoint(Option<Point>),
     LocationContextMenuIndex(Option<usize>),
     LocationMenuAction(LocationMenuAction),
+    LocationSiblingsMenu(Option<usize>),
     Drag(Option<Rectangle>),
     EditLocation(Option<EditLocation>),
     EditLocationComplete(usize),
+    EditLocationCompleteInline,
     EditLocationEnable,
     EditLocationSubmit,
     OpenInNewTab(PathBuf),
     EmptyTrash,
     #[cfg(feature = "desktop")]
     ExecEntryAction(Option<PathBuf>, usize),
+    Filter(Filter),
     Gallery(bool),
     GalleryPrevious,
     GalleryNext,
     GalleryToggle,
+    GalleryDelete,
+    GalleryFocus(usize),
+    GallerySlideshow(bool),
+    GalleryTransform(ImageTransform),
+    GalleryTransformed(PathBuf, Result<(), String>),
+    HexMode(bool),
+    HexNextPage,
+    HexOffsetInput(String),
+    HexOffsetSubmit,
+    HexPreviousPage,
+    HexSearchInput(String),
+    HexSearchResult(PathBuf, Result<Option<u64>, String>),
+    HexSearchSubmit,
+    GoHistory(usize),
+    Gesture(Gesture),
     GoNext,
     GoPrevious,
+    HistoryMenu(Option<bool>),
     ItemDown,
     ItemLeft,
     ItemRight,
     ItemUp,
     Location(Location),
     LocationUp,
+    /// Touch held on the item view without moving
+    LongPress(Option<Point>),
     Open(Option<PathBuf>),
+    /// Rename the item in place, or stop renaming if None
+    Rename(Option<PathBuf>),
+    RenameInput(String),
+    RenameSubmit,
     RightClick(Option<usize>),
     MiddleClick(usize),
     Scroll(Viewport),
     ScrollToFocus,
+    /// Scroll to the position the location had in the history, or the tab had before
+    ScrollRestore,
+    KineticScroll,
+    TouchScrollEnd,
     SearchContext(Location, SearchContextWrapper),
     SearchReady(bool),
     SelectAll,
     SelectFirst,
+    SelectInvert,
     SelectLast,
+    /// Select the paths, opening the folder of the first one if none of them is shown
+    SelectPaths(Vec<PathBuf>),
+    /// Toggle the selection of the focused item, moving the focus down if true
+    SelectToggle(bool),
     SetOpenWith(Mime, String),
+    SetSelectedPermission(u32, bool),
     SetSort(HeadingOptions, bool),
     TabComplete(PathBuf, Vec<(String, PathBuf)>),
+    Tap(Option<Point>),
     Thumbnail(PathBuf, ItemThumbnail),
+    ToggleDimHidden,
     ToggleShowHidden,
+    /// Two fingers moved over the item view
+    TouchScroll(Vector),
     View(View),
     ToggleSort(HeadingOptions),
     Drop(Option<(Location, ClipboardPaste)>),
     DndHover(Location),
     DndEnter(Location),
     DndLeave(Location),
+    DndDrop(Point),
     WindowDrag,
     WindowToggleMaximize,
     ZoomIn,
@@ -1177,6 +1595,22 @@ pub enum Message {
     HighlightDeactivate(usize),
     HighlightActivate(usize),
     DirectorySize(PathBuf, DirSize),
+    #[cfg(feature = "gstreamer")]
+    MediaPlayPause,
+    #[cfg(feature = "gstreamer")]
+    MediaSeek(f64),
+    #[cfg(feature = "gstreamer")]
+    MediaTick,
+    TextPreview(PathBuf, Arc<HighlightedText>),
+    MediaMetadata(PathBuf, Arc<MediaMetadata>),
+    OpenUrl(String),
+    Checksums(PathBuf, Result<Arc<Checksums>, String>),
+    ChecksumsCalculate(PathBuf),
+    CopyText(String),
+    #[cfg(feature = "pdf")]
+    PdfPage(PathBuf, Arc<PdfPage>),
+    #[cfg(feature = "pdf")]
+    PdfPageChange(PathBuf, u16),
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -1265,9 +1699,22 @@ impl Clone for ItemThumbnail {
 }
 
 impl ItemThumbnail {
-    pub fn new(path: &Path, metadata: fs::Metadata, mime: mime::Mime, thumbnail_size: u32) -> Self {
+    pub fn new(
+        path: &Path,
+        metadata: fs::Metadata,
+        mime: mime::Mime,
+        thumbnail_size: u32,
+        limits: PreviewLimits,
+    ) -> Self {
+        let modified_opt = metadata.modified().ok();
+        if let Some(reason) = thumbnailer::skip_reason(path, modified_opt) {
+            log::debug!("not thumbnailing {:?}: {}", path, reason);
+            return ItemThumbnail::NotImage;
+        }
+
         let size = metadata.len();
-        let check_size = |thumbnailer: &str, max_size| {
+        let mut skip_reason_opt = None;
+        let mut check_size = |thumbnailer: &str, max_size| {
             if size <= max_size {
                 true
             } else {
@@ -1278,13 +1725,18 @@ impl ItemThumbnail {
                     format_size(size),
                     format_size(max_size)
                 );
+                skip_reason_opt = Some(fl!(
+                    "preview-too-large",
+                    size = format_size(size),
+                    max_size = format_size(max_size)
+                ));
                 false
             }
         };
-        //TODO: adjust limits for internal thumbnailers as desired
+        // Vector and text files are far more expensive per byte than raster images
         if mime.type_() == mime::IMAGE
             && mime.subtype() == mime::SVG
-            && check_size("svg", 8 * 1000 * 1000)
+            && check_size("svg", limits.max_size() / 8)
         {
             // Try built-in svg thumbnailer
             match fs::read(path) {
@@ -1296,9 +1748,17 @@ impl ItemThumbnail {
                     log::warn!("failed to read {:?}: {}", path, err);
                 }
             }
-        } else if mime.type_() == mime::IMAGE && check_size("image", 64 * 1000 * 1000) {
+        } else if mime.type_() == mime::IMAGE && check_size("image", limits.max_size()) {
             // Try built-in image thumbnailer
-            match image::ImageReader::open(path).and_then(|img| img.with_guessed_format()) {
+            match image::ImageReader::open(path)
+                .and_then(|img| img.with_guessed_format())
+                .map(|mut reader| {
+                    // Small files can still decode to huge images
+                    let mut image_limits = image::Limits::default();
+                    image_limits.max_alloc = Some(limits.max_size().saturating_mul(8));
+                    reader.limits(image_limits);
+                    reader
+                }) {
                 Ok(reader) => match reader.decode() {
                     Ok(image) => {
                         let thumbnail =
@@ -1320,7 +1780,7 @@ impl ItemThumbnail {
                     log::warn!("failed to read {:?}: {}", path, err);
                 }
             }
-        } else if mime.type_() == mime::TEXT && check_size("text", 8 * 1000 * 1000) {
+        } else if mime.type_() == mime::TEXT && check_size("text", limits.max_size() / 8) {
             /*TODO: fix performance issues, widget::text_editr::Content::with_text forces all text to shape, which blocks rendering
             match fs::read_to_string(&path) {
                 Ok(data) => {
@@ -1356,8 +1816,20 @@ impl ItemThumbnail {
             let Some(mut command) = thumbnailer.command(path, file.path(), thumbnail_size) else {
                 continue;
             };
-            match command.status() {
-                Ok(status) => {
+            match command
+                .spawn()
+                .and_then(|mut child| thumbnailer::wait_timeout(&mut child, limits.timeout()))
+            {
+                Ok(None) => {
+                    // A thumbnailer that hangs on this file will likely hang again
+                    thumbnailer::record_skip(
+                        path,
+                        modified_opt,
+                        fl!("preview-timed-out", seconds = limits.timeout_secs),
+                    );
+                    return ItemThumbnail::NotImage;
+                }
+                Ok(Some(status)) => {
                     if status.success() {
                         match image::ImageReader::open(file.path())
                             .and_then(|img| img.with_guessed_format())
@@ -1391,6 +1863,10 @@ impl ItemThumbnail {
             }
         }
 
+        if let Some(reason) = skip_reason_opt {
+            thumbnailer::record_skip(path, modified_opt, reason);
+        }
+
         ItemThumbnail::NotImage
     }
 }
@@ -1406,6 +1882,7 @@ pub struct Item {
     pub icon_handle_grid: widget::icon::Handle,
     pub icon_handle_list: widget::icon::Handle,
     pub icon_handle_list_condensed: widget::icon::Handle,
+    pub emblems: Vec<Emblem>,
     pub thumbnail_opt: Option<ItemThumbnail>,
     pub button_id: widget::Id,
     pub pos_opt: Cell<Option<(usize, usize)>>,
@@ -1414,9 +1891,65 @@ pub struct Item {
     pub highlighted: bool,
     pub overlaps_drag_rect: bool,
     pub dir_size: DirSize,
+    /// Size of the folder was requested with Space and is shown instead of the item count
+    pub size_requested: bool,
+    /// Folder was hovered, its size is calculated for the tooltip
+    pub size_hovered: bool,
+    pub text_preview_opt: Option<Arc<HighlightedText>>,
+    pub media_metadata_opt: Option<Arc<MediaMetadata>>,
+    pub checksums_opt: Option<ChecksumState>,
+    /// Color of the name from the color rules
+    pub color_opt: Option<HexColor>,
+    /// Page of the PDF preview that should be shown
+    #[cfg(feature = "pdf")]
+    pub pdf_page_index: u16,
+    #[cfg(feature = "pdf")]
+    pub pdf_page_opt: Option<Arc<PdfPage>>,
 }
 
 impl Item {
+    /// Expose the name of the item to screen readers, and how many items are selected with it
+    fn accessible<'a>(
+        &self,
+        button: widget::button::Button<'a, Message>,
+        (selected, total): (usize, usize),
+    ) -> widget::button::Button<'a, Message> {
+        let button = a11y::name(button, self.name.clone());
+        if self.selected {
+            a11y::describe(
+                button,
+                fl!("a11y-selected", selected = selected, total = total),
+            )
+        } else {
+            button
+        }
+    }
+
+    /// Draw the emblems of the item over the bottom left corner of its icon
+    fn emblem_icon<'a>(&self, icon: widget::icon::Icon, size: u16) -> Element<'a, Message> {
+        if self.emblems.is_empty() {
+            return icon.into();
+        }
+        let emblem_size = (size / 3).max(8);
+        let mut emblems = widget::row::with_capacity(self.emblems.len());
+        for emblem in &self.emblems {
+            emblems = emblems.push(
+                widget::icon::from_name(emblem.icon_name())
+                    .size(emblem_size)
+                    .icon(),
+            );
+        }
+        Stack::with_children(vec![
+            icon.into(),
+            widget::container(emblems)
+                .width(Length::Fixed(size as f32))
+                .height(Length::Fixed(size as f32))
+                .align_y(Vertical::Bottom)
+                .into(),
+        ])
+        .into()
+    }
+
     fn display_name(name: &str) -> String {
         // In order to wrap at periods and underscores, add a zero width space after each one
         name.replace(".", ".\u{200B}").replace("_", "_\u{200B}")
@@ -1426,7 +1959,94 @@ impl Item {
         self.location_opt.as_ref()?.path_opt()
     }
 
+    /// Guess if the name does not fit in `lines` lines of `width` pixels and is cut off
+    fn name_truncated(&self, width: f32, lines: usize) -> bool {
+        let chars_per_line = (width / NAME_CHAR_WIDTH).floor().max(1.0) as usize;
+        self.name.chars().count() > chars_per_line * lines
+    }
+
+    /// Tooltip shown when hovering the item. Folders always show their details, other items
+    /// only when their name is `truncated`, which also adds the full name.
+    fn hover_details(&self, truncated: bool) -> Option<String> {
+        let mut lines = Vec::new();
+        if truncated {
+            lines.push(self.name.clone());
+        }
+        match &self.metadata {
+            ItemMetadata::Path { metadata, children } if metadata.is_dir() => {
+                let size = match &self.dir_size {
+                    DirSize::Calculating(_) => fl!("calculating"),
+                    DirSize::Directory(size) => format_size(*size),
+                    DirSize::NotDirectory => String::new(),
+                    DirSize::Error(err) => err.clone(),
+                };
+                lines.push(fl!("items", items = children));
+                lines.push(fl!("item-size", size = size));
+                lines.push(fl!("item-permissions", permissions = format_mode(metadata)));
+                if let Ok(time) = metadata.modified() {
+                    lines.push(fl!(
+                        "item-modified",
+                        modified = format_time(time).to_string()
+                    ));
+                }
+            }
+            ItemMetadata::Path { metadata, .. } if truncated => {
+                lines.push(fl!("item-size", size = format_size(metadata.len())));
+                if let Ok(time) = metadata.modified() {
+                    lines.push(fl!(
+                        "item-modified",
+                        modified = format_time(time).to_string()
+                    ));
+                }
+            }
+            ItemMetadata::SimpleFile { size } if truncated => {
+                lines.push(fl!("item-size", size = format_size(*size)));
+            }
+            _ => {}
+        }
+        if lines.is_empty() {
+            return None;
+        }
+        if let Some(target) = self
+            .path_opt()
+            .filter(|path| path.is_symlink())
+            .and_then(|path| fs::read_link(path).ok())
+        {
+            lines.push(fl!("item-target", target = target.display().to_string()));
+        }
+        Some(lines.join("\n"))
+    }
+
+    fn is_executable(&self) -> bool {
+        match &self.metadata {
+            ItemMetadata::Path { metadata, .. } => {
+                !metadata.is_dir() && metadata.permissions().mode() & 0o111 != 0
+            }
+            _ => false,
+        }
+    }
+
+    /// Class of the name text, in the color of the color rules unless the item is selected
+    fn name_class(&self) -> theme::Text {
+        match self.color_opt {
+            Some(color) if !self.selected => {
+                theme::Text::Color(Color::from_rgb8(color.r, color.g, color.b))
+            }
+            _ => theme::Text::Default,
+        }
+    }
+
+    /// Folders, and archives that dropped items are added to
+    pub fn is_drop_target(&self) -> bool {
+        self.metadata.is_dir()
+            || operation::APPENDABLE_ARCHIVE_TYPES.contains(&self.mime.essence_str())
+    }
+
     pub fn can_gallery(&self) -> bool {
+        #[cfg(feature = "gstreamer")]
+        if media::is_media(&self.mime) {
+            return true;
+        }
         self.mime.type_() == mime::IMAGE || self.mime.type_() == mime::TEXT
     }
 
@@ -1487,6 +2107,99 @@ impl Item {
         row
     }
 
+    #[cfg(feature = "pdf")]
+    fn pdf_preview(&self) -> Option<Element<'_, Message>> {
+        let cosmic_theme::Spacing { space_xxxs, .. } = theme::active().cosmic().spacing;
+        let pdf_page = self.pdf_page_opt.as_ref()?;
+        let path = self.path_opt()?;
+        let index = self.pdf_page_index;
+        let last = pdf_page.page_count.saturating_sub(1);
+        Some(
+            widget::column::with_children(vec![
+                widget::container(widget::image(pdf_page.handle.clone()))
+                    .center_x(Length::Fill)
+                    .max_height(THUMBNAIL_SIZE as f32)
+                    .into(),
+                widget::row::with_children(vec![
+                    widget::horizontal_space().into(),
+                    widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                        .on_press_maybe(
+                            (index > 0)
+                                .then(|| Message::PdfPageChange(path.to_path_buf(), index - 1)),
+                        )
+                        .into(),
+                    widget::text::body(fl!(
+                        "pdf-page",
+                        page = index + 1,
+                        pages = pdf_page.page_count
+                    ))
+                    .into(),
+                    widget::button::icon(widget::icon::from_name("go-next-symbolic"))
+                        .on_press_maybe(
+                            (index < last)
+                                .then(|| Message::PdfPageChange(path.to_path_buf(), index + 1)),
+                        )
+                        .into(),
+                    widget::horizontal_space().into(),
+                ])
+                .align_y(Alignment::Center)
+                .spacing(space_xxxs)
+                .into(),
+            ])
+            .spacing(space_xxxs)
+            .into(),
+        )
+    }
+
+    #[cfg(not(feature = "pdf"))]
+    fn pdf_preview(&self) -> Option<Element<'_, Message>> {
+        None
+    }
+
+    fn checksums_view(&self, path: &Path) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_xxxs, .. } = theme::active().cosmic().spacing;
+
+        let mut column = widget::column::with_capacity(5).spacing(space_xxxs);
+        column = column.push(widget::text::heading(fl!("checksums")));
+        match &self.checksums_opt {
+            None => {
+                column = column.push(
+                    widget::button::standard(fl!("calculate-checksums"))
+                        .on_press(Message::ChecksumsCalculate(path.to_path_buf())),
+                );
+            }
+            Some(ChecksumState::Calculating(_)) => {
+                column = column.push(widget::text::body(fl!("calculating")));
+            }
+            Some(ChecksumState::Done(checksums)) => {
+                for (kind, hash) in checksums.0.iter() {
+                    column = column.push(
+                        widget::row::with_children(vec![
+                            widget::column::with_children(vec![
+                                widget::text::caption_heading(kind.to_string()).into(),
+                                widget::text::caption(hash.as_str())
+                                    .font(font::mono())
+                                    .into(),
+                            ])
+                            .width(Length::Fill)
+                            .into(),
+                            widget::button::icon(widget::icon::from_name("edit-copy-symbolic"))
+                                .tooltip(fl!("copy"))
+                                .on_press(Message::CopyText(hash.clone()))
+                                .into(),
+                        ])
+                        .align_y(Alignment::Center)
+                        .spacing(space_xxxs),
+                    );
+                }
+            }
+            Some(ChecksumState::Error(err)) => {
+                column = column.push(widget::text::body(err.as_str()));
+            }
+        }
+        column.into()
+    }
+
     pub fn preview_view<'a>(
         &'a self,
         mime_app_cache_opt: Option<&'a mime_app::MimeAppCache>,
@@ -1500,11 +2213,20 @@ impl Item {
 
         let mut column = widget::column().spacing(space_m);
 
-        column = column.push(
-            widget::container(self.preview(sizes))
-                .center_x(Length::Fill)
-                .max_height(THUMBNAIL_SIZE as f32),
-        );
+        if let Some(text_preview) = &self.text_preview_opt {
+            column = column.push(highlight::view(
+                text_preview,
+                Length::Fixed(THUMBNAIL_SIZE as f32),
+            ));
+        } else if let Some(pdf_preview) = self.pdf_preview() {
+            column = column.push(pdf_preview);
+        } else {
+            column = column.push(
+                widget::container(self.preview(sizes))
+                    .center_x(Length::Fill)
+                    .max_height(THUMBNAIL_SIZE as f32),
+            );
+        }
 
         let mut details = widget::column().spacing(space_xxxs);
         details = details.push(widget::text::heading(self.name.clone()));
@@ -1512,6 +2234,12 @@ impl Item {
             "type",
             mime = self.mime.to_string()
         )));
+        if let Some(text_preview) = &self.text_preview_opt {
+            details = details.push(widget::text::body(fl!(
+                "language",
+                name = text_preview.syntax_name.as_str()
+            )));
+        }
         let mut settings = Vec::new();
         if let Some(mime_app_cache) = mime_app_cache_opt {
             let mime_apps = mime_app_cache.get(&self.mime);
@@ -1570,7 +2298,15 @@ impl Item {
                     )));
                 }
 
-                #[cfg(not(target_os = "windows"))]
+                if let Some(reason) = self
+                    .path_opt()
+                    .and_then(|path| thumbnailer::skip_reason(path, metadata.modified().ok()))
+                {
+                    details =
+                        details.push(widget::text::body(fl!("preview-skipped", reason = reason)));
+                }
+
+                #[cfg(not(target_os = "windows"))]
                 {
                     settings.push(
                         widget::settings::item::builder(format_permissions_owner(
@@ -1614,11 +2350,35 @@ impl Item {
         }
         column = column.push(details);
 
+        if let Some(media_metadata) = &self.media_metadata_opt {
+            if !media_metadata.entries.is_empty() {
+                column = column.push(media_metadata.view(Message::OpenUrl));
+            }
+        }
+
+        if let Some(path) = self.path_opt().filter(|_| !self.metadata.is_dir()) {
+            column = column.push(self.checksums_view(path));
+        }
+
         if let Some(path) = self.path_opt() {
-            column = column.push(
+            let mut row = widget::row().spacing(space_xxxs);
+            row = row.push(
                 widget::button::standard(fl!("open"))
                     .on_press(Message::Open(Some(path.to_path_buf()))),
             );
+            if self.text_preview_opt.is_some() {
+                row = row.push(
+                    widget::button::standard(fl!("open-full-viewer"))
+                        .on_press(Message::Gallery(true)),
+                );
+            }
+            if !self.metadata.is_dir() {
+                row = row.push(
+                    widget::button::standard(fl!("open-hex-viewer"))
+                        .on_press(Message::HexMode(true)),
+                );
+            }
+            column = column.push(row);
         }
 
         if !settings.is_empty() {
@@ -1705,6 +2465,123 @@ impl HeadingOptions {
     }
 }
 
+// MIME types of the documents filter, besides text
+const DOCUMENT_MIME_TYPES: &[&str] = &[
+    "application/epub+zip",
+    "application/msword",
+    "application/pdf",
+    "application/rtf",
+    "application/vnd.ms-excel",
+    "application/vnd.ms-powerpoint",
+    "application/vnd.oasis.opendocument.*",
+    "application/vnd.openxmlformats-officedocument.*",
+];
+
+// MIME types of the archives filter
+const ARCHIVE_MIME_TYPES: &[&str] = &[
+    "application/gzip",
+    "application/vnd.rar",
+    "application/x-7z-compressed",
+    "application/x-bzip*",
+    "application/x-compressed-tar",
+    "application/x-rar",
+    "application/x-tar",
+    "application/x-xz*",
+    "application/x-zstd*",
+    "application/zip",
+    "application/zstd",
+];
+
+static FILTER_NAMES: Lazy<Vec<String>> = Lazy::new(|| {
+    Filter::all()
+        .iter()
+        .map(|filter| filter.to_string())
+        .collect()
+});
+
+/// Quick filter of a pane, shown in addition to the search term
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Filter {
+    #[default]
+    All,
+    Images,
+    Video,
+    Audio,
+    Documents,
+    Archives,
+    Executables,
+    Today,
+    ThisWeek,
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "{}", fl!("filter-all")),
+            Self::Images => write!(f, "{}", fl!("filter-images")),
+            Self::Video => write!(f, "{}", fl!("filter-video")),
+            Self::Audio => write!(f, "{}", fl!("filter-audio")),
+            Self::Documents => write!(f, "{}", fl!("filter-documents")),
+            Self::Archives => write!(f, "{}", fl!("filter-archives")),
+            Self::Executables => write!(f, "{}", fl!("filter-executables")),
+            Self::Today => write!(f, "{}", fl!("filter-today")),
+            Self::ThisWeek => write!(f, "{}", fl!("filter-this-week")),
+        }
+    }
+}
+
+impl Filter {
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::All,
+            Self::Images,
+            Self::Video,
+            Self::Audio,
+            Self::Documents,
+            Self::Archives,
+            Self::Executables,
+            Self::Today,
+            Self::ThisWeek,
+        ]
+    }
+
+    /// Folders are kept by the type filters so that they can still be opened
+    pub fn matches(&self, item: &Item) -> bool {
+        let mime_matches = |patterns: &[&str]| {
+            let essence = item.mime.essence_str();
+            patterns
+                .iter()
+                .any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => essence.starts_with(prefix),
+                    None => essence == *pattern,
+                })
+        };
+        let modified_since = |days: u64| {
+            let today = chrono::Local::now().date_naive();
+            item.metadata.modified().is_some_and(|modified| {
+                chrono::DateTime::<chrono::Local>::from(modified).date_naive()
+                    >= today - chrono::Days::new(days)
+            })
+        };
+        match self {
+            Self::All => true,
+            _ if item.metadata.is_dir() && !matches!(self, Self::Today | Self::ThisWeek) => true,
+            Self::Images => item.mime.type_() == mime::IMAGE,
+            Self::Video => item.mime.type_() == mime::VIDEO,
+            Self::Audio => item.mime.type_() == mime::AUDIO,
+            Self::Documents => item.mime.type_() == mime::TEXT || mime_matches(DOCUMENT_MIME_TYPES),
+            Self::Archives => mime_matches(ARCHIVE_MIME_TYPES),
+            Self::Executables => item.is_executable(),
+            Self::Today => modified_since(0),
+            Self::ThisWeek => {
+                use chrono::Datelike;
+                let weekday = chrono::Local::now().weekday();
+                modified_since(format_config().first_day_of_week.days_since(weekday).into())
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum Mode {
     App,
@@ -1742,6 +2619,14 @@ impl fmt::Debug for SearchContextWrapper {
     }
 }
 
+/// Row and column of the nth desktop cell, filled top to bottom and then left to right,
+/// with the cells that do not fit on the screen on pages below
+fn desktop_cell(n: usize, rows: usize, cols: usize) -> (usize, usize) {
+    let page_cells = rows * cols;
+    let cell = n % page_cells;
+    ((n / page_cells) * rows + cell % rows, cell / rows)
+}
+
 // TODO when creating items, pass <Arc<SelectedItems>> to each item
 // as a drag data, so that when dnd is initiated, they are all included
 pub struct Tab {
@@ -1751,20 +2636,52 @@ pub struct Tab {
     pub location_context_menu_index: Option<usize>,
     pub context_menu: Option<Point>,
     pub mode: Mode,
+    /// Pane showing the tab, the tabs of both panes are the same otherwise
+    pub pane: PaneType,
     pub scroll_opt: Option<AbsoluteOffset>,
+    /// Scroll positions of the locations in the history, by history index
+    history_scroll: HashMap<usize, AbsoluteOffset>,
+    /// Scroll position restored when the items of a location from the history are loaded
+    scroll_restore: Option<AbsoluteOffset>,
+    /// Pixels per frame that touch scrolling continues with after the fingers are lifted
+    scroll_velocity: f32,
+    kinetic_scroll: bool,
     pub size_opt: Cell<Option<Size>>,
     pub item_view_size_opt: Cell<Option<Size>>,
     pub edit_location: Option<EditLocation>,
     pub edit_location_id: widget::Id,
+    /// Item being renamed in place, with the name typed so far
+    pub rename: Option<(PathBuf, String)>,
+    rename_id: widget::Id,
     pub history_i: usize,
     pub history: Vec<Location>,
-    pub config: TabConfig1,
+    /// Dropdown with the previous (false) or next (true) locations of the history
+    pub history_menu: Option<bool>,
+    /// Dropdown with the folders inside of an ancestor of the location, by ancestor index
+    pub siblings_menu: Option<(usize, Vec<PathBuf>)>,
+    /// File system containing the location, shown in the header
+    pub filesystem: Option<Filesystem>,
+    pub config: TabConfig,
     pub sort_name: HeadingOptions,
     pub sort_direction: bool,
+    /// Quick filter chosen in the header
+    pub filter: Filter,
     pub gallery: bool,
+    /// Pinned tabs are kept in front of the other tabs and cannot be closed
+    pub pinned: bool,
+    /// Locked tabs keep their location, other locations are opened in a new tab
+    pub locked: bool,
     pub(crate) parent_item_opt: Option<Item>,
     pub(crate) items_opt: Option<Vec<Item>>,
     pub dnd_hovered: Option<(Location, Instant)>,
+    /// Modifiers held while dragging, for showing the drop effect
+    pub dnd_modifiers: Modifiers,
+    /// Where the last drag was dropped on the tab
+    dnd_drop_point: Option<Point>,
+    /// Icon positions on the desktop, see [`crate::config::Config::desktop_layouts`]
+    pub desktop_layouts: BTreeMap<String, DesktopLayout>,
+    /// Distance between desktop grid cells, from the last layout
+    desktop_cell_opt: Cell<Option<Size>>,
     scrollable_id: widget::Id,
     select_focus: Option<usize>,
     select_range: Option<(usize, usize)>,
@@ -1772,19 +2689,134 @@ pub struct Tab {
     selected_clicked: bool,
     last_right_click: Option<usize>,
     search_context: Option<SearchContext>,
+    #[cfg(feature = "gstreamer")]
+    media_opt: Option<MediaPlayer>,
+    hex_mode: bool,
+    hex_view_opt: Option<HexView>,
+    hex_offset_input: String,
+    hex_search_input: String,
+    hex_status_opt: Option<String>,
+    slideshow: bool,
+    /// Selected items the gallery steps through, when more than one was selected
+    filmstrip: Vec<PathBuf>,
+    /// Mount points skipped by the directory sizes, read again with every scan
+    size_skipped_mounts: Arc<HashSet<PathBuf>>,
+}
+
+// File system types that are never descended into when calculating directory sizes
+const SIZE_SKIPPED_FS_TYPES: &[&str] = &[
+    // Pseudo file systems
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "devtmpfs",
+    "efivarfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "proc",
+    "pstore",
+    "securityfs",
+    "sysfs",
+    "tracefs",
+    // Network file systems
+    "9p",
+    "afs",
+    "ceph",
+    "cifs",
+    "davfs",
+    "fuse.gvfsd-fuse",
+    "fuse.rclone",
+    "fuse.sshfs",
+    "glusterfs",
+    "ncpfs",
+    "nfs",
+    "nfs4",
+    "smb3",
+    "smbfs",
+    "sshfs",
+];
+
+// Parse the mount points of pseudo and network file systems from /proc/self/mountinfo
+fn parse_skipped_mount_points(mountinfo: &str) -> HashSet<PathBuf> {
+    let mut mount_points = HashSet::new();
+    for line in mountinfo.lines() {
+        // Optional fields are terminated by a single hyphen
+        let Some((fields, fs_fields)) = line.split_once(" - ") else {
+            continue;
+        };
+        let Some(mount_point) = fields.split(' ').nth(4) else {
+            continue;
+        };
+        let Some(fs_type) = fs_fields.split(' ').next() else {
+            continue;
+        };
+        if SIZE_SKIPPED_FS_TYPES.contains(&fs_type) {
+            // Spaces and other special characters are escaped as octal
+            let mount_point = mount_point
+                .replace("\\040", " ")
+                .replace("\\011", "\t")
+                .replace("\\012", "\n")
+                .replace("\\134", "\\");
+            mount_points.insert(PathBuf::from(mount_point));
+        }
+    }
+    mount_points
+}
+
+fn skipped_mount_points() -> HashSet<PathBuf> {
+    match fs::read_to_string("/proc/self/mountinfo") {
+        Ok(mountinfo) => parse_skipped_mount_points(&mountinfo),
+        Err(err) => {
+            log::debug!("failed to read /proc/self/mountinfo: {}", err);
+            HashSet::new()
+        }
+    }
 }
 
-fn calculate_dir_size(path: &Path, controller: Controller) -> Result<u64, String> {
+/// Calculate the size of all files below `path`.
+///
+/// Every inode is counted once, so hard links and directories that are visible more than once
+/// through bind mounts do not inflate the total. Pseudo and network file systems are skipped.
+/// When `one_file_system` is set, other file systems (including btrfs subvolumes) are not
+/// entered.
+fn calculate_dir_size(
+    path: &Path,
+    one_file_system: bool,
+    skipped: &HashSet<PathBuf>,
+    controller: Controller,
+) -> Result<u64, String> {
+    let mut seen = HashSet::new();
     let mut total = 0;
-    for entry_res in WalkDir::new(path) {
+    let mut walker = WalkDir::new(path)
+        .same_file_system(one_file_system)
+        .into_iter();
+    while let Some(entry_res) = walker.next() {
         controller.check()?;
         //TODO: report more errors?
-        if let Ok(entry) = entry_res {
-            if let Ok(metadata) = entry.metadata() {
-                if metadata.is_file() {
-                    total += metadata.len();
-                }
+        let Ok(entry) = entry_res else {
+            continue;
+        };
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            if entry.depth() > 0 && skipped.contains(entry.path()) {
+                walker.skip_current_dir();
+                continue;
             }
+            if !seen.insert((metadata.dev(), metadata.ino())) {
+                // Already visited through another mount
+                walker.skip_current_dir();
+                continue;
+            }
+        } else if metadata.is_file() && seen.insert((metadata.dev(), metadata.ino())) {
+            total += metadata.len();
         }
     }
     Ok(total)
@@ -1809,6 +2841,121 @@ fn folder_name<P: AsRef<Path>>(path: P) -> (String, bool) {
     (name, found_home)
 }
 
+// File system types that do not allow the characters reserved by Windows in names
+const RESERVED_CHARS_FS_TYPES: &[&str] = &["exfat", "msdos", "ntfs", "ntfs3", "vfat"];
+
+/// Explain why an item in `parent` cannot be named `name`, or return None if it can.
+///
+/// `from` is the item being renamed, which may keep its name. `fs_type` is the type of the file
+/// system containing `parent`, used to reject characters it does not allow.
+pub fn name_error(parent: &Path, from: Option<&Path>, name: &str, fs_type: &str) -> Option<String> {
+    if name == "." || name == ".." {
+        return Some(fl!("name-invalid", filename = name));
+    }
+    if name.contains('/') {
+        return Some(fl!("name-no-slashes"));
+    }
+    if RESERVED_CHARS_FS_TYPES.contains(&fs_type) {
+        if let Some(c) = name
+            .chars()
+            .find(|c| c.is_control() || "\"*:<>?\\|".contains(*c))
+        {
+            return Some(fl!(
+                "name-reserved-character",
+                character = c.escape_debug().to_string(),
+                filesystem = fs_type
+            ));
+        }
+        if name.ends_with(['.', ' ']) {
+            return Some(fl!("name-reserved-ending", filesystem = fs_type));
+        }
+    }
+    let path = parent.join(name);
+    if from != Some(path.as_path()) && path.exists() {
+        return Some(if path.is_dir() {
+            fl!("folder-already-exists")
+        } else {
+            fl!("file-already-exists")
+        });
+    }
+    None
+}
+
+/// Number of characters before the extension, which are selected when renaming a file
+fn stem_len(name: &str) -> usize {
+    let stem = match name.rfind('.') {
+        Some(0) | None => name,
+        Some(i) => &name[..i],
+    };
+    // Compound extensions like .tar.gz are kept together
+    let stem = match stem.strip_suffix(".tar") {
+        Some(some) if !some.is_empty() => some,
+        _ => stem,
+    };
+    stem.chars().count()
+}
+
+/// Hide items with names matching one of the patterns, like dot files and names in .hidden files
+pub fn hide_matching(items: &mut [Item], patterns: &[glob::Pattern]) {
+    for item in items.iter_mut() {
+        if patterns.iter().any(|pattern| pattern.matches(&item.name)) {
+            item.hidden = true;
+        }
+    }
+}
+
+enum ColorMatcher {
+    Folder,
+    Executable,
+    Symlink,
+    Hidden,
+    Mime(glob::Pattern),
+    Name(glob::Pattern),
+}
+
+impl ColorMatcher {
+    fn new(pattern: &str) -> Option<Self> {
+        Some(match pattern.trim() {
+            "" => return None,
+            "folder" => Self::Folder,
+            "executable" => Self::Executable,
+            "symlink" => Self::Symlink,
+            "hidden" => Self::Hidden,
+            pattern if pattern.contains('/') => Self::Mime(glob::Pattern::new(pattern).ok()?),
+            pattern => Self::Name(glob::Pattern::new(pattern).ok()?),
+        })
+    }
+
+    fn matches(&self, item: &Item) -> bool {
+        let options = glob::MatchOptions {
+            case_sensitive: false,
+            ..glob::MatchOptions::new()
+        };
+        match self {
+            Self::Folder => item.metadata.is_dir(),
+            Self::Executable => item.is_executable(),
+            Self::Symlink => item.path_opt().is_some_and(|path| path.is_symlink()),
+            Self::Hidden => item.hidden,
+            Self::Mime(pattern) => pattern.matches(item.mime.essence_str()),
+            Self::Name(pattern) => pattern.matches_with(&item.name, options),
+        }
+    }
+}
+
+/// Set the colors of the names from the first color rule that the items match
+pub fn color_matching(items: &mut [Item], rules: &[ColorRule]) {
+    let matchers: Vec<(ColorMatcher, HexColor)> = rules
+        .iter()
+        .filter_map(|rule| Some((ColorMatcher::new(&rule.pattern)?, rule.color?)))
+        .collect();
+    for item in items.iter_mut() {
+        item.color_opt = matchers
+            .iter()
+            .find(|(matcher, _color)| matcher.matches(item))
+            .map(|(_matcher, color)| *color);
+    }
+}
+
 // parse .hidden file and return files path
 fn parse_hidden_file(path: &PathBuf) -> Vec<String> {
     let file = match File::open(path) {
@@ -1827,7 +2974,7 @@ fn parse_hidden_file(path: &PathBuf) -> Vec<String> {
 }
 
 impl Tab {
-    pub fn new(location: Location, config: TabConfig1) -> Self {
+    pub fn new(location: Location, config: TabConfig) -> Self {
         let history = vec![location.clone()];
         Self {
             location,
@@ -1835,17 +2982,30 @@ impl Tab {
             location_context_menu_point: None,
             location_context_menu_index: None,
             mode: Mode::App,
+            pane: PaneType::LeftPane,
             scroll_opt: None,
+            history_scroll: HashMap::new(),
+            scroll_restore: None,
+            scroll_velocity: 0.0,
+            kinetic_scroll: false,
             size_opt: Cell::new(None),
             item_view_size_opt: Cell::new(None),
             edit_location: None,
             edit_location_id: widget::Id::unique(),
+            rename: None,
+            rename_id: widget::Id::unique(),
             history_i: 0,
             history,
+            history_menu: None,
+            siblings_menu: None,
+            filesystem: None,
             config,
             sort_name: HeadingOptions::Name,
             sort_direction: true,
+            filter: Filter::All,
             gallery: false,
+            pinned: false,
+            locked: false,
             parent_item_opt: None,
             items_opt: None,
             scrollable_id: widget::Id::unique(),
@@ -1853,9 +3013,23 @@ impl Tab {
             select_range: None,
             clicked: None,
             dnd_hovered: None,
+            dnd_modifiers: Modifiers::empty(),
+            dnd_drop_point: None,
+            desktop_layouts: BTreeMap::new(),
+            desktop_cell_opt: Cell::new(None),
             selected_clicked: false,
             last_right_click: None,
             search_context: None,
+            #[cfg(feature = "gstreamer")]
+            media_opt: None,
+            hex_mode: false,
+            hex_view_opt: None,
+            hex_offset_input: String::new(),
+            hex_search_input: String::new(),
+            hex_status_opt: None,
+            slideshow: false,
+            filmstrip: Vec::new(),
+            size_skipped_mounts: Arc::default(),
         }
     }
 
@@ -1903,6 +3077,116 @@ impl Tab {
             }
         }
         self.items_opt = Some(items);
+        self.size_skipped_mounts = Arc::new(skipped_mount_points());
+    }
+
+    /// Combined properties of the selected items, used when more than one item is selected
+    pub fn selection_preview_view(&self) -> Option<Element<'_, Message>> {
+        let cosmic_theme::Spacing {
+            space_xxxs,
+            space_xs,
+            space_m,
+            ..
+        } = theme::active().cosmic().spacing;
+
+        let selected: Vec<&Item> = self
+            .items_opt()?
+            .iter()
+            .filter(|item| item.selected)
+            .collect();
+        if selected.len() < 2 {
+            return None;
+        }
+
+        let mut files = 0;
+        let mut folders = 0;
+        let mut total = 0;
+        let mut calculating = false;
+        let mut mimes = HashMap::<String, usize>::new();
+        let mut modes = Vec::new();
+        for item in selected.iter() {
+            *mimes
+                .entry(item.mime.essence_str().to_string())
+                .or_default() += 1;
+            if item.metadata.is_dir() {
+                folders += 1;
+            } else {
+                files += 1;
+            }
+            match &item.metadata {
+                ItemMetadata::Path { metadata, .. } => {
+                    modes.push(metadata.mode());
+                    if metadata.is_dir() {
+                        match &item.dir_size {
+                            DirSize::Calculating(_) => calculating = true,
+                            DirSize::Directory(size) => total += size,
+                            DirSize::NotDirectory | DirSize::Error(_) => {}
+                        }
+                    } else {
+                        total += metadata.len();
+                    }
+                }
+                ItemMetadata::SimpleFile { size } => total += size,
+                _ => {}
+            }
+        }
+
+        let mut details = widget::column().spacing(space_xxxs);
+        details = details.push(widget::text::heading(fl!(
+            "selected-items",
+            items = selected.len()
+        )));
+        details = details.push(widget::text::body(fl!(
+            "selected-files-folders",
+            files = files,
+            folders = folders
+        )));
+        let mut size = format_size(total);
+        if calculating {
+            size = format!("{} ({})", size, fl!("calculating"));
+        }
+        details = details.push(widget::text::body(fl!("item-size", size = size)));
+
+        // Most common types first
+        let mut mimes: Vec<(String, usize)> = mimes.into_iter().collect();
+        mimes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let mut types = widget::column().spacing(space_xxxs);
+        for (mime, count) in mimes {
+            types = types.push(widget::text::body(fl!(
+                "type-count",
+                mime = mime,
+                items = count
+            )));
+        }
+
+        let mut column = widget::column().spacing(space_m);
+        column = column.push(details);
+        column = column.push(types);
+
+        // Permissions can only be changed if all items are local files
+        if modes.len() == selected.len() {
+            let mut section = widget::settings::section().title(fl!("permissions"));
+            for (owner, shift) in [(fl!("owner"), 6), (fl!("group"), 3), (fl!("other"), 0)] {
+                let mut row = widget::row().spacing(space_xs);
+                for (label, bit) in [
+                    (fl!("permission-read"), 0o4),
+                    (fl!("permission-write"), 0o2),
+                    (fl!("permission-execute"), 0o1),
+                ] {
+                    let bit = bit << shift;
+                    // Only permissions shared by all items are checked
+                    let checked = modes.iter().all(|mode| mode & bit != 0);
+                    row = row.push(
+                        widget::checkbox(label, checked)
+                            .on_toggle(move |set| Message::SetSelectedPermission(bit, set)),
+                    );
+                }
+                section = section.add(widget::settings::item::builder(owner).control(row));
+            }
+            column = column.push(section);
+        }
+
+        Some(column.into())
     }
 
     pub fn selected_locations(&self) -> Vec<Location> {
@@ -1922,7 +3206,7 @@ impl Tab {
     pub fn select_all(&mut self) {
         if let Some(ref mut items) = self.items_opt {
             for item in items.iter_mut() {
-                if !self.config.show_hidden && item.hidden {
+                if (!self.config.show_hidden && item.hidden) || !self.filter.matches(item) {
                     item.selected = false;
                     continue;
                 }
@@ -1931,6 +3215,16 @@ impl Tab {
         }
     }
 
+    pub fn select_invert(&mut self) {
+        if let Some(ref mut items) = self.items_opt {
+            for item in items.iter_mut() {
+                item.selected = !item.selected
+                    && (self.config.show_hidden || !item.hidden)
+                    && self.filter.matches(item);
+            }
+        }
+    }
+
     pub fn select_none(&mut self) -> bool {
         self.select_focus = None;
         let mut had_selection = false;
@@ -2022,6 +3316,61 @@ impl Tab {
         found
     }
 
+    /// Scroll the item view by the distance fingers moved
+    fn scroll_by(&mut self, delta_y: f32, commands: &mut Vec<Command>) {
+        let offset = self.scroll_opt.unwrap_or(AbsoluteOffset { x: 0.0, y: 0.0 });
+        let offset = AbsoluteOffset {
+            x: offset.x,
+            y: (offset.y - delta_y).max(0.0),
+        };
+        if offset.y == 0.0 {
+            // Kinetic scrolling stops at the top
+            self.kinetic_scroll = false;
+        }
+        self.scroll_opt = Some(offset);
+        commands.push(Command::Iced(
+            scrollable::scroll_to(self.scrollable_id.clone(), offset).into(),
+        ));
+    }
+
+    /// Number of the selected and of the shown items
+    fn selection_count(&self) -> (usize, usize) {
+        let Some(items) = self.items_opt.as_ref() else {
+            return (0, 0);
+        };
+        let shown: Vec<&Item> = items
+            .iter()
+            .filter(|item| (!item.hidden || self.config.show_hidden) && self.filter.matches(item))
+            .collect();
+        (
+            shown.iter().filter(|item| item.selected).count(),
+            shown.len(),
+        )
+    }
+
+    /// Focused or first selected item and the point below it where the menu key opens the
+    /// context menu
+    fn context_menu_point(&self) -> (Option<usize>, Point) {
+        let items = self.items_opt.as_deref().unwrap_or_default();
+        let focus_opt = self
+            .select_focus
+            .filter(|i| items.get(*i).is_some_and(|item| item.selected))
+            .or_else(|| items.iter().position(|item| item.selected));
+        let point = match focus_opt.and_then(|i| items[i].rect_opt.get()) {
+            Some(rect) => Point::new(rect.x + rect.width / 2.0, rect.y + rect.height),
+            None => Point::new(0.0, self.scroll_opt.map_or(0.0, |offset| offset.y)),
+        };
+        (focus_opt, point)
+    }
+
+    /// Index of the shown item at a point of the item view
+    fn item_at(&self, point: Point) -> Option<usize> {
+        self.items_opt
+            .as_ref()?
+            .iter()
+            .position(|item| item.rect_opt.get().is_some_and(|rect| rect.contains(point)))
+    }
+
     pub fn select_rect(&mut self, rect: Rectangle, mod_ctrl: bool, mod_shift: bool) {
         if let Some(ref mut items) = self.items_opt {
             for item in items.iter_mut() {
@@ -2151,18 +3500,27 @@ impl Tab {
     pub fn change_location(&mut self, location: &Location, history_i_opt: Option<usize>) {
         self.location = location.clone();
         self.context_menu = None;
+        self.history_menu = None;
+        self.siblings_menu = None;
         self.edit_location = None;
         self.items_opt = None;
-        //TODO: remember scroll by location?
-        self.scroll_opt = None;
+        self.update_filesystem();
+        if let Some(offset) = self.scroll_opt.take() {
+            self.history_scroll.insert(self.history_i, offset);
+        }
+        self.kinetic_scroll = false;
         self.select_focus = None;
         self.search_context = None;
         if let Some(history_i) = history_i_opt {
             // Navigating in history
             self.history_i = history_i;
+            self.scroll_restore = self.history_scroll.get(&history_i).copied();
         } else {
             // Truncate history to remove next entries
             self.history.truncate(self.history_i + 1);
+            let history_i = self.history_i;
+            self.history_scroll.retain(|i, _| *i <= history_i);
+            self.scroll_restore = None;
 
             // Push to the front of history
             self.history_i = self.history.len();
@@ -2170,6 +3528,46 @@ impl Tab {
         }
     }
 
+    /// Read the type and free space of the file system containing the location again
+    pub fn update_filesystem(&mut self) {
+        self.filesystem = self.location.path_opt().and_then(|path| {
+            let (total, free) = mounter::disk_usage(path)?;
+            Some(Filesystem {
+                fs_type: mounter::filesystem_type(path).unwrap_or_default(),
+                total,
+                free,
+            })
+        });
+    }
+
+    /// Open the clicked item, returns the location to change to if it is a folder
+    fn open_clicked(
+        &self,
+        click_i_opt: Option<usize>,
+        commands: &mut Vec<Command>,
+    ) -> Option<Location> {
+        let Some(clicked_item) = self
+            .items_opt
+            .as_ref()
+            .and_then(|items| click_i_opt.and_then(|click_i| items.get(click_i)))
+        else {
+            log::warn!("no item for click index {:?}", click_i_opt);
+            return None;
+        };
+        let Some(location) = &clicked_item.location_opt else {
+            log::warn!("no location for item {:?}", clicked_item);
+            return None;
+        };
+        if clicked_item.metadata.is_dir() {
+            return Some(location.clone());
+        }
+        match location.path_opt() {
+            Some(path) => commands.push(Command::OpenFile(path.to_path_buf())),
+            None => log::warn!("no path for item {:?}", clicked_item),
+        }
+        None
+    }
+
     pub fn update(&mut self, message: Message, modifiers: Modifiers) -> Vec<Command> {
         let mut commands = Vec::new();
         let mut cd = None;
@@ -2180,8 +3578,22 @@ impl Tab {
             Message::AddNetworkDrive => {
                 commands.push(Command::AddNetworkDrive);
             }
+            Message::ArrangeDesktop => {
+                if let Some(key) = self.desktop_layout_key() {
+                    if self.desktop_layouts.remove(&key).is_some() {
+                        commands.push(Command::DesktopLayout(key, DesktopLayout::new()));
+                    }
+                }
+            }
             Message::ClickRelease(click_i_opt) => {
-                if click_i_opt == self.clicked.take() {
+                // A single click opens the item when it is released, unless it was dragged
+                if self.config.single_click_open && click_i_opt.is_some() && !mod_ctrl && !mod_shift
+                {
+                    self.clicked = None;
+                    cd = self.open_clicked(click_i_opt, &mut commands);
+                } else if click_i_opt == self.clicked.take() {
+                    return commands;
+                } else if click_i_opt.is_none() && !self.config.click_empty_clears_selection {
                     return commands;
                 }
                 self.context_menu = None;
@@ -2207,25 +3619,39 @@ impl Tab {
                     }
                 }
             }
+            Message::Tap(point_opt) => {
+                // Tapping an item selects and opens it, tapping empty space clears the selection
+                let click_i_opt = point_opt.and_then(|point| self.item_at(point));
+                commands.extend(self.update(Message::Click(click_i_opt), modifiers));
+                if click_i_opt.is_some() {
+                    cd = self.open_clicked(click_i_opt, &mut commands);
+                }
+            }
+            Message::LongPress(point_opt) => {
+                let click_i_opt = point_opt.and_then(|point| self.item_at(point));
+                commands.extend(self.update(Message::RightClick(click_i_opt), modifiers));
+                commands.extend(self.update(Message::ContextMenu(point_opt), modifiers));
+            }
+            Message::TouchScroll(delta) => {
+                self.kinetic_scroll = false;
+                self.scroll_velocity = (self.scroll_velocity + delta.y) / 2.0;
+                self.scroll_by(delta.y, &mut commands);
+            }
+            Message::TouchScrollEnd => {
+                self.kinetic_scroll = self.scroll_velocity.abs() > KINETIC_SCROLL_MIN_VELOCITY;
+            }
+            Message::KineticScroll => {
+                if self.kinetic_scroll {
+                    self.scroll_by(self.scroll_velocity, &mut commands);
+                    self.scroll_velocity *= KINETIC_SCROLL_FRICTION;
+                    self.kinetic_scroll = self.kinetic_scroll
+                        && self.scroll_velocity.abs() > KINETIC_SCROLL_MIN_VELOCITY;
+                }
+            }
             Message::DoubleClick(click_i_opt) => {
-                if let Some(clicked_item) = self
-                    .items_opt
-                    .as_ref()
-                    .and_then(|items| click_i_opt.and_then(|click_i| items.get(click_i)))
-                {
-                    if let Some(location) = &clicked_item.location_opt {
-                        if clicked_item.metadata.is_dir() {
-                            cd = Some(location.clone());
-                        } else if let Some(path) = location.path_opt() {
-                            commands.push(Command::OpenFile(path.to_path_buf()));
-                        } else {
-                            log::warn!("no path for item {:?}", clicked_item);
-                        }
-                    } else {
-                        log::warn!("no location for item {:?}", clicked_item);
-                    }
-                } else {
-                    log::warn!("no item for click index {:?}", click_i_opt);
+                // With single click open, the first click already opened the item
+                if self.config.double_click_open && !self.config.single_click_open {
+                    cd = self.open_clicked(click_i_opt, &mut commands);
                 }
             }
             Message::Click(click_i_opt) => {
@@ -2233,8 +3659,19 @@ impl Tab {
                 self.context_menu = None;
                 self.edit_location = None;
                 self.location_context_menu_index = None;
+                // Clicking anything but the item being renamed stops renaming it
+                if let Some((from, _)) = &self.rename {
+                    let clicked_path =
+                        click_i_opt.and_then(|i| self.items_opt.as_ref()?.get(i)?.path_opt());
+                    if clicked_path != Some(from) {
+                        self.rename = None;
+                    }
+                }
                 if click_i_opt.is_none() {
                     self.clicked = click_i_opt;
+                    if !self.config.click_empty_clears_selection {
+                        return commands;
+                    }
                 }
 
                 if mod_shift {
@@ -2353,12 +3790,26 @@ impl Tab {
                 }
             }
             Message::Config(config) => {
+                if config.preview_limits != self.config.preview_limits {
+                    // Files skipped for size or time may fit the new limits
+                    thumbnailer::clear_skips();
+                    if let Some(items) = &mut self.items_opt {
+                        for item in items.iter_mut() {
+                            if matches!(item.thumbnail_opt, Some(ItemThumbnail::NotImage)) {
+                                item.thumbnail_opt = None;
+                            }
+                            item.media_metadata_opt = None;
+                        }
+                    }
+                }
                 // View is preserved for existing tabs
                 let view = self.config.view;
                 let show_hidden = self.config.show_hidden;
+                let dim_hidden = self.config.dim_hidden;
                 self.config = config;
                 self.config.view = view;
                 self.config.show_hidden = show_hidden;
+                self.config.dim_hidden = dim_hidden;
             }
             Message::ContextAction(action) => {
                 // Close context menu
@@ -2366,6 +3817,12 @@ impl Tab {
 
                 commands.push(Command::Action(action));
             }
+            Message::ContextMenuFocus => {
+                // The selection is kept like when right clicking a selected item
+                let (focus_opt, point) = self.context_menu_point();
+                self.last_right_click = focus_opt;
+                commands.extend(self.update(Message::ContextMenu(Some(point)), modifiers));
+            }
             Message::ContextMenu(point_opt) => {
                 self.edit_location = None;
                 if point_opt.is_none() || !mod_shift {
@@ -2410,8 +3867,8 @@ impl Tab {
                             //TODO: blocking code, run in command
                             match item_from_path(&path, IconSizes::default()) {
                                 Ok(item) => {
-                                    commands.push(Command::Preview(PreviewKind::Custom1(
-                                        PreviewItem1(item),
+                                    commands.push(Command::Preview(PreviewKind::Custom(
+                                        PreviewItem(item),
                                     )));
                                 }
                                 Err(err) => {
@@ -2456,7 +3913,20 @@ impl Tab {
             Message::EditLocationComplete(selected) => {
                 if let Some(mut edit_location) = self.edit_location.take() {
                     edit_location.selected = Some(selected);
-                    cd = edit_location.resolve();
+                    cd = edit_location.submit();
+                }
+            }
+            Message::EditLocationCompleteInline => {
+                if let Some(edit_location) = &mut self.edit_location {
+                    if edit_location.complete_inline() {
+                        commands.push(Command::Iced(
+                            widget::text_input::focus(self.edit_location_id.clone()).into(),
+                        ));
+                        commands.push(Command::Iced(
+                            widget::text_input::move_cursor_to_end(self.edit_location_id.clone())
+                                .into(),
+                        ));
+                    }
                 }
             }
             Message::EditLocationEnable => {
@@ -2467,7 +3937,7 @@ impl Tab {
             }
             Message::EditLocationSubmit => {
                 if let Some(edit_location) = self.edit_location.take() {
-                    cd = edit_location.resolve();
+                    cd = edit_location.submit();
                 }
             }
             Message::OpenInNewTab(path) => {
@@ -2495,35 +3965,188 @@ impl Tab {
                     None => log::warn!("Invalid desktop entry path passed to ExecEntryAction"),
                 }
             }
+            Message::Filter(filter) => {
+                self.filter = filter;
+            }
             Message::Gallery(gallery) => {
-                self.gallery = gallery;
+                self.set_gallery(gallery);
             }
-            Message::GalleryPrevious | Message::GalleryNext => {
-                let mut pos_opt = None;
-                if let Some(mut indices) = self.column_sort() {
-                    if matches!(message, Message::GalleryPrevious) {
-                        indices.reverse();
+            Message::GalleryDelete => {
+                if let Some(path) = self.gallery_focus_path() {
+                    for command in self.update(Message::GalleryNext, modifiers) {
+                        commands.push(command);
                     }
-                    let mut found = false;
-                    for (index, item) in indices {
-                        if self.select_focus.is_none() {
-                            found = true;
-                        }
-                        if self.select_focus == Some(index) {
-                            found = true;
-                            continue;
-                        }
-                        if found && item.can_gallery() {
-                            pos_opt = item.pos_opt.get();
-                            if pos_opt.is_some() {
+                    self.filmstrip.retain(|x| x != &path);
+                    commands.push(Command::MoveToTrash(vec![path]));
+                }
+            }
+            Message::GalleryFocus(index) => {
+                self.select_focus = Some(index);
+            }
+            Message::GallerySlideshow(slideshow) => {
+                self.slideshow = slideshow;
+            }
+            Message::GalleryTransform(image_transform) => {
+                if self.config.read_only {
+                    log::info!("Cannot change images in a read-only pane.");
+                    return commands;
+                }
+                let item_opt = self
+                    .select_focus
+                    .and_then(|index| self.items_opt.as_ref()?.get(index));
+                if let Some(item) = item_opt.filter(|item| transform::can_transform(&item.mime)) {
+                    if let Some(path) = item.path_opt().map(|path| path.to_path_buf()) {
+                        let mime = item.mime.clone();
+                        commands.push(Command::Iced(
+                            cosmic::Task::perform(
+                                async move {
+                                    let image_path = path.clone();
+                                    let result = tokio::task::spawn_blocking(move || {
+                                        transform::apply(&image_path, &mime, image_transform)
+                                    })
+                                    .await
+                                    .unwrap_or_else(|err| Err(err.to_string()));
+                                    Message::GalleryTransformed(path, result)
+                                },
+                                |x| x,
+                            )
+                            .into(),
+                        ));
+                    }
+                }
+            }
+            Message::GalleryTransformed(path, result) => match result {
+                Ok(()) => {
+                    let location = Location::Path(path);
+                    if let Some(ref mut items) = self.items_opt {
+                        for item in items.iter_mut() {
+                            if item.location_opt.as_ref() == Some(&location) {
+                                // Thumbnail again, this also drops the cached full size image
+                                item.thumbnail_opt = None;
                                 break;
                             }
                         }
                     }
                 }
-                if let Some((row, col)) = pos_opt {
-                    // Should mod_shift be available?
-                    self.select_position(row, col, mod_shift);
+                Err(err) => {
+                    log::warn!("failed to transform {:?}: {}", path, err);
+                    commands.push(Command::Toast(err));
+                }
+            },
+            Message::HexMode(hex_mode) => {
+                if hex_mode {
+                    self.set_gallery(true);
+                }
+                self.hex_mode = hex_mode;
+            }
+            Message::HexNextPage => {
+                if let Some(hex_view) = &mut self.hex_view_opt {
+                    self.hex_status_opt = hex_view.next_page().err();
+                }
+            }
+            Message::HexOffsetInput(input) => {
+                self.hex_offset_input = input;
+            }
+            Message::HexOffsetSubmit => {
+                if let Some(hex_view) = &mut self.hex_view_opt {
+                    self.hex_status_opt = match hexview::parse_offset(&self.hex_offset_input) {
+                        Some(offset) => hex_view.load_at(offset).err(),
+                        None => Some(fl!("hex-invalid-offset")),
+                    };
+                }
+            }
+            Message::HexPreviousPage => {
+                if let Some(hex_view) = &mut self.hex_view_opt {
+                    self.hex_status_opt = hex_view.previous_page().err();
+                }
+            }
+            Message::HexSearchInput(input) => {
+                self.hex_search_input = input;
+            }
+            Message::HexSearchResult(path, result) => {
+                if let Some(hex_view) = &mut self.hex_view_opt {
+                    if hex_view.path() == path {
+                        self.hex_status_opt = match result {
+                            Ok(Some(offset)) => {
+                                let len = hexview::parse_pattern(&self.hex_search_input)
+                                    .map_or(1, |pattern| pattern.len() as u64);
+                                hex_view.show_match(offset, len).err()
+                            }
+                            Ok(None) => Some(fl!("hex-not-found")),
+                            Err(err) => Some(err),
+                        };
+                    }
+                }
+            }
+            Message::HexSearchSubmit => {
+                if let Some(hex_view) = &self.hex_view_opt {
+                    if let Some(pattern) = hexview::parse_pattern(&self.hex_search_input) {
+                        let path = hex_view.path().to_path_buf();
+                        let start = hex_view.search_start();
+                        self.hex_status_opt = Some(fl!("hex-searching"));
+                        commands.push(Command::Iced(
+                            cosmic::Task::perform(
+                                async move {
+                                    let result = {
+                                        let path = path.clone();
+                                        tokio::task::spawn_blocking(move || {
+                                            hexview::find(&path, &pattern, start)
+                                        })
+                                        .await
+                                        .unwrap()
+                                    };
+                                    Message::HexSearchResult(path, result)
+                                },
+                                |x| x,
+                            )
+                            .into(),
+                        ));
+                    }
+                }
+            }
+            Message::GalleryPrevious | Message::GalleryNext if !self.filmstrip.is_empty() => {
+                let len = self.filmstrip.len();
+                let next = match self
+                    .gallery_focus_path()
+                    .and_then(|path| self.filmstrip.iter().position(|x| x == &path))
+                {
+                    Some(i) if matches!(message, Message::GalleryPrevious) => (i + len - 1) % len,
+                    Some(i) => (i + 1) % len,
+                    None => 0,
+                };
+                let location = Location::Path(self.filmstrip[next].clone());
+                if let Some(items) = &self.items_opt {
+                    self.select_focus = items
+                        .iter()
+                        .position(|item| item.location_opt.as_ref() == Some(&location));
+                }
+            }
+            Message::GalleryPrevious | Message::GalleryNext => {
+                let mut pos_opt = None;
+                if let Some(mut indices) = self.column_sort() {
+                    if matches!(message, Message::GalleryPrevious) {
+                        indices.reverse();
+                    }
+                    let mut found = false;
+                    for (index, item) in indices {
+                        if self.select_focus.is_none() {
+                            found = true;
+                        }
+                        if self.select_focus == Some(index) {
+                            found = true;
+                            continue;
+                        }
+                        if found && item.can_gallery() {
+                            pos_opt = item.pos_opt.get();
+                            if pos_opt.is_some() {
+                                break;
+                            }
+                        }
+                    }
+                }
+                if let Some((row, col)) = pos_opt {
+                    // Should mod_shift be available?
+                    self.select_position(row, col, mod_shift);
                 }
                 if let Some(offset) = self.select_focus_scroll() {
                     commands.push(Command::Iced(
@@ -2538,12 +4161,35 @@ impl Tab {
                 if let Some(indices) = self.column_sort() {
                     for (_, item) in indices.iter() {
                         if item.selected && item.can_gallery() {
-                            self.gallery = !self.gallery;
+                            self.set_gallery(!self.gallery);
                             break;
                         }
                     }
                 }
             }
+            Message::GoHistory(history_i) => {
+                self.history_menu = None;
+                if let Some(location) = self.history.get(history_i) {
+                    cd = Some(location.clone());
+                    history_i_opt = Some(history_i);
+                }
+            }
+            Message::Gesture(gesture) => {
+                let message = match gesture {
+                    Gesture::Up => Message::LocationUp,
+                    Gesture::Left | Gesture::RockerBack | Gesture::SwipeBack => Message::GoPrevious,
+                    Gesture::Right | Gesture::RockerForward | Gesture::SwipeForward => {
+                        Message::GoNext
+                    }
+                    Gesture::PinchIn => Message::ZoomOut,
+                    Gesture::PinchOut => Message::ZoomIn,
+                    Gesture::Down => {
+                        commands.push(Command::Action(Action::TabRescan));
+                        return commands;
+                    }
+                };
+                commands.extend(self.update(message, modifiers));
+            }
             Message::GoNext => {
                 if let Some(history_i) = self.history_i.checked_add(1) {
                     if let Some(location) = self.history.get(history_i) {
@@ -2560,6 +4206,22 @@ impl Tab {
                     }
                 }
             }
+            Message::HistoryMenu(history_menu) => {
+                self.history_menu = history_menu;
+            }
+            Message::LocationSiblingsMenu(ancestor_index_opt) => {
+                self.siblings_menu = None;
+                if let Some(ancestor_index) = ancestor_index_opt {
+                    if let Some(ancestor) = self
+                        .location
+                        .path_opt()
+                        .and_then(|path| path.ancestors().nth(ancestor_index))
+                    {
+                        let folders = sub_folders(ancestor, self.config.show_hidden);
+                        self.siblings_menu = Some((ancestor_index, folders));
+                    }
+                }
+            }
             Message::ItemDown => {
                 if let Some(edit_location) = &mut self.edit_location {
                     edit_location.select(true);
@@ -2760,6 +4422,7 @@ impl Tab {
                         }
                     }
                     None => {
+                        let mut paths = Vec::new();
                         if let Some(ref mut items) = self.items_opt {
                             for item in items.iter() {
                                 if item.selected {
@@ -2768,7 +4431,7 @@ impl Tab {
                                             //TODO: allow opening multiple tabs?
                                             cd = Some(location.clone());
                                         } else if let Some(path) = location.path_opt() {
-                                            commands.push(Command::OpenFile(path.to_path_buf()));
+                                            paths.push(path.to_path_buf());
                                         }
                                     } else {
                                         //TODO: open properties?
@@ -2776,6 +4439,57 @@ impl Tab {
                                 }
                             }
                         }
+                        // Several files are opened together, so apps are launched once
+                        if paths.len() == 1 {
+                            commands.extend(paths.pop().map(Command::OpenFile));
+                        } else if !paths.is_empty() {
+                            commands.push(Command::OpenFiles(paths));
+                        }
+                    }
+                }
+            }
+            Message::Rename(path_opt) => {
+                self.rename = path_opt.and_then(|path| {
+                    let name = path.file_name()?.to_str()?.to_string();
+                    Some((path, name))
+                });
+                if let Some((path, name)) = &self.rename {
+                    // Only the stem is selected, so typing keeps the extension
+                    let end = if path.is_dir() {
+                        name.chars().count()
+                    } else {
+                        stem_len(name)
+                    };
+                    commands.push(Command::Iced(
+                        widget::text_input::focus(self.rename_id.clone()).into(),
+                    ));
+                    commands.push(Command::Iced(
+                        widget::text_input::select_range(self.rename_id.clone(), 0, end).into(),
+                    ));
+                }
+            }
+            Message::RenameInput(input) => {
+                if let Some((_, name)) = &mut self.rename {
+                    *name = input;
+                }
+            }
+            Message::RenameSubmit => {
+                if let Some((from, name)) = &self.rename {
+                    let fs_type = self
+                        .filesystem
+                        .as_ref()
+                        .map_or("", |filesystem| filesystem.fs_type.as_str());
+                    if let Some(parent) = from.parent() {
+                        // Invalid names stay in the input, where the error is shown
+                        if !name.is_empty()
+                            && name_error(parent, Some(from.as_path()), name, fs_type).is_none()
+                        {
+                            let to = parent.join(name);
+                            if &to != from {
+                                commands.push(Command::Rename(from.clone(), to));
+                            }
+                            self.rename = None;
+                        }
                     }
                 }
             }
@@ -2829,12 +4543,23 @@ impl Tab {
             Message::HighlightActivate(i) => {
                 if let Some(item) = self.items_opt.as_mut().and_then(|f| f.get_mut(i)) {
                     item.highlighted = true;
+                    if item.metadata.is_dir() {
+                        item.size_hovered = true;
+                    }
                 }
             }
 
             Message::Scroll(viewport) => {
                 self.scroll_opt = Some(viewport.absolute_offset());
             }
+            Message::ScrollRestore => {
+                if let Some(offset) = self.scroll_restore.take().or(self.scroll_opt) {
+                    self.scroll_opt = Some(offset);
+                    commands.push(Command::Iced(
+                        scrollable::scroll_to(self.scrollable_id.clone(), offset).into(),
+                    ));
+                }
+            }
             Message::ScrollToFocus => {
                 if let Some(offset) = self.select_focus_scroll() {
                     commands.push(Command::Iced(
@@ -2905,6 +4630,23 @@ impl Tab {
                     ));
                 }
             }
+            Message::SelectPaths(paths) => {
+                let shown = |path: &PathBuf| match &self.location {
+                    Location::Search(root, ..) => path.starts_with(root),
+                    location => path.parent() == location.path_opt().map(|dir| dir.as_path()),
+                };
+                if paths.iter().any(shown) {
+                    self.select_paths(paths);
+                } else if let Some(parent) = paths.first().and_then(|path| path.parent()) {
+                    let location = Location::Path(parent.to_path_buf());
+                    if self.locked {
+                        commands.push(Command::OpenLocationInNewTab(location, Some(paths)));
+                    } else {
+                        self.change_location(&location, None);
+                        commands.push(Command::ChangeLocation(self.title(), location, Some(paths)));
+                    }
+                }
+            }
             Message::SelectFirst => {
                 if self.select_position(0, 0, mod_shift) {
                     if let Some(offset) = self.select_focus_scroll() {
@@ -2917,6 +4659,15 @@ impl Tab {
                     }
                 }
             }
+            Message::SelectInvert => {
+                self.select_invert();
+                if self.select_focus.take().is_some() {
+                    // Unfocus currently focused button
+                    commands.push(Command::Iced(
+                        widget::button::focus(widget::Id::unique()).into(),
+                    ));
+                }
+            }
             Message::SelectLast => {
                 if let Some(ref items) = self.items_opt {
                     if let Some(last_pos) = items.iter().filter_map(|item| item.pos_opt.get()).max()
@@ -2935,9 +4686,69 @@ impl Tab {
                     }
                 }
             }
+            Message::SelectToggle(down) => {
+                let (row, col) = self.select_focus_pos_opt().unwrap_or((0, 0));
+                if let Some(ref mut items) = self.items_opt {
+                    if let Some(item) = items
+                        .iter_mut()
+                        .find(|item| item.pos_opt.get() == Some((row, col)))
+                    {
+                        item.selected = !item.selected;
+                        // Space calculates the size of selected folders, Insert only moves on
+                        if item.selected && !down && item.metadata.is_dir() {
+                            item.size_requested = true;
+                        }
+                    }
+                    let focus_pos = if down { (row + 1, col) } else { (row, col) };
+                    if let Some(i) = items
+                        .iter()
+                        .position(|item| item.pos_opt.get() == Some(focus_pos))
+                    {
+                        self.select_focus = Some(i);
+                        self.select_range = None;
+                    }
+                }
+                if let Some(offset) = self.select_focus_scroll() {
+                    commands.push(Command::Iced(
+                        scrollable::scroll_to(self.scrollable_id.clone(), offset).into(),
+                    ));
+                }
+                if let Some(id) = self.select_focus_id() {
+                    commands.push(Command::Iced(widget::button::focus(id).into()));
+                }
+            }
             Message::SetOpenWith(mime, id) => {
                 commands.push(Command::SetOpenWith(mime, id));
             }
+            Message::SetSelectedPermission(bit, set) => {
+                if let Some(ref mut items) = self.items_opt {
+                    for item in items.iter_mut().filter(|item| item.selected) {
+                        let Some(path) = item.path_opt().map(Path::to_path_buf) else {
+                            continue;
+                        };
+                        let ItemMetadata::Path { metadata, .. } = &mut item.metadata else {
+                            continue;
+                        };
+                        let mode = if set {
+                            metadata.mode() | bit
+                        } else {
+                            metadata.mode() & !bit
+                        };
+                        if let Err(err) =
+                            fs::set_permissions(&path, fs::Permissions::from_mode(mode & 0o7777))
+                        {
+                            log::warn!("failed to set permissions of {:?}: {}", path, err);
+                            continue;
+                        }
+                        match fs::metadata(&path) {
+                            Ok(new_metadata) => *metadata = new_metadata,
+                            Err(err) => {
+                                log::warn!("failed to read metadata of {:?}: {}", path, err);
+                            }
+                        }
+                    }
+                }
+            }
             Message::SetSort(heading_option, dir) => {
                 if !matches!(self.location, Location::Search(..)) {
                     self.sort_name = heading_option;
@@ -2983,6 +4794,9 @@ impl Tab {
                     }
                 }
             }
+            Message::ToggleDimHidden => {
+                self.config.dim_hidden = !self.config.dim_hidden;
+            }
             Message::ToggleShowHidden => {
                 self.config.show_hidden = !self.config.show_hidden;
                 if let Location::Search(path, term, ..) = &self.location {
@@ -3011,8 +4825,34 @@ impl Tab {
             }
             Message::Drop(Some((to, mut from))) => {
                 self.dnd_hovered = None;
+                let drop_point = self.dnd_drop_point.take();
+                if self.config.read_only && to != Location::Trash {
+                    log::info!("Cannot drop into a read-only pane.");
+                    return commands;
+                }
+                // Dropping into the trash always moves, otherwise the modifiers decide
+                if to != Location::Trash {
+                    from.kind = ClipboardKind::for_drop(modifiers);
+                }
                 match to {
+                    // Items dropped on archives are added to them
+                    Location::Path(to) if to.is_file() => {
+                        commands.push(Command::AddToArchive(from.paths, to))
+                    }
+                    // Icons dragged on the desktop they are on are moved instead of copied
+                    Location::Desktop(to, ..)
+                        if drop_point.is_some()
+                            && from.paths.iter().all(|p| p.parent() == Some(to.as_path())) =>
+                    {
+                        if let Some(point) = drop_point {
+                            commands.extend(self.desktop_move(&from.paths, point));
+                        }
+                    }
                     Location::Desktop(to, ..) | Location::Path(to) => {
+                        if from.paths.iter().any(|p| to.starts_with(p)) {
+                            log::info!("Cannot drop a folder into itself.");
+                            return commands;
+                        }
                         if let Ok(entries) = fs::read_dir(&to) {
                             for i in entries.into_iter().filter_map(|e| e.ok()) {
                                 let i = i.path();
@@ -3035,23 +4875,26 @@ impl Tab {
             }
             Message::Drop(None) => {
                 self.dnd_hovered = None;
+                self.dnd_drop_point = None;
             }
             Message::DndHover(loc) => {
                 if self
                     .dnd_hovered
                     .as_ref()
-                    .is_some_and(|(l, i)| *l == loc && i.elapsed() > HOVER_DURATION)
+                    .is_some_and(|(l, i)| *l == loc && i.elapsed() >= SPRING_LOAD_DURATION)
                 {
+                    self.dnd_hovered = None;
                     cd = Some(loc);
                 }
             }
             Message::DndEnter(loc) => {
                 self.dnd_hovered = Some((loc.clone(), Instant::now()));
-                if loc != self.location {
+                // Archives are drop targets that are not opened when hovered
+                if loc != self.location && !loc.path_opt().is_some_and(|path| path.is_file()) {
                     commands.push(Command::Iced(
                         cosmic::Task::perform(
                             async move {
-                                tokio::time::sleep(HOVER_DURATION).await;
+                                tokio::time::sleep(SPRING_LOAD_DURATION).await;
                                 Message::DndHover(loc)
                             },
                             |x| x,
@@ -3065,6 +4908,9 @@ impl Tab {
                     self.dnd_hovered = None;
                 }
             }
+            Message::DndDrop(point) => {
+                self.dnd_drop_point = Some(point);
+            }
             Message::WindowDrag => {
                 commands.push(Command::WindowDrag);
             }
@@ -3093,8 +4939,130 @@ impl Tab {
                     }
                 }
             }
+            Message::TextPreview(path, text_preview) => {
+                let location = Location::Path(path);
+                if let Some(ref mut items) = self.items_opt {
+                    for item in items.iter_mut() {
+                        if item.location_opt.as_ref() == Some(&location) {
+                            item.text_preview_opt = Some(text_preview);
+                            break;
+                        }
+                    }
+                }
+            }
+            Message::MediaMetadata(path, media_metadata) => {
+                let location = Location::Path(path);
+                if let Some(ref mut items) = self.items_opt {
+                    for item in items.iter_mut() {
+                        if item.location_opt.as_ref() == Some(&location) {
+                            item.media_metadata_opt = Some(media_metadata);
+                            break;
+                        }
+                    }
+                }
+            }
+            Message::OpenUrl(url) => {
+                if let Err(err) = open::that_detached(&url) {
+                    log::warn!("failed to open {:?}: {}", url, err);
+                }
+            }
+            Message::Checksums(path, result) => {
+                let location = Location::Path(path);
+                if let Some(ref mut items) = self.items_opt {
+                    for item in items.iter_mut() {
+                        if item.location_opt.as_ref() == Some(&location) {
+                            item.checksums_opt = Some(match result {
+                                Ok(checksums) => ChecksumState::Done(checksums),
+                                Err(err) => ChecksumState::Error(err),
+                            });
+                            break;
+                        }
+                    }
+                }
+            }
+            Message::ChecksumsCalculate(path) => {
+                let location = Location::Path(path.clone());
+                if let Some(ref mut items) = self.items_opt {
+                    for item in items.iter_mut() {
+                        if item.location_opt.as_ref() == Some(&location) {
+                            let controller = Controller::default();
+                            item.checksums_opt =
+                                Some(ChecksumState::Calculating(controller.clone()));
+                            commands.push(Command::Iced(
+                                cosmic::Task::perform(
+                                    async move {
+                                        let result = {
+                                            let path = path.clone();
+                                            tokio::task::spawn_blocking(move || {
+                                                checksum::calculate(&path, &controller)
+                                            })
+                                            .await
+                                            .unwrap()
+                                        };
+                                        Message::Checksums(path, result.map(Arc::new))
+                                    },
+                                    |x| x,
+                                )
+                                .into(),
+                            ));
+                            break;
+                        }
+                    }
+                }
+            }
+            Message::CopyText(text) => {
+                commands.push(Command::Iced(clipboard::write(text).into()));
+            }
+            #[cfg(feature = "pdf")]
+            Message::PdfPage(path, pdf_page) => {
+                let location = Location::Path(path);
+                if let Some(ref mut items) = self.items_opt {
+                    for item in items.iter_mut() {
+                        if item.location_opt.as_ref() == Some(&location) {
+                            item.pdf_page_index = pdf_page.index;
+                            item.pdf_page_opt = Some(pdf_page);
+                            break;
+                        }
+                    }
+                }
+            }
+            #[cfg(feature = "pdf")]
+            Message::PdfPageChange(path, index) => {
+                let location = Location::Path(path);
+                if let Some(ref mut items) = self.items_opt {
+                    for item in items.iter_mut() {
+                        if item.location_opt.as_ref() == Some(&location) {
+                            item.pdf_page_index = index;
+                            break;
+                        }
+                    }
+                }
+            }
+            #[cfg(feature = "gstreamer")]
+            Message::MediaPlayPause => {
+                if let Some(media) = &mut self.media_opt {
+                    media.set_playing(!media.is_playing());
+                }
+            }
+            #[cfg(feature = "gstreamer")]
+            Message::MediaSeek(secs) => {
+                if let Some(media) = &mut self.media_opt {
+                    media.seek(secs);
+                }
+            }
+            #[cfg(feature = "gstreamer")]
+            Message::MediaTick => {
+                if let Some(media) = &mut self.media_opt {
+                    media.poll();
+                }
+            }
         }
 
+        // Start or stop media playback when the gallery item changes
+        #[cfg(feature = "gstreamer")]
+        self.update_media();
+        self.update_hex_view();
+
         // Scroll to top if needed
         if self.scroll_opt.is_none() {
             let offset = AbsoluteOffset { x: 0.0, y: 0.0 };
@@ -3128,7 +5096,9 @@ impl Tab {
                     }
                 }
                 if location != self.location || selected_paths.is_some() {
-                    if location.path_opt().map_or(true, |path| path.is_dir()) {
+                    if self.locked && location != self.location {
+                        commands.push(Command::OpenLocationInNewTab(location, selected_paths));
+                    } else if location.path_opt().map_or(true, |path| path.is_dir()) {
                         if selected_paths.is_none() {
                             selected_paths = self
                                 .location
@@ -3151,6 +5121,145 @@ impl Tab {
         commands
     }
 
+    fn set_gallery(&mut self, gallery: bool) {
+        if gallery && !self.gallery {
+            self.slideshow = false;
+            self.filmstrip.clear();
+            if let Some(items) = &self.items_opt {
+                let paths: Vec<PathBuf> = items
+                    .iter()
+                    .filter(|item| item.selected && item.can_gallery())
+                    .filter_map(|item| item.path_opt().map(|path| path.to_path_buf()))
+                    .collect();
+                // Culling several selected items only steps through those
+                if paths.len() > 1 {
+                    self.filmstrip = paths;
+                }
+            }
+        }
+        self.gallery = gallery;
+    }
+
+    fn gallery_focus_path(&self) -> Option<PathBuf> {
+        let items = self.items_opt.as_ref()?;
+        let item = items.get(self.select_focus?)?;
+        item.path_opt().map(|path| path.to_path_buf())
+    }
+
+    fn filmstrip_view(&self) -> Option<Element<Message>> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+        let items = self.items_opt.as_ref()?;
+        if self.filmstrip.is_empty() {
+            return None;
+        }
+
+        let size = ICON_SIZE_GRID as f32;
+        let mut row = widget::row::with_capacity(self.filmstrip.len()).spacing(space_xxs);
+        for path in self.filmstrip.iter() {
+            let location = Location::Path(path.clone());
+            let Some(index) = items
+                .iter()
+                .position(|item| item.location_opt.as_ref() == Some(&location))
+            else {
+                continue;
+            };
+            let item = &items[index];
+            let thumbnail: Element<Message> = match item.thumbnail_opt.as_ref() {
+                Some(ItemThumbnail::Image(handle, _)) => widget::image(handle.clone())
+                    .content_fit(ContentFit::Cover)
+                    .width(size)
+                    .height(size)
+                    .into(),
+                _ => widget::icon::icon(item.icon_handle_grid.clone())
+                    .size(ICON_SIZE_GRID)
+                    .into(),
+            };
+            row = row.push(
+                widget::button::custom(thumbnail)
+                    .padding(space_xxs)
+                    .class(button_style(
+                        self.select_focus == Some(index),
+                        false,
+                        false,
+                        false,
+                        false,
+                        false,
+                    ))
+                    .on_press(Message::GalleryFocus(index)),
+            );
+        }
+        Some(
+            widget::container(widget::scrollable(row).direction(
+                widget::scrollable::Direction::Horizontal(widget::scrollable::Scrollbar::default()),
+            ))
+            .center_x(Length::Fill)
+            .into(),
+        )
+    }
+
+    fn update_hex_view(&mut self) {
+        if !self.gallery {
+            self.hex_mode = false;
+        }
+        // Files that cannot be shown otherwise are always shown as hex
+        let path_opt = self
+            .select_focus
+            .filter(|_| self.gallery)
+            .and_then(|index| self.items_opt.as_ref()?.get(index))
+            .filter(|item| !item.metadata.is_dir() && (self.hex_mode || !item.can_gallery()))
+            .and_then(|item| item.path_opt())
+            .map(|path| path.to_path_buf());
+        match path_opt {
+            Some(path) => {
+                if self.hex_view_opt.as_ref().map(|hex_view| hex_view.path())
+                    != Some(path.as_path())
+                {
+                    self.hex_status_opt = None;
+                    self.hex_view_opt = match HexView::new(&path) {
+                        Ok(hex_view) => Some(hex_view),
+                        Err(err) => {
+                            log::warn!("failed to show {:?} as hex: {}", path, err);
+                            self.hex_status_opt = Some(err);
+                            None
+                        }
+                    };
+                }
+            }
+            None => {
+                self.hex_view_opt = None;
+            }
+        }
+    }
+
+    #[cfg(feature = "gstreamer")]
+    fn update_media(&mut self) {
+        let path_opt = if self.gallery {
+            self.select_focus
+                .and_then(|index| self.items_opt.as_ref()?.get(index))
+                .filter(|item| media::is_media(&item.mime))
+                .and_then(|item| item.path_opt())
+                .map(|path| path.to_path_buf())
+        } else {
+            None
+        };
+        match path_opt {
+            Some(path) => {
+                if self.media_opt.as_ref().map(|media| media.path()) != Some(path.as_path()) {
+                    self.media_opt = match MediaPlayer::new(&path) {
+                        Ok(media) => Some(media),
+                        Err(err) => {
+                            log::warn!("failed to play {:?}: {}", path, err);
+                            None
+                        }
+                    };
+                }
+            }
+            None => {
+                self.media_opt = None;
+            }
+        }
+    }
+
     pub(crate) fn sort_options(&self) -> (HeadingOptions, bool, bool) {
         match self.location {
             Location::Search(..) => (HeadingOptions::Modified, false, false),
@@ -3325,7 +5434,11 @@ impl Tab {
                         .as_ref()
                         .unwrap_or(&ItemThumbnail::NotImage)
                     {
-                        ItemThumbnail::NotImage => {}
+                        ItemThumbnail::NotImage => {
+                            if let Some(text_preview) = &item.text_preview_opt {
+                                element_opt = Some(highlight::view(text_preview, Length::Fill));
+                            }
+                        }
                         ItemThumbnail::Image(handle, _) => {
                             if let Some(path) = item.path_opt() {
                                 element_opt = Some(
@@ -3372,6 +5485,47 @@ impl Tab {
                 }
             }
         }
+        #[cfg(feature = "gstreamer")]
+        if let Some(media) = &self.media_opt {
+            element_opt = Some(media.view(Message::MediaPlayPause, Message::MediaSeek));
+        }
+        if let Some(hex_view) = &self.hex_view_opt {
+            let mut toolbar = widget::row::with_capacity(6)
+                .align_y(Alignment::Center)
+                .spacing(space_xxs);
+            toolbar = toolbar.push(
+                widget::button::icon(widget::icon::from_name("go-up-symbolic"))
+                    .on_press(Message::HexPreviousPage),
+            );
+            toolbar = toolbar.push(
+                widget::button::icon(widget::icon::from_name("go-down-symbolic"))
+                    .on_press(Message::HexNextPage),
+            );
+            toolbar = toolbar.push(
+                widget::text_input(fl!("hex-offset"), &self.hex_offset_input)
+                    .on_input(Message::HexOffsetInput)
+                    .on_submit(Message::HexOffsetSubmit)
+                    .width(Length::Fixed(160.0)),
+            );
+            toolbar = toolbar.push(
+                widget::text_input::search_input(fl!("hex-search"), &self.hex_search_input)
+                    .on_input(Message::HexSearchInput)
+                    .on_submit(Message::HexSearchSubmit)
+                    .width(Length::Fixed(240.0)),
+            );
+            toolbar = toolbar.push(widget::text::body(fl!(
+                "item-size",
+                size = format_size(hex_view.file_len())
+            )));
+            if let Some(status) = &self.hex_status_opt {
+                toolbar = toolbar.push(widget::text::body(status));
+            }
+            element_opt = Some(
+                widget::column::with_children(vec![toolbar.into(), hex_view.view()])
+                    .spacing(space_xxs)
+                    .into(),
+            );
+        }
 
         let mut column = widget::column::with_capacity(2);
         column = column.push(widget::Space::with_height(Length::Fixed(space_m.into())));
@@ -3382,6 +5536,64 @@ impl Tab {
                 row = row.push(name);
             }
             row = row.push(widget::horizontal_space());
+            // Images in read-only panes are not changed
+            let can_transform = !self.config.read_only
+                && self
+                    .select_focus
+                    .and_then(|index| self.items_opt.as_ref()?.get(index))
+                    .map_or(false, |item| transform::can_transform(&item.mime));
+            if can_transform {
+                for (icon, image_transform) in [
+                    ("object-rotate-left-symbolic", ImageTransform::RotateLeft),
+                    ("object-rotate-right-symbolic", ImageTransform::RotateRight),
+                    (
+                        "object-flip-horizontal-symbolic",
+                        ImageTransform::FlipHorizontal,
+                    ),
+                    (
+                        "object-flip-vertical-symbolic",
+                        ImageTransform::FlipVertical,
+                    ),
+                ] {
+                    row = row.push(
+                        widget::button::icon(widget::icon::from_name(icon))
+                            .class(theme::Button::Standard)
+                            .on_press(Message::GalleryTransform(image_transform)),
+                    );
+                }
+            }
+            row = row.push(
+                widget::button::icon(widget::icon::from_name(if self.slideshow {
+                    "media-playback-pause-symbolic"
+                } else {
+                    "media-playback-start-symbolic"
+                }))
+                .class(theme::Button::Standard)
+                .on_press(Message::GallerySlideshow(!self.slideshow)),
+            );
+            if self
+                .gallery_focus_path()
+                .is_some_and(|path| crate::print::can_print(&path))
+            {
+                row = row.push(
+                    widget::button::icon(widget::icon::from_name("document-print-symbolic"))
+                        .class(theme::Button::Standard)
+                        .on_press(Message::ContextAction(Action::Print)),
+                );
+            }
+            row = row.push(
+                widget::button::icon(widget::icon::from_name("user-trash-symbolic"))
+                    .class(theme::Button::Standard)
+                    .on_press(Message::GalleryDelete),
+            );
+            row = row.push(
+                widget::button::text(if self.hex_mode {
+                    fl!("hex-mode-off")
+                } else {
+                    fl!("hex-mode-on")
+                })
+                .on_press(Message::HexMode(!self.hex_mode)),
+            );
             row = row.push(
                 widget::button::icon(widget::icon::from_name("window-close-symbolic"))
                     .class(theme::Button::Standard)
@@ -3420,6 +5632,10 @@ impl Tab {
             row = row.push(widget::Space::with_width(Length::Fixed(space_m.into())));
             column = column.push(row);
         }
+        if let Some(filmstrip) = self.filmstrip_view() {
+            column = column.push(filmstrip);
+            column = column.push(widget::Space::with_height(Length::Fixed(space_m.into())));
+        }
 
         widget::container(column)
             .width(Length::Fill)
@@ -3482,26 +5698,69 @@ impl Tab {
             .padding([space_xxxs, 0]);
         let mut w = 0.0;
 
-        let mut prev_button =
+        // A right click on the history buttons shows a dropdown with the locations
+        let history_button = |button, forward| {
+            let history_menu = if self.history_menu == Some(forward) {
+                None
+            } else {
+                Some(forward)
+            };
+            let mouse_area = crate::mouse_area::MouseArea::new(button)
+                .on_right_press(move |_point_opt| Message::HistoryMenu(history_menu));
+            let mut popover =
+                widget::popover(mouse_area).position(widget::popover::Position::Bottom);
+            if self.history_menu == Some(forward) {
+                popover = popover.popup(menu::history_menu(self, forward));
+            }
+            popover
+        };
+
+        let mut prev_button = a11y::name(
             widget::button::custom(widget::icon::from_name("go-previous-symbolic").size(16))
                 .padding(space_xxs)
-                .class(theme::Button::Icon);
+                .class(theme::Button::Icon),
+            fl!("go-back"),
+        );
         if self.history_i > 0 && !self.history.is_empty() {
             prev_button = prev_button.on_press(Message::GoPrevious);
         }
-        row = row.push(prev_button);
+        row = row.push(history_button(prev_button, false));
         w += 16.0 + 2.0 * space_xxs as f32;
 
-        let mut next_button =
+        let mut next_button = a11y::name(
             widget::button::custom(widget::icon::from_name("go-next-symbolic").size(16))
                 .padding(space_xxs)
-                .class(theme::Button::Icon);
+                .class(theme::Button::Icon),
+            fl!("go-forward"),
+        );
         if self.history_i + 1 < self.history.len() {
             next_button = next_button.on_press(Message::GoNext);
         }
-        row = row.push(next_button);
+        row = row.push(history_button(next_button, true));
         w += 16.0 + 2.0 * space_xxs as f32;
 
+        let mut up_button = a11y::name(
+            widget::button::custom(widget::icon::from_name("go-up-symbolic").size(16))
+                .padding(space_xxs)
+                .class(theme::Button::Icon),
+            fl!("go-up"),
+        );
+        if matches!(&self.location, Location::Path(path) if path.parent().is_some()) {
+            up_button = up_button.on_press(Message::LocationUp);
+        }
+        row = row.push(up_button);
+        w += 16.0 + 2.0 * space_xxs as f32;
+
+        if self.config.read_only {
+            row = row.push(widget::tooltip(
+                widget::container(widget::icon::from_name("changes-prevent-symbolic").size(16))
+                    .padding(space_xxs),
+                widget::text::body(fl!("read-only-pane")),
+                widget::tooltip::Position::Bottom,
+            ));
+            w += 16.0 + 2.0 * space_xxs as f32;
+        }
+
         row = row.push(widget::Space::with_width(Length::Fixed(space_s.into())));
         w += space_s as f32;
 
@@ -3511,6 +5770,22 @@ impl Tab {
         let size_width = 100.0;
         let condensed = size.width < (name_width + modified_width + size_width);
 
+        // The file system is only shown if there is enough space for the breadcrumbs
+        let filesystem_width = 160.0;
+        let filesystem_opt = self
+            .filesystem
+            .as_ref()
+            .filter(|filesystem| !condensed && filesystem.total > 0);
+        if filesystem_opt.is_some() {
+            w += filesystem_width + space_s as f32;
+        }
+
+        // Room for the quick filter dropdown
+        let show_filter = !matches!(self.mode, Mode::Desktop);
+        if show_filter {
+            w += 120.0 + space_s as f32;
+        }
+
         let (sort_name, sort_direction, _) = self.sort_options();
         let heading_item = |name, width, msg| {
             let mut row = widget::row::with_capacity(2)
@@ -3652,12 +5927,27 @@ impl Tab {
                             widget::text::heading(name).wrapping(text::Wrapping::None),
                         )
                     } else {
-                        children.push(
-                            widget::icon::from_name("go-next-symbolic")
-                                .size(16)
-                                .icon()
-                                .into(),
-                        );
+                        // The separator after a folder shows a dropdown with the folders inside
+                        let siblings_menu_open = self
+                            .siblings_menu
+                            .as_ref()
+                            .map_or(false, |(ancestor_index, _)| *ancestor_index == index);
+                        let separator = widget::button::custom(
+                            widget::icon::from_name("go-next-symbolic").size(16),
+                        )
+                        .padding(0)
+                        .class(theme::Button::Icon)
+                        .on_press(Message::LocationSiblingsMenu(if siblings_menu_open {
+                            None
+                        } else {
+                            Some(index)
+                        }));
+                        let mut popover =
+                            widget::popover(separator).position(widget::popover::Position::Bottom);
+                        if siblings_menu_open {
+                            popover = popover.popup(menu::siblings_menu(self));
+                        }
+                        children.push(popover.into());
                         w += 16.0;
                         (
                             text_width_body(&name),
@@ -3750,6 +6040,43 @@ impl Tab {
         for child in children {
             row = row.push(child);
         }
+        row = row.push(widget::horizontal_space());
+        if show_filter {
+            row = row.push(
+                widget::dropdown(
+                    FILTER_NAMES.as_slice(),
+                    Filter::all()
+                        .iter()
+                        .position(|filter| *filter == self.filter),
+                    |index| Message::Filter(Filter::all()[index]),
+                )
+                .width(Length::Shrink),
+            );
+        }
+        if let Some(filesystem) = filesystem_opt {
+            let free_space = fl!(
+                "free-space",
+                free = format_size(filesystem.free),
+                total = format_size(filesystem.total)
+            );
+            row = row.push(widget::tooltip(
+                widget::column::with_children(vec![
+                    widget::row::with_children(vec![
+                        widget::text::caption(filesystem.fs_type.clone()).into(),
+                        widget::horizontal_space().into(),
+                        widget::text::caption(format_size(filesystem.free)).into(),
+                    ])
+                    .into(),
+                    widget::progress_bar(0.0..=1.0, filesystem.used_ratio())
+                        .height(Length::Fixed(4.0))
+                        .into(),
+                ])
+                .spacing(space_xxxs)
+                .width(Length::Fixed(filesystem_width)),
+                widget::text::body(free_space),
+                widget::tooltip::Position::Bottom,
+            ));
+        }
         let mut column = widget::column::with_capacity(4).padding([0, space_s]);
         column = column.push(row);
         column = column.push(accent_rule);
@@ -3768,7 +6095,7 @@ impl Tab {
             self.location_context_menu_index,
         ) {
             popover = popover
-                .popup(menu::location_context_menu1(index))
+                .popup(menu::location_context_menu(index))
                 .position(widget::popover::Position::Point(point))
         }
 
@@ -3787,7 +6114,9 @@ impl Tab {
                         .into(),
                     widget::text::body(if has_hidden {
                         fl!("empty-folder-hidden")
-                    } else if matches!(self.location, Location::Search(..)) {
+                    } else if matches!(self.location, Location::Search(..))
+                        || self.filter != Filter::All
+                    {
                         fl!("no-results")
                     } else {
                         fl!("empty-folder")
@@ -3805,6 +6134,161 @@ impl Tab {
         .into()
     }
 
+    /// Input replacing the name of the item that is renamed in place, with any error next to it
+    fn rename_input(&self, item: &Item, grid: bool) -> Option<Element<'_, Message>> {
+        let (from, name) = self.rename.as_ref()?;
+        if item.path_opt() != Some(from) {
+            return None;
+        }
+        let parent = from.parent()?;
+        let fs_type = self
+            .filesystem
+            .as_ref()
+            .map_or("", |filesystem| filesystem.fs_type.as_str());
+        let input = widget::text_input("", name.as_str())
+            .id(self.rename_id.clone())
+            .on_input(Message::RenameInput)
+            .on_submit(Message::RenameSubmit);
+        let Some(error) = name_error(parent, Some(from.as_path()), name, fs_type) else {
+            return Some(input.into());
+        };
+        let error = widget::text::caption(error);
+        Some(if grid {
+            widget::column::with_children(vec![input.into(), error.into()])
+                .align_x(Alignment::Center)
+                .into()
+        } else {
+            widget::row::with_children(vec![input.into(), error.into()])
+                .align_y(Alignment::Center)
+                .spacing(theme::active().cosmic().spacing.space_xxs)
+                .width(Length::Fill)
+                .into()
+        })
+    }
+
+    fn desktop_layout_key(&self) -> Option<String> {
+        let Location::Desktop(_path, output, _desktop_config) = &self.location else {
+            return None;
+        };
+        let size = self.size_opt.get()?;
+        Some(desktop_layout_key(
+            output,
+            size.width as u32,
+            size.height as u32,
+        ))
+    }
+
+    /// Positions of the desktop icons, the remembered ones first and the others in the
+    /// free cells in sort order. None if the icons are arranged automatically.
+    fn desktop_positions(
+        &self,
+        items: &[(usize, &Item)],
+        rows: usize,
+        cols: usize,
+        cell: Size,
+    ) -> Option<HashMap<usize, Point>> {
+        let Location::Desktop(_path, _output, desktop_config) = &self.location else {
+            return None;
+        };
+        if desktop_config.auto_arrange {
+            return None;
+        }
+        let layout_opt = self
+            .desktop_layout_key()
+            .and_then(|key| self.desktop_layouts.get(&key));
+        let visible_items = || {
+            items
+                .iter()
+                .filter(|(_, item)| self.config.show_hidden || !item.hidden)
+        };
+
+        let mut positions = HashMap::new();
+        let mut occupied = Vec::new();
+        if let Some(layout) = layout_opt {
+            for &(i, item) in visible_items() {
+                if let Some(position) = layout.get(&item.name) {
+                    let point = Point::new(position.x as f32, position.y as f32);
+                    occupied.push(Rectangle::new(point, cell));
+                    positions.insert(i, point);
+                }
+            }
+        }
+
+        let mut n = 0;
+        for &(i, _item) in visible_items() {
+            if positions.contains_key(&i) {
+                continue;
+            }
+            loop {
+                let (row, col) = desktop_cell(n, rows, cols);
+                n += 1;
+                let point = Point::new(col as f32 * cell.width, row as f32 * cell.height);
+                // Shrunk a little so that icons rounded to the next pixel do not block two cells
+                let rect = Rectangle::new(
+                    Point::new(point.x + 1.0, point.y + 1.0),
+                    Size::new(cell.width - 2.0, cell.height - 2.0),
+                );
+                if !occupied.iter().any(|other| other.intersects(&rect)) {
+                    positions.insert(i, point);
+                    break;
+                }
+            }
+        }
+        Some(positions)
+    }
+
+    /// Move the dropped desktop icons so that the first one is centered on `point`.
+    /// All icons are remembered where they are, so that they stay in place.
+    fn desktop_move(&mut self, paths: &[PathBuf], point: Point) -> Option<Command> {
+        let Location::Desktop(_path, _output, desktop_config) = &self.location else {
+            return None;
+        };
+        if desktop_config.auto_arrange {
+            return None;
+        }
+        let align_to_grid = desktop_config.align_to_grid;
+        let key = self.desktop_layout_key()?;
+        let cell = self.desktop_cell_opt.get()?;
+        let size = self.size_opt.get()?;
+        let space_m = f32::from(theme::active().cosmic().spacing.space_m);
+        let items = self.items_opt.as_ref()?;
+        let is_moved = |item: &Item| item.path_opt().is_some_and(|path| paths.contains(path));
+        let anchor = items
+            .iter()
+            .find(|item| is_moved(item))
+            .and_then(|item| item.rect_opt.get())?;
+        let offset = point - anchor.center();
+
+        let mut layout = self.desktop_layouts.get(&key).cloned().unwrap_or_default();
+        for item in items {
+            let Some(rect) = item.rect_opt.get() else {
+                continue;
+            };
+            let mut position = Point::new(rect.x - space_m, rect.y);
+            if is_moved(item) {
+                position = position + offset;
+                if align_to_grid {
+                    position.x = (position.x / cell.width).round() * cell.width;
+                    position.y = (position.y / cell.height).round() * cell.height;
+                }
+                position.x = position
+                    .x
+                    .min(size.width - 2.0 * space_m - rect.width)
+                    .max(0.0);
+                position.y = position.y.min(size.height - rect.height).max(0.0);
+            }
+            layout.insert(
+                item.name.clone(),
+                DesktopPosition {
+                    x: position.x.round() as u32,
+                    y: position.y.round() as u32,
+                },
+            );
+        }
+        self.desktop_layouts.insert(key.clone(), layout.clone());
+        Some(Command::DesktopLayout(key, layout))
+    }
+
     pub fn grid_view(&self) -> (Option<Element<'static, Message>>, Element<Message>, bool) {
         let cosmic_theme::Spacing {
             space_m,
@@ -3812,8 +6296,9 @@ impl Tab {
             space_xxxs,
             ..
         } = theme::active().cosmic().spacing;
+        let selection_count = self.selection_count();
 
-        let TabConfig1 {
+        let TabConfig {
             show_hidden,
             mut icon_sizes,
             ..
@@ -3827,6 +6312,7 @@ impl Tab {
 
         let text_height = 3 * 20; // 3 lines of text
         let item_width = (3 * space_xxs + icon_sizes.grid() + 3 * space_xxs) as usize;
+        let name_width = (item_width - 2 * space_xxxs as usize) as f32;
         let item_height =
             (space_xxxs + icon_sizes.grid() + space_xxxs + text_height + space_xxxs) as usize;
 
@@ -3857,6 +6343,13 @@ impl Tab {
             rows_m1 + 1
         };
 
+        let cell_width = item_width + column_spacing as usize;
+        let cell_height = item_height + grid_spacing as usize;
+        if matches!(self.location, Location::Desktop(..)) {
+            self.desktop_cell_opt
+                .set(Some(Size::new(cell_width as f32, cell_height as f32)));
+        }
+
         let mut grid = widget::grid()
             .column_spacing(column_spacing)
             .row_spacing(grid_spacing)
@@ -3876,28 +6369,48 @@ impl Tab {
             let mut page_row = 0;
             let mut hidden = 0;
             let mut grid_elements = Vec::new();
+            let desktop_positions = self.desktop_positions(
+                &items,
+                rows,
+                cols,
+                Size::new(cell_width as f32, cell_height as f32),
+            );
+            let mut desktop_elements: Vec<Element<Message>> = Vec::new();
             for &(i, item) in items.iter() {
-                if !show_hidden && item.hidden {
+                if (!show_hidden && item.hidden) || !self.filter.matches(item) {
                     item.pos_opt.set(None);
                     item.rect_opt.set(None);
                     hidden += 1;
                     continue;
                 }
+                let desktop_position = desktop_positions
+                    .as_ref()
+                    .and_then(|positions| positions.get(&i).copied());
+                if let Some(position) = desktop_position {
+                    row = (position.y / cell_height as f32).round() as usize;
+                    col = (position.x / cell_width as f32).round() as usize;
+                }
                 item.pos_opt.set(Some((row, col)));
                 item.rect_opt.set(Some(Rectangle::new(
-                    Point::new(
-                        (col * (item_width + column_spacing as usize) + space_m as usize) as f32,
-                        (row * (item_height + grid_spacing as usize)) as f32,
-                    ),
+                    match desktop_position {
+                        Some(position) => Point::new(position.x + space_m as f32, position.y),
+                        None => Point::new(
+                            (col * cell_width + space_m as usize) as f32,
+                            (row * cell_height) as f32,
+                        ),
+                    },
                     Size::new(item_width as f32, item_height as f32),
                 )));
 
                 //TODO: one focus group per grid item (needs custom widget)
                 let buttons: Vec<Element<Message>> = vec![
                     widget::button::custom(
-                        widget::icon::icon(item.icon_handle_grid.clone())
-                            .content_fit(ContentFit::Contain)
-                            .size(icon_sizes.grid()),
+                        item.emblem_icon(
+                            widget::icon::icon(item.icon_handle_grid.clone())
+                                .content_fit(ContentFit::Contain)
+                                .size(icon_sizes.grid()),
+                            icon_sizes.grid(),
+                        ),
                     )
                     .padding(space_xxxs)
                     .class(button_style(
@@ -3906,23 +6419,36 @@ impl Tab {
                         false,
                         false,
                         false,
+                        self.config.dim_hidden && item.hidden,
                     ))
                     .into(),
-                    widget::tooltip(
-                        widget::button::custom(widget::text::body(&item.display_name))
-                            .id(item.button_id.clone())
-                            .padding([0, space_xxxs])
-                            .class(button_style(
-                                item.selected,
-                                item.highlighted,
-                                true,
-                                true,
-                                matches!(self.mode, Mode::Desktop),
-                            )),
-                        widget::text::body(&item.name),
-                        widget::tooltip::Position::Bottom,
-                    )
-                    .into(),
+                    match self.rename_input(item, true) {
+                        Some(input) => input,
+                        None => widget::tooltip(
+                            item.accessible(
+                                widget::button::custom(
+                                    widget::text::body(&item.display_name).class(item.name_class()),
+                                )
+                                .id(item.button_id.clone())
+                                .padding([0, space_xxxs])
+                                .class(button_style(
+                                    item.selected,
+                                    item.highlighted,
+                                    true,
+                                    true,
+                                    matches!(self.mode, Mode::Desktop),
+                                    self.config.dim_hidden && item.hidden,
+                                )),
+                                selection_count,
+                            ),
+                            widget::text::body(
+                                item.hover_details(item.name_truncated(name_width, 3))
+                                    .unwrap_or_else(|| item.name.clone()),
+                            ),
+                            widget::tooltip::Position::Bottom,
+                        )
+                        .into(),
+                    },
                 ];
 
                 let mut column = widget::column::with_capacity(buttons.len())
@@ -3942,7 +6468,7 @@ impl Tab {
                 }
 
                 let column: Element<Message> =
-                    if item.metadata.is_dir() && item.location_opt.is_some() {
+                    if item.is_drop_target() && item.location_opt.is_some() {
                         self.dnd_dest(&item.location_opt.clone().unwrap(), column)
                     } else {
                         column.into()
@@ -3963,11 +6489,19 @@ impl Tab {
                     .on_enter(move || Message::HighlightActivate(i))
                     .on_exit(move || Message::HighlightDeactivate(i));
 
-                //TODO: error if the row or col is already set?
-                while grid_elements.len() <= row {
-                    grid_elements.push(Vec::new());
+                if let Some(position) = desktop_position {
+                    desktop_elements.push(
+                        widget::container(mouse_area)
+                            .padding(Padding::ZERO.top(position.y).left(position.x))
+                            .into(),
+                    );
+                } else {
+                    //TODO: error if the row or col is already set?
+                    while grid_elements.len() <= row {
+                        grid_elements.push(Vec::new());
+                    }
+                    grid_elements[row].push(mouse_area);
                 }
-                grid_elements[row].push(mouse_area);
 
                 count += 1;
                 if matches!(self.mode, Mode::Desktop) {
@@ -4001,8 +6535,27 @@ impl Tab {
                 return (None, self.empty_view(hidden > 0), false);
             }
 
-            children.push(grid.into());
-
+            match desktop_positions {
+                Some(positions) => {
+                    // Icons are placed freely on top of a space that spans all of them
+                    let (right, bottom) = positions.values().fold((0.0, 0.0), |(x, y), p| {
+                        (
+                            f32::max(x, p.x + item_width as f32),
+                            f32::max(y, p.y + item_height as f32),
+                        )
+                    });
+                    let mut layers: Vec<Element<Message>> =
+                        vec![Space::new(Length::Fixed(right), Length::Fixed(bottom)).into()];
+                    layers.extend(desktop_elements);
+                    children.push(
+                        widget::container(Stack::with_children(layers))
+                            .padding(space_xxs)
+                            .into(),
+                    );
+                }
+                None => children.push(grid.into()),
+            }
+
             //TODO: HACK If we don't reach the bottom of the view, go ahead and add a spacer to do that
             {
                 let mut max_bottom = 0;
@@ -4062,10 +6615,12 @@ impl Tab {
                                     false,
                                     false,
                                     false,
+                                    self.config.dim_hidden && item.hidden,
                                 )),
-                                widget::button::custom(widget::text::body(
-                                    item.display_name.clone(),
-                                ))
+                                widget::button::custom(
+                                    widget::text::body(item.display_name.clone())
+                                        .class(item.name_class()),
+                                )
                                 .id(item.button_id.clone())
                                 .on_press(Message::Click(Some(*i)))
                                 .padding([0, space_xxxs])
@@ -4075,6 +6630,7 @@ impl Tab {
                                     true,
                                     true,
                                     false,
+                                    self.config.dim_hidden && item.hidden,
                                 )),
                             ];
 
@@ -4116,8 +6672,9 @@ impl Tab {
             space_xxs,
             ..
         } = theme::active().cosmic().spacing;
+        let selection_count = self.selection_count();
 
-        let TabConfig1 {
+        let TabConfig {
             show_hidden,
             icon_sizes,
             ..
@@ -4136,6 +6693,12 @@ impl Tab {
             icon_sizes.list()
         };
         let row_height = icon_size + 2 * space_xxs;
+        // Space left for the names, which are cut off when they do not fit
+        let name_space = if condensed {
+            size.width - (icon_size + 4 * space_xxs) as f32
+        } else {
+            size.width - (icon_size + 5 * space_xxs) as f32 - modified_width - size_width
+        };
 
         let mut children: Vec<Element<_>> = Vec::new();
         let mut y = 0;
@@ -4148,7 +6711,7 @@ impl Tab {
             let mut count = 0;
             let mut hidden = 0;
             for (i, item) in items {
-                if item.hidden && !show_hidden {
+                if (item.hidden && !show_hidden) || !self.filter.matches(item) {
                     item.pos_opt.set(None);
                     item.rect_opt.set(None);
                     hidden += 1;
@@ -4182,7 +6745,14 @@ impl Tab {
 
                 let size_text = match &item.metadata {
                     ItemMetadata::Path { metadata, children } => {
-                        if metadata.is_dir() {
+                        if metadata.is_dir() && item.size_requested {
+                            match &item.dir_size {
+                                DirSize::Calculating(_) => fl!("calculating"),
+                                DirSize::Directory(size) => format_size(*size),
+                                DirSize::NotDirectory => String::new(),
+                                DirSize::Error(err) => err.clone(),
+                            }
+                        } else if metadata.is_dir() {
                             //TODO: translate
                             if *children == 1 {
                                 format!("{} item", children)
@@ -4217,12 +6787,18 @@ impl Tab {
 
                 let row = if condensed {
                     widget::row::with_children(vec![
-                        widget::icon::icon(item.icon_handle_list_condensed.clone())
-                            .content_fit(ContentFit::Contain)
-                            .size(icon_size)
-                            .into(),
+                        item.emblem_icon(
+                            widget::icon::icon(item.icon_handle_list_condensed.clone())
+                                .content_fit(ContentFit::Contain)
+                                .size(icon_size),
+                            icon_size,
+                        ),
                         widget::column::with_children(vec![
-                            widget::text::body(item.display_name.clone()).into(),
+                            self.rename_input(item, false).unwrap_or_else(|| {
+                                widget::text::body(item.display_name.clone())
+                                    .class(item.name_class())
+                                    .into()
+                            }),
                             //TODO: translate?
                             widget::text::caption(format!("{} - {}", modified_text, size_text))
                                 .into(),
@@ -4234,12 +6810,16 @@ impl Tab {
                     .spacing(space_xxs)
                 } else if is_search {
                     widget::row::with_children(vec![
-                        widget::icon::icon(item.icon_handle_list_condensed.clone())
-                            .content_fit(ContentFit::Contain)
-                            .size(icon_size)
-                            .into(),
+                        item.emblem_icon(
+                            widget::icon::icon(item.icon_handle_list_condensed.clone())
+                                .content_fit(ContentFit::Contain)
+                                .size(icon_size),
+                            icon_size,
+                        ),
                         widget::column::with_children(vec![
-                            widget::text::body(item.display_name.clone()).into(),
+                            widget::text::body(item.display_name.clone())
+                                .class(item.name_class())
+                                .into(),
                             widget::text::caption(match item.path_opt() {
                                 Some(path) => path.display().to_string(),
                                 None => String::new(),
@@ -4260,13 +6840,18 @@ impl Tab {
                     .spacing(space_xxs)
                 } else {
                     widget::row::with_children(vec![
-                        widget::icon::icon(item.icon_handle_list.clone())
-                            .content_fit(ContentFit::Contain)
-                            .size(icon_size)
-                            .into(),
-                        widget::text::body(item.display_name.clone())
-                            .width(Length::Fill)
-                            .into(),
+                        item.emblem_icon(
+                            widget::icon::icon(item.icon_handle_list.clone())
+                                .content_fit(ContentFit::Contain)
+                                .size(icon_size),
+                            icon_size,
+                        ),
+                        self.rename_input(item, false).unwrap_or_else(|| {
+                            widget::text::body(item.display_name.clone())
+                                .class(item.name_class())
+                                .width(Length::Fill)
+                                .into()
+                        }),
                         widget::text::body(modified_text.clone())
                             .width(Length::Fixed(modified_width))
                             .into(),
@@ -4281,17 +6866,21 @@ impl Tab {
 
                 let button = |row| {
                     let mouse_area = crate::mouse_area::MouseArea::new(
-                        widget::button::custom(row)
-                            .width(Length::Fill)
-                            .id(item.button_id.clone())
-                            .padding([0, space_xxs])
-                            .class(button_style(
-                                item.selected,
-                                item.highlighted,
-                                true,
-                                true,
-                                false,
-                            )),
+                        item.accessible(
+                            widget::button::custom(row)
+                                .width(Length::Fill)
+                                .id(item.button_id.clone())
+                                .padding([0, space_xxs])
+                                .class(button_style(
+                                    item.selected,
+                                    item.highlighted,
+                                    true,
+                                    true,
+                                    false,
+                                    self.config.dim_hidden && item.hidden,
+                                )),
+                            selection_count,
+                        ),
                     )
                     .on_press(move |_| Message::Click(Some(i)))
                     .on_double_click(move |_| Message::DoubleClick(Some(i)))
@@ -4310,11 +6899,21 @@ impl Tab {
                 };
 
                 let button_row = button(row.into());
+                let button_row: Element<_> = if item.is_drop_target() && item.location_opt.is_some()
+                {
+                    self.dnd_dest(item.location_opt.as_ref().unwrap(), button_row)
+                } else {
+                    button_row.into()
+                };
                 let button_row: Element<_> =
-                    if item.metadata.is_dir() && item.location_opt.is_some() {
-                        self.dnd_dest(item.location_opt.as_ref().unwrap(), button_row)
-                    } else {
-                        button_row.into()
+                    match item.hover_details(item.name_truncated(name_space, 1)) {
+                        Some(details) => widget::tooltip(
+                            button_row,
+                            widget::text::body(details),
+                            widget::tooltip::Position::FollowCursor,
+                        )
+                        .into(),
+                        None => button_row,
                     };
 
                 if item.selected || !drag_items.is_empty() {
@@ -4474,16 +7073,17 @@ impl Tab {
                     .collect::<Vec<PathBuf>>()
             })
             .unwrap_or_default();
-        let item_view =
-            DndSource::<Message, ClipboardCopy>::with_id(item_view, Id::new("tab-view-left"));
+        let dnd_id = match self.pane {
+            PaneType::RightPane => "tab-view-right",
+            _ => "tab-view-left",
+        };
+        let item_view = DndSource::<Message, DndDrag>::with_id(item_view, Id::new(dnd_id));
 
         let item_view = match drag_list {
             Some(drag_list) if self.selected_clicked => {
                 let drag_list = ArcElementWrapper::<Message>(Arc::new(Mutex::new(drag_list)));
                 item_view
-                    .drag_content(move || {
-                        ClipboardCopy::new(crate::clipboard::ClipboardKind::Copy, &files)
-                    })
+                    .drag_content(move || DndDrag::new(&files))
                     .drag_icon(move |v| {
                         let state: tree::State = Widget::<Message, _, _>::state(&drag_list);
                         (Element::from(drag_list.clone()).map(|_m| ()), state, v)
@@ -4500,6 +7100,13 @@ impl Tab {
             .on_resize(|_| Message::ScrollToFocus)
             .on_back_press(move |_point_opt| Message::GoPrevious)
             .on_forward_press(move |_point_opt| Message::GoNext)
+            .on_gesture(Message::Gesture)
+            .on_tap(Message::Tap)
+            .on_long_press(Message::LongPress)
+            .on_touch_scroll(Message::TouchScroll)
+            .on_touch_scroll_end(|| Message::TouchScrollEnd)
+            .drag_gestures(self.config.mouse_gestures)
+            .rocker_gestures(self.config.rocker_gestures)
             .on_scroll(respond_to_scroll_direction);
 
         if self.context_menu.is_some() {
@@ -4512,7 +7119,7 @@ impl Tab {
 
         if let Some(point) = self.context_menu {
             popover = popover
-                .popup(menu::context_menu1(self, key_binds))
+                .popup(menu::context_menu(self, key_binds))
                 .position(widget::popover::Position::Point(point));
         }
         let mut tab_column = widget::column::with_capacity(3);
@@ -4561,6 +7168,27 @@ impl Tab {
             }
             _ => {}
         }
+        if self.dnd_hovered.as_ref().map(|(l, _)| l) == Some(&tab_location)
+            && tab_location.path_opt().is_some()
+        {
+            let (icon_name, label) = match ClipboardKind::for_drop(self.dnd_modifiers) {
+                ClipboardKind::Copy => ("edit-copy-symbolic", fl!("drop-copy")),
+                ClipboardKind::Cut => ("edit-cut-symbolic", fl!("drop-move")),
+                ClipboardKind::Link => ("insert-link-symbolic", fl!("drop-link")),
+            };
+            tab_column = tab_column.push(
+                widget::layer_container(
+                    widget::row::with_children(vec![
+                        widget::icon::from_name(icon_name).size(16).icon().into(),
+                        widget::text::body(label).into(),
+                    ])
+                    .align_y(Alignment::Center)
+                    .spacing(space_xxs),
+                )
+                .padding([space_xxs, space_xs])
+                .layer(cosmic_theme::Layer::Primary),
+            );
+        }
         let mut tab_view = widget::container(tab_column)
             .height(Length::Fill)
             .width(Length::Fill);
@@ -4596,7 +7224,8 @@ impl Tab {
             }
         })
         .on_enter(move |_, _, _| Message::DndEnter(tab_location_2.clone()))
-        .on_leave(move || Message::DndLeave(tab_location_3.clone()));
+        .on_leave(move || Message::DndLeave(tab_location_3.clone()))
+        .on_drop(|x, y| Message::DndDrop(Point::new(x as f32, y as f32)));
 
         dnd_dest.into()
     }
@@ -4614,6 +7243,33 @@ impl Tab {
         let jobs = num_cpus::get();
         let mut subscriptions = Vec::with_capacity(jobs + 1);
 
+        if self.gallery && self.slideshow {
+            subscriptions.push(
+                cosmic::iced::time::every(Duration::from_secs(
+                    self.config.slideshow_interval_secs.max(1).into(),
+                ))
+                .map(|_| Message::GalleryNext),
+            );
+        }
+
+        if self.kinetic_scroll {
+            subscriptions.push(
+                cosmic::iced::time::every(Duration::from_millis(16))
+                    .map(|_| Message::KineticScroll),
+            );
+        }
+
+        #[cfg(feature = "gstreamer")]
+        if self
+            .media_opt
+            .as_ref()
+            .map_or(false, |media| media.is_playing())
+        {
+            subscriptions.push(
+                cosmic::iced::time::every(Duration::from_millis(50)).map(|_| Message::MediaTick),
+            );
+        }
+
         if let Some(items) = &self.items_opt {
             //TODO: move to function
             let visible_rect = {
@@ -4657,16 +7313,22 @@ impl Tab {
                     continue;
                 };
                 let mime = item.mime.clone();
+                let limits = self.config.preview_limits;
 
                 subscriptions.push(Subscription::run_with_id(
                     ("thumbnail", path.clone()),
-                    stream::channel(1, |mut output| async move {
+                    stream::channel(1, move |mut output| async move {
                         let message = {
                             let path = path.clone();
                             tokio::task::spawn_blocking(move || {
                                 let start = Instant::now();
-                                let thumbnail =
-                                    ItemThumbnail::new(&path, metadata, mime, THUMBNAIL_SIZE);
+                                let thumbnail = ItemThumbnail::new(
+                                    &path,
+                                    metadata,
+                                    mime,
+                                    THUMBNAIL_SIZE,
+                                    limits,
+                                );
                                 log::debug!("thumbnailed {:?} in {:?}", path, start.elapsed());
                                 Message::Thumbnail(path.clone(), thumbnail)
                             })
@@ -4690,72 +7352,281 @@ impl Tab {
                 }
             }
 
+            // Load directory size for the previewed items, for folders selected with Space and
+            // for hovered folders
+            let mut dir_size_items = Vec::new();
             if preview {
-                // Load directory size for selected items
-                if let Some(item) = items
+                dir_size_items.extend(items.iter().filter(|item| item.selected));
+                if dir_size_items.is_empty() {
+                    dir_size_items.extend(self.parent_item_opt.as_ref());
+                }
+            }
+            dir_size_items.extend(
+                items
                     .iter()
-                    .find(|item| item.selected)
-                    .or(self.parent_item_opt.as_ref())
-                {
-                    // Item must have a path
+                    .filter(|item| item.size_requested || item.size_hovered),
+            );
+            for item in dir_size_items {
+                // Item must have a path
+                if let Some(path) = item.path_opt().map(|path| path.to_path_buf()) {
+                    // Item must be calculating directory size
+                    if let DirSize::Calculating(controller) = &item.dir_size {
+                        let controller = controller.clone();
+                        let one_file_system = self.config.size_one_file_system;
+                        let skipped = self.size_skipped_mounts.clone();
+                        subscriptions.push(Subscription::run_with_id(
+                            ("dir_size", path.clone()),
+                            stream::channel(1, move |mut output| async move {
+                                let message = {
+                                    let path = path.clone();
+                                    tokio::task::spawn_blocking(move || {
+                                        let start = Instant::now();
+                                        match calculate_dir_size(
+                                            &path,
+                                            one_file_system,
+                                            &skipped,
+                                            controller,
+                                        ) {
+                                            Ok(size) => {
+                                                log::debug!(
+                                                    "calculated directory size of {:?} in {:?}",
+                                                    path,
+                                                    start.elapsed()
+                                                );
+                                                Message::DirectorySize(
+                                                    path.clone(),
+                                                    DirSize::Directory(size),
+                                                )
+                                            }
+                                            Err(err) => {
+                                                log::warn!(
+                                            "failed to calculate directory size of {:?}: {}",
+                                            path,
+                                            err
+                                        );
+                                                Message::DirectorySize(
+                                                    path.clone(),
+                                                    DirSize::Error(err),
+                                                )
+                                            }
+                                        }
+                                    })
+                                    .await
+                                    .unwrap()
+                                };
+
+                                match output.send(message).await {
+                                    Ok(()) => {}
+                                    Err(err) => {
+                                        log::warn!(
+                                            "failed to send directory size for {:?}: {}",
+                                            &path,
+                                            err
+                                        );
+                                    }
+                                }
+
+                                std::future::pending().await
+                            }),
+                        ));
+                    }
+                }
+            }
+            // Load camera, tag, or stream metadata for the previewed item
+            if preview {
+                if let Some(item) = items.iter().find(|item| {
+                    item.selected
+                        && item.media_metadata_opt.is_none()
+                        && metadata::supported(&item.mime)
+                }) {
                     if let Some(path) = item.path_opt().map(|path| path.to_path_buf()) {
-                        // Item must be calculating directory size
-                        if let DirSize::Calculating(controller) = &item.dir_size {
-                            let controller = controller.clone();
-                            subscriptions.push(Subscription::run_with_id(
-                                ("dir_size", path.clone()),
-                                stream::channel(1, |mut output| async move {
-                                    let message = {
-                                        let path = path.clone();
-                                        tokio::task::spawn_blocking(move || {
-                                            let start = Instant::now();
-                                            match calculate_dir_size(&path, controller) {
-                                                Ok(size) => {
-                                                    log::debug!(
-                                                        "calculated directory size of {:?} in {:?}",
-                                                        path,
-                                                        start.elapsed()
-                                                    );
-                                                    Message::DirectorySize(
-                                                        path.clone(),
-                                                        DirSize::Directory(size),
-                                                    )
-                                                }
-                                                Err(err) => {
-                                                    log::warn!(
-                                                "failed to calculate directory size of {:?}: {}",
-                                                path,
-                                                err
-                                            );
-                                                    Message::DirectorySize(
-                                                        path.clone(),
-                                                        DirSize::Error(err),
-                                                    )
-                                                }
+                        let mime = item.mime.clone();
+                        let limits = self.config.preview_limits;
+                        subscriptions.push(Subscription::run_with_id(
+                            ("media_metadata", path.clone()),
+                            stream::channel(1, move |mut output| async move {
+                                let message = {
+                                    let path = path.clone();
+                                    tokio::task::spawn_blocking(move || {
+                                        let start = Instant::now();
+                                        let media_metadata = metadata::load(&path, &mime, limits);
+                                        log::debug!(
+                                            "loaded metadata of {:?} in {:?}",
+                                            path,
+                                            start.elapsed()
+                                        );
+                                        Message::MediaMetadata(
+                                            path.clone(),
+                                            Arc::new(media_metadata),
+                                        )
+                                    })
+                                    .await
+                                    .unwrap()
+                                };
+
+                                match output.send(message).await {
+                                    Ok(()) => {}
+                                    Err(err) => {
+                                        log::warn!(
+                                            "failed to send metadata for {:?}: {}",
+                                            &path,
+                                            err
+                                        );
+                                    }
+                                }
+
+                                std::future::pending().await
+                            }),
+                        ));
+                    }
+                }
+            }
+
+            // Render the requested page of the previewed PDF
+            #[cfg(feature = "pdf")]
+            if preview {
+                if let Some(item) = items.iter().find(|item| {
+                    item.selected
+                        && pdf::is_pdf(&item.mime)
+                        && item.pdf_page_opt.as_ref().map(|pdf_page| pdf_page.index)
+                            != Some(item.pdf_page_index)
+                }) {
+                    let limits = self.config.preview_limits;
+                    let too_large = match &item.metadata {
+                        ItemMetadata::Path { metadata, .. } => metadata.len() > limits.max_size(),
+                        _ => false,
+                    };
+                    if let Some(path) = item
+                        .path_opt()
+                        .filter(|_| !too_large)
+                        .map(|path| path.to_path_buf())
+                    {
+                        let index = item.pdf_page_index;
+                        subscriptions.push(Subscription::run_with_id(
+                            ("pdf_page", path.clone(), index),
+                            stream::channel(1, move |mut output| async move {
+                                let message_opt = {
+                                    let path = path.clone();
+                                    tokio::task::spawn_blocking(move || {
+                                        let start = Instant::now();
+                                        match pdf::render_page(&path, index, 2 * THUMBNAIL_SIZE) {
+                                            Ok(pdf_page) => {
+                                                log::debug!(
+                                                    "rendered page {} of {:?} in {:?}",
+                                                    index,
+                                                    path,
+                                                    start.elapsed()
+                                                );
+                                                Some(Message::PdfPage(
+                                                    path.clone(),
+                                                    Arc::new(pdf_page),
+                                                ))
                                             }
-                                        })
-                                        .await
-                                        .unwrap()
-                                    };
+                                            Err(err) => {
+                                                log::warn!("{}", err);
+                                                None
+                                            }
+                                        }
+                                    })
+                                    .await
+                                    .unwrap()
+                                };
 
+                                if let Some(message) = message_opt {
                                     match output.send(message).await {
                                         Ok(()) => {}
                                         Err(err) => {
                                             log::warn!(
-                                                "failed to send directory size for {:?}: {}",
+                                                "failed to send PDF page for {:?}: {}",
                                                 &path,
                                                 err
                                             );
                                         }
                                     }
+                                }
 
-                                    std::future::pending().await
-                                }),
-                            ));
-                        }
+                                std::future::pending().await
+                            }),
+                        ));
                     }
                 }
             }
+
+            // Load highlighted text for the previewed item, or more of it for the gallery
+            let text_preview_item_opt = if self.gallery {
+                self.select_focus
+                    .and_then(|index| items.get(index))
+                    .map(|item| (item, highlight::VIEWER_MAX_BYTES))
+            } else if preview {
+                items
+                    .iter()
+                    .find(|item| item.selected)
+                    .map(|item| (item, highlight::PREVIEW_MAX_BYTES))
+            } else {
+                None
+            };
+            if let Some((item, max_bytes)) = text_preview_item_opt {
+                let needs_text_preview = !item.metadata.is_dir()
+                    && highlight::is_text(&item.mime)
+                    && match &item.text_preview_opt {
+                        Some(text_preview) => {
+                            text_preview.truncated && text_preview.max_bytes < max_bytes
+                        }
+                        None => true,
+                    };
+                if let Some(path) = item
+                    .path_opt()
+                    .filter(|_| needs_text_preview)
+                    .map(|path| path.to_path_buf())
+                {
+                    let dark = theme::active().cosmic().is_dark;
+                    subscriptions.push(Subscription::run_with_id(
+                        ("text_preview", path.clone(), max_bytes),
+                        stream::channel(1, move |mut output| async move {
+                            let message_opt = {
+                                let path = path.clone();
+                                tokio::task::spawn_blocking(move || {
+                                    let start = Instant::now();
+                                    match highlight::highlight_file(&path, max_bytes, dark) {
+                                        Ok(text_preview) => {
+                                            log::debug!(
+                                                "highlighted {:?} in {:?}",
+                                                path,
+                                                start.elapsed()
+                                            );
+                                            Some(Message::TextPreview(
+                                                path.clone(),
+                                                Arc::new(text_preview),
+                                            ))
+                                        }
+                                        Err(err) => {
+                                            log::warn!("failed to preview text: {}", err);
+                                            None
+                                        }
+                                    }
+                                })
+                                .await
+                                .unwrap()
+                            };
+
+                            if let Some(message) = message_opt {
+                                match output.send(message).await {
+                                    Ok(()) => {}
+                                    Err(err) => {
+                                        log::warn!(
+                                            "failed to send text preview for {:?}: {}",
+                                            &path,
+                                            err
+                                        );
+                                    }
+                                }
+                            }
+
+                            std::future::pending().await
+                        }),
+                    ));
+                }
+            }
         }
 
         // Load search items incrementally
@@ -5110,20 +7981,29 @@ fn text_editor_class(
 
 #[cfg(test)]
 mod tests {
-    use std::{fs, io, path::PathBuf};
+    use std::{collections::HashSet, fs, io, path::PathBuf};
 
-    use cosmic::{iced::mouse::ScrollDelta, iced_runtime::keyboard::Modifiers};
+    use cosmic::{
+        iced::{mouse::ScrollDelta, widget::scrollable::AbsoluteOffset},
+        iced_runtime::keyboard::Modifiers,
+    };
     use log::{debug, trace};
     use tempfile::TempDir;
     use test_log::test;
 
-    use super::{respond_to_scroll_direction, scan_path, Location, Message, Tab};
+    use super::{
+        calculate_dir_size, desktop_cell, expand_path, format_relative, format_size_units,
+        hide_matching, name_error, parse_skipped_mount_points, respond_to_scroll_direction,
+        scan_path, stem_len, EditLocation, Filter, Location, Message, Tab,
+    };
     use crate::{
         app::test_utils::{
             assert_eq_tab_path, empty_fs, eq_path_item, filter_dirs, read_dir_sorted, simple_fs,
             tab_click_new, NAME_LEN, NUM_DIRS, NUM_FILES, NUM_HIDDEN, NUM_NESTED,
         },
-        config::{IconSizes, TabConfig1},
+        config::{FirstDayOfWeek, IconSizes, SizeUnits, TabConfig},
+        mime_icon::Emblem,
+        operation::Controller,
     };
 
     // Boilerplate for tab tests. Checks if simulated clicks selected items.
@@ -5164,7 +8044,7 @@ mod tests {
     fn tab_history() -> io::Result<(TempDir, Tab, Vec<PathBuf>)> {
         let fs = simple_fs(NUM_FILES, NUM_NESTED, NUM_DIRS, NUM_NESTED, NAME_LEN)?;
         let path = fs.path();
-        let mut tab = Tab::new(Location::Path(path.into()), TabConfig1::default());
+        let mut tab = Tab::new(Location::Path(path.into()), TabConfig::default());
 
         // All directories (simple_fs only produces one nested layer)
         let dirs: Vec<PathBuf> = filter_dirs(path)?
@@ -5261,7 +8141,7 @@ mod tests {
             .next()
             .expect("temp directory should have at least one directory");
 
-        let mut tab = Tab::new(Location::Path(path.to_owned()), TabConfig1::default());
+        let mut tab = Tab::new(Location::Path(path.to_owned()), TabConfig::default());
         debug!(
             "Emitting Message::Location(Location::Path(\"{}\"))",
             next_dir.display()
@@ -5289,6 +8169,22 @@ mod tests {
         tab_selects_item(&[1], Modifiers::empty(), &[false, true])
     }
 
+    #[test]
+    fn tab_context_menu_focus_keeps_selection() -> io::Result<()> {
+        let (_fs, mut tab) = tab_click_new(NUM_FILES, NUM_NESTED, NUM_DIRS, NUM_NESTED, NAME_LEN)?;
+        tab.update(Message::Click(Some(1)), Modifiers::empty());
+        tab.update(Message::ContextMenuFocus, Modifiers::empty());
+
+        assert!(tab.context_menu.is_some());
+        let items = tab
+            .items_opt
+            .as_deref()
+            .expect("tab should be populated with items");
+        assert!(items[1].selected);
+
+        Ok(())
+    }
+
     #[test]
     fn tab_click_double_opens_folder() -> io::Result<()> {
         let (fs, mut tab) = tab_click_new(NUM_FILES, NUM_NESTED, NUM_DIRS, NUM_NESTED, NAME_LEN)?;
@@ -5354,6 +8250,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn tab_history_restores_scroll_position() -> io::Result<()> {
+        let (_fs, mut tab, _dirs) = tab_history()?;
+        let offset = AbsoluteOffset { x: 0.0, y: 120.0 };
+        tab.scroll_opt = Some(offset);
+
+        tab.update(Message::GoPrevious, Modifiers::empty());
+        tab.update(Message::ScrollRestore, Modifiers::empty());
+        assert_eq!(tab.scroll_opt, Some(AbsoluteOffset { x: 0.0, y: 0.0 }));
+
+        tab.update(Message::GoNext, Modifiers::empty());
+        tab.update(Message::ScrollRestore, Modifiers::empty());
+        assert_eq!(tab.scroll_opt, Some(offset));
+
+        Ok(())
+    }
+
     #[test]
     fn tab_scroll_up_with_ctrl_modifier_zooms() -> io::Result<()> {
         let message_maybe =
@@ -5393,7 +8306,7 @@ mod tests {
     fn tab_empty_history_does_nothing_on_prev_next() -> io::Result<()> {
         let fs = simple_fs(0, NUM_NESTED, NUM_DIRS, 0, NAME_LEN)?;
         let path = fs.path();
-        let mut tab = Tab::new(Location::Path(path.into()), TabConfig1::default());
+        let mut tab = Tab::new(Location::Path(path.into()), TabConfig::default());
 
         // Tab's location shouldn't change if GoPrev or GoNext is triggered
         debug!("Emitting Message::GoPrevious",);
@@ -5415,7 +8328,7 @@ mod tests {
             .next()
             .expect("should be at least one directory");
 
-        let mut tab = Tab::new(Location::Path(next_dir.clone()), TabConfig1::default());
+        let mut tab = Tab::new(Location::Path(next_dir.clone()), TabConfig::default());
         // This will eventually yield false once root is hit
         while next_dir.pop() {
             debug!("Emitting Message::LocationUp",);
@@ -5448,8 +8361,255 @@ mod tests {
         }
 
         debug!("Creating tab for directory of long file names");
-        Tab::new(Location::Path(path.into()), TabConfig1::default());
+        Tab::new(Location::Path(path.into()), TabConfig::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn skipped_mount_points_from_mountinfo() {
+        let mountinfo = "\
+22 1 0:21 / / rw,relatime shared:1 - btrfs /dev/sda2 rw,subvol=/root
+23 22 0:22 / /proc rw,nosuid,nodev,noexec,relatime shared:5 - proc proc rw
+24 22 0:23 / /home rw,relatime shared:2 - btrfs /dev/sda2 rw,subvol=/home
+25 24 0:24 / /home/user/My\\040Share rw,relatime shared:30 - cifs //server/share rw
+26 22 0:21 /srv /mnt/srv rw,relatime shared:1 - btrfs /dev/sda2 rw,subvol=/root
+";
+        let skipped = parse_skipped_mount_points(mountinfo);
+        assert_eq!(2, skipped.len());
+        assert!(skipped.contains(&PathBuf::from("/proc")));
+        assert!(skipped.contains(&PathBuf::from("/home/user/My Share")));
+    }
+
+    #[test]
+    fn location_input_expansion() {
+        let home_dir = dirs::home_dir().expect("home directory");
+        assert_eq!(home_dir, expand_path("~"));
+        assert_eq!(home_dir.join("Documents"), expand_path("~/Documents"));
+        assert_eq!(PathBuf::from("/tmp/~user"), expand_path("/tmp/~user"));
+
+        std::env::set_var("COMMANDER_TEST_DIR", "/srv/data");
+        assert_eq!(
+            PathBuf::from("/srv/data/a"),
+            expand_path("$COMMANDER_TEST_DIR/a")
+        );
+        assert_eq!(
+            PathBuf::from("/srv/data_b"),
+            expand_path("${COMMANDER_TEST_DIR}_b")
+        );
+        // Unknown variables are kept as typed
+        assert_eq!(
+            PathBuf::from("/a/$COMMANDER_TEST_UNSET/${b"),
+            expand_path("/a/$COMMANDER_TEST_UNSET/${b")
+        );
+
+        let submit = |input: &str| EditLocation::from(Location::Path(input.into())).submit();
+        assert_eq!(
+            Some(Location::Path("/tmp/a b".into())),
+            submit("file:///tmp/a%20b")
+        );
+        assert_eq!(Some(Location::Trash), submit("trash:///"));
+        assert_eq!(
+            Some(Location::Network(
+                "sftp://host/".to_string(),
+                "sftp://host/".to_string()
+            )),
+            submit("sftp://host/")
+        );
+    }
+
+    #[test]
+    fn edit_location_completes_inline() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+        fs::create_dir(path.join("Documents"))?;
+        fs::create_dir(path.join("Downloads"))?;
+
+        let mut edit_location = EditLocation::from(Location::Path(path.join("d")));
+        edit_location.completions = Some(vec![
+            ("Documents".to_string(), path.join("Documents")),
+            ("Downloads".to_string(), path.join("Downloads")),
+        ]);
+        assert!(edit_location.complete_inline());
+        assert_eq!(Location::Path(path.join("Do")), edit_location.location);
+
+        edit_location.completions = Some(vec![("Downloads".to_string(), path.join("Downloads"))]);
+        assert!(edit_location.complete_inline());
+        assert_eq!(
+            Some(format!("{}/", path.join("Downloads").display()).as_str()),
+            edit_location
+                .location
+                .path_opt()
+                .and_then(|path| path.to_str())
+        );
+        assert!(!edit_location.complete_inline());
+
+        Ok(())
+    }
+
+    #[test]
+    fn dir_size_counts_hard_links_once() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+
+        fs::write(path.join("file"), [0u8; 1024])?;
+        fs::hard_link(path.join("file"), path.join("link"))?;
+        fs::create_dir(path.join("dir"))?;
+        fs::write(path.join("dir").join("other"), [0u8; 512])?;
+
+        let size = calculate_dir_size(path, true, &HashSet::new(), Controller::default())
+            .expect("should calculate directory size");
+        assert_eq!(1024 + 512, size);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rename_selects_stem() {
+        assert_eq!(6, stem_len("report.txt"));
+        assert_eq!(7, stem_len("archive.tar.gz"));
+        assert_eq!(8, stem_len(".profile"));
+        assert_eq!(8, stem_len("Makefile"));
+        assert_eq!(4, stem_len("Über.md"));
+    }
+
+    #[test]
+    fn sizes_use_configured_units() {
+        assert_eq!("999 B", format_size_units(999, SizeUnits::Si));
+        assert_eq!("1.5 KB", format_size_units(1500, SizeUnits::Si));
+        assert_eq!("1.5 KiB", format_size_units(1536, SizeUnits::Iec));
+        assert_eq!("1000 B", format_size_units(1000, SizeUnits::Iec));
+        assert_eq!("2.0 GiB", format_size_units(2 << 30, SizeUnits::Iec));
+        assert_eq!("1500 B", format_size_units(1500, SizeUnits::Bytes));
+    }
+
+    #[test]
+    fn relative_dates_only_cover_last_week() {
+        let now = chrono::Local::now();
+        assert!(format_relative(now - chrono::TimeDelta::hours(3), now).is_some());
+        assert!(format_relative(now - chrono::TimeDelta::days(8), now).is_none());
+        assert!(format_relative(now + chrono::TimeDelta::minutes(5), now).is_none());
+
+        assert_eq!(0, FirstDayOfWeek::Monday.days_since(chrono::Weekday::Mon));
+        assert_eq!(1, FirstDayOfWeek::Sunday.days_since(chrono::Weekday::Mon));
+        assert_eq!(6, FirstDayOfWeek::Saturday.days_since(chrono::Weekday::Fri));
+    }
+
+    #[test]
+    fn name_errors() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+        fs::write(path.join("taken"), [])?;
+
+        assert!(name_error(path, None, "..", "ext4").is_some());
+        assert!(name_error(path, None, "a/b", "ext4").is_some());
+        assert!(name_error(path, None, "taken", "ext4").is_some());
+        assert!(name_error(path, Some(path.join("taken").as_path()), "taken", "ext4").is_none());
+        assert!(name_error(path, None, "a:b?", "ext4").is_none());
+        assert!(name_error(path, None, "a:b?", "vfat").is_some());
+        assert!(name_error(path, None, "trailing.", "exfat").is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scan_path_shows_symlink_emblems() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+        fs::write(path.join("target"), [])?;
+        std::os::unix::fs::symlink(path.join("target"), path.join("link"))?;
+        std::os::unix::fs::symlink(path.join("missing"), path.join("broken"))?;
+
+        let items = scan_path(&path.to_path_buf(), IconSizes::default());
+        let emblems = |name: &str| {
+            items
+                .iter()
+                .find(|item| item.name == name)
+                .map(|item| item.emblems.clone())
+        };
+        assert_eq!(Some(vec![Emblem::Symlink]), emblems("link"));
+        assert_eq!(Some(vec![Emblem::BrokenSymlink]), emblems("broken"));
+        assert_eq!(Some(Vec::new()), emblems("target"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn hover_details_show_truncated_names() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+        let long_name = "a file with a name that does not fit in the list.txt";
+        fs::write(path.join(long_name), [])?;
+        std::os::unix::fs::symlink(path.join(long_name), path.join("link"))?;
+
+        let items = scan_path(&path.to_path_buf(), IconSizes::default());
+        let item = |name: &str| items.iter().find(|item| item.name == name).unwrap();
+        assert!(item(long_name).name_truncated(200.0, 1));
+        assert!(!item(long_name).name_truncated(200.0, 3));
+        assert_eq!(None, item("link").hover_details(false));
+
+        let details = item(long_name).hover_details(true).unwrap();
+        assert!(details.starts_with(long_name));
+        let details = item("link").hover_details(true).unwrap();
+        assert!(details.lines().last().unwrap().contains(long_name));
+
+        Ok(())
+    }
+
+    #[test]
+    fn hide_patterns_hide_backup_files() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+        for name in ["notes.txt", "notes.txt~", "notes.bak", ".#notes.txt"] {
+            fs::write(path.join(name), [])?;
+        }
+
+        let mut items = scan_path(&path.to_path_buf(), IconSizes::default());
+        let patterns: Vec<_> = ["*~", "*.bak", ".#*"]
+            .iter()
+            .map(|pattern| glob::Pattern::new(pattern).unwrap())
+            .collect();
+        hide_matching(&mut items, &patterns);
+        for item in items {
+            assert_eq!(item.name != "notes.txt", item.hidden, "{}", item.name);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn filters_keep_folders_of_type_filters() -> io::Result<()> {
+        let fs = empty_fs()?;
+        let path = fs.path();
+        fs::create_dir(path.join("photos"))?;
+        for name in ["photo.png", "notes.txt", "backup.tar"] {
+            fs::write(path.join(name), [])?;
+        }
+
+        let items = scan_path(&path.to_path_buf(), IconSizes::default());
+        let matching = |filter: Filter| {
+            let mut names: Vec<_> = items
+                .iter()
+                .filter(|item| filter.matches(item))
+                .map(|item| item.name.as_str())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(vec!["photo.png", "photos"], matching(Filter::Images));
+        assert_eq!(vec!["notes.txt", "photos"], matching(Filter::Documents));
+        assert_eq!(vec!["backup.tar", "photos"], matching(Filter::Archives));
+        assert_eq!(4, matching(Filter::Today).len());
 
         Ok(())
     }
+
+    #[test]
+    fn desktop_cells_fill_columns_then_pages() {
+        let cells: Vec<_> = (0..7).map(|n| desktop_cell(n, 2, 3)).collect();
+        assert_eq!(
+            cells,
+            [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2), (2, 0)]
+        );
+    }
 }
@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    fs, io,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use crate::fl;
+
+/// Where the contents of a new file come from
+#[derive(Clone, Debug)]
+pub enum TemplateSource {
+    /// Built-in contents and whether the file is executable
+    Builtin(&'static str, bool),
+    /// A file in the templates folder
+    File(PathBuf),
+}
+
+#[derive(Clone, Debug)]
+pub struct Template {
+    /// Name shown in the menu
+    pub name: String,
+    /// File name of the new file, a number is added if it already exists
+    pub file_name: String,
+    pub source: TemplateSource,
+}
+
+impl Template {
+    /// Create a new file from the template in `dir`, returning its path
+    pub fn create(&self, dir: &Path) -> io::Result<PathBuf> {
+        let path = unique_path(dir, &self.file_name);
+        match &self.source {
+            TemplateSource::Builtin(contents, executable) => {
+                fs::write(&path, contents)?;
+                if *executable {
+                    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+                }
+            }
+            TemplateSource::File(from) => {
+                // Copying keeps the permissions of the template
+                fs::copy(from, &path)?;
+            }
+        }
+        Ok(path)
+    }
+}
+
+/// Built-in templates followed by the files in the XDG templates folder
+pub fn templates() -> Vec<Template> {
+    let mut templates = vec![
+        Template {
+            name: fl!("template-text"),
+            file_name: format!("{}.txt", fl!("template-document-name")),
+            source: TemplateSource::Builtin("", false),
+        },
+        Template {
+            name: fl!("template-markdown"),
+            file_name: format!("{}.md", fl!("template-document-name")),
+            source: TemplateSource::Builtin("# \n", false),
+        },
+        Template {
+            name: fl!("template-script"),
+            file_name: format!("{}.sh", fl!("template-script-name")),
+            source: TemplateSource::Builtin("#!/bin/sh\n\n", true),
+        },
+    ];
+    if let Some(dir) = dirs::template_dir() {
+        templates.extend(dir_templates(&dir));
+    }
+    templates
+}
+
+fn dir_templates(dir: &Path) -> Vec<Template> {
+    let mut templates = Vec::new();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            log::debug!("failed to read templates from {:?}: {}", dir, err);
+            return templates;
+        }
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if file_name.starts_with('.') || !path.is_file() {
+            continue;
+        }
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(file_name);
+        templates.push(Template {
+            name: name.to_string(),
+            file_name: file_name.to_string(),
+            source: TemplateSource::File(path.clone()),
+        });
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
+/// Path for `file_name` in `dir`, adding a number before the extension if it already exists
+fn unique_path(dir: &Path, file_name: &str) -> PathBuf {
+    let path = dir.join(file_name);
+    if !path.exists() {
+        return path;
+    }
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (file_name, None),
+    };
+    let mut i = 2;
+    loop {
+        let path = dir.join(match extension {
+            Some(extension) => format!("{} ({}).{}", stem, i, extension),
+            None => format!("{} ({})", stem, i),
+        });
+        if !path.exists() {
+            return path;
+        }
+        i += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{dir_templates, unique_path, Template, TemplateSource};
+
+    #[test]
+    fn create_unique() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = Template {
+            name: "Script".to_string(),
+            file_name: "New script.sh".to_string(),
+            source: TemplateSource::Builtin("#!/bin/sh\n", true),
+        };
+        let first = template.create(dir.path()).unwrap();
+        let second = template.create(dir.path()).unwrap();
+        assert_eq!(first, dir.path().join("New script.sh"));
+        assert_eq!(second, dir.path().join("New script (2).sh"));
+        assert_eq!(fs::read_to_string(&second).unwrap(), "#!/bin/sh\n");
+        assert_eq!(
+            unique_path(dir.path(), ".hidden"),
+            dir.path().join(".hidden")
+        );
+    }
+
+    #[test]
+    fn templates_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("Report.odt"), "").unwrap();
+        fs::write(dir.path().join(".hidden"), "").unwrap();
+        fs::create_dir(dir.path().join("Folder")).unwrap();
+        let templates = dir_templates(dir.path());
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].name, "Report");
+        assert_eq!(templates[0].file_name, "Report.odt");
+    }
+}
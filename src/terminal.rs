@@ -39,6 +39,7 @@ use indexmap::IndexSet;
 
 pub use alacritty_terminal::grid::Scroll as TerminalScroll;
 
+use crate::config::{CursorStyle, ProfileId};
 use crate::mouse_reporter::MouseReporter;
 use crate::pane_grid;
 
@@ -46,6 +47,16 @@ use crate::pane_grid;
 /// Duplicated from alacritty
 pub const MIN_CURSOR_CONTRAST: f64 = 1.5;
 
+impl From<CursorStyle> for CursorShape {
+    fn from(cursor_style: CursorStyle) -> Self {
+        match cursor_style {
+            CursorStyle::Block => Self::Block,
+            CursorStyle::Underline => Self::Underline,
+            CursorStyle::Beam => Self::Beam,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Size {
     pub width: u32,
@@ -169,7 +180,7 @@ pub struct Terminal {
     pub context_menu: Option<cosmic::iced::Point>,
     pub metadata_set: IndexSet<Metadata>,
     pub needs_update: bool,
-    //pub profile_id_opt: Option<ProfileId>,
+    pub profile_id_opt: Option<ProfileId>,
     pub tab_title_override: Option<String>,
     pub term: Arc<FairMutex<Term<EventProxy>>>,
     bold_font_weight: Weight,
@@ -196,7 +207,7 @@ impl Terminal {
         options: Options,
         //app_config: &AppConfig,
         colors: Colors,
-        //profile_id_opt: Option<ProfileId>,
+        profile_id_opt: Option<ProfileId>,
         tab_title_override: Option<String>,
     ) -> Result<Self, io::Error> {
         let font_stretch = Stretch::Normal;
@@ -268,7 +279,7 @@ impl Terminal {
             mouse_reporter: Default::default(),
             needs_update: true,
             notifier,
-            //profile_id_opt,
+            profile_id_opt,
             search_regex_opt: None,
             search_value: String::new(),
             size,
@@ -297,6 +308,27 @@ impl Terminal {
         &self.colors
     }
 
+    /// Change the colors, for example after the color scheme was changed in the settings
+    pub fn set_colors(&mut self, colors: Colors) {
+        if self.colors == colors {
+            return;
+        }
+        self.colors = colors;
+
+        // The default metadata is always the first one
+        let default_bg = convert_color(&self.colors, Color::Named(NamedColor::Background));
+        let default_fg = convert_color(&self.colors, Color::Named(NamedColor::Foreground));
+        self.metadata_set.clear();
+        let (default_metada_idx, _) = self
+            .metadata_set
+            .insert_full(Metadata::new(default_bg, default_fg));
+        self.default_attrs = self
+            .default_attrs
+            .color(default_fg)
+            .metadata(default_metada_idx);
+        self.needs_update = true;
+    }
+
     pub fn default_attrs(&self) -> &Attrs<'static> {
         &self.default_attrs
     }
@@ -4,14 +4,13 @@ use alacritty_terminal::{
 };
 use hex_color::HexColor;
 use palette::{encoding::Srgb, rgb::Rgb as PRgb, FromColor, Okhsl};
-use std::collections::HashMap;
+use std::{collections::HashMap, fs, io, path::Path};
 
 use crate::config::{
     ColorScheme, ColorSchemeAnsi, ColorSchemeKind, COSMIC_THEME_DARK, COSMIC_THEME_LIGHT,
 };
 
 // Fill missing dim/bright colors with derived values from normal ones.
-#[allow(dead_code)]
 struct ColorDerive {
     dim_saturation_adjustment: f32,
     dim_lightness_adjustment: f32,
@@ -19,7 +18,6 @@ struct ColorDerive {
     bright_lightness_adjustment: f32,
 }
 
-#[allow(dead_code)]
 impl ColorDerive {
     fn new() -> Self {
         Self {
@@ -350,3 +348,381 @@ pub fn terminal_themes() -> HashMap<(String, ColorSchemeKind), Colors> {
     themes
 }
 
+/// Formats of color scheme files that can be imported
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ColorSchemeFormat {
+    Alacritty,
+    ITerm2,
+    Xresources,
+}
+
+impl ColorSchemeFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+        if name.ends_with(".itermcolors") {
+            Some(Self::ITerm2)
+        } else if name.ends_with(".toml") {
+            Some(Self::Alacritty)
+        } else if name.contains("xresources") || name.ends_with(".xdefaults") {
+            Some(Self::Xresources)
+        } else {
+            None
+        }
+    }
+}
+
+/// Check if the file at `path` looks like a color scheme that can be imported
+pub fn is_color_scheme_file(path: &Path) -> bool {
+    ColorSchemeFormat::from_path(path).is_some()
+}
+
+/// Parse colors like `#RRGGBB`, `0xRRGGBB` or `rgb:RR/GG/BB`
+fn parse_hex(value: &str) -> Option<HexColor> {
+    let value = value.trim().trim_matches(['"', '\'']);
+    if let Some(rgb) = value.strip_prefix("rgb:") {
+        let mut components = rgb
+            .split('/')
+            .map(|component| u8::from_str_radix(component.get(..2)?, 16).ok());
+        return Some(HexColor::rgb(
+            components.next()??,
+            components.next()??,
+            components.next()??,
+        ));
+    }
+    let hex = value
+        .strip_prefix('#')
+        .or_else(|| value.strip_prefix("0x"))?;
+    HexColor::parse_rgb(&format!("#{}", hex)).ok()
+}
+
+/// Names of the ANSI colors, in the order of their indexes
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+fn ansi_color_mut(ansi: &mut ColorSchemeAnsi, index: usize) -> Option<&mut Option<HexColor>> {
+    match index {
+        0 => Some(&mut ansi.black),
+        1 => Some(&mut ansi.red),
+        2 => Some(&mut ansi.green),
+        3 => Some(&mut ansi.yellow),
+        4 => Some(&mut ansi.blue),
+        5 => Some(&mut ansi.magenta),
+        6 => Some(&mut ansi.cyan),
+        7 => Some(&mut ansi.white),
+        _ => None,
+    }
+}
+
+/// Set one of the 16 ANSI colors, where 8 to 15 are the bright ones
+fn set_ansi(color_scheme: &mut ColorScheme, index: usize, color: HexColor) {
+    let (ansi, index) = if index < 8 {
+        (&mut color_scheme.normal, index)
+    } else {
+        (&mut color_scheme.bright, index - 8)
+    };
+    if let Some(ansi_color) = ansi_color_mut(ansi, index) {
+        *ansi_color = Some(color);
+    }
+}
+
+fn parse_xresources(text: &str) -> ColorScheme {
+    let mut color_scheme = ColorScheme::default();
+    let mut defines = HashMap::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(define) = line.strip_prefix("#define") {
+            let mut words = define.split_whitespace();
+            if let (Some(name), Some(value)) = (words.next(), words.next()) {
+                defines.insert(name.to_string(), value.to_string());
+            }
+            continue;
+        }
+        if line.is_empty() || line.starts_with('!') || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        let Some(color) = parse_hex(defines.get(value).map_or(value, |x| x.as_str())) else {
+            continue;
+        };
+        // Only the last part of the resource is used, like in `*.color0` or `URxvt.foreground`
+        let key = key.trim().rsplit(['.', '*']).next().unwrap_or_default();
+        match key {
+            "foreground" => color_scheme.foreground = Some(color),
+            "background" => color_scheme.background = Some(color),
+            "cursorColor" => color_scheme.cursor = Some(color),
+            _ => {
+                if let Some(index) = key
+                    .strip_prefix("color")
+                    .and_then(|index| index.parse::<usize>().ok())
+                {
+                    set_ansi(&mut color_scheme, index, color);
+                }
+            }
+        }
+    }
+    color_scheme
+}
+
+fn parse_iterm2(data: &[u8]) -> Result<ColorScheme, String> {
+    let value = plist::Value::from_reader(io::Cursor::new(data)).map_err(|err| err.to_string())?;
+    let dict = value
+        .as_dictionary()
+        .ok_or_else(|| "property list is not a dictionary".to_string())?;
+    let color = |key: &str| -> Option<HexColor> {
+        let color = dict.get(key)?.as_dictionary()?;
+        let component = |name: &str| -> Option<u8> {
+            let value = color.get(&format!("{} Component", name))?.as_real()?;
+            Some((value.clamp(0.0, 1.0) * 255.0).round() as u8)
+        };
+        Some(HexColor::rgb(
+            component("Red")?,
+            component("Green")?,
+            component("Blue")?,
+        ))
+    };
+
+    let mut color_scheme = ColorScheme {
+        foreground: color("Foreground Color"),
+        background: color("Background Color"),
+        cursor: color("Cursor Color"),
+        bright_foreground: color("Bold Color"),
+        ..Default::default()
+    };
+    for index in 0..16 {
+        if let Some(color) = color(&format!("Ansi {} Color", index)) {
+            set_ansi(&mut color_scheme, index, color);
+        }
+    }
+    Ok(color_scheme)
+}
+
+fn parse_alacritty(text: &str) -> Result<ColorScheme, String> {
+    let table: toml::Table = text
+        .parse()
+        .map_err(|err: toml::de::Error| err.to_string())?;
+    let colors = table
+        .get("colors")
+        .and_then(|colors| colors.as_table())
+        .ok_or_else(|| "missing colors table".to_string())?;
+    let color = |section: &str, key: &str| -> Option<HexColor> {
+        parse_hex(colors.get(section)?.get(key)?.as_str()?)
+    };
+
+    let mut color_scheme = ColorScheme {
+        foreground: color("primary", "foreground"),
+        background: color("primary", "background"),
+        cursor: color("cursor", "cursor"),
+        bright_foreground: color("primary", "bright_foreground"),
+        dim_foreground: color("primary", "dim_foreground"),
+        ..Default::default()
+    };
+    for (index, name) in ANSI_NAMES.iter().enumerate() {
+        for (section, ansi) in [
+            ("normal", &mut color_scheme.normal),
+            ("bright", &mut color_scheme.bright),
+            ("dim", &mut color_scheme.dim),
+        ] {
+            if let Some(ansi_color) = ansi_color_mut(ansi, index) {
+                *ansi_color = color(section, name);
+            }
+        }
+    }
+    Ok(color_scheme)
+}
+
+/// Dark or light, depending on the lightness of the background
+pub fn color_scheme_kind(color_scheme: &ColorScheme) -> ColorSchemeKind {
+    let Some(background) = color_scheme.background else {
+        return ColorSchemeKind::Dark;
+    };
+    let okhsl = ColorDerive::rgb_to_okhsl(Rgb {
+        r: background.r,
+        g: background.g,
+        b: background.b,
+    });
+    if okhsl.lightness >= 0.5 {
+        ColorSchemeKind::Light
+    } else {
+        ColorSchemeKind::Dark
+    }
+}
+
+/// Import a color scheme from an alacritty, iTerm2 or Xresources file.
+///
+/// Missing bright and dim colors are derived from the normal ones.
+pub fn import(path: &Path) -> Result<ColorScheme, String> {
+    let format = ColorSchemeFormat::from_path(path)
+        .ok_or_else(|| format!("unknown color scheme format of {:?}", path))?;
+    let data = fs::read(path).map_err(|err| format!("failed to read {:?}: {}", path, err))?;
+    let color_scheme = match format {
+        ColorSchemeFormat::Alacritty => parse_alacritty(&String::from_utf8_lossy(&data)),
+        ColorSchemeFormat::ITerm2 => parse_iterm2(&data),
+        ColorSchemeFormat::Xresources => Ok(parse_xresources(&String::from_utf8_lossy(&data))),
+    }
+    .map_err(|err| format!("failed to parse {:?}: {}", path, err))?;
+
+    let mut colors = Colors::from(&color_scheme);
+    for named_color in [
+        NamedColor::Foreground,
+        NamedColor::Black,
+        NamedColor::Red,
+        NamedColor::Green,
+        NamedColor::Yellow,
+        NamedColor::Blue,
+        NamedColor::Magenta,
+        NamedColor::Cyan,
+        NamedColor::White,
+    ] {
+        if colors[named_color].is_none() {
+            return Err(format!("missing {:?} color in {:?}", named_color, path));
+        }
+    }
+
+    let color_derive = match color_scheme_kind(&color_scheme) {
+        ColorSchemeKind::Dark => ColorDerive::new(),
+        // Like the builtin light theme, dim only a little
+        ColorSchemeKind::Light => ColorDerive::new().with_dim_lightness_adjustment(-0.07),
+    };
+    color_derive.fill_missing_brights(&mut colors);
+    color_derive.fill_missing_dims(&mut colors);
+
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().trim_matches('.').to_string())
+        .unwrap_or_default();
+    Ok(ColorScheme::from((name.as_str(), &colors)))
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_color::HexColor;
+    use std::{fs, path::Path};
+
+    use super::{
+        color_scheme_kind, import, is_color_scheme_file, parse_alacritty, parse_iterm2,
+        parse_xresources,
+    };
+    use crate::config::ColorSchemeKind;
+
+    #[test]
+    fn xresources() {
+        let color_scheme = parse_xresources(
+            "! comment\n\
+             #define base03 #002b36\n\
+             *background: base03\n\
+             URxvt.foreground: #839496\n\
+             *.cursorColor: rgb:93/a1/a1\n\
+             *color1: #dc322f\n\
+             *.color9: #cb4b16\n",
+        );
+        assert_eq!(
+            color_scheme.background,
+            Some(HexColor::rgb(0x00, 0x2b, 0x36))
+        );
+        assert_eq!(
+            color_scheme.foreground,
+            Some(HexColor::rgb(0x83, 0x94, 0x96))
+        );
+        assert_eq!(color_scheme.cursor, Some(HexColor::rgb(0x93, 0xa1, 0xa1)));
+        assert_eq!(
+            color_scheme.normal.red,
+            Some(HexColor::rgb(0xdc, 0x32, 0x2f))
+        );
+        assert_eq!(
+            color_scheme.bright.red,
+            Some(HexColor::rgb(0xcb, 0x4b, 0x16))
+        );
+        assert_eq!(color_scheme_kind(&color_scheme), ColorSchemeKind::Dark);
+    }
+
+    #[test]
+    fn alacritty() {
+        let color_scheme = parse_alacritty(
+            "[colors.primary]\n\
+             background = '#fdf6e3'\n\
+             foreground = '0x586e75'\n\
+             [colors.cursor]\n\
+             cursor = 'CellForeground'\n\
+             [colors.normal]\n\
+             blue = '#268bd2'\n\
+             [colors.dim]\n\
+             blue = '#1b6497'\n",
+        )
+        .unwrap();
+        assert_eq!(
+            color_scheme.foreground,
+            Some(HexColor::rgb(0x58, 0x6e, 0x75))
+        );
+        assert_eq!(color_scheme.cursor, None);
+        assert_eq!(
+            color_scheme.normal.blue,
+            Some(HexColor::rgb(0x26, 0x8b, 0xd2))
+        );
+        assert_eq!(color_scheme.dim.blue, Some(HexColor::rgb(0x1b, 0x64, 0x97)));
+        assert_eq!(color_scheme.bright.blue, None);
+        assert_eq!(color_scheme_kind(&color_scheme), ColorSchemeKind::Light);
+
+        assert!(parse_alacritty("[window]\nopacity = 0.9\n").is_err());
+    }
+
+    #[test]
+    fn iterm2() {
+        let color_scheme = parse_iterm2(
+            br#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>Ansi 2 Color</key>
+	<dict>
+		<key>Blue Component</key>
+		<real>0.0</real>
+		<key>Green Component</key>
+		<real>1</real>
+		<key>Red Component</key>
+		<real>0.5</real>
+	</dict>
+	<key>Ansi 12 Color</key>
+	<dict>
+		<key>Blue Component</key>
+		<real>1</real>
+		<key>Green Component</key>
+		<real>0.0</real>
+		<key>Red Component</key>
+		<real>0.0</real>
+	</dict>
+</dict>
+</plist>
+"#,
+        )
+        .unwrap();
+        assert_eq!(color_scheme.normal.green, Some(HexColor::rgb(128, 255, 0)));
+        assert_eq!(color_scheme.bright.blue, Some(HexColor::rgb(0, 0, 255)));
+        assert_eq!(color_scheme.foreground, None);
+    }
+
+    #[test]
+    fn import_derives_colors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Test.Xresources");
+        let mut text = "*foreground: #cccccc\n".to_string();
+        for index in 0..8 {
+            text.push_str(&format!("*color{}: #40404{}\n", index, index));
+        }
+        fs::write(&path, &text).unwrap();
+        assert!(is_color_scheme_file(&path));
+        assert!(!is_color_scheme_file(Path::new("notes.txt")));
+
+        let color_scheme = import(&path).unwrap();
+        assert_eq!(color_scheme.name, "Test");
+        assert!(color_scheme.bright.white.is_some());
+        assert!(color_scheme.dim.black.is_some());
+
+        // Normal colors are required
+        fs::write(&path, "*foreground: #cccccc\n").unwrap();
+        assert!(import(&path).is_err());
+    }
+}
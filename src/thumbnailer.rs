@@ -3,7 +3,15 @@
 
 use mime_guess::Mime;
 use once_cell::sync::Lazy;
-use std::{collections::HashMap, fs, path::Path, process, sync::Mutex, time::Instant};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
 
 #[derive(Clone, Debug)]
 pub struct Thumbnailer {
@@ -155,3 +163,73 @@ pub fn thumbnailer(mime: &Mime) -> Vec<Thumbnailer> {
     let thumbnailer_cache = THUMBNAILER_CACHE.lock().unwrap();
     thumbnailer_cache.get(mime)
 }
+
+/// Wait for `child` to exit, killing it if it takes longer than `timeout`.
+///
+/// Returns `None` if the child was killed.
+pub fn wait_timeout(
+    child: &mut process::Child,
+    timeout: Duration,
+) -> io::Result<Option<process::ExitStatus>> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if start.elapsed() >= timeout {
+            if let Err(err) = child.kill() {
+                log::warn!("failed to kill process {}: {}", child.id(), err);
+            }
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+// Files that previews gave up on, with the modified time they were skipped at
+static PREVIEW_SKIPS: Lazy<Mutex<HashMap<PathBuf, (Option<SystemTime>, String)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Remember that previews of `path` should not be attempted again until it is modified
+pub fn record_skip(path: &Path, modified_opt: Option<SystemTime>, reason: String) {
+    log::warn!("skipping previews of {:?}: {}", path, reason);
+    let mut skips = PREVIEW_SKIPS.lock().unwrap();
+    skips.insert(path.to_path_buf(), (modified_opt, reason));
+}
+
+/// Reason previews of `path` were skipped, if it has not been modified since
+pub fn skip_reason(path: &Path, modified_opt: Option<SystemTime>) -> Option<String> {
+    let skips = PREVIEW_SKIPS.lock().unwrap();
+    skips
+        .get(path)
+        .filter(|(skip_modified_opt, _)| *skip_modified_opt == modified_opt)
+        .map(|(_, reason)| reason.clone())
+}
+
+/// Forget all skipped files, the reasons depend on the preview limits
+pub fn clear_skips() {
+    let mut skips = PREVIEW_SKIPS.lock().unwrap();
+    skips.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::time::SystemTime;
+
+    use super::{clear_skips, record_skip, skip_reason};
+
+    #[test]
+    fn skips_are_cleared() {
+        let path = Path::new("/nonexistent/skips_are_cleared.png");
+        let modified = Some(SystemTime::UNIX_EPOCH);
+        record_skip(path, modified, "too large".to_string());
+        assert_eq!(skip_reason(path, modified).as_deref(), Some("too large"));
+        // A modified file is tried again
+        assert_eq!(skip_reason(path, Some(SystemTime::now())), None);
+
+        clear_skips();
+        assert_eq!(skip_reason(path, modified), None);
+    }
+}
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use mime_guess::Mime;
+use std::{
+    fs,
+    io::Read,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+/// Programs that are run when opened, they are made executable first if needed
+const BINARY_TYPES: &[&str] = &["application/x-executable", "application/vnd.appimage"];
+
+/// Interpreters for scripts that are not executable, by MIME type
+const SCRIPT_INTERPRETERS: &[(&str, &str)] = &[
+    ("application/x-perl", "perl"),
+    ("application/x-ruby", "ruby"),
+    ("application/x-sh", "sh"),
+    ("application/x-shellscript", "sh"),
+    ("text/x-python", "python3"),
+    ("text/x-python3", "python3"),
+    ("text/x-shellscript", "sh"),
+];
+
+/// Expand the placeholders of a user tool command line into arguments.
+///
+/// `%f` is replaced by the selected files, `%d` by the directory of the active
+/// pane, `%D` by the directory of the other pane, and `%%` by a percent sign.
+/// A `%f` argument on its own is replaced by one argument for each file.
+pub fn expand(
+    command: &str,
+    files: &[PathBuf],
+    dir: &Path,
+    other_dir_opt: Option<&Path>,
+) -> Option<Vec<String>> {
+    let mut args = Vec::new();
+    for arg in shlex::split(command)? {
+        if arg == "%f" {
+            args.extend(files.iter().map(|file| file.to_string_lossy().into_owned()));
+            continue;
+        }
+
+        let mut expanded = String::with_capacity(arg.len());
+        let mut chars = arg.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                expanded.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('f') => {
+                    let files: Vec<_> = files.iter().map(|file| file.to_string_lossy()).collect();
+                    expanded.push_str(&files.join(" "));
+                }
+                Some('d') => expanded.push_str(&dir.to_string_lossy()),
+                Some('D') => expanded.push_str(&other_dir_opt?.to_string_lossy()),
+                Some('%') => expanded.push('%'),
+                Some(c) => {
+                    expanded.push('%');
+                    expanded.push(c);
+                }
+                None => expanded.push('%'),
+            }
+        }
+        args.push(expanded);
+    }
+    if args.is_empty() {
+        None
+    } else {
+        Some(args)
+    }
+}
+
+/// Command line that runs the file at `path` from its own directory, if it is
+/// executable or a script. Scripts without the executable bit are passed to their
+/// interpreter.
+pub fn run_command(path: &Path, mime: &Mime) -> Option<String> {
+    let metadata = fs::metadata(path).ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+    let name = path.file_name()?.to_str()?;
+    let file = shlex::try_quote(&format!("./{}", name)).ok()?.into_owned();
+    if metadata.permissions().mode() & 0o111 != 0 {
+        return Some(file);
+    }
+    SCRIPT_INTERPRETERS
+        .iter()
+        .find(|(mime_type, _)| mime.essence_str() == *mime_type)
+        .map(|(_, interpreter)| format!("{} {}", interpreter, file))
+}
+
+pub fn is_binary(mime: &Mime) -> bool {
+    BINARY_TYPES.contains(&mime.essence_str())
+}
+
+/// Whether the file at `path` can be run instead of opened: binaries, and scripts that are
+/// executable and start with a shebang
+pub fn is_runnable(path: &Path, mime: &Mime) -> bool {
+    if is_binary(mime) {
+        return true;
+    }
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() && metadata.permissions().mode() & 0o111 != 0 => {}
+        _ => return false,
+    }
+    let mut magic = [0; 2];
+    fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok()
+        && &magic == b"#!"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        os::unix::fs::PermissionsExt,
+        path::{Path, PathBuf},
+    };
+
+    use super::{expand, is_runnable, run_command};
+
+    #[test]
+    fn placeholders() {
+        let files = [PathBuf::from("/a/one file"), PathBuf::from("/a/two")];
+        assert_eq!(
+            expand(
+                "meld %f --label='%d vs %D' 100%%",
+                &files,
+                Path::new("/a"),
+                Some(Path::new("/b"))
+            ),
+            Some(vec![
+                "meld".to_string(),
+                "/a/one file".to_string(),
+                "/a/two".to_string(),
+                "--label=/a vs /b".to_string(),
+                "100%".to_string(),
+            ])
+        );
+        assert_eq!(
+            expand("echo files:%f", &files, Path::new("/a"), None),
+            Some(vec![
+                "echo".to_string(),
+                "files:/a/one file /a/two".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        // The other pane directory is required when used
+        assert_eq!(expand("cp %f %D", &[], Path::new("/a"), None), None);
+        assert_eq!(expand("", &[], Path::new("/a"), None), None);
+        assert_eq!(expand("echo 'unclosed", &[], Path::new("/a"), None), None);
+    }
+
+    #[test]
+    fn run_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let shell = "application/x-shellscript".parse().unwrap();
+        let text = "text/plain".parse().unwrap();
+
+        let script = dir.path().join("build it.sh");
+        fs::write(&script, "echo built\n").unwrap();
+        assert_eq!(
+            run_command(&script, &shell),
+            Some("sh './build it.sh'".to_string())
+        );
+
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(
+            run_command(&script, &shell),
+            Some("'./build it.sh'".to_string())
+        );
+
+        let notes = dir.path().join("notes.txt");
+        fs::write(&notes, "").unwrap();
+        assert_eq!(run_command(&notes, &text), None);
+        assert_eq!(run_command(dir.path(), &text), None);
+    }
+
+    #[test]
+    fn runnable_scripts_need_shebang_and_executable_bit() {
+        let dir = tempfile::tempdir().unwrap();
+        let shell = "application/x-shellscript".parse().unwrap();
+        let binary = "application/x-executable".parse().unwrap();
+
+        let script = dir.path().join("build.sh");
+        fs::write(&script, "#!/bin/sh\necho built\n").unwrap();
+        assert!(!is_runnable(&script, &shell));
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(is_runnable(&script, &shell));
+
+        let notes = dir.path().join("notes.txt");
+        fs::write(&notes, "echo built\n").unwrap();
+        fs::set_permissions(&notes, fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(!is_runnable(&notes, &shell));
+
+        // Binaries are made executable when they are run
+        assert!(is_runnable(&dir.path().join("program"), &binary));
+    }
+}
@@ -0,0 +1,263 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use mime_guess::{mime, Mime};
+use std::{fs, path::Path, process};
+
+use crate::fl;
+
+/// Subtypes of the images that are transformed without loss. JPEG files are transformed by
+/// jpegtran without decoding them, the others are encoded again without compression loss.
+const LOSSLESS_SUBTYPES: &[&str] = &["bmp", "jpeg", "png", "tiff"];
+
+/// TIFF compressions that lose details, JPEG and WebP
+const TIFF_LOSSY_COMPRESSIONS: &[u16] = &[6, 7, 34892, 50001];
+
+/// Rotation or mirroring of an image file
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ImageTransform {
+    RotateLeft,
+    RotateRight,
+    FlipHorizontal,
+    FlipVertical,
+}
+
+impl ImageTransform {
+    fn jpegtran_args(&self) -> &'static [&'static str] {
+        match self {
+            Self::RotateLeft => &["-rotate", "270"],
+            Self::RotateRight => &["-rotate", "90"],
+            Self::FlipHorizontal => &["-flip", "horizontal"],
+            Self::FlipVertical => &["-flip", "vertical"],
+        }
+    }
+
+    fn apply_image(&self, image: image::DynamicImage) -> image::DynamicImage {
+        match self {
+            Self::RotateLeft => image.rotate270(),
+            Self::RotateRight => image.rotate90(),
+            Self::FlipHorizontal => image.fliph(),
+            Self::FlipVertical => image.flipv(),
+        }
+    }
+}
+
+pub fn can_transform(mime: &Mime) -> bool {
+    mime.type_() == mime::IMAGE && LOSSLESS_SUBTYPES.contains(&mime.subtype().as_str())
+}
+
+fn is_jpeg(mime: &Mime) -> bool {
+    mime.type_() == mime::IMAGE && mime.subtype() == mime::JPEG
+}
+
+/// Whether the PNG file has an animation control chunk before its image data
+fn is_apng(data: &[u8]) -> bool {
+    let mut i = 8;
+    while let Some(header) = data.get(i..i + 8) {
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        match &header[4..] {
+            b"acTL" => return true,
+            b"IDAT" => return false,
+            _ => i += 12 + len,
+        }
+    }
+    false
+}
+
+/// Compression of the first page of the TIFF file and whether more pages follow it
+fn tiff_info(data: &[u8]) -> Option<(u16, bool)> {
+    let little_endian = match data.get(..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let u16_at = |i: usize| {
+        let bytes = [*data.get(i)?, *data.get(i + 1)?];
+        Some(if little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    };
+    let u32_at = |i: usize| {
+        let bytes = [
+            *data.get(i)?,
+            *data.get(i + 1)?,
+            *data.get(i + 2)?,
+            *data.get(i + 3)?,
+        ];
+        Some(if little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    };
+    // BigTIFF files are not read
+    if u16_at(2)? != 42 {
+        return None;
+    }
+    let ifd = u32_at(4)? as usize;
+    let count = u16_at(ifd)? as usize;
+    // Uncompressed if the tag is missing
+    let mut compression = 1;
+    for entry in (0..count).map(|i| ifd + 2 + i * 12) {
+        if u16_at(entry)? == 259 {
+            compression = u16_at(entry + 8)?;
+        }
+    }
+    let next = u32_at(ifd + 2 + count * 12)?;
+    Some((compression, next != 0))
+}
+
+/// Refuse images that would lose frames, pages or details when they are encoded again
+fn check_lossless(path: &Path, mime: &Mime) -> Result<(), String> {
+    let name = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().to_string(),
+    );
+    if !can_transform(mime) {
+        return Err(fl!("image-transform-lossy", name = name));
+    }
+    let data = || fs::read(path).map_err(|err| format!("failed to read {:?}: {}", path, err));
+    if mime.subtype() == mime::PNG && is_apng(&data()?) {
+        return Err(fl!("image-transform-animated", name = name));
+    }
+    if mime.subtype() == "tiff" {
+        let (compression, pages) =
+            tiff_info(&data()?).ok_or_else(|| format!("failed to read TIFF file {:?}", path))?;
+        if pages {
+            return Err(fl!("image-transform-animated", name = name));
+        }
+        if TIFF_LOSSY_COMPRESSIONS.contains(&compression) {
+            return Err(fl!("image-transform-lossy", name = name));
+        }
+    }
+    Ok(())
+}
+
+/// Transform the image at `path` in place, refusing images that would lose something.
+///
+/// JPEG files are transformed with jpegtran so that they are not encoded again, which fails
+/// if the size does not allow a perfect transform. PNG, BMP and TIFF files are decoded and
+/// saved again in the same format.
+pub fn apply(path: &Path, mime: &Mime, transform: ImageTransform) -> Result<(), String> {
+    check_lossless(path, mime)?;
+    let parent = path
+        .parent()
+        .ok_or_else(|| format!("{:?} has no parent directory", path))?;
+    let metadata = fs::metadata(path)
+        .map_err(|err| format!("failed to read metadata of {:?}: {}", path, err))?;
+    let suffix = path
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+    // The new file is written next to the original, so it can replace it atomically
+    let file = tempfile::Builder::new()
+        .prefix(".commander-")
+        .suffix(&suffix)
+        .tempfile_in(parent)
+        .map_err(|err| format!("failed to create temporary file in {:?}: {}", parent, err))?;
+
+    if is_jpeg(mime) {
+        let output = process::Command::new("jpegtran")
+            .arg("-copy")
+            .arg("all")
+            .arg("-perfect")
+            .args(transform.jpegtran_args())
+            .arg("-outfile")
+            .arg(file.path())
+            .arg(path)
+            .output()
+            .map_err(|err| format!("failed to run jpegtran for {:?}: {}", path, err))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // The edge blocks of images that are not a multiple of the block size would be lost
+            if stderr.contains("not perfect") {
+                let name = path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string();
+                return Err(fl!("image-transform-lossy", name = name));
+            }
+            return Err(format!(
+                "failed to run jpegtran for {:?}: {}: {}",
+                path,
+                output.status,
+                stderr.trim()
+            ));
+        }
+    } else {
+        let reader = image::ImageReader::open(path)
+            .and_then(|reader| reader.with_guessed_format())
+            .map_err(|err| format!("failed to read {:?}: {}", path, err))?;
+        let format = reader
+            .format()
+            .ok_or_else(|| format!("unknown image format of {:?}", path))?;
+        let image = reader
+            .decode()
+            .map_err(|err| format!("failed to decode {:?}: {}", path, err))?;
+        transform
+            .apply_image(image)
+            .save_with_format(file.path(), format)
+            .map_err(|err| format!("failed to save {:?}: {}", file.path(), err))?;
+    }
+
+    fs::set_permissions(file.path(), metadata.permissions())
+        .map_err(|err| format!("failed to set permissions of {:?}: {}", file.path(), err))?;
+    file.persist(path)
+        .map_err(|err| format!("failed to replace {:?}: {}", path, err))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, can_transform, is_apng, tiff_info, ImageTransform};
+
+    #[test]
+    fn rotate_png() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("image.png");
+        let mut image = image::RgbaImage::new(2, 1);
+        image.put_pixel(0, 0, image::Rgba([255, 0, 0, 255]));
+        image.put_pixel(1, 0, image::Rgba([0, 0, 255, 255]));
+        image.save(&path).unwrap();
+
+        let mime = mime_guess::from_path(&path).first_or_octet_stream();
+        apply(&path, &mime, ImageTransform::RotateRight).unwrap();
+        let rotated = image::open(&path).unwrap().into_rgba8();
+        assert_eq!(rotated.dimensions(), (1, 2));
+        assert_eq!(rotated.get_pixel(0, 0), &image::Rgba([255, 0, 0, 255]));
+        assert_eq!(rotated.get_pixel(0, 1), &image::Rgba([0, 0, 255, 255]));
+
+        apply(&path, &mime, ImageTransform::FlipVertical).unwrap();
+        let flipped = image::open(&path).unwrap().into_rgba8();
+        assert_eq!(flipped.get_pixel(0, 0), &image::Rgba([0, 0, 255, 255]));
+
+        // Only the transformed file is left behind
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn lossy_and_animated_images_are_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["image.gif", "image.webp"] {
+            let mime = mime_guess::from_path(name).first_or_octet_stream();
+            assert!(!can_transform(&mime));
+            assert!(apply(&dir.path().join(name), &mime, ImageTransform::RotateLeft).is_err());
+        }
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        for (chunk, len) in [(b"IHDR", 13), (b"acTL", 8), (b"IDAT", 0)] {
+            png.extend_from_slice(&(len as u32).to_be_bytes());
+            png.extend_from_slice(chunk);
+            png.extend(std::iter::repeat(0).take(len + 4));
+        }
+        assert!(is_apng(&png));
+
+        // One page compressed with JPEG, followed by another page
+        let mut tiff = b"II\x2a\x00\x08\x00\x00\x00\x01\x00".to_vec();
+        tiff.extend_from_slice(&[0x03, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00]);
+        tiff.extend_from_slice(&[0x07, 0x00, 0x00, 0x00, 0x1a, 0x00, 0x00, 0x00]);
+        assert_eq!(tiff_info(&tiff), Some((7, true)));
+    }
+}
@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Trash bins of volumes that the trash crate does not find, like the shares mounted by
+//! GVfs, which are all on one FUSE mount. Each share gets its own `.Trash-$uid` as described
+//! in the freedesktop.org trash specification, and the items in it work with the functions
+//! of the trash crate.
+
+use chrono::{Local, NaiveDateTime, TimeZone};
+use std::{
+    ffi::OsString,
+    fs::{self, DirBuilder, OpenOptions},
+    io::{self, Write},
+    os::unix::fs::{DirBuilderExt, MetadataExt},
+    path::{Path, PathBuf},
+};
+use trash::TrashItem;
+
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Directory of the GVfs FUSE mount, containing a directory for each share
+fn gvfs_dir() -> Option<PathBuf> {
+    dirs::runtime_dir().map(|dir| dir.join("gvfs"))
+}
+
+/// Top directory of the volume containing `path`, the last ancestor on the same device
+fn top_dir(path: &Path) -> io::Result<PathBuf> {
+    let gvfs_dir_opt = gvfs_dir();
+    let mut top_dir = path
+        .parent()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no parent"))?;
+    let dev = fs::metadata(top_dir)?.dev();
+    while let Some(parent) = top_dir.parent() {
+        if Some(parent) == gvfs_dir_opt.as_deref() || fs::metadata(parent)?.dev() != dev {
+            break;
+        }
+        top_dir = parent;
+    }
+    Ok(top_dir.to_path_buf())
+}
+
+/// `.Trash-$uid` in the top directory of a volume
+fn trash_dir(top_dir: &Path) -> PathBuf {
+    top_dir.join(format!(".Trash-{}", uzers::get_current_uid()))
+}
+
+/// Trash bins of the GVfs shares that exist
+pub fn trash_dirs() -> Vec<PathBuf> {
+    let mut trash_dirs = Vec::new();
+    let Some(gvfs_dir) = gvfs_dir() else {
+        return trash_dirs;
+    };
+    // The directory does not exist if GVfs is not running
+    if let Ok(entries) = fs::read_dir(gvfs_dir) {
+        for entry in entries.flatten() {
+            let trash_dir = trash_dir(&entry.path());
+            if trash_dir.is_dir() {
+                trash_dirs.push(trash_dir);
+            }
+        }
+    }
+    trash_dirs
+}
+
+/// Move the item to the trash bin of its volume, which is created if needed.
+/// Items of the home volume belong in the home trash of the trash crate and are refused.
+pub fn trash(path: &Path) -> io::Result<()> {
+    let top_dir = top_dir(path)?;
+    if let Some(home_dir) = dirs::home_dir() {
+        if fs::metadata(&top_dir)?.dev() == fs::metadata(home_dir)?.dev() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "item is on the home volume",
+            ));
+        }
+    }
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no name"))?;
+    let relative = path
+        .strip_prefix(&top_dir)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    // Paths are percent encoded like in file URLs
+    let encoded = url::Url::from_file_path(Path::new("/").join(relative))
+        .map_err(|()| io::Error::new(io::ErrorKind::InvalidInput, "path cannot be encoded"))?
+        .path()[1..]
+        .to_string();
+
+    let trash_dir = trash_dir(&top_dir);
+    for dir in [trash_dir.join("files"), trash_dir.join("info")] {
+        DirBuilder::new().recursive(true).mode(0o700).create(dir)?;
+    }
+
+    // Creating the info file claims the name in the trash bin
+    for i in 0.. {
+        let mut trash_name = name.to_os_string();
+        if i > 0 {
+            trash_name.push(format!(".{}", i));
+        }
+        let mut info_name = trash_name.clone();
+        info_name.push(".trashinfo");
+        let info_path = trash_dir.join("info").join(info_name);
+        let mut info_file = match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&info_path)
+        {
+            Ok(ok) => ok,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        };
+        let result = write!(
+            info_file,
+            "[Trash Info]\nPath={}\nDeletionDate={}\n",
+            encoded,
+            Local::now().format(DATE_FORMAT)
+        )
+        .and_then(|()| fs::rename(path, trash_dir.join("files").join(&trash_name)));
+        if result.is_err() {
+            let _ = fs::remove_file(&info_path);
+        }
+        return result;
+    }
+    unreachable!()
+}
+
+/// Item of the info file, with the original path relative to the top directory
+fn item_from_info(top_dir: &Path, info_path: &Path) -> Option<TrashItem> {
+    let info = fs::read_to_string(info_path).ok()?;
+    let mut original_path_opt = None;
+    let mut time_deleted = 0;
+    for line in info.lines() {
+        if let Some(value) = line.strip_prefix("Path=") {
+            let decoded = url::Url::parse(&format!("file:///{}", value.trim_start_matches('/')))
+                .ok()?
+                .to_file_path()
+                .ok()?;
+            original_path_opt = Some(if value.starts_with('/') {
+                decoded
+            } else {
+                top_dir.join(decoded.strip_prefix("/").ok()?)
+            });
+        } else if let Some(value) = line.strip_prefix("DeletionDate=") {
+            time_deleted = NaiveDateTime::parse_from_str(value, DATE_FORMAT)
+                .ok()
+                .and_then(|date_time| Local.from_local_datetime(&date_time).earliest())
+                .map_or(0, |date_time| date_time.timestamp());
+        }
+    }
+    let original_path = original_path_opt?;
+    Some(TrashItem {
+        id: OsString::from(info_path),
+        name: original_path.file_name()?.to_os_string(),
+        original_parent: original_path.parent()?.to_path_buf(),
+        time_deleted,
+    })
+}
+
+/// Items in the trash bins of the trash crate and of the GVfs shares
+// This config statement is from trash::os_limited
+#[cfg(any(
+    target_os = "windows",
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+))]
+pub fn list() -> Result<Vec<TrashItem>, trash::Error> {
+    let mut items = trash::os_limited::list()?;
+    for trash_dir in trash_dirs() {
+        let Some(top_dir) = trash_dir.parent() else {
+            continue;
+        };
+        let Ok(entries) = fs::read_dir(trash_dir.join("info")) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let info_path = entry.path();
+            if info_path.extension().and_then(|x| x.to_str()) != Some("trashinfo") {
+                continue;
+            }
+            match item_from_info(top_dir, &info_path) {
+                Some(item) => items.push(item),
+                None => log::warn!("failed to parse trash info {:?}", info_path),
+            }
+        }
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::item_from_info;
+
+    #[test]
+    fn item_from_relative_info() {
+        let dir = tempfile::tempdir().unwrap();
+        let info_path = dir.path().join("a b.txt.1.trashinfo");
+        fs::write(
+            &info_path,
+            "[Trash Info]\nPath=docs/a%20b.txt\nDeletionDate=2024-05-01T12:30:00\n",
+        )
+        .unwrap();
+
+        let item = item_from_info(Path::new("/media/share"), &info_path).unwrap();
+        assert_eq!(item.name, "a b.txt");
+        assert_eq!(item.original_parent, Path::new("/media/share/docs"));
+        assert_eq!(item.id, info_path.as_os_str());
+        assert!(item.time_deleted > 0);
+    }
+}